@@ -0,0 +1,105 @@
+//! Benchmarks for the git layer against generated repositories.
+//!
+//! Run with `cargo bench --bench git_layer`. Repositories are generated once
+//! per group, so only the measured calls are timed.
+
+#[allow(dead_code, unused_imports)]
+#[path = "../src/git/mod.rs"]
+mod git;
+mod support;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use git::{CommitGraphData, DiffDisplayOptions, FileDiff, FileStatus, GraphFilter};
+use git2::Sort;
+use std::hint::black_box;
+use support::{RepoSpec, SyntheticRepo};
+
+fn file_status(c: &mut Criterion) {
+    let mut group = c.benchmark_group("FileStatus::get_all");
+    group.sample_size(20);
+
+    for files in [1_000, 10_000] {
+        let synthetic = SyntheticRepo::generate(RepoSpec {
+            files,
+            commits: 10,
+            modified: files / 20,
+            untracked: files / 20,
+            ..Default::default()
+        });
+        group.bench_with_input(BenchmarkId::from_parameter(files), &synthetic, |b, s| {
+            b.iter(|| FileStatus::get_all(black_box(&s.repo)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn commit_graph(c: &mut Criterion) {
+    let synthetic = SyntheticRepo::generate(RepoSpec {
+        files: 200,
+        commits: 5_000,
+        modified: 0,
+        untracked: 0,
+        ..Default::default()
+    });
+
+    let mut group = c.benchmark_group("CommitGraphData::build");
+    group.sample_size(20);
+    let filter = GraphFilter::default();
+    // First page, and a page deep in history as "load more" requests it
+    for offset in [0, 2_000] {
+        group.bench_with_input(BenchmarkId::new("offset", offset), &offset, |b, &offset| {
+            b.iter(|| {
+                CommitGraphData::build(black_box(&synthetic.repo), &filter, 100, offset).unwrap()
+            });
+        });
+    }
+    group.finish();
+}
+
+fn diffs(c: &mut Criterion) {
+    let synthetic = SyntheticRepo::generate(RepoSpec {
+        files: 500,
+        lines_per_file: 5_000,
+        commits: 20,
+        modified: 1,
+        untracked: 0,
+        ..Default::default()
+    });
+    let head = synthetic
+        .repo
+        .head()
+        .unwrap()
+        .peel_to_commit()
+        .unwrap()
+        .id()
+        .to_string();
+    let initial = {
+        let mut revwalk = synthetic.repo.revwalk().unwrap();
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE).unwrap();
+        revwalk.push_head().unwrap();
+        revwalk.next().unwrap().unwrap().to_string()
+    };
+
+    // No size limit, so every file is diffed in full
+    let (repo, max_size, options) = (&synthetic.repo, u64::MAX, DiffDisplayOptions::default());
+    let path = synthetic.modified_file();
+
+    let mut group = c.benchmark_group("diff");
+    group.sample_size(20);
+    group.bench_function("get_file_diff (5000 lines)", |b| {
+        b.iter(|| FileDiff::get_file_diff(repo, black_box(path), max_size, options).unwrap());
+    });
+    group.bench_function("get_unstaged_diff (5000 lines)", |b| {
+        b.iter(|| FileDiff::get_unstaged_diff(repo, black_box(path), max_size, options).unwrap());
+    });
+    group.bench_function("get_commit_diff (HEAD)", |b| {
+        b.iter(|| FileDiff::get_commit_diff(repo, black_box(&head), max_size, options).unwrap());
+    });
+    group.bench_function("get_commit_diff (initial, 500 files)", |b| {
+        b.iter(|| FileDiff::get_commit_diff(repo, black_box(&initial), max_size, options).unwrap());
+    });
+    group.finish();
+}
+
+criterion_group!(benches, file_status, commit_graph, diffs);
+criterion_main!(benches);
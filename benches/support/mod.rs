@@ -0,0 +1,170 @@
+//! Synthetic repositories for benchmarking the git layer
+
+use git2::{Oid, Repository, Signature, Time};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Shape of a generated repository
+#[derive(Clone, Copy, Debug)]
+pub struct RepoSpec {
+    /// Tracked files in the initial commit
+    pub files: usize,
+    /// Files per directory
+    pub files_per_dir: usize,
+    /// Lines per tracked file
+    pub lines_per_file: usize,
+    /// Commits after the initial one
+    pub commits: usize,
+    /// A feature branch is forked and merged back every this many commits
+    pub merge_every: usize,
+    /// Tracked files left modified in the working tree
+    pub modified: usize,
+    /// Untracked files in the working tree
+    pub untracked: usize,
+}
+
+impl Default for RepoSpec {
+    fn default() -> Self {
+        Self {
+            files: 1_000,
+            files_per_dir: 50,
+            lines_per_file: 40,
+            commits: 200,
+            merge_every: 10,
+            modified: 50,
+            untracked: 50,
+        }
+    }
+}
+
+/// A generated repository, deleted when dropped
+pub struct SyntheticRepo {
+    _dir: TempDir,
+    pub repo: Repository,
+    pub files: Vec<String>,
+}
+
+impl SyntheticRepo {
+    pub fn generate(spec: RepoSpec) -> Self {
+        let dir = TempDir::new().expect("create temp dir");
+        let repo = Repository::init(dir.path()).expect("init repository");
+        let workdir = dir.path().to_path_buf();
+
+        let files: Vec<String> = (0..spec.files)
+            .map(|i| format!("src/dir{:04}/file{:05}.rs", i / spec.files_per_dir.max(1), i))
+            .collect();
+        for (i, path) in files.iter().enumerate() {
+            write_file(&workdir, path, &file_content(i, 0, spec.lines_per_file));
+        }
+
+        let generator = Self {
+            _dir: dir,
+            repo,
+            files,
+        };
+        let all_files = generator.files.clone();
+        let mut head = generator.commit(&all_files, &[], "Initial commit", 0, true);
+
+        // History on main, with a side commit forked and merged back
+        // periodically so the graph has more than one lane
+        let mut feature: Option<Oid> = None;
+        for n in 1..=spec.commits {
+            let tick = n as i64;
+            let path = generator.files[(n * 7919) % generator.files.len()].clone();
+            write_file(&workdir, &path, &file_content(n, n, spec.lines_per_file));
+            let paths = [path.clone()];
+
+            let phase = match spec.merge_every {
+                0 => 1,
+                every => n % every,
+            };
+            if phase == spec.merge_every / 2 && feature.is_none() {
+                let message = format!("Feature change {} in {}", n, path);
+                feature = Some(generator.commit(&paths, &[head], &message, tick, false));
+            } else if let (0, Some(side)) = (phase, feature) {
+                let message = format!("Merge feature {}", n);
+                head = generator.commit(&paths, &[head, side], &message, tick, true);
+                feature = None;
+            } else {
+                let message = format!("Change {} in {}", n, path);
+                head = generator.commit(&paths, &[head], &message, tick, true);
+            }
+        }
+
+        for i in 0..spec.modified.min(generator.files.len()) {
+            let path = generator.files[(i * 104_729) % generator.files.len()].clone();
+            write_file(&workdir, &path, &file_content(i, spec.commits + 1, spec.lines_per_file));
+        }
+        for i in 0..spec.untracked {
+            write_file(&workdir, &format!("untracked/new{:05}.txt", i), "untracked\n");
+        }
+
+        generator
+    }
+
+    /// Stage `paths` and commit the index with the given parents, moving
+    /// HEAD only when `update_head` is set
+    fn commit(
+        &self,
+        paths: &[String],
+        parents: &[Oid],
+        message: &str,
+        tick: i64,
+        update_head: bool,
+    ) -> Oid {
+        let mut index = self.repo.index().expect("open index");
+        for path in paths {
+            index.add_path(Path::new(path)).expect("stage file");
+        }
+        index.write().expect("write index");
+        let tree_id = index.write_tree().expect("write tree");
+        let tree = self.repo.find_tree(tree_id).expect("find tree");
+
+        // Spread commits a minute apart so time sorting is deterministic
+        let time = Time::new(1_600_000_000 + tick * 60, 0);
+        let sig = Signature::new("Bench", "bench@example.com", &time).expect("signature");
+        let parents: Vec<_> = parents
+            .iter()
+            .map(|oid| self.repo.find_commit(*oid).expect("find parent"))
+            .collect();
+        let parent_refs: Vec<_> = parents.iter().collect();
+
+        self.repo
+            .commit(
+                update_head.then_some("HEAD"),
+                &sig,
+                &sig,
+                message,
+                &tree,
+                &parent_refs,
+            )
+            .expect("commit")
+    }
+
+    /// Working tree path of a tracked file with uncommitted changes
+    pub fn modified_file(&self) -> &str {
+        &self.files[0]
+    }
+}
+
+fn write_file(workdir: &Path, path: &str, content: &str) {
+    let full = workdir.join(path);
+    if let Some(parent) = full.parent() {
+        fs::create_dir_all(parent).expect("create directory");
+    }
+    fs::write(full, content).expect("write file");
+}
+
+/// Rust-looking source whose lines differ between revisions
+fn file_content(seed: usize, revision: usize, lines: usize) -> String {
+    (0..lines)
+        .map(|line| {
+            if (line + revision).is_multiple_of(5) {
+                format!("    let value_{} = compute({}, {});\n", line, seed, revision)
+            } else {
+                format!("    let value_{} = compute({});\n", line, seed)
+            }
+        })
+        .collect()
+}
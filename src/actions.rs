@@ -1,4 +1,5 @@
 use gpui::*;
+use std::path::PathBuf;
 
 // Git operations
 actions!(
@@ -9,9 +10,14 @@ actions!(
         DiscardAll,
         CreateCommit,
         AmendCommit,
+        UndoCommit,
+        RedoCommit,
         Push,
+        ForcePush,
         Pull,
         Fetch,
+        FetchFullHistory,
+        PullLfsObjects,
         Refresh,
     ]
 );
@@ -21,7 +27,9 @@ actions!(
     awabancha,
     [
         OpenRepository,
+        CloneRepository,
         CloseRepository,
+        ClearRecentProjects,
         OpenSettings,
         CloseModal,
         Cancel,
@@ -29,9 +37,66 @@ actions!(
         CloseDiff,
         ShowConflictDialog,
         CloseConflictDialog,
+        ShowReviewNotes,
+        ShowTodos,
+        ShowSearch,
+        ToggleLogConsole,
+        TogglePerfOverlay,
+        ToggleProjectTree,
+        ReviewChanges,
     ]
 );
 
+// Application
+actions!(awabancha, [Quit,]);
+
+/// Open a project from the Open Recent or dock menu
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = awabancha, no_json)]
+pub struct OpenRecentProject {
+    pub path: PathBuf,
+}
+
+/// Filter the history to the commits of an author, by name or email
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = awabancha, no_json)]
+pub struct FilterByAuthor {
+    pub author: String,
+}
+
+/// Show who last changed each line of a working tree file
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = awabancha, no_json)]
+pub struct ShowBlame {
+    pub path: String,
+}
+
+/// Open the project tree with the history of a file
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = awabancha, no_json)]
+pub struct ShowFileHistory {
+    pub path: String,
+}
+
+/// Open the comparison of two commits or branches
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = awabancha, no_json)]
+pub struct CompareRevisions {
+    /// Commit SHA or branch name the comparison starts from
+    pub base: String,
+    pub head: String,
+}
+
+/// Save the tree of a commit or branch as a zip or tar.gz archive
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = awabancha, no_json)]
+pub struct ExportSnapshot {
+    /// Commit SHA or branch name
+    pub revision: String,
+    /// Short name for the suggested file name, e.g. the branch or short SHA
+    pub name: String,
+}
+
 // Branch operations
 actions!(
     awabancha,
@@ -40,15 +105,44 @@ actions!(
         DeleteBranch,
         CheckoutBranch,
         MergeBranch,
+        CompareBranches,
+        ShowWorktrees,
     ]
 );
 
+// History operations
+actions!(awabancha, [ShowReflog, UndoOperation, ShowTrash,]);
+
 // Tag operations
-actions!(awabancha, [CreateTag, DeleteTag,]);
+actions!(awabancha, [CreateTag, DeleteTag, ShowTags,]);
 
 // Stash operations
 actions!(awabancha, [StashSave, StashPop, StashApply, StashDrop,]);
 
+// Review mode actions
+actions!(
+    review_mode,
+    [
+        ReviewStage,
+        ReviewSkip,
+        ReviewDiscard,
+        ReviewOpenEditor,
+        ReviewPrevious,
+    ]
+);
+
+// Diff viewer actions
+actions!(
+    diff_viewer,
+    [
+        NextHunk,
+        PreviousHunk,
+        NextDiffFile,
+        PreviousDiffFile,
+        ToggleDiffFileList,
+    ]
+);
+
 // Text input actions
 actions!(
     text_input,
@@ -71,18 +165,51 @@ actions!(
 );
 
 pub fn register_actions(cx: &mut App) {
+    cx.on_action(|_: &Quit, cx| cx.quit());
+
     // Register keybindings
     cx.bind_keys([
         // Git operations
         KeyBinding::new("cmd-s", StageAll, None),
         KeyBinding::new("cmd-enter", CreateCommit, None),
+        KeyBinding::new("cmd-alt-z", UndoCommit, None),
+        KeyBinding::new("cmd-alt-shift-z", RedoCommit, None),
         KeyBinding::new("cmd-shift-p", Push, None),
+        KeyBinding::new("cmd-alt-shift-p", ForcePush, None),
         KeyBinding::new("cmd-shift-l", Pull, None),
         KeyBinding::new("cmd-r", Refresh, None),
+        KeyBinding::new("cmd-shift-b", CompareBranches, None),
+        KeyBinding::new("cmd-shift-o", ShowWorktrees, None),
+        KeyBinding::new("cmd-shift-s", StashSave, None),
+        KeyBinding::new("cmd-shift-t", ShowTags, None),
+        KeyBinding::new("cmd-shift-r", ShowReflog, None),
+        KeyBinding::new("cmd-alt-u", UndoOperation, None),
+        KeyBinding::new("cmd-shift-d", ShowTrash, None),
         // Navigation
+        KeyBinding::new("cmd-q", Quit, None),
         KeyBinding::new("cmd-o", OpenRepository, None),
         KeyBinding::new("cmd-,", OpenSettings, None),
+        KeyBinding::new("cmd-shift-n", ShowReviewNotes, None),
+        KeyBinding::new("cmd-shift-f", ShowSearch, None),
+        KeyBinding::new("cmd-alt-l", ToggleLogConsole, None),
+        KeyBinding::new("cmd-alt-p", TogglePerfOverlay, None),
+        KeyBinding::new("cmd-shift-e", ToggleProjectTree, None),
+        KeyBinding::new("cmd-alt-r", ReviewChanges, None),
         KeyBinding::new("escape", Cancel, None),
+        // Review mode
+        KeyBinding::new("s", ReviewStage, Some("ReviewMode")),
+        KeyBinding::new("n", ReviewSkip, Some("ReviewMode")),
+        KeyBinding::new("right", ReviewSkip, Some("ReviewMode")),
+        KeyBinding::new("d", ReviewDiscard, Some("ReviewMode")),
+        KeyBinding::new("e", ReviewOpenEditor, Some("ReviewMode")),
+        KeyBinding::new("p", ReviewPrevious, Some("ReviewMode")),
+        KeyBinding::new("left", ReviewPrevious, Some("ReviewMode")),
+        // Diff viewer
+        KeyBinding::new("n", NextHunk, Some("DiffViewer")),
+        KeyBinding::new("p", PreviousHunk, Some("DiffViewer")),
+        KeyBinding::new("shift-n", NextDiffFile, Some("DiffViewer")),
+        KeyBinding::new("shift-p", PreviousDiffFile, Some("DiffViewer")),
+        KeyBinding::new("f", ToggleDiffFileList, Some("DiffViewer")),
         // Text input
         KeyBinding::new("backspace", Backspace, Some("TextInput")),
         KeyBinding::new("delete", Delete, Some("TextInput")),
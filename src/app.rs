@@ -1,11 +1,32 @@
 use crate::actions::*;
 use crate::components::ToastContainer;
-use crate::state::{GitState, RecentProjects, RepositoryWatcher, SettingsState, ToastState};
-use crate::views::{ConflictDialog, DiffViewer, MainLayout, SettingsView, WelcomeView};
+use crate::git::{self, DiscardScope};
+use crate::i18n;
+use crate::menus;
+use crate::state::{
+    AutoFetchInterval, AvatarState, ControlCommand, ControlRequest, ControlServer, DraftState,
+    GitCredentials, GitState, LogEntry, LogState, RecentProjects, RemoteOperation,
+    RepositoryWatcher, ReviewNotes, SessionState, SettingsState, SigningPolicy, ToastAction,
+    ToastState, ToastType, TodoState,
+};
+use crate::theme::{self, ActiveTheme, Appearance, ThemeRegistry, ThemeWatcher};
+use crate::views::{
+    AuthDialog, AuthDialogEvent, BlameDismissed, BlameView, BranchCompareDismissed,
+    BranchCompareView, CloneDialog, CloneDialogEvent, CompareDismissed, CompareView, ConflictDialog,
+    DiffViewer, DiscardDialog, DraftRecoveryDialog, DraftRecoveryEvent, ExternalEditorView,
+    ForcePushDialog, ForcePushDialogEvent, GitAuthView, GitIdentityView, HostingTokenView,
+    LogConsole, LogConsoleDismissed, MainLayout, MergeDialog, OperationPreviewDialog, PerfOverlay,
+    PruneReportDialog, PruneReportDismissed, ReflogEvent, ReflogView, RepoSettingsView, ReviewMode,
+    ReviewModeDismissed, ReviewNotesDismissed, ReviewNotesView, SearchDismissed, SearchView,
+    SettingsView, SigningListsView, StashBranchDialog, StashDialog, StashDialogDismissed,
+    TagsPanel, TagsPanelEvent, TodosDismissed, TodosView, TrashDismissed, TrashView, WelcomeView,
+    WorktreesEvent, WorktreesView,
+};
+use futures::channel::mpsc::UnboundedReceiver;
+use futures::StreamExt;
 use gpui::prelude::*;
 use gpui::*;
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::path::{Path, PathBuf};
 
 pub struct Assets;
 
@@ -28,20 +49,96 @@ pub struct Awabancha {
     pub recent_projects: Entity<RecentProjects>,
     /// Toast notifications
     pub toast_state: Entity<ToastState>,
+    /// UI state persisted across restarts
+    pub session: Entity<SessionState>,
+    /// Local review notes for all repositories
+    pub review_notes: Entity<ReviewNotes>,
+    /// TODO/FIXME scan results, kept between openings of the panel
+    pub todo_state: Entity<TodoState>,
+    /// Captured log records for the logging console
+    pub log_state: Entity<LogState>,
+    /// Autosaved commit message and conflict choices
+    pub drafts: Entity<DraftState>,
+    /// Author avatars shown in the history
+    pub avatars: Entity<AvatarState>,
     /// Current view mode
     pub view_mode: ViewMode,
     /// Show settings modal
     pub show_settings: bool,
+    /// Repository tab of the settings modal, created when it opens
+    repo_settings: Option<Entity<RepoSettingsView>>,
+    /// Git config identity section of the settings modal
+    git_identity: Option<Entity<GitIdentityView>>,
+    /// Hosting API token section of the settings modal
+    hosting_token: Option<Entity<HostingTokenView>>,
+    git_auth: Option<Entity<GitAuthView>>,
+    /// External editor section of the settings modal
+    external_editor: Option<Entity<ExternalEditorView>>,
+    signing_lists: Option<Entity<SigningListsView>>,
     /// Show diff viewer modal
     pub show_diff: bool,
+    /// Focus for the diff viewer's hunk and file keys
+    diff_focus: FocusHandle,
+    /// Scroll position of the diff viewer, for jumping to hunks
+    diff_scroll: ScrollHandle,
+    /// Show the file list beside diffs of several files
+    diff_file_list: bool,
     /// Show conflict dialog modal
     pub show_conflict_dialog: bool,
     /// Conflict dialog entity
     conflict_dialog: Option<Entity<ConflictDialog>>,
+    /// Re-authentication dialog, shown when a remote rejects credentials
+    auth_dialog: Option<Entity<AuthDialog>>,
+    /// Clone dialog, opened from the File menu or the welcome screen
+    clone_dialog: Option<Entity<CloneDialog>>,
+    /// Confirmation listing the remote commits a force push would overwrite
+    force_push_dialog: Option<Entity<ForcePushDialog>>,
+    /// Summary of branches removed by a prune-enabled fetch
+    prune_report: Option<Entity<PruneReportDialog>>,
+    /// Branch comparison / bulk cherry-pick view
+    branch_compare: Option<Entity<BranchCompareView>>,
+    /// Worktree list / quick-switch view
+    worktrees_view: Option<Entity<WorktreesView>>,
+    /// Review notes scratchpad
+    review_notes_view: Option<Entity<ReviewNotesView>>,
+    /// TODO/FIXME comment list
+    todos_view: Option<Entity<TodosView>>,
+    search_view: Option<Entity<SearchView>>,
+    /// Who last changed each line of a file
+    blame_view: Option<Entity<BlameView>>,
+    compare_view: Option<Entity<CompareView>>,
+    /// Full-screen keyboard review of unstaged changes
+    review_mode: Option<Entity<ReviewMode>>,
+    /// Tag list and actions
+    tags_panel: Option<Entity<TagsPanel>>,
+    /// Stash dialog with message and options
+    stash_dialog: Option<Entity<StashDialog>>,
+    /// Reflog browser with operation undo
+    reflog_view: Option<Entity<ReflogView>>,
+    /// Discarded changes backed up to the trash
+    trash_view: Option<Entity<TrashView>>,
+    /// Developer logging console
+    log_console: Option<Entity<LogConsole>>,
+    /// Developer frame time and notify count overlay
+    perf_overlay: Option<Entity<PerfOverlay>>,
+    /// Offer to restore drafts left by a session that quit unexpectedly
+    draft_recovery: Option<Entity<DraftRecoveryDialog>>,
     /// Main layout entity (created when repository is opened)
     main_layout: Option<Entity<MainLayout>>,
     /// File system watcher for auto-refresh
-    watcher: Arc<Mutex<RepositoryWatcher>>,
+    watcher: RepositoryWatcher,
+    /// Local JSON-RPC socket for editor integration
+    control_server: ControlServer,
+    /// Always-on socket through which later invocations of the app hand
+    /// this one a repository to open
+    instance_server: ControlServer,
+    /// Periodic background fetch and the interval it was started with;
+    /// dropping the task stops it
+    auto_fetch: Option<(AutoFetchInterval, Task<()>)>,
+    /// System appearance, used when the theme follows the system
+    window_appearance: Appearance,
+    /// Watches the user themes directory for hot-reload
+    theme_watcher: ThemeWatcher,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -51,11 +148,59 @@ pub enum ViewMode {
 }
 
 impl Awabancha {
-    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
-        let git_state = cx.new(|_| GitState::new());
+    pub fn new(
+        log_receiver: UnboundedReceiver<LogEntry>,
+        initial_path: Option<PathBuf>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
         let settings = cx.new(|cx| SettingsState::load(cx));
+        let settings_data = settings.read(cx).data.clone();
+        let hosting_token = settings.read(cx).hosting_token();
+        let git_state = cx.new(|_| {
+            let mut state = GitState::new();
+            state.autostash = settings_data.autostash;
+            state.preview_operations = settings_data.preview_operations;
+            state.trash_discards = settings_data.trash_discards;
+            state.merge_mode = settings_data.merge_mode.into();
+            state.hosting_token = hosting_token;
+            state.editor_command = settings_data.editor_command.clone();
+            state.commit_lint = settings_data.commit_lint;
+            state.run_hooks = settings_data.run_hooks;
+            state.signing_policy = settings_data.signing_policy;
+            state.protected_branches = settings_data.protected_branches.clone();
+            state.allowed_signers = settings_data.allowed_signers.clone();
+            state.max_diff_size = settings_data.max_diff_size.bytes();
+            state.diff_options = settings_data.diff_options;
+            state
+        });
         let recent_projects = cx.new(|cx| RecentProjects::load(cx));
-        let toast_state = cx.new(|_| ToastState::new());
+        recent_projects.update(cx, |recent, cx| recent.refresh_statuses(cx));
+        let projects = recent_projects.read(cx).projects().to_vec();
+        menus::set_menus(&projects, cx);
+        let toast_state = cx.new(|_| {
+            let mut state = ToastState::new();
+            state.position = settings_data.toast_position;
+            state.sticky_errors = settings_data.sticky_errors;
+            state.durations = settings_data.toast_durations;
+            state
+        });
+        let session = cx.new(SessionState::load);
+        let review_notes = cx.new(ReviewNotes::load);
+        let todo_state = cx.new(|_| TodoState::new());
+        let log_state = cx.new(|cx| LogState::new(log_receiver, cx));
+        let drafts = cx.new(DraftState::load);
+        let avatars = cx.new(|cx| AvatarState::new(settings_data.fetch_avatars, cx));
+        if !settings.read(cx).data.restore_session {
+            session.update(cx, |session, cx| session.clear(cx));
+        }
+
+        // Follow system appearance changes when the theme is set to System
+        cx.observe_window_appearance(window, |app, window, cx| {
+            app.window_appearance = window.appearance().into();
+            app.apply_theme(cx);
+        })
+        .detach();
 
         // Set up window activation observer for auto-refresh
         let git_state_for_activation = git_state.clone();
@@ -69,25 +214,267 @@ impl Awabancha {
         })
         .detach();
 
+        // Observe settings so open modals reflect changes
+        cx.observe(&settings, |this, settings, cx| {
+            let settings = settings.read(cx);
+            // Overrides for the open repository win over the global values
+            let merge_mode = settings.merge_mode().into();
+            let (remote_name, identity) = (settings.default_remote(), settings.identity());
+            let data = &settings.data;
+            let (autostash, preview_operations) = (data.autostash, data.preview_operations);
+            let (trash_discards, control_socket) = (data.trash_discards, data.control_socket);
+            let (toast_position, sticky_errors) = (data.toast_position, data.sticky_errors);
+            let (toast_durations, fetch_avatars) = (data.toast_durations, data.fetch_avatars);
+            let hosting_token = settings.hosting_token();
+            let editor_command = data.editor_command.clone();
+            let (commit_lint, run_hooks) = (data.commit_lint, data.run_hooks);
+            let signing_policy = data.signing_policy;
+            let protected_branches = data.protected_branches.clone();
+            let allowed_signers = data.allowed_signers.clone();
+            let (max_diff_size, diff_options) = (data.max_diff_size.bytes(), data.diff_options);
+            let locale = data.locale;
+            this.git_state.update(cx, |state, cx| {
+                state.autostash = autostash;
+                state.preview_operations = preview_operations;
+                state.trash_discards = trash_discards;
+                state.merge_mode = merge_mode;
+                state.remote_name = remote_name;
+                state.identity = identity;
+                state.editor_command = editor_command;
+                if state.commit_lint != commit_lint {
+                    // The commit form shows the message checks
+                    state.commit_lint = commit_lint;
+                    cx.notify();
+                }
+                state.run_hooks = run_hooks;
+                state.signing_policy = signing_policy;
+                state.protected_branches = protected_branches;
+                state.allowed_signers = allowed_signers;
+                state.max_diff_size = max_diff_size;
+                state.set_diff_options(diff_options, cx);
+                state.set_hosting_token(hosting_token, cx);
+            });
+            this.toast_state.update(cx, |state, cx| {
+                state.position = toast_position;
+                state.sticky_errors = sticky_errors;
+                state.durations = toast_durations;
+                cx.notify();
+            });
+            this.avatars
+                .update(cx, |avatars, cx| avatars.set_fetch(fetch_avatars, cx));
+            if control_socket != this.control_server.is_running() {
+                if control_socket {
+                    this.start_control_server(cx);
+                } else {
+                    this.control_server.stop();
+                }
+            }
+            this.apply_theme(cx);
+            i18n::set_locale(locale, cx);
+            this.update_auto_fetch(cx);
+            cx.notify();
+        })
+        .detach();
+
+        // Observe recent projects so the welcome screen shows their status
+        // and the Open Recent and dock menus list them
+        cx.observe(&recent_projects, |_this, recent_projects, cx| {
+            let projects = recent_projects.read(cx).projects().to_vec();
+            menus::set_menus(&projects, cx);
+            cx.notify();
+        })
+        .detach();
+
         // Observe toast state for re-renders
         cx.observe(&toast_state, |_this, _toast_state, cx| {
             cx.notify();
         })
         .detach();
 
-        Self {
+        let mut app = Self {
             repository_path: None,
             git_state,
             settings,
             recent_projects,
             toast_state,
+            session,
+            review_notes,
+            todo_state,
+            log_state,
+            drafts,
+            avatars,
             view_mode: ViewMode::Welcome,
             show_settings: false,
+            repo_settings: None,
+            git_identity: None,
+            hosting_token: None,
+            git_auth: None,
+            external_editor: None,
+            signing_lists: None,
             show_diff: false,
+            diff_focus: cx.focus_handle(),
+            diff_scroll: ScrollHandle::new(),
+            diff_file_list: true,
             show_conflict_dialog: false,
             conflict_dialog: None,
+            auth_dialog: None,
+            clone_dialog: None,
+            force_push_dialog: None,
+            prune_report: None,
+            branch_compare: None,
+            worktrees_view: None,
+            review_notes_view: None,
+            todos_view: None,
+            search_view: None,
+            blame_view: None,
+            compare_view: None,
+            review_mode: None,
+            tags_panel: None,
+            stash_dialog: None,
+            reflog_view: None,
+            trash_view: None,
+            log_console: None,
+            perf_overlay: None,
+            draft_recovery: None,
             main_layout: None,
-            watcher: Arc::new(Mutex::new(RepositoryWatcher::new())),
+            watcher: RepositoryWatcher::new(),
+            control_server: ControlServer::new(),
+            instance_server: ControlServer::new(),
+            auto_fetch: None,
+            window_appearance: window.appearance().into(),
+            theme_watcher: ThemeWatcher::new(),
+        };
+
+        app.apply_theme(cx);
+        i18n::set_locale(app.settings.read(cx).data.locale, cx);
+        app.start_theme_watcher(cx);
+        app.update_auto_fetch(cx);
+        match initial_path {
+            // Opened from the command line: skip the welcome screen
+            Some(path) => {
+                if let Err(e) = app.open_containing_repository(&path, cx) {
+                    log::error!("Failed to open {}: {}", path.display(), e);
+                    app.toast_state.update(cx, |toast, cx| {
+                        toast.error(format!("Can't open {}: {}", path.display(), e), cx)
+                    });
+                }
+            }
+            None => app.restore_session(cx),
+        }
+        app.start_instance_server(cx);
+        if app.settings.read(cx).data.control_socket {
+            app.start_control_server(cx);
+        }
+        app
+    }
+
+    /// (Re)start the periodic background fetch when the interval setting
+    /// changed. Fetching only moves remote-tracking refs, so new commits show
+    /// up in the ahead/behind counts without touching the working tree.
+    fn update_auto_fetch(&mut self, cx: &mut Context<Self>) {
+        let interval = self.settings.read(cx).data.auto_fetch_interval;
+        if self.auto_fetch.as_ref().map(|(current, _)| *current) == Some(interval) {
+            return;
+        }
+        let Some(period) = interval.duration() else {
+            self.auto_fetch = None;
+            return;
+        };
+
+        let task = cx.spawn(async move |this, cx| loop {
+            cx.background_executor().timer(period).await;
+            let result = this.update(cx, |app, cx| {
+                if app.view_mode != ViewMode::Repository {
+                    return;
+                }
+                let settings = app.settings.read(cx);
+                let auth = settings.get_auth_credentials();
+                let prune = settings.data.fetch_prune;
+                app.git_state.update(cx, |state, cx| {
+                    state.fetch_all_in_background(auth, prune, cx);
+                });
+            });
+            if result.is_err() {
+                break;
+            }
+        });
+        self.auto_fetch = Some((interval, task));
+    }
+
+    /// Install the theme chosen in settings, resolved against the system
+    /// appearance and the installed user themes
+    fn apply_theme(&self, cx: &mut Context<Self>) {
+        let data = &self.settings.read(cx).data;
+        let appearance = data.theme.appearance(self.window_appearance);
+        let mut theme = theme::resolve_theme(data.user_theme.as_deref(), appearance, cx);
+        theme.graph_palette = data.graph_palette;
+        theme::set_theme(theme, cx);
+    }
+
+    /// Reload user themes whenever a file in the themes directory changes
+    fn start_theme_watcher(&mut self, cx: &mut Context<Self>) {
+        let mut changes = match self.theme_watcher.watch() {
+            Ok(changes) => changes,
+            Err(e) => {
+                log::warn!("Failed to watch themes directory: {}", e);
+                return;
+            }
+        };
+
+        cx.spawn(async move |this, cx| {
+            while changes.next().await.is_some() {
+                let result = this.update(cx, |app, cx| {
+                    ThemeRegistry::reload(cx);
+                    app.apply_theme(cx);
+                    // The settings theme picker lists the installed themes
+                    cx.notify();
+                });
+
+                if result.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Reopen the repository and diff that were open when the app last quit
+    fn restore_session(&mut self, cx: &mut Context<Self>) {
+        let session = self.session.read(cx);
+        let Some(path) = session.restorable_repository() else {
+            return;
+        };
+        let open_diff = session.data.open_diff.clone();
+        let diff_unstaged = session.data.diff_unstaged;
+        let selected_commit = session.data.selected_commit.clone();
+
+        self.open_repository(path, cx);
+
+        if let Some(sha) = selected_commit {
+            // The commit may have been rewritten or be too far back to load
+            let selected = self
+                .git_state
+                .update(cx, |state, cx| state.select_commit_by_sha(&sha, cx));
+            if selected.is_err() {
+                self.session
+                    .update(cx, |session, cx| session.set_selected_commit(None, cx));
+            }
+        }
+
+        if let Some(diff_path) = open_diff {
+            let loaded = self.git_state.update(cx, |state, cx| {
+                if diff_unstaged {
+                    state.load_unstaged_diff(&diff_path, cx)
+                } else {
+                    state.load_file_diff(&diff_path, cx)
+                }
+            });
+            // The file may have been committed or reverted since
+            if loaded.is_ok() {
+                self.show_diff = true;
+            } else {
+                self.session.update(cx, |session, cx| session.set_open_diff(None, cx));
+            }
         }
     }
 
@@ -106,16 +493,46 @@ impl Awabancha {
         });
 
         // Open the repository
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.open_repository(&path, cx) {
+        let opened = self.git_state.update(cx, |state, cx| {
+            state.open_repository(&path, cx).map_err(|e| {
                 log::error!("Failed to open repository: {}", e);
-            }
+            })
         });
 
+        if opened.is_ok() {
+            self.settings.update(cx, |settings, cx| {
+                settings.set_current_repo(Some(&path), cx);
+            });
+            self.session.update(cx, |session, cx| {
+                session.set_last_repository(Some(path.clone()), cx);
+            });
+            self.drafts.update(cx, |drafts, _| drafts.set_repository(Some(path.clone())));
+            self.offer_draft_recovery(&path, cx);
+
+            // A repository left mid merge or pick opens straight into resolving it
+            if self.git_state.read(cx).conflict_info.is_some() {
+                self.open_conflict_dialog(cx);
+            }
+        }
+
+        // Refresh ahead/behind data without blocking the UI
+        let settings = self.settings.read(cx);
+        if opened.is_ok() && settings.data.fetch_on_open {
+            let auth = settings.get_auth_credentials();
+            let prune = settings.data.fetch_prune;
+            self.git_state.update(cx, |state, cx| {
+                state.fetch_all_in_background(auth, prune, cx);
+            });
+        }
+
         // Create main layout
         let git_state = self.git_state.clone();
         let settings = self.settings.clone();
-        self.main_layout = Some(cx.new(|cx| MainLayout::new(git_state, settings, cx)));
+        let session = self.session.clone();
+        let drafts = self.drafts.clone();
+        let avatars = self.avatars.clone();
+        self.main_layout =
+            Some(cx.new(|cx| MainLayout::new(git_state, settings, session, drafts, avatars, cx)));
 
         // Start file watcher
         self.start_watching(path.clone(), cx);
@@ -125,47 +542,34 @@ impl Awabancha {
         cx.notify();
     }
 
-    fn start_watching(&self, path: PathBuf, cx: &mut Context<Self>) {
-        // Start the watcher
-        if let Ok(mut watcher) = self.watcher.lock() {
-            if let Err(e) = watcher.watch(path) {
+    fn start_watching(&mut self, path: PathBuf, cx: &mut Context<Self>) {
+        let mut events = match self.watcher.watch(path) {
+            Ok(events) => events,
+            Err(e) => {
                 log::warn!("Failed to start file watcher: {}", e);
+                return;
             }
-        }
+        };
 
-        // Spawn a background task to poll for changes
-        let watcher = self.watcher.clone();
+        // Forward watcher events to git state; the stream ends when the
+        // watcher is stopped
         let git_state = self.git_state.clone();
-
         cx.spawn(async move |this, cx| {
-            loop {
-                // Sleep for a bit
-                cx.background_executor()
-                    .timer(std::time::Duration::from_millis(500))
-                    .await;
-
-                // Check if watcher detected changes
-                let should_refresh = watcher
-                    .lock()
-                    .map(|w| w.poll())
-                    .unwrap_or(false);
-
-                if should_refresh {
-                    let _ = this.update(cx, |_app, cx| {
-                        git_state.update(cx, |state, cx| {
-                            state.refresh(cx);
-                        });
+            while let Some(batch) = events.next().await {
+                let result = this.update(cx, |app, cx| {
+                    let head_change = git_state.update(cx, |state, cx| {
+                        state.handle_watcher_events(&batch, cx)
                     });
-                }
 
-                // Check if we should stop (repository closed)
-                let should_stop = this
-                    .update(cx, |app, _cx| {
-                        app.view_mode == ViewMode::Welcome
-                    })
-                    .unwrap_or(true);
+                    // e.g. `git checkout` in a terminal
+                    if let Some(branch) = head_change {
+                        app.toast_state.update(cx, |toast, cx| {
+                            toast.info(format!("Branch changed to {} externally", branch), cx);
+                        });
+                    }
+                });
 
-                if should_stop {
+                if result.is_err() {
                     break;
                 }
             }
@@ -173,18 +577,156 @@ impl Awabancha {
         .detach();
     }
 
+    /// Accept requests from editors and scripts on the control socket
+    fn start_control_server(&mut self, cx: &mut Context<Self>) {
+        let requests = match self.control_server.start() {
+            Ok(requests) => requests,
+            Err(e) => {
+                log::warn!("Failed to start control socket: {}", e);
+                return;
+            }
+        };
+        self.serve_control_requests(requests, false, cx);
+    }
+
+    /// Accept repositories to open from later invocations of the app, so
+    /// `awabancha <path>` reuses this window instead of starting another
+    fn start_instance_server(&mut self, cx: &mut Context<Self>) {
+        let Some(path) = ControlServer::instance_socket_path() else {
+            return;
+        };
+        match self.instance_server.start_at(path) {
+            Ok(requests) => self.serve_control_requests(requests, true, cx),
+            Err(e) => log::warn!("Failed to start instance socket: {}", e),
+        }
+    }
+
+    /// Handle requests until the server stops. `open_only` refuses anything
+    /// but opening a repository, for the socket that is always on.
+    fn serve_control_requests(
+        &mut self,
+        mut requests: UnboundedReceiver<ControlRequest>,
+        open_only: bool,
+        cx: &mut Context<Self>,
+    ) {
+        cx.spawn(async move |this, cx| {
+            while let Some(request) = requests.next().await {
+                let command = request.command.clone();
+                if open_only && !matches!(command, ControlCommand::OpenRepository { .. }) {
+                    request.respond(Err("Only openRepository is accepted here".to_string()));
+                    continue;
+                }
+                match this.update(cx, |app, cx| app.handle_control_command(command, cx)) {
+                    Ok(result) => request.respond(result),
+                    Err(_) => break,
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Carry out a control socket request, replying with a JSON result or an
+    /// error message
+    fn handle_control_command(
+        &mut self,
+        command: ControlCommand,
+        cx: &mut Context<Self>,
+    ) -> Result<serde_json::Value, String> {
+        cx.activate(true);
+
+        match command {
+            ControlCommand::OpenRepository { path } => {
+                let workdir = self.open_containing_repository(&path, cx)?;
+                Ok(serde_json::json!({ "repository": workdir }))
+            }
+            ControlCommand::ShowDiff { path, repository } => {
+                let repository = match repository {
+                    Some(repository) => repository,
+                    None if path.is_absolute() => path.clone(),
+                    None => self.repository_path.clone().ok_or("No repository is open")?,
+                };
+                let workdir = self.open_containing_repository(&repository, cx)?;
+
+                let relative = if path.is_absolute() {
+                    let path = path.canonicalize().unwrap_or(path);
+                    path.strip_prefix(&workdir)
+                        .map_err(|_| format!("{} is not in {}", path.display(), workdir.display()))?
+                        .to_path_buf()
+                } else {
+                    path
+                };
+                let relative = relative.to_string_lossy().replace('\\', "/");
+
+                self.git_state
+                    .update(cx, |state, cx| state.load_file_diff(&relative, cx))
+                    .map_err(|e| e.to_string())?;
+                self.show_diff = true;
+                self.session
+                    .update(cx, |session, cx| session.set_open_diff(Some(relative.clone()), cx));
+                cx.notify();
+                Ok(serde_json::json!({ "path": relative }))
+            }
+            ControlCommand::ShowCommit { rev, repository } => {
+                if let Some(repository) = repository {
+                    self.open_containing_repository(&repository, cx)?;
+                }
+                let (sha, summary) = self
+                    .git_state
+                    .read(cx)
+                    .resolve_commit(&rev)
+                    .map_err(|e| e.to_string())?;
+                self.git_state
+                    .update(cx, |state, cx| state.focus_commit(&sha, cx))
+                    .map_err(|e| e.to_string())?;
+                if self.show_diff {
+                    self.close_diff(cx);
+                }
+                Ok(serde_json::json!({ "sha": sha, "summary": summary }))
+            }
+        }
+    }
+
+    /// Open the repository containing `path` unless it is already open,
+    /// returning its working directory
+    fn open_containing_repository(
+        &mut self,
+        path: &Path,
+        cx: &mut Context<Self>,
+    ) -> Result<PathBuf, String> {
+        let repo = git2::Repository::discover(path).map_err(|e| e.message().to_string())?;
+        let workdir = repo
+            .workdir()
+            .ok_or("Bare repositories are not supported")?
+            .to_path_buf();
+        let workdir = workdir.canonicalize().unwrap_or(workdir);
+
+        let current = self.repository_path.as_ref().and_then(|p| p.canonicalize().ok());
+        if current.as_ref() != Some(&workdir) {
+            self.open_repository(workdir.clone(), cx);
+        }
+        Ok(workdir)
+    }
+
     pub fn close_repository(&mut self, cx: &mut Context<Self>) {
         // Stop the watcher
-        if let Ok(mut watcher) = self.watcher.lock() {
-            watcher.stop();
-        }
+        self.watcher.stop();
 
         self.git_state.update(cx, |state, cx| {
             state.close_repository(cx);
         });
+        self.session.update(cx, |session, cx| {
+            session.set_last_repository(None, cx);
+        });
+        self.settings.update(cx, |settings, cx| settings.set_current_repo(None, cx));
+        self.drafts.update(cx, |drafts, _| drafts.set_repository(None));
+        self.draft_recovery = None;
+        self.review_mode = None;
+        self.repo_settings = None;
         self.repository_path = None;
         self.view_mode = ViewMode::Welcome;
         self.main_layout = None;
+        self.recent_projects
+            .update(cx, |recent, cx| recent.refresh_statuses(cx));
         cx.notify();
     }
 
@@ -219,6 +761,81 @@ impl Awabancha {
         .detach();
     }
 
+    /// Ask where a recent project that was moved is now, then open it
+    fn locate_project(&mut self, old_path: PathBuf, cx: &mut Context<Self>) {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: false,
+            directories: true,
+            multiple: false,
+            prompt: Some("Locate Repository".into()),
+        });
+
+        cx.spawn(async move |this, cx| {
+            if let Ok(Ok(Some(paths))) = receiver.await {
+                if let Some(path) = paths.into_iter().next() {
+                    this.update(cx, |app, cx| {
+                        app.recent_projects.update(cx, |recent, cx| {
+                            recent.relocate_project(&old_path, path.clone(), cx);
+                        });
+                        app.open_repository(path, cx);
+                    })
+                    .ok();
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn handle_open_recent_project(
+        &mut self,
+        action: &OpenRecentProject,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if action.path.exists() {
+            self.open_repository(action.path.clone(), cx);
+        } else {
+            self.locate_project(action.path.clone(), cx);
+        }
+    }
+
+    fn handle_clear_recent_projects(
+        &mut self,
+        _: &ClearRecentProjects,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.recent_projects.update(cx, |recent, cx| recent.clear_all(cx));
+    }
+
+    fn handle_clone_repository(
+        &mut self,
+        _: &CloneRepository,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.clone_dialog.is_some() {
+            return;
+        }
+        let auth = self.settings.read(cx).get_auth_credentials();
+        let dialog = cx.new(|cx| CloneDialog::new(auth, cx));
+
+        cx.subscribe(&dialog, |this, _dialog, event: &CloneDialogEvent, cx| {
+            this.clone_dialog = None;
+            if let CloneDialogEvent::Cloned(path) = event {
+                this.open_repository(path.clone(), cx);
+                this.toast_state.update(cx, |toast, cx| {
+                    toast.success(format!("Cloned into {}", path.display()), cx);
+                });
+            }
+            cx.notify();
+        })
+        .detach();
+
+        self.clone_dialog = Some(dialog);
+        cx.notify();
+    }
+
     fn handle_close_repository(
         &mut self,
         _: &CloseRepository,
@@ -231,39 +848,198 @@ impl Awabancha {
     fn handle_open_settings(
         &mut self,
         _: &OpenSettings,
-        _window: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         self.show_settings = true;
+        // Rebuilt on each opening so the fields show the saved overrides
+        self.repo_settings = self.repository_path.as_ref().map(|path| {
+            let settings = self.settings.clone();
+            let remotes = self.git_state.read(cx).remote_names();
+            let path = path.clone();
+            cx.new(|cx| RepoSettingsView::new(settings, path, remotes, cx))
+        });
+        let (git_state, settings) = (self.git_state.clone(), self.settings.clone());
+        self.git_identity = Some(cx.new(|cx| GitIdentityView::new(git_state, settings, cx)));
+        let settings = self.settings.clone();
+        self.hosting_token = Some(cx.new(|cx| HostingTokenView::new(settings, cx)));
+        let (git_state, settings) = (self.git_state.clone(), self.settings.clone());
+        self.git_auth = Some(cx.new(|cx| GitAuthView::new(git_state, settings, window, cx)));
+        let settings = self.settings.clone();
+        self.external_editor = Some(cx.new(|cx| ExternalEditorView::new(settings, cx)));
+        let settings = self.settings.clone();
+        self.signing_lists = Some(cx.new(|cx| SigningListsView::new(settings, cx)));
         cx.notify();
     }
 
     fn handle_cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
-        if self.show_conflict_dialog {
+        if self.log_console.is_some() {
+            self.log_console = None;
+            cx.notify();
+        } else if self.git_state.read(cx).pending_preview.is_some() {
+            self.git_state.update(cx, |state, cx| state.dismiss_preview(cx));
+        } else if self.git_state.read(cx).pending_discard.is_some() {
+            self.git_state.update(cx, |state, cx| state.cancel_discard(cx));
+        } else if self.git_state.read(cx).pending_merge.is_some() {
+            self.git_state.update(cx, |state, cx| state.cancel_merge(cx));
+        } else if self.git_state.read(cx).pending_stash_branch.is_some() {
+            self.git_state.update(cx, |state, cx| state.cancel_stash_branch(cx));
+        } else if self.draft_recovery.is_some() {
+            // Leave the drafts on disk so they are offered again next time
+            self.draft_recovery = None;
+            cx.notify();
+        } else if self.auth_dialog.is_some() {
+            self.auth_dialog = None;
+            cx.notify();
+        } else if self.clone_dialog.is_some() {
+            self.clone_dialog = None;
+            cx.notify();
+        } else if self.force_push_dialog.is_some() {
+            self.force_push_dialog = None;
+            cx.notify();
+        } else if self.prune_report.is_some() {
+            self.prune_report = None;
+            cx.notify();
+        } else if self.branch_compare.is_some() {
+            self.branch_compare = None;
+            cx.notify();
+        } else if self.worktrees_view.is_some() {
+            self.worktrees_view = None;
+            cx.notify();
+        } else if self.review_notes_view.is_some() {
+            self.review_notes_view = None;
+            cx.notify();
+        } else if self.todos_view.is_some() {
+            self.todos_view = None;
+            cx.notify();
+        } else if self.search_view.is_some() {
+            self.search_view = None;
+            cx.notify();
+        } else if self.blame_view.is_some() {
+            self.blame_view = None;
+            cx.notify();
+        } else if self.compare_view.is_some() {
+            self.compare_view = None;
+            cx.notify();
+        } else if self.review_mode.is_some() {
+            self.review_mode = None;
+            cx.notify();
+        } else if self.tags_panel.is_some() {
+            self.tags_panel = None;
+            cx.notify();
+        } else if self.stash_dialog.is_some() {
+            self.stash_dialog = None;
+            cx.notify();
+        } else if self.reflog_view.is_some() {
+            self.reflog_view = None;
+            cx.notify();
+        } else if self.trash_view.is_some() {
+            self.trash_view = None;
+            cx.notify();
+        } else if self.show_conflict_dialog {
             self.show_conflict_dialog = false;
             cx.notify();
         } else if self.show_diff {
-            self.show_diff = false;
-            self.git_state.update(cx, |state, cx| {
-                state.clear_diff(cx);
-            });
-            cx.notify();
+            self.close_diff(cx);
         } else if self.show_settings {
             self.show_settings = false;
             cx.notify();
         }
     }
 
-    fn handle_show_diff(&mut self, _: &ShowDiff, _window: &mut Window, cx: &mut Context<Self>) {
+    fn handle_show_diff(&mut self, _: &ShowDiff, window: &mut Window, cx: &mut Context<Self>) {
         self.show_diff = true;
+        self.diff_scroll.set_offset(point(px(0.0), px(0.0)));
+        window.focus(&self.diff_focus, cx);
+        let git_state = self.git_state.read(cx);
+        let path = git_state.current_diff.as_ref().map(|d| d.path.clone());
+        let unstaged = git_state.diff_is_unstaged();
+        self.session.update(cx, |session, cx| {
+            session.set_open_diff(path, cx);
+            session.set_diff_unstaged(unstaged, cx);
+        });
         cx.notify();
     }
 
+    fn handle_next_hunk(&mut self, _: &NextHunk, _window: &mut Window, cx: &mut Context<Self>) {
+        self.step_diff_hunk(true, cx);
+    }
+
+    fn handle_previous_hunk(
+        &mut self,
+        _: &PreviousHunk,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.step_diff_hunk(false, cx);
+    }
+
+    /// Scroll the diff viewer to the next or previous hunk
+    fn step_diff_hunk(&mut self, forward: bool, cx: &mut Context<Self>) {
+        if !self.show_diff {
+            return;
+        }
+        let line = self
+            .git_state
+            .update(cx, |state, cx| state.step_diff_hunk(forward, cx));
+        if let Some(line) = line {
+            self.diff_scroll.scroll_to_top_of_item(line);
+        }
+    }
+
+    fn handle_next_diff_file(
+        &mut self,
+        _: &NextDiffFile,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.step_diff_file(true, cx);
+    }
+
+    fn handle_previous_diff_file(
+        &mut self,
+        _: &PreviousDiffFile,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.step_diff_file(false, cx);
+    }
+
+    /// Show the next or previous file of a commit or comparison diff
+    fn step_diff_file(&mut self, forward: bool, cx: &mut Context<Self>) {
+        if !self.show_diff {
+            return;
+        }
+        let moved = self
+            .git_state
+            .update(cx, |state, cx| state.step_diff_file(forward, cx));
+        if moved {
+            self.diff_scroll.set_offset(point(px(0.0), px(0.0)));
+        }
+    }
+
+    fn handle_toggle_diff_file_list(
+        &mut self,
+        _: &ToggleDiffFileList,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.show_diff {
+            self.diff_file_list = !self.diff_file_list;
+            cx.notify();
+        }
+    }
+
     fn handle_close_diff(&mut self, _: &CloseDiff, _window: &mut Window, cx: &mut Context<Self>) {
+        self.close_diff(cx);
+    }
+
+    fn close_diff(&mut self, cx: &mut Context<Self>) {
         self.show_diff = false;
         self.git_state.update(cx, |state, cx| {
             state.clear_diff(cx);
         });
+        self.session.update(cx, |session, cx| session.set_open_diff(None, cx));
         cx.notify();
     }
 
@@ -273,10 +1049,15 @@ impl Awabancha {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.open_conflict_dialog(cx);
+    }
+
+    fn open_conflict_dialog(&mut self, cx: &mut Context<Self>) {
         // Create conflict dialog entity if needed
         if self.conflict_dialog.is_none() {
             let git_state = self.git_state.clone();
-            self.conflict_dialog = Some(cx.new(|cx| ConflictDialog::new(git_state, cx)));
+            let drafts = self.drafts.clone();
+            self.conflict_dialog = Some(cx.new(|cx| ConflictDialog::new(git_state, drafts, cx)));
         }
         self.show_conflict_dialog = true;
         cx.notify();
@@ -292,154 +1073,1342 @@ impl Awabancha {
         cx.notify();
     }
 
-    fn handle_refresh(&mut self, _: &Refresh, _window: &mut Window, cx: &mut Context<Self>) {
-        self.git_state.update(cx, |state, cx| {
-            state.refresh(cx);
-        });
+    fn handle_compare_branches(
+        &mut self,
+        _: &CompareBranches,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let view = cx.new(|cx| BranchCompareView::new(git_state, cx));
+        cx.subscribe(&view, |this, _view, _event: &BranchCompareDismissed, cx| {
+            this.branch_compare = None;
+            cx.notify();
+        })
+        .detach();
+
+        self.branch_compare = Some(view);
+        cx.notify();
     }
 
-    fn handle_stage_all(&mut self, _: &StageAll, _window: &mut Window, cx: &mut Context<Self>) {
-        let result = self.git_state.update(cx, |state, cx| state.stage_all(cx));
-        match result {
-            Ok(_) => {
+    fn handle_show_worktrees(
+        &mut self,
+        _: &ShowWorktrees,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let view = cx.new(|cx| WorktreesView::new(git_state, cx));
+        cx.subscribe(&view, |this, _view, event: &WorktreesEvent, cx| {
+            this.worktrees_view = None;
+            if let WorktreesEvent::Open(path) = event {
+                // Switch the current window over to the selected worktree
+                this.open_repository(path.clone(), cx);
+            }
+            cx.notify();
+        })
+        .detach();
+
+        self.worktrees_view = Some(view);
+        cx.notify();
+    }
+
+    fn handle_show_review_notes(
+        &mut self,
+        _: &ShowReviewNotes,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let review_notes = self.review_notes.clone();
+        let view = cx.new(|cx| ReviewNotesView::new(git_state, review_notes, cx));
+        cx.subscribe(&view, |this, _view, _event: &ReviewNotesDismissed, cx| {
+            this.review_notes_view = None;
+            cx.notify();
+        })
+        .detach();
+
+        self.review_notes_view = Some(view);
+        cx.notify();
+    }
+
+    fn handle_show_todos(&mut self, _: &ShowTodos, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let todo_state = self.todo_state.clone();
+        let view = cx.new(|cx| TodosView::new(git_state, todo_state, cx));
+        cx.subscribe(&view, |this, _view, _event: &TodosDismissed, cx| {
+            this.todos_view = None;
+            cx.notify();
+        })
+        .detach();
+
+        self.todos_view = Some(view);
+        cx.notify();
+    }
+
+    fn handle_show_search(&mut self, _: &ShowSearch, window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let view = cx.new(|cx| SearchView::new(git_state, cx));
+        cx.subscribe(&view, |this, _view, _event: &SearchDismissed, cx| {
+            this.search_view = None;
+            cx.notify();
+        })
+        .detach();
+
+        let focus_handle = view.read(cx).query_input.focus_handle(cx);
+        window.focus(&focus_handle, cx);
+        self.search_view = Some(view);
+        cx.notify();
+    }
+
+    fn handle_show_blame(
+        &mut self,
+        action: &ShowBlame,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let path = action.path.clone();
+        let view = cx.new(|cx| BlameView::new(git_state, path, cx));
+        cx.subscribe(&view, |this, _view, _event: &BlameDismissed, cx| {
+            this.blame_view = None;
+            cx.notify();
+        })
+        .detach();
+
+        self.blame_view = Some(view);
+        cx.notify();
+    }
+
+    fn handle_compare_revisions(
+        &mut self,
+        action: &CompareRevisions,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let (base, head) = (action.base.clone(), action.head.clone());
+        let view = cx.new(|cx| CompareView::new(git_state, base, head, cx));
+        cx.subscribe(&view, |this, _view, _event: &CompareDismissed, cx| {
+            this.compare_view = None;
+            cx.notify();
+        })
+        .detach();
+
+        self.compare_view = Some(view);
+        cx.notify();
+    }
+
+    fn handle_show_file_history(
+        &mut self,
+        action: &ShowFileHistory,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(main_layout) = &self.main_layout {
+            main_layout.update(cx, |layout, cx| layout.show_file_history(&action.path, cx));
+        }
+    }
+
+    fn handle_review_changes(
+        &mut self,
+        _: &ReviewChanges,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let view = cx.new(|cx| ReviewMode::new(git_state, cx));
+        cx.subscribe(&view, |this, _view, _event: &ReviewModeDismissed, cx| {
+            this.review_mode = None;
+            cx.notify();
+        })
+        .detach();
+
+        window.focus(&view.read(cx).focus_handle(), cx);
+        self.review_mode = Some(view);
+        cx.notify();
+    }
+
+    /// Show or hide the logging console; available on every screen
+    fn handle_toggle_log_console(
+        &mut self,
+        _: &ToggleLogConsole,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.log_console.take().is_none() {
+            let log_state = self.log_state.clone();
+            let view = cx.new(|cx| LogConsole::new(log_state, cx));
+            cx.subscribe(&view, |this, _view, _event: &LogConsoleDismissed, cx| {
+                this.log_console = None;
+                cx.notify();
+            })
+            .detach();
+            self.log_console = Some(view);
+        }
+        cx.notify();
+    }
+
+    /// Show or hide the performance overlay; available on every screen
+    fn handle_toggle_perf_overlay(
+        &mut self,
+        _: &TogglePerfOverlay,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.perf_overlay.take().is_none() {
+            let git_state = self.git_state.clone();
+            let (settings, session) = (self.settings.clone(), self.session.clone());
+            let toast_state = self.toast_state.clone();
+            self.perf_overlay = Some(cx.new(|cx| {
+                let mut overlay = PerfOverlay::new(git_state, cx);
+                overlay.watch("SettingsState", &settings, cx);
+                overlay.watch("SessionState", &session, cx);
+                overlay.watch("ToastState", &toast_state, cx);
+                overlay
+            }));
+        }
+        cx.notify();
+    }
+
+    fn handle_toggle_project_tree(
+        &mut self,
+        _: &ToggleProjectTree,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(main_layout) = &self.main_layout {
+            main_layout.update(cx, |layout, cx| layout.toggle_project_tree(cx));
+        }
+    }
+
+    fn handle_show_tags(&mut self, _: &ShowTags, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let view = cx.new(|cx| TagsPanel::new(git_state, cx));
+        cx.subscribe(&view, |this, _view, event: &TagsPanelEvent, cx| match event {
+            TagsPanelEvent::Push(name) => {
+                let auth = this.settings.read(cx).get_auth_credentials();
+                this.run_remote_operation(RemoteOperation::PushTag(name.clone()), auth, cx);
+            }
+            TagsPanelEvent::Dismissed => {
+                this.tags_panel = None;
+                cx.notify();
+            }
+        })
+        .detach();
+
+        self.tags_panel = Some(view);
+        cx.notify();
+    }
+
+    /// Prompt to restore drafts the previous session left for `path`
+    fn offer_draft_recovery(&mut self, path: &Path, cx: &mut Context<Self>) {
+        let Some(draft) = self.drafts.read(cx).recovered_for(path).cloned() else {
+            return;
+        };
+
+        let view = cx.new(|cx| DraftRecoveryDialog::new(draft, cx));
+        cx.subscribe(&view, |this, _view, event: &DraftRecoveryEvent, cx| {
+            match event {
+                DraftRecoveryEvent::Restore => {
+                    this.drafts.update(cx, |drafts, cx| drafts.restore(cx));
+                    // Reopen the conflict dialog so the restored choices are visible
+                    if this.git_state.read(cx).conflict_info.is_some() {
+                        this.open_conflict_dialog(cx);
+                    }
+                }
+                DraftRecoveryEvent::Discard => {
+                    this.drafts.update(cx, |drafts, cx| drafts.discard_recovered(cx));
+                }
+            }
+            this.draft_recovery = None;
+            cx.notify();
+        })
+        .detach();
+
+        self.draft_recovery = Some(view);
+    }
+
+    fn handle_show_reflog(&mut self, _: &ShowReflog, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let view = cx.new(|cx| ReflogView::new(git_state, cx));
+        cx.subscribe(&view, |this, _view, event: &ReflogEvent, cx| match event {
+            ReflogEvent::Undo => this.undo_operation(cx),
+            ReflogEvent::Dismissed => {
+                this.reflog_view = None;
+                cx.notify();
+            }
+        })
+        .detach();
+
+        self.reflog_view = Some(view);
+        cx.notify();
+    }
+
+    fn handle_show_trash(&mut self, _: &ShowTrash, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let view = cx.new(|cx| TrashView::new(git_state, cx));
+        cx.subscribe(&view, |this, _view, _event: &TrashDismissed, cx| {
+            this.trash_view = None;
+            cx.notify();
+        })
+        .detach();
+
+        self.trash_view = Some(view);
+        cx.notify();
+    }
+
+    fn handle_undo_operation(
+        &mut self,
+        _: &UndoOperation,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+        self.undo_operation(cx);
+    }
+
+    /// Undo the most recent destructive operation, reporting the outcome
+    fn undo_operation(&mut self, cx: &mut Context<Self>) {
+        let result = self.git_state.update(cx, |state, cx| state.undo_operation(cx));
+        match result {
+            Ok(operation) => {
+                self.toast_state.update(cx, |toast, cx| {
+                    toast.success(format!("Undid {}", operation.label), cx);
+                });
+            }
+            Err(e) => {
+                let details = self.git_state.read(cx).error_details("Undo operation", &e);
+                self.toast_state.update(cx, |toast, cx| {
+                    toast.error_with_details(format!("Failed to undo: {}", e), &details, cx);
+                });
+            }
+        }
+    }
+
+    fn handle_stash_save(&mut self, _: &StashSave, window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let git_state = self.git_state.clone();
+        let view = cx.new(|cx| StashDialog::new(git_state, cx));
+        cx.subscribe(&view, |this, _view, _event: &StashDialogDismissed, cx| {
+            this.stash_dialog = None;
+            cx.notify();
+        })
+        .detach();
+
+        let focus_handle = view.read(cx).focus_handle(cx);
+        window.focus(&focus_handle, cx);
+        self.stash_dialog = Some(view);
+        cx.notify();
+    }
+
+    fn handle_refresh(&mut self, _: &Refresh, _window: &mut Window, cx: &mut Context<Self>) {
+        self.git_state.update(cx, |state, cx| {
+            state.refresh(cx);
+        });
+    }
+
+    fn handle_stage_all(&mut self, _: &StageAll, _window: &mut Window, cx: &mut Context<Self>) {
+        let result = self.git_state.update(cx, |state, cx| state.stage_all(cx));
+        match result {
+            Ok(_) => {
                 self.toast_state.update(cx, |toast, cx| {
                     toast.success("All files staged", cx);
                 });
             }
             Err(e) => {
+                let details = self.git_state.read(cx).error_details("Stage all", &e);
                 self.toast_state.update(cx, |toast, cx| {
-                    toast.error(format!("Failed to stage: {}", e), cx);
+                    toast.error_with_details(format!("Failed to stage: {}", e), &details, cx);
+                });
+            }
+        }
+    }
+
+    /// Ask for confirmation before discarding every tracked change
+    fn handle_discard_all(&mut self, _: &DiscardAll, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+        self.git_state
+            .update(cx, |state, cx| state.request_discard(DiscardScope::All, cx));
+    }
+
+    fn handle_create_commit(
+        &mut self,
+        _: &CreateCommit,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.notify();
+    }
+
+    /// Soft-reset the last commit, offering a redo toast for a short while
+    fn handle_undo_commit(&mut self, _: &UndoCommit, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let result = self.git_state.update(cx, |state, cx| state.undo_last_commit(cx));
+        self.toast_state.update(cx, |toast, cx| match result {
+            Ok(undone) => {
+                let redo = ToastAction::new("Redo", |window, cx| {
+                    window.dispatch_action(Box::new(RedoCommit), cx);
+                });
+                let message = format!("Undid commit '{}'; changes are staged", undone.summary);
+                toast.show_with_action(message, ToastType::Success, redo, cx);
+            }
+            Err(e) => toast.error(format!("Failed to undo commit: {}", e), cx),
+        });
+    }
+
+    fn handle_redo_commit(&mut self, _: &RedoCommit, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+
+        let result = self.git_state.update(cx, |state, cx| state.redo_commit(cx));
+        self.toast_state.update(cx, |toast, cx| match result {
+            Ok(undone) => toast.success(format!("Restored commit '{}'", undone.summary), cx),
+            Err(e) => toast.error(format!("Failed to redo commit: {}", e), cx),
+        });
+    }
+
+    /// Push the current branch. A branch with no upstream yet asks whether
+    /// to make it track the branch it is pushed to.
+    fn handle_push(&mut self, _: &Push, _window: &mut Window, cx: &mut Context<Self>) {
+        let git_state = self.git_state.read(cx);
+        let remote_name = git_state.remote_name.clone();
+        let untracked_branch = git_state
+            .current_branch_without_upstream()
+            .filter(|_| git_state.remote_names().contains(&remote_name));
+        if let Some(branch) = untracked_branch {
+            self.prompt_push_set_upstream(branch, remote_name, cx);
+            return;
+        }
+
+        let auth = self.settings.read(cx).get_auth_credentials();
+        self.run_remote_operation(RemoteOperation::Push, auth, cx);
+    }
+
+    fn prompt_push_set_upstream(
+        &mut self,
+        branch: String,
+        remote_name: String,
+        cx: &mut Context<Self>,
+    ) {
+        let set_upstream =
+            Self::remote_operation_action("Push and Track", RemoteOperation::PushSetUpstream, cx);
+        let push_only = Self::remote_operation_action("Push Only", RemoteOperation::Push, cx);
+
+        self.toast_state.update(cx, |toast, cx| {
+            toast.show_with_actions(
+                format!(
+                    "'{}' has no upstream branch. Track {}/{} after pushing?",
+                    branch, remote_name, branch
+                ),
+                ToastType::Info,
+                vec![set_upstream, push_only],
+                cx,
+            );
+        });
+    }
+
+    /// Confirm a force push with lease, showing the remote commits it would
+    /// overwrite
+    fn handle_force_push(&mut self, _: &ForcePush, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Repository || self.force_push_dialog.is_some() {
+            return;
+        }
+        let plan = match self.git_state.read(cx).force_push_plan() {
+            Ok(plan) => plan,
+            Err(e) => {
+                self.toast_state.update(cx, |toast, cx| {
+                    toast.error(format!("Can't force push: {}", e), cx);
+                });
+                return;
+            }
+        };
+        let dialog = cx.new(|cx| ForcePushDialog::new(plan, cx));
+
+        cx.subscribe(&dialog, |this, _dialog, event: &ForcePushDialogEvent, cx| {
+            this.force_push_dialog = None;
+            if let ForcePushDialogEvent::Confirmed { expected } = event {
+                let auth = this.settings.read(cx).get_auth_credentials();
+                let operation = RemoteOperation::ForcePush {
+                    expected: expected.clone(),
+                };
+                this.run_remote_operation(operation, auth, cx);
+            }
+            cx.notify();
+        })
+        .detach();
+
+        self.force_push_dialog = Some(dialog);
+        cx.notify();
+    }
+
+    fn handle_pull(&mut self, _: &Pull, _window: &mut Window, cx: &mut Context<Self>) {
+        let auth = self.settings.read(cx).get_auth_credentials();
+        self.run_remote_operation(RemoteOperation::Pull, auth, cx);
+    }
+
+    fn handle_fetch(&mut self, _: &Fetch, _window: &mut Window, cx: &mut Context<Self>) {
+        let auth = self.settings.read(cx).get_auth_credentials();
+        self.run_remote_operation(RemoteOperation::Fetch, auth, cx);
+    }
+
+    /// Filter the history to an author's commits, clicked from a commit
+    fn handle_filter_by_author(
+        &mut self,
+        action: &FilterByAuthor,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Repository {
+            return;
+        }
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.filter_by_author(&action.author, cx));
+        if let Err(e) = result {
+            self.toast_state.update(cx, |toast, cx| {
+                toast.error(format!("Can't filter by author: {}", e), cx);
+            });
+        }
+    }
+
+    /// Ask where to save a snapshot of a commit or branch, then write it in
+    /// the background. The file extension picks zip or tar.gz.
+    fn handle_export_snapshot(
+        &mut self,
+        action: &ExportSnapshot,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo_path) = self.repository_path.clone() else {
+            return;
+        };
+        let repo_name = repo_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "snapshot".to_string());
+        let prefix = format!("{}-{}", repo_name, action.name.replace('/', "-"));
+        let file_name = format!("{}.{}", prefix, git::ArchiveFormat::Zip.extension());
+        let snapshot = git::Snapshot::new(action.revision.clone(), prefix);
+        let receiver = cx.prompt_for_new_path(&repo_path, Some(&file_name));
+
+        cx.spawn(async move |this, cx| {
+            let Ok(Ok(Some(path))) = receiver.await else {
+                return;
+            };
+            let Ok(task) = this.update(cx, |this, cx| {
+                this.git_state
+                    .read(cx)
+                    .export_snapshot(snapshot, path.clone(), cx)
+            }) else {
+                return;
+            };
+            let result = task.await;
+
+            this.update(cx, |this, cx| {
+                this.toast_state.update(cx, |toast, cx| match result {
+                    Ok(files) => {
+                        let name = path.file_name().unwrap_or_default().to_string_lossy();
+                        toast.success(format!("Exported {} files to {}", files, name), cx);
+                    }
+                    Err(e) => toast.error(format!("Failed to export snapshot: {:#}", e), cx),
+                });
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn handle_pull_lfs_objects(
+        &mut self,
+        _: &PullLfsObjects,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.git_state.read(cx).lfs_pulling {
+            return;
+        }
+        let task = self.git_state.update(cx, |state, cx| state.pull_lfs_objects(cx));
+
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                let missing = this.git_state.read(cx).lfs_missing.len();
+                this.toast_state.update(cx, |toast, cx| match result {
+                    Ok(()) if missing == 0 => toast.success("Downloaded Git LFS objects", cx),
+                    Ok(()) => toast.warning(
+                        format!("{} Git LFS objects are still missing", missing),
+                        cx,
+                    ),
+                    Err(e) => toast.error(format!("Failed to pull LFS objects: {:#}", e), cx),
                 });
-            }
-        }
+            })
+            .ok();
+        })
+        .detach();
     }
 
-    fn handle_create_commit(
+    fn handle_fetch_full_history(
         &mut self,
-        _: &CreateCommit,
+        _: &FetchFullHistory,
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        cx.notify();
+        let auth = self.settings.read(cx).get_auth_credentials();
+        self.run_remote_operation(RemoteOperation::FetchFullHistory, auth, cx);
     }
 
-    fn handle_push(&mut self, _: &Push, _window: &mut Window, cx: &mut Context<Self>) {
-        let settings = self.settings.read(cx);
-        let auth = settings.get_auth_credentials();
-        let _ = settings;
+    /// Run a remote operation, prompting for new credentials if the remote
+    /// rejects the current ones
+    fn run_remote_operation(
+        &mut self,
+        operation: RemoteOperation,
+        auth: Option<GitCredentials>,
+        cx: &mut Context<Self>,
+    ) {
+        // Evaluate the signing policy before a pull merges incoming commits
+        if operation == RemoteOperation::Pull && !self.preview_pull_signatures(auth.as_ref(), cx) {
+            return;
+        }
+
+        let prune = operation == RemoteOperation::Fetch && self.settings.read(cx).data.fetch_prune;
+        let result = self.git_state.update(cx, |state, cx| {
+            if prune {
+                state.fetch_prune(auth.as_ref(), cx).map(Some)
+            } else {
+                state
+                    .run_remote_operation(operation.clone(), auth.as_ref(), cx)
+                    .map(|_| None)
+            }
+        });
 
-        let result = self.git_state.update(cx, |state, cx| state.push(auth.as_ref(), cx));
         match result {
-            Ok(_) => {
+            Ok(prune_report) => {
+                // Credentials entered in the dialog worked; remember them
+                if self.auth_dialog.take().is_some() {
+                    if let Some(creds) = auth {
+                        self.settings.update(cx, |settings, cx| {
+                            settings.set_username(Some(creds.username), cx);
+                            settings.set_token(Some(creds.password), cx);
+                        });
+                    }
+                }
+
+                let message = match &operation {
+                    RemoteOperation::Push => "Pushed to remote".to_string(),
+                    RemoteOperation::PushSetUpstream => {
+                        "Pushed to remote and set upstream".to_string()
+                    }
+                    RemoteOperation::ForcePush { .. } => "Force pushed to remote".to_string(),
+                    RemoteOperation::Pull => "Pulled from remote".to_string(),
+                    RemoteOperation::Fetch => "Fetched from remote".to_string(),
+                    RemoteOperation::FetchFullHistory => "Fetched the full history".to_string(),
+                    RemoteOperation::PushTag(name) => format!("Pushed tag {}", name),
+                };
+                let git_state = self.git_state.read(cx);
+                let stash_conflict = git_state.is_stash_conflict();
+                let merge_conflict = !stash_conflict && git_state.conflict_info.is_some();
                 self.toast_state.update(cx, |toast, cx| {
-                    toast.success("Pushed to remote", cx);
+                    if stash_conflict || merge_conflict {
+                        let warning = if stash_conflict {
+                            "Pulled, but your stashed changes conflict"
+                        } else {
+                            "Pulled, but the merge has conflicts"
+                        };
+                        toast.show_with_action(
+                            warning,
+                            ToastType::Warning,
+                            Self::view_conflicts_action(),
+                            cx,
+                        );
+                    } else {
+                        toast.success(message, cx);
+                    }
                 });
+
+                if operation == RemoteOperation::Fetch {
+                    self.report_signing_violations(cx);
+                }
+                if let Some(report) = prune_report.filter(|report| !report.is_empty()) {
+                    self.show_prune_report(report, cx);
+                }
+                cx.notify();
+            }
+            Err(e) if git::remote::is_auth_error(&e) => {
+                if let Some(dialog) = &self.auth_dialog {
+                    dialog.update(cx, |dialog, cx| {
+                        dialog.set_error(format!("Authentication failed: {}", e), cx);
+                    });
+                } else {
+                    self.show_auth_dialog(operation, cx);
+                }
             }
             Err(e) => {
+                self.auth_dialog = None;
+                let details = self.git_state.read(cx).error_details(operation.label(), &e);
+                let rejected =
+                    matches!(operation, RemoteOperation::Push | RemoteOperation::PushSetUpstream)
+                    && git::remote::is_non_fast_forward(&e);
+
+                let retry = Self::remote_operation_action("Retry", operation.clone(), cx);
+
                 self.toast_state.update(cx, |toast, cx| {
-                    toast.error(format!("Push failed: {}", e), cx);
+                    if rejected {
+                        let pull = ToastAction::new("Pull", |window, cx| {
+                            window.dispatch_action(Box::new(Pull), cx);
+                        });
+                        // After an amend or rebase the remote commits are meant to go
+                        let force_push = ToastAction::new("Force Push...", |window, cx| {
+                            window.dispatch_action(Box::new(ForcePush), cx);
+                        });
+                        toast.error_with_actions(
+                            "Push rejected: the remote branch has commits you don't have. \
+                             Pull them first, or force push if you rewrote history.",
+                            &details,
+                            vec![pull, force_push, retry],
+                            cx,
+                        );
+                    } else {
+                        let message = format!("{} failed: {}", operation.label(), e);
+                        toast.error_with_actions(message, &details, vec![retry], cx);
+                    }
                 });
+                cx.notify();
             }
         }
     }
 
-    fn handle_pull(&mut self, _: &Pull, _window: &mut Window, cx: &mut Context<Self>) {
-        let settings = self.settings.read(cx);
-        let auth = settings.get_auth_credentials();
-        let _ = settings;
+    /// Toast button running a remote operation with the saved credentials
+    fn remote_operation_action(
+        label: &'static str,
+        operation: RemoteOperation,
+        cx: &mut Context<Self>,
+    ) -> ToastAction {
+        let this = cx.entity().downgrade();
+        ToastAction::new(label, move |_window, cx| {
+            this.update(cx, |app, cx| {
+                let auth = app.settings.read(cx).get_auth_credentials();
+                app.run_remote_operation(operation.clone(), auth, cx);
+            })
+            .ok();
+        })
+    }
 
-        let result = self.git_state.update(cx, |state, cx| state.pull(auth.as_ref(), cx));
-        match result {
-            Ok(_) => {
-                self.toast_state.update(cx, |toast, cx| {
-                    toast.success("Pulled from remote", cx);
-                });
+    /// Toast button opening the conflict dialog
+    fn view_conflicts_action() -> ToastAction {
+        ToastAction::new("View Conflicts", |window, cx| {
+            window.dispatch_action(Box::new(ShowConflictDialog), cx);
+        })
+    }
+
+    /// Fetch and check incoming commits for the current branch before pulling.
+    /// Returns false if the signing policy blocks the pull.
+    fn preview_pull_signatures(
+        &mut self,
+        auth: Option<&GitCredentials>,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let settings = &self.settings.read(cx).data;
+        let policy = settings.signing_policy;
+        let protected_branches = settings.protected_branches.clone();
+        let allowed_signers = settings.allowed_signers.clone();
+
+        if policy == SigningPolicy::Off {
+            return true;
+        }
+
+        let Some(branch) = self
+            .git_state
+            .read(cx)
+            .current_branch()
+            .map(|b| b.to_string())
+            .filter(|b| protected_branches.contains(b))
+        else {
+            return true;
+        };
+
+        // Fetch errors (including auth failures) resurface from the pull itself
+        if self
+            .git_state
+            .update(cx, |state, cx| state.fetch(auth, cx))
+            .is_err()
+        {
+            return true;
+        }
+
+        let violations = match self
+            .git_state
+            .read(cx)
+            .check_incoming_signatures(&[branch], &allowed_signers)
+        {
+            Ok(violations) => violations,
+            Err(e) => {
+                log::warn!("Failed to verify commit signatures: {}", e);
+                Vec::new()
             }
+        };
+
+        if violations.is_empty() {
+            return true;
+        }
+
+        let message = git::SigningViolation::describe(&violations);
+        if policy == SigningPolicy::Block {
+            self.toast_state.update(cx, |toast, cx| {
+                toast.error(format!("Pull blocked: {}", message), cx);
+            });
+            false
+        } else {
+            self.toast_state.update(cx, |toast, cx| {
+                toast.warning(message, cx);
+            });
+            true
+        }
+    }
+
+    /// Warn about fetched commits on protected branches that fail the signing policy
+    fn report_signing_violations(&mut self, cx: &mut Context<Self>) {
+        let settings = &self.settings.read(cx).data;
+        let policy = settings.signing_policy;
+        let protected_branches = settings.protected_branches.clone();
+        let allowed_signers = settings.allowed_signers.clone();
+
+        if policy == SigningPolicy::Off {
+            return;
+        }
+
+        let violations = match self
+            .git_state
+            .read(cx)
+            .check_incoming_signatures(&protected_branches, &allowed_signers)
+        {
+            Ok(violations) => violations,
             Err(e) => {
-                self.toast_state.update(cx, |toast, cx| {
-                    toast.error(format!("Pull failed: {}", e), cx);
-                });
+                log::warn!("Failed to verify commit signatures: {}", e);
+                return;
             }
+        };
+
+        if violations.is_empty() {
+            return;
+        }
+
+        let mut message = git::SigningViolation::describe(&violations);
+        if policy == SigningPolicy::Block {
+            message.push_str(". Pulling or merging into these branches is blocked");
+        }
+        self.toast_state.update(cx, |toast, cx| {
+            toast.warning(message, cx);
+        });
+    }
+
+    fn show_prune_report(&mut self, report: git::remote::PruneReport, cx: &mut Context<Self>) {
+        // Nothing to decide; a toast is enough
+        if report.orphaned.is_empty() {
+            self.toast_state.update(cx, |toast, cx| {
+                toast.info(format!("Pruned {}", report.pruned.join(", ")), cx);
+            });
+            return;
         }
+
+        let git_state = self.git_state.clone();
+        let dialog = cx.new(|cx| PruneReportDialog::new(git_state, report, cx));
+
+        cx.subscribe(&dialog, |this, _dialog, _event: &PruneReportDismissed, cx| {
+            this.prune_report = None;
+            cx.notify();
+        })
+        .detach();
+
+        self.prune_report = Some(dialog);
+    }
+
+    fn show_auth_dialog(&mut self, operation: RemoteOperation, cx: &mut Context<Self>) {
+        let username = self.settings.read(cx).data.git_username.clone();
+        let dialog = cx.new(|cx| AuthDialog::new(operation, username, cx));
+
+        cx.subscribe(&dialog, |this, _dialog, event: &AuthDialogEvent, cx| match event {
+            AuthDialogEvent::Submitted {
+                operation,
+                credentials,
+            } => {
+                this.run_remote_operation(operation.clone(), Some(credentials.clone()), cx);
+            }
+            AuthDialogEvent::Cancelled => {
+                this.auth_dialog = None;
+                cx.notify();
+            }
+        })
+        .detach();
+
+        self.auth_dialog = Some(dialog);
+        cx.notify();
     }
 }
 
 impl Render for Awabancha {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
         let recent_projects = self.recent_projects.clone();
         let settings = self.settings.clone();
         let show_settings = self.show_settings;
+        let repo_settings = self.repo_settings.clone();
+        let git_identity = self.git_identity.clone();
+        let hosting_token = self.hosting_token.clone();
+        let git_auth = self.git_auth.clone();
+        let external_editor = self.external_editor.clone();
+        let signing_lists = self.signing_lists.clone();
         let show_diff = self.show_diff;
+        let diff_focus = self.diff_focus.clone();
+        let diff_scroll = self.diff_scroll.clone();
+        let diff_file_list = self.diff_file_list;
         let show_conflict_dialog = self.show_conflict_dialog;
         let conflict_dialog = self.conflict_dialog.clone();
+        let auth_dialog = self.auth_dialog.clone();
+        let clone_dialog = self.clone_dialog.clone();
+        let force_push_dialog = self.force_push_dialog.clone();
+        let prune_report = self.prune_report.clone();
+        let branch_compare = self.branch_compare.clone();
+        let worktrees_view = self.worktrees_view.clone();
+        let review_notes_view = self.review_notes_view.clone();
+        let todos_view = self.todos_view.clone();
+        let search_view = self.search_view.clone();
+        let blame_view = self.blame_view.clone();
+        let compare_view = self.compare_view.clone();
+        let review_mode = self.review_mode.clone();
+        let tags_panel = self.tags_panel.clone();
+        let stash_dialog = self.stash_dialog.clone();
+        let log_console = self.log_console.clone();
+        let perf_overlay = self.perf_overlay.clone();
+        let reflog_view = self.reflog_view.clone();
+        let trash_view = self.trash_view.clone();
+        let draft_recovery = self.draft_recovery.clone();
+        let git_state = self.git_state.clone();
+        let toast_state = self.toast_state.clone();
         let current_diff = self.git_state.read(cx).current_diff.clone();
         let has_conflicts = self.git_state.read(cx).conflict_info.is_some();
+        let stash_conflict = self.git_state.read(cx).is_stash_conflict();
+        let has_preview = self.git_state.read(cx).pending_preview.is_some();
+        let has_pending_discard = self.git_state.read(cx).pending_discard.is_some();
+        let has_pending_merge = self.git_state.read(cx).pending_merge.is_some();
+        let has_pending_stash_branch = self.git_state.read(cx).pending_stash_branch.is_some();
 
         div()
             .id("awabancha-root")
             .key_context("Awabancha")
             .on_action(cx.listener(Self::handle_open_repository))
+            .on_action(cx.listener(Self::handle_open_recent_project))
+            .on_action(cx.listener(Self::handle_clear_recent_projects))
+            .on_action(cx.listener(Self::handle_clone_repository))
             .on_action(cx.listener(Self::handle_close_repository))
             .on_action(cx.listener(Self::handle_open_settings))
             .on_action(cx.listener(Self::handle_cancel))
             .on_action(cx.listener(Self::handle_refresh))
             .on_action(cx.listener(Self::handle_stage_all))
+            .on_action(cx.listener(Self::handle_discard_all))
             .on_action(cx.listener(Self::handle_create_commit))
+            .on_action(cx.listener(Self::handle_undo_commit))
+            .on_action(cx.listener(Self::handle_redo_commit))
             .on_action(cx.listener(Self::handle_push))
+            .on_action(cx.listener(Self::handle_force_push))
             .on_action(cx.listener(Self::handle_pull))
+            .on_action(cx.listener(Self::handle_fetch))
+            .on_action(cx.listener(Self::handle_fetch_full_history))
+            .on_action(cx.listener(Self::handle_pull_lfs_objects))
+            .on_action(cx.listener(Self::handle_filter_by_author))
+            .on_action(cx.listener(Self::handle_export_snapshot))
             .on_action(cx.listener(Self::handle_show_diff))
             .on_action(cx.listener(Self::handle_close_diff))
+            .on_action(cx.listener(Self::handle_next_hunk))
+            .on_action(cx.listener(Self::handle_previous_hunk))
+            .on_action(cx.listener(Self::handle_next_diff_file))
+            .on_action(cx.listener(Self::handle_previous_diff_file))
+            .on_action(cx.listener(Self::handle_toggle_diff_file_list))
             .on_action(cx.listener(Self::handle_show_conflict_dialog))
             .on_action(cx.listener(Self::handle_close_conflict_dialog))
+            .on_action(cx.listener(Self::handle_compare_branches))
+            .on_action(cx.listener(Self::handle_show_worktrees))
+            .on_action(cx.listener(Self::handle_show_review_notes))
+            .on_action(cx.listener(Self::handle_show_todos))
+            .on_action(cx.listener(Self::handle_show_search))
+            .on_action(cx.listener(Self::handle_show_blame))
+            .on_action(cx.listener(Self::handle_compare_revisions))
+            .on_action(cx.listener(Self::handle_show_file_history))
+            .on_action(cx.listener(Self::handle_review_changes))
+            .on_action(cx.listener(Self::handle_toggle_log_console))
+            .on_action(cx.listener(Self::handle_toggle_perf_overlay))
+            .on_action(cx.listener(Self::handle_toggle_project_tree))
+            .on_action(cx.listener(Self::handle_show_reflog))
+            .on_action(cx.listener(Self::handle_undo_operation))
+            .on_action(cx.listener(Self::handle_show_trash))
+            .on_action(cx.listener(Self::handle_show_tags))
+            .on_action(cx.listener(Self::handle_stash_save))
             .flex()
             .flex_col()
             .size_full()
-            .bg(rgb(0x1e1e2e))
-            .text_color(rgb(0xcdd6f4))
+            .bg(theme.base)
+            .text_color(theme.text)
             .relative()
             .when(self.view_mode == ViewMode::Welcome, |this| {
                 this.child(
-                    WelcomeView::new(recent_projects.clone())
-                        .on_open_repository(cx.listener(|this, path: &PathBuf, _window, cx| {
-                            this.open_repository(path.clone(), cx);
-                        }))
-                        .on_open_dialog(cx.listener(|this, _: &(), _window, cx| {
-                            this.open_repository_dialog(cx);
-                        })),
+                    WelcomeView::new(recent_projects.clone())
+                        .on_open_repository(cx.listener(|this, path: &PathBuf, _window, cx| {
+                            this.open_repository(path.clone(), cx);
+                        }))
+                        .on_open_dialog(cx.listener(|this, _: &(), _window, cx| {
+                            this.open_repository_dialog(cx);
+                        }))
+                        .on_locate_project(cx.listener(|this, path: &PathBuf, _window, cx| {
+                            this.locate_project(path.clone(), cx);
+                        })),
+                )
+            })
+            .when_some(self.main_layout.clone(), |this, main_layout| {
+                this.child(main_layout)
+            })
+            // Review mode covers the layout; dialogs it opens stack above
+            .when_some(review_mode, |this, view| {
+                this.child(div().absolute().inset_0().child(view))
+            })
+            // Conflict indicator and button when conflicts exist
+            .when(has_conflicts && !show_conflict_dialog, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .bottom_4()
+                        .right_4()
+                        .child(
+                            div()
+                                .id("conflict-indicator")
+                                .px_4()
+                                .py_2()
+                                .rounded_lg()
+                                .bg(theme.red)
+                                .text_sm()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(theme.base)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.maroon))
+                                .child(if stash_conflict {
+                                    "⚠ Stash Conflicts - Click to Resolve"
+                                } else {
+                                    "⚠ Merge Conflicts - Click to Resolve"
+                                })
+                                .on_click(|_event, window, cx| {
+                                    window.dispatch_action(Box::new(ShowConflictDialog), cx);
+                                }),
+                        ),
+                )
+            })
+            // Conflict dialog modal overlay
+            .when(show_conflict_dialog && conflict_dialog.is_some(), |this| {
+                let dialog = conflict_dialog.unwrap();
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(700.0))
+                                .h(px(500.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(dialog),
+                        ),
+                )
+            })
+            // Operation preview modal overlay
+            .when(has_preview, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(600.0))
+                                .h(px(520.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(OperationPreviewDialog::new(git_state.clone())),
+                        ),
+                )
+            })
+            // Discard confirmation modal overlay
+            .when(has_pending_discard, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(560.0))
+                                .h(px(460.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(DiscardDialog::new(git_state.clone())),
+                        ),
+                )
+            })
+            // Merge branch modal overlay
+            .when(has_pending_merge, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(560.0))
+                                .h(px(480.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(MergeDialog::new(git_state.clone())),
+                        ),
+                )
+            })
+            // Apply stash onto branch modal overlay
+            .when(has_pending_stash_branch, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(480.0))
+                                .h(px(420.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(StashBranchDialog::new(git_state.clone())),
+                        ),
+                )
+            })
+            // Diff viewer modal overlay
+            .when(show_diff && current_diff.is_some(), |this| {
+                let diff = current_diff.unwrap();
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .key_context("DiffViewer")
+                        .track_focus(&diff_focus)
+                        .child(
+                            div()
+                                .id("diff-backdrop")
+                                .absolute()
+                                .inset_0()
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.close_diff(cx);
+                                })),
+                        )
+                        .child(
+                            DiffViewer::new(diff)
+                                .with_permalinks(git_state, toast_state)
+                                .with_navigation(diff_scroll, diff_file_list)
+                                .with_options(settings.clone()),
+                        ),
+                )
+            })
+            // Settings modal overlay
+            .when(show_settings, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .child(
+                            div()
+                                .id("settings-backdrop")
+                                .absolute()
+                                .inset_0()
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.show_settings = false;
+                                    cx.notify();
+                                })),
+                        )
+                        .child(
+                            SettingsView::new(settings)
+                                .repository(repo_settings)
+                                .identity(git_identity)
+                                .git_auth(git_auth)
+                                .hosting_token(hosting_token)
+                                .external_editor(external_editor)
+                                .signing_lists(signing_lists),
+                        ),
+                )
+            })
+            // Branch comparison modal overlay
+            .when_some(branch_compare, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(700.0))
+                                .h(px(500.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
+                )
+            })
+            // TODOs modal overlay
+            .when_some(todos_view, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(720.0))
+                                .h(px(560.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
                 )
             })
-            .when_some(self.main_layout.clone(), |this, main_layout| {
-                this.child(main_layout)
+            // Search modal overlay
+            .when_some(search_view, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(860.0))
+                                .h(px(600.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
+                )
             })
-            // Conflict indicator and button when conflicts exist
-            .when(has_conflicts && !show_conflict_dialog, |this| {
+            // Blame modal overlay
+            .when_some(blame_view, |this, view| {
                 this.child(
                     div()
                         .absolute()
-                        .bottom_4()
-                        .right_4()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
                         .child(
                             div()
-                                .id("conflict-indicator")
-                                .px_4()
-                                .py_2()
+                                .w(px(900.0))
+                                .h(px(600.0))
                                 .rounded_lg()
-                                .bg(rgb(0xf38ba8))
-                                .text_sm()
-                                .font_weight(FontWeight::SEMIBOLD)
-                                .text_color(rgb(0x1e1e2e))
-                                .cursor_pointer()
-                                .hover(|s| s.bg(rgb(0xeba0ac)))
-                                .child("⚠ Merge Conflicts - Click to Resolve")
-                                .on_click(|_event, window, cx| {
-                                    window.dispatch_action(Box::new(ShowConflictDialog), cx);
-                                }),
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
                         ),
                 )
             })
-            // Conflict dialog modal overlay
-            .when(show_conflict_dialog && conflict_dialog.is_some(), |this| {
-                let dialog = conflict_dialog.unwrap();
+            // Comparison modal overlay
+            .when_some(compare_view, |this, view| {
                 this.child(
                     div()
                         .absolute()
@@ -447,61 +2416,283 @@ impl Render for Awabancha {
                         .flex()
                         .items_center()
                         .justify_center()
-                        .bg(rgba(0x00000088))
+                        .bg(theme.backdrop)
                         .child(
                             div()
-                                .w(px(700.0))
-                                .h(px(500.0))
+                                .w(px(1000.0))
+                                .h(px(640.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
+                )
+            })
+            // Tags modal overlay
+            .when_some(tags_panel, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(640.0))
+                                .h(px(520.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
+                )
+            })
+            // Stash dialog modal overlay
+            .when_some(stash_dialog, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(460.0))
+                                .h(px(340.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
+                )
+            })
+            // Review notes modal overlay
+            .when_some(review_notes_view, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(600.0))
+                                .h(px(520.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
+                )
+            })
+            // Worktrees modal overlay
+            .when_some(worktrees_view, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(600.0))
+                                .h(px(480.0))
                                 .rounded_lg()
                                 .overflow_hidden()
                                 .border_1()
-                                .border_color(rgb(0x313244))
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
+                )
+            })
+            // Prune report modal overlay
+            .when_some(prune_report, |this, dialog| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(480.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
                                 .child(dialog),
                         ),
                 )
             })
-            // Diff viewer modal overlay
-            .when(show_diff && current_diff.is_some(), |this| {
-                let diff = current_diff.unwrap();
+            // Re-authentication dialog modal overlay
+            .when_some(auth_dialog, |this, dialog| {
                 this.child(
                     div()
                         .absolute()
                         .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
                         .child(
                             div()
-                                .id("diff-backdrop")
-                                .absolute()
-                                .inset_0()
-                                .on_click(cx.listener(|this, _event, _window, cx| {
-                                    this.show_diff = false;
-                                    this.git_state.update(cx, |state, cx| {
-                                        state.clear_diff(cx);
-                                    });
-                                    cx.notify();
-                                })),
-                        )
-                        .child(DiffViewer::new(diff)),
+                                .w(px(440.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(dialog),
+                        ),
                 )
             })
-            // Settings modal overlay
-            .when(show_settings, |this| {
+            // Clone dialog modal overlay
+            .when_some(clone_dialog, |this, dialog| {
                 this.child(
                     div()
                         .absolute()
                         .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
                         .child(
                             div()
-                                .id("settings-backdrop")
-                                .absolute()
-                                .inset_0()
-                                .on_click(cx.listener(|this, _event, _window, cx| {
-                                    this.show_settings = false;
-                                    cx.notify();
-                                })),
-                        )
-                        .child(SettingsView::new(settings)),
+                                .w(px(520.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(dialog),
+                        ),
+                )
+            })
+            // Force push confirmation modal overlay
+            .when_some(force_push_dialog, |this, dialog| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(560.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(dialog),
+                        ),
+                )
+            })
+            // Reflog modal overlay
+            .when_some(reflog_view, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(760.0))
+                                .h(px(560.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
+                )
+            })
+            // Trash modal overlay
+            .when_some(trash_view, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(640.0))
+                                .h(px(520.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
+                )
+            })
+            // Draft recovery prompt overlay
+            .when_some(draft_recovery, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(480.0))
+                                .h(px(360.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
                 )
             })
+            // Logging console overlay, above other modals
+            .when_some(log_console, |this, view| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(theme.backdrop)
+                        .child(
+                            div()
+                                .w(px(900.0))
+                                .h(px(600.0))
+                                .rounded_lg()
+                                .overflow_hidden()
+                                .border_1()
+                                .border_color(theme.surface0)
+                                .child(view),
+                        ),
+                )
+            })
+            // Performance overlay, out of the way of the toasts
+            .when_some(perf_overlay, |this, view| {
+                this.child(div().absolute().top_2().right_2().child(view))
+            })
             // Toast notifications (always on top)
             .child(ToastContainer::new(self.toast_state.clone()))
     }
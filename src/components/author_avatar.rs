@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+use std::path::PathBuf;
+
+/// A round author avatar, showing the author's initial until the image has
+/// loaded or when there is none
+#[derive(IntoElement)]
+pub struct AuthorAvatar {
+    name: String,
+    image: Option<PathBuf>,
+    size: Pixels,
+}
+
+impl AuthorAvatar {
+    pub fn new(name: &str, image: Option<PathBuf>) -> Self {
+        Self {
+            name: name.to_string(),
+            image,
+            size: px(16.0),
+        }
+    }
+
+    pub fn size(mut self, size: Pixels) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+impl RenderOnce for AuthorAvatar {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let initial = self
+            .name
+            .chars()
+            .next()
+            .map(|c| c.to_uppercase().to_string())
+            .unwrap_or_default();
+
+        div()
+            .flex_shrink_0()
+            .size(self.size)
+            .rounded_full()
+            .overflow_hidden()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(theme.surface1)
+            .text_size(self.size * 0.6)
+            .text_color(theme.text)
+            .when(self.image.is_none(), |this| this.child(initial))
+            .when_some(self.image, |this, image| {
+                this.child(img(image).size(self.size).rounded_full())
+            })
+    }
+}
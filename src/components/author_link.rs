@@ -0,0 +1,56 @@
+#![allow(dead_code)]
+
+use crate::actions::FilterByAuthor;
+use crate::git::CommitInfo;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// A commit's author name that filters the history to their commits when
+/// clicked
+#[derive(IntoElement)]
+pub struct AuthorLink {
+    id: ElementId,
+    name: String,
+    /// Matched against author names and emails; the email when there is one
+    query: String,
+}
+
+impl AuthorLink {
+    pub fn new(id: impl Into<ElementId>, commit: &CommitInfo) -> Self {
+        Self::with_author(id, &commit.author, &commit.email)
+    }
+
+    /// Link for an author known only by name and email, such as from blame
+    pub fn with_author(id: impl Into<ElementId>, name: &str, email: &str) -> Self {
+        let query = if email.is_empty() { name } else { email };
+        Self {
+            id: id.into(),
+            name: name.to_string(),
+            query: query.to_string(),
+        }
+    }
+}
+
+impl RenderOnce for AuthorLink {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let query = self.query;
+
+        div()
+            .id(self.id)
+            .cursor_pointer()
+            .hover(|s| s.text_color(theme.blue))
+            .child(self.name)
+            .on_click(move |_event, window, cx| {
+                // Rows under the link have their own click handlers
+                cx.stop_propagation();
+                window.dispatch_action(
+                    Box::new(FilterByAuthor {
+                        author: query.clone(),
+                    }),
+                    cx,
+                );
+            })
+    }
+}
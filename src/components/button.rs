@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+use crate::theme::ActiveTheme;
 use gpui::prelude::*;
 use gpui::*;
 
@@ -55,12 +56,13 @@ impl Button {
 }
 
 impl RenderOnce for Button {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         let (bg, hover_bg, text_color) = match self.variant {
-            ButtonVariant::Primary => (rgb(0x89b4fa), rgb(0xb4befe), rgb(0x1e1e2e)),
-            ButtonVariant::Secondary => (rgb(0x313244), rgb(0x45475a), rgb(0xcdd6f4)),
-            ButtonVariant::Danger => (rgb(0xf38ba8), rgb(0xeba0ac), rgb(0x1e1e2e)),
-            ButtonVariant::Ghost => (rgba(0x00000000), rgb(0x313244), rgb(0xcdd6f4)),
+            ButtonVariant::Primary => (theme.blue, theme.lavender, theme.base),
+            ButtonVariant::Secondary => (theme.surface0, theme.surface1, theme.text),
+            ButtonVariant::Danger => (theme.red, theme.maroon, theme.base),
+            ButtonVariant::Ghost => (rgba(0x00000000), theme.surface0, theme.text),
         };
 
         let disabled = self.disabled || self.loading;
@@ -76,8 +78,8 @@ impl RenderOnce for Button {
             .rounded_md()
             .text_sm()
             .font_weight(FontWeight::MEDIUM)
-            .bg(if disabled { rgb(0x45475a) } else { bg })
-            .text_color(if disabled { rgb(0x6c7086) } else { text_color })
+            .bg(if disabled { theme.surface1 } else { bg })
+            .text_color(if disabled { theme.overlay0 } else { text_color })
             .child(if self.loading {
                 "Loading...".to_string()
             } else {
@@ -1,13 +1,21 @@
 #![allow(dead_code)]
 
+use crate::theme::ActiveTheme;
 use gpui::prelude::*;
 use gpui::*;
+use std::rc::Rc;
+
+type ClickHandler = Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>;
 
 #[derive(IntoElement)]
 pub struct ContextMenuItem {
     pub label: String,
     pub danger: bool,
     pub disabled: bool,
+    /// Element id and handler, for items that do something when clicked
+    on_click: Option<(ElementId, ClickHandler)>,
+    /// A divider line rather than an item
+    separator: bool,
 }
 
 impl ContextMenuItem {
@@ -16,6 +24,16 @@ impl ContextMenuItem {
             label: label.into(),
             danger: false,
             disabled: false,
+            on_click: None,
+            separator: false,
+        }
+    }
+
+    /// Line dividing groups of items
+    pub fn separator() -> Self {
+        Self {
+            separator: true,
+            ..Self::new("")
         }
     }
 
@@ -28,28 +46,45 @@ impl ContextMenuItem {
         self.disabled = disabled;
         self
     }
+
+    pub fn on_click(
+        mut self,
+        id: impl Into<ElementId>,
+        handler: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_click = Some((id.into(), Rc::new(handler)));
+        self
+    }
 }
 
 impl RenderOnce for ContextMenuItem {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        if self.separator {
+            return div().my_1().h_px().bg(theme.surface1).into_any_element();
+        }
+
         let base = div()
             .px_3()
             .py_1()
             .text_sm()
-            .text_color(if self.danger {
-                rgb(0xf38ba8)
-            } else if self.disabled {
-                rgb(0x6c7086)
+            .text_color(if self.disabled {
+                theme.overlay0
+            } else if self.danger {
+                theme.red
             } else {
-                rgb(0xcdd6f4)
+                theme.text
             });
 
-        if self.disabled {
-            base.child(self.label)
-        } else {
-            base.cursor_pointer()
-                .hover(|s| s.bg(rgb(0x45475a)))
+        match self.on_click {
+            Some((id, handler)) if !self.disabled => base
+                .id(id)
+                .cursor_pointer()
+                .hover(|s| s.bg(theme.surface1))
                 .child(self.label)
+                .on_click(move |event, window, cx| handler(event, window, cx))
+                .into_any_element(),
+            _ => base.child(self.label).into_any_element(),
         }
     }
 }
@@ -58,16 +93,28 @@ impl RenderOnce for ContextMenuItem {
 pub struct ContextMenu {
     items: Vec<ContextMenuItem>,
     position: Point<Pixels>,
+    /// Muted line above the items, e.g. the path the menu is for
+    header: Option<String>,
 }
 
 impl ContextMenu {
     pub fn new(items: Vec<ContextMenuItem>, position: Point<Pixels>) -> Self {
-        Self { items, position }
+        Self {
+            items,
+            position,
+            header: None,
+        }
+    }
+
+    pub fn with_header(mut self, header: impl Into<String>) -> Self {
+        self.header = Some(header.into());
+        self
     }
 }
 
 impl RenderOnce for ContextMenu {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         div()
             .absolute()
             .inset_0()
@@ -77,12 +124,28 @@ impl RenderOnce for ContextMenu {
                     .left(self.position.x)
                     .top(self.position.y)
                     .min_w_40()
+                    .max_w(px(280.0))
                     .py_1()
                     .rounded_md()
-                    .bg(rgb(0x313244))
+                    .bg(theme.surface0)
                     .border_1()
-                    .border_color(rgb(0x45475a))
+                    .border_color(theme.surface1)
                     .shadow_lg()
+                    // Clicks on items shouldn't reach the click-outside handler
+                    .on_mouse_down(MouseButton::Left, |_event, _window, cx| {
+                        cx.stop_propagation();
+                    })
+                    .when_some(self.header, |this, header| {
+                        this.child(
+                            div()
+                                .px_3()
+                                .py_1()
+                                .text_xs()
+                                .text_color(theme.overlay0)
+                                .text_ellipsis()
+                                .child(header),
+                        )
+                    })
                     .children(self.items),
             )
     }
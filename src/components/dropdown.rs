@@ -1,26 +1,41 @@
 #![allow(dead_code)]
 
+use crate::theme::ActiveTheme;
 use gpui::prelude::*;
 use gpui::*;
+use std::rc::Rc;
 
 pub struct DropdownOption {
     pub value: String,
     pub label: String,
 }
 
+type ToggleHandler = Rc<dyn Fn(bool, &mut Window, &mut App)>;
+type SelectHandler = Rc<dyn Fn(&str, &mut Window, &mut App)>;
+
+/// Select control. The caller owns the open state and passes it in with
+/// `open`, updating it from `on_toggle`.
 #[derive(IntoElement)]
 pub struct Dropdown {
+    id: SharedString,
     options: Vec<DropdownOption>,
     selected: Option<String>,
     placeholder: SharedString,
+    open: bool,
+    on_toggle: Option<ToggleHandler>,
+    on_select: Option<SelectHandler>,
 }
 
 impl Dropdown {
-    pub fn new(options: Vec<DropdownOption>) -> Self {
+    pub fn new(id: impl Into<SharedString>, options: Vec<DropdownOption>) -> Self {
         Self {
+            id: id.into(),
             options,
             selected: None,
             placeholder: "Select...".into(),
+            open: false,
+            on_toggle: None,
+            on_select: None,
         }
     }
 
@@ -33,24 +48,50 @@ impl Dropdown {
         self.placeholder = placeholder.into();
         self
     }
+
+    pub fn open(mut self, open: bool) -> Self {
+        self.open = open;
+        self
+    }
+
+    /// Called with the requested open state when the trigger is clicked or
+    /// an option is chosen
+    pub fn on_toggle(mut self, handler: impl Fn(bool, &mut Window, &mut App) + 'static) -> Self {
+        self.on_toggle = Some(Rc::new(handler));
+        self
+    }
+
+    /// Called with the value of the chosen option
+    pub fn on_select(mut self, handler: impl Fn(&str, &mut Window, &mut App) + 'static) -> Self {
+        self.on_select = Some(Rc::new(handler));
+        self
+    }
 }
 
 impl RenderOnce for Dropdown {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         let selected_label = self
             .selected
             .as_ref()
             .and_then(|v| self.options.iter().find(|o| &o.value == v))
             .map(|o| o.label.clone())
             .unwrap_or_else(|| self.placeholder.to_string());
+        let open = self.open;
+        let on_toggle = self.on_toggle;
+        let on_select = self.on_select;
+        let selected = self.selected;
 
         div()
             .relative()
             .w_full()
+            .flex()
+            .flex_col()
+            .gap_1()
             // Trigger
             .child(
                 div()
-                    .id("dropdown-trigger")
+                    .id(ElementId::Name(self.id.clone()))
                     .flex()
                     .items_center()
                     .justify_between()
@@ -58,22 +99,67 @@ impl RenderOnce for Dropdown {
                     .px_3()
                     .py_2()
                     .rounded_md()
-                    .bg(rgb(0x313244))
+                    .bg(theme.surface0)
                     .border_1()
-                    .border_color(rgb(0x45475a))
+                    .border_color(if open { theme.blue } else { theme.surface1 })
                     .cursor_pointer()
-                    .hover(|s| s.border_color(rgb(0x6c7086)))
+                    .hover(|s| s.border_color(theme.overlay0))
                     .child(
                         div()
                             .text_sm()
-                            .text_color(if self.selected.is_some() {
-                                rgb(0xcdd6f4)
+                            .text_color(if selected.is_some() {
+                                theme.text
                             } else {
-                                rgb(0x6c7086)
+                                theme.overlay0
                             })
                             .child(selected_label),
                     )
-                    .child(div().text_xs().text_color(rgb(0x6c7086)).child("▼")),
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.overlay0)
+                            .child(if open { "▲" } else { "▼" }),
+                    )
+                    .when_some(on_toggle.clone(), |this, on_toggle| {
+                        this.on_click(move |_event, window, cx| on_toggle(!open, window, cx))
+                    }),
             )
+            // Options, shown inline below the trigger while open
+            .when(open, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .py_1()
+                        .rounded_md()
+                        .bg(theme.surface0)
+                        .border_1()
+                        .border_color(theme.surface1)
+                        .children(self.options.into_iter().enumerate().map(|(ix, option)| {
+                            let is_selected = selected.as_ref() == Some(&option.value);
+                            let on_toggle = on_toggle.clone();
+                            let on_select = on_select.clone();
+                            let value = option.value;
+
+                            div()
+                                .id(ElementId::Name(format!("{}-option-{}", self.id, ix).into()))
+                                .px_3()
+                                .py_1()
+                                .text_sm()
+                                .text_color(if is_selected { theme.blue } else { theme.text })
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface1))
+                                .child(option.label)
+                                .on_click(move |_event, window, cx| {
+                                    if let Some(on_select) = &on_select {
+                                        on_select(&value, window, cx);
+                                    }
+                                    if let Some(on_toggle) = &on_toggle {
+                                        on_toggle(false, window, cx);
+                                    }
+                                })
+                        })),
+                )
+            })
     }
 }
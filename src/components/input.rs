@@ -4,11 +4,15 @@ use crate::actions::{
     Backspace, Copy, Cut, Delete, End, Enter, Home, Left, Paste, Right, SelectAll, SelectLeft,
     SelectRight, ShowCharacterPalette,
 };
+use crate::theme::ActiveTheme;
 use gpui::prelude::*;
 use gpui::*;
 use std::ops::Range;
 use unicode_segmentation::UnicodeSegmentation;
 
+/// Shown in place of each character of a password input
+const PASSWORD_MASK: char = '•';
+
 /// Event emitted when text input content changes
 #[derive(Clone, Debug)]
 pub struct TextInputChanged(pub SharedString);
@@ -27,6 +31,8 @@ pub struct TextInputView {
     last_bounds: Option<Bounds<Pixels>>,
     is_selecting: bool,
     multiline: bool,
+    /// Show a dot per character instead of the content
+    password: bool,
     on_change: Option<Box<dyn Fn(&str, &mut Window, &mut App) + 'static>>,
     on_submit: Option<Box<dyn Fn(&str, &mut Window, &mut App) + 'static>>,
 }
@@ -44,6 +50,7 @@ impl TextInputView {
             last_bounds: None,
             is_selecting: false,
             multiline: false,
+            password: false,
             on_change: None,
             on_submit: None,
         }
@@ -67,6 +74,12 @@ impl TextInputView {
         self
     }
 
+    /// Mask the content, e.g. for tokens. Copying and cutting are disabled.
+    pub fn password(mut self, password: bool) -> Self {
+        self.password = password;
+        self
+    }
+
     pub fn on_change(mut self, handler: impl Fn(&str, &mut Window, &mut App) + 'static) -> Self {
         self.on_change = Some(Box::new(handler));
         self
@@ -81,6 +94,15 @@ impl TextInputView {
         &self.content
     }
 
+    pub fn set_placeholder(
+        &mut self,
+        placeholder: impl Into<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        self.placeholder = placeholder.into();
+        cx.notify();
+    }
+
     pub fn set_content(&mut self, content: impl Into<SharedString>, cx: &mut Context<Self>) {
         let content: SharedString = content.into();
         let len = content.len();
@@ -170,6 +192,28 @@ impl TextInputView {
         self.offset_from_utf16(range_utf16.start)..self.offset_from_utf16(range_utf16.end)
     }
 
+    /// Offset into the displayed text of an offset into the content, which
+    /// differ when the content is masked
+    fn display_offset(&self, offset: usize) -> usize {
+        if self.password {
+            self.content[..offset].chars().count() * PASSWORD_MASK.len_utf8()
+        } else {
+            offset
+        }
+    }
+
+    fn content_offset(&self, display_offset: usize) -> usize {
+        if self.password {
+            let chars = display_offset / PASSWORD_MASK.len_utf8();
+            self.content
+                .char_indices()
+                .nth(chars)
+                .map_or(self.content.len(), |(ix, _)| ix)
+        } else {
+            display_offset
+        }
+    }
+
     fn index_for_mouse_position(&self, position: Point<Pixels>) -> usize {
         if self.content.is_empty() {
             return 0;
@@ -185,7 +229,7 @@ impl TextInputView {
         if position.y > bounds.bottom() {
             return self.content.len();
         }
-        line.closest_index_for_x(position.x - bounds.left())
+        self.content_offset(line.closest_index_for_x(position.x - bounds.left()))
     }
 
     fn notify_change(&self, window: &mut Window, cx: &mut App) {
@@ -268,7 +312,7 @@ impl TextInputView {
     }
 
     fn handle_copy(&mut self, _: &Copy, _window: &mut Window, cx: &mut Context<Self>) {
-        if !self.selected_range.is_empty() {
+        if !self.selected_range.is_empty() && !self.password {
             cx.write_to_clipboard(ClipboardItem::new_string(
                 self.content[self.selected_range.clone()].to_string(),
             ));
@@ -276,7 +320,7 @@ impl TextInputView {
     }
 
     fn handle_cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
-        if !self.selected_range.is_empty() {
+        if !self.selected_range.is_empty() && !self.password {
             cx.write_to_clipboard(ClipboardItem::new_string(
                 self.content[self.selected_range.clone()].to_string(),
             ));
@@ -469,6 +513,7 @@ impl Focusable for TextInputView {
 
 impl Render for TextInputView {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
         let is_focused = self.focus_handle.is_focused(_window);
         let height = if self.multiline { px(80.) } else { px(32.) };
 
@@ -500,12 +545,12 @@ impl Render for TextInputView {
             .px_3()
             .py_2()
             .rounded_md()
-            .bg(rgb(0x313244))
+            .bg(theme.surface0)
             .border_1()
             .border_color(if is_focused {
-                rgb(0x89b4fa)
+                theme.blue
             } else {
-                rgb(0x45475a)
+                theme.surface1
             })
             .text_sm()
             .child(TextInputElement {
@@ -567,14 +612,23 @@ impl Element for TextInputElement {
         window: &mut Window,
         cx: &mut App,
     ) -> Self::PrepaintState {
+        let theme = *cx.theme();
         let input = self.input.read(cx);
         let content = input.content.clone();
-        let selected_range = input.selected_range.clone();
-        let cursor = input.cursor_offset();
+        let selected_range = input.display_offset(input.selected_range.start)
+            ..input.display_offset(input.selected_range.end);
+        let marked_range = input
+            .marked_range
+            .as_ref()
+            .map(|range| input.display_offset(range.start)..input.display_offset(range.end));
+        let cursor = input.display_offset(input.cursor_offset());
         let style = window.text_style();
 
         let (display_text, text_color): (SharedString, Hsla) = if content.is_empty() {
-            (input.placeholder.clone(), hsla(0., 0., 0.5, 1.0))
+            (input.placeholder.clone(), theme.overlay0.into())
+        } else if input.password {
+            let masked = PASSWORD_MASK.to_string().repeat(content.chars().count());
+            (masked.into(), style.color)
         } else {
             (content, style.color)
         };
@@ -588,7 +642,7 @@ impl Element for TextInputElement {
             strikethrough: None,
         };
 
-        let runs = if let Some(marked_range) = input.marked_range.as_ref() {
+        let runs = if let Some(marked_range) = marked_range.as_ref() {
             vec![
                 TextRun {
                     len: marked_range.start,
@@ -629,7 +683,7 @@ impl Element for TextInputElement {
                         point(bounds.left() + cursor_pos, bounds.top()),
                         size(px(2.), bounds.bottom() - bounds.top()),
                     ),
-                    rgb(0x89b4fa),
+                    theme.blue,
                 )),
             )
         } else {
@@ -645,7 +699,7 @@ impl Element for TextInputElement {
                             bounds.bottom(),
                         ),
                     ),
-                    rgba(0x89b4fa40),
+                    theme.selection,
                 )),
                 None,
             )
@@ -739,7 +793,8 @@ impl TextInput {
 }
 
 impl RenderOnce for TextInput {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         let display_value = if self.password && !self.value.is_empty() {
             "•".repeat(self.value.len())
         } else if self.value.is_empty() {
@@ -758,17 +813,17 @@ impl RenderOnce for TextInput {
             .px_3()
             .py_2()
             .rounded_md()
-            .bg(rgb(0x313244))
+            .bg(theme.surface0)
             .border_1()
-            .border_color(rgb(0x45475a))
+            .border_color(theme.surface1)
             .text_sm()
             .text_color(if is_placeholder {
-                rgb(0x6c7086)
+                theme.overlay0
             } else {
-                rgb(0xcdd6f4)
+                theme.text
             })
-            .hover(|s| s.border_color(rgb(0x6c7086)))
-            .focus(|s| s.border_color(rgb(0x89b4fa)));
+            .hover(|s| s.border_color(theme.overlay0))
+            .focus(|s| s.border_color(theme.blue));
 
         if self.multiline {
             base.h_20().child(display_value)
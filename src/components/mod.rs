@@ -1,9 +1,15 @@
+pub mod author_avatar;
+pub mod author_link;
 pub mod button;
 pub mod context_menu;
 pub mod dropdown;
 pub mod input;
 pub mod modal;
+pub mod signature_badge;
 pub mod toast;
 
+pub use author_avatar::*;
+pub use author_link::*;
 pub use input::*;
+pub use signature_badge::*;
 pub use toast::*;
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+use crate::theme::ActiveTheme;
 use gpui::prelude::*;
 use gpui::*;
 
@@ -19,14 +20,15 @@ impl Modal {
 }
 
 impl RenderOnce for Modal {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         div()
             .absolute()
             .inset_0()
             .flex()
             .items_center()
             .justify_center()
-            .bg(rgba(0x00000088))
+            .bg(theme.backdrop)
             .child(
                 div()
                     .flex()
@@ -35,9 +37,9 @@ impl RenderOnce for Modal {
                     .w(px(500.0))
                     .h(px(400.0))
                     .rounded_lg()
-                    .bg(rgb(0x1e1e2e))
+                    .bg(theme.base)
                     .border_1()
-                    .border_color(rgb(0x313244))
+                    .border_color(theme.surface0)
                     .overflow_hidden()
                     // Header
                     .child(
@@ -47,14 +49,14 @@ impl RenderOnce for Modal {
                             .justify_between()
                             .px_4()
                             .py_3()
-                            .bg(rgb(0x181825))
+                            .bg(theme.mantle)
                             .border_b_1()
-                            .border_color(rgb(0x313244))
+                            .border_color(theme.surface0)
                             .child(
                                 div()
                                     .text_sm()
                                     .font_weight(FontWeight::SEMIBOLD)
-                                    .text_color(rgb(0xcdd6f4))
+                                    .text_color(theme.text)
                                     .child(self.title.to_string()),
                             )
                             .child(
@@ -64,9 +66,9 @@ impl RenderOnce for Modal {
                                     .py_1()
                                     .rounded_md()
                                     .text_sm()
-                                    .text_color(rgb(0x9399b2))
+                                    .text_color(theme.overlay2)
                                     .cursor_pointer()
-                                    .hover(|s| s.bg(rgb(0x313244)).text_color(rgb(0xcdd6f4)))
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
                                     .child("×"),
                             ),
                     )
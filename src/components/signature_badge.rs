@@ -0,0 +1,105 @@
+#![allow(dead_code)]
+
+use crate::git::{CommitSignature, SignatureTrust};
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// A verified, unverified or unknown badge for a signed commit, with the
+/// signer and key in its tooltip. Unsigned commits render nothing.
+#[derive(IntoElement)]
+pub struct SignatureBadge {
+    id: ElementId,
+    signature: CommitSignature,
+}
+
+impl SignatureBadge {
+    pub fn new(id: impl Into<ElementId>, signature: CommitSignature) -> Self {
+        Self {
+            id: id.into(),
+            signature,
+        }
+    }
+}
+
+impl RenderOnce for SignatureBadge {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let badge = div().id(self.id);
+        let Some(trust) = self.signature.status.trust() else {
+            return badge;
+        };
+        let color = match trust {
+            SignatureTrust::Verified => theme.green,
+            SignatureTrust::Unverified => theme.red,
+            SignatureTrust::Unknown => theme.yellow,
+        };
+        let signature = self.signature;
+
+        badge
+            .flex_shrink_0()
+            .px_1()
+            .rounded_sm()
+            .border_1()
+            .border_color(color)
+            .text_xs()
+            .text_color(color)
+            .child(trust.label())
+            .tooltip(move |_window, cx| {
+                let signature = signature.clone();
+                cx.new(|_| SignatureTooltip { signature }).into()
+            })
+    }
+}
+
+/// What the git CLI reported about a signature
+struct SignatureTooltip {
+    signature: CommitSignature,
+}
+
+impl Render for SignatureTooltip {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let signature = &self.signature;
+        // The fingerprint identifies the key fully; SSH keys only have one
+        let key = signature.fingerprint.as_ref().or(signature.key_id.as_ref());
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_px()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .bg(theme.mantle)
+            .border_1()
+            .border_color(theme.surface1)
+            .text_xs()
+            .text_color(theme.text)
+            .child(
+                div()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child(capitalize(signature.status.label())),
+            )
+            .child(match &signature.signer {
+                Some(signer) => format!("Signed by {}", signer),
+                None => "Unknown signer".to_string(),
+            })
+            .when_some(key.cloned(), |this, key| {
+                this.child(
+                    div()
+                        .font_family("monospace")
+                        .text_color(theme.overlay0)
+                        .child(key),
+                )
+            })
+    }
+}
+
+fn capitalize(label: &str) -> String {
+    let mut chars = label.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use crate::state::{ToastMessage, ToastState, ToastType};
+use crate::theme::ActiveTheme;
 use gpui::prelude::*;
 use gpui::*;
 
@@ -21,21 +22,47 @@ impl Toast {
 }
 
 impl RenderOnce for Toast {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         let (bg, border, icon) = match self.message.toast_type {
-            ToastType::Success => (rgb(0x1a3d2e), rgb(0xa6e3a1), "✓"),
-            ToastType::Error => (rgb(0x3d1a1a), rgb(0xf38ba8), "✕"),
-            ToastType::Warning => (rgb(0x3d3d1a), rgb(0xf9e2af), "⚠"),
-            ToastType::Info => (rgb(0x1a2a3d), rgb(0x89b4fa), "ℹ"),
+            ToastType::Success => (theme.green_bg, theme.green, "✓"),
+            ToastType::Error => (theme.red_bg, theme.red, "✕"),
+            ToastType::Warning => (theme.yellow_bg, theme.yellow, "⚠"),
+            ToastType::Info => (theme.blue_bg, theme.blue, "ℹ"),
         };
 
         let id = self.message.id;
+        let expanded = self.message.expanded;
         let toast_state = self.toast_state.clone();
+        let toast_state_details = self.toast_state.clone();
+
+        let actions = self.message.actions.iter().enumerate().map(|(ix, action)| {
+            let action = action.clone();
+            let toast_state = self.toast_state.clone();
+            div()
+                .id(ElementId::Name(format!("toast-action-{}-{}", id, ix).into()))
+                .px_2()
+                .py_1()
+                .rounded_md()
+                .bg(theme.surface0)
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(border)
+                .cursor_pointer()
+                .hover(|s| s.bg(theme.surface1))
+                .child(action.label.clone())
+                .on_click(move |_event, window, cx| {
+                    (action.handler)(window, cx);
+                    toast_state.update(cx, |state, cx| {
+                        state.dismiss(id, cx);
+                    });
+                })
+        });
 
         div()
             .id(ElementId::Name(format!("toast-{}", id).into()))
             .flex()
-            .items_center()
+            .items_start()
             .gap_3()
             .px_4()
             .py_3()
@@ -50,9 +77,67 @@ impl RenderOnce for Toast {
             .child(
                 div()
                     .flex_1()
-                    .text_sm()
-                    .text_color(rgb(0xcdd6f4))
-                    .child(self.message.message.clone()),
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .child(self.message.message.clone()),
+                    )
+                    .when_some(self.message.details.clone(), |this, details| {
+                        this.child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_3()
+                                .child(
+                                    div()
+                                        .id(ElementId::Name(
+                                            format!("toast-toggle-details-{}", id).into(),
+                                        ))
+                                        .text_xs()
+                                        .text_color(theme.overlay2)
+                                        .underline()
+                                        .cursor_pointer()
+                                        .hover(|s| s.text_color(theme.text))
+                                        .child(if expanded {
+                                            "Hide details"
+                                        } else {
+                                            "Show details"
+                                        })
+                                        .on_click(move |_event, _window, cx| {
+                                            toast_state_details.update(cx, |state, cx| {
+                                                state.toggle_details(id, cx);
+                                            });
+                                        }),
+                                )
+                                .child(CopyDetailsButton::new(
+                                    ElementId::Name(format!("toast-copy-details-{}", id).into()),
+                                    details.clone(),
+                                )),
+                        )
+                        .when(expanded, |this| {
+                            this.child(
+                                div()
+                                    .id(ElementId::Name(format!("toast-details-{}", id).into()))
+                                    .max_h(px(160.0))
+                                    .overflow_y_scroll()
+                                    .p_2()
+                                    .rounded_md()
+                                    .bg(theme.mantle)
+                                    .font_family("monospace")
+                                    .text_xs()
+                                    .text_color(theme.overlay2)
+                                    .child(details),
+                            )
+                        })
+                    })
+                    // Action buttons
+                    .when(!self.message.actions.is_empty(), |this| {
+                        this.child(div().flex().flex_wrap().gap_2().pt_1().children(actions))
+                    }),
             )
             // Dismiss button
             .child(
@@ -60,9 +145,9 @@ impl RenderOnce for Toast {
                     .id(ElementId::Name(format!("toast-dismiss-{}", id).into()))
                     .px_1()
                     .text_sm()
-                    .text_color(rgb(0x6c7086))
+                    .text_color(theme.overlay0)
                     .cursor_pointer()
-                    .hover(|s| s.text_color(rgb(0xcdd6f4)))
+                    .hover(|s| s.text_color(theme.text))
                     .child("×")
                     .on_click(move |_event, _window, cx| {
                         toast_state.update(cx, |state, cx| {
@@ -73,6 +158,41 @@ impl RenderOnce for Toast {
     }
 }
 
+/// Copies an error report to the clipboard for bug reports
+#[derive(IntoElement)]
+pub struct CopyDetailsButton {
+    id: ElementId,
+    report: String,
+}
+
+impl CopyDetailsButton {
+    pub fn new(id: impl Into<ElementId>, report: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            report: report.into(),
+        }
+    }
+}
+
+impl RenderOnce for CopyDetailsButton {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let report = self.report;
+
+        div()
+            .id(self.id)
+            .text_xs()
+            .text_color(theme.overlay2)
+            .underline()
+            .cursor_pointer()
+            .hover(|s| s.text_color(theme.text))
+            .child("Copy details")
+            .on_click(move |_event, _window, cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(report.clone()));
+            })
+    }
+}
+
 /// Container for all toast notifications
 #[derive(IntoElement)]
 pub struct ToastContainer {
@@ -95,12 +215,18 @@ impl RenderOnce for ToastContainer {
             .cloned()
             .collect();
 
+        let position = self.toast_state.read(cx).position;
+
         div()
             .absolute()
-            .bottom_4()
-            .right_4()
+            .when(position.is_top(), |this| this.top_4())
+            .when(!position.is_top(), |this| this.bottom_4())
+            .when(position.is_left(), |this| this.left_4())
+            .when(!position.is_left(), |this| this.right_4())
             .flex()
-            .flex_col()
+            // Newest toast sits nearest the corner
+            .when(position.is_top(), |this| this.flex_col_reverse())
+            .when(!position.is_top(), |this| this.flex_col())
             .gap_2()
             .w_80()
             .children(
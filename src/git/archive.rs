@@ -0,0 +1,243 @@
+#![allow(dead_code)]
+
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use git2::{ObjectType, Oid, Repository, Tree, TreeWalkMode, TreeWalkResult};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// File mode git uses for symlinks
+const MODE_SYMLINK: i32 = 0o120000;
+/// File mode git uses for executables
+const MODE_EXECUTABLE: i32 = 0o100755;
+
+/// Container a snapshot is written in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Format matching a file name's extension, zip unless it's a tarball
+    pub fn from_path(path: &Path) -> Self {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            ArchiveFormat::TarGz
+        } else {
+            ArchiveFormat::Zip
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+/// What a tree entry is written as
+enum EntryKind {
+    Dir,
+    File { executable: bool },
+    Symlink,
+}
+
+/// Tree entry of a snapshot, in walk order
+struct SnapshotEntry {
+    path: String,
+    oid: Oid,
+    kind: EntryKind,
+}
+
+/// The tree of a commit, written out as an archive like `git archive`
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    /// Commit, branch or tag the tree is taken from
+    pub revision: String,
+    /// Directory the files are put in inside the archive, e.g.
+    /// "project-1a2b3c4"; empty puts them at the top level
+    pub prefix: String,
+}
+
+impl Snapshot {
+    pub fn new(revision: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self {
+            revision: revision.into(),
+            prefix: prefix.into(),
+        }
+    }
+
+    /// Write the snapshot to `path` in the format its extension names,
+    /// returning the number of files written. Blobs are read one at a time
+    /// so the tree is never held in memory; a partly written archive is
+    /// removed on failure.
+    pub fn write(&self, repo: &Repository, path: &Path) -> Result<usize> {
+        let result = self.write_to(repo, path, ArchiveFormat::from_path(path));
+        if result.is_err() {
+            let _ = fs::remove_file(path);
+        }
+        result
+    }
+
+    fn write_to(&self, repo: &Repository, path: &Path, format: ArchiveFormat) -> Result<usize> {
+        let commit = repo
+            .revparse_single(&self.revision)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("resolving {}", self.revision))?;
+        let mtime = commit.time().seconds();
+        let entries = self.entries(&commit.tree()?)?;
+        let file = BufWriter::new(
+            File::create(path).with_context(|| format!("creating {}", path.display()))?,
+        );
+
+        match format {
+            ArchiveFormat::Zip => write_zip(repo, &entries, mtime, file),
+            ArchiveFormat::TarGz => write_tar_gz(repo, &entries, mtime, file),
+        }
+    }
+
+    /// Directories, files and symlinks of a tree, with the prefix applied.
+    /// Submodules are left as empty directories, as `git archive` does.
+    fn entries(&self, tree: &Tree) -> Result<Vec<SnapshotEntry>> {
+        let prefix = match self.prefix.trim_matches('/') {
+            "" => String::new(),
+            prefix => format!("{}/", prefix),
+        };
+        let mut entries = Vec::new();
+        if !prefix.is_empty() {
+            entries.push(SnapshotEntry {
+                path: prefix.clone(),
+                oid: tree.id(),
+                kind: EntryKind::Dir,
+            });
+        }
+
+        tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+            let Some(name) = entry.name() else {
+                return TreeWalkResult::Skip;
+            };
+            let path = format!("{}{}{}", prefix, dir, name);
+            let kind = match entry.kind() {
+                Some(ObjectType::Tree) | Some(ObjectType::Commit) => EntryKind::Dir,
+                Some(ObjectType::Blob) if entry.filemode() == MODE_SYMLINK => EntryKind::Symlink,
+                Some(ObjectType::Blob) => EntryKind::File {
+                    executable: entry.filemode() == MODE_EXECUTABLE,
+                },
+                _ => return TreeWalkResult::Skip,
+            };
+            let path = match kind {
+                EntryKind::Dir => format!("{}/", path),
+                _ => path,
+            };
+            entries.push(SnapshotEntry {
+                path,
+                oid: entry.id(),
+                kind,
+            });
+            TreeWalkResult::Ok
+        })?;
+        Ok(entries)
+    }
+}
+
+fn write_zip(
+    repo: &Repository,
+    entries: &[SnapshotEntry],
+    mtime: i64,
+    file: impl Write + io::Seek,
+) -> Result<usize> {
+    let mut zip = ZipWriter::new(file);
+    let mut options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    if let Some(time) = DateTime::<Utc>::from_timestamp(mtime, 0).and_then(zip_time) {
+        options = options.last_modified_time(time);
+    }
+    let mut files = 0;
+
+    for entry in entries {
+        match entry.kind {
+            EntryKind::Dir => {
+                zip.add_directory(entry.path.as_str(), options.unix_permissions(0o755))?;
+            }
+            EntryKind::Symlink => {
+                let blob = repo.find_blob(entry.oid)?;
+                let target = String::from_utf8_lossy(blob.content());
+                zip.add_symlink(entry.path.as_str(), target, options)?;
+                files += 1;
+            }
+            EntryKind::File { executable } => {
+                let mode = if executable { 0o755 } else { 0o644 };
+                zip.start_file(entry.path.as_str(), options.unix_permissions(mode))?;
+                zip.write_all(repo.find_blob(entry.oid)?.content())?;
+                files += 1;
+            }
+        }
+    }
+    zip.finish()?.flush()?;
+    Ok(files)
+}
+
+/// Zip timestamps only cover the years 1980 to 2107; other commits keep the
+/// default
+fn zip_time(time: DateTime<Utc>) -> Option<zip::DateTime> {
+    zip::DateTime::from_date_and_time(
+        u16::try_from(time.year()).ok()?,
+        time.month() as u8,
+        time.day() as u8,
+        time.hour() as u8,
+        time.minute() as u8,
+        time.second() as u8,
+    )
+    .ok()
+}
+
+fn write_tar_gz(
+    repo: &Repository,
+    entries: &[SnapshotEntry],
+    mtime: i64,
+    file: impl Write,
+) -> Result<usize> {
+    let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let mut files = 0;
+
+    for entry in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_mtime(mtime.max(0) as u64);
+        match entry.kind {
+            EntryKind::Dir => {
+                header.set_entry_type(tar::EntryType::Directory);
+                header.set_mode(0o755);
+                header.set_size(0);
+                tar.append_data(&mut header, &entry.path, io::empty())?;
+            }
+            EntryKind::Symlink => {
+                let blob = repo.find_blob(entry.oid)?;
+                let target = String::from_utf8_lossy(blob.content()).into_owned();
+                header.set_entry_type(tar::EntryType::Symlink);
+                header.set_mode(0o777);
+                header.set_size(0);
+                tar.append_link(&mut header, &entry.path, target)?;
+                files += 1;
+            }
+            EntryKind::File { executable } => {
+                let blob = repo.find_blob(entry.oid)?;
+                header.set_entry_type(tar::EntryType::Regular);
+                header.set_mode(if executable { 0o755 } else { 0o644 });
+                header.set_size(blob.size() as u64);
+                tar.append_data(&mut header, &entry.path, blob.content())?;
+                files += 1;
+            }
+        }
+    }
+    tar.into_inner()?.finish()?.flush()?;
+    Ok(files)
+}
@@ -0,0 +1,78 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::Repository;
+use std::collections::HashMap;
+
+use super::GraphFilter;
+
+/// Most recent commits of an author whose changed files are counted; older
+/// commits only add to the commit count
+const PATH_SCAN_LIMIT: usize = 500;
+
+/// How much an author contributed to the history the graph shows: their
+/// commit count and the files they changed most often
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AuthorSummary {
+    /// Name or email filtered by, as typed or clicked
+    pub query: String,
+    /// Name and email on the author's most recent commit
+    pub name: String,
+    pub email: String,
+    pub commit_count: usize,
+    /// Files changed most often, with the number of commits changing each
+    pub top_paths: Vec<(String, usize)>,
+}
+
+impl AuthorSummary {
+    /// Summarize the commits whose author name or email contains `query`,
+    /// keeping the `top` most touched files
+    pub fn read(repo: &Repository, query: &str, top: usize) -> Result<Self> {
+        let filter = GraphFilter {
+            author: Some(query.to_string()),
+            ..GraphFilter::default()
+        };
+        let mut summary = Self {
+            query: query.to_string(),
+            ..Self::default()
+        };
+        let mut path_counts: HashMap<String, usize> = HashMap::new();
+
+        for oid in filter.revwalk(repo)? {
+            let commit = repo.find_commit(oid?)?;
+            if !filter.matches(repo, &commit)? {
+                continue;
+            }
+            if summary.commit_count == 0 {
+                let author = commit.author();
+                summary.name = author.name().unwrap_or_default().to_string();
+                summary.email = author.email().unwrap_or_default().to_string();
+            }
+            summary.commit_count += 1;
+            if summary.commit_count > PATH_SCAN_LIMIT {
+                continue;
+            }
+
+            let tree = commit.tree()?;
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+            for delta in diff.deltas() {
+                let path = delta.new_file().path().or_else(|| delta.old_file().path());
+                if let Some(path) = path {
+                    *path_counts
+                        .entry(path.to_string_lossy().to_string())
+                        .or_default() += 1;
+                }
+            }
+        }
+
+        let mut top_paths: Vec<(String, usize)> = path_counts.into_iter().collect();
+        top_paths.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_paths.truncate(top);
+        summary.top_paths = top_paths;
+        Ok(summary)
+    }
+}
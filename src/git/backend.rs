@@ -0,0 +1,142 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::Repository;
+use std::path::Path;
+
+use super::{
+    BranchInfo, CommitGraphData, DiffDisplayOptions, FileDiff, FileStatus, GraphFilter,
+    RefMapCache, RepositoryInfo, TagInfo,
+};
+
+/// Opens repositories for one version control implementation.
+///
+/// `GitState` reads status, history, diffs and refs through this, so another
+/// implementation (e.g. the `git` CLI, or a jj or Sapling bridge) can be
+/// swapped in without touching the state or the views. Operations that write
+/// to the repository still go through git2 directly.
+pub trait VcsBackend: Send + Sync {
+    /// Short name for logs and error reports
+    fn name(&self) -> &'static str;
+
+    fn open(&self, path: &Path) -> Result<Box<dyn RepositoryBackend>>;
+}
+
+/// Read access to an open repository
+pub trait RepositoryBackend {
+    fn info(&self) -> Result<RepositoryInfo>;
+
+    /// Changed files in the index and working tree
+    fn status(&self) -> Result<Vec<FileStatus>>;
+
+    /// `limit` commits of history matching `filter`, starting `offset`
+    /// matching commits from the top. `refs` keeps the branch and tag labels
+    /// between calls, so refreshes and further pages don't rebuild them.
+    fn commit_graph(
+        &self,
+        filter: &GraphFilter,
+        limit: usize,
+        offset: usize,
+        refs: &RefMapCache,
+    ) -> Result<CommitGraphData>;
+
+    /// Staged and unstaged changes of a file against HEAD. Files larger than
+    /// `max_size` bytes come back as a summary without lines.
+    fn file_diff(&self, path: &str, max_size: u64, options: DiffDisplayOptions) -> Result<FileDiff>;
+
+    /// Changes of a file not yet staged
+    fn unstaged_diff(
+        &self,
+        path: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> Result<FileDiff>;
+
+    /// Files changed by a commit against its first parent
+    fn commit_diff(
+        &self,
+        sha: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> Result<Vec<FileDiff>>;
+
+    fn branches(&self) -> Result<Vec<BranchInfo>>;
+
+    fn tags(&self) -> Result<Vec<TagInfo>>;
+}
+
+/// The built-in backend, using libgit2
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Git2Backend;
+
+impl VcsBackend for Git2Backend {
+    fn name(&self) -> &'static str {
+        "libgit2"
+    }
+
+    fn open(&self, path: &Path) -> Result<Box<dyn RepositoryBackend>> {
+        Ok(Box::new(Git2Repository {
+            repo: Repository::open(path)?,
+        }))
+    }
+}
+
+pub struct Git2Repository {
+    repo: Repository,
+}
+
+impl RepositoryBackend for Git2Repository {
+    fn info(&self) -> Result<RepositoryInfo> {
+        RepositoryInfo::from_repo(&self.repo)
+    }
+
+    fn status(&self) -> Result<Vec<FileStatus>> {
+        FileStatus::get_all(&self.repo)
+    }
+
+    fn commit_graph(
+        &self,
+        filter: &GraphFilter,
+        limit: usize,
+        offset: usize,
+        refs: &RefMapCache,
+    ) -> Result<CommitGraphData> {
+        let refs = refs.get(&self.repo)?;
+        CommitGraphData::build_with_refs(&self.repo, filter, limit, offset, &refs)
+    }
+
+    fn file_diff(
+        &self,
+        path: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> Result<FileDiff> {
+        FileDiff::get_file_diff(&self.repo, path, max_size, options)
+    }
+
+    fn unstaged_diff(
+        &self,
+        path: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> Result<FileDiff> {
+        FileDiff::get_unstaged_diff(&self.repo, path, max_size, options)
+    }
+
+    fn commit_diff(
+        &self,
+        sha: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> Result<Vec<FileDiff>> {
+        FileDiff::get_commit_diff(&self.repo, sha, max_size, options)
+    }
+
+    fn branches(&self) -> Result<Vec<BranchInfo>> {
+        BranchInfo::get_all(&self.repo)
+    }
+
+    fn tags(&self) -> Result<Vec<TagInfo>> {
+        TagInfo::get_all(&self.repo)
+    }
+}
@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+
+use git2::Repository;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes checked for a NUL when telling binary files from text, as git does
+pub const BINARY_CHECK_SIZE: usize = 8000;
+
+/// Bytes of each side of a binary file shown in the hex preview
+pub const HEX_PREVIEW_BYTES: usize = 512;
+
+/// Bytes per row of a hex dump
+const HEX_ROW_BYTES: usize = 16;
+
+/// Content git would treat as binary: it has a NUL in its first
+/// `BINARY_CHECK_SIZE` bytes
+pub fn is_binary_content(content: &[u8]) -> bool {
+    content[..content.len().min(BINARY_CHECK_SIZE)].contains(&0)
+}
+
+/// Whether a file on disk is binary, reading only its start
+pub fn is_binary_file(path: &Path) -> bool {
+    let mut start = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(BINARY_CHECK_SIZE as u64).read_to_end(&mut start))
+        .is_ok_and(|_| is_binary_content(&start))
+}
+
+/// Whether a changed file is binary: its working tree copy, or the version
+/// in HEAD when it was deleted
+pub fn is_binary_path(repo: &Repository, path: &str) -> bool {
+    if let Some(full_path) = repo.workdir().map(|dir| dir.join(path)) {
+        if full_path.is_file() {
+            return is_binary_file(&full_path);
+        }
+    }
+    repo.head()
+        .and_then(|head| head.peel_to_tree())
+        .and_then(|tree| tree.get_path(Path::new(path)))
+        .and_then(|entry| repo.find_blob(entry.id()))
+        .is_ok_and(|blob| blob.is_binary())
+}
+
+/// Start of both sides of a binary file, as hex dump rows
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HexPreview {
+    /// Empty when the file was added
+    pub old: Vec<String>,
+    /// Empty when the file was deleted
+    pub new: Vec<String>,
+}
+
+impl HexPreview {
+    /// Preview of the first `HEX_PREVIEW_BYTES` of each side
+    pub fn new(old: &[u8], new: &[u8]) -> Self {
+        Self {
+            old: hex_dump(&old[..old.len().min(HEX_PREVIEW_BYTES)]),
+            new: hex_dump(&new[..new.len().min(HEX_PREVIEW_BYTES)]),
+        }
+    }
+}
+
+/// Rows of offset, hex bytes and printable ASCII, like `xxd`:
+/// `00000000  89 50 4e 47 0d 0a 1a 0a  ...  |.PNG....|`
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(HEX_ROW_BYTES)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let mut hex = String::new();
+            for ix in 0..HEX_ROW_BYTES {
+                match chunk.get(ix) {
+                    Some(byte) => hex.push_str(&format!("{:02x} ", byte)),
+                    None => hex.push_str("   "),
+                }
+                // An extra space splits the row in two groups of eight
+                if ix == HEX_ROW_BYTES / 2 - 1 {
+                    hex.push(' ');
+                }
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|&byte| {
+                    if byte.is_ascii_graphic() || byte == b' ' {
+                        byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:08x}  {} |{}|", row * HEX_ROW_BYTES, hex, ascii)
+        })
+        .collect()
+}
@@ -0,0 +1,100 @@
+#![allow(dead_code)]
+
+use super::is_binary_content;
+use anyhow::{bail, Context as _, Result};
+use chrono::{DateTime, Utc};
+use git2::{BlameOptions, Oid, Repository, Signature};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Commit that last changed a run of lines
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlameCommit {
+    pub sha: String,
+    pub short_sha: String,
+    pub author: String,
+    pub timestamp: DateTime<Utc>,
+    pub summary: String,
+}
+
+/// A line of a blamed file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlameLine {
+    /// 1-based line number in the working tree file
+    pub line: u32,
+    pub content: String,
+    /// `None` for lines changed since HEAD
+    pub commit: Option<BlameCommit>,
+    /// First line of a run last changed by the same commit
+    pub starts_run: bool,
+}
+
+/// Who last changed each line of a file
+#[derive(Clone, Debug)]
+pub struct FileBlame {
+    pub path: String,
+    pub lines: Vec<BlameLine>,
+}
+
+impl FileBlame {
+    /// Blame the working tree copy of `path` on top of HEAD, so lines
+    /// changed since the last commit show as uncommitted
+    pub fn get(repo: &Repository, path: &str) -> Result<Self> {
+        let workdir = repo.workdir().context("The repository has no working tree")?;
+        let content = std::fs::read(workdir.join(path))
+            .with_context(|| format!("reading {}", path))?;
+        if is_binary_content(&content) {
+            bail!("{} is a binary file", path);
+        }
+
+        let mut opts = BlameOptions::new();
+        let blame = repo.blame_file(Path::new(path), Some(&mut opts))?;
+        let blame = blame.blame_buffer(&content)?;
+
+        // Hunks often share a commit, which only needs to be looked up once
+        let mut commits: HashMap<Oid, BlameCommit> = HashMap::new();
+        let mut lines = Vec::new();
+        let mut previous = None;
+        for (ix, text) in String::from_utf8_lossy(&content).lines().enumerate() {
+            let hunk = blame.get_line(ix + 1);
+            let hunk_start = hunk.as_ref().map(|hunk| hunk.final_start_line());
+            let commit = hunk
+                .filter(|hunk| !hunk.final_commit_id().is_zero())
+                .map(|hunk| {
+                    let oid = hunk.final_commit_id();
+                    commits
+                        .entry(oid)
+                        .or_insert_with(|| blame_commit(repo, oid, &hunk.final_signature()))
+                        .clone()
+                });
+            lines.push(BlameLine {
+                line: ix as u32 + 1,
+                content: text.to_string(),
+                commit,
+                starts_run: ix == 0 || hunk_start != previous,
+            });
+            previous = hunk_start;
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            lines,
+        })
+    }
+}
+
+fn blame_commit(repo: &Repository, oid: Oid, signature: &Signature) -> BlameCommit {
+    let sha = oid.to_string();
+    let commit = repo.find_commit(oid).ok();
+    let seconds = commit.as_ref().map_or(0, |commit| commit.time().seconds());
+    BlameCommit {
+        short_sha: sha[..7].to_string(),
+        sha,
+        author: signature.name().unwrap_or("Unknown").to_string(),
+        timestamp: DateTime::from_timestamp(seconds, 0).unwrap_or_default(),
+        summary: commit
+            .as_ref()
+            .and_then(|commit| commit.summary().map(str::to_string))
+            .unwrap_or_default(),
+    }
+}
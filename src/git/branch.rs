@@ -1,7 +1,9 @@
 #![allow(dead_code)]
 
-use anyhow::Result;
-use git2::{BranchType, Repository};
+use anyhow::{bail, Context as _, Result};
+use git2::{BranchType, Reference, ReferenceFormat, Repository};
+
+use super::commit_signature;
 
 /// Branch information
 #[derive(Clone, Debug)]
@@ -9,6 +11,8 @@ pub struct BranchInfo {
     pub name: String,
     pub is_head: bool,
     pub upstream: Option<String>,
+    /// Commits ahead of and behind the upstream, for local branches that track one
+    pub ahead_behind: Option<(usize, usize)>,
     pub branch_type: BranchKind,
 }
 
@@ -36,15 +40,21 @@ impl BranchInfo {
             let (branch, _) = branch?;
             if let Some(name) = branch.name()? {
                 let is_head = head_name.as_ref().map(|h| h == name).unwrap_or(false);
-                let upstream = branch
-                    .upstream()
-                    .ok()
+                let upstream_branch = branch.upstream().ok();
+                let upstream = upstream_branch
+                    .as_ref()
                     .and_then(|u| u.name().ok().flatten().map(|s| s.to_string()));
+                let ahead_behind = upstream_branch.as_ref().and_then(|u| {
+                    let local = branch.get().target()?;
+                    let remote = u.get().target()?;
+                    repo.graph_ahead_behind(local, remote).ok()
+                });
 
                 branches.push(BranchInfo {
                     name: name.to_string(),
                     is_head,
                     upstream,
+                    ahead_behind,
                     branch_type: BranchKind::Local,
                 });
             }
@@ -58,6 +68,7 @@ impl BranchInfo {
                     name: name.to_string(),
                     is_head: false,
                     upstream: None,
+                    ahead_behind: None,
                     branch_type: BranchKind::Remote,
                 });
             }
@@ -72,17 +83,8 @@ impl BranchInfo {
                 return std::cmp::Ordering::Greater;
             }
 
-            let priority = |name: &str| -> u8 {
-                match name {
-                    "main" => 0,
-                    "master" => 1,
-                    "develop" | "dev" => 2,
-                    _ => 3,
-                }
-            };
-
-            let a_priority = priority(&a.name);
-            let b_priority = priority(&b.name);
+            let a_priority = mainline_priority(&a.name);
+            let b_priority = mainline_priority(&b.name);
 
             if a_priority != b_priority {
                 a_priority.cmp(&b_priority)
@@ -107,6 +109,190 @@ impl BranchInfo {
             .filter(|b| b.branch_type == BranchKind::Remote)
             .collect())
     }
+
+    /// Upstream with the ahead/behind counts, e.g. "origin/main ↑2 ↓1"
+    pub fn tracking_label(&self) -> Option<String> {
+        let upstream = self.upstream.as_ref()?;
+        Some(match self.ahead_behind {
+            Some((0, 0)) | None => upstream.clone(),
+            Some((ahead, 0)) => format!("{} ↑{}", upstream, ahead),
+            Some((0, behind)) => format!("{} ↓{}", upstream, behind),
+            Some((ahead, behind)) => format!("{} ↑{} ↓{}", upstream, ahead, behind),
+        })
+    }
+
+    /// Make the local branch `name` track the remote-tracking branch
+    /// `upstream` (e.g. "origin/main"), or stop tracking with `None`
+    pub fn set_upstream(repo: &Repository, name: &str, upstream: Option<&str>) -> Result<()> {
+        let mut branch = repo.find_branch(name, BranchType::Local)?;
+        branch.set_upstream(upstream)?;
+        Ok(())
+    }
+
+    /// Create a local branch tracking the remote-tracking branch
+    /// `remote_branch`, named without the remote ("origin/feature" becomes
+    /// "feature"), and return its name. A local branch of that name is
+    /// reused if it already tracks `remote_branch`.
+    pub fn create_tracking(repo: &Repository, remote_branch: &str) -> Result<String> {
+        let remote = repo.find_branch(remote_branch, BranchType::Remote)?;
+        let refname = remote.get().name().context("Invalid remote branch name")?;
+        let remote_name = repo.branch_remote_name(refname)?;
+        let remote_name = remote_name.as_str().context("Invalid remote name")?;
+        let local = remote_branch
+            .strip_prefix(&format!("{}/", remote_name))
+            .unwrap_or(remote_branch);
+        if local == "HEAD" {
+            bail!("'{}' is not a branch", remote_branch);
+        }
+
+        if let Ok(existing) = repo.find_branch(local, BranchType::Local) {
+            let upstream = existing.upstream().ok();
+            let upstream = upstream.as_ref().and_then(|u| u.name().ok().flatten());
+            if upstream != Some(remote_branch) {
+                bail!(
+                    "A local branch named '{}' already exists and doesn't track '{}'",
+                    local,
+                    remote_branch
+                );
+            }
+            return Ok(local.to_string());
+        }
+
+        let commit = remote.get().peel_to_commit()?;
+        let mut branch = repo.branch(local, &commit, false)?;
+        branch.set_upstream(Some(remote_branch))?;
+        Ok(local.to_string())
+    }
+}
+
+/// How likely a branch is to be the repository's main line of history,
+/// lowest first: main, master, then develop
+pub fn mainline_priority(name: &str) -> u8 {
+    match name {
+        "main" => 0,
+        "master" => 1,
+        "develop" | "dev" => 2,
+        _ => 3,
+    }
+}
+
+/// Branch checked out before the current HEAD, like `git checkout -`, read
+/// from the HEAD reflog. Branches deleted since are skipped.
+pub fn previous_branch(repo: &Repository) -> Option<String> {
+    let current = repo
+        .head()
+        .ok()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand().map(|s| s.to_string()));
+    let reflog = repo.reflog("HEAD").ok()?;
+
+    reflog.iter().find_map(|entry| {
+        let message = entry.message()?;
+        let (from, _to) = message.strip_prefix("checkout: moving from ")?.split_once(" to ")?;
+        let exists = repo.find_branch(from, BranchType::Local).is_ok();
+        (exists && current.as_deref() != Some(from)).then(|| from.to_string())
+    })
+}
+
+/// A branch name git would reject, with a corrected name when one can be derived
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidBranchName {
+    pub reason: String,
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for InvalidBranchName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl std::error::Error for InvalidBranchName {}
+
+impl BranchInfo {
+    /// Check `name` against git's ref name rules, explaining the first rule
+    /// it breaks
+    pub fn validate_name(name: &str) -> std::result::Result<(), InvalidBranchName> {
+        let refname = format!("refs/heads/{}", name);
+        let error = match Reference::normalize_name(&refname, ReferenceFormat::NORMAL) {
+            // Normalizing only collapses slashes; make the user see that too
+            Ok(normalized) if normalized == refname => return Ok(()),
+            Ok(_) => None,
+            Err(e) => Some(e),
+        };
+
+        let reason = if name.trim().is_empty() {
+            "Branch name is empty".to_string()
+        } else if name.chars().any(char::is_whitespace) {
+            "Branch names can't contain spaces".to_string()
+        } else if name.contains("..") {
+            "Branch names can't contain '..'".to_string()
+        } else if name.starts_with('/') || name.ends_with('/') || name.contains("//") {
+            "Branch names can't start or end with '/' or contain '//'".to_string()
+        } else if let Some(c) = name.chars().find(|c| FORBIDDEN_CHARS.contains(c)) {
+            format!("Branch names can't contain '{}'", c)
+        } else if name.contains("@{") || name == "@" {
+            "Branch names can't contain '@{' or be '@'".to_string()
+        } else if name
+            .split('/')
+            .any(|part| part.starts_with('.') || part.ends_with(".lock"))
+            || name.ends_with('.')
+        {
+            "Branch name parts can't start with '.' or end with '.' or '.lock'".to_string()
+        } else {
+            error.map_or_else(|| "Invalid branch name".to_string(), |e| e.message().to_string())
+        };
+
+        Err(InvalidBranchName {
+            reason,
+            suggestion: suggest_branch_name(name),
+        })
+    }
+}
+
+/// Characters never allowed in a ref name
+const FORBIDDEN_CHARS: &[char] = &['~', '^', ':', '?', '*', '[', '\\'];
+
+/// Fix the common mistakes in a branch name: spaces become dashes, and
+/// forbidden characters, repeated dots and slashes, and leading dots are
+/// dropped. Returns `None` if nothing valid is left.
+fn suggest_branch_name(name: &str) -> Option<String> {
+    let mut cleaned: String = name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| !FORBIDDEN_CHARS.contains(c) && !c.is_control())
+        .collect();
+    cleaned = cleaned.replace("@{", "@");
+    while cleaned.contains("..") {
+        cleaned = cleaned.replace("..", ".");
+    }
+
+    let parts: Vec<&str> = cleaned
+        .split('/')
+        .map(|part| {
+            let part = part.trim_start_matches('.');
+            part.strip_suffix(".lock").unwrap_or(part).trim_end_matches('.')
+        })
+        .filter(|part| !part.is_empty())
+        .collect();
+    let suggestion = parts.join("/");
+
+    let refname = format!("refs/heads/{}", suggestion);
+    let valid = Reference::normalize_name(&refname, ReferenceFormat::NORMAL)
+        .is_ok_and(|normalized| normalized == refname);
+    (valid && suggestion != "@").then_some(suggestion)
+}
+
+/// A branch merge awaiting confirmation
+#[derive(Clone, Debug)]
+pub struct MergeRequest {
+    /// Local branch merged into HEAD
+    pub branch: String,
+    pub mode: MergeMode,
+    /// Why the last attempt failed; nothing was merged
+    pub error: Option<String>,
 }
 
 /// Merge mode
@@ -119,43 +305,84 @@ pub enum MergeMode {
 }
 
 impl MergeMode {
+    pub const ALL: [Self; 4] = [Self::Auto, Self::FfOnly, Self::NoFf, Self::Squash];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MergeMode::Auto => "Auto",
+            MergeMode::FfOnly => "FF-Only",
+            MergeMode::NoFf => "No-FF",
+            MergeMode::Squash => "Squash",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            MergeMode::Auto => "Fast-forward when possible, otherwise create a merge commit",
+            MergeMode::FfOnly => "Only move the branch forward; fail if a merge is needed",
+            MergeMode::NoFf => "Always create a merge commit",
+            MergeMode::Squash => "Stage the combined changes as one commit to write yourself",
+        }
+    }
+
+    /// Merge a local branch into HEAD. Returns whether conflicts were left
+    /// in the index for resolution.
     pub fn merge_branch(
         repo: &Repository,
         branch_name: &str,
         mode: MergeMode,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         let branch = repo.find_branch(branch_name, BranchType::Local)?;
         let branch_commit = branch.get().peel_to_commit()?;
-        let annotated = repo.find_annotated_commit(branch_commit.id())?;
+        Self::merge_commit(
+            repo,
+            branch_commit.id(),
+            &format!("branch '{}'", branch_name),
+            mode,
+        )
+    }
+
+    /// Merge a commit into HEAD. `label` describes what is merged and goes
+    /// into the merge commit message, e.g. "branch 'main'". Returns whether
+    /// conflicts were left in the index for resolution.
+    pub fn merge_commit(
+        repo: &Repository,
+        oid: git2::Oid,
+        label: &str,
+        mode: MergeMode,
+    ) -> Result<bool> {
+        let commit = repo.find_commit(oid)?;
+        let annotated = repo.find_annotated_commit(oid)?;
 
         let (analysis, _) = repo.merge_analysis(&[&annotated])?;
+        if analysis.is_up_to_date() {
+            anyhow::bail!("Already up to date");
+        }
 
         match mode {
             MergeMode::FfOnly => {
                 if !analysis.is_fast_forward() {
                     anyhow::bail!("Cannot fast-forward, merge required");
                 }
-                Self::fast_forward_merge(repo, &branch_commit)?;
-            }
-            MergeMode::NoFf => {
-                Self::create_merge_commit(repo, branch_name, &branch_commit)?;
+                Self::fast_forward_merge(repo, &commit)?;
+                Ok(false)
             }
+            MergeMode::NoFf => Self::create_merge_commit(repo, &annotated, label),
             MergeMode::Squash => {
-                Self::squash_merge(repo, &annotated)?;
+                Self::squash_merge(repo, &commit, &annotated)?;
+                Ok(false)
             }
             MergeMode::Auto => {
                 if analysis.is_fast_forward() {
-                    Self::fast_forward_merge(repo, &branch_commit)?;
+                    Self::fast_forward_merge(repo, &commit)?;
+                    Ok(false)
                 } else if analysis.is_normal() {
-                    repo.merge(&[&annotated], None, None)?;
-                    // Merge commit will be created when conflicts are resolved or if clean
+                    Self::create_merge_commit(repo, &annotated, label)
                 } else {
                     anyhow::bail!("Nothing to merge");
                 }
             }
         }
-
-        Ok(())
     }
 
     fn fast_forward_merge(repo: &Repository, commit: &git2::Commit) -> Result<()> {
@@ -167,45 +394,52 @@ impl MergeMode {
         Ok(())
     }
 
+    /// Merge into the index and working tree and commit the result when
+    /// clean. On conflicts the merge is left in progress (MERGE_HEAD set)
+    /// so it can be finished from the conflict dialog.
     fn create_merge_commit(
         repo: &Repository,
-        branch_name: &str,
-        branch_commit: &git2::Commit,
-    ) -> Result<()> {
-        let head = repo.head()?.peel_to_commit()?;
-        let sig = repo.signature()?;
-
-        // Merge the trees
-        let ancestor = repo.find_commit(repo.merge_base(head.id(), branch_commit.id())?)?;
-        let mut index = repo.merge_trees(
-            &ancestor.tree()?,
-            &head.tree()?,
-            &branch_commit.tree()?,
-            None,
-        )?;
+        annotated: &git2::AnnotatedCommit,
+        label: &str,
+    ) -> Result<bool> {
+        repo.merge(&[annotated], None, None)?;
 
+        let mut index = repo.index()?;
         if index.has_conflicts() {
-            anyhow::bail!("Merge has conflicts");
+            return Ok(true);
         }
 
-        let tree_oid = index.write_tree_to(repo)?;
+        let head = repo.head()?.peel_to_commit()?;
+        let their_commit = repo.find_commit(annotated.id())?;
+        let sig = commit_signature(repo)?;
+        let tree_oid = index.write_tree()?;
         let tree = repo.find_tree(tree_oid)?;
 
-        let message = format!("Merge branch '{}'", branch_name);
+        let message = format!("Merge {}", label);
         repo.commit(
             Some("HEAD"),
             &sig,
             &sig,
             &message,
             &tree,
-            &[&head, branch_commit],
+            &[&head, &their_commit],
         )?;
 
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
-        Ok(())
+        repo.cleanup_state()?;
+        Ok(false)
     }
 
-    fn squash_merge(repo: &Repository, annotated: &git2::AnnotatedCommit) -> Result<()> {
+    fn squash_merge(
+        repo: &Repository,
+        commit: &git2::Commit,
+        annotated: &git2::AnnotatedCommit,
+    ) -> Result<()> {
+        // A conflicted squash has no merge to finish, so refuse up front
+        let head = repo.head()?.peel_to_commit()?;
+        if repo.merge_commits(&head, commit, None)?.has_conflicts() {
+            anyhow::bail!("Squash merge has conflicts");
+        }
+
         repo.merge(&[annotated], None, None)?;
         // Don't create commit yet - leave staged for user to commit
         repo.cleanup_state()?;
@@ -2,8 +2,13 @@
 
 use anyhow::Result;
 use chrono::{DateTime, TimeZone, Utc};
-use git2::{Oid, Repository, Sort};
+use git2::{DiffOptions, Oid, Repository, Revwalk, Sort};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use super::{commit_signature, lineage_name, GraphLayout};
 
 /// Single commit information
 #[derive(Clone, Debug)]
@@ -62,25 +67,24 @@ impl CommitInfo {
         }
     }
 
-    pub fn relative_time(&self) -> String {
-        let now = Utc::now();
-        let duration = now.signed_duration_since(self.timestamp);
-
-        if duration.num_days() > 365 {
-            format!("{} years ago", duration.num_days() / 365)
-        } else if duration.num_days() > 30 {
-            format!("{} months ago", duration.num_days() / 30)
-        } else if duration.num_days() > 7 {
-            format!("{} weeks ago", duration.num_days() / 7)
-        } else if duration.num_days() > 0 {
-            format!("{} days ago", duration.num_days())
-        } else if duration.num_hours() > 0 {
-            format!("{} hours ago", duration.num_hours())
-        } else if duration.num_minutes() > 0 {
-            format!("{} minutes ago", duration.num_minutes())
-        } else {
-            "just now".to_string()
-        }
+    /// Commits reachable from `tip` but not from `base`, newest first
+    pub fn get_range(repo: &Repository, base: &str, tip: &str, limit: usize) -> Result<Vec<Self>> {
+        let base_oid = repo.revparse_single(base)?.peel_to_commit()?.id();
+        let tip_oid = repo.revparse_single(tip)?.peel_to_commit()?.id();
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(tip_oid)?;
+        revwalk.hide(base_oid)?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+        let empty = HashMap::new();
+        revwalk
+            .take(limit)
+            .map(|oid| {
+                let commit = repo.find_commit(oid?)?;
+                Ok(Self::from_commit(&commit, &empty, &empty, &empty))
+            })
+            .collect()
     }
 }
 
@@ -93,7 +97,7 @@ pub enum EdgeType {
 }
 
 /// Edge in the commit graph
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GraphEdge {
     pub from_sha: String,
     pub to_sha: String,
@@ -101,7 +105,8 @@ pub struct GraphEdge {
     pub to_column: usize,
     pub from_row: usize,
     pub to_row: usize,
-    pub color: u32,
+    /// Lane color index, resolved against the theme palette when drawn
+    pub color: usize,
     pub edge_type: EdgeType,
 }
 
@@ -111,7 +116,110 @@ pub struct GraphNode {
     pub commit: CommitInfo,
     pub column: usize,
     pub row: usize,
-    pub color: u32,
+    /// Lane color index, resolved against the theme palette when drawn
+    pub color: usize,
+}
+
+/// Restricts the history shown in the commit graph. The default shows HEAD
+/// and every local branch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GraphFilter {
+    /// Only history reachable from this branch or revision
+    pub branch: Option<String>,
+    /// Case-insensitive substring of the author name or email
+    pub author: Option<String>,
+    /// Commits made at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Commits made before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Only commits changing paths matched by this pathspec
+    pub path: Option<String>,
+}
+
+impl GraphFilter {
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Walk from the filter's branch, or HEAD and every local branch, newest
+    /// first
+    pub fn revwalk<'r>(&self, repo: &'r Repository) -> Result<Revwalk<'r>> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TIME | Sort::TOPOLOGICAL)?;
+        match &self.branch {
+            Some(branch) => {
+                let oid = repo
+                    .revparse_single(branch)
+                    .and_then(|object| object.peel_to_commit())
+                    .map_err(|_| anyhow::anyhow!("Unknown branch '{}'", branch))?
+                    .id();
+                revwalk.push(oid)?;
+            }
+            None => {
+                revwalk.push_head()?;
+
+                // Also include all branches
+                for branch in repo.branches(Some(git2::BranchType::Local))? {
+                    let (branch, _) = branch?;
+                    if let Some(oid) = branch.get().target() {
+                        let _ = revwalk.push(oid);
+                    }
+                }
+            }
+        }
+        Ok(revwalk)
+    }
+
+    /// Whether commits are filtered one by one, rather than just the walk's
+    /// starting points
+    fn filters_commits(&self) -> bool {
+        self.author.is_some() || self.since.is_some() || self.until.is_some() || self.path.is_some()
+    }
+
+    pub fn matches(&self, repo: &Repository, commit: &git2::Commit) -> Result<bool> {
+        if let Some(author) = &self.author {
+            let needle = author.to_lowercase();
+            let signature = commit.author();
+            let hit = [signature.name(), signature.email()]
+                .into_iter()
+                .flatten()
+                .any(|field| field.to_lowercase().contains(&needle));
+            if !hit {
+                return Ok(false);
+            }
+        }
+
+        let time = Utc.timestamp_opt(commit.time().seconds(), 0).single();
+        if let (Some(since), Some(time)) = (self.since, time) {
+            if time < since {
+                return Ok(false);
+            }
+        }
+        if let (Some(until), Some(time)) = (self.until, time) {
+            if time >= until {
+                return Ok(false);
+            }
+        }
+
+        if let Some(path) = &self.path {
+            return touches_path(repo, commit, path);
+        }
+        Ok(true)
+    }
+}
+
+/// Whether `commit` changes anything matched by `pathspec` compared to its
+/// first parent, or at all for a root commit
+fn touches_path(repo: &Repository, commit: &git2::Commit, pathspec: &str) -> Result<bool> {
+    let tree = commit.tree()?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let mut options = DiffOptions::new();
+    options.pathspec(pathspec);
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))?;
+    Ok(diff.deltas().len() > 0)
 }
 
 /// Complete commit graph data
@@ -120,55 +228,150 @@ pub struct CommitGraphData {
     pub nodes: Vec<GraphNode>,
     pub edges: Vec<GraphEdge>,
     pub max_column: usize,
+    /// Lanes open after the last row, continued by `append`
+    layout: GraphLayout,
 }
 
 impl CommitGraphData {
-    /// Build commit graph from repository
-    pub fn build(repo: &Repository, limit: usize, offset: usize) -> Result<Self> {
-        let mut revwalk = repo.revwalk()?;
-        revwalk.set_sorting(Sort::TIME | Sort::TOPOLOGICAL)?;
-        revwalk.push_head()?;
-
-        // Also include all branches
-        for branch in repo.branches(Some(git2::BranchType::Local))? {
-            let (branch, _) = branch?;
-            if let Some(oid) = branch.get().target() {
-                let _ = revwalk.push(oid);
-            }
-        }
-
-        // Build reference maps
-        let branches_map = Self::build_branches_map(repo)?;
-        let remotes_map = Self::build_remotes_map(repo)?;
-        let tags_map = Self::build_tags_map(repo)?;
+    /// Build commit graph from repository, showing only commits that match
+    /// `filter`
+    pub fn build(
+        repo: &Repository,
+        filter: &GraphFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Self> {
+        Self::build_with_refs(repo, filter, limit, offset, &RefMaps::build(repo)?)
+    }
 
-        // Collect commits
+    /// Like `build`, labelling commits from reference maps built earlier
+    pub fn build_with_refs(
+        repo: &Repository,
+        filter: &GraphFilter,
+        limit: usize,
+        offset: usize,
+        refs: &RefMaps,
+    ) -> Result<Self> {
+        let revwalk = filter.revwalk(repo)?;
+
+        // Collect commits; `offset` counts commits that pass the filter
+        let filters_commits = filter.filters_commits();
         let mut commits: Vec<git2::Commit> = Vec::new();
-        for (i, oid_result) in revwalk.enumerate() {
-            if i < offset {
-                continue;
-            }
-            if i >= offset + limit {
+        let mut matched = 0;
+        for oid in revwalk.flatten() {
+            if commits.len() >= limit {
                 break;
             }
-            if let Ok(oid) = oid_result {
-                if let Ok(commit) = repo.find_commit(oid) {
-                    commits.push(commit);
+            // Commits before the page are only looked up when filtering
+            let commit = if filters_commits || matched >= offset {
+                match repo.find_commit(oid) {
+                    Ok(commit) => Some(commit),
+                    Err(_) => continue,
+                }
+            } else {
+                None
+            };
+            if let Some(commit) = &commit {
+                if filters_commits && !filter.matches(repo, commit)? {
+                    continue;
                 }
             }
+            matched += 1;
+            if matched > offset {
+                commits.extend(commit);
+            }
+        }
+
+        // Build graph layout; parents of filtered commits are mostly
+        // hidden, so their lanes would never close
+        let layout = if filter.filters_commits() {
+            GraphLayout::flat()
+        } else {
+            GraphLayout::new().with_branch_names(refs.branch_names())
+        };
+        let mut graph = Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            max_column: 0,
+            layout,
+        };
+        graph.extend(
+            commits
+                .iter()
+                .map(|c| CommitInfo::from_commit(c, &refs.branches, &refs.remotes, &refs.tags)),
+        );
+        Ok(graph)
+    }
+
+    /// Append the next page of the same history below the loaded rows,
+    /// continuing the lanes open at the bottom
+    pub fn append(&mut self, page: CommitGraphData) {
+        self.extend(page.nodes.into_iter().map(|node| node.commit));
+    }
+
+    fn extend(&mut self, commits: impl IntoIterator<Item = CommitInfo>) {
+        for commit in commits {
+            let (node, edges) = self.layout.push(commit);
+            self.nodes.push(node);
+            self.edges.extend(edges);
         }
+        self.max_column = self.layout.max_column();
+    }
+}
 
-        // Build graph layout
-        let (nodes, edges, max_column) =
-            Self::layout_graph(&commits, &branches_map, &remotes_map, &tags_map);
+/// Branches, remote branches and tags by the commit they point to
+#[derive(Clone, Debug, Default)]
+pub struct RefMaps {
+    pub branches: HashMap<Oid, Vec<String>>,
+    pub remotes: HashMap<Oid, Vec<String>>,
+    pub tags: HashMap<Oid, Vec<String>>,
+}
 
+impl RefMaps {
+    pub fn build(repo: &Repository) -> Result<Self> {
         Ok(Self {
-            nodes,
-            edges,
-            max_column,
+            branches: Self::build_branches_map(repo)?,
+            remotes: Self::build_remotes_map(repo)?,
+            tags: Self::build_tags_map(repo)?,
         })
     }
 
+    /// Branch at every commit a branch or remote branch points to, by
+    /// commit sha
+    pub fn branch_names(&self) -> HashMap<String, String> {
+        let empty = Vec::new();
+        self.branches
+            .keys()
+            .chain(self.remotes.keys())
+            .filter_map(|oid| {
+                let branch = self.branches.get(oid).and_then(|names| names.first());
+                let remotes = self.remotes.get(oid).unwrap_or(&empty);
+                lineage_name(branch.map(String::as_str), remotes)
+                    .map(|name| (oid.to_string(), name))
+            })
+            .collect()
+    }
+
+    /// Hash of the name and target of every branch, remote branch and tag.
+    /// Reading refs is much cheaper than building the maps, which peels
+    /// every annotated tag.
+    pub fn fingerprint(repo: &Repository) -> Result<u64> {
+        let mut hasher = DefaultHasher::new();
+        for reference in repo.references()? {
+            let reference = reference?;
+            let name = reference.name_bytes();
+            if [&b"refs/heads/"[..], b"refs/remotes/", b"refs/tags/"]
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+            {
+                name.hash(&mut hasher);
+                reference.target().hash(&mut hasher);
+                reference.symbolic_target_bytes().hash(&mut hasher);
+            }
+        }
+        Ok(hasher.finish())
+    }
+
     fn build_branches_map(repo: &Repository) -> Result<HashMap<Oid, Vec<String>>> {
         let mut map: HashMap<Oid, Vec<String>> = HashMap::new();
         for branch in repo.branches(Some(git2::BranchType::Local))? {
@@ -210,132 +413,34 @@ impl CommitGraphData {
         })?;
         Ok(map)
     }
+}
 
-    fn layout_graph(
-        commits: &[git2::Commit],
-        branches_map: &HashMap<Oid, Vec<String>>,
-        remotes_map: &HashMap<Oid, Vec<String>>,
-        tags_map: &HashMap<Oid, Vec<String>>,
-    ) -> (Vec<GraphNode>, Vec<GraphEdge>, usize) {
-        // Color palette for branches
-        const COLORS: [u32; 8] = [
-            0x89b4fa, // Blue
-            0xa6e3a1, // Green
-            0xf9e2af, // Yellow
-            0xfab387, // Orange
-            0xf38ba8, // Red
-            0xcba6f7, // Purple
-            0x94e2d5, // Teal
-            0xf5c2e7, // Pink
-        ];
-
-        let mut nodes = Vec::new();
-        let mut edges = Vec::new();
-        let mut max_column = 0;
-
-        // Track active columns (which parent SHAs are in which columns)
-        let mut active_columns: Vec<Option<String>> = Vec::new();
-        // Map SHA to row index
-        let mut sha_to_row: HashMap<String, usize> = HashMap::new();
-        // Map SHA to column
-        let mut sha_to_column: HashMap<String, usize> = HashMap::new();
-
-        for (row, commit) in commits.iter().enumerate() {
-            let sha = commit.id().to_string();
-            sha_to_row.insert(sha.clone(), row);
-
-            // Find or assign column for this commit
-            let column = if let Some(col) = sha_to_column.get(&sha) {
-                *col
-            } else {
-                // Find first available column or add new one
-                let col = active_columns
-                    .iter()
-                    .position(|c| c.is_none())
-                    .unwrap_or_else(|| {
-                        active_columns.push(None);
-                        active_columns.len() - 1
-                    });
-                sha_to_column.insert(sha.clone(), col);
-                col
-            };
-
-            // Mark this column as available (commit is being rendered)
-            if column < active_columns.len() {
-                active_columns[column] = None;
-            }
-
-            max_column = max_column.max(column);
-
-            let color = COLORS[column % COLORS.len()];
-
-            // Create node
-            let commit_info = CommitInfo::from_commit(commit, branches_map, remotes_map, tags_map);
-            nodes.push(GraphNode {
-                commit: commit_info,
-                column,
-                row,
-                color,
-            });
-
-            // Process parents and create edges
-            let parents: Vec<Oid> = commit.parents().map(|p| p.id()).collect();
-
-            for (parent_idx, parent_oid) in parents.iter().enumerate() {
-                let parent_sha = parent_oid.to_string();
-
-                // Determine parent column
-                let parent_column = if parent_idx == 0 {
-                    // First parent stays in same column
-                    sha_to_column.insert(parent_sha.clone(), column);
-                    if column < active_columns.len() {
-                        active_columns[column] = Some(parent_sha.clone());
-                    }
-                    column
-                } else {
-                    // Other parents get new columns
-                    let new_col = active_columns
-                        .iter()
-                        .position(|c| c.is_none())
-                        .unwrap_or_else(|| {
-                            active_columns.push(None);
-                            active_columns.len() - 1
-                        });
-                    sha_to_column.insert(parent_sha.clone(), new_col);
-                    if new_col < active_columns.len() {
-                        active_columns[new_col] = Some(parent_sha.clone());
-                    } else {
-                        active_columns.push(Some(parent_sha.clone()));
-                    }
-                    max_column = max_column.max(new_col);
-                    new_col
-                };
-
-                // Find parent row if it exists
-                let parent_row = sha_to_row.get(&parent_sha).copied().unwrap_or(row + 1);
-
-                let edge_type = if parents.len() > 1 {
-                    EdgeType::Merge
-                } else if column != parent_column {
-                    EdgeType::Branch
-                } else {
-                    EdgeType::Linear
-                };
+/// Reference maps of the last graph build, reused by later builds and pages
+/// until a ref is added, removed or moved. Shared by the graph loads of one
+/// repository, which may run on other threads.
+#[derive(Debug, Default)]
+pub struct RefMapCache {
+    cached: Mutex<Option<(u64, Arc<RefMaps>)>>,
+}
 
-                edges.push(GraphEdge {
-                    from_sha: sha.clone(),
-                    to_sha: parent_sha,
-                    from_column: column,
-                    to_column: parent_column,
-                    from_row: row,
-                    to_row: parent_row,
-                    color,
-                    edge_type,
-                });
+impl RefMapCache {
+    /// Maps for the refs as they are now, built only if they changed since
+    /// the last call
+    pub fn get(&self, repo: &Repository) -> Result<Arc<RefMaps>> {
+        let fingerprint = RefMaps::fingerprint(repo)?;
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((key, maps)) = cached.as_ref() {
+            if *key == fingerprint {
+                return Ok(maps.clone());
             }
         }
+        let maps = Arc::new(RefMaps::build(repo)?);
+        *cached = Some((fingerprint, maps.clone()));
+        Ok(maps)
+    }
 
-        (nodes, edges, max_column)
+    pub fn clear(&self) {
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) = None;
     }
 }
 
@@ -357,8 +462,9 @@ impl ResetMode {
     }
 }
 
-/// Revert a commit (create an undo commit)
-pub fn revert_commit(repo: &Repository, sha: &str, mainline: Option<u32>) -> Result<Oid> {
+/// Revert a commit (create an undo commit). Returns None when the revert
+/// stopped on conflicts, leaving the repository mid-revert.
+pub fn revert_commit(repo: &Repository, sha: &str, mainline: Option<u32>) -> Result<Option<Oid>> {
     let oid = git2::Oid::from_str(sha)?;
     let commit = repo.find_commit(oid)?;
 
@@ -370,8 +476,16 @@ pub fn revert_commit(repo: &Repository, sha: &str, mainline: Option<u32>) -> Res
 
     repo.revert(&commit, Some(&mut revert_opts))?;
 
-    // Create the revert commit
-    let sig = repo.signature()?;
+    if repo.index()?.has_conflicts() {
+        return Ok(None);
+    }
+    commit_revert(repo, &commit).map(Some)
+}
+
+/// Commit the revert of `commit` left in the index and leave the revert
+/// state
+pub fn commit_revert(repo: &Repository, commit: &git2::Commit) -> Result<Oid> {
+    let sig = commit_signature(repo)?;
     let mut index = repo.index()?;
     let tree_id = index.write_tree()?;
     let tree = repo.find_tree(tree_id)?;
@@ -379,7 +493,7 @@ pub fn revert_commit(repo: &Repository, sha: &str, mainline: Option<u32>) -> Res
 
     let message = format!("Revert \"{}\"\n\nThis reverts commit {}.",
         commit.summary().unwrap_or(""),
-        &sha[..7]);
+        &commit.id().to_string()[..7]);
 
     let new_commit = repo.commit(
         Some("HEAD"),
@@ -396,21 +510,24 @@ pub fn revert_commit(repo: &Repository, sha: &str, mainline: Option<u32>) -> Res
     Ok(new_commit)
 }
 
-/// Cherry-pick a commit
-pub fn cherry_pick(repo: &Repository, sha: &str) -> Result<Oid> {
+/// Cherry-pick a commit. Returns None when the pick stopped on conflicts,
+/// leaving the repository mid cherry-pick.
+pub fn cherry_pick(repo: &Repository, sha: &str) -> Result<Option<Oid>> {
     let oid = git2::Oid::from_str(sha)?;
     let commit = repo.find_commit(oid)?;
 
     repo.cherrypick(&commit, None)?;
 
-    // Check for conflicts
-    let index = repo.index()?;
-    if index.has_conflicts() {
-        anyhow::bail!("Cherry-pick resulted in conflicts. Resolve them and commit manually.");
+    if repo.index()?.has_conflicts() {
+        return Ok(None);
     }
+    commit_cherry_pick(repo, &commit).map(Some)
+}
 
-    // Create the cherry-pick commit
-    let sig = repo.signature()?;
+/// Commit the cherry-pick of `commit` left in the index, noting where it was
+/// picked from, and leave the cherry-pick state
+pub fn commit_cherry_pick(repo: &Repository, commit: &git2::Commit) -> Result<Oid> {
+    let sig = commit_signature(repo)?;
     let mut index = repo.index()?;
     let tree_id = index.write_tree()?;
     let tree = repo.find_tree(tree_id)?;
@@ -419,7 +536,7 @@ pub fn cherry_pick(repo: &Repository, sha: &str) -> Result<Oid> {
     let message = format!(
         "{}\n\n(cherry picked from commit {})",
         commit.message().unwrap_or(""),
-        &sha[..7]
+        &commit.id().to_string()[..7]
     );
 
     let new_commit = repo.commit(
@@ -447,3 +564,80 @@ pub fn reset_to_commit(repo: &Repository, sha: &str, mode: ResetMode) -> Result<
 
     Ok(())
 }
+
+/// Commit the working tree contents of `paths` on top of HEAD, ignoring
+/// whatever else is staged.
+///
+/// The commit tree is built in a temporary index from HEAD plus the selected
+/// paths, so the real index only changes for the committed paths.
+pub fn commit_paths(repo: &Repository, paths: &[String], message: &str) -> Result<Oid> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow::anyhow!("Repository has no working directory"))?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    let mut index = git2::Index::new()?;
+    index.read_tree(&head.tree()?)?;
+
+    for path in paths {
+        let full_path = workdir.join(path);
+        let Ok(metadata) = std::fs::symlink_metadata(&full_path) else {
+            // Deleted in the working tree
+            index.remove_path(std::path::Path::new(path))?;
+            continue;
+        };
+
+        let (data, mode) = if metadata.file_type().is_symlink() {
+            let target = std::fs::read_link(&full_path)?;
+            (target.to_string_lossy().as_bytes().to_vec(), 0o120000)
+        } else {
+            let existing_mode = index.get_path(std::path::Path::new(path), 0).map(|e| e.mode);
+            (std::fs::read(&full_path)?, existing_mode.unwrap_or(0o100644))
+        };
+
+        let entry = git2::IndexEntry {
+            ctime: git2::IndexTime::new(0, 0),
+            mtime: git2::IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode,
+            uid: 0,
+            gid: 0,
+            file_size: data.len() as u32,
+            id: repo.blob(&data)?,
+            flags: 0,
+            flags_extended: 0,
+            path: path.as_bytes().to_vec(),
+        };
+        index.add(&entry)?;
+    }
+
+    let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+    if tree.id() == head.tree_id() {
+        anyhow::bail!("The selected files have no changes to commit");
+    }
+
+    let sig = commit_signature(repo)?;
+    let oid = repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&head])?;
+
+    // Match the real index to the new commit for the committed paths only
+    let commit = repo.find_commit(oid)?;
+    repo.reset_default(Some(commit.as_object()), paths.iter().map(|p| p.as_str()))?;
+
+    Ok(oid)
+}
+
+/// Whether a commit is reachable from any remote-tracking branch, i.e. has
+/// already been pushed somewhere
+pub fn is_pushed(repo: &Repository, oid: Oid) -> Result<bool> {
+    for branch in repo.branches(Some(git2::BranchType::Remote))? {
+        let (branch, _) = branch?;
+        let Some(tip) = branch.get().target() else {
+            continue;
+        };
+        if tip == oid || repo.graph_descendant_of(tip, oid)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
@@ -0,0 +1,124 @@
+#![allow(dead_code)]
+
+use super::{CommitInfo, DiffDisplayOptions, FileDiff};
+use anyhow::Result;
+use git2::{Oid, Repository, Sort};
+use std::collections::HashMap;
+
+/// Which commits and changes a comparison of two revisions shows, as in
+/// `git log` and `git diff`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompareMode {
+    /// `base..head`: commits on head that aren't on base, and the difference
+    /// between the two trees
+    #[default]
+    TwoDot,
+    /// `base...head`: commits on either side since they diverged, and what
+    /// head changed since the merge base
+    ThreeDot,
+}
+
+impl CompareMode {
+    pub const ALL: [Self; 2] = [Self::TwoDot, Self::ThreeDot];
+
+    pub fn separator(self) -> &'static str {
+        match self {
+            Self::TwoDot => "..",
+            Self::ThreeDot => "...",
+        }
+    }
+}
+
+/// Commits and file changes between two revisions
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub base: String,
+    pub head: String,
+    pub mode: CompareMode,
+    /// Short SHA of the newest common ancestor, if the histories share one
+    pub merge_base: Option<String>,
+    /// Commits reachable from head but not base, newest first
+    pub head_commits: Vec<CommitInfo>,
+    /// Commits reachable from base but not head, newest first; only listed
+    /// in three-dot mode
+    pub base_commits: Vec<CommitInfo>,
+    pub files: Vec<FileDiff>,
+}
+
+impl Comparison {
+    /// Compare `base` and `head`, listing at most `limit` commits per side.
+    /// Files over `max_size` bytes are summarized instead of diffed.
+    pub fn get(
+        repo: &Repository,
+        base: &str,
+        head: &str,
+        mode: CompareMode,
+        limit: usize,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> Result<Self> {
+        let base_commit = repo.revparse_single(base)?.peel_to_commit()?;
+        let head_commit = repo.revparse_single(head)?.peel_to_commit()?;
+        let merge_base = repo.merge_base(base_commit.id(), head_commit.id()).ok();
+
+        let (base_id, head_id) = (base_commit.id(), head_commit.id());
+        let head_commits = commits_between(repo, base_id, head_id, limit)?;
+        let base_commits = match mode {
+            CompareMode::TwoDot => Vec::new(),
+            CompareMode::ThreeDot => commits_between(repo, head_id, base_id, limit)?,
+        };
+
+        // Without a common ancestor three-dot has nothing to start from, so
+        // like git it shows everything in head
+        let old_tree = match mode {
+            CompareMode::TwoDot => Some(base_commit.tree()?),
+            CompareMode::ThreeDot => match merge_base {
+                Some(oid) => Some(repo.find_commit(oid)?.tree()?),
+                None => None,
+            },
+        };
+        let head_tree = head_commit.tree()?;
+        let files =
+            FileDiff::get_tree_diff(repo, old_tree.as_ref(), &head_tree, max_size, options)?;
+
+        Ok(Self {
+            base: base.to_string(),
+            head: head.to_string(),
+            mode,
+            merge_base: merge_base.map(|oid| oid.to_string()[..7].to_string()),
+            head_commits,
+            base_commits,
+            files,
+        })
+    }
+
+    pub fn additions(&self) -> usize {
+        self.files.iter().map(|file| file.additions).sum()
+    }
+
+    pub fn deletions(&self) -> usize {
+        self.files.iter().map(|file| file.deletions).sum()
+    }
+}
+
+/// Commits reachable from `tip` but not `hidden`, newest first
+fn commits_between(
+    repo: &Repository,
+    hidden: Oid,
+    tip: Oid,
+    limit: usize,
+) -> Result<Vec<CommitInfo>> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(tip)?;
+    revwalk.hide(hidden)?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+    let empty = HashMap::new();
+    revwalk
+        .take(limit)
+        .map(|oid| {
+            let commit = repo.find_commit(oid?)?;
+            Ok(CommitInfo::from_commit(&commit, &empty, &empty, &empty))
+        })
+        .collect()
+}
@@ -2,7 +2,9 @@
 
 use anyhow::Result;
 use git2::Repository;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
+use super::{commit_signature, InterruptedPick, PickKind, StashEntry};
 
 /// Conflicted file info
 #[derive(Clone, Debug)]
@@ -10,6 +12,14 @@ pub struct ConflictedFile {
     pub path: String,
     pub is_deleted_by_us: bool,
     pub is_deleted_by_them: bool,
+    /// The common ancestor has a version of the file
+    pub has_base: bool,
+}
+
+impl ConflictedFile {
+    pub fn supports(&self, strategy: ConflictStrategy) -> bool {
+        strategy.applies(!self.is_deleted_by_us, !self.is_deleted_by_them, self.has_base)
+    }
 }
 
 /// Merge conflict information
@@ -19,15 +29,26 @@ pub struct ConflictInfo {
     pub source_branch: Option<String>,
     pub target_branch: Option<String>,
     pub is_merging: bool,
+    /// Conflicts left by reapplying stashed changes, with no operation in progress
+    pub is_stash_conflict: bool,
+    /// Conflicts left by a cherry-pick or revert rather than a merge
+    pub pick: Option<PickKind>,
+    /// Conventional message for the merge commit, e.g. "Merge branch 'x' into y"
+    pub merge_message: Option<String>,
 }
 
 impl ConflictInfo {
     pub fn get(repo: &Repository) -> Result<Option<Self>> {
         let state = repo.state();
 
+        // Reapplying a stash can leave conflicts without any operation in progress
+        let is_stash_conflict = state == git2::RepositoryState::Clean;
+
         if state != git2::RepositoryState::Merge
             && state != git2::RepositoryState::RebaseMerge
             && state != git2::RepositoryState::CherryPick
+            && state != git2::RepositoryState::Revert
+            && !is_stash_conflict
         {
             return Ok(None);
         }
@@ -57,30 +78,56 @@ impl ConflictInfo {
 
             let is_deleted_by_us = conflict.our.is_none();
             let is_deleted_by_them = conflict.their.is_none();
+            let has_base = conflict.ancestor.is_some();
 
             conflicted_files.push(ConflictedFile {
                 path,
                 is_deleted_by_us,
                 is_deleted_by_them,
+                has_base,
             });
         }
 
         // Try to get branch names from MERGE_HEAD and HEAD
-        let source_branch = Self::get_merge_head_branch(repo);
+        let merge_source = Self::get_merge_head_branch(repo);
+        let pick = InterruptedPick::get(repo)?;
+        let source_branch = if is_stash_conflict {
+            Some("stashed changes".to_string())
+        } else if let Some(pick) = &pick {
+            Some(pick.short_sha().to_string())
+        } else {
+            merge_source.as_ref().map(|(name, _)| name.clone())
+        };
         let target_branch = repo
             .head()
             .ok()
             .and_then(|h| h.shorthand().map(|s| s.to_string()));
 
+        let merge_message = merge_source
+            .filter(|_| state == git2::RepositoryState::Merge)
+            .map(|(name, is_branch)| {
+                let kind = if is_branch { "branch" } else { "commit" };
+                // A detached HEAD has no branch to name
+                match target_branch.as_deref().filter(|t| *t != "HEAD") {
+                    Some(target) => format!("Merge {} '{}' into {}", kind, name, target),
+                    None => format!("Merge {} '{}'", kind, name),
+                }
+            });
+
         Ok(Some(ConflictInfo {
             conflicted_files,
             source_branch,
             target_branch,
             is_merging: state == git2::RepositoryState::Merge,
+            is_stash_conflict,
+            pick: pick.map(|pick| pick.kind),
+            merge_message,
         }))
     }
 
-    fn get_merge_head_branch(repo: &Repository) -> Option<String> {
+    /// The branch at MERGE_HEAD, or its short SHA when no branch points there.
+    /// The flag tells whether a branch was found.
+    fn get_merge_head_branch(repo: &Repository) -> Option<(String, bool)> {
         let merge_head_path = repo.path().join("MERGE_HEAD");
         if merge_head_path.exists() {
             if let Ok(content) = std::fs::read_to_string(&merge_head_path) {
@@ -91,25 +138,37 @@ impl ConflictInfo {
                         for branch in branches.flatten() {
                             if branch.0.get().target() == Some(oid) {
                                 if let Ok(Some(name)) = branch.0.name() {
-                                    return Some(name.to_string());
+                                    return Some((name.to_string(), true));
                                 }
                             }
                         }
                     }
-                    return Some(oid_str[..7].to_string());
+                    return Some((oid_str[..7].to_string(), false));
                 }
             }
         }
         None
     }
 
-    pub fn resolve_all(repo: &Repository, strategy: ConflictStrategy) -> Result<()> {
+    /// Resolve every conflict with `strategy`. Files the strategy does not
+    /// apply to stay conflicted; returns how many were left.
+    pub fn resolve_all(repo: &Repository, strategy: ConflictStrategy) -> Result<usize> {
         let mut index = repo.index()?;
         let conflicts: Vec<_> = index.conflicts()?.collect();
+        let mut skipped = 0;
 
         for conflict in conflicts {
             let conflict = conflict?;
 
+            if !strategy.applies(
+                conflict.our.is_some(),
+                conflict.their.is_some(),
+                conflict.ancestor.is_some(),
+            ) {
+                skipped += 1;
+                continue;
+            }
+
             let path = conflict
                 .our
                 .as_ref()
@@ -121,7 +180,7 @@ impl ConflictInfo {
         }
 
         index.write()?;
-        Ok(())
+        Ok(skipped)
     }
 
     pub fn resolve_file(
@@ -131,16 +190,19 @@ impl ConflictInfo {
         conflict: &git2::IndexConflict,
         strategy: ConflictStrategy,
     ) -> Result<()> {
-        let entry = match strategy {
-            ConflictStrategy::Ours => conflict.our.as_ref(),
-            ConflictStrategy::Theirs => conflict.their.as_ref(),
+        let blob_content = |entry: Option<&git2::IndexEntry>| -> Result<Option<Vec<u8>>> {
+            entry
+                .map(|e| Ok(repo.find_blob(e.id)?.content().to_vec()))
+                .transpose()
+        };
+        let content = match strategy {
+            ConflictStrategy::Ours => blob_content(conflict.our.as_ref())?,
+            ConflictStrategy::Theirs => blob_content(conflict.their.as_ref())?,
+            ConflictStrategy::Base => blob_content(conflict.ancestor.as_ref())?,
+            ConflictStrategy::Union => Some(Self::union_content(repo, conflict)?),
         };
 
-        if let Some(entry) = entry {
-            // Get the blob content
-            let blob = repo.find_blob(entry.id)?;
-            let content = blob.content();
-
+        if let Some(content) = content {
             // Write the resolved content to working directory
             let workdir = repo.workdir().ok_or_else(|| anyhow::anyhow!("No workdir"))?;
             let file_path = workdir.join(path);
@@ -164,8 +226,56 @@ impl ConflictInfo {
         Ok(())
     }
 
+    /// Resolve a conflicted file with hand-merged content
+    pub fn resolve_with_content(repo: &Repository, path: &str, content: &str) -> Result<()> {
+        let workdir = repo.workdir().ok_or_else(|| anyhow::anyhow!("No workdir"))?;
+        std::fs::write(workdir.join(path), content)?;
+
+        // Staging the file clears its conflict entries
+        let mut index = repo.index()?;
+        index.add_path(Path::new(path))?;
+        index.write()?;
+        Ok(())
+    }
+
+    /// Both sides merged line by line, keeping the lines of both where they
+    /// conflict instead of writing conflict markers
+    fn union_content(repo: &Repository, conflict: &git2::IndexConflict) -> Result<Vec<u8>> {
+        let (Some(ours), Some(theirs)) = (conflict.our.as_ref(), conflict.their.as_ref()) else {
+            anyhow::bail!("Union needs both sides of the conflict");
+        };
+
+        // Added on both sides: merge against an empty file
+        let empty_base;
+        let ancestor = match conflict.ancestor.as_ref() {
+            Some(ancestor) => ancestor,
+            None => {
+                empty_base = git2::IndexEntry {
+                    ctime: ours.ctime,
+                    mtime: ours.mtime,
+                    dev: ours.dev,
+                    ino: ours.ino,
+                    mode: ours.mode,
+                    uid: ours.uid,
+                    gid: ours.gid,
+                    file_size: 0,
+                    id: repo.blob(&[])?,
+                    flags: ours.flags,
+                    flags_extended: ours.flags_extended,
+                    path: ours.path.clone(),
+                };
+                &empty_base
+            }
+        };
+
+        let mut opts = git2::MergeFileOptions::new();
+        opts.favor(git2::FileFavor::Union);
+        let result = repo.merge_file_from_index(ancestor, ours, theirs, Some(&mut opts))?;
+        Ok(result.content().to_vec())
+    }
+
     pub fn complete_merge(repo: &Repository, message: Option<&str>) -> Result<()> {
-        let sig = repo.signature()?;
+        let sig = commit_signature(repo)?;
         let mut index = repo.index()?;
 
         if index.has_conflicts() {
@@ -198,6 +308,25 @@ impl ConflictInfo {
         Ok(())
     }
 
+    /// Finish a resolved stash reapply: unstage the resolved changes so the
+    /// working tree looks as it did before the stash, then drop the autostash
+    pub fn complete_stash_apply(repo: &mut Repository) -> Result<()> {
+        let mut index = repo.index()?;
+        if index.has_conflicts() {
+            anyhow::bail!("Cannot finish with unresolved conflicts");
+        }
+
+        // The tree borrows the repository, which dropping the stash needs
+        {
+            let head_tree = repo.head()?.peel_to_tree()?;
+            index.read_tree(&head_tree)?;
+            index.write()?;
+        }
+
+        StashEntry::drop_latest_autostash(repo)?;
+        Ok(())
+    }
+
     pub fn abort_merge(repo: &Repository) -> Result<()> {
         let head = repo.head()?.peel_to_commit()?;
         repo.reset(&head.into_object(), git2::ResetType::Hard, None)?;
@@ -206,8 +335,40 @@ impl ConflictInfo {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum ConflictStrategy {
     Ours,
     Theirs,
+    /// Keep the changes of both sides, without conflict markers
+    Union,
+    /// Restore the common ancestor's version
+    Base,
+}
+
+impl ConflictStrategy {
+    pub const ALL: [ConflictStrategy; 4] = [
+        ConflictStrategy::Ours,
+        ConflictStrategy::Theirs,
+        ConflictStrategy::Union,
+        ConflictStrategy::Base,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ConflictStrategy::Ours => "Ours",
+            ConflictStrategy::Theirs => "Theirs",
+            ConflictStrategy::Union => "Union",
+            ConflictStrategy::Base => "Base",
+        }
+    }
+
+    /// Whether the strategy can resolve a conflict with the given sides.
+    /// Union needs both sides; Base needs a common ancestor.
+    pub fn applies(self, has_ours: bool, has_theirs: bool, has_base: bool) -> bool {
+        match self {
+            ConflictStrategy::Ours | ConflictStrategy::Theirs => true,
+            ConflictStrategy::Union => has_ours && has_theirs,
+            ConflictStrategy::Base => has_base,
+        }
+    }
 }
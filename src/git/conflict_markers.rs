@@ -0,0 +1,209 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// How one conflict hunk is resolved
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HunkResolution {
+    Ours,
+    Theirs,
+    /// Our lines followed by theirs
+    Both,
+    /// Text written by hand
+    Custom(String),
+}
+
+impl HunkResolution {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HunkResolution::Ours => "Ours",
+            HunkResolution::Theirs => "Theirs",
+            HunkResolution::Both => "Both",
+            HunkResolution::Custom(_) => "Edited",
+        }
+    }
+}
+
+/// One conflicted region between `<<<<<<<` and `>>>>>>>` markers. Lines
+/// keep their line endings.
+#[derive(Clone, Debug)]
+pub struct ConflictHunk {
+    /// Text after the `<<<<<<<` marker, usually the branch or "HEAD"
+    pub ours_label: String,
+    pub theirs_label: String,
+    pub ours: Vec<String>,
+    /// Common ancestor lines, present with diff3-style markers
+    pub base: Option<Vec<String>>,
+    pub theirs: Vec<String>,
+}
+
+impl ConflictHunk {
+    /// Text the resolution puts in place of the hunk
+    pub fn resolve(&self, resolution: &HunkResolution) -> String {
+        match resolution {
+            HunkResolution::Ours => self.ours.concat(),
+            HunkResolution::Theirs => self.theirs.concat(),
+            HunkResolution::Both => {
+                let mut text = self.ours.concat();
+                // Keep the last of our lines from running into theirs
+                if !text.is_empty() && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                text.push_str(&self.theirs.concat());
+                text
+            }
+            HunkResolution::Custom(text) => text.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ConflictSegment {
+    /// Lines both sides agree on
+    Common(Vec<String>),
+    Conflict(ConflictHunk),
+}
+
+/// A working tree file with conflict markers, split into agreed text and
+/// conflict hunks
+#[derive(Clone, Debug)]
+pub struct ConflictDocument {
+    pub segments: Vec<ConflictSegment>,
+}
+
+impl ConflictDocument {
+    pub fn parse(content: &str) -> Result<Self> {
+        enum Section {
+            Common,
+            Ours,
+            Base,
+            Theirs,
+        }
+
+        let mut segments = Vec::new();
+        let mut common = Vec::new();
+        let mut hunk: Option<ConflictHunk> = None;
+        let mut section = Section::Common;
+
+        for (number, line) in content.split_inclusive('\n').enumerate() {
+            let number = number + 1;
+            match section {
+                Section::Common => {
+                    if let Some(label) = marker_label(line, OURS_MARKER) {
+                        if !common.is_empty() {
+                            segments.push(ConflictSegment::Common(std::mem::take(&mut common)));
+                        }
+                        hunk = Some(ConflictHunk {
+                            ours_label: label,
+                            theirs_label: String::new(),
+                            ours: Vec::new(),
+                            base: None,
+                            theirs: Vec::new(),
+                        });
+                        section = Section::Ours;
+                    } else {
+                        common.push(line.to_string());
+                    }
+                }
+                Section::Ours | Section::Base => {
+                    let current = hunk.as_mut().expect("hunk is open");
+                    if marker_label(line, BASE_MARKER).is_some() {
+                        if current.base.is_some() {
+                            anyhow::bail!("Unexpected base marker on line {}", number);
+                        }
+                        current.base = Some(Vec::new());
+                        section = Section::Base;
+                    } else if marker_label(line, SEPARATOR).is_some() {
+                        section = Section::Theirs;
+                    } else if marker_label(line, THEIRS_MARKER).is_some()
+                        || marker_label(line, OURS_MARKER).is_some()
+                    {
+                        anyhow::bail!("Conflict marker out of place on line {}", number);
+                    } else if let (Section::Base, Some(base)) = (&section, &mut current.base) {
+                        base.push(line.to_string());
+                    } else {
+                        current.ours.push(line.to_string());
+                    }
+                }
+                Section::Theirs => {
+                    let current = hunk.as_mut().expect("hunk is open");
+                    if let Some(label) = marker_label(line, THEIRS_MARKER) {
+                        current.theirs_label = label;
+                        segments.push(ConflictSegment::Conflict(hunk.take().unwrap()));
+                        section = Section::Common;
+                    } else if marker_label(line, OURS_MARKER).is_some()
+                        || marker_label(line, BASE_MARKER).is_some()
+                        || marker_label(line, SEPARATOR).is_some()
+                    {
+                        anyhow::bail!("Conflict marker out of place on line {}", number);
+                    } else {
+                        current.theirs.push(line.to_string());
+                    }
+                }
+            }
+        }
+
+        if hunk.is_some() {
+            anyhow::bail!("Unterminated conflict at end of file");
+        }
+        if !common.is_empty() {
+            segments.push(ConflictSegment::Common(common));
+        }
+
+        let document = Self { segments };
+        if document.hunk_count() == 0 {
+            anyhow::bail!("No conflict markers found");
+        }
+        Ok(document)
+    }
+
+    pub fn hunks(&self) -> impl Iterator<Item = &ConflictHunk> {
+        self.segments.iter().filter_map(|segment| match segment {
+            ConflictSegment::Conflict(hunk) => Some(hunk),
+            ConflictSegment::Common(_) => None,
+        })
+    }
+
+    pub fn hunk_count(&self) -> usize {
+        self.hunks().count()
+    }
+
+    /// The file with every hunk replaced by its resolution, in hunk order.
+    /// Fails if a hunk has no resolution.
+    pub fn resolve(&self, resolutions: &[Option<HunkResolution>]) -> Result<String> {
+        let mut text = String::new();
+        let mut hunk_index = 0;
+        for segment in &self.segments {
+            match segment {
+                ConflictSegment::Common(lines) => text.push_str(&lines.concat()),
+                ConflictSegment::Conflict(hunk) => {
+                    let resolution = resolutions
+                        .get(hunk_index)
+                        .and_then(|r| r.as_ref())
+                        .ok_or_else(|| {
+                            anyhow::anyhow!("Conflict {} is not resolved", hunk_index + 1)
+                        })?;
+                    text.push_str(&hunk.resolve(resolution));
+                    hunk_index += 1;
+                }
+            }
+        }
+        Ok(text)
+    }
+}
+
+/// The text after `marker` if `line` is that conflict marker
+fn marker_label(line: &str, marker: &str) -> Option<String> {
+    let rest = line.strip_prefix(marker)?;
+    let rest = rest.trim_end_matches(['\n', '\r']);
+    if rest.is_empty() {
+        Some(String::new())
+    } else {
+        rest.strip_prefix(' ').map(|label| label.to_string())
+    }
+}
@@ -1,7 +1,15 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
-use git2::{DiffOptions, Repository};
+use git2::{DiffOptions, Oid, Repository};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Read;
+use std::ops::Range;
+use std::path::Path;
+
+/// Skip intraline diffing when the token grid would be larger than this
+const MAX_INTRALINE_CELLS: usize = 250_000;
 
 /// Line in a diff
 #[derive(Clone, Debug)]
@@ -10,6 +18,9 @@ pub struct DiffLine {
     pub line_type: DiffLineType,
     pub old_lineno: Option<u32>,
     pub new_lineno: Option<u32>,
+    /// Byte ranges within `content` that changed relative to the paired
+    /// deleted/added line (empty when the whole line is new or unpaired)
+    pub changed_ranges: Vec<Range<usize>>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -20,6 +31,53 @@ pub enum DiffLineType {
     Header,
 }
 
+/// A changed line, identified by its old line number (deletions) or new
+/// line number (additions)
+pub type DiffLineId = (Option<u32>, Option<u32>);
+
+/// What to do with selected lines of an unstaged diff
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineAction {
+    /// Copy the changes into the index
+    Stage,
+    /// Revert the changes in the working tree
+    Discard,
+}
+
+/// How diffs are computed for display, like `git diff -w`,
+/// `--ignore-blank-lines` and `-U<n>`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiffDisplayOptions {
+    /// Ignore whitespace when comparing lines
+    pub ignore_whitespace: bool,
+    /// Ignore changes whose lines are all blank
+    pub ignore_blank_lines: bool,
+    /// Unchanged lines shown around each change
+    pub context_lines: u32,
+}
+
+impl Default for DiffDisplayOptions {
+    fn default() -> Self {
+        Self {
+            ignore_whitespace: false,
+            ignore_blank_lines: false,
+            context_lines: 3,
+        }
+    }
+}
+
+impl DiffDisplayOptions {
+    /// Context line counts offered in the diff viewer
+    pub const CONTEXT_LINES: [u32; 4] = [0, 3, 10, 25];
+
+    fn apply(&self, opts: &mut DiffOptions) {
+        opts.ignore_whitespace(self.ignore_whitespace)
+            .ignore_blank_lines(self.ignore_blank_lines)
+            .context_lines(self.context_lines);
+    }
+}
+
 /// Diff for a single file
 #[derive(Clone, Debug)]
 pub struct FileDiff {
@@ -28,23 +86,136 @@ pub struct FileDiff {
     pub lines: Vec<DiffLine>,
     pub additions: usize,
     pub deletions: usize,
+    /// Compares the index to the working tree, so lines can be staged or
+    /// discarded individually
+    pub unstaged: bool,
+    /// Size of the file before and after the change in bytes (0 when it was
+    /// added or deleted)
+    pub old_size: u64,
+    pub new_size: u64,
+    /// One side is larger than the size limit, so the lines were not loaded
+    pub too_large: bool,
+    /// Git found binary content, so there are no lines to show
+    pub is_binary: bool,
+    /// Blobs of each side, `None` when the side is missing or is the
+    /// unhashed working tree file
+    pub old_id: Option<Oid>,
+    pub new_id: Option<Oid>,
 }
 
 impl FileDiff {
-    /// Get diff for a file in the working directory
-    pub fn get_file_diff(repo: &Repository, path: &str) -> Result<Self> {
+    /// Get diff for a file in the working directory. Files larger than
+    /// `max_size` bytes on either side are summarized instead of diffed.
+    pub fn get_file_diff(
+        repo: &Repository,
+        path: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> Result<Self> {
         let mut opts = DiffOptions::new();
-        opts.pathspec(path);
+        opts.pathspec(path)
+            .include_untracked(true)
+            .show_untracked_content(true);
+        options.apply(&mut opts);
 
         // Compare HEAD to working directory
         let head = repo.head()?.peel_to_tree()?;
         let diff = repo.diff_tree_to_workdir_with_index(Some(&head), Some(&mut opts))?;
 
-        Self::from_diff(&diff, path)
+        Self::from_diff(repo, &diff, path, max_size)
+    }
+
+    /// Get the unstaged changes of a file (index to working directory)
+    pub fn get_unstaged_diff(
+        repo: &Repository,
+        path: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> Result<Self> {
+        let mut opts = DiffOptions::new();
+        opts.pathspec(path);
+        options.apply(&mut opts);
+
+        let diff = repo.diff_index_to_workdir(None, Some(&mut opts))?;
+        let mut file_diff = Self::from_diff(repo, &diff, path, max_size)?;
+        file_diff.unstaged = true;
+        Ok(file_diff)
+    }
+
+    /// Stage or discard the selected added and deleted lines of `path`'s
+    /// unstaged changes, leaving the rest of each hunk as it is
+    pub fn apply_lines(
+        repo: &Repository,
+        path: &str,
+        lines: &[DiffLineId],
+        action: LineAction,
+    ) -> Result<()> {
+        let mut opts = DiffOptions::new();
+        opts.pathspec(path);
+        let diff = repo.diff_index_to_workdir(None, Some(&mut opts))?;
+
+        if diff.deltas().len() == 0 {
+            anyhow::bail!("No unstaged changes in {}", path);
+        }
+        let patch = git2::Patch::from_diff(&diff, 0)?
+            .ok_or_else(|| anyhow::anyhow!("Cannot stage lines of binary file {}", path))?;
+
+        // Discarding reverse-applies the selection to the working tree
+        let reverse = action == LineAction::Discard;
+        let Some(text) = partial_patch(&patch, path, lines, reverse)? else {
+            anyhow::bail!("No changed lines selected");
+        };
+
+        let partial = git2::Diff::from_buffer(text.as_bytes())?;
+        let location = match action {
+            LineAction::Stage => git2::ApplyLocation::Index,
+            LineAction::Discard => git2::ApplyLocation::WorkDir,
+        };
+        repo.apply(&partial, location, None)?;
+        Ok(())
+    }
+
+    /// The first hunk, from its `@@` header up to the next one
+    pub fn first_hunk(&self) -> &[DiffLine] {
+        let starts = self.hunk_starts();
+        let Some(&start) = starts.first() else {
+            return &[];
+        };
+        let end = starts.get(1).copied().unwrap_or(self.lines.len());
+        &self.lines[start..end]
+    }
+
+    /// Indices into `lines` of each hunk's `@@` header
+    pub fn hunk_starts(&self) -> Vec<usize> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| {
+                line.line_type == DiffLineType::Header && line.content.starts_with("@@")
+            })
+            .map(|(ix, _)| ix)
+            .collect()
+    }
+
+    /// Line of the new file the first change is at, for opening the file
+    /// there; deletions count as the line that follows them
+    pub fn first_changed_line(&self) -> Option<u32> {
+        let first_change = self.lines.iter().position(|line| {
+            matches!(line.line_type, DiffLineType::Addition | DiffLineType::Deletion)
+        })?;
+        self.lines[first_change..]
+            .iter()
+            .find_map(|line| line.new_lineno)
+            .or_else(|| self.lines[..first_change].iter().rev().find_map(|l| l.new_lineno))
     }
 
     /// Get diff for a specific commit
-    pub fn get_commit_diff(repo: &Repository, sha: &str) -> Result<Vec<Self>> {
+    pub fn get_commit_diff(
+        repo: &Repository,
+        sha: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> Result<Vec<Self>> {
         let oid = git2::Oid::from_str(sha)?;
         let commit = repo.find_commit(oid)?;
         let tree = commit.tree()?;
@@ -55,7 +226,21 @@ impl FileDiff {
             None
         };
 
-        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        Self::get_tree_diff(repo, parent_tree.as_ref(), &tree, max_size, options)
+    }
+
+    /// Diffs of every file that differs between two trees; `old` is `None`
+    /// to diff against an empty tree
+    pub fn get_tree_diff(
+        repo: &Repository,
+        old: Option<&git2::Tree>,
+        new: &git2::Tree,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> Result<Vec<Self>> {
+        let mut opts = DiffOptions::new();
+        options.apply(&mut opts);
+        let diff = repo.diff_tree_to_tree(old, Some(new), Some(&mut opts))?;
 
         let mut diffs = Vec::new();
         let deltas: Vec<_> = diff.deltas().collect();
@@ -67,7 +252,7 @@ impl FileDiff {
                 .map(|p| p.to_string_lossy().to_string())
                 .unwrap_or_else(|| "unknown".to_string());
 
-            if let Ok(file_diff) = Self::from_diff(&diff, &path) {
+            if let Ok(file_diff) = Self::from_diff(repo, &diff, &path, max_size) {
                 diffs.push(file_diff);
             }
         }
@@ -75,12 +260,45 @@ impl FileDiff {
         Ok(diffs)
     }
 
-    fn from_diff(diff: &git2::Diff, target_path: &str) -> Result<Self> {
+    fn from_diff(
+        repo: &Repository,
+        diff: &git2::Diff,
+        target_path: &str,
+        max_size: u64,
+    ) -> Result<Self> {
+        let delta = diff
+            .deltas()
+            .find(|delta| delta.new_file().path() == Some(Path::new(target_path)))
+            .ok_or_else(|| anyhow::anyhow!("File not found in diff: {}", target_path))?;
+        let old_size = file_size(repo, &delta.old_file());
+        let new_size = file_size(repo, &delta.new_file());
+        let blob_id = |file: git2::DiffFile| Some(file.id()).filter(|id| !id.is_zero());
+        let (old_id, new_id) = (blob_id(delta.old_file()), blob_id(delta.new_file()));
+
+        // Printing the patch would load both sides in full
+        if old_size > max_size || new_size > max_size {
+            return Ok(Self {
+                path: target_path.to_string(),
+                old_path: delta.old_file().path().map(|p| p.to_string_lossy().to_string()),
+                lines: Vec::new(),
+                additions: 0,
+                deletions: 0,
+                unstaged: false,
+                old_size,
+                new_size,
+                too_large: true,
+                is_binary: delta.flags().is_binary(),
+                old_id,
+                new_id,
+            });
+        }
+
         let mut lines = Vec::new();
         let mut additions = 0;
         let mut deletions = 0;
         let mut old_path = None;
         let mut found = false;
+        let mut is_binary = false;
 
         diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
             let path = delta
@@ -94,6 +312,9 @@ impl FileDiff {
             }
 
             found = true;
+            // Known once the content is loaded, when git prints
+            // "Binary files differ" instead of hunks
+            is_binary |= delta.flags().is_binary() || line.origin() == 'B';
 
             if old_path.is_none() {
                 old_path = delta
@@ -121,6 +342,7 @@ impl FileDiff {
                 line_type,
                 old_lineno: line.old_lineno(),
                 new_lineno: line.new_lineno(),
+                changed_ranges: Vec::new(),
             });
 
             true
@@ -130,12 +352,281 @@ impl FileDiff {
             anyhow::bail!("File not found in diff: {}", target_path);
         }
 
+        compute_intraline_changes(&mut lines);
+
         Ok(Self {
             path: target_path.to_string(),
             old_path,
             lines,
             additions,
             deletions,
+            unstaged: false,
+            old_size,
+            new_size,
+            too_large: false,
+            is_binary,
+            old_id,
+            new_id,
         })
     }
+
+    /// Up to `limit` bytes from the start of the old or new side. The new
+    /// side of a working tree diff is read from the file itself.
+    pub fn read_side(&self, repo: &Repository, new: bool, limit: usize) -> Result<Vec<u8>> {
+        let id = if new { self.new_id } else { self.old_id };
+        // Working tree content is hashed for the diff without being stored
+        if let Some(blob) = id.and_then(|id| repo.find_blob(id).ok()) {
+            let content = blob.content();
+            return Ok(content[..content.len().min(limit)].to_vec());
+        }
+        let missing = if new { self.new_size } else { self.old_size } == 0;
+        let Some(workdir) = repo.workdir().filter(|_| new && !missing) else {
+            return Ok(Vec::new());
+        };
+        let mut content = Vec::new();
+        File::open(workdir.join(&self.path))?
+            .take(limit as u64)
+            .read_to_end(&mut content)?;
+        Ok(content)
+    }
+
+    /// Bytes the change adds or removes overall
+    pub fn size_change(&self) -> i64 {
+        self.new_size as i64 - self.old_size as i64
+    }
+}
+
+/// Size of one side of a delta in bytes. libgit2 leaves it at 0 until the
+/// content is loaded, so it's read from the object header or the working
+/// tree file instead.
+fn file_size(repo: &Repository, file: &git2::DiffFile) -> u64 {
+    if !file.exists() {
+        return 0;
+    }
+    if file.size() > 0 {
+        return file.size();
+    }
+    if !file.id().is_zero() {
+        if let Ok((size, _)) = repo.odb().and_then(|odb| odb.read_header(file.id())) {
+            return size as u64;
+        }
+    }
+    file.path()
+        .zip(repo.workdir())
+        .and_then(|(path, workdir)| workdir.join(path).metadata().ok())
+        .map_or(0, |metadata| metadata.len())
+}
+
+/// Write a patch containing only the `selected` changes of `patch`.
+///
+/// Unselected deletions stay as context (or are dropped when reversed) and
+/// unselected additions are dropped (or stay as context when reversed), so
+/// the result applies cleanly on top of the index (or the working tree).
+fn partial_patch(
+    patch: &git2::Patch,
+    path: &str,
+    selected: &[DiffLineId],
+    reverse: bool,
+) -> Result<Option<String>> {
+    let mut text = format!("diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n", path);
+    let mut any_changes = false;
+    // Line shift caused by the hunks written so far
+    let mut offset: i64 = 0;
+
+    for hunk_ix in 0..patch.num_hunks() {
+        let (hunk, line_count) = patch.hunk(hunk_ix)?;
+        let mut body = String::new();
+        let (mut old_count, mut new_count) = (0i64, 0i64);
+        let mut has_changes = false;
+
+        for line_ix in 0..line_count {
+            let line = patch.line_in_hunk(hunk_ix, line_ix)?;
+            let is_selected = selected.contains(&(line.old_lineno(), line.new_lineno()));
+            let origin = match (line.origin(), is_selected, reverse) {
+                (' ', _, _) => ' ',
+                ('+', true, false) | ('-', true, true) => '+',
+                ('-', true, false) | ('+', true, true) => '-',
+                ('-', false, false) | ('+', false, true) => ' ',
+                // Unselected lines that don't exist on the old side, and
+                // end-of-file markers (re-added below from the content)
+                _ => continue,
+            };
+
+            match origin {
+                '+' => new_count += 1,
+                '-' => old_count += 1,
+                _ => {
+                    old_count += 1;
+                    new_count += 1;
+                }
+            }
+            has_changes |= origin != ' ';
+
+            let content = String::from_utf8_lossy(line.content());
+            body.push(origin);
+            body.push_str(&content);
+            if !content.ends_with('\n') {
+                body.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+
+        if !has_changes {
+            continue;
+        }
+        any_changes = true;
+
+        let old_start = i64::from(if reverse { hunk.new_start() } else { hunk.old_start() });
+        // Empty sides name the line before the hunk, as in `git diff`
+        let new_start = old_start + offset + i64::from(old_count == 0) - i64::from(new_count == 0);
+        text.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_count, new_start, new_count
+        ));
+        text.push_str(&body);
+        offset += new_count - old_count;
+    }
+
+    Ok(any_changes.then_some(text))
+}
+
+/// Pair each run of deleted lines with the run of added lines that follows it
+/// and mark the words that differ within each pair
+fn compute_intraline_changes(lines: &mut [DiffLine]) {
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].line_type != DiffLineType::Deletion {
+            i += 1;
+            continue;
+        }
+
+        let deletions_start = i;
+        while i < lines.len() && lines[i].line_type == DiffLineType::Deletion {
+            i += 1;
+        }
+        let additions_start = i;
+        while i < lines.len() && lines[i].line_type == DiffLineType::Addition {
+            i += 1;
+        }
+
+        let pairs = (additions_start - deletions_start).min(i - additions_start);
+        for offset in 0..pairs {
+            let old_ix = deletions_start + offset;
+            let new_ix = additions_start + offset;
+            if let Some((old_ranges, new_ranges)) =
+                diff_words(&lines[old_ix].content, &lines[new_ix].content)
+            {
+                lines[old_ix].changed_ranges = old_ranges;
+                lines[new_ix].changed_ranges = new_ranges;
+            }
+        }
+    }
+}
+
+/// Changed byte ranges of an old and a new line
+type WordChanges = (Vec<Range<usize>>, Vec<Range<usize>>);
+
+/// Split a line into word, whitespace and single punctuation tokens
+fn tokenize(text: &str) -> Vec<Range<usize>> {
+    let text = text.trim_end_matches(['\n', '\r']);
+    let mut tokens = Vec::new();
+    let mut start: Option<(usize, bool)> = None;
+
+    for (ix, ch) in text.char_indices() {
+        let is_word = ch.is_alphanumeric() || ch == '_';
+        let is_space = ch.is_whitespace();
+
+        // `start` tracks an open word or whitespace run
+        match start {
+            Some((_, word)) if (word && is_word) || (!word && is_space) => {}
+            _ => {
+                if let Some((token_start, _)) = start {
+                    tokens.push(token_start..ix);
+                }
+                if is_word || is_space {
+                    start = Some((ix, is_word));
+                } else {
+                    tokens.push(ix..ix + ch.len_utf8());
+                    start = None;
+                }
+            }
+        }
+    }
+
+    if let Some((token_start, _)) = start {
+        tokens.push(token_start..text.len());
+    }
+
+    tokens
+}
+
+/// Word-level LCS between two lines. Returns the changed byte ranges for the
+/// old and new line, or None if the lines share nothing meaningful.
+fn diff_words(old: &str, new: &str) -> Option<WordChanges> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+
+    if n == 0 || m == 0 || (n + 1) * (m + 1) > MAX_INTRALINE_CELLS {
+        return None;
+    }
+
+    // lcs[i][j] = length of the LCS of old_tokens[i..] and new_tokens[j..]
+    let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[at(i, j)] = if old[old_tokens[i].clone()] == new[new_tokens[j].clone()] {
+                lcs[at(i + 1, j + 1)] + 1
+            } else {
+                lcs[at(i + 1, j)].max(lcs[at(i, j + 1)])
+            };
+        }
+    }
+
+    let mut old_common = vec![false; n];
+    let mut new_common = vec![false; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[old_tokens[i].clone()] == new[new_tokens[j].clone()] {
+            old_common[i] = true;
+            new_common[j] = true;
+            i += 1;
+            j += 1;
+        } else if lcs[at(i + 1, j)] >= lcs[at(i, j + 1)] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    // Highlighting every token of a rewritten line is just noise
+    let shares_content = old_tokens
+        .iter()
+        .zip(&old_common)
+        .any(|(range, common)| *common && !old[range.clone()].trim().is_empty());
+    if !shares_content {
+        return None;
+    }
+
+    Some((
+        changed_ranges(&old_tokens, &old_common),
+        changed_ranges(&new_tokens, &new_common),
+    ))
+}
+
+/// Merge adjacent non-common tokens into contiguous byte ranges
+fn changed_ranges(tokens: &[Range<usize>], common: &[bool]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+
+    for (range, common) in tokens.iter().zip(common) {
+        if *common {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => ranges.push(range.clone()),
+        }
+    }
+
+    ranges
 }
@@ -0,0 +1,247 @@
+#![allow(dead_code)]
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use super::{mainline_priority, CommitInfo, EdgeType, GraphEdge, GraphNode};
+
+/// A column of the graph waiting for a commit further down the history
+#[derive(Clone, Debug)]
+struct Lane {
+    /// Commit the lane leads to
+    sha: String,
+    color: usize,
+    /// Branch whose line of history the lane follows, if known
+    lineage: Option<String>,
+}
+
+/// Edge from a placed commit to a parent that hasn't been placed yet
+#[derive(Clone, Debug)]
+struct PendingEdge {
+    from_sha: String,
+    from_row: usize,
+    from_column: usize,
+    color: usize,
+    /// Leads to a parent other than the first
+    merge: bool,
+}
+
+/// Assigns commits to columns the way `git log --graph` does.
+///
+/// Commits are placed one row at a time, newest first. Each lane waits for
+/// the next commit of one line of history: a commit takes the leftmost lane
+/// waiting for it, its first parent continues that lane and further parents
+/// of a merge open lanes right of it. Lanes that meet at a common ancestor
+/// are merged into the leftmost, which carries on the line of history the
+/// ancestor belongs to, or else the mainline's, and lanes that end are
+/// removed so the ones right of them move left. The state is kept between
+/// calls, so a page of history can be appended without disturbing the rows
+/// before it.
+///
+/// A lane's color comes from the line of history it follows rather than its
+/// column: the branch at its tip, or the commit that opened it when no branch
+/// points there. A branch keeps its color as lanes shift, pages load and the
+/// graph is rebuilt.
+#[derive(Clone, Debug, Default)]
+pub struct GraphLayout {
+    lanes: Vec<Lane>,
+    /// Edges by the parent they lead to, drawn once the parent is placed
+    pending: HashMap<String, Vec<PendingEdge>>,
+    /// Branch by the commit it points to, naming lanes opened for the
+    /// parents of merges
+    branch_names: HashMap<String, String>,
+    rows: usize,
+    max_column: usize,
+    flat: bool,
+}
+
+impl GraphLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Layout for history with commits filtered out, whose parents are
+    /// mostly not shown: every commit goes in the first column
+    pub fn flat() -> Self {
+        Self {
+            flat: true,
+            ..Self::default()
+        }
+    }
+
+    /// Color the lanes opened for merged parents after the branches still
+    /// pointing at them
+    pub fn with_branch_names(mut self, branch_names: HashMap<String, String>) -> Self {
+        self.branch_names = branch_names;
+        self
+    }
+
+    /// Rightmost column used by a commit or a lane passing a row
+    pub fn max_column(&self) -> usize {
+        self.max_column
+    }
+
+    /// Place the next commit, returning its node and the edges from the
+    /// commits above that end at it
+    pub fn push(&mut self, commit: CommitInfo) -> (GraphNode, Vec<GraphEdge>) {
+        let row = self.rows;
+        self.rows += 1;
+        let sha = commit.sha.clone();
+
+        let (column, color) = if self.flat {
+            (0, 0)
+        } else {
+            self.place(&commit)
+        };
+        self.max_column = self.max_column.max(column);
+
+        let edges = self
+            .pending
+            .remove(&sha)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|pending| GraphEdge {
+                edge_type: if pending.merge {
+                    EdgeType::Merge
+                } else if pending.from_column != column {
+                    EdgeType::Branch
+                } else {
+                    EdgeType::Linear
+                },
+                from_sha: pending.from_sha,
+                to_sha: sha.clone(),
+                from_column: pending.from_column,
+                to_column: column,
+                from_row: pending.from_row,
+                to_row: row,
+                color: pending.color,
+            })
+            .collect();
+
+        for (index, parent) in commit.parents.iter().enumerate() {
+            // Edges are drawn in the color of the lane leading to the parent
+            let color = self
+                .lanes
+                .iter()
+                .find(|lane| lane.sha == *parent)
+                .map_or(color, |lane| lane.color);
+            self.pending.entry(parent.clone()).or_default().push(PendingEdge {
+                from_sha: sha.clone(),
+                from_row: row,
+                from_column: column,
+                color,
+                merge: index > 0,
+            });
+        }
+
+        let node = GraphNode {
+            commit,
+            column,
+            row,
+            color,
+        };
+        (node, edges)
+    }
+
+    /// Take the lane for `commit` and update the lanes for its parents,
+    /// returning its column and color
+    fn place(&mut self, commit: &CommitInfo) -> (usize, usize) {
+        let waiting: Vec<usize> = self
+            .lanes
+            .iter()
+            .enumerate()
+            .filter(|(_, lane)| lane.sha == commit.sha)
+            .map(|(index, _)| index)
+            .collect();
+
+        // A commit no lane is waiting for is the tip of a branch
+        let column = match waiting.first() {
+            Some(&column) => column,
+            None => {
+                let name = lineage_name(commit.branch.as_deref(), &commit.remotes);
+                let color = lineage_color(name.as_deref().unwrap_or(&commit.sha));
+                self.lanes.push(Lane {
+                    sha: commit.sha.clone(),
+                    color,
+                    lineage: name,
+                });
+                self.lanes.len() - 1
+            }
+        };
+
+        // Where lines of history meet, the leftmost lane continues the one
+        // the commit belongs to, or else the mainline, so the color doesn't
+        // depend on which branch happens to be drawn leftmost
+        if waiting.len() > 1 {
+            let own = lineage_name(commit.branch.as_deref(), &commit.remotes);
+            let survivor = waiting
+                .iter()
+                .copied()
+                .min_by_key(|&index| {
+                    let lineage = self.lanes[index].lineage.as_deref();
+                    let own_line = own.is_some() && lineage == own.as_deref();
+                    (!own_line, lineage.map_or(u8::MAX, mainline_priority))
+                })
+                .unwrap_or(column);
+            let Lane { color, lineage, .. } = self.lanes[survivor].clone();
+            self.lanes[column].color = color;
+            self.lanes[column].lineage = lineage;
+        }
+        let color = self.lanes[column].color;
+
+        // The lanes right of it are also waiting for this commit: they end
+        // here, where their lines of history meet
+        for &index in waiting.iter().skip(1).rev() {
+            self.lanes.remove(index);
+        }
+        self.max_column = self.max_column.max(self.lanes.len().saturating_sub(1));
+
+        let mut parents = commit.parents.iter();
+        match parents.next() {
+            Some(first) => self.lanes[column].sha = first.clone(),
+            None => {
+                self.lanes.remove(column);
+            }
+        }
+
+        let mut insert_at = column + 1;
+        for parent in parents {
+            if self.lanes.iter().any(|lane| lane.sha == *parent) {
+                continue;
+            }
+            let lineage = self.branch_names.get(parent).cloned();
+            let color = lineage_color(lineage.as_deref().unwrap_or(parent));
+            self.lanes.insert(
+                insert_at,
+                Lane {
+                    sha: parent.clone(),
+                    color,
+                    lineage,
+                },
+            );
+            insert_at += 1;
+        }
+        self.max_column = self.max_column.max(self.lanes.len().saturating_sub(1));
+
+        (column, color)
+    }
+}
+
+/// Name of the line of history at a commit: its local branch, or else a
+/// remote branch without the remote, so `main` and `origin/main` match
+pub fn lineage_name(branch: Option<&str>, remotes: &[String]) -> Option<String> {
+    branch.map(str::to_string).or_else(|| {
+        remotes.first().map(|remote| match remote.split_once('/') {
+            Some((_, name)) => name.to_string(),
+            None => remote.clone(),
+        })
+    })
+}
+
+/// Color index for a line of history, the same every time it is laid out
+fn lineage_color(name: &str) -> usize {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    hasher.finish() as usize
+}
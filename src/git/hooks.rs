@@ -0,0 +1,136 @@
+#![allow(dead_code)]
+
+use anyhow::{bail, Context as _, Result};
+use git2::Repository;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+
+/// Hooks run before a commit is created, in order
+pub const COMMIT_HOOKS: [&str; 2] = ["pre-commit", "commit-msg"];
+
+/// Executable hook script `name` of a repository, looked up in
+/// `core.hooksPath` when set and `.git/hooks` otherwise
+pub fn hook_path(repo: &Repository, name: &str) -> Option<PathBuf> {
+    let configured = repo
+        .config()
+        .ok()
+        .and_then(|config| config.get_path("core.hooksPath").ok());
+    let dir = match configured {
+        Some(dir) if dir.is_absolute() => dir,
+        // Relative paths are relative to where hooks run: the working tree
+        Some(dir) => repo.workdir().unwrap_or(repo.path()).join(dir),
+        None => repo.path().join("hooks"),
+    };
+    let path = dir.join(name);
+    is_executable(&path).then_some(path)
+}
+
+/// Whether any hook that runs before a commit is installed
+pub fn has_commit_hooks(repo: &Repository) -> bool {
+    COMMIT_HOOKS.iter().any(|name| hook_path(repo, name).is_some())
+}
+
+/// Run hook `name` from the working tree, passing its stdout and stderr to
+/// `on_output` line by line as they are written. Returns whether the hook
+/// exists, and fails when it exits with a nonzero status.
+pub fn run_hook(
+    repo: &Repository,
+    name: &str,
+    args: &[&OsStr],
+    on_output: &mut dyn FnMut(String),
+) -> Result<bool> {
+    let Some(hook) = hook_path(repo, name) else {
+        return Ok(false);
+    };
+    let mut child = hook_command(&hook)
+        .args(args)
+        .current_dir(repo.workdir().unwrap_or(repo.path()))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("running the {} hook", name))?;
+
+    // Both pipes are drained at once so neither fills up and blocks the hook
+    let (tx, rx) = mpsc::channel();
+    let readers: Vec<_> = [
+        child.stdout.take().map(|out| Box::new(out) as Box<dyn Read + Send>),
+        child.stderr.take().map(|err| Box::new(err) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|pipe| {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(pipe).lines().map_while(std::io::Result::ok) {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        })
+    })
+    .collect();
+    drop(tx);
+    for line in rx {
+        on_output(line);
+    }
+    for reader in readers {
+        let _ = reader.join();
+    }
+
+    let status = child.wait().with_context(|| format!("waiting for the {} hook", name))?;
+    if !status.success() {
+        match status.code() {
+            Some(code) => bail!("The {} hook failed with exit code {}", name, code),
+            None => bail!("The {} hook was terminated", name),
+        }
+    }
+    Ok(true)
+}
+
+/// Run the pre-commit and commit-msg hooks before committing `message`,
+/// returning the message as commit-msg left it. Fails at the first hook that
+/// rejects the commit.
+pub fn run_commit_hooks(
+    repo: &Repository,
+    message: &str,
+    on_output: &mut dyn FnMut(String),
+) -> Result<String> {
+    run_hook(repo, "pre-commit", &[], on_output)?;
+
+    if hook_path(repo, "commit-msg").is_none() {
+        return Ok(message.to_string());
+    }
+    let message_file = repo.path().join("COMMIT_EDITMSG");
+    fs::write(&message_file, message).context("writing COMMIT_EDITMSG")?;
+    run_hook(repo, "commit-msg", &[message_file.as_os_str()], on_output)?;
+    fs::read_to_string(&message_file).context("reading COMMIT_EDITMSG")
+}
+
+/// Command running a hook script. Windows can't execute shell scripts
+/// directly, so they go through the `sh` that ships with Git for Windows.
+fn hook_command(hook: &Path) -> Command {
+    if cfg!(windows) {
+        let mut command = Command::new("sh");
+        command.arg(hook);
+        command
+    } else {
+        Command::new(hook)
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
@@ -0,0 +1,340 @@
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::io::Read;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// Give up on a hosting API request after this long
+const API_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Known git hosting services, used to build web URLs
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HostingProvider {
+    GitHub,
+    GitLab,
+    Bitbucket,
+    /// Gitea, Forgejo and other self-hosted forges with Gitea-style URLs
+    Other,
+}
+
+impl HostingProvider {
+    /// Name shown in menu items, e.g. "Open Commit on GitHub"
+    pub fn name(self) -> Option<&'static str> {
+        match self {
+            Self::GitHub => Some("GitHub"),
+            Self::GitLab => Some("GitLab"),
+            Self::Bitbucket => Some("Bitbucket"),
+            Self::Other => None,
+        }
+    }
+
+    /// What the service calls a pull request
+    pub fn pull_request_name(self) -> &'static str {
+        match self {
+            Self::GitLab => "Merge Request",
+            _ => "Pull Request",
+        }
+    }
+
+    /// How the service refers to a pull request, e.g. "#12" or GitLab's "!12"
+    pub fn pull_request_reference(self, number: u64) -> String {
+        match self {
+            Self::GitLab => format!("!{}", number),
+            _ => format!("#{}", number),
+        }
+    }
+}
+
+/// An open pull request (merge request on GitLab) from a branch of the
+/// repository itself
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    /// Branch the request merges from
+    pub branch: String,
+    pub draft: bool,
+}
+
+#[derive(Deserialize)]
+struct GitHubPull {
+    number: u64,
+    title: String,
+    html_url: String,
+    head: GitHubHead,
+    #[serde(default)]
+    draft: bool,
+}
+
+#[derive(Deserialize)]
+struct GitHubHead {
+    #[serde(rename = "ref")]
+    branch: String,
+    /// Missing when the fork was deleted
+    repo: Option<GitHubRepo>,
+}
+
+#[derive(Deserialize)]
+struct GitHubRepo {
+    full_name: String,
+}
+
+#[derive(Deserialize)]
+struct GitLabMergeRequest {
+    iid: u64,
+    title: String,
+    web_url: String,
+    source_branch: String,
+    source_project_id: u64,
+    target_project_id: u64,
+    #[serde(default)]
+    draft: bool,
+}
+
+/// Browser URL of a repository derived from its remote URL
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteWebUrl {
+    pub provider: HostingProvider,
+    pub host: String,
+    /// Repository path on the host, e.g. "owner/repo"
+    pub repo_path: String,
+}
+
+impl RemoteWebUrl {
+    /// Parse HTTPS, SSH (`ssh://`) and scp-style (`git@host:owner/repo`)
+    /// remote URLs. Returns None for local paths and unrecognized formats.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+
+        let (host, path) = if let Some((scheme, rest)) = url.split_once("://") {
+            if !matches!(scheme, "https" | "http" | "ssh" | "git" | "git+ssh") {
+                return None;
+            }
+            let (authority, path) = rest.split_once('/')?;
+            // Drop credentials and port
+            let host = authority.rsplit('@').next()?;
+            let host = host.split(':').next()?;
+            (host, path)
+        } else {
+            // scp-like syntax: [user@]host:path (a single letter is a drive)
+            let (authority, path) = url.split_once(':')?;
+            if authority.contains('/') || authority.len() == 1 {
+                return None;
+            }
+            let host = authority.rsplit('@').next()?;
+            (host, path)
+        };
+
+        let repo_path = path
+            .trim_matches('/')
+            .trim_end_matches(".git")
+            .trim_end_matches('/')
+            .to_string();
+        if host.is_empty() || !repo_path.contains('/') {
+            return None;
+        }
+
+        let host = host.to_lowercase();
+        let provider = if host.contains("github") {
+            HostingProvider::GitHub
+        } else if host.contains("gitlab") {
+            HostingProvider::GitLab
+        } else if host.contains("bitbucket") {
+            HostingProvider::Bitbucket
+        } else {
+            HostingProvider::Other
+        };
+
+        Some(Self {
+            provider,
+            host,
+            repo_path,
+        })
+    }
+
+    /// Repository home page
+    pub fn base_url(&self) -> String {
+        format!("https://{}/{}", self.host, self.repo_path)
+    }
+
+    /// Link to `path` at `sha`, optionally highlighting a range of lines
+    pub fn permalink(&self, sha: &str, path: &str, lines: Option<RangeInclusive<u32>>) -> String {
+        let base = self.base_url();
+        let path = path.trim_start_matches('/');
+
+        let mut url = match self.provider {
+            HostingProvider::GitHub => format!("{}/blob/{}/{}", base, sha, path),
+            HostingProvider::GitLab => format!("{}/-/blob/{}/{}", base, sha, path),
+            HostingProvider::Bitbucket => format!("{}/src/{}/{}", base, sha, path),
+            HostingProvider::Other => format!("{}/src/commit/{}/{}", base, sha, path),
+        };
+
+        if let Some(lines) = lines {
+            let (start, end) = (*lines.start(), *lines.end());
+            let fragment = match (self.provider, start == end) {
+                (HostingProvider::Bitbucket, true) => format!("#lines-{}", start),
+                (HostingProvider::Bitbucket, false) => format!("#lines-{}:{}", start, end),
+                (HostingProvider::GitLab, false) => format!("#L{}-{}", start, end),
+                (_, true) => format!("#L{}", start),
+                (_, false) => format!("#L{}-L{}", start, end),
+            };
+            url.push_str(&fragment);
+        }
+
+        url
+    }
+
+    /// Link to a commit
+    pub fn commit_url(&self, sha: &str) -> String {
+        match self.provider {
+            HostingProvider::GitHub | HostingProvider::Other => {
+                format!("{}/commit/{}", self.base_url(), sha)
+            }
+            HostingProvider::GitLab => format!("{}/-/commit/{}", self.base_url(), sha),
+            HostingProvider::Bitbucket => format!("{}/commits/{}", self.base_url(), sha),
+        }
+    }
+
+    /// Page for opening a pull request from `branch`, prefilled with the
+    /// branch to merge into when known
+    pub fn compare_url(&self, branch: &str, base: Option<&str>) -> String {
+        let base_url = self.base_url();
+        match (self.provider, base) {
+            (HostingProvider::GitHub, Some(base)) => {
+                format!("{}/compare/{}...{}?expand=1", base_url, base, branch)
+            }
+            (HostingProvider::GitHub, None) => format!("{}/compare/{}?expand=1", base_url, branch),
+            (HostingProvider::GitLab, _) => {
+                let mut url = format!(
+                    "{}/-/merge_requests/new?merge_request%5Bsource_branch%5D={}",
+                    base_url,
+                    encode_component(branch)
+                );
+                if let Some(base) = base {
+                    url.push_str("&merge_request%5Btarget_branch%5D=");
+                    url.push_str(&encode_component(base));
+                }
+                url
+            }
+            (HostingProvider::Bitbucket, _) => {
+                let mut url = format!(
+                    "{}/pull-requests/new?source={}",
+                    base_url,
+                    encode_component(branch)
+                );
+                if let Some(base) = base {
+                    url.push_str("&dest=");
+                    url.push_str(&encode_component(base));
+                }
+                url
+            }
+            (HostingProvider::Other, Some(base)) => {
+                format!("{}/compare/{}...{}", base_url, base, branch)
+            }
+            (HostingProvider::Other, None) => format!("{}/compare/{}", base_url, branch),
+        }
+    }
+
+    /// API endpoint listing open pull requests, for the services whose API
+    /// is supported
+    pub fn pull_requests_api_url(&self) -> Option<String> {
+        match self.provider {
+            HostingProvider::GitHub => {
+                // GitHub Enterprise serves the API under /api/v3
+                let api = if self.host == "github.com" {
+                    "https://api.github.com".to_string()
+                } else {
+                    format!("https://{}/api/v3", self.host)
+                };
+                Some(format!(
+                    "{}/repos/{}/pulls?state=open&per_page=100",
+                    api, self.repo_path
+                ))
+            }
+            HostingProvider::GitLab => Some(format!(
+                "https://{}/api/v4/projects/{}/merge_requests?state=opened&per_page=100",
+                self.host,
+                encode_component(&self.repo_path)
+            )),
+            HostingProvider::Bitbucket | HostingProvider::Other => None,
+        }
+    }
+
+    /// Read the open pull requests from an API response, leaving out
+    /// requests from forks
+    pub fn parse_pull_requests(&self, json: &str) -> Result<Vec<PullRequest>> {
+        let requests = match self.provider {
+            HostingProvider::GitHub => serde_json::from_str::<Vec<GitHubPull>>(json)?
+                .into_iter()
+                .filter(|pull| {
+                    pull.head
+                        .repo
+                        .as_ref()
+                        .is_some_and(|repo| repo.full_name.eq_ignore_ascii_case(&self.repo_path))
+                })
+                .map(|pull| PullRequest {
+                    number: pull.number,
+                    title: pull.title,
+                    url: pull.html_url,
+                    branch: pull.head.branch,
+                    draft: pull.draft,
+                })
+                .collect(),
+            HostingProvider::GitLab => serde_json::from_str::<Vec<GitLabMergeRequest>>(json)?
+                .into_iter()
+                .filter(|request| request.source_project_id == request.target_project_id)
+                .map(|request| PullRequest {
+                    number: request.iid,
+                    title: request.title,
+                    url: request.web_url,
+                    branch: request.source_branch,
+                    draft: request.draft,
+                })
+                .collect(),
+            HostingProvider::Bitbucket | HostingProvider::Other => Vec::new(),
+        };
+        Ok(requests)
+    }
+
+    /// Ask the hosting service for the repository's open pull requests,
+    /// authenticating with an access token. Blocks, so run it in the
+    /// background.
+    pub fn fetch_pull_requests(&self, token: &str) -> Result<Vec<PullRequest>> {
+        let Some(url) = self.pull_requests_api_url() else {
+            return Ok(Vec::new());
+        };
+        let request = ureq::get(&url).timeout(API_TIMEOUT);
+        let request = match self.provider {
+            HostingProvider::GitLab => request.set("PRIVATE-TOKEN", token),
+            _ => request
+                .set("Authorization", &format!("Bearer {}", token))
+                .set("Accept", "application/vnd.github+json"),
+        };
+        let mut json = String::new();
+        request
+            .call()
+            .with_context(|| format!("requesting {}", url))?
+            .into_reader()
+            .read_to_string(&mut json)
+            .context("reading pull requests")?;
+        self.parse_pull_requests(&json)
+    }
+}
+
+/// Percent-encode everything but unreserved characters, for query values and
+/// GitLab project paths
+fn encode_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,135 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::{Config, ConfigLevel, ErrorCode, Repository, Signature};
+use std::fs;
+
+/// Config file in the git directory holding the identity override
+const OVERRIDE_FILE: &str = "awabancha-identity";
+
+/// Git config level the identity is read from and written to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdentityScope {
+    /// `~/.gitconfig`, shared by every repository
+    Global,
+    /// `.git/config` of one repository
+    Repository,
+}
+
+impl IdentityScope {
+    /// Open just this level's config file. The global file is created on
+    /// first write if it doesn't exist yet.
+    pub fn open(self, repo: Option<&Repository>) -> Result<Config> {
+        match self {
+            IdentityScope::Global => {
+                let path = match Config::find_global() {
+                    Ok(path) => path,
+                    Err(_) => dirs::home_dir()
+                        .ok_or_else(|| anyhow::anyhow!("No home directory"))?
+                        .join(".gitconfig"),
+                };
+                Ok(Config::open(&path)?)
+            }
+            IdentityScope::Repository => {
+                let repo = repo.ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+                Ok(repo.config()?.open_level(ConfigLevel::Local)?)
+            }
+        }
+    }
+}
+
+/// Author name and email, either as set in git config or as an override
+/// that replaces `user.name` and `user.email` for commits made from the app
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Identity {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+impl Identity {
+    /// Identity from text fields, where an empty field means unset
+    pub fn new(name: &str, email: &str) -> Self {
+        let field = |value: &str| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        Self {
+            name: field(name),
+            email: field(email),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.email.is_none()
+    }
+
+    /// `user.name` and `user.email` from a single config level
+    pub fn read_config(config: &Config) -> Self {
+        let get = |key| config.get_string(key).ok().filter(|v| !v.is_empty());
+        Self {
+            name: get("user.name"),
+            email: get("user.email"),
+        }
+    }
+
+    /// Write `user.name` and `user.email`, removing the keys left unset
+    pub fn write_config(&self, config: &mut Config) -> Result<()> {
+        for (key, value) in [("user.name", &self.name), ("user.email", &self.email)] {
+            match value {
+                Some(value) => config.set_str(key, value)?,
+                None => {
+                    if let Err(e) = config.remove(key) {
+                        if e.code() != ErrorCode::NotFound {
+                            return Err(e.into());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Make `repo.signature()` use this identity for this handle only. The
+    /// values are written to a config file in the git directory that is
+    /// added at the app level, which outranks the local, global and system
+    /// levels; the repository's own config is left untouched.
+    pub fn apply(&self, repo: &Repository) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let path = repo.path().join(OVERRIDE_FILE);
+        let contents = self.config_text();
+        if fs::read_to_string(&path).ok().as_deref() != Some(contents.as_str()) {
+            fs::write(&path, &contents)?;
+        }
+
+        repo.config()?.add_file(&path, ConfigLevel::App, true)?;
+        Ok(())
+    }
+
+    fn config_text(&self) -> String {
+        let mut text = String::from("[user]\n");
+        if let Some(name) = &self.name {
+            text.push_str(&format!("\tname = {}\n", quote(name)));
+        }
+        if let Some(email) = &self.email {
+            text.push_str(&format!("\temail = {}\n", quote(email)));
+        }
+        text
+    }
+}
+
+/// Quote a config value so spaces, `#` and `;` survive
+fn quote(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{}\"", escaped)
+}
+
+/// The signature commits are made with, explaining where to set it when no
+/// identity is configured instead of libgit2's bare "config value not found"
+pub fn commit_signature(repo: &Repository) -> Result<Signature<'static>> {
+    repo.signature().map_err(|e| match e.code() {
+        ErrorCode::NotFound => anyhow::anyhow!(
+            "No commit author configured: set your name and email under Git Identity in Settings"
+        ),
+        _ => e.into(),
+    })
+}
@@ -0,0 +1,100 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::Repository;
+use std::fs;
+
+/// A `.gitignore` rule covering an untracked path
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IgnorePattern {
+    /// Just this file, anchored to the repository root
+    File(String),
+    /// Every file with this extension, anywhere
+    Extension(String),
+    /// This directory and everything below it, anchored to the root
+    Directory(String),
+}
+
+impl IgnorePattern {
+    /// The rules that can ignore `path`: the file itself, then its extension
+    /// and parent directory when it has them
+    pub fn options_for(path: &str) -> Vec<Self> {
+        let path = path.trim_end_matches('/');
+        let mut options = vec![IgnorePattern::File(path.to_string())];
+
+        let name = path.rsplit('/').next().unwrap_or(path);
+        if let Some((stem, ext)) = name.rsplit_once('.') {
+            // ".env" is a name, not an extension
+            if !stem.is_empty() && !ext.is_empty() {
+                options.push(IgnorePattern::Extension(ext.to_string()));
+            }
+        }
+        if let Some((dir, _)) = path.rsplit_once('/') {
+            options.push(IgnorePattern::Directory(dir.to_string()));
+        }
+
+        options
+    }
+
+    /// Menu label, e.g. "Ignore *.log"
+    pub fn label(&self) -> String {
+        match self {
+            IgnorePattern::File(_) => "Ignore this file".to_string(),
+            IgnorePattern::Extension(ext) => format!("Ignore all *.{} files", ext),
+            IgnorePattern::Directory(dir) => format!("Ignore {}/", dir),
+        }
+    }
+
+    /// The line written to `.gitignore`
+    pub fn rule(&self) -> String {
+        match self {
+            IgnorePattern::File(path) => format!("/{}", escape(path)),
+            IgnorePattern::Extension(ext) => format!("*.{}", escape(ext)),
+            IgnorePattern::Directory(dir) => format!("/{}/", escape(dir)),
+        }
+    }
+}
+
+/// Escape glob characters and trailing spaces so the rule matches the path
+/// literally
+fn escape(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for c in path.chars() {
+        if matches!(c, '*' | '?' | '[' | '\\' | '!' | '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    if escaped.ends_with(' ') {
+        escaped.pop();
+        escaped.push_str("\\ ");
+    }
+    escaped
+}
+
+/// Append `pattern` to the `.gitignore` at the repository root, creating it
+/// if needed. Does nothing if the rule is already there.
+pub fn add_to_gitignore(repo: &Repository, pattern: &IgnorePattern) -> Result<()> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow::anyhow!("Repository has no working directory"))?;
+    let path = workdir.join(".gitignore");
+    let rule = pattern.rule();
+
+    let mut contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    if contents.lines().any(|line| line.trim_end() == rule) {
+        return Ok(());
+    }
+
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&rule);
+    contents.push('\n');
+    fs::write(&path, contents)?;
+    Ok(())
+}
@@ -0,0 +1,161 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::{Oid, Repository, RepositoryState, ResetType};
+
+use super::ConflictInfo;
+
+/// What the repository was left in the middle of, other than a cherry-pick
+/// or revert (see `InterruptedPick`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterruptedKind {
+    Merge,
+    Rebase,
+    Bisect,
+    ApplyMailbox,
+}
+
+/// A merge, rebase, bisect or `git am` that was still running when the
+/// repository was opened, usually started from the git CLI
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterruptedOperation {
+    pub kind: InterruptedKind,
+    /// Message `git merge` saved for the merge commit
+    pub merge_message: Option<String>,
+    /// Branch the rebase or bisect started from
+    pub head_name: Option<String>,
+}
+
+impl InterruptedOperation {
+    pub fn get(repo: &Repository) -> Result<Option<Self>> {
+        let kind = match repo.state() {
+            RepositoryState::Merge => InterruptedKind::Merge,
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => InterruptedKind::Rebase,
+            RepositoryState::Bisect => InterruptedKind::Bisect,
+            RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => {
+                InterruptedKind::ApplyMailbox
+            }
+            _ => return Ok(None),
+        };
+
+        let merge_message = match kind {
+            InterruptedKind::Merge => read_merge_message(repo),
+            _ => None,
+        };
+        let head_name = match kind {
+            InterruptedKind::Rebase => rebase_file(repo, "head-name"),
+            InterruptedKind::Bisect => read_trimmed(repo, "BISECT_START"),
+            InterruptedKind::Merge | InterruptedKind::ApplyMailbox => None,
+        }
+        .map(|name| name.strip_prefix("refs/heads/").unwrap_or(&name).to_string());
+
+        Ok(Some(Self {
+            kind,
+            merge_message,
+            head_name,
+        }))
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self.kind {
+            InterruptedKind::Merge => "Merge",
+            InterruptedKind::Rebase => "Rebase",
+            InterruptedKind::Bisect => "Bisect",
+            InterruptedKind::ApplyMailbox => "Patch application",
+        }
+    }
+
+    /// Only a merge can be finished from the app; the rest need the git CLI
+    /// or can be aborted
+    pub fn can_commit(&self) -> bool {
+        self.kind == InterruptedKind::Merge
+    }
+
+    /// Commit the merge with the message git saved for it
+    pub fn commit(&self, repo: &Repository) -> Result<()> {
+        if !self.can_commit() {
+            anyhow::bail!("A {} can't be finished here", self.label().to_lowercase());
+        }
+        ConflictInfo::complete_merge(repo, self.merge_message.as_deref())
+    }
+
+    /// Go back to where the operation started, like `git <op> --abort`
+    /// (`git bisect reset` for a bisect)
+    pub fn abort(&self, repo: &Repository) -> Result<()> {
+        match self.kind {
+            InterruptedKind::Merge => ConflictInfo::abort_merge(repo),
+            InterruptedKind::Rebase | InterruptedKind::ApplyMailbox => {
+                let orig_head = rebase_file(repo, "orig-head")
+                    .or_else(|| read_trimmed(repo, "ORIG_HEAD"))
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Can't find where the {} started", self.label())
+                    })?;
+                // Read before resetting, which clears the rebase state
+                let head_name = rebase_file(repo, "head-name")
+                    .filter(|name| name.starts_with("refs/heads/"));
+                let commit = repo.find_commit(Oid::from_str(&orig_head)?)?;
+                repo.reset(commit.as_object(), ResetType::Hard, None)?;
+
+                // A rebase detaches HEAD; put the branch back where it was
+                if let Some(head_name) = head_name {
+                    repo.reference(&head_name, commit.id(), true, "rebase: aborting")?;
+                    repo.set_head(&head_name)?;
+                }
+                repo.cleanup_state()?;
+                Ok(())
+            }
+            InterruptedKind::Bisect => {
+                let start = read_trimmed(repo, "BISECT_START")
+                    .ok_or_else(|| anyhow::anyhow!("Can't find where the bisect started"))?;
+                let branch = format!("refs/heads/{}", start);
+                if let Ok(reference) = repo.find_reference(&branch) {
+                    let tree = reference.peel_to_tree()?;
+                    repo.checkout_tree(tree.as_object(), None)?;
+                    repo.set_head(&branch)?;
+                } else {
+                    let commit = repo.find_commit(Oid::from_str(&start)?)?;
+                    repo.checkout_tree(commit.as_object(), None)?;
+                    repo.set_head_detached(commit.id())?;
+                }
+
+                for mut reference in repo.references_glob("refs/bisect/*")?.flatten() {
+                    reference.delete()?;
+                }
+                // cleanup_state only removes BISECT_LOG
+                for file in ["BISECT_START", "BISECT_TERMS", "BISECT_NAMES", "BISECT_EXPECTED_REV"]
+                {
+                    let _ = std::fs::remove_file(repo.path().join(file));
+                }
+                repo.cleanup_state()?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// MERGE_MSG without git's comment lines
+fn read_merge_message(repo: &Repository) -> Option<String> {
+    let content = std::fs::read_to_string(repo.path().join("MERGE_MSG")).ok()?;
+    let message = content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let message = message.trim();
+    (!message.is_empty()).then(|| message.to_string())
+}
+
+fn read_trimmed(repo: &Repository, file: &str) -> Option<String> {
+    let content = std::fs::read_to_string(repo.path().join(file)).ok()?;
+    let content = content.trim();
+    (!content.is_empty()).then(|| content.to_string())
+}
+
+/// A file from whichever of `rebase-merge` or `rebase-apply` git is using
+fn rebase_file(repo: &Repository, name: &str) -> Option<String> {
+    ["rebase-merge", "rebase-apply"]
+        .iter()
+        .find_map(|dir| read_trimmed(repo, &format!("{}/{}", dir, name)))
+}
@@ -0,0 +1,270 @@
+#![allow(dead_code)]
+
+use super::{is_binary_file, DiffLineType, FileDiff};
+use anyhow::{bail, Context as _, Result};
+use git2::{AttrCheckFlags, Repository};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// First line of every Git LFS pointer file
+pub const LFS_POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+
+/// Pointer files are always smaller than this; larger files are never read
+/// in full to check
+const MAX_POINTER_SIZE: u64 = 1024;
+
+/// Files matching an LFS pattern at least this big are warned about when
+/// staged as regular blobs
+pub const LFS_WARN_SIZE: u64 = 1024 * 1024;
+
+/// Contents of a Git LFS pointer file, which stands in for a large file in
+/// the repository
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LfsPointer {
+    /// SHA-256 of the actual file
+    pub oid: String,
+    /// Size of the actual file in bytes
+    pub size: u64,
+}
+
+impl LfsPointer {
+    /// Parse pointer file content; `None` if it isn't a pointer
+    pub fn parse(content: &[u8]) -> Option<Self> {
+        if content.len() as u64 >= MAX_POINTER_SIZE {
+            return None;
+        }
+        let text = std::str::from_utf8(content).ok()?;
+        let mut lines = text.lines();
+        if lines.next()?.trim_end() != LFS_POINTER_VERSION {
+            return None;
+        }
+
+        let (mut oid, mut size) = (None, None);
+        for line in lines {
+            match line.split_once(' ') {
+                Some(("oid", value)) => oid = value.strip_prefix("sha256:").map(str::to_string),
+                Some(("size", value)) => size = value.trim().parse().ok(),
+                _ => {}
+            }
+        }
+        let oid = oid.filter(|oid| oid.len() == 64 && oid.chars().all(|c| c.is_ascii_hexdigit()));
+        Some(Self {
+            oid: oid?,
+            size: size?,
+        })
+    }
+
+    /// Pointer stored in a working tree file, if it is one
+    pub fn read(path: &Path) -> Option<Self> {
+        let mut content = Vec::new();
+        File::open(path)
+            .ok()?
+            .take(MAX_POINTER_SIZE)
+            .read_to_end(&mut content)
+            .ok()?;
+        Self::parse(&content)
+    }
+
+    /// Where the object is kept in the local LFS store
+    pub fn object_path(&self, repo: &Repository) -> PathBuf {
+        repo.path()
+            .join("lfs/objects")
+            .join(&self.oid[..2])
+            .join(&self.oid[2..4])
+            .join(&self.oid)
+    }
+
+    /// The object has been downloaded to the local LFS store
+    pub fn is_downloaded(&self, repo: &Repository) -> bool {
+        self.object_path(repo).is_file()
+    }
+}
+
+/// Pointers before and after a change to a file stored with Git LFS. A side
+/// is `None` when the file was added or deleted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LfsChange {
+    pub old: Option<LfsPointer>,
+    pub new: Option<LfsPointer>,
+}
+
+impl LfsChange {
+    /// The pointers a diff changes between, if it is the diff of a pointer
+    /// file. Pointers are three lines, so the hunk always covers all of them.
+    pub fn from_diff(diff: &FileDiff) -> Option<Self> {
+        let side = |changed: DiffLineType| -> String {
+            diff.lines
+                .iter()
+                .filter(|line| line.line_type == DiffLineType::Context || line.line_type == changed)
+                .map(|line| line.content.as_str())
+                .collect()
+        };
+        let (old_text, new_text) = (side(DiffLineType::Deletion), side(DiffLineType::Addition));
+        let old = LfsPointer::parse(old_text.as_bytes());
+        let new = LfsPointer::parse(new_text.as_bytes());
+
+        // The other side must be a pointer too, or missing entirely
+        let old_ok = old.is_some() || old_text.is_empty();
+        let new_ok = new.is_some() || new_text.is_empty();
+        ((old.is_some() || new.is_some()) && old_ok && new_ok).then_some(Self { old, new })
+    }
+}
+
+/// Git LFS details of a changed file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LfsFile {
+    /// Set when the working tree holds a pointer instead of the file
+    pub pointer: Option<LfsPointer>,
+    /// Size of the actual file: the size the pointer records, or the size in
+    /// the working tree
+    pub size: u64,
+    /// The content is available locally
+    pub downloaded: bool,
+}
+
+impl LfsFile {
+    /// LFS details of a working tree file, or `None` when no `filter=lfs`
+    /// attribute applies to it
+    pub fn get(repo: &Repository, path: &str) -> Option<Self> {
+        if !is_lfs_tracked(repo, path) {
+            return None;
+        }
+        let full_path = repo.workdir()?.join(path);
+        let size = full_path.metadata().map(|m| m.len()).unwrap_or(0);
+
+        Some(match LfsPointer::read(&full_path) {
+            Some(pointer) => Self {
+                size: pointer.size,
+                downloaded: pointer.is_downloaded(repo),
+                pointer: Some(pointer),
+            },
+            None => Self {
+                pointer: None,
+                size,
+                downloaded: true,
+            },
+        })
+    }
+}
+
+/// `.gitattributes` sends the path through the LFS filter
+pub fn is_lfs_tracked(repo: &Repository, path: &str) -> bool {
+    repo.get_attr(Path::new(path), "filter", AttrCheckFlags::FILE_THEN_INDEX)
+        .ok()
+        .flatten()
+        == Some("lfs")
+}
+
+/// Whether the repository uses Git LFS at all, so the rest of the checks can
+/// be skipped cheaply for the many repositories that don't
+pub fn uses_lfs(repo: &Repository) -> bool {
+    if repo.path().join("lfs").is_dir() {
+        return true;
+    }
+    repo.workdir()
+        .and_then(|dir| std::fs::read_to_string(dir.join(".gitattributes")).ok())
+        .is_some_and(|attributes| attributes.contains("filter=lfs"))
+}
+
+/// Tracked files whose working tree copy is still a pointer because the
+/// object was never downloaded, e.g. after cloning without Git LFS
+/// installed
+pub fn missing_lfs_objects(repo: &Repository) -> Result<Vec<String>> {
+    if !uses_lfs(repo) {
+        return Ok(Vec::new());
+    }
+    let Some(workdir) = repo.workdir() else {
+        return Ok(Vec::new());
+    };
+
+    let index = repo.index()?;
+    let mut missing = Vec::new();
+    for entry in index.iter() {
+        // Only pointers are small enough to be worth opening
+        if u64::from(entry.file_size) >= MAX_POINTER_SIZE {
+            continue;
+        }
+        let path = String::from_utf8_lossy(&entry.path).to_string();
+        if !is_lfs_tracked(repo, &path) {
+            continue;
+        }
+        if let Some(pointer) = LfsPointer::read(&workdir.join(&path)) {
+            if !pointer.is_downloaded(repo) {
+                missing.push(path);
+            }
+        }
+    }
+    Ok(missing)
+}
+
+/// Why staging a working tree file would be a mistake: it matches an LFS
+/// pattern but is a large or binary file rather than a pointer, and libgit2
+/// doesn't run the LFS filter, so it would be stored as a regular blob
+pub fn lfs_staging_warning(repo: &Repository, path: &str) -> Option<String> {
+    let file = LfsFile::get(repo, path)?;
+    if file.pointer.is_some() {
+        return None;
+    }
+    let full_path = repo.workdir()?.join(path);
+    if file.size < LFS_WARN_SIZE && !is_binary_file(&full_path) {
+        return None;
+    }
+    Some(format!(
+        "{} ({}) matches a Git LFS pattern but would be committed as a regular file",
+        path,
+        format_size(file.size)
+    ))
+}
+
+/// Stage files with `git add`, which runs the LFS clean filter so pointers
+/// are stored instead of the content
+pub fn stage_with_lfs(repo: &Repository, paths: &[String]) -> Result<()> {
+    let workdir = repo.workdir().context("The repository has no working tree")?;
+    let output = Command::new("git")
+        .arg("add")
+        .arg("--")
+        .args(paths)
+        .current_dir(workdir)
+        .output()
+        .context("running git add")?;
+    if !output.status.success() {
+        bail!("git add failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+/// Download missing LFS objects for the checked out commit and replace the
+/// pointers in the working tree with their content, like `git lfs pull`
+pub fn lfs_pull(repo: &Repository) -> Result<()> {
+    let workdir = repo.workdir().context("The repository has no working tree")?;
+    let output = Command::new("git")
+        .args(["lfs", "pull"])
+        .current_dir(workdir)
+        .output()
+        .context("running git lfs pull; is Git LFS installed?")?;
+    if !output.status.success() {
+        bail!(
+            "git lfs pull failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Byte count in the largest unit that keeps it at least 1, e.g. "4.2 MB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
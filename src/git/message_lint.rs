@@ -0,0 +1,218 @@
+#![allow(dead_code)]
+
+/// Longest subject line before it gets truncated in `git log --oneline`,
+/// GitHub and most other tools
+pub const MAX_SUBJECT_LENGTH: usize = 72;
+
+/// Verbs commit subjects commonly start with, in the imperative mood. Their
+/// "-s", "-ed" and "-ing" forms are flagged.
+const COMMON_VERBS: &[&str] = &[
+    "add", "adjust", "allow", "apply", "avoid", "build", "bump", "change", "check", "clean",
+    "close", "convert", "correct", "create", "delete", "deprecate", "disable", "display",
+    "document", "drop", "enable", "ensure", "extract", "fix", "handle", "hide", "implement",
+    "improve", "introduce", "load", "make", "merge", "move", "open", "prevent", "reduce",
+    "refactor", "release", "remove", "rename", "replace", "return", "revert", "rework", "save",
+    "show", "simplify", "skip", "speed", "stop", "support", "update", "upgrade", "use",
+];
+
+/// Problem found in a commit message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintWarning {
+    /// Name of the rule that raised it
+    pub rule: &'static str,
+    pub message: String,
+}
+
+/// A check commit messages are run through
+pub trait MessageRule: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Warnings for `message`, whose first line is the subject
+    fn check(&self, message: &str) -> Vec<String>;
+}
+
+/// Runs commit messages through a list of rules
+pub struct MessageLinter {
+    rules: Vec<Box<dyn MessageRule>>,
+}
+
+impl Default for MessageLinter {
+    /// The built-in rules
+    fn default() -> Self {
+        Self::new()
+            .with_rule(SubjectLength(MAX_SUBJECT_LENGTH))
+            .with_rule(SubjectTrailingPeriod)
+            .with_rule(ImperativeSubject)
+            .with_rule(BlankLineAfterSubject)
+    }
+}
+
+impl MessageLinter {
+    /// A linter without any rules
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    pub fn with_rule(mut self, rule: impl MessageRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Warnings of every rule, in rule order. Empty messages and the
+    /// messages git generates for merges, reverts and fixups are not checked.
+    pub fn lint(&self, message: &str) -> Vec<LintWarning> {
+        let subject = message.lines().next().unwrap_or("").trim();
+        let generated = ["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "];
+        if subject.is_empty() || generated.iter().any(|prefix| subject.starts_with(prefix)) {
+            return Vec::new();
+        }
+        self.rules
+            .iter()
+            .flat_map(|rule| {
+                rule.check(message).into_iter().map(|message| LintWarning {
+                    rule: rule.name(),
+                    message,
+                })
+            })
+            .collect()
+    }
+}
+
+fn subject(message: &str) -> &str {
+    message.lines().next().unwrap_or("").trim_end()
+}
+
+/// The subject is at most this many characters long
+pub struct SubjectLength(pub usize);
+
+impl MessageRule for SubjectLength {
+    fn name(&self) -> &'static str {
+        "subject-length"
+    }
+
+    fn check(&self, message: &str) -> Vec<String> {
+        let length = subject(message).chars().count();
+        if length > self.0 {
+            vec![format!("Subject is {} characters; keep it to {}", length, self.0)]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// The subject doesn't end with a period
+pub struct SubjectTrailingPeriod;
+
+impl MessageRule for SubjectTrailingPeriod {
+    fn name(&self) -> &'static str {
+        "subject-period"
+    }
+
+    fn check(&self, message: &str) -> Vec<String> {
+        let subject = subject(message);
+        // An ellipsis is deliberate
+        if subject.ends_with('.') && !subject.ends_with("..") {
+            vec!["Subject ends with a period".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// The subject starts with a verb in the imperative mood ("Add", not
+/// "Added" or "Adds"), after an optional `type(scope):` prefix
+pub struct ImperativeSubject;
+
+impl MessageRule for ImperativeSubject {
+    fn name(&self) -> &'static str {
+        "imperative-mood"
+    }
+
+    fn check(&self, message: &str) -> Vec<String> {
+        let subject = subject(message);
+        // Conventional commits put the verb after "feat(ui): "
+        let subject = match subject.split_once(": ") {
+            Some((prefix, rest)) if !prefix.contains(' ') => rest,
+            _ => subject,
+        };
+        let Some(word) = subject.split_whitespace().next() else {
+            return Vec::new();
+        };
+        let word = word.trim_end_matches(|c: char| !c.is_alphanumeric());
+        match imperative_of(&word.to_lowercase()) {
+            Some(verb) => vec![format!(
+                "Use the imperative mood: \"{}\" instead of \"{}\"",
+                capitalize_like(verb, word),
+                word
+            )],
+            None => Vec::new(),
+        }
+    }
+}
+
+/// The verb `word` is the "-s", "-ed" or "-ing" form of
+fn imperative_of(word: &str) -> Option<&'static str> {
+    COMMON_VERBS
+        .iter()
+        .copied()
+        .find(|verb| *verb != word && inflections(verb).iter().any(|form| form == word))
+}
+
+/// Third person, past and present participle forms of a verb, with and
+/// without a doubled final consonant ("dropped", "stopping")
+fn inflections(verb: &str) -> Vec<String> {
+    let last = verb.chars().last().unwrap_or_default();
+    let consonant_y = verb.ends_with('y')
+        && !verb[..verb.len() - 1].ends_with(|c: char| "aeiou".contains(c));
+    let mut forms = Vec::new();
+
+    if ["s", "x", "z", "ch", "sh"].iter().any(|end| verb.ends_with(end)) {
+        forms.push(format!("{}es", verb));
+    } else if consonant_y {
+        forms.push(format!("{}ies", &verb[..verb.len() - 1]));
+    } else {
+        forms.push(format!("{}s", verb));
+    }
+
+    if let Some(stem) = verb.strip_suffix('e') {
+        forms.push(format!("{}d", verb));
+        forms.push(format!("{}ing", stem));
+    } else if consonant_y {
+        forms.push(format!("{}ied", &verb[..verb.len() - 1]));
+        forms.push(format!("{}ing", verb));
+    } else {
+        forms.push(format!("{}ed", verb));
+        forms.push(format!("{}ing", verb));
+        forms.push(format!("{}{}ed", verb, last));
+        forms.push(format!("{}{}ing", verb, last));
+    }
+    forms
+}
+
+/// `verb` with the first letter in the case of `like`'s
+fn capitalize_like(verb: &str, like: &str) -> String {
+    let upper = like.chars().next().is_some_and(char::is_uppercase);
+    let mut chars = verb.chars();
+    match chars.next() {
+        Some(first) if upper => first.to_uppercase().chain(chars).collect(),
+        _ => verb.to_string(),
+    }
+}
+
+/// A body is separated from the subject by a blank line
+pub struct BlankLineAfterSubject;
+
+impl MessageRule for BlankLineAfterSubject {
+    fn name(&self) -> &'static str {
+        "blank-line"
+    }
+
+    fn check(&self, message: &str) -> Vec<String> {
+        match message.lines().nth(1) {
+            Some(line) if !line.trim().is_empty() => {
+                vec!["Leave a blank line between the subject and the body".to_string()]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
@@ -1,18 +1,75 @@
+pub mod archive;
+pub mod author;
+pub mod backend;
+pub mod binary;
+pub mod blame;
 pub mod branch;
 pub mod commit;
+pub mod compare;
 pub mod conflict;
+pub mod conflict_markers;
 pub mod diff;
+pub mod graph_layout;
+pub mod hooks;
+pub mod hosting;
+pub mod identity;
+pub mod ignore;
+pub mod interrupted;
+pub mod lfs;
+pub mod message_lint;
+pub mod patch;
+pub mod pickaxe;
+pub mod preview;
+pub mod project;
+pub mod reflog;
 pub mod remote;
 pub mod repository;
+pub mod search;
+pub mod sequencer;
+pub mod signature;
 pub mod stash;
 pub mod status;
 pub mod tag;
+pub mod trash;
+pub mod todo;
+pub mod worktree;
 
+pub use archive::*;
+pub use author::*;
+pub use backend::*;
+pub use binary::*;
+pub use blame::*;
 pub use branch::*;
 pub use commit::*;
+pub use compare::*;
 pub use conflict::*;
+pub use conflict_markers::*;
 pub use diff::*;
+pub use graph_layout::*;
+pub use hooks::*;
+pub use hosting::*;
+pub use identity::*;
+pub use ignore::*;
+pub use interrupted::*;
+pub use lfs::*;
+pub use message_lint::*;
+pub use patch::*;
+pub use pickaxe::*;
+pub use preview::*;
+pub use project::*;
+pub use reflog::*;
 pub use repository::*;
+pub use search::*;
+pub use sequencer::*;
+pub use signature::*;
 pub use stash::*;
 pub use status::*;
 pub use tag::*;
+pub use trash::*;
+pub use todo::*;
+pub use worktree::*;
+
+#[cfg(test)]
+pub(crate) mod test_repo;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,236 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, Utc};
+use git2::{Commit, Diff, DiffFormat, DiffOptions, DiffStatsFormat, Oid, Repository};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use super::commit_signature;
+
+/// Signature line closing each message, where `git format-patch` puts its version
+const SIGNATURE: &str = concat!("Awabancha ", env!("CARGO_PKG_VERSION"));
+
+/// Width diffstats are wrapped to, as `git format-patch` does
+const STAT_WIDTH: usize = 72;
+
+/// How a commit range is written out as email patches
+#[derive(Clone, Debug)]
+pub struct MboxOptions {
+    /// Prepend a "[PATCH 0/n]" cover letter with a shortlog and diffstat
+    pub cover_letter: bool,
+    /// Text in the subject brackets, e.g. "PATCH" or "RFC PATCH"
+    pub subject_prefix: String,
+}
+
+impl Default for MboxOptions {
+    fn default() -> Self {
+        Self {
+            cover_letter: false,
+            subject_prefix: "PATCH".to_string(),
+        }
+    }
+}
+
+impl MboxOptions {
+    /// Format `shas` (oldest first) as one mbox that `git am` can apply.
+    /// Merge commits are refused since they have no single patch.
+    pub fn export(&self, repo: &Repository, shas: &[String]) -> Result<String> {
+        if shas.is_empty() {
+            anyhow::bail!("No commits to export");
+        }
+
+        let commits = shas
+            .iter()
+            .map(|sha| repo.find_commit(Oid::from_str(sha)?).map_err(Into::into))
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(merge) = commits.iter().find(|c| c.parent_count() > 1) {
+            anyhow::bail!("Cannot export merge commit {} as a patch", short(merge.id()));
+        }
+
+        let total = commits.len();
+        let mut mbox = String::new();
+        if self.cover_letter {
+            self.write_cover_letter(repo, &commits, &mut mbox)?;
+        }
+        for (index, commit) in commits.iter().enumerate() {
+            self.write_patch(repo, commit, index + 1, total, &mut mbox)?;
+        }
+        Ok(mbox)
+    }
+
+    fn subject_tag(&self, number: usize, total: usize) -> String {
+        // A single patch is unnumbered unless a cover letter comes first
+        if total == 1 && !self.cover_letter {
+            return self.subject_prefix.clone();
+        }
+        let width = total.to_string().len();
+        format!("{} {:0width$}/{}", self.subject_prefix, number, total)
+    }
+
+    fn write_patch(
+        &self,
+        repo: &Repository,
+        commit: &Commit,
+        number: usize,
+        total: usize,
+        mbox: &mut String,
+    ) -> Result<()> {
+        let parent_tree = match commit.parent_count() {
+            0 => None,
+            _ => Some(commit.parent(0)?.tree()?),
+        };
+        let diff = tree_diff(repo, parent_tree.as_ref(), &commit.tree()?)?;
+
+        let author = commit.author();
+        let subject = format!(
+            "[{}] {}",
+            self.subject_tag(number, total),
+            commit.summary().unwrap_or_default()
+        );
+        write_headers(
+            mbox,
+            commit.id(),
+            &mailbox(&author),
+            &format_date(author.when()),
+            &subject,
+        )?;
+
+        let body = commit.body().unwrap_or_default().trim_end();
+        if !body.is_empty() {
+            writeln!(mbox, "{}", body)?;
+        }
+        writeln!(mbox, "---")?;
+        mbox.push_str(&diffstat(&diff)?);
+        writeln!(mbox)?;
+        mbox.push_str(&patch_text(&diff)?);
+        write_signature(mbox)
+    }
+
+    fn write_cover_letter(
+        &self,
+        repo: &Repository,
+        commits: &[Commit],
+        mbox: &mut String,
+    ) -> Result<()> {
+        let first = &commits[0];
+        let last = &commits[commits.len() - 1];
+        let base_tree = match first.parent_count() {
+            0 => None,
+            _ => Some(first.parent(0)?.tree()?),
+        };
+        let diff = tree_diff(repo, base_tree.as_ref(), &last.tree()?)?;
+
+        let sender = commit_signature(repo)?;
+        let subject = format!(
+            "[{}] *** SUBJECT HERE ***",
+            self.subject_tag(0, commits.len())
+        );
+        write_headers(
+            mbox,
+            Oid::zero(),
+            &mailbox(&sender),
+            &format_date(sender.when()),
+            &subject,
+        )?;
+        writeln!(mbox, "*** BLURB HERE ***")?;
+        writeln!(mbox)?;
+
+        // Shortlog: summaries grouped by author
+        let mut authors: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for commit in commits {
+            let name = commit.author().name().unwrap_or_default().to_string();
+            let summary = commit.summary().unwrap_or_default().to_string();
+            authors.entry(name).or_default().push(summary);
+        }
+        for (name, summaries) in &authors {
+            writeln!(mbox, "{} ({}):", name, summaries.len())?;
+            for summary in summaries {
+                writeln!(mbox, "  {}", summary)?;
+            }
+            writeln!(mbox)?;
+        }
+
+        mbox.push_str(&diffstat(&diff)?);
+        writeln!(mbox)?;
+        write_signature(mbox)
+    }
+}
+
+fn tree_diff<'a>(
+    repo: &'a Repository,
+    old: Option<&git2::Tree>,
+    new: &git2::Tree,
+) -> Result<Diff<'a>> {
+    let mut opts = DiffOptions::new();
+    // `git am` needs binary contents to apply binary changes
+    opts.show_binary(true);
+    Ok(repo.diff_tree_to_tree(old, Some(new), Some(&mut opts))?)
+}
+
+fn write_headers(
+    mbox: &mut String,
+    id: Oid,
+    from: &str,
+    date: &str,
+    subject: &str,
+) -> Result<()> {
+    // The fixed date marks the line as a format-patch separator, not a real date
+    writeln!(mbox, "From {} Mon Sep 17 00:00:00 2001", id)?;
+    writeln!(mbox, "From: {}", from)?;
+    writeln!(mbox, "Date: {}", date)?;
+    writeln!(mbox, "Subject: {}", subject)?;
+    writeln!(mbox, "MIME-Version: 1.0")?;
+    writeln!(mbox, "Content-Type: text/plain; charset=UTF-8")?;
+    writeln!(mbox, "Content-Transfer-Encoding: 8bit")?;
+    writeln!(mbox)?;
+    Ok(())
+}
+
+fn write_signature(mbox: &mut String) -> Result<()> {
+    writeln!(mbox, "-- ")?;
+    writeln!(mbox, "{}", SIGNATURE)?;
+    writeln!(mbox)?;
+    Ok(())
+}
+
+fn mailbox(signature: &git2::Signature) -> String {
+    format!(
+        "{} <{}>",
+        signature.name().unwrap_or_default(),
+        signature.email().unwrap_or_default()
+    )
+}
+
+/// RFC 2822 date in the signature's own timezone
+fn format_date(time: git2::Time) -> String {
+    let offset = FixedOffset::east_opt(time.offset_minutes() * 60)
+        .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+    DateTime::<Utc>::from_timestamp(time.seconds(), 0)
+        .unwrap_or_default()
+        .with_timezone(&offset)
+        .to_rfc2822()
+}
+
+fn diffstat(diff: &Diff) -> Result<String> {
+    let format = DiffStatsFormat::FULL | DiffStatsFormat::INCLUDE_SUMMARY;
+    let stats = diff.stats()?.to_buf(format, STAT_WIDTH)?;
+    Ok(stats.as_str().unwrap_or_default().to_string())
+}
+
+fn patch_text(diff: &Diff) -> Result<String> {
+    let mut text = String::new();
+    diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+        // File and hunk headers carry their own text; content lines need the origin
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            text.push(line.origin());
+        }
+        text.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })?;
+    Ok(text)
+}
+
+fn short(id: Oid) -> String {
+    id.to_string()[..7].to_string()
+}
@@ -0,0 +1,64 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::{DiffFormat, DiffOptions, Oid, Repository};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::{CommitInfo, GraphFilter};
+
+/// Commits a pickaxe search looks at, newest first: the history the graph
+/// walks for `filter`, without its per-commit criteria
+pub fn pickaxe_candidates(repo: &Repository, filter: &GraphFilter) -> Result<Vec<Oid>> {
+    Ok(filter.revwalk(repo)?.flatten().collect())
+}
+
+/// Commits among `oids` that change how often `needle` occurs in a file, as
+/// `git log -S` does. Only single-line needles are found, and binary files
+/// are skipped.
+pub fn pickaxe_matches(repo: &Repository, oids: &[Oid], needle: &str) -> Result<Vec<CommitInfo>> {
+    if needle.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let empty = HashMap::new();
+    let mut matches = Vec::new();
+    for oid in oids {
+        let commit = repo.find_commit(*oid)?;
+        if changes_occurrences(repo, &commit, needle)? {
+            matches.push(CommitInfo::from_commit(&commit, &empty, &empty, &empty));
+        }
+    }
+    Ok(matches)
+}
+
+/// Whether `commit` adds or removes occurrences of `needle` compared to its
+/// first parent. Unchanged lines count the same on both sides, so comparing
+/// the added and removed lines per file is enough.
+fn changes_occurrences(repo: &Repository, commit: &git2::Commit, needle: &str) -> Result<bool> {
+    let tree = commit.tree()?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let mut options = DiffOptions::new();
+    options.context_lines(0);
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))?;
+
+    // Occurrences added minus occurrences removed, by file
+    let mut balance: HashMap<Option<PathBuf>, i64> = HashMap::new();
+    diff.print(DiffFormat::Patch, |delta, _hunk, line| {
+        let sign = match line.origin() {
+            '+' => 1,
+            '-' => -1,
+            _ => return true,
+        };
+        let count = String::from_utf8_lossy(line.content()).matches(needle).count() as i64;
+        if count > 0 {
+            let path = delta.new_file().path().or(delta.old_file().path());
+            *balance.entry(path.map(PathBuf::from)).or_default() += sign * count;
+        }
+        true
+    })?;
+    Ok(balance.values().any(|b| *b != 0))
+}
@@ -0,0 +1,259 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::{Delta, Repository, StatusOptions, Tree};
+
+use super::{CommitInfo, FileStatusType, ResetMode};
+
+/// Maximum number of commits listed in a preview
+const PREVIEW_COMMIT_LIMIT: usize = 200;
+
+/// An operation that can be previewed before it runs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PreviewOperation {
+    /// Reset HEAD to a commit
+    Reset { sha: String, mode: ResetMode },
+    /// Merge a commit into HEAD
+    Merge { sha: String },
+    /// Replay the commits of HEAD onto another commit
+    Rebase { onto: String },
+    /// Delete untracked files
+    Clean,
+}
+
+impl PreviewOperation {
+    pub fn title(&self) -> String {
+        match self {
+            PreviewOperation::Reset { sha, mode } => {
+                let mode = match mode {
+                    ResetMode::Soft => "soft",
+                    ResetMode::Mixed => "mixed",
+                    ResetMode::Hard => "hard",
+                };
+                format!("Reset ({}) to {}", mode, short(sha))
+            }
+            PreviewOperation::Merge { sha } => format!("Merge {} into HEAD", short(sha)),
+            PreviewOperation::Rebase { onto } => format!("Rebase HEAD onto {}", short(onto)),
+            PreviewOperation::Clean => "Clean untracked files".to_string(),
+        }
+    }
+
+    /// Whether the app can run this operation, rather than only preview it
+    pub fn can_run(&self) -> bool {
+        matches!(
+            self,
+            PreviewOperation::Reset { .. }
+                | PreviewOperation::Merge { .. }
+                | PreviewOperation::Clean
+        )
+    }
+
+    /// Operations that throw away work
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            PreviewOperation::Reset {
+                mode: ResetMode::Hard,
+                ..
+            } | PreviewOperation::Clean
+        )
+    }
+}
+
+/// A file the operation would change
+#[derive(Clone, Debug)]
+pub struct PreviewFile {
+    pub path: String,
+    pub status: FileStatusType,
+}
+
+/// The computed effect of an operation, without applying it
+#[derive(Clone, Debug)]
+pub struct OperationPreview {
+    pub operation: PreviewOperation,
+    /// One-line description of what will happen
+    pub summary: String,
+    /// Heading for `commits`, e.g. "Commits to replay"
+    pub commits_label: &'static str,
+    pub commits: Vec<CommitInfo>,
+    /// Files whose committed content changes relative to HEAD
+    pub changed_files: Vec<PreviewFile>,
+    /// Local changes or untracked files that would be lost
+    pub discarded: Vec<String>,
+    /// Paths that would conflict
+    pub conflicts: Vec<String>,
+}
+
+impl OperationPreview {
+    /// Compute the effect of `operation`. Refs, the index and the working tree
+    /// are left untouched; simulated merges may write unreferenced tree objects.
+    pub fn compute(repo: &Repository, operation: PreviewOperation) -> Result<Self> {
+        let mut preview = Self {
+            operation: operation.clone(),
+            summary: String::new(),
+            commits_label: "",
+            commits: Vec::new(),
+            changed_files: Vec::new(),
+            discarded: Vec::new(),
+            conflicts: Vec::new(),
+        };
+
+        match &operation {
+            PreviewOperation::Reset { sha, mode } => preview.reset(repo, sha, *mode)?,
+            PreviewOperation::Merge { sha } => preview.merge(repo, sha)?,
+            PreviewOperation::Rebase { onto } => preview.rebase(repo, onto)?,
+            PreviewOperation::Clean => preview.clean(repo)?,
+        }
+
+        Ok(preview)
+    }
+
+    /// Whether the operation would succeed without conflicts
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    fn reset(&mut self, repo: &Repository, sha: &str, mode: ResetMode) -> Result<()> {
+        let head = repo.head()?.peel_to_commit()?;
+        let target = repo.revparse_single(sha)?.peel_to_commit()?;
+
+        self.commits_label = "Commits no longer on the branch";
+        self.commits = CommitInfo::get_range(repo, sha, "HEAD", PREVIEW_COMMIT_LIMIT)?;
+        self.changed_files = tree_changes(repo, &head.tree()?, &target.tree()?)?;
+
+        self.summary = match mode {
+            ResetMode::Soft => "HEAD moves; the differences stay staged".to_string(),
+            ResetMode::Mixed => "HEAD moves; the differences become unstaged changes".to_string(),
+            ResetMode::Hard => {
+                self.discarded = local_changes(repo, false)?;
+                "HEAD, the index and the working tree are set to the target".to_string()
+            }
+        };
+        Ok(())
+    }
+
+    fn merge(&mut self, repo: &Repository, sha: &str) -> Result<()> {
+        let head = repo.head()?.peel_to_commit()?;
+        let theirs = repo.revparse_single(sha)?.peel_to_commit()?;
+        let annotated = repo.find_annotated_commit(theirs.id())?;
+        let (analysis, _) = repo.merge_analysis(&[&annotated])?;
+
+        self.commits_label = "Commits to merge";
+        self.commits = CommitInfo::get_range(repo, "HEAD", sha, PREVIEW_COMMIT_LIMIT)?;
+
+        if analysis.is_up_to_date() {
+            self.summary = "Already up to date".to_string();
+        } else if analysis.is_fast_forward() {
+            self.summary = "Fast-forward".to_string();
+            self.changed_files = tree_changes(repo, &head.tree()?, &theirs.tree()?)?;
+        } else {
+            let mut index = repo.merge_commits(&head, &theirs, None)?;
+            if index.has_conflicts() {
+                self.conflicts = index_conflicts(&index)?;
+                self.summary = "The merge will stop with conflicts".to_string();
+            } else {
+                let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+                self.changed_files = tree_changes(repo, &head.tree()?, &tree)?;
+                self.summary = "A merge commit will be created".to_string();
+            }
+        }
+        Ok(())
+    }
+
+    fn rebase(&mut self, repo: &Repository, onto: &str) -> Result<()> {
+        let head = repo.head()?.peel_to_commit()?;
+        let onto_commit = repo.revparse_single(onto)?.peel_to_commit()?;
+        let base = repo.merge_base(head.id(), onto_commit.id())?;
+
+        self.commits_label = "Commits to replay";
+        let mut commits =
+            CommitInfo::get_range(repo, &base.to_string(), "HEAD", PREVIEW_COMMIT_LIMIT)?;
+        // Replay oldest first; merge commits are dropped like `git rebase` does
+        commits.reverse();
+        commits.retain(|c| c.parents.len() <= 1);
+
+        let mut tree = onto_commit.tree()?;
+        for info in &commits {
+            let commit = repo.find_commit(git2::Oid::from_str(&info.sha)?)?;
+            let parent_tree = commit.parent(0)?.tree()?;
+            let mut index = repo.merge_trees(&parent_tree, &tree, &commit.tree()?, None)?;
+
+            if index.has_conflicts() {
+                self.conflicts = index_conflicts(&index)?;
+                self.summary = format!("The rebase will stop at {}", info.short_sha);
+                break;
+            }
+            tree = repo.find_tree(index.write_tree_to(repo)?)?;
+        }
+
+        if self.conflicts.is_empty() {
+            self.changed_files = tree_changes(repo, &head.tree()?, &tree)?;
+            self.summary = format!("{} commit(s) will be replayed cleanly", commits.len());
+        }
+        self.commits = commits;
+        Ok(())
+    }
+
+    fn clean(&mut self, repo: &Repository) -> Result<()> {
+        self.discarded = local_changes(repo, true)?;
+        self.summary = "Untracked files will be deleted; ignored files are kept".to_string();
+        Ok(())
+    }
+}
+
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+/// Files that differ between two trees
+fn tree_changes(repo: &Repository, old: &Tree, new: &Tree) -> Result<Vec<PreviewFile>> {
+    let diff = repo.diff_tree_to_tree(Some(old), Some(new), None)?;
+
+    Ok(diff
+        .deltas()
+        .filter_map(|delta| {
+            let status = match delta.status() {
+                Delta::Added => FileStatusType::Added,
+                Delta::Deleted => FileStatusType::Deleted,
+                Delta::Renamed => FileStatusType::Renamed,
+                _ => FileStatusType::Modified,
+            };
+            let path = delta.new_file().path().or(delta.old_file().path())?;
+            Some(PreviewFile {
+                path: path.to_string_lossy().to_string(),
+                status,
+            })
+        })
+        .collect())
+}
+
+/// Uncommitted changes to tracked files, or only untracked files
+fn local_changes(repo: &Repository, untracked_only: bool) -> Result<Vec<String>> {
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(untracked_only)
+        .recurse_untracked_dirs(untracked_only)
+        .include_ignored(false);
+
+    let statuses = repo.statuses(Some(&mut opts))?;
+    Ok(statuses
+        .iter()
+        .filter(|entry| entry.status().is_wt_new() == untracked_only)
+        .filter_map(|entry| entry.path().map(|p| p.to_string()))
+        .collect())
+}
+
+fn index_conflicts(index: &git2::Index) -> Result<Vec<String>> {
+    let mut paths = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let entry = conflict
+            .our
+            .as_ref()
+            .or(conflict.their.as_ref())
+            .or(conflict.ancestor.as_ref());
+        if let Some(entry) = entry {
+            paths.push(String::from_utf8_lossy(&entry.path).to_string());
+        }
+    }
+    Ok(paths)
+}
@@ -0,0 +1,94 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::{DiffOptions, Repository, Sort, StatusOptions};
+use std::collections::{BTreeMap, HashMap};
+
+use super::CommitInfo;
+
+/// A file in the project tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectFile {
+    pub path: String,
+    /// In the index; untracked files are listed only when asked for
+    pub tracked: bool,
+}
+
+impl ProjectFile {
+    /// Files in the index, plus untracked files that aren't ignored when
+    /// `include_untracked` is set, sorted by path
+    pub fn list(repo: &Repository, include_untracked: bool) -> Result<Vec<Self>> {
+        let index = repo.index()?;
+        // Conflicted files have several index entries; keep one per path
+        let mut files: BTreeMap<String, bool> = index
+            .iter()
+            .filter_map(|entry| String::from_utf8(entry.path).ok())
+            .map(|path| (path, true))
+            .collect();
+
+        if include_untracked {
+            let mut opts = StatusOptions::new();
+            opts.include_untracked(true)
+                .recurse_untracked_dirs(true)
+                .include_ignored(false)
+                .exclude_submodules(true);
+            for entry in repo.statuses(Some(&mut opts))?.iter() {
+                if entry.status().contains(git2::Status::WT_NEW) {
+                    if let Some(path) = entry.path() {
+                        files.entry(path.to_string()).or_insert(false);
+                    }
+                }
+            }
+        }
+
+        Ok(files
+            .into_iter()
+            .map(|(path, tracked)| ProjectFile { path, tracked })
+            .collect())
+    }
+}
+
+impl CommitInfo {
+    /// Commits from HEAD that changed `path`, newest first. Renames are
+    /// not followed.
+    pub fn file_history(repo: &Repository, path: &str, limit: usize) -> Result<Vec<Self>> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+        let mut opts = DiffOptions::new();
+        opts.pathspec(path).disable_pathspec_match(true);
+
+        let empty = HashMap::new();
+        let mut history = Vec::new();
+        for oid in revwalk {
+            if history.len() >= limit {
+                break;
+            }
+            let commit = repo.find_commit(oid?)?;
+            let tree = commit.tree()?;
+
+            // A merge touches the file only if it differs from every parent
+            let changed = if commit.parent_count() == 0 {
+                tree.get_path(std::path::Path::new(path)).is_ok()
+            } else {
+                let mut changed = true;
+                for parent in commit.parents() {
+                    let parent_tree = parent.tree()?;
+                    let diff =
+                        repo.diff_tree_to_tree(Some(&parent_tree), Some(&tree), Some(&mut opts))?;
+                    if diff.deltas().len() == 0 {
+                        changed = false;
+                        break;
+                    }
+                }
+                changed
+            };
+
+            if changed {
+                history.push(Self::from_commit(&commit, &empty, &empty, &empty));
+            }
+        }
+        Ok(history)
+    }
+}
@@ -0,0 +1,146 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use chrono::{DateTime, TimeZone, Utc};
+use git2::{Oid, Repository, ResetType, StatusOptions};
+
+/// An entry in a reference's reflog
+#[derive(Clone, Debug)]
+pub struct ReflogEntry {
+    /// Position in the reflog; 0 is the most recent (`HEAD@{0}`)
+    pub index: usize,
+    pub old_sha: String,
+    pub new_sha: String,
+    pub short_sha: String,
+    pub message: String,
+    pub committer: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ReflogEntry {
+    /// Most recent entries of `refname`'s reflog, newest first
+    pub fn get(repo: &Repository, refname: &str, limit: usize) -> Result<Vec<Self>> {
+        let reflog = repo.reflog(refname)?;
+        let entries = reflog
+            .iter()
+            .take(limit)
+            .enumerate()
+            .map(|(index, entry)| {
+                let new_sha = entry.id_new().to_string();
+                let committer = entry.committer();
+                Self {
+                    index,
+                    old_sha: entry.id_old().to_string(),
+                    short_sha: new_sha[..7].to_string(),
+                    new_sha,
+                    message: entry.message().unwrap_or("").to_string(),
+                    committer: committer.name().unwrap_or("Unknown").to_string(),
+                    timestamp: Utc
+                        .timestamp_opt(committer.when().seconds(), 0)
+                        .single()
+                        .unwrap_or_else(Utc::now),
+                }
+            })
+            .collect();
+        Ok(entries)
+    }
+}
+
+/// Where a reference pointed before an operation
+#[derive(Clone, Debug)]
+pub struct RefSnapshot {
+    /// Full reference name, e.g. `refs/heads/main`
+    pub name: String,
+    /// `None` if the reference did not exist
+    pub oid: Option<Oid>,
+}
+
+/// HEAD and the references an operation touches, captured beforehand so the
+/// operation can be undone
+#[derive(Clone, Debug)]
+pub struct OperationSnapshot {
+    /// Description of the operation, e.g. "Delete branch 'feature'"
+    pub label: String,
+    /// Branch HEAD was on, or `None` when detached
+    pub head_ref: Option<String>,
+    pub head_oid: Oid,
+    pub refs: Vec<RefSnapshot>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl OperationSnapshot {
+    /// Capture HEAD, its branch and `refs` before running an operation
+    pub fn capture(repo: &Repository, label: impl Into<String>, refs: &[String]) -> Result<Self> {
+        let head = repo.head()?;
+        let head_oid = head
+            .target()
+            .ok_or_else(|| anyhow::anyhow!("HEAD does not point to a commit"))?;
+        let head_ref = if repo.head_detached()? {
+            None
+        } else {
+            head.name().map(|name| name.to_string())
+        };
+
+        let mut names: Vec<String> = head_ref.iter().cloned().collect();
+        for name in refs {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+
+        Ok(Self {
+            label: label.into(),
+            head_ref,
+            head_oid,
+            refs: names
+                .into_iter()
+                .map(|name| RefSnapshot {
+                    oid: repo.refname_to_id(&name).ok(),
+                    name,
+                })
+                .collect(),
+            timestamp: Utc::now(),
+        })
+    }
+
+    /// Put HEAD and the captured references back where they were.
+    ///
+    /// Refuses when moving HEAD would overwrite uncommitted changes.
+    pub fn restore(&self, repo: &Repository) -> Result<()> {
+        let current_oid = repo.head().ok().and_then(|head| head.target());
+        let moves_head = current_oid != Some(self.head_oid);
+        if moves_head && has_uncommitted_changes(repo)? {
+            anyhow::bail!("Commit or stash your changes before undoing '{}'", self.label);
+        }
+
+        let message = format!("awabancha: undo {}", self.label);
+        for snapshot in &self.refs {
+            match snapshot.oid {
+                Some(oid) => {
+                    repo.reference(&snapshot.name, oid, true, &message)?;
+                }
+                None => {
+                    if let Ok(mut reference) = repo.find_reference(&snapshot.name) {
+                        reference.delete()?;
+                    }
+                }
+            }
+        }
+
+        match &self.head_ref {
+            Some(name) => repo.set_head(name)?,
+            None => repo.set_head_detached(self.head_oid)?,
+        }
+        if moves_head {
+            let commit = repo.find_commit(self.head_oid)?;
+            repo.reset(commit.as_object(), ResetType::Hard, None)?;
+        }
+        Ok(())
+    }
+}
+
+pub(super) fn has_uncommitted_changes(repo: &Repository) -> Result<bool> {
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(false).include_ignored(false);
+    Ok(!repo.statuses(Some(&mut opts))?.is_empty())
+}
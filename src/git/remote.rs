@@ -1,7 +1,10 @@
 #![allow(dead_code)]
 
+use super::CommitInfo;
 use anyhow::Result;
-use git2::Repository;
+use git2::{BranchType, Oid, Repository};
+use std::collections::HashSet;
+use std::path::Path;
 
 /// Remote information
 #[derive(Clone, Debug)]
@@ -72,6 +75,130 @@ impl RemoteAuth {
     }
 }
 
+/// Check whether an error from a remote operation was caused by rejected or
+/// missing credentials (HTTP 401/403)
+pub fn is_auth_error(error: &anyhow::Error) -> bool {
+    let Some(git_error) = error.downcast_ref::<git2::Error>() else {
+        return false;
+    };
+
+    if git_error.code() == git2::ErrorCode::Auth {
+        return true;
+    }
+
+    let message = git_error.message().to_lowercase();
+    git_error.class() == git2::ErrorClass::Http
+        && (message.contains("401")
+            || message.contains("403")
+            || message.contains("authentication")
+            || message.contains("unauthorized"))
+}
+
+/// Directory name `git clone` would pick for `url`: its last path segment
+/// without a `.git` suffix
+pub fn clone_directory_name(url: &str) -> Option<String> {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    // scp-like URLs separate the host with a colon, e.g. git@host:owner/repo
+    let name = url.rsplit(['/', ':', '\\']).next()?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Options for cloning large repositories
+#[derive(Clone, Debug, Default)]
+pub struct CloneOptions {
+    /// Fetch only this many commits of history (a shallow clone)
+    pub depth: Option<u32>,
+    /// Branch to check out instead of the remote's default
+    pub branch: Option<String>,
+    /// Fetch only `branch`, now and on later fetches
+    pub single_branch: bool,
+}
+
+/// Clone `url` into `destination`, which must be missing or empty
+pub fn clone_repository(
+    url: &str,
+    destination: &Path,
+    auth: Option<&RemoteAuth>,
+    options: &CloneOptions,
+) -> Result<Repository> {
+    let mut fetch_opts = git2::FetchOptions::new();
+    if let Some(auth) = auth {
+        fetch_opts.remote_callbacks(auth.create_callbacks());
+    }
+    if let Some(depth) = options.depth {
+        fetch_opts.depth(depth.min(i32::MAX as u32) as i32);
+    }
+
+    let mut builder = git2::build::RepoBuilder::new();
+    builder.fetch_options(fetch_opts);
+    if let Some(branch) = &options.branch {
+        builder.branch(branch);
+        if options.single_branch {
+            let branch = branch.clone();
+            builder.remote_create(move |repo, name, url| {
+                let refspec = format!("+refs/heads/{0}:refs/remotes/{1}/{0}", branch, name);
+                repo.remote_with_fetch(name, url, &refspec)
+            });
+        }
+    } else if options.single_branch {
+        anyhow::bail!("Choose the branch to clone on its own");
+    }
+
+    Ok(builder.clone(url, destination)?)
+}
+
+/// Fetch the history a shallow or single-branch clone left out: deepen to
+/// the full history and start fetching every branch of `remote_name`
+pub fn fetch_full_history(
+    repo: &Repository,
+    remote_name: &str,
+    callbacks: git2::RemoteCallbacks<'_>,
+) -> Result<()> {
+    let all_branches = format!("+refs/heads/*:refs/remotes/{}/*", remote_name);
+    let remote = repo.find_remote(remote_name)?;
+    let refspecs = remote.fetch_refspecs()?;
+    if !refspecs.iter().flatten().any(|spec| spec == all_branches) {
+        repo.remote_add_fetch(remote_name, &all_branches)?;
+    }
+
+    // The remote must be looked up again to see the added refspec
+    let mut remote = repo.find_remote(remote_name)?;
+    let mut fetch_opts = git2::FetchOptions::new();
+    fetch_opts.remote_callbacks(callbacks);
+    if repo.is_shallow() {
+        // libgit2's GIT_FETCH_DEPTH_UNSHALLOW
+        fetch_opts.depth(i32::MAX);
+    }
+    remote.fetch::<&str>(&[], Some(&mut fetch_opts), None)?;
+    Ok(())
+}
+
+/// Branch the remote's HEAD points at, e.g. "main", as recorded by clone or
+/// `git remote set-head`
+pub fn default_branch(repo: &Repository, remote_name: &str) -> Option<String> {
+    let head = repo
+        .find_reference(&format!("refs/remotes/{}/HEAD", remote_name))
+        .ok()?;
+    let target = head.symbolic_target()?;
+    target
+        .strip_prefix(&format!("refs/remotes/{}/", remote_name))
+        .map(str::to_string)
+}
+
+/// Check whether a push was rejected because the remote branch has commits
+/// that aren't in the local branch
+pub fn is_non_fast_forward(error: &anyhow::Error) -> bool {
+    let Some(git_error) = error.downcast_ref::<git2::Error>() else {
+        return false;
+    };
+
+    let message = git_error.message().to_lowercase();
+    git_error.code() == git2::ErrorCode::NotFastForward
+        || message.contains("non-fast-forward")
+        || message.contains("not present locally")
+}
+
 pub fn push_to_remote(
     repo: &Repository,
     remote_name: &str,
@@ -95,6 +222,7 @@ pub fn fetch_from_remote(
     repo: &Repository,
     remote_name: &str,
     auth: Option<&RemoteAuth>,
+    prune: bool,
 ) -> Result<()> {
     let mut remote = repo.find_remote(remote_name)?;
 
@@ -102,12 +230,151 @@ pub fn fetch_from_remote(
     if let Some(auth) = auth {
         fetch_opts.remote_callbacks(auth.create_callbacks());
     }
+    if prune {
+        fetch_opts.prune(git2::FetchPrune::On);
+    }
 
     remote.fetch::<&str>(&[], Some(&mut fetch_opts), None)?;
 
     Ok(())
 }
 
+/// Connect to a remote and list its refs without fetching anything, like
+/// `git ls-remote`. Returns how many refs the remote advertises.
+pub fn ls_remote(
+    repo: &Repository,
+    remote_name: &str,
+    callbacks: git2::RemoteCallbacks<'_>,
+) -> Result<usize> {
+    let mut remote = repo.find_remote(remote_name)?;
+    let connection = remote.connect_auth(git2::Direction::Fetch, Some(callbacks), None)?;
+    Ok(connection.list()?.len())
+}
+
+/// Remotes fetched by `fetch_all_remotes`, and the ones that failed
+#[derive(Debug, Default)]
+pub struct FetchAllReport {
+    pub fetched: Vec<String>,
+    /// Remote names with the error fetching them
+    pub failed: Vec<(String, anyhow::Error)>,
+}
+
+/// Fetch every configured remote. A remote that fails doesn't stop the rest;
+/// it is listed in the report instead. With `prune`, remote-tracking branches
+/// deleted on a remote are removed.
+pub fn fetch_all_remotes(
+    repo: &Repository,
+    auth: Option<&RemoteAuth>,
+    prune: bool,
+) -> Result<FetchAllReport> {
+    let mut report = FetchAllReport::default();
+
+    for name in repo.remotes()?.iter().flatten() {
+        match fetch_from_remote(repo, name, auth, prune) {
+            Ok(()) => report.fetched.push(name.to_string()),
+            Err(e) => report.failed.push((name.to_string(), e)),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Local branch whose upstream was removed by a prune
+#[derive(Clone, Debug)]
+pub struct OrphanedBranch {
+    pub name: String,
+    /// Remote-tracking branch it used to track (e.g. "origin/feature")
+    pub upstream: String,
+    /// Whether the branch tip is already contained in HEAD
+    pub is_merged: bool,
+}
+
+/// Result of a prune-enabled fetch
+#[derive(Clone, Debug, Default)]
+pub struct PruneReport {
+    pub remote: String,
+    /// Remote-tracking branches that were removed (e.g. "origin/feature")
+    pub pruned: Vec<String>,
+    /// Local branches that tracked one of the pruned branches
+    pub orphaned: Vec<OrphanedBranch>,
+}
+
+impl PruneReport {
+    pub fn is_empty(&self) -> bool {
+        self.pruned.is_empty()
+    }
+}
+
+fn remote_tracking_branches(repo: &Repository, remote_name: &str) -> Result<HashSet<String>> {
+    let prefix = format!("{}/", remote_name);
+    let mut names = HashSet::new();
+
+    for branch in repo.branches(Some(BranchType::Remote))? {
+        let (branch, _) = branch?;
+        if let Some(name) = branch.name()? {
+            if name.starts_with(&prefix) && !name.ends_with("/HEAD") {
+                names.insert(name.to_string());
+            }
+        }
+    }
+
+    Ok(names)
+}
+
+/// Fetch a remote with pruning enabled and report which remote-tracking
+/// branches were removed and which local branches lost their upstream
+pub fn fetch_with_prune(
+    repo: &Repository,
+    remote_name: &str,
+    callbacks: git2::RemoteCallbacks<'_>,
+) -> Result<PruneReport> {
+    let before = remote_tracking_branches(repo, remote_name)?;
+
+    // Upstreams must be resolved before the fetch removes them
+    let mut upstreams = Vec::new();
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        let (Some(name), Ok(upstream)) = (branch.name()?, branch.upstream()) else {
+            continue;
+        };
+        if let (Some(upstream_name), Some(tip)) = (upstream.name()?, branch.get().target()) {
+            upstreams.push((name.to_string(), upstream_name.to_string(), tip));
+        }
+    }
+
+    let mut remote = repo.find_remote(remote_name)?;
+    let mut fetch_opts = git2::FetchOptions::new();
+    fetch_opts.remote_callbacks(callbacks);
+    fetch_opts.prune(git2::FetchPrune::On);
+    remote.fetch::<&str>(&[], Some(&mut fetch_opts), None)?;
+
+    let after = remote_tracking_branches(repo, remote_name)?;
+    let mut pruned: Vec<String> = before.difference(&after).cloned().collect();
+    pruned.sort();
+
+    let head = repo.head().ok().and_then(|h| h.target());
+    let orphaned = upstreams
+        .into_iter()
+        .filter(|(_, upstream, _)| pruned.contains(upstream))
+        .map(|(name, upstream, tip)| {
+            let is_merged = head
+                .map(|head| head == tip || repo.graph_descendant_of(head, tip).unwrap_or(false))
+                .unwrap_or(false);
+            OrphanedBranch {
+                name,
+                upstream,
+                is_merged,
+            }
+        })
+        .collect();
+
+    Ok(PruneReport {
+        remote: remote_name.to_string(),
+        pruned,
+        orphaned,
+    })
+}
+
 pub fn pull_from_remote(
     repo: &Repository,
     remote_name: &str,
@@ -115,7 +382,7 @@ pub fn pull_from_remote(
     auth: Option<&RemoteAuth>,
 ) -> Result<()> {
     // First fetch
-    fetch_from_remote(repo, remote_name, auth)?;
+    fetch_from_remote(repo, remote_name, auth, false)?;
 
     // Then merge
     let fetch_head = repo.find_reference("FETCH_HEAD")?;
@@ -137,3 +404,100 @@ pub fn pull_from_remote(
 
     Ok(())
 }
+
+/// Most overwritten commits listed before a force push
+const FORCE_PUSH_PREVIEW_LIMIT: usize = 100;
+
+/// What force-pushing the current branch would replace on the remote
+#[derive(Clone, Debug)]
+pub struct ForcePushPlan {
+    pub branch: String,
+    /// Remote-tracking branch standing in for the remote one, e.g. "origin/main"
+    pub remote_branch: String,
+    /// Where the remote branch was last fetched at, used as the lease; `None`
+    /// if it hasn't been pushed yet
+    pub expected: Option<Oid>,
+    /// Commits on the remote branch that are not in HEAD and would be dropped
+    pub overwritten: Vec<CommitInfo>,
+}
+
+impl ForcePushPlan {
+    pub fn read(repo: &Repository, remote_name: &str) -> Result<Self> {
+        let head = repo.head()?;
+        if !head.is_branch() {
+            anyhow::bail!("HEAD is not on a branch");
+        }
+        let branch = head.shorthand().unwrap_or("HEAD").to_string();
+        let remote_branch = format!("{}/{}", remote_name, branch);
+
+        let expected = repo
+            .find_branch(&remote_branch, BranchType::Remote)
+            .ok()
+            .and_then(|b| b.get().target());
+        let overwritten = match expected {
+            Some(_) => CommitInfo::get_range(
+                repo,
+                "HEAD",
+                &format!("refs/remotes/{}", remote_branch),
+                FORCE_PUSH_PREVIEW_LIMIT,
+            )?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            branch,
+            remote_branch,
+            expected,
+            overwritten,
+        })
+    }
+}
+
+/// Force-push `branch` like `git push --force-with-lease`: the push is
+/// refused if the remote branch no longer points at `expected`, so commits
+/// pushed by someone else since the last fetch are never overwritten unseen
+pub fn force_push_with_lease(
+    repo: &Repository,
+    remote_name: &str,
+    branch: &str,
+    expected: Option<Oid>,
+    mut callbacks: git2::RemoteCallbacks<'_>,
+) -> Result<()> {
+    let refname = format!("refs/heads/{}", branch);
+    let lease_ref = refname.clone();
+    callbacks.push_negotiation(move |updates| {
+        for update in updates {
+            if update.dst_refname() != Some(lease_ref.as_str()) {
+                continue;
+            }
+            // The remote reports a zero id for a branch it doesn't have
+            let current = Some(update.src()).filter(|oid| !oid.is_zero());
+            if current != expected {
+                return Err(git2::Error::from_str(
+                    "stale info: the remote branch changed since the last fetch",
+                ));
+            }
+        }
+        Ok(())
+    });
+
+    let rejection = std::rc::Rc::new(std::cell::RefCell::new(None));
+    let rejection_slot = rejection.clone();
+    callbacks.push_update_reference(move |_refname, status| {
+        if let Some(status) = status {
+            *rejection_slot.borrow_mut() = Some(status.to_string());
+        }
+        Ok(())
+    });
+
+    let mut remote = repo.find_remote(remote_name)?;
+    let mut push_opts = git2::PushOptions::new();
+    push_opts.remote_callbacks(callbacks);
+    let refspec = format!("+{}:{}", refname, refname);
+    remote.push(&[&refspec], Some(&mut push_opts))?;
+
+    if let Some(reason) = rejection.borrow_mut().take() {
+        anyhow::bail!("Remote rejected the push: {}", reason);
+    }
+    Ok(())
+}
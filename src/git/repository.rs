@@ -1,7 +1,9 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
-use git2::Repository;
+use chrono::{DateTime, Utc};
+use git2::{Repository, RepositoryState, StatusOptions};
+use std::path::Path;
 
 /// Repository information (HEAD, branches, remotes)
 #[derive(Clone, Debug)]
@@ -12,6 +14,10 @@ pub struct RepositoryInfo {
     pub current_branch: Option<String>,
     /// Is HEAD detached?
     pub is_detached: bool,
+    /// Commit HEAD points at
+    pub head_sha: Option<String>,
+    /// Branch to go back to from a detached HEAD
+    pub previous_branch: Option<String>,
     /// Commits ahead of upstream
     pub ahead: usize,
     /// Commits behind upstream
@@ -20,6 +26,12 @@ pub struct RepositoryInfo {
     pub remote_name: Option<String>,
     /// Remote URL
     pub remote_url: Option<String>,
+    /// Operation in progress, e.g. a merge or rebase
+    pub state: RepositoryState,
+    /// When any remote was last fetched, by this app or the git CLI
+    pub last_fetch: Option<DateTime<Utc>>,
+    /// Cloned with limited history depth
+    pub is_shallow: bool,
 }
 
 impl RepositoryInfo {
@@ -34,6 +46,12 @@ impl RepositoryInfo {
         };
 
         let head_ref = head.name().map(|s| s.to_string());
+        let head_sha = head.target().map(|oid| oid.to_string());
+        let previous_branch = if is_detached {
+            super::previous_branch(repo)
+        } else {
+            None
+        };
 
         // Get ahead/behind counts
         let (ahead, behind) = if let Some(ref branch_name) = current_branch {
@@ -45,17 +63,47 @@ impl RepositoryInfo {
         // Get remote info
         let (remote_name, remote_url) = Self::get_remote_info(repo);
 
+        // Every fetch rewrites FETCH_HEAD
+        let last_fetch = std::fs::metadata(repo.path().join("FETCH_HEAD"))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .map(DateTime::<Utc>::from);
+
         Ok(Self {
             head_ref,
             current_branch,
             is_detached,
+            head_sha,
+            previous_branch,
             ahead,
             behind,
             remote_name,
             remote_url,
+            state: repo.state(),
+            last_fetch,
+            is_shallow: repo.is_shallow(),
         })
     }
 
+    /// Name of the operation in progress, or None when there is none
+    pub fn state_label(&self) -> Option<&'static str> {
+        match self.state {
+            RepositoryState::Clean => None,
+            RepositoryState::Merge => Some("Merging"),
+            RepositoryState::Revert | RepositoryState::RevertSequence => Some("Reverting"),
+            RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+                Some("Cherry-picking")
+            }
+            RepositoryState::Bisect => Some("Bisecting"),
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => Some("Rebasing"),
+            RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => {
+                Some("Applying patches")
+            }
+        }
+    }
+
     fn get_ahead_behind(repo: &Repository, branch_name: &str) -> Result<(usize, usize)> {
         let local_branch = repo.find_branch(branch_name, git2::BranchType::Local)?;
         let upstream = local_branch.upstream()?;
@@ -88,3 +136,38 @@ impl RepositoryInfo {
         }
     }
 }
+
+/// Branch and working tree state of a repository that isn't open, as shown
+/// for recent projects
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepositorySummary {
+    /// Current branch, or None when HEAD is detached
+    pub branch: Option<String>,
+    /// Changed, staged or untracked files
+    pub dirty: bool,
+}
+
+impl RepositorySummary {
+    pub fn read(path: &Path) -> Result<Self> {
+        let repo = Repository::open(path)?;
+
+        // HEAD's symbolic target also names an unborn branch
+        let branch = repo.find_reference("HEAD").ok().and_then(|head| {
+            head.symbolic_target()
+                .map(|target| target.trim_start_matches("refs/heads/").to_string())
+        });
+
+        let dirty = if repo.is_bare() {
+            false
+        } else {
+            let mut options = StatusOptions::new();
+            options
+                .include_untracked(true)
+                .recurse_untracked_dirs(false)
+                .exclude_submodules(true);
+            !repo.statuses(Some(&mut options))?.is_empty()
+        };
+
+        Ok(Self { branch, dirty })
+    }
+}
@@ -0,0 +1,204 @@
+#![allow(dead_code)]
+
+use super::is_binary_content;
+use anyhow::{Context as _, Result};
+use git2::{ObjectType, Repository, TreeWalkMode, TreeWalkResult};
+use std::collections::BTreeSet;
+use std::fs;
+use std::ops::Range;
+
+/// Files larger than this are not searched
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Which version of the tracked files a text search reads
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SearchScope {
+    /// Files in the index, as they are on disk, including unsaved edits
+    #[default]
+    WorkingTree,
+    /// Files as committed at HEAD
+    Head,
+}
+
+impl SearchScope {
+    pub const ALL: [Self; 2] = [Self::WorkingTree, Self::Head];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::WorkingTree => "Working Tree",
+            Self::Head => "HEAD",
+        }
+    }
+}
+
+/// Text to look for in tracked files
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub text: String,
+    pub scope: SearchScope,
+    /// Off by default; ignoring case only folds ASCII letters
+    pub case_sensitive: bool,
+}
+
+/// A line containing the query
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub path: String,
+    /// 1-based line number
+    pub line: u32,
+    pub text: String,
+    /// Byte ranges of each occurrence in `text`
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// How a finished search went
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchSummary {
+    pub files_searched: usize,
+    pub matches: usize,
+    /// The match limit was reached before every file was searched
+    pub truncated: bool,
+}
+
+/// Search tracked files for `query`, handing each file's matching lines to
+/// `on_matches` in path order. Binary files and files over 1 MB are
+/// skipped. Stops after `limit` matches, or as soon as `on_matches`
+/// returns false.
+pub fn search_files(
+    repo: &Repository,
+    query: &SearchQuery,
+    limit: usize,
+    on_matches: &mut dyn FnMut(Vec<SearchMatch>) -> bool,
+) -> Result<SearchSummary> {
+    let mut summary = SearchSummary::default();
+    if query.text.is_empty() {
+        return Ok(summary);
+    }
+    let needle = if query.case_sensitive {
+        query.text.clone()
+    } else {
+        query.text.to_ascii_lowercase()
+    };
+
+    for (path, content) in tracked_files(repo, query.scope)? {
+        let Some(content) = content.load(repo) else {
+            continue;
+        };
+        summary.files_searched += 1;
+
+        let mut matches = search_content(&path, &content, &needle, query.case_sensitive);
+        if matches.is_empty() {
+            continue;
+        }
+        let remaining = limit - summary.matches;
+        if matches.len() >= remaining {
+            summary.truncated = matches.len() > remaining;
+            matches.truncate(remaining);
+        }
+        summary.matches += matches.len();
+        if !on_matches(matches) || summary.matches == limit {
+            break;
+        }
+    }
+    Ok(summary)
+}
+
+/// Where a tracked file's content comes from
+enum FileContent {
+    Disk(std::path::PathBuf),
+    Blob(git2::Oid),
+}
+
+impl FileContent {
+    /// The file's bytes, or `None` when it's missing, too large or binary
+    fn load(&self, repo: &Repository) -> Option<Vec<u8>> {
+        let content = match self {
+            Self::Disk(path) => {
+                let metadata = fs::metadata(path).ok()?;
+                if !metadata.is_file() || metadata.len() > MAX_FILE_SIZE {
+                    return None;
+                }
+                fs::read(path).ok()?
+            }
+            Self::Blob(oid) => {
+                let blob = repo.find_blob(*oid).ok()?;
+                if blob.size() as u64 > MAX_FILE_SIZE {
+                    return None;
+                }
+                blob.content().to_vec()
+            }
+        };
+        (!is_binary_content(&content)).then_some(content)
+    }
+}
+
+/// Tracked file paths in `scope`, sorted, with where to read each from
+fn tracked_files(repo: &Repository, scope: SearchScope) -> Result<Vec<(String, FileContent)>> {
+    match scope {
+        SearchScope::WorkingTree => {
+            let workdir = repo.workdir().context("The repository has no working tree")?;
+            // Conflicted files have several index entries
+            let paths: BTreeSet<String> = repo
+                .index()?
+                .iter()
+                .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+                .collect();
+            Ok(paths
+                .into_iter()
+                .map(|path| {
+                    let file = FileContent::Disk(workdir.join(&path));
+                    (path, file)
+                })
+                .collect())
+        }
+        SearchScope::Head => {
+            // An unborn branch has nothing committed to search
+            let Ok(head) = repo.head() else {
+                return Ok(Vec::new());
+            };
+            let tree = head.peel_to_tree()?;
+            let mut files = Vec::new();
+            tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+                if entry.kind() == Some(ObjectType::Blob) {
+                    let path = format!("{}{}", dir, entry.name().unwrap_or_default());
+                    files.push((path, FileContent::Blob(entry.id())));
+                }
+                TreeWalkResult::Ok
+            })?;
+            files.sort_by(|a, b| a.0.cmp(&b.0));
+            Ok(files)
+        }
+    }
+}
+
+/// Lines of `content` containing `needle`, which is already lowercased when
+/// the search ignores case
+fn search_content(
+    path: &str,
+    content: &[u8],
+    needle: &str,
+    case_sensitive: bool,
+) -> Vec<SearchMatch> {
+    let text = String::from_utf8_lossy(content);
+    text.lines()
+        .enumerate()
+        .filter_map(|(ix, line)| {
+            // ASCII lowercasing keeps byte offsets the same as in `line`
+            let haystack = if case_sensitive {
+                line.to_string()
+            } else {
+                line.to_ascii_lowercase()
+            };
+            let ranges: Vec<Range<usize>> = haystack
+                .match_indices(needle)
+                .map(|(start, found)| start..start + found.len())
+                .collect();
+            (!ranges.is_empty()).then(|| SearchMatch {
+                path: path.to_string(),
+                line: ix as u32 + 1,
+                text: line.to_string(),
+                ranges,
+            })
+        })
+        .collect()
+}
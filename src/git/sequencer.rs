@@ -0,0 +1,188 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::{Oid, Repository, RepositoryState, ResetType};
+use std::collections::VecDeque;
+
+use super::{commit_cherry_pick, commit_revert};
+use super::reflog::has_uncommitted_changes;
+
+/// Which kind of pick stopped on conflicts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PickKind {
+    CherryPick,
+    Revert,
+}
+
+impl PickKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::CherryPick => "Cherry-pick",
+            Self::Revert => "Revert",
+        }
+    }
+}
+
+/// A cherry-pick or revert that stopped on conflicts, found from the
+/// repository state whether the app or the git CLI started it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterruptedPick {
+    pub kind: PickKind,
+    /// Commit being picked or reverted
+    pub sha: String,
+    pub summary: String,
+}
+
+impl InterruptedPick {
+    pub fn get(repo: &Repository) -> Result<Option<Self>> {
+        let (kind, head_file) = match repo.state() {
+            RepositoryState::CherryPick => (PickKind::CherryPick, "CHERRY_PICK_HEAD"),
+            RepositoryState::Revert => (PickKind::Revert, "REVERT_HEAD"),
+            _ => return Ok(None),
+        };
+        let Ok(content) = std::fs::read_to_string(repo.path().join(head_file)) else {
+            return Ok(None);
+        };
+        let commit = repo.find_commit(Oid::from_str(content.trim())?)?;
+        Ok(Some(Self {
+            kind,
+            sha: commit.id().to_string(),
+            summary: commit.summary().unwrap_or("").to_string(),
+        }))
+    }
+
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..7]
+    }
+
+    /// Commit the resolved pick with the message it would have had without
+    /// conflicts
+    pub fn continue_pick(&self, repo: &Repository) -> Result<Oid> {
+        if repo.index()?.has_conflicts() {
+            anyhow::bail!("Resolve every conflict before continuing");
+        }
+        let commit = repo.find_commit(Oid::from_str(&self.sha)?)?;
+        match self.kind {
+            PickKind::CherryPick => commit_cherry_pick(repo, &commit),
+            PickKind::Revert => commit_revert(repo, &commit),
+        }
+    }
+
+    /// Throw away the pick and its conflicts, going back to HEAD
+    pub fn abort(&self, repo: &Repository) -> Result<()> {
+        let head = repo.head()?.peel_to_commit()?;
+        repo.reset(head.as_object(), ResetType::Hard, None)?;
+        repo.cleanup_state()?;
+        Ok(())
+    }
+}
+
+/// What one step of a cherry-pick sequence did
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PickStep {
+    /// The commit was applied and committed
+    Picked(Oid),
+    /// The commit conflicted; the sequence waits for `continue_pick` or `abort`
+    Conflicted(String),
+    /// Every commit has been picked
+    Finished,
+}
+
+/// Cherry-picks commits onto HEAD one at a time, like `git cherry-pick a b c`.
+///
+/// A commit that conflicts leaves the repository mid cherry-pick and stops
+/// the sequence. Once the conflicts are resolved `continue_pick` commits it
+/// and the rest can follow, or `abort` puts HEAD back where it was before the
+/// first pick. The working tree has to be clean to start, so aborting never
+/// throws away changes that weren't part of the sequence.
+#[derive(Clone, Debug)]
+pub struct CherryPickSequence {
+    /// HEAD before the first pick
+    orig_head: Oid,
+    /// Commits not picked yet, in the order they are applied
+    todo: VecDeque<String>,
+    total: usize,
+    /// Commit whose pick stopped on conflicts
+    stopped: Option<String>,
+}
+
+impl CherryPickSequence {
+    /// Start a sequence picking `shas` in order onto the current HEAD
+    pub fn new(repo: &Repository, shas: Vec<String>) -> Result<Self> {
+        if repo.state() != RepositoryState::Clean {
+            anyhow::bail!("Finish or abort the operation in progress before cherry-picking");
+        }
+        if has_uncommitted_changes(repo)? {
+            anyhow::bail!("Commit or stash your changes before cherry-picking");
+        }
+        Ok(Self {
+            orig_head: repo.head()?.peel_to_commit()?.id(),
+            total: shas.len(),
+            todo: shas.into(),
+            stopped: None,
+        })
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Commits picked and committed so far
+    pub fn completed(&self) -> usize {
+        self.total - self.todo.len() - usize::from(self.stopped.is_some())
+    }
+
+    /// Commit the next step picks
+    pub fn next_pick(&self) -> Option<&str> {
+        self.todo.front().map(String::as_str)
+    }
+
+    /// Commit whose pick is waiting on conflicts
+    pub fn stopped_at(&self) -> Option<&str> {
+        self.stopped.as_deref()
+    }
+
+    /// Pick the next commit, stopping if it conflicts
+    pub fn step(&mut self, repo: &Repository) -> Result<PickStep> {
+        if self.stopped.is_some() {
+            anyhow::bail!("Resolve the conflicts and continue before picking more commits");
+        }
+        let Some(sha) = self.todo.front().cloned() else {
+            return Ok(PickStep::Finished);
+        };
+        let commit = repo.find_commit(Oid::from_str(&sha)?)?;
+        repo.cherrypick(&commit, None)?;
+        self.todo.pop_front();
+
+        if repo.index()?.has_conflicts() {
+            self.stopped = Some(sha.clone());
+            return Ok(PickStep::Conflicted(sha));
+        }
+        commit_cherry_pick(repo, &commit).map(PickStep::Picked)
+    }
+
+    /// Commit the resolved pick the sequence stopped on, with the original
+    /// message, so the rest can be picked
+    pub fn continue_pick(&mut self, repo: &Repository) -> Result<Oid> {
+        let sha = self
+            .stopped
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No cherry-pick is waiting on conflicts"))?;
+        if repo.index()?.has_conflicts() {
+            anyhow::bail!("Resolve every conflict before continuing");
+        }
+        let commit = repo.find_commit(Oid::from_str(&sha)?)?;
+        let oid = commit_cherry_pick(repo, &commit)?;
+        self.stopped = None;
+        Ok(oid)
+    }
+
+    /// Drop the commits picked so far and any pick in progress, putting HEAD
+    /// back where the sequence started
+    pub fn abort(&self, repo: &Repository) -> Result<()> {
+        let head = repo.find_commit(self.orig_head)?;
+        repo.reset(head.as_object(), ResetType::Hard, None)?;
+        repo.cleanup_state()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+#![allow(dead_code)]
+
+use anyhow::{bail, Result};
+use git2::{BranchType, Repository};
+use std::path::Path;
+use std::process::Command;
+
+/// Signature verification result for a commit, as reported by `git log %G?`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SignatureStatus {
+    /// Good signature from a trusted key
+    Good,
+    /// Good signature with unknown key validity
+    UnknownValidity,
+    /// Bad signature
+    Bad,
+    /// Good signature but the signature or key has expired
+    Expired,
+    /// Good signature made by a revoked key
+    Revoked,
+    /// Signature cannot be checked (e.g. missing key)
+    CannotCheck,
+    /// No signature
+    Unsigned,
+}
+
+impl SignatureStatus {
+    fn from_code(code: &str) -> Self {
+        match code {
+            "G" => Self::Good,
+            "U" => Self::UnknownValidity,
+            "B" => Self::Bad,
+            "X" | "Y" => Self::Expired,
+            "R" => Self::Revoked,
+            "E" => Self::CannotCheck,
+            _ => Self::Unsigned,
+        }
+    }
+
+    /// Whether the signature itself verified successfully
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Good | Self::UnknownValidity)
+    }
+
+    /// How the commit is badged in the history; unsigned commits get none
+    pub fn trust(&self) -> Option<SignatureTrust> {
+        match self {
+            Self::Good => Some(SignatureTrust::Verified),
+            Self::Bad | Self::Expired | Self::Revoked => Some(SignatureTrust::Unverified),
+            Self::UnknownValidity | Self::CannotCheck => Some(SignatureTrust::Unknown),
+            Self::Unsigned => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Good => "good signature",
+            Self::UnknownValidity => "good signature (unknown validity)",
+            Self::Bad => "bad signature",
+            Self::Expired => "expired signature",
+            Self::Revoked => "revoked key",
+            Self::CannotCheck => "signature cannot be checked",
+            Self::Unsigned => "unsigned",
+        }
+    }
+}
+
+/// Badge of a signed commit
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SignatureTrust {
+    /// Good signature from a trusted key
+    Verified,
+    /// The signature is bad, expired or made by a revoked key
+    Unverified,
+    /// The signature couldn't be checked, or the key isn't trusted
+    Unknown,
+}
+
+impl SignatureTrust {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Verified => "Verified",
+            Self::Unverified => "Unverified",
+            Self::Unknown => "Unknown",
+        }
+    }
+}
+
+/// Signature information for a single commit
+#[derive(Clone, Debug)]
+pub struct CommitSignature {
+    pub sha: String,
+    pub summary: String,
+    pub status: SignatureStatus,
+    /// Key ID of the signing key
+    pub key_id: Option<String>,
+    /// Fingerprint of the signing key
+    pub fingerprint: Option<String>,
+    /// Name of the signer, or the SSH principal
+    pub signer: Option<String>,
+}
+
+impl CommitSignature {
+    /// Verify signatures of commits reachable from `incoming` but not `base`.
+    ///
+    /// libgit2 can extract signatures but not verify them, so this delegates to
+    /// the git CLI, which honours the user's gpg/ssh configuration
+    /// (including `gpg.ssh.allowedSignersFile`).
+    pub fn get_range(repo: &Repository, base: &str, incoming: &str) -> Result<Vec<Self>> {
+        let dir = repo.workdir().unwrap_or_else(|| repo.path());
+        Self::log(dir, &[format!("{}..{}", base, incoming)])
+    }
+
+    /// Verify the signatures of specific commits, in the repository at `dir`
+    /// so it can run off the main thread
+    pub fn verify(dir: &Path, shas: &[String]) -> Result<Vec<Self>> {
+        if shas.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut args = vec!["--no-walk=unsorted".to_string()];
+        args.extend(shas.iter().cloned());
+        Self::log(dir, &args)
+    }
+
+    fn log(dir: &Path, args: &[String]) -> Result<Vec<Self>> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["log", "--format=%H%x1f%G?%x1f%GK%x1f%GF%x1f%GS%x1f%s"])
+            .args(args)
+            .output()?;
+
+        if !output.status.success() {
+            bail!(
+                "git log failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\x1f');
+                let sha = fields.next()?.to_string();
+                let status = SignatureStatus::from_code(fields.next()?);
+                let key_id = fields.next().and_then(non_empty);
+                let fingerprint = fields.next().and_then(non_empty);
+                let signer = fields.next().and_then(non_empty);
+                let summary = fields.next().unwrap_or("").to_string();
+
+                Some(Self {
+                    sha,
+                    summary,
+                    status,
+                    key_id,
+                    fingerprint,
+                    signer,
+                })
+            })
+            .collect())
+    }
+
+    /// Check whether the commit satisfies the signing policy.
+    ///
+    /// An empty allowed-signers list accepts any valid signature. Otherwise
+    /// an entry has to be the full fingerprint or long key ID of the signing
+    /// key; shorter IDs are too easy to collide with to be trusted.
+    pub fn is_allowed(&self, allowed_signers: &[String]) -> bool {
+        if !self.status.is_valid() {
+            return false;
+        }
+
+        if allowed_signers.is_empty() {
+            return true;
+        }
+
+        let keys = [self.key_id.as_deref(), self.fingerprint.as_deref()];
+        allowed_signers
+            .iter()
+            .map(|allowed| allowed.trim())
+            .filter(|allowed| !allowed.is_empty())
+            .any(|allowed| keys.iter().flatten().any(|key| same_key(key, allowed)))
+    }
+}
+
+/// GPG key IDs and fingerprints are hex, so case doesn't matter; SSH
+/// fingerprints (`SHA256:...`) are base64 and must match exactly
+fn same_key(key: &str, allowed: &str) -> bool {
+    if key.starts_with("SHA256:") {
+        key == allowed
+    } else {
+        key.eq_ignore_ascii_case(allowed)
+    }
+}
+
+/// An incoming commit on a protected branch that fails the signing policy
+#[derive(Clone, Debug)]
+pub struct SigningViolation {
+    pub branch: String,
+    pub commit: CommitSignature,
+}
+
+impl SigningViolation {
+    /// Check commits on each protected branch's upstream that are not yet in
+    /// the local branch
+    pub fn check_incoming(
+        repo: &Repository,
+        protected_branches: &[String],
+        allowed_signers: &[String],
+    ) -> Result<Vec<Self>> {
+        let mut violations = Vec::new();
+
+        for name in protected_branches {
+            let Ok(branch) = repo.find_branch(name, BranchType::Local) else {
+                continue;
+            };
+            let Ok(upstream) = branch.upstream() else {
+                continue;
+            };
+            let (Some(local_ref), Some(upstream_ref)) = (branch.get().name(), upstream.get().name())
+            else {
+                continue;
+            };
+
+            for commit in CommitSignature::get_range(repo, local_ref, upstream_ref)? {
+                if !commit.is_allowed(allowed_signers) {
+                    violations.push(Self {
+                        branch: name.clone(),
+                        commit,
+                    });
+                }
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Check the commits a merge of `incoming` would bring into HEAD, when
+    /// HEAD is one of the protected branches
+    pub fn check_merge(
+        repo: &Repository,
+        incoming: &str,
+        protected_branches: &[String],
+        allowed_signers: &[String],
+    ) -> Result<Vec<Self>> {
+        let Ok(head) = repo.head() else {
+            return Ok(Vec::new());
+        };
+        let Some(branch) = head
+            .is_branch()
+            .then(|| head.shorthand())
+            .flatten()
+            .filter(|name| protected_branches.iter().any(|b| b == name))
+        else {
+            return Ok(Vec::new());
+        };
+
+        Ok(CommitSignature::get_range(repo, "HEAD", incoming)?
+            .into_iter()
+            .filter(|commit| !commit.is_allowed(allowed_signers))
+            .map(|commit| Self {
+                branch: branch.to_string(),
+                commit,
+            })
+            .collect())
+    }
+
+    /// One line summing up the violations, for a toast or an error
+    pub fn describe(violations: &[Self]) -> String {
+        let mut branches: Vec<&str> = violations.iter().map(|v| v.branch.as_str()).collect();
+        branches.dedup();
+
+        let count = violations.len();
+        format!(
+            "{} incoming commit{} on {} {} unsigned or signed by an untrusted key",
+            count,
+            if count == 1 { "" } else { "s" },
+            branches.join(", "),
+            if count == 1 { "is" } else { "are" }
+        )
+    }
+}
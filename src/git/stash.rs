@@ -1,7 +1,12 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
-use git2::Repository;
+use git2::{Oid, Repository, StashFlags, StashSaveOptions, StatusOptions};
+
+use super::commit_signature;
+
+/// Message of stashes created automatically around pull and checkout
+pub const AUTOSTASH_MESSAGE: &str = "awabancha autostash";
 
 /// Stash entry
 #[derive(Clone, Debug)]
@@ -11,6 +16,19 @@ pub struct StashEntry {
     pub oid: String,
 }
 
+/// A stash waiting to be applied onto another branch
+#[derive(Clone, Debug)]
+pub struct StashBranchRequest {
+    pub index: usize,
+    pub message: String,
+    /// Local branch to apply onto, once picked
+    pub branch: Option<String>,
+    /// Keep the stash in the list after it applies cleanly
+    pub keep_stash: bool,
+    /// Why the last attempt failed
+    pub error: Option<String>,
+}
+
 impl StashEntry {
     pub fn get_all(repo: &mut Repository) -> Result<Vec<Self>> {
         let mut stashes = Vec::new();
@@ -27,10 +45,30 @@ impl StashEntry {
         Ok(stashes)
     }
 
-    pub fn save(repo: &mut Repository, message: Option<&str>) -> Result<()> {
-        let sig = repo.signature()?;
-        let msg = message.unwrap_or("WIP");
-        repo.stash_save(&sig, msg, None)?;
+    /// Stash local changes. `flags` selects untracked files and keep-index;
+    /// when `paths` is non-empty only changes to those paths are stashed.
+    ///
+    /// Without a message git's default ("WIP on <branch>: ...") is used.
+    /// libgit2 can't name a stash of some paths, so `message` is ignored
+    /// when `paths` is non-empty.
+    pub fn save(
+        repo: &mut Repository,
+        message: Option<&str>,
+        flags: StashFlags,
+        paths: &[String],
+    ) -> Result<()> {
+        let sig = commit_signature(repo)?;
+        if paths.is_empty() {
+            repo.stash_save2(&sig, message, Some(flags))?;
+            return Ok(());
+        }
+
+        let mut opts = StashSaveOptions::new(sig);
+        opts.flags(Some(flags));
+        for path in paths {
+            opts.pathspec(path);
+        }
+        repo.stash_save_ext(Some(&mut opts))?;
         Ok(())
     }
 
@@ -48,4 +86,91 @@ impl StashEntry {
         repo.stash_drop(index)?;
         Ok(())
     }
+
+    /// Check out `branch` and apply stash `index` there. Refuses when tracked
+    /// files have local changes, which would be carried over and mixed into
+    /// the stash. The stash is dropped after a clean apply unless `keep_stash`,
+    /// and always kept on conflicts. Returns true if the apply conflicted.
+    pub fn apply_onto_branch(
+        repo: &mut Repository,
+        index: usize,
+        branch: &str,
+        keep_stash: bool,
+    ) -> Result<bool> {
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(false).include_ignored(false);
+        if !repo.statuses(Some(&mut opts))?.is_empty() {
+            anyhow::bail!("Commit or stash your local changes before switching branches");
+        }
+
+        let refname = format!("refs/heads/{}", branch);
+        if repo.head()?.name() != Some(refname.as_str()) {
+            let target = repo.revparse_single(&refname)?;
+            repo.checkout_tree(&target, None)?;
+            repo.set_head(&refname)?;
+        }
+
+        let applied = repo.stash_apply(index, None);
+        if repo.index()?.has_conflicts() {
+            return Ok(true);
+        }
+        applied?;
+
+        if !keep_stash {
+            repo.stash_drop(index)?;
+        }
+        Ok(false)
+    }
+
+    /// Stash all local changes, including untracked files, if the working
+    /// tree is dirty. Returns the stash commit to reapply afterwards.
+    pub fn autostash_save(repo: &mut Repository) -> Result<Option<Oid>> {
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true).include_ignored(false);
+        if repo.statuses(Some(&mut opts))?.is_empty() {
+            return Ok(None);
+        }
+
+        let sig = commit_signature(repo)?;
+        let oid = repo.stash_save(&sig, AUTOSTASH_MESSAGE, Some(StashFlags::INCLUDE_UNTRACKED))?;
+        Ok(Some(oid))
+    }
+
+    /// Reapply an autostash. It is dropped when it applies cleanly and kept
+    /// when the reapply conflicts, so the changes survive an abort.
+    /// Returns true if the reapply left conflicts in the index.
+    pub fn autostash_apply(repo: &mut Repository, oid: Oid) -> Result<bool> {
+        let index = Self::find_index(repo, oid)?
+            .ok_or_else(|| anyhow::anyhow!("Autostash {} no longer exists", oid))?;
+
+        let applied = repo.stash_apply(index, None);
+        if repo.index()?.has_conflicts() {
+            return Ok(true);
+        }
+        applied?;
+
+        repo.stash_drop(index)?;
+        Ok(false)
+    }
+
+    /// Drop the most recent autostash, once its conflicts have been resolved
+    pub fn drop_latest_autostash(repo: &mut Repository) -> Result<()> {
+        let index = Self::get_all(repo)?
+            .into_iter()
+            .find(|stash| stash.message.ends_with(AUTOSTASH_MESSAGE))
+            .map(|stash| stash.index);
+
+        if let Some(index) = index {
+            repo.stash_drop(index)?;
+        }
+        Ok(())
+    }
+
+    fn find_index(repo: &mut Repository, oid: Oid) -> Result<Option<usize>> {
+        let oid = oid.to_string();
+        Ok(Self::get_all(repo)?
+            .into_iter()
+            .find(|stash| stash.oid == oid)
+            .map(|stash| stash.index))
+    }
 }
@@ -14,6 +14,129 @@ pub enum FileStatusType {
     Conflicted,
 }
 
+impl FileStatusType {
+    pub fn symbol(self) -> char {
+        match self {
+            FileStatusType::Added => 'A',
+            FileStatusType::Modified => 'M',
+            FileStatusType::Deleted => 'D',
+            FileStatusType::Renamed => 'R',
+            FileStatusType::Untracked => '?',
+            FileStatusType::Conflicted => '!',
+        }
+    }
+}
+
+/// Status groups the file list can be narrowed to
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatusGroup {
+    /// Tracked changes: added, modified, deleted and renamed files
+    Modified,
+    Untracked,
+    Conflicted,
+}
+
+impl StatusGroup {
+    pub const ALL: [Self; 3] = [Self::Modified, Self::Untracked, Self::Conflicted];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StatusGroup::Modified => "Modified",
+            StatusGroup::Untracked => "Untracked",
+            StatusGroup::Conflicted => "Conflicted",
+        }
+    }
+
+    pub fn contains(self, status: FileStatusType) -> bool {
+        match self {
+            StatusGroup::Modified => !matches!(
+                status,
+                FileStatusType::Untracked | FileStatusType::Conflicted
+            ),
+            StatusGroup::Untracked => status == FileStatusType::Untracked,
+            StatusGroup::Conflicted => status == FileStatusType::Conflicted,
+        }
+    }
+}
+
+/// Narrows the file list by path and status
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileFilter {
+    /// Case-insensitive substring of the path, or a glob if it contains `*`
+    /// or `?`. A glob without '/' is matched against the file name.
+    pub query: String,
+    /// Status groups to show; empty shows every status
+    pub groups: Vec<StatusGroup>,
+}
+
+impl FileFilter {
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty() || !self.groups.is_empty()
+    }
+
+    pub fn toggle_group(&mut self, group: StatusGroup) {
+        if let Some(ix) = self.groups.iter().position(|g| *g == group) {
+            self.groups.remove(ix);
+        } else {
+            self.groups.push(group);
+        }
+    }
+
+    pub fn matches(&self, file: &FileStatus) -> bool {
+        if !self.groups.is_empty() && !self.groups.iter().any(|g| g.contains(file.status)) {
+            return false;
+        }
+
+        let query = self.query.trim().to_lowercase();
+        if query.is_empty() {
+            return true;
+        }
+        let path = file.path.to_lowercase();
+        if !query.contains(['*', '?']) {
+            return path.contains(&query);
+        }
+        if query.contains('/') {
+            glob_match(&query, &path)
+        } else {
+            let name = path.rsplit('/').next().unwrap_or(&path);
+            glob_match(&query, name)
+        }
+    }
+}
+
+/// Match `text` against a glob where `*` is any run of characters and `?`
+/// is any single character
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character
+                Some((star, star_t)) => {
+                    backtrack = Some((star, star_t + 1));
+                    p = star + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
 /// File status entry
 #[derive(Clone, Debug)]
 pub struct FileStatus {
@@ -137,25 +260,58 @@ impl FileStatus {
         Ok(files)
     }
 
-    pub fn status_color(&self) -> u32 {
-        match self.status {
-            FileStatusType::Added => 0xa6e3a1,      // Green
-            FileStatusType::Modified => 0xfab387,   // Orange
-            FileStatusType::Deleted => 0xf38ba8,    // Red
-            FileStatusType::Renamed => 0x89b4fa,    // Blue
-            FileStatusType::Untracked => 0x9399b2,  // Gray
-            FileStatusType::Conflicted => 0xf9e2af, // Yellow
+    /// Delete untracked files and directories, leaving ignored files alone.
+    /// Returns the number of entries removed.
+    pub fn clean_untracked(repo: &Repository) -> Result<usize> {
+        let workdir = repo
+            .workdir()
+            .ok_or_else(|| anyhow::anyhow!("Repository has no working directory"))?;
+
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(false)
+            .include_ignored(false);
+
+        let mut removed = 0;
+        for entry in repo.statuses(Some(&mut opts))?.iter() {
+            if !entry.status().is_wt_new() {
+                continue;
+            }
+            let Some(path) = entry.path() else {
+                continue;
+            };
+            let full_path = workdir.join(path);
+            // Untracked directories are reported as a single "dir/" entry
+            if full_path.is_dir() {
+                std::fs::remove_dir_all(&full_path)?;
+            } else {
+                std::fs::remove_file(&full_path)?;
+            }
+            removed += 1;
         }
+
+        Ok(removed)
+    }
+
+    /// Paths excluded by ignore rules. Ignored directories are listed once,
+    /// with a trailing '/', rather than file by file.
+    pub fn get_ignored(repo: &Repository) -> Result<Vec<String>> {
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(false)
+            .include_ignored(true)
+            .recurse_ignored_dirs(false);
+
+        let mut paths: Vec<String> = repo
+            .statuses(Some(&mut opts))?
+            .iter()
+            .filter(|entry| entry.status().is_ignored())
+            .filter_map(|entry| entry.path().map(|p| p.to_string()))
+            .collect();
+        paths.sort();
+        Ok(paths)
     }
 
     pub fn status_char(&self) -> char {
-        match self.status {
-            FileStatusType::Added => 'A',
-            FileStatusType::Modified => 'M',
-            FileStatusType::Deleted => 'D',
-            FileStatusType::Renamed => 'R',
-            FileStatusType::Untracked => '?',
-            FileStatusType::Conflicted => '!',
-        }
+        self.status.symbol()
     }
 }
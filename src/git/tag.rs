@@ -1,15 +1,28 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
-use git2::Repository;
+use chrono::{DateTime, TimeZone, Utc};
+use git2::{Oid, Repository, Sort};
+use std::collections::HashMap;
+
+use super::commit_signature;
+
+/// Most commits listed in a release notes draft
+const RELEASE_NOTES_COMMIT_LIMIT: usize = 1000;
 
 /// Tag information
 #[derive(Clone, Debug)]
 pub struct TagInfo {
     pub name: String,
+    /// SHA of the tagged commit
     pub sha: String,
+    pub short_sha: String,
     pub message: Option<String>,
     pub is_annotated: bool,
+    /// Summary of the tagged commit
+    pub summary: String,
+    /// Tagger date for annotated tags, commit date otherwise
+    pub timestamp: DateTime<Utc>,
 }
 
 impl TagInfo {
@@ -21,24 +34,33 @@ impl TagInfo {
                 .trim_start_matches("refs/tags/")
                 .to_string();
 
-            if let Ok(obj) = repo.find_object(oid, None) {
-                let (sha, message, is_annotated) = if let Some(tag) = obj.as_tag() {
-                    (
-                        tag.target_id().to_string(),
-                        tag.message().map(|s| s.to_string()),
-                        true,
-                    )
-                } else {
-                    (oid.to_string(), None, false)
-                };
-
-                tags.push(TagInfo {
-                    name,
-                    sha,
-                    message,
-                    is_annotated,
-                });
-            }
+            let Ok(obj) = repo.find_object(oid, None) else {
+                return true;
+            };
+            let Ok(commit) = obj.peel_to_commit() else {
+                return true;
+            };
+
+            let (message, is_annotated, seconds) = if let Some(tag) = obj.as_tag() {
+                let seconds = tag
+                    .tagger()
+                    .map(|sig| sig.when().seconds())
+                    .unwrap_or_else(|| commit.time().seconds());
+                (tag.message().map(|s| s.trim().to_string()), true, seconds)
+            } else {
+                (None, false, commit.time().seconds())
+            };
+
+            let sha = commit.id().to_string();
+            tags.push(TagInfo {
+                name,
+                short_sha: sha[..7].to_string(),
+                sha,
+                message,
+                is_annotated,
+                summary: commit.summary().unwrap_or("").to_string(),
+                timestamp: Utc.timestamp_opt(seconds, 0).single().unwrap_or_else(Utc::now),
+            });
 
             true
         })?;
@@ -74,7 +96,7 @@ impl TagInfo {
             repo.head()?.peel(git2::ObjectType::Commit)?
         };
 
-        let sig = repo.signature()?;
+        let sig = commit_signature(repo)?;
         repo.tag(name, &target, &sig, message, false)?;
         Ok(())
     }
@@ -83,4 +105,78 @@ impl TagInfo {
         repo.tag_delete(name)?;
         Ok(())
     }
+
+    /// Push a tag to a remote
+    pub fn push(
+        repo: &Repository,
+        remote_name: &str,
+        name: &str,
+        callbacks: git2::RemoteCallbacks<'_>,
+    ) -> Result<()> {
+        let mut remote = repo.find_remote(remote_name)?;
+        let mut push_opts = git2::PushOptions::new();
+        push_opts.remote_callbacks(callbacks);
+
+        let refspec = format!("refs/tags/{}:refs/tags/{}", name, name);
+        remote.push(&[&refspec], Some(&mut push_opts))?;
+        Ok(())
+    }
+
+    /// Draft Markdown release notes for tag `name` at `sha`, listing the
+    /// commits since the nearest earlier tag (or all history if none)
+    pub fn release_notes(repo: &Repository, name: &str, sha: &str) -> Result<String> {
+        let target = repo.revparse_single(sha)?.peel_to_commit()?.id();
+
+        // Commit -> tags pointing at it, ignoring the tag being released
+        let mut tagged: HashMap<Oid, Vec<String>> = HashMap::new();
+        for tag in Self::get_all(repo)? {
+            if tag.name != name {
+                tagged.entry(Oid::from_str(&tag.sha)?).or_default().push(tag.name);
+            }
+        }
+
+        // The nearest tagged ancestor is the previous release
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(target)?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        let mut previous = None;
+        for oid in revwalk {
+            let oid = oid?;
+            if let Some(names) = tagged.get(&oid).filter(|_| oid != target) {
+                previous = Some((oid, names[0].clone()));
+                break;
+            }
+        }
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(target)?;
+        if let Some((oid, _)) = &previous {
+            revwalk.hide(*oid)?;
+        }
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+        let mut lines = Vec::new();
+        for oid in revwalk.take(RELEASE_NOTES_COMMIT_LIMIT) {
+            let commit = repo.find_commit(oid?)?;
+            // Merge commits only repeat what they bring in
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            let short_sha = &commit.id().to_string()[..7];
+            lines.push(format!("- {} ({})", commit.summary().unwrap_or(""), short_sha));
+        }
+
+        let mut notes = format!("# {}\n\n", name);
+        match &previous {
+            Some((_, previous)) => notes.push_str(&format!("Changes since {}:\n\n", previous)),
+            None => notes.push_str("Changes:\n\n"),
+        }
+        if lines.is_empty() {
+            notes.push_str("- No changes\n");
+        } else {
+            notes.push_str(&lines.join("\n"));
+            notes.push('\n');
+        }
+        Ok(notes)
+    }
 }
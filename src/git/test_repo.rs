@@ -0,0 +1,98 @@
+//! Throwaway repositories for the git and state tests
+
+use git2::{Oid, Repository, Signature, Time};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A repository on disk, deleted when dropped
+pub struct TestRepo {
+    dir: TempDir,
+    pub repo: Repository,
+    tick: i64,
+}
+
+impl TestRepo {
+    pub fn new() -> Self {
+        let dir = TempDir::new().expect("create temp dir");
+        let repo = Repository::init(dir.path()).expect("init repository");
+        // Merges and other operations commit with the configured identity
+        let mut config = repo.config().expect("open config");
+        config.set_str("user.name", "Test").expect("set user.name");
+        config
+            .set_str("user.email", "test@example.com")
+            .expect("set user.email");
+        let mut test_repo = Self { dir, repo, tick: 0 };
+        test_repo.commit_file("README.md", "initial\n", "Initial commit");
+        test_repo
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    pub fn write(&self, path: &str, content: &str) {
+        let full = self.path().join(path);
+        if let Some(parent) = full.parent() {
+            fs::create_dir_all(parent).expect("create directory");
+        }
+        fs::write(full, content).expect("write file");
+    }
+
+    pub fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.path().join(path)).expect("read file")
+    }
+
+    /// Write, stage and commit a file on the current branch
+    pub fn commit_file(&mut self, path: &str, content: &str, message: &str) -> Oid {
+        self.write(path, content);
+        let mut index = self.repo.index().expect("open index");
+        index.add_path(Path::new(path)).expect("stage file");
+        index.write().expect("write index");
+        let tree = self
+            .repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("find tree");
+
+        // Commits a minute apart keep the graph order deterministic
+        self.tick += 1;
+        let time = Time::new(1_600_000_000 + self.tick * 60, 0);
+        let sig = Signature::new("Test", "test@example.com", &time).expect("signature");
+        let parent = self.repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+        self.repo
+            .commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .expect("commit")
+    }
+
+    pub fn checkout(&self, branch: &str) {
+        self.repo
+            .set_head(&format!("refs/heads/{}", branch))
+            .expect("set head");
+        self.repo
+            .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .expect("checkout");
+    }
+
+    pub fn create_branch(&self, name: &str) {
+        let head = self.repo.head().unwrap().peel_to_commit().unwrap();
+        self.repo.branch(name, &head, false).expect("create branch");
+    }
+
+    /// Name of the branch the repository was initialised on
+    pub fn default_branch(&self) -> String {
+        self.repo.head().unwrap().shorthand().unwrap().to_string()
+    }
+
+    /// A `feature` branch and the default branch editing the same line
+    pub fn with_conflicting_branches(mut self) -> Self {
+        let main = self.default_branch();
+        self.commit_file("shared.txt", "base\n", "Add shared file");
+        self.create_branch("feature");
+        self.commit_file("shared.txt", "ours\n", "Change on main");
+        self.checkout("feature");
+        self.commit_file("shared.txt", "theirs\n", "Change on feature");
+        self.checkout(&main);
+        self
+    }
+}
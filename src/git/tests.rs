@@ -0,0 +1,857 @@
+//! Tests for the git layer, on commits built in memory or throwaway
+//! repositories
+
+use crate::git::remote::{self, clone_directory_name, CloneOptions, ForcePushPlan};
+use crate::git::test_repo::TestRepo;
+use crate::git::{
+    format_size, hex_dump, is_binary_content, search_files, BranchInfo, CommitInfo,
+    CommitSignature, CompareMode, Comparison, DiffDisplayOptions, EdgeType, FileBlame, GraphLayout,
+    HexPreview, HostingProvider, IgnorePattern, LfsPointer, MessageLinter, PullRequest,
+    RefMapCache, RemoteWebUrl, RepositoryInfo, RepositorySummary, SearchMatch, SearchQuery,
+    SearchScope, SignatureStatus, Snapshot, HEX_PREVIEW_BYTES,
+};
+use chrono::Utc;
+use git2::Repository;
+use std::fs;
+use std::io::Read;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn commit(sha: &str, parents: &[&str]) -> CommitInfo {
+    CommitInfo {
+        sha: sha.to_string(),
+        short_sha: sha.to_string(),
+        message: String::new(),
+        author: String::new(),
+        email: String::new(),
+        timestamp: Utc::now(),
+        parents: parents.iter().map(|p| p.to_string()).collect(),
+        branch: None,
+        branches: Vec::new(),
+        remotes: Vec::new(),
+        tags: Vec::new(),
+    }
+}
+
+/// Lay out `commits` newest first, returning each commit's column and the
+/// edges as (from, to, type)
+fn layout(
+    layout: &mut GraphLayout,
+    commits: &[(&str, &[&str])],
+) -> (Vec<usize>, Vec<(String, String, EdgeType)>) {
+    let mut columns = Vec::new();
+    let mut edges = Vec::new();
+    for (sha, parents) in commits {
+        let (node, node_edges) = layout.push(commit(sha, parents));
+        columns.push(node.column);
+        edges.extend(
+            node_edges
+                .into_iter()
+                .map(|e| (e.from_sha, e.to_sha, e.edge_type)),
+        );
+    }
+    (columns, edges)
+}
+
+#[test]
+fn linear_history_stays_in_one_lane() {
+    let mut graph = GraphLayout::new();
+    let (columns, edges) = layout(&mut graph, &[("c", &["b"]), ("b", &["a"]), ("a", &[])]);
+
+    assert_eq!(columns, [0, 0, 0]);
+    assert_eq!(graph.max_column(), 0);
+    assert!(edges.iter().all(|(_, _, kind)| *kind == EdgeType::Linear));
+}
+
+#[test]
+fn merge_opens_a_lane_that_closes_at_the_fork_point() {
+    let mut graph = GraphLayout::new();
+    let (columns, edges) = layout(
+        &mut graph,
+        &[
+            ("merge", &["main", "feature"]),
+            ("feature", &["base"]),
+            ("main", &["base"]),
+            ("base", &[]),
+        ],
+    );
+
+    assert_eq!(columns, [0, 1, 0, 0]);
+    assert_eq!(graph.max_column(), 1);
+    let kind = |from: &str, to: &str| {
+        edges
+            .iter()
+            .find(|(f, t, _)| f == from && t == to)
+            .map(|(_, _, kind)| *kind)
+    };
+    assert_eq!(kind("merge", "main"), Some(EdgeType::Linear));
+    assert_eq!(kind("merge", "feature"), Some(EdgeType::Merge));
+    assert_eq!(kind("feature", "base"), Some(EdgeType::Branch));
+    assert_eq!(kind("main", "base"), Some(EdgeType::Linear));
+}
+
+#[test]
+fn merge_parent_already_in_a_lane_joins_it() {
+    let mut graph = GraphLayout::new();
+    let (columns, _) = layout(
+        &mut graph,
+        &[
+            ("tip", &["side"]),
+            ("merge", &["main", "side"]),
+            ("side", &["base"]),
+            ("main", &["base"]),
+            ("base", &[]),
+        ],
+    );
+
+    // `side` is already waited for by the lane from `tip`, so the merge
+    // doesn't open a third one
+    assert_eq!(columns, [0, 1, 0, 1, 0]);
+    assert_eq!(graph.max_column(), 1);
+}
+
+#[test]
+fn ended_lanes_are_compacted_and_not_reused_while_occupied() {
+    let mut graph = GraphLayout::new();
+    let (columns, _) = layout(
+        &mut graph,
+        &[
+            ("t1", &["a"]),
+            ("t2", &["b"]),
+            ("t3", &["c"]),
+            // `b` is a root, so its lane ends and `c`'s lane moves left
+            ("b", &[]),
+            ("c", &["a"]),
+            // A new tip while `a` is waited for in two lanes gets its own
+            ("t4", &["a"]),
+            ("a", &[]),
+        ],
+    );
+
+    assert_eq!(columns, [0, 1, 2, 1, 1, 2, 0]);
+    assert_eq!(graph.max_column(), 2);
+}
+
+#[test]
+fn layout_continues_across_pages() {
+    let history: &[(&str, &[&str])] = &[
+        ("m2", &["m1"]),
+        ("f2", &["f1"]),
+        ("m1", &["base"]),
+        ("f1", &["base"]),
+        ("base", &[]),
+    ];
+    let mut whole = GraphLayout::new();
+    let (expected, expected_edges) = layout(&mut whole, history);
+
+    let mut paged = GraphLayout::new();
+    let (mut columns, mut edges) = layout(&mut paged, &history[..2]);
+    let (more_columns, more_edges) = layout(&mut paged, &history[2..]);
+    columns.extend(more_columns);
+    edges.extend(more_edges);
+
+    assert_eq!(columns, expected);
+    assert_eq!(columns, [0, 1, 0, 1, 0]);
+    assert_eq!(edges, expected_edges);
+}
+
+/// Lay out `commits`, where each may be the tip of a branch, returning the
+/// color of every commit by sha
+fn colors(layout: &mut GraphLayout, commits: &[(&str, &[&str], Option<&str>)]) -> Vec<usize> {
+    commits
+        .iter()
+        .map(|(sha, parents, branch)| {
+            let mut commit = commit(sha, parents);
+            commit.branch = branch.map(str::to_string);
+            layout.push(commit).0.color
+        })
+        .collect()
+}
+
+#[test]
+fn lane_colors_follow_branches_as_columns_shift() {
+    let history: &[(&str, &[&str], Option<&str>)] = &[
+        ("m2", &["base"], Some("main")),
+        ("f1", &["base"], Some("feature")),
+        ("base", &[], None),
+    ];
+    let before = colors(&mut GraphLayout::new(), history);
+
+    // A new branch above both moves them a column right
+    let mut shifted = vec![("t1", &["base"][..], Some("topic"))];
+    shifted.extend_from_slice(history);
+    let mut graph = GraphLayout::new();
+    let after = colors(&mut graph, &shifted);
+
+    assert_eq!(after[1..], before[..]);
+    assert_ne!(after[1], after[2]);
+    // The lane keeps its color down to where it ends
+    assert_eq!(before[2], before[0]);
+}
+
+#[test]
+fn merged_lanes_take_the_color_of_their_branch() {
+    let tip = colors(
+        &mut GraphLayout::new(),
+        &[("f1", &["base"], Some("feature"))],
+    );
+
+    let names = [("f1".to_string(), "feature".to_string())]
+        .into_iter()
+        .collect();
+    let mut graph = GraphLayout::new().with_branch_names(names);
+    let merged = colors(
+        &mut graph,
+        &[
+            ("merge", &["main", "f1"], Some("main")),
+            ("main", &["base"], None),
+            ("f1", &["base"], None),
+        ],
+    );
+
+    assert_eq!(merged[2], tip[0]);
+    assert_eq!(merged[1], merged[0]);
+}
+
+#[test]
+fn flat_layout_keeps_filtered_history_in_one_column() {
+    let mut graph = GraphLayout::flat();
+    let (columns, _) = layout(
+        &mut graph,
+        &[
+            ("x", &["hidden1"]),
+            ("y", &["hidden2"]),
+            ("z", &["hidden3"]),
+        ],
+    );
+
+    assert_eq!(columns, [0, 0, 0]);
+    assert_eq!(graph.max_column(), 0);
+}
+
+#[test]
+fn clone_directory_name_follows_git_clone() {
+    let name = |url| clone_directory_name(url);
+    assert_eq!(
+        name("https://github.com/owner/repo.git").as_deref(),
+        Some("repo")
+    );
+    assert_eq!(
+        name("https://github.com/owner/repo/").as_deref(),
+        Some("repo")
+    );
+    assert_eq!(
+        name("git@github.com:owner/repo.git").as_deref(),
+        Some("repo")
+    );
+    assert_eq!(name("git@host:repo").as_deref(), Some("repo"));
+    assert_eq!(name("/srv/git/project").as_deref(), Some("project"));
+    assert_eq!(name(""), None);
+}
+
+#[test]
+fn compare_urls_prefill_the_pull_request_form() {
+    let github = RemoteWebUrl::parse("git@github.com:owner/repo.git").unwrap();
+    assert_eq!(
+        github.compare_url("feature/x", Some("main")),
+        "https://github.com/owner/repo/compare/main...feature/x?expand=1"
+    );
+    assert_eq!(
+        github.compare_url("feature", None),
+        "https://github.com/owner/repo/compare/feature?expand=1"
+    );
+    let gitlab = RemoteWebUrl::parse("https://gitlab.com/group/sub/repo.git").unwrap();
+    assert_eq!(
+        gitlab.compare_url("feature/x", Some("main")),
+        "https://gitlab.com/group/sub/repo/-/merge_requests/new\
+         ?merge_request%5Bsource_branch%5D=feature%2Fx&merge_request%5Btarget_branch%5D=main"
+    );
+    assert_eq!(
+        gitlab.pull_requests_api_url().as_deref(),
+        Some(
+            "https://gitlab.com/api/v4/projects/group%2Fsub%2Frepo/merge_requests\
+             ?state=opened&per_page=100"
+        )
+    );
+    let enterprise = RemoteWebUrl::parse("https://github.example.com/owner/repo").unwrap();
+    assert_eq!(
+        enterprise.pull_requests_api_url().as_deref(),
+        Some("https://github.example.com/api/v3/repos/owner/repo/pulls?state=open&per_page=100")
+    );
+}
+
+#[test]
+fn pull_requests_from_forks_are_left_out() {
+    let github = RemoteWebUrl::parse("https://github.com/Owner/Repo").unwrap();
+    let json = r#"[
+        {"number": 12, "title": "Add x", "html_url": "https://github.com/Owner/Repo/pull/12",
+         "draft": true, "head": {"ref": "feature", "repo": {"full_name": "owner/repo"}}},
+        {"number": 13, "title": "Fork", "html_url": "https://github.com/Owner/Repo/pull/13",
+         "head": {"ref": "main", "repo": {"full_name": "someone/repo"}}},
+        {"number": 14, "title": "Gone", "html_url": "https://github.com/Owner/Repo/pull/14",
+         "head": {"ref": "old", "repo": null}}
+    ]"#;
+    assert_eq!(
+        github.parse_pull_requests(json).unwrap(),
+        [PullRequest {
+            number: 12,
+            title: "Add x".to_string(),
+            url: "https://github.com/Owner/Repo/pull/12".to_string(),
+            branch: "feature".to_string(),
+            draft: true,
+        }]
+    );
+
+    let gitlab = RemoteWebUrl::parse("git@gitlab.com:group/repo.git").unwrap();
+    let json = r#"[
+        {"iid": 3, "title": "Fix", "web_url": "https://gitlab.com/group/repo/-/merge_requests/3",
+         "source_branch": "fix", "source_project_id": 1, "target_project_id": 1},
+        {"iid": 4, "title": "Fork", "web_url": "https://gitlab.com/group/repo/-/merge_requests/4",
+         "source_branch": "main", "source_project_id": 2, "target_project_id": 1}
+    ]"#;
+    let requests = gitlab.parse_pull_requests(json).unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        (requests[0].number, requests[0].branch.as_str()),
+        (3, "fix")
+    );
+    assert_eq!(HostingProvider::GitLab.pull_request_reference(3), "!3");
+}
+
+#[test]
+fn message_lint_flags_common_subject_problems() {
+    let linter = MessageLinter::default();
+    let rules =
+        |message: &str| -> Vec<&str> { linter.lint(message).into_iter().map(|w| w.rule).collect() };
+
+    assert!(rules("Add archive export\n\nWrites zip files.").is_empty());
+    assert!(rules("feat(ui): show avatars").is_empty());
+    assert_eq!(rules(&"a".repeat(73)), ["subject-length"]);
+    assert_eq!(rules("Add export."), ["subject-period"]);
+    assert!(rules("Add export...").is_empty());
+    assert_eq!(
+        rules("Added export\nMore text"),
+        ["imperative-mood", "blank-line"]
+    );
+    assert_eq!(rules("fix: dropped frames"), ["imperative-mood"]);
+
+    let warnings = linter.lint("Fixes the build");
+    assert_eq!(
+        warnings[0].message,
+        "Use the imperative mood: \"Fix\" instead of \"Fixes\""
+    );
+    let warnings = linter.lint("Updating docs");
+    assert_eq!(
+        warnings[0].message,
+        "Use the imperative mood: \"Update\" instead of \"Updating\""
+    );
+
+    // Messages git writes itself are left alone
+    assert!(rules("Merge branch 'feature' into main.").is_empty());
+    assert!(rules("fixup! Added export.").is_empty());
+}
+
+#[test]
+fn lfs_pointers_are_parsed_and_sizes_formatted() {
+    let oid = "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+    let pointer = format!(
+        "version https://git-lfs.github.com/spec/v1\noid sha256:{}\nsize 12345\n",
+        oid
+    );
+    assert_eq!(
+        LfsPointer::parse(pointer.as_bytes()),
+        Some(LfsPointer {
+            oid: oid.to_string(),
+            size: 12345
+        })
+    );
+    // Not a pointer: other content, a bad oid or a missing size
+    assert_eq!(LfsPointer::parse(b"version 1\nsize 3\n"), None);
+    let short_oid = "version https://git-lfs.github.com/spec/v1\noid sha256:abc\nsize 1\n";
+    assert_eq!(LfsPointer::parse(short_oid.as_bytes()), None);
+    let no_size = format!(
+        "version https://git-lfs.github.com/spec/v1\noid sha256:{}\n",
+        oid
+    );
+    assert_eq!(LfsPointer::parse(no_size.as_bytes()), None);
+
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(1536), "1.5 KB");
+    assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+}
+
+#[test]
+fn binary_content_is_detected_and_dumped_as_hex() {
+    assert!(is_binary_content(b"GIF89a\0\x01"));
+    assert!(!is_binary_content("plain text\n".as_bytes()));
+    // Only the start is checked, as git does
+    let mut late_nul = vec![b'a'; 9000];
+    late_nul.push(0);
+    assert!(!is_binary_content(&late_nul));
+
+    let rows = hex_dump(b"GIF89a\0\x01 hello!!\xff");
+    assert_eq!(
+        rows,
+        [
+            "00000000  47 49 46 38 39 61 00 01  20 68 65 6c 6c 6f 21 21  |GIF89a.. hello!!|",
+            "00000010  ff                                                |.|",
+        ]
+    );
+
+    let preview = HexPreview::new(&[], &[0; 4096]);
+    assert!(preview.old.is_empty());
+    assert_eq!(preview.new.len(), HEX_PREVIEW_BYTES / 16);
+}
+
+#[test]
+fn allowed_signers_must_name_the_whole_key() {
+    let signed = |key_id: &str, fingerprint: &str| CommitSignature {
+        sha: "abc".to_string(),
+        summary: String::new(),
+        status: SignatureStatus::Good,
+        key_id: Some(key_id.to_string()),
+        fingerprint: Some(fingerprint.to_string()),
+        signer: None,
+    };
+    let allowed = |entries: &[&str]| entries.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+
+    let gpg = signed(
+        "4AA5B7F3C91D2E60",
+        "0F1E2D3C4B5A69788796A5B44AA5B7F3C91D2E60",
+    );
+    assert!(gpg.is_allowed(&[]));
+    assert!(gpg.is_allowed(&allowed(&["0f1e2d3c4b5a69788796a5b44aa5b7f3c91d2e60"])));
+    assert!(gpg.is_allowed(&allowed(&[" 4AA5B7F3C91D2E60 "])));
+    // Short IDs and other partial matches don't count
+    assert!(!gpg.is_allowed(&allowed(&["C91D2E60"])));
+    assert!(!gpg.is_allowed(&allowed(&["FF4AA5B7F3C91D2E60"])));
+    assert!(!gpg.is_allowed(&allowed(&[""])));
+
+    let ssh_key = "SHA256:q8Fz0nKkYb3XhVd2Lr9aPw1uTe7sGm4cJo6iNy5QxAB";
+    let ssh = signed(ssh_key, ssh_key);
+    assert!(ssh.is_allowed(&allowed(&[ssh_key])));
+    assert!(!ssh.is_allowed(&allowed(&[&ssh_key.to_lowercase()])));
+
+    let unsigned = CommitSignature {
+        status: SignatureStatus::Unsigned,
+        ..signed("", "")
+    };
+    assert!(!unsigned.is_allowed(&[]));
+}
+
+#[test]
+fn repository_summary_reports_branch_and_dirty_state() {
+    let repo = TestRepo::new();
+    let branch = repo.default_branch();
+
+    let clean = RepositorySummary::read(repo.path()).unwrap();
+    assert_eq!(clean.branch.as_deref(), Some(branch.as_str()));
+    assert!(!clean.dirty);
+
+    repo.write("new.txt", "untracked\n");
+    assert!(RepositorySummary::read(repo.path()).unwrap().dirty);
+
+    let missing = repo.path().join("moved-away");
+    assert!(RepositorySummary::read(&missing).is_err());
+}
+
+#[test]
+fn repository_info_reports_operation_in_progress_and_last_fetch() {
+    let repo = TestRepo::new().with_conflicting_branches();
+
+    let info = RepositoryInfo::from_repo(&repo.repo).unwrap();
+    assert_eq!(info.state_label(), None);
+    assert!(info.last_fetch.is_none());
+
+    // A conflicting merge stops half way, like `git merge feature`
+    let feature = repo.repo.find_reference("refs/heads/feature").unwrap();
+    let feature = repo.repo.reference_to_annotated_commit(&feature).unwrap();
+    repo.repo.merge(&[&feature], None, None).unwrap();
+    fs::write(repo.repo.path().join("FETCH_HEAD"), "").unwrap();
+
+    let info = RepositoryInfo::from_repo(&repo.repo).unwrap();
+    assert_eq!(info.state_label(), Some("Merging"));
+    assert!(info.last_fetch.is_some());
+}
+
+#[test]
+fn fetching_all_remotes_prunes_deleted_branches_only_when_asked() {
+    let mut upstream = TestRepo::new();
+    let head = upstream.commit_file("a.txt", "a\n", "Add a");
+    let commit = upstream.repo.find_commit(head).unwrap();
+    upstream.repo.branch("gone", &commit, false).unwrap();
+
+    let local = TestRepo::new();
+    // A remote that can't be reached doesn't stop the others
+    let missing = local.path().join("missing");
+    local
+        .repo
+        .remote("broken", missing.to_str().unwrap())
+        .unwrap();
+    local
+        .repo
+        .remote("origin", upstream.path().to_str().unwrap())
+        .unwrap();
+    let tracking = || {
+        local
+            .repo
+            .find_branch("origin/gone", git2::BranchType::Remote)
+    };
+
+    let report = remote::fetch_all_remotes(&local.repo, None, false).unwrap();
+    assert_eq!(report.fetched, ["origin"]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, "broken");
+    assert!(tracking().is_ok());
+
+    upstream
+        .repo
+        .find_branch("gone", git2::BranchType::Local)
+        .unwrap()
+        .delete()
+        .unwrap();
+    remote::fetch_all_remotes(&local.repo, None, false).unwrap();
+    assert!(tracking().is_ok());
+
+    remote::fetch_all_remotes(&local.repo, None, true).unwrap();
+    assert!(tracking().is_err());
+}
+
+#[test]
+fn listing_a_remote_connects_without_fetching() {
+    let mut upstream = TestRepo::new();
+    let head = upstream.commit_file("a.txt", "a\n", "Add a");
+    let commit = upstream.repo.find_commit(head).unwrap();
+    upstream.repo.branch("feature", &commit, false).unwrap();
+
+    let local = TestRepo::new();
+    local
+        .repo
+        .remote("origin", upstream.path().to_str().unwrap())
+        .unwrap();
+
+    // HEAD and both branches
+    let callbacks = git2::RemoteCallbacks::new();
+    let refs = remote::ls_remote(&local.repo, "origin", callbacks).unwrap();
+    assert_eq!(refs, 3);
+    assert!(local
+        .repo
+        .find_branch("origin/feature", git2::BranchType::Remote)
+        .is_err());
+
+    let callbacks = git2::RemoteCallbacks::new();
+    assert!(remote::ls_remote(&local.repo, "missing", callbacks).is_err());
+}
+
+#[test]
+fn force_push_with_lease_overwrites_only_the_fetched_remote_tip() {
+    // libgit2 only pushes to bare repositories on disk
+    let upstream_dir = TempDir::new().unwrap();
+    let upstream = Repository::init_bare(upstream_dir.path()).unwrap();
+    let mut local = TestRepo::new();
+    local
+        .repo
+        .remote("origin", upstream_dir.path().to_str().unwrap())
+        .unwrap();
+    local.create_branch("feature");
+    local.checkout("feature");
+    local.commit_file("a.txt", "a\n", "Add a");
+    let callbacks = git2::RemoteCallbacks::new();
+    remote::force_push_with_lease(&local.repo, "origin", "feature", None, callbacks).unwrap();
+    remote::fetch_all_remotes(&local.repo, None, false).unwrap();
+
+    // Rewrite the pushed commit, as after an amend
+    local
+        .repo
+        .reset(
+            &local.repo.revparse_single("HEAD~1").unwrap(),
+            git2::ResetType::Hard,
+            None,
+        )
+        .unwrap();
+    let rewritten = local.commit_file("a.txt", "amended\n", "Add a (amended)");
+
+    let plan = ForcePushPlan::read(&local.repo, "origin").unwrap();
+    assert_eq!(plan.remote_branch, "origin/feature");
+    assert_eq!(plan.overwritten.len(), 1);
+    assert_eq!(plan.overwritten[0].message.trim(), "Add a");
+
+    // A lease that doesn't match the remote tip is refused
+    let stale = Some(rewritten);
+    let callbacks = git2::RemoteCallbacks::new();
+    let refused = remote::force_push_with_lease(&local.repo, "origin", "feature", stale, callbacks);
+    assert!(refused.is_err());
+
+    let callbacks = git2::RemoteCallbacks::new();
+    remote::force_push_with_lease(&local.repo, "origin", "feature", plan.expected, callbacks)
+        .unwrap();
+    let pushed = upstream
+        .find_branch("feature", git2::BranchType::Local)
+        .unwrap();
+    assert_eq!(pushed.get().target(), Some(rewritten));
+}
+
+#[test]
+fn single_branch_clone_fetches_other_branches_after_full_history_fetch() {
+    let upstream = TestRepo::new();
+    let main = upstream.default_branch();
+    upstream.create_branch("feature");
+    let dir = TempDir::new().unwrap();
+    let destination = dir.path().join("clone");
+
+    let options = CloneOptions {
+        depth: None,
+        branch: Some("feature".to_string()),
+        single_branch: true,
+    };
+    let url = upstream.path().to_str().unwrap();
+    let clone = remote::clone_repository(url, &destination, None, &options).unwrap();
+    let origin_main = format!("origin/{}", main);
+    assert_eq!(clone.head().unwrap().shorthand(), Some("feature"));
+    assert!(clone
+        .find_branch(&origin_main, git2::BranchType::Remote)
+        .is_err());
+
+    remote::fetch_full_history(&clone, "origin", git2::RemoteCallbacks::new()).unwrap();
+    assert!(clone
+        .find_branch(&origin_main, git2::BranchType::Remote)
+        .is_ok());
+    assert!(!RepositoryInfo::from_repo(&clone).unwrap().is_shallow);
+
+    let options = CloneOptions {
+        single_branch: true,
+        ..CloneOptions::default()
+    };
+    let missing_branch = remote::clone_repository(url, &dir.path().join("other"), None, &options);
+    assert!(missing_branch.is_err());
+}
+
+#[test]
+fn snapshots_contain_the_tree_of_the_revision() {
+    let mut repo = TestRepo::new();
+    let first = repo.commit_file("src/main.rs", "fn main() {}\n", "Add main");
+    repo.commit_file("README.md", "# Later\n", "Add readme");
+    repo.write("untracked.txt", "not committed\n");
+    let out = TempDir::new().expect("create temp dir");
+
+    // Zip, at an older commit
+    let zip_path = out.path().join("snapshot.zip");
+    let files = Snapshot::new(first.to_string(), "project-1")
+        .write(&repo.repo, &zip_path)
+        .expect("write zip");
+    assert_eq!(files, 2);
+    let mut zip = zip::ZipArchive::new(fs::File::open(&zip_path).unwrap()).unwrap();
+    let mut names: Vec<_> = zip.file_names().map(str::to_string).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [
+            "project-1/",
+            "project-1/README.md",
+            "project-1/src/",
+            "project-1/src/main.rs"
+        ]
+    );
+    let mut content = String::new();
+    let mut readme = zip.by_name("project-1/README.md").unwrap();
+    readme.read_to_string(&mut content).unwrap();
+    assert_eq!(content, "initial\n");
+
+    // tar.gz, at the branch, without a prefix
+    let tar_path = out.path().join("snapshot.tar.gz");
+    let files = Snapshot::new(repo.default_branch(), "")
+        .write(&repo.repo, &tar_path)
+        .expect("write tarball");
+    assert_eq!(files, 2);
+    let gz = flate2::read::GzDecoder::new(fs::File::open(&tar_path).unwrap());
+    let mut names: Vec<_> = tar::Archive::new(gz)
+        .entries()
+        .unwrap()
+        .map(|entry| entry.unwrap())
+        .filter(|entry| entry.header().entry_type().is_file())
+        .map(|entry| entry.path().unwrap().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["README.md", "src/main.rs"]);
+
+    // A failed export leaves no partial file behind
+    let bad_path = out.path().join("bad.zip");
+    assert!(Snapshot::new("no-such-branch", "")
+        .write(&repo.repo, &bad_path)
+        .is_err());
+    assert!(!bad_path.exists());
+}
+
+#[test]
+fn blame_attributes_lines_to_commits_and_marks_uncommitted_ones() {
+    let mut repo = TestRepo::new();
+    let first = repo.commit_file("a.txt", "one\ntwo\n", "Add a");
+    let second = repo.commit_file("a.txt", "one\ntwo\nthree\n", "Add three");
+    repo.write("a.txt", "one\nTWO\nthree\n");
+
+    let blame = FileBlame::get(&repo.repo, "a.txt").unwrap();
+    let shas: Vec<_> = blame
+        .lines
+        .iter()
+        .map(|line| line.commit.as_ref().map(|commit| commit.sha.clone()))
+        .collect();
+    assert_eq!(
+        shas,
+        [Some(first.to_string()), None, Some(second.to_string())]
+    );
+    assert_eq!(blame.lines[2].commit.as_ref().unwrap().summary, "Add three");
+    assert_eq!(blame.lines[1].content, "TWO");
+    assert!(blame.lines.iter().all(|line| line.starts_run));
+}
+
+#[test]
+fn comparisons_follow_two_dot_and_three_dot_semantics() {
+    let mut repo = TestRepo::new();
+    let main = repo.default_branch();
+    repo.commit_file("shared.txt", "base\n", "Base");
+    repo.create_branch("feature");
+    let on_main = repo.commit_file("main.txt", "main\n", "Main work");
+    repo.checkout("feature");
+    let on_feature = repo.commit_file("feature.txt", "feature\n", "Feature work");
+
+    let paths = |comparison: &Comparison| -> Vec<String> {
+        comparison
+            .files
+            .iter()
+            .map(|file| file.path.clone())
+            .collect()
+    };
+    let shas = |commits: &[CommitInfo]| -> Vec<String> {
+        commits.iter().map(|commit| commit.sha.clone()).collect()
+    };
+
+    let compare = |mode| {
+        let options = DiffDisplayOptions::default();
+        Comparison::get(&repo.repo, &main, "feature", mode, 100, u64::MAX, options).unwrap()
+    };
+
+    // Two-dot diffs the trees directly, so main's file shows as deleted
+    let two_dot = compare(CompareMode::TwoDot);
+    assert_eq!(shas(&two_dot.head_commits), [on_feature.to_string()]);
+    assert!(two_dot.base_commits.is_empty());
+    assert_eq!(paths(&two_dot), ["feature.txt", "main.txt"]);
+    assert_eq!(two_dot.deletions(), 1);
+
+    // Three-dot lists both sides but diffs only what feature changed
+    let three_dot = compare(CompareMode::ThreeDot);
+    assert_eq!(shas(&three_dot.head_commits), [on_feature.to_string()]);
+    assert_eq!(shas(&three_dot.base_commits), [on_main.to_string()]);
+    assert_eq!(paths(&three_dot), ["feature.txt"]);
+    assert_eq!((three_dot.additions(), three_dot.deletions()), (1, 0));
+    assert!(three_dot.merge_base.is_some());
+}
+
+#[test]
+fn text_search_reads_the_working_tree_or_head_and_stops_at_the_limit() {
+    let mut repo = TestRepo::new();
+    repo.commit_file(
+        "src/lib.rs",
+        "fn needle() {}\n    let x = Needle;\n",
+        "Add lib",
+    );
+    repo.commit_file("notes.txt", "no match here\n", "Add notes");
+    repo.write("src/lib.rs", "fn needle() {}\n// needle needle\n");
+    repo.write("untracked.txt", "needle\n");
+
+    let search = |text: &str, scope, case_sensitive, limit| {
+        let query = SearchQuery {
+            text: text.to_string(),
+            scope,
+            case_sensitive,
+        };
+        let mut found = Vec::new();
+        let summary = search_files(&repo.repo, &query, limit, &mut |matches| {
+            found.extend(matches);
+            true
+        })
+        .unwrap();
+        (found, summary)
+    };
+    let lines = |found: &[SearchMatch]| -> Vec<(String, u32)> {
+        found.iter().map(|m| (m.path.clone(), m.line)).collect()
+    };
+    let lib_lines = |lines: &[u32]| -> Vec<(String, u32)> {
+        lines
+            .iter()
+            .map(|line| ("src/lib.rs".to_string(), *line))
+            .collect()
+    };
+
+    // The working tree has the unsaved edit; untracked files aren't searched
+    let (found, summary) = search("needle", SearchScope::WorkingTree, false, 100);
+    assert_eq!(lines(&found), lib_lines(&[1, 2]));
+    assert_eq!(found[1].ranges, [3..9, 10..16]);
+    assert_eq!((summary.files_searched, summary.matches), (3, 2));
+    assert!(!summary.truncated);
+
+    // HEAD has the committed line, which only matches ignoring case
+    let (found, _) = search("needle", SearchScope::Head, false, 100);
+    assert_eq!(lines(&found), lib_lines(&[1, 2]));
+    assert_eq!(found[1].ranges, vec![12..18]);
+    let (found, _) = search("needle", SearchScope::Head, true, 100);
+    assert_eq!(lines(&found), lib_lines(&[1]));
+
+    let (found, summary) = search("needle", SearchScope::WorkingTree, false, 1);
+    assert_eq!(found.len(), 1);
+    assert!(summary.truncated);
+}
+
+#[test]
+fn graph_ref_labels_are_reused_until_a_ref_changes() {
+    let mut repo = TestRepo::new();
+    let cache = RefMapCache::default();
+    let first = cache.get(&repo.repo).unwrap();
+    assert!(Arc::ptr_eq(&first, &cache.get(&repo.repo).unwrap()));
+
+    // Adding a tag or moving a branch rebuilds the maps
+    let head = repo.repo.head().unwrap().peel_to_commit().unwrap().id();
+    let target = repo.repo.find_object(head, None).unwrap();
+    repo.repo.tag_lightweight("v1", &target, false).unwrap();
+    drop(target);
+    let tagged = cache.get(&repo.repo).unwrap();
+    assert!(!Arc::ptr_eq(&first, &tagged));
+    assert_eq!(tagged.tags[&head], ["v1"]);
+
+    let oid = repo.commit_file("a.txt", "a\n", "Add a");
+    let moved = cache.get(&repo.repo).unwrap();
+    assert!(!Arc::ptr_eq(&tagged, &moved));
+    assert_eq!(moved.branches[&oid], [repo.default_branch()]);
+}
+
+#[test]
+fn invalid_branch_names_are_rejected_with_a_suggestion() {
+    assert!(BranchInfo::validate_name("feature/login").is_ok());
+
+    for (name, suggestion) in [
+        ("my new feature", "my-new-feature"),
+        ("fix..typo", "fix.typo"),
+        ("feature/", "feature"),
+        ("release//1.0", "release/1.0"),
+        ("topic.lock", "topic"),
+        ("what?", "what"),
+    ] {
+        let error = BranchInfo::validate_name(name).unwrap_err();
+        assert_eq!(error.suggestion.as_deref(), Some(suggestion), "{}", name);
+    }
+    assert_eq!(
+        BranchInfo::validate_name("...").unwrap_err().suggestion,
+        None
+    );
+}
+
+#[test]
+fn ignore_options_cover_the_file_its_extension_and_directory() {
+    assert_eq!(
+        IgnorePattern::options_for("logs/debug.log"),
+        vec![
+            IgnorePattern::File("logs/debug.log".to_string()),
+            IgnorePattern::Extension("log".to_string()),
+            IgnorePattern::Directory("logs".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,200 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::{BlameOptions, Oid, Repository};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Comment markers picked up by the scan
+const MARKERS: [&str; 2] = ["TODO", "FIXME"];
+
+/// Files larger than this are not scanned
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// A TODO/FIXME comment in a tracked file
+#[derive(Clone, Debug)]
+pub struct TodoItem {
+    pub path: String,
+    /// 1-based line in the working tree file
+    pub line: u32,
+    pub marker: &'static str,
+    /// Text following the marker
+    pub text: String,
+    /// Last commit to touch the line; `None` if the line is uncommitted
+    pub commit: Option<TodoCommit>,
+}
+
+/// Blame information for a TODO line
+#[derive(Clone, Debug)]
+pub struct TodoCommit {
+    pub sha: String,
+    pub short_sha: String,
+    pub author: String,
+    pub email: String,
+    /// Line number in that commit's version of the file
+    pub line: u32,
+}
+
+/// Per-file scan results, reused while a file's size and modification time
+/// are unchanged so repeated scans only read edited files
+#[derive(Default)]
+pub struct TodoCache {
+    /// HEAD when the cache was filled; blame goes stale when it moves
+    head: Option<Oid>,
+    files: HashMap<String, CachedFile>,
+}
+
+struct CachedFile {
+    modified: SystemTime,
+    len: u64,
+    items: Vec<TodoItem>,
+}
+
+impl TodoCache {
+    /// Scan tracked files for TODO/FIXME comments, sorted by path and line
+    pub fn scan(&mut self, repo: &Repository) -> Result<Vec<TodoItem>> {
+        let workdir = repo
+            .workdir()
+            .ok_or_else(|| anyhow::anyhow!("Repository has no working directory"))?
+            .to_path_buf();
+
+        let head = repo.head().ok().and_then(|head| head.target());
+        if head != self.head {
+            self.files.clear();
+            self.head = head;
+        }
+
+        let index = repo.index()?;
+        let mut files = HashMap::new();
+        for entry in index.iter() {
+            let path = String::from_utf8_lossy(&entry.path).to_string();
+            // Conflicted files have several index entries
+            if files.contains_key(&path) {
+                continue;
+            }
+            let Ok(metadata) = fs::metadata(workdir.join(&path)) else {
+                continue;
+            };
+            if !metadata.is_file() || metadata.len() > MAX_FILE_SIZE {
+                continue;
+            }
+            let modified = metadata.modified()?;
+            let len = metadata.len();
+
+            let cached = match self.files.remove(&path) {
+                Some(cached) if cached.modified == modified && cached.len == len => cached,
+                _ => CachedFile {
+                    modified,
+                    len,
+                    items: scan_file(repo, &workdir, &path),
+                },
+            };
+            files.insert(path, cached);
+        }
+        self.files = files;
+
+        let mut items: Vec<TodoItem> = self
+            .files
+            .values()
+            .flat_map(|file| file.items.iter().cloned())
+            .collect();
+        items.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+        Ok(items)
+    }
+}
+
+fn scan_file(repo: &Repository, workdir: &Path, path: &str) -> Vec<TodoItem> {
+    let Ok(content) = fs::read(workdir.join(path)) else {
+        return Vec::new();
+    };
+    // Skip binary files
+    if content.contains(&0) {
+        return Vec::new();
+    }
+
+    let text = String::from_utf8_lossy(&content);
+    let mut items: Vec<TodoItem> = text
+        .lines()
+        .enumerate()
+        .filter_map(|(ix, line)| {
+            let (marker, text) = find_marker(line)?;
+            Some(TodoItem {
+                path: path.to_string(),
+                line: ix as u32 + 1,
+                marker,
+                text,
+                commit: None,
+            })
+        })
+        .collect();
+
+    if !items.is_empty() {
+        if let Err(e) = blame_items(repo, path, &content, &mut items) {
+            // Files that are not in HEAD yet have nothing to blame
+            log::debug!("No blame for {}: {}", path, e);
+        }
+    }
+    items
+}
+
+/// Find a marker as a whole word, returning it and the text after it
+fn find_marker(line: &str) -> Option<(&'static str, String)> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    for marker in MARKERS {
+        for (pos, _) in line.match_indices(marker) {
+            let end = pos + marker.len();
+            let before = line[..pos].chars().next_back();
+            let after = line[end..].chars().next();
+            if before.is_some_and(is_word) || after.is_some_and(is_word) {
+                continue;
+            }
+
+            let text = line[end..]
+                .trim_start_matches(|c: char| c == ':' || c.is_whitespace())
+                .trim_end_matches("*/")
+                .trim_end_matches("-->")
+                .trim()
+                .to_string();
+            return Some((marker, text));
+        }
+    }
+    None
+}
+
+/// Fill in the last commit to touch each item's line, blaming the working
+/// tree content on top of HEAD
+fn blame_items(
+    repo: &Repository,
+    path: &str,
+    content: &[u8],
+    items: &mut [TodoItem],
+) -> Result<()> {
+    let mut opts = BlameOptions::new();
+    let blame = repo.blame_file(Path::new(path), Some(&mut opts))?;
+    let blame = blame.blame_buffer(content)?;
+
+    for item in items {
+        let Some(hunk) = blame.get_line(item.line as usize) else {
+            continue;
+        };
+        let oid = hunk.final_commit_id();
+        if oid.is_zero() {
+            continue;
+        }
+
+        let sha = oid.to_string();
+        let offset = item.line as usize - hunk.final_start_line();
+        let signature = hunk.final_signature();
+        item.commit = Some(TodoCommit {
+            short_sha: sha[..7].to_string(),
+            sha,
+            author: signature.name().unwrap_or("Unknown").to_string(),
+            email: signature.email().unwrap_or("").to_string(),
+            line: (hunk.orig_start_line() + offset) as u32,
+        });
+    }
+    Ok(())
+}
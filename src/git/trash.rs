@@ -0,0 +1,147 @@
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory inside `.git` holding backups of discarded changes
+const TRASH_DIR: &str = "awabancha-trash";
+const ENTRY_FILE: &str = "entry.json";
+const FILES_DIR: &str = "files";
+
+/// What a discard applies to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiscardScope {
+    File(String),
+    /// Tracked files under a directory; untracked files are kept
+    Directory(String),
+    /// Tracked files among a selection; untracked files are kept
+    Files(Vec<String>),
+    /// Every tracked change in the working tree
+    All,
+}
+
+impl DiscardScope {
+    pub fn title(&self) -> String {
+        match self {
+            DiscardScope::File(path) => format!("Discard changes to {}", path),
+            DiscardScope::Directory(dir) => format!("Discard changes in {}/", dir),
+            DiscardScope::Files(paths) => format!("Discard changes to {} files", paths.len()),
+            DiscardScope::All => "Discard all changes".to_string(),
+        }
+    }
+}
+
+/// A discard awaiting confirmation
+#[derive(Clone, Debug)]
+pub struct DiscardRequest {
+    pub scope: DiscardScope,
+    /// Files whose changes would be lost
+    pub files: Vec<String>,
+    /// Copy the working tree files to the trash before discarding
+    pub backup: bool,
+    /// Why the last attempt failed; nothing was discarded
+    pub error: Option<String>,
+}
+
+/// Working tree files backed up before a discard
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    /// Description of the discard, e.g. "Discard changes in src/"
+    pub label: String,
+    /// Paths relative to the working tree
+    pub files: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TrashEntry {
+    fn trash_dir(repo: &Repository) -> PathBuf {
+        repo.path().join(TRASH_DIR)
+    }
+
+    fn entry_dir(&self, repo: &Repository) -> PathBuf {
+        Self::trash_dir(repo).join(&self.id)
+    }
+
+    fn workdir(repo: &Repository) -> Result<&Path> {
+        repo.workdir()
+            .ok_or_else(|| anyhow::anyhow!("Repository has no working directory"))
+    }
+
+    /// Copy the working tree versions of `paths` into the trash.
+    ///
+    /// Returns `None` when none of the files exist on disk (e.g. all deleted).
+    pub fn backup(repo: &Repository, label: &str, paths: &[String]) -> Result<Option<Self>> {
+        let workdir = Self::workdir(repo)?;
+        let created_at = Utc::now();
+        let entry = Self {
+            id: created_at.format("%Y%m%d-%H%M%S%.3f").to_string(),
+            label: label.to_string(),
+            files: paths
+                .iter()
+                .filter(|path| workdir.join(path).is_file())
+                .cloned()
+                .collect(),
+            created_at,
+        };
+        if entry.files.is_empty() {
+            return Ok(None);
+        }
+
+        let files_dir = entry.entry_dir(repo).join(FILES_DIR);
+        for path in &entry.files {
+            let target = files_dir.join(path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(workdir.join(path), &target)
+                .with_context(|| format!("Failed to back up {}", path))?;
+        }
+        fs::write(
+            entry.entry_dir(repo).join(ENTRY_FILE),
+            serde_json::to_string_pretty(&entry)?,
+        )?;
+        Ok(Some(entry))
+    }
+
+    /// Backed up discards, newest first
+    pub fn list(repo: &Repository) -> Result<Vec<Self>> {
+        let dir = Self::trash_dir(repo);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries: Vec<Self> = fs::read_dir(dir)?
+            .filter_map(|item| item.ok())
+            .filter_map(|item| fs::read_to_string(item.path().join(ENTRY_FILE)).ok())
+            .filter_map(|content| serde_json::from_str(&content).ok())
+            .collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+        Ok(entries)
+    }
+
+    /// Write the backed up files back into the working tree, overwriting
+    /// their current contents, then remove the entry
+    pub fn restore(&self, repo: &Repository) -> Result<()> {
+        let workdir = Self::workdir(repo)?;
+        let files_dir = self.entry_dir(repo).join(FILES_DIR);
+        for path in &self.files {
+            let target = workdir.join(path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(files_dir.join(path), &target)
+                .with_context(|| format!("Failed to restore {}", path))?;
+        }
+        self.delete(repo)
+    }
+
+    pub fn delete(&self, repo: &Repository) -> Result<()> {
+        fs::remove_dir_all(self.entry_dir(repo))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+use git2::{
+    BranchType, Repository, WorktreeAddOptions, WorktreeLockStatus, WorktreePruneOptions,
+};
+use std::path::{Path, PathBuf};
+
+/// Working tree attached to a repository
+#[derive(Clone, Debug)]
+pub struct WorktreeInfo {
+    /// Worktree name (None for the main working tree)
+    pub name: Option<String>,
+    pub path: PathBuf,
+    /// Checked out branch (None when detached or unreadable)
+    pub branch: Option<String>,
+    pub is_main: bool,
+    /// Whether this is the working tree of the opened repository
+    pub is_current: bool,
+    pub is_locked: bool,
+    /// Whether the worktree directory is missing and can be pruned
+    pub is_prunable: bool,
+}
+
+impl WorktreeInfo {
+    /// List the main working tree followed by all linked worktrees
+    pub fn get_all(repo: &Repository) -> Result<Vec<Self>> {
+        let current = repo.workdir().map(normalize);
+        let mut worktrees = Vec::new();
+
+        // Linked worktrees report the main repository through commondir
+        let main_repo = Repository::open(repo.commondir())?;
+        if let Some(workdir) = main_repo.workdir() {
+            worktrees.push(WorktreeInfo {
+                name: None,
+                path: workdir.to_path_buf(),
+                branch: head_branch(&main_repo),
+                is_main: true,
+                is_current: current.as_deref() == Some(normalize(workdir).as_path()),
+                is_locked: false,
+                is_prunable: false,
+            });
+        }
+
+        for name in main_repo.worktrees()?.iter().flatten() {
+            let worktree = main_repo.find_worktree(name)?;
+            let path = worktree.path().to_path_buf();
+            let is_prunable = worktree.validate().is_err();
+            let branch = if is_prunable {
+                None
+            } else {
+                Repository::open_from_worktree(&worktree)
+                    .ok()
+                    .and_then(|r| head_branch(&r))
+            };
+
+            worktrees.push(WorktreeInfo {
+                name: Some(name.to_string()),
+                is_current: current.as_deref() == Some(normalize(&path).as_path()),
+                path,
+                branch,
+                is_main: false,
+                is_locked: !matches!(worktree.is_locked(), Ok(WorktreeLockStatus::Unlocked)),
+                is_prunable,
+            });
+        }
+
+        Ok(worktrees)
+    }
+
+    /// Add a linked worktree at `path` checking out `branch`. The branch is
+    /// created from HEAD when `create_branch` is set.
+    pub fn add(repo: &Repository, path: &Path, branch: &str, create_branch: bool) -> Result<()> {
+        if path.exists() && path.read_dir()?.next().is_some() {
+            anyhow::bail!("Directory is not empty: {}", path.display());
+        }
+
+        let branch_ref = if create_branch {
+            let head = repo.head()?.peel_to_commit()?;
+            repo.branch(branch, &head, false)?
+        } else {
+            repo.find_branch(branch, BranchType::Local)?
+        };
+
+        // Worktree names must be unique; derive one from the directory name
+        let base_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| branch.replace('/', "-"));
+        let existing: Vec<String> = repo
+            .worktrees()?
+            .iter()
+            .flatten()
+            .map(|s| s.to_string())
+            .collect();
+        let mut name = base_name.clone();
+        let mut suffix = 1;
+        while existing.contains(&name) {
+            suffix += 1;
+            name = format!("{}-{}", base_name, suffix);
+        }
+
+        let reference = branch_ref.into_reference();
+        let mut opts = WorktreeAddOptions::new();
+        opts.reference(Some(&reference));
+        repo.worktree(&name, path, Some(&opts))?;
+        Ok(())
+    }
+
+    /// Remove a linked worktree and its working directory
+    pub fn remove(repo: &Repository, name: &str, force: bool) -> Result<()> {
+        let worktree = repo.find_worktree(name)?;
+
+        if !matches!(worktree.is_locked()?, WorktreeLockStatus::Unlocked) {
+            anyhow::bail!("Worktree '{}' is locked", name);
+        }
+
+        // Refuse to throw away uncommitted work unless forced
+        if !force && worktree.validate().is_ok() {
+            let wt_repo = Repository::open_from_worktree(&worktree)?;
+            let mut opts = git2::StatusOptions::new();
+            opts.include_untracked(true).include_ignored(false);
+            if !wt_repo.statuses(Some(&mut opts))?.is_empty() {
+                anyhow::bail!("Worktree '{}' has uncommitted changes", name);
+            }
+        }
+
+        let mut opts = WorktreePruneOptions::new();
+        opts.valid(true).working_tree(true);
+        worktree.prune(Some(&mut opts))?;
+        Ok(())
+    }
+}
+
+fn head_branch(repo: &Repository) -> Option<String> {
+    let head = repo.head().ok()?;
+    if head.is_branch() {
+        head.shorthand().map(|s| s.to_string())
+    } else {
+        None
+    }
+}
+
+/// Compare worktree paths without trailing separators or symlinks
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.components().collect())
+}
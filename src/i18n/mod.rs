@@ -1,7 +1,11 @@
 #![allow(dead_code)]
 
+#[cfg(test)]
+mod tests;
 mod translations;
 
+use chrono::{DateTime, TimeDelta, Utc};
+use gpui::{App, Global};
 use std::collections::HashMap;
 
 /// Supported locales
@@ -12,6 +16,10 @@ pub enum Locale {
     Ja,
     ZhHans,
     ZhHant,
+    Ko,
+    De,
+    Fr,
+    Es,
 }
 
 impl Locale {
@@ -21,6 +29,10 @@ impl Locale {
             Locale::Ja => "ja",
             Locale::ZhHans => "zh-Hans",
             Locale::ZhHant => "zh-Hant",
+            Locale::Ko => "ko",
+            Locale::De => "de",
+            Locale::Fr => "fr",
+            Locale::Es => "es",
         }
     }
 
@@ -30,12 +42,118 @@ impl Locale {
             Locale::Ja => "日本語",
             Locale::ZhHans => "简体中文",
             Locale::ZhHant => "繁體中文",
+            Locale::Ko => "한국어",
+            Locale::De => "Deutsch",
+            Locale::Fr => "Français",
+            Locale::Es => "Español",
         }
     }
 
     pub fn all() -> &'static [Locale] {
-        &[Locale::En, Locale::Ja, Locale::ZhHans, Locale::ZhHant]
+        &[
+            Locale::En,
+            Locale::Ja,
+            Locale::ZhHans,
+            Locale::ZhHant,
+            Locale::Ko,
+            Locale::De,
+            Locale::Fr,
+            Locale::Es,
+        ]
     }
+
+    /// Locales a key is looked up in, in order, when this one lacks it
+    pub fn fallback_chain(self) -> &'static [Locale] {
+        match self {
+            Locale::En => &[Locale::En],
+            Locale::Ja => &[Locale::Ja, Locale::En],
+            Locale::ZhHans => &[Locale::ZhHans, Locale::En],
+            Locale::ZhHant => &[Locale::ZhHant, Locale::En],
+            Locale::Ko => &[Locale::Ko, Locale::En],
+            Locale::De => &[Locale::De, Locale::En],
+            Locale::Fr => &[Locale::Fr, Locale::En],
+            Locale::Es => &[Locale::Es, Locale::En],
+        }
+    }
+
+    /// Plural form a whole number takes, following the CLDR cardinal rules
+    pub fn plural_category(self, count: i64) -> PluralCategory {
+        match self {
+            // No grammatical plural
+            Locale::Ja | Locale::ZhHans | Locale::ZhHant | Locale::Ko => PluralCategory::Other,
+            // Zero is singular in French
+            Locale::Fr if count == 0 || count == 1 => PluralCategory::One,
+            Locale::En | Locale::De | Locale::Es if count == 1 => PluralCategory::One,
+            _ => PluralCategory::Other,
+        }
+    }
+
+    /// The supported locale for a BCP 47 or POSIX language tag such as
+    /// `ja-JP`, `zh_TW.UTF-8` or `zh-Hant-HK`
+    pub fn from_language_tag(tag: &str) -> Option<Locale> {
+        let tag = tag.split(['.', '@']).next()?.replace('_', "-").to_ascii_lowercase();
+        let mut parts = tag.split('-');
+        match parts.next()? {
+            "en" => Some(Locale::En),
+            "ja" => Some(Locale::Ja),
+            "ko" => Some(Locale::Ko),
+            "de" => Some(Locale::De),
+            "fr" => Some(Locale::Fr),
+            "es" => Some(Locale::Es),
+            "zh" => {
+                let traditional = parts.any(|part| matches!(part, "hant" | "tw" | "hk" | "mo"));
+                Some(if traditional { Locale::ZhHant } else { Locale::ZhHans })
+            }
+            _ => None,
+        }
+    }
+
+    /// The first supported language in the system's preferences (NSLocale on
+    /// macOS, `LC_ALL`/`LC_MESSAGES`/`LANG` on Linux), or English
+    pub fn system() -> Locale {
+        sys_locale::get_locales()
+            .find_map(|tag| Locale::from_language_tag(&tag))
+            .unwrap_or_default()
+    }
+}
+
+impl Global for Locale {}
+
+/// CLDR plural categories the supported locales use for whole numbers.
+/// Plural keys are stored as `key.one` and `key.other`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralCategory {
+    One,
+    Other,
+}
+
+impl PluralCategory {
+    fn suffix(self) -> &'static str {
+        match self {
+            PluralCategory::One => "one",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+/// Access to the current locale from any context
+pub trait ActiveLocale {
+    fn locale(&self) -> Locale;
+}
+
+impl ActiveLocale for App {
+    fn locale(&self) -> Locale {
+        *self.global::<Locale>()
+    }
+}
+
+/// Switch every window to `locale`
+pub fn set_locale(locale: Locale, cx: &mut App) {
+    if cx.has_global::<Locale>() && cx.locale() == locale {
+        return;
+    }
+    cx.set_global(locale);
+    cx.refresh_windows();
 }
 
 /// Translation function - gets a translation for the given key
@@ -52,19 +170,49 @@ pub fn t_with_vars(locale: Locale, key: &str, vars: &[(&str, &str)]) -> String {
     result
 }
 
-/// Get translation for a key, falling back to English if not found
+/// Plural translation, picking the form for `count` and substituting it
+/// for `{count}`
+pub fn t_plural(locale: Locale, key: &str, count: i64) -> String {
+    t_plural_with_vars(locale, key, count, &[])
+}
+
+/// Plural translation with variable substitution besides `{count}`
+pub fn t_plural_with_vars(locale: Locale, key: &str, count: i64, vars: &[(&str, &str)]) -> String {
+    let count_text = count.to_string();
+    let mut result = get_plural_translation(locale, key, count).replace("{count}", &count_text);
+    for (name, value) in vars {
+        result = result.replace(&format!("{{{}}}", name), value);
+    }
+    result
+}
+
+/// Get translation for a key, falling back along the locale's chain and
+/// then to the key itself
 fn get_translation(locale: Locale, key: &str) -> String {
-    let translations = get_translations(locale);
-    translations
-        .get(key)
-        .copied()
-        .or_else(|| {
-            // Fallback to English
-            if locale != Locale::En {
-                get_translations(Locale::En).get(key).copied()
-            } else {
-                None
-            }
+    locale
+        .fallback_chain()
+        .iter()
+        .find_map(|locale| get_translations(*locale).get(key).copied())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Get the form of a plural key for `count`. Each locale in the chain is
+/// tried with its own category, then with `other`, so a language can leave
+/// out forms that are the same.
+fn get_plural_translation(locale: Locale, key: &str, count: i64) -> String {
+    let other = format!("{}.{}", key, PluralCategory::Other.suffix());
+    locale
+        .fallback_chain()
+        .iter()
+        .find_map(|locale| {
+            let translations = get_translations(*locale);
+            let category = locale.plural_category(count);
+            let form = format!("{}.{}", key, category.suffix());
+            translations
+                .get(form.as_str())
+                .or_else(|| translations.get(other.as_str()))
+                .copied()
         })
         .map(|s| s.to_string())
         .unwrap_or_else(|| key.to_string())
@@ -76,25 +224,35 @@ fn get_translations(locale: Locale) -> &'static HashMap<&'static str, &'static s
         Locale::Ja => translations::JA,
         Locale::ZhHans => translations::ZH_HANS,
         Locale::ZhHant => translations::ZH_HANT,
+        Locale::Ko => translations::KO,
+        Locale::De => translations::DE,
+        Locale::Fr => translations::FR,
+        Locale::Es => translations::ES,
     }
 }
 
-/// Format relative time
-pub fn format_relative_time(locale: Locale, days: i64) -> String {
-    if days == 0 {
-        t(locale, "time.today")
-    } else if days == 1 {
-        t(locale, "time.yesterday")
+/// How long ago something happened, from "just now" through minutes, hours,
+/// days, weeks and months up to years
+pub fn format_relative_time(locale: Locale, elapsed: TimeDelta) -> String {
+    let days = elapsed.num_days();
+    if elapsed.num_minutes() < 1 {
+        t(locale, "time.justNow")
+    } else if elapsed.num_hours() < 1 {
+        t_plural(locale, "time.minutesAgo", elapsed.num_minutes())
+    } else if days < 1 {
+        t_plural(locale, "time.hoursAgo", elapsed.num_hours())
     } else if days < 7 {
-        t_with_vars(locale, "time.daysAgo", &[("days", &days.to_string())])
+        t_plural(locale, "time.daysAgo", days)
     } else if days < 30 {
-        let weeks = days / 7;
-        t_with_vars(locale, "time.weeksAgo", &[("weeks", &weeks.to_string())])
+        t_plural(locale, "time.weeksAgo", days / 7)
     } else if days < 365 {
-        let months = days / 30;
-        t_with_vars(locale, "time.monthsAgo", &[("months", &months.to_string())])
+        t_plural(locale, "time.monthsAgo", days / 30)
     } else {
-        let years = days / 365;
-        t_with_vars(locale, "time.yearsAgo", &[("years", &years.to_string())])
+        t_plural(locale, "time.yearsAgo", days / 365)
     }
 }
+
+/// How long before now `timestamp` was, e.g. "3 days ago"
+pub fn time_ago(locale: Locale, timestamp: DateTime<Utc>) -> String {
+    format_relative_time(locale, Utc::now().signed_duration_since(timestamp))
+}
@@ -0,0 +1,150 @@
+//! Tests for translation lookup, fallbacks and plural forms
+
+use super::{format_relative_time, get_translations, t, t_plural, Locale, PluralCategory};
+use chrono::TimeDelta;
+use std::collections::BTreeSet;
+
+/// A key without its plural suffix
+fn base_key(key: &str) -> &str {
+    key.strip_suffix(".one")
+        .or_else(|| key.strip_suffix(".other"))
+        .unwrap_or(key)
+}
+
+/// `{name}` placeholders in a translation
+fn placeholders(text: &str) -> BTreeSet<&str> {
+    text.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+#[test]
+fn every_locale_translates_every_english_key() {
+    let english = get_translations(Locale::En);
+    // Languages without a singular form only need `other`
+    let english_keys: BTreeSet<&str> = english.keys().map(|key| base_key(key)).collect();
+    for locale in Locale::all() {
+        let translations = get_translations(*locale);
+        let keys: BTreeSet<&str> = translations.keys().map(|key| base_key(key)).collect();
+        let missing: Vec<_> = english_keys.difference(&keys).collect();
+        assert!(missing.is_empty(), "{:?} is missing {:?}", locale, missing);
+        // Languages with a singular form need every form English has
+        if locale.plural_category(1) == PluralCategory::One {
+            let missing: Vec<_> = english
+                .keys()
+                .filter(|key| !translations.contains_key(*key))
+                .collect();
+            assert!(missing.is_empty(), "{:?} is missing {:?}", locale, missing);
+        }
+
+        for (key, text) in translations.iter() {
+            let english_text = english
+                .get(key)
+                .unwrap_or_else(|| panic!("{:?} has {} which English doesn't", locale, key));
+            assert_eq!(
+                placeholders(text),
+                placeholders(english_text),
+                "{:?} {} changes the placeholders",
+                locale,
+                key
+            );
+        }
+    }
+}
+
+#[test]
+fn plural_keys_always_have_an_other_form() {
+    for locale in Locale::all() {
+        let translations = get_translations(*locale);
+        for key in translations.keys().filter(|key| key.ends_with(".one")) {
+            let other = format!("{}.other", base_key(key));
+            assert!(translations.contains_key(other.as_str()), "{:?} lacks {}", locale, other);
+        }
+    }
+}
+
+#[test]
+fn fallback_chains_start_with_the_locale_and_end_in_english() {
+    for locale in Locale::all() {
+        let chain = locale.fallback_chain();
+        assert_eq!(chain.first(), Some(locale));
+        assert_eq!(chain.last(), Some(&Locale::En));
+    }
+}
+
+#[test]
+fn unknown_keys_fall_back_to_the_key_itself() {
+    for locale in Locale::all() {
+        assert_eq!(t(*locale, "no.such.key"), "no.such.key");
+        assert_eq!(t_plural(*locale, "no.such.key", 2), "no.such.key");
+    }
+}
+
+#[test]
+fn plural_categories_follow_each_language() {
+    use PluralCategory::{One, Other};
+    let categories = |locale: Locale| [0, 1, 2, 21].map(|count| locale.plural_category(count));
+    assert_eq!(categories(Locale::En), [Other, One, Other, Other]);
+    assert_eq!(categories(Locale::De), [Other, One, Other, Other]);
+    assert_eq!(categories(Locale::Es), [Other, One, Other, Other]);
+    assert_eq!(categories(Locale::Fr), [One, One, Other, Other]);
+    for locale in [Locale::Ja, Locale::Ko, Locale::ZhHans, Locale::ZhHant] {
+        assert_eq!(categories(locale), [Other; 4]);
+    }
+}
+
+#[test]
+fn plural_translations_pick_the_form_for_the_count() {
+    assert_eq!(t_plural(Locale::En, "commit.count", 1), "1 commit");
+    assert_eq!(t_plural(Locale::En, "commit.count", 0), "0 commits");
+    assert_eq!(t_plural(Locale::De, "commit.buttonFiles", 1), "Committen (1 Datei)");
+    assert_eq!(t_plural(Locale::De, "commit.buttonFiles", 3), "Committen (3 Dateien)");
+    assert_eq!(t_plural(Locale::Fr, "conflict.count", 0), "0 conflit");
+    assert_eq!(t_plural(Locale::Es, "search.results", 2), "2 resultados");
+    assert_eq!(
+        t_plural(Locale::Fr, "commit.buttonSelectedFiles", 1),
+        "Valider la sélection (1 fichier)"
+    );
+    assert_eq!(
+        t_plural(Locale::En, "commit.countFiltered", 2),
+        "2 commits (filtered)"
+    );
+    assert_eq!(t_plural(Locale::Ja, "commit.count", 1), "1件のコミット");
+    assert_eq!(t_plural(Locale::Ko, "commit.count", 1), "커밋 1개");
+    assert_eq!(t_plural(Locale::Fr, "time.monthsAgo", 1), "il y a 1 mois");
+}
+
+#[test]
+fn relative_times_go_from_minutes_to_years() {
+    assert_eq!(format_relative_time(Locale::En, TimeDelta::seconds(30)), "just now");
+    // Clock skew can put commits in the future
+    assert_eq!(format_relative_time(Locale::En, TimeDelta::minutes(-5)), "just now");
+    assert_eq!(format_relative_time(Locale::En, TimeDelta::minutes(1)), "1 minute ago");
+    assert_eq!(format_relative_time(Locale::En, TimeDelta::minutes(59)), "59 minutes ago");
+    assert_eq!(format_relative_time(Locale::En, TimeDelta::hours(1)), "1 hour ago");
+    assert_eq!(format_relative_time(Locale::En, TimeDelta::hours(30)), "1 day ago");
+    assert_eq!(format_relative_time(Locale::En, TimeDelta::days(3)), "3 days ago");
+    assert_eq!(format_relative_time(Locale::En, TimeDelta::days(7)), "1 week ago");
+    assert_eq!(format_relative_time(Locale::De, TimeDelta::days(400)), "vor 1 Jahr");
+    assert_eq!(format_relative_time(Locale::Es, TimeDelta::days(60)), "hace 2 meses");
+    assert_eq!(format_relative_time(Locale::Fr, TimeDelta::hours(2)), "il y a 2 heures");
+    assert_eq!(format_relative_time(Locale::Ja, TimeDelta::minutes(5)), "5分前");
+    assert_eq!(format_relative_time(Locale::ZhHans, TimeDelta::days(14)), "2 周前");
+}
+
+#[test]
+fn system_language_tags_map_to_supported_locales() {
+    assert_eq!(Locale::from_language_tag("en-US"), Some(Locale::En));
+    assert_eq!(Locale::from_language_tag("ja_JP.UTF-8"), Some(Locale::Ja));
+    assert_eq!(Locale::from_language_tag("zh-CN"), Some(Locale::ZhHans));
+    assert_eq!(Locale::from_language_tag("zh-Hans-SG"), Some(Locale::ZhHans));
+    assert_eq!(Locale::from_language_tag("zh_TW"), Some(Locale::ZhHant));
+    assert_eq!(Locale::from_language_tag("zh-Hant-HK"), Some(Locale::ZhHant));
+    assert_eq!(Locale::from_language_tag("ko-KR"), Some(Locale::Ko));
+    assert_eq!(Locale::from_language_tag("de_AT.UTF-8"), Some(Locale::De));
+    assert_eq!(Locale::from_language_tag("fr-CA"), Some(Locale::Fr));
+    assert_eq!(Locale::from_language_tag("es-419"), Some(Locale::Es));
+    assert_eq!(Locale::from_language_tag("pt-BR"), None);
+    assert_eq!(Locale::from_language_tag("C"), None);
+}
@@ -5,6 +5,10 @@ pub static EN: &LazyLock<HashMap<&'static str, &'static str>> = &EN_TRANSLATIONS
 pub static JA: &LazyLock<HashMap<&'static str, &'static str>> = &JA_TRANSLATIONS;
 pub static ZH_HANS: &LazyLock<HashMap<&'static str, &'static str>> = &ZH_HANS_TRANSLATIONS;
 pub static ZH_HANT: &LazyLock<HashMap<&'static str, &'static str>> = &ZH_HANT_TRANSLATIONS;
+pub static KO: &LazyLock<HashMap<&'static str, &'static str>> = &KO_TRANSLATIONS;
+pub static DE: &LazyLock<HashMap<&'static str, &'static str>> = &DE_TRANSLATIONS;
+pub static FR: &LazyLock<HashMap<&'static str, &'static str>> = &FR_TRANSLATIONS;
+pub static ES: &LazyLock<HashMap<&'static str, &'static str>> = &ES_TRANSLATIONS;
 
 static EN_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
     HashMap::from([
@@ -87,7 +91,8 @@ static EN_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
 
         // Search
         ("search.placeholder", "Search commits..."),
-        ("search.results", "{count} results"),
+        ("search.results.one", "{count} result"),
+        ("search.results.other", "{count} results"),
         ("search.none", "No results found"),
 
         // Branches & Tags
@@ -99,12 +104,18 @@ static EN_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
 
         // Commits
         ("commit.history", "Commit History"),
-        ("commit.count", "{count} commits"),
+        ("commit.count.one", "{count} commit"),
+        ("commit.count.other", "{count} commits"),
+        ("commit.countFiltered.one", "{count} commit (filtered)"),
+        ("commit.countFiltered.other", "{count} commits (filtered)"),
         ("commit.none", "No commits"),
         ("commit.messagePlaceholder", "Enter commit message..."),
         ("commit.amend", "Amend"),
         ("commit.button", "Commit"),
-        ("commit.buttonFiles", "Commit ({count} files)"),
+        ("commit.buttonFiles.one", "Commit ({count} file)"),
+        ("commit.buttonFiles.other", "Commit ({count} files)"),
+        ("commit.buttonSelectedFiles.one", "Commit Selected ({count} file)"),
+        ("commit.buttonSelectedFiles.other", "Commit Selected ({count} files)"),
         ("commit.loadMore", "Load More"),
 
         // File List
@@ -123,8 +134,10 @@ static EN_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
         ("diff.title", "Diff"),
         ("diff.close", "Close"),
         ("diff.noDiff", "No diff available"),
-        ("diff.additions", "{count} additions"),
-        ("diff.deletions", "{count} deletions"),
+        ("diff.additions.one", "{count} addition"),
+        ("diff.additions.other", "{count} additions"),
+        ("diff.deletions.one", "{count} deletion"),
+        ("diff.deletions.other", "{count} deletions"),
 
         // Context Menu
         ("context.checkout", "Checkout"),
@@ -155,21 +168,101 @@ static EN_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
 
         // Settings
         ("settings.title", "Settings"),
+        ("settings.tabGeneral", "General"),
+        ("settings.tabRepository", "Repository"),
+        ("settings.repoHint", "These settings apply only to this repository"),
+        ("settings.repoIdentity", "Commit Identity"),
+        ("settings.repoName", "Name"),
+        ("settings.repoEmail", "Email"),
+        ("settings.repoUseGitConfig", "From git config"),
+        ("settings.repoRemote", "Default Remote"),
+        ("settings.repoUseGlobal", "Global"),
+        ("settings.repoReset", "Reset to Global"),
         ("settings.general", "General"),
         ("settings.language", "Language"),
+        ("settings.theme", "Theme"),
+        ("settings.graphPalette", "Graph colors"),
+        ("settings.graphPaletteTheme", "Theme"),
+        ("settings.fetchAvatars", "Download Avatars"),
+        ("settings.themeDark", "Dark"),
+        ("settings.themeLight", "Light"),
+        ("settings.themeSystem", "System"),
+        ("settings.fetchOnOpen", "Fetch on Open"),
+        ("settings.restoreSession", "Restore Session"),
+        ("settings.fetchPrune", "Prune on Fetch"),
+        ("settings.autoFetch", "Auto-Fetch"),
+        ("settings.autostash", "Autostash on Pull/Checkout"),
+        ("settings.previewOperations", "Preview Reset Before Running"),
+        ("settings.trashDiscards", "Back Up Discarded Changes"),
+        ("settings.controlSocket", "Editor Integration Socket"),
+        ("settings.toastPosition", "Notification Position"),
+        ("settings.stickyErrors", "Keep Errors Until Dismissed"),
+        ("settings.maxDiffSize", "Largest File to Diff"),
+        ("settings.on", "On"),
+        ("settings.off", "Off"),
         ("settings.gitAuth", "Git Authentication"),
         ("settings.gitAuthMethod", "Authentication Method"),
         ("settings.gitUsername", "Username"),
         ("settings.gitUsernamePlaceholder", "Enter username"),
         ("settings.gitToken", "Token"),
         ("settings.gitTokenPlaceholder", "Enter token"),
+        ("settings.gitTokenStorage", "Stored in"),
+        ("settings.gitTokenPlaintext", "Settings file (plaintext)"),
+        ("settings.gitTokenForget", "Forget"),
         ("settings.gitTokenPaste", "Paste"),
+        ("settings.gitTokenSaved", "Saved; type a new token to replace it"),
+        ("settings.gitTestConnection", "Test Connection"),
+        ("settings.gitTestRunning", "Connecting to {remote}..."),
+        ("settings.gitTestSuccess", "Connected to {remote}"),
+        ("settings.gitIdentity", "Git Identity"),
+        ("settings.gitIdentityGlobal", "Global"),
+        ("settings.gitIdentityRepository", "This Repository"),
+        ("settings.gitIdentityHint", "Commits need a name and email"),
+        ("settings.hostingToken", "API Token for Pull Requests"),
+        ("settings.hostingTokenNone", "No token; pull request badges are off"),
+        (
+            "settings.hostingTokenHint",
+            "Stored in the system credential store and sent only to GitHub or GitLab",
+        ),
+        ("settings.editorCommand", "External Editor"),
+        ("settings.editorCommandClear", "Use System Default"),
+        (
+            "settings.editorCommandHint",
+            "{file} and {line} are replaced; an editor name alone, like code or zed, opens the line",
+        ),
         ("settings.merge", "Merge Strategy"),
         ("settings.mergeLabel", "Default Merge Mode"),
         ("settings.mergeAuto", "Auto"),
         ("settings.mergeFfOnly", "Fast-forward only"),
         ("settings.mergeNoFf", "No fast-forward"),
         ("settings.mergeSquash", "Squash"),
+        ("settings.commitMessages", "Commit Messages"),
+        ("settings.commitLint", "Message Checks"),
+        ("settings.commitLintOff", "Off"),
+        ("settings.commitLintWarn", "Warn"),
+        ("settings.commitLintBlock", "Block"),
+        ("settings.runHooks", "Run pre-commit and commit-msg Hooks"),
+        ("settings.signing", "Commit Signing"),
+        ("settings.signingPolicy", "Unsigned Incoming Commits"),
+        ("settings.signingOff", "Off"),
+        ("settings.signingWarn", "Warn"),
+        ("settings.signingBlock", "Block"),
+        ("settings.protectedBranches", "Protected Branches"),
+        ("settings.allowedSigners", "Allowed Signers"),
+        ("settings.allowedSignersAny", "Any valid signature"),
+        ("settings.signingOffHint", "Off: Signatures are not checked"),
+        (
+            "settings.signingWarnHint",
+            "Warn: Notify after fetch when incoming commits are unsigned or untrusted",
+        ),
+        (
+            "settings.signingBlockHint",
+            "Block: Refuse to pull or merge unsigned or untrusted commits into protected branches",
+        ),
+        (
+            "settings.signingListsHint",
+            "Separate entries with commas. Signers are full key fingerprints or long key IDs",
+        ),
         ("settings.about", "About"),
         ("settings.version", "Version"),
         ("settings.github", "GitHub"),
@@ -182,7 +275,8 @@ static EN_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
         // Conflict Resolution
         ("conflict.title", "Merge Conflicts"),
         ("conflict.subtitle", "Resolve conflicts to complete the merge"),
-        ("conflict.count", "{count} conflicts"),
+        ("conflict.count.one", "{count} conflict"),
+        ("conflict.count.other", "{count} conflicts"),
         ("conflict.bulkResolve", "Bulk Resolve"),
         ("conflict.perFile", "Per File"),
         ("conflict.useOurs", "Use Ours"),
@@ -194,10 +288,19 @@ static EN_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
         // Time
         ("time.today", "Today"),
         ("time.yesterday", "Yesterday"),
-        ("time.daysAgo", "{days} days ago"),
-        ("time.weeksAgo", "{weeks} weeks ago"),
-        ("time.monthsAgo", "{months} months ago"),
-        ("time.yearsAgo", "{years} years ago"),
+        ("time.justNow", "just now"),
+        ("time.minutesAgo.one", "{count} minute ago"),
+        ("time.minutesAgo.other", "{count} minutes ago"),
+        ("time.hoursAgo.one", "{count} hour ago"),
+        ("time.hoursAgo.other", "{count} hours ago"),
+        ("time.daysAgo.one", "{count} day ago"),
+        ("time.daysAgo.other", "{count} days ago"),
+        ("time.weeksAgo.one", "{count} week ago"),
+        ("time.weeksAgo.other", "{count} weeks ago"),
+        ("time.monthsAgo.one", "{count} month ago"),
+        ("time.monthsAgo.other", "{count} months ago"),
+        ("time.yearsAgo.one", "{count} year ago"),
+        ("time.yearsAgo.other", "{count} years ago"),
 
         // Header
         ("header.branch", "Branch"),
@@ -205,6 +308,11 @@ static EN_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
         ("header.ahead", "{count} ahead"),
         ("header.behind", "{count} behind"),
         ("header.settings", "Settings"),
+
+        // Status Bar
+        ("status.fetched", "Fetched {time}"),
+        ("status.fetching", "Fetching..."),
+        ("status.neverFetched", "Never fetched"),
     ])
 });
 
@@ -289,7 +397,7 @@ static JA_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
 
         // Search
         ("search.placeholder", "コミットを検索..."),
-        ("search.results", "{count}件の結果"),
+        ("search.results.other", "{count}件の結果"),
         ("search.none", "結果がありません"),
 
         // Branches & Tags
@@ -301,12 +409,14 @@ static JA_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
 
         // Commits
         ("commit.history", "コミット履歴"),
-        ("commit.count", "{count}件のコミット"),
+        ("commit.count.other", "{count}件のコミット"),
+        ("commit.countFiltered.other", "{count}件のコミット（絞り込み中）"),
         ("commit.none", "コミットはありません"),
         ("commit.messagePlaceholder", "コミットメッセージを入力..."),
         ("commit.amend", "修正"),
         ("commit.button", "コミット"),
-        ("commit.buttonFiles", "コミット（{count}ファイル）"),
+        ("commit.buttonFiles.other", "コミット（{count}ファイル）"),
+        ("commit.buttonSelectedFiles.other", "選択をコミット（{count}ファイル）"),
         ("commit.loadMore", "もっと読み込む"),
 
         // File List
@@ -325,8 +435,8 @@ static JA_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
         ("diff.title", "差分"),
         ("diff.close", "閉じる"),
         ("diff.noDiff", "差分はありません"),
-        ("diff.additions", "{count}件の追加"),
-        ("diff.deletions", "{count}件の削除"),
+        ("diff.additions.other", "{count}件の追加"),
+        ("diff.deletions.other", "{count}件の削除"),
 
         // Context Menu
         ("context.checkout", "チェックアウト"),
@@ -357,21 +467,86 @@ static JA_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
 
         // Settings
         ("settings.title", "設定"),
+        ("settings.tabGeneral", "一般"),
+        ("settings.tabRepository", "リポジトリ"),
+        ("settings.repoHint", "この設定はこのリポジトリにのみ適用されます"),
+        ("settings.repoIdentity", "コミットの作成者"),
+        ("settings.repoName", "名前"),
+        ("settings.repoEmail", "メール"),
+        ("settings.repoUseGitConfig", "git config の値"),
+        ("settings.repoRemote", "デフォルトのリモート"),
+        ("settings.repoUseGlobal", "グローバル"),
+        ("settings.repoReset", "グローバル設定に戻す"),
         ("settings.general", "一般"),
         ("settings.language", "言語"),
+        ("settings.theme", "テーマ"),
+        ("settings.graphPalette", "グラフの色"),
+        ("settings.graphPaletteTheme", "テーマ"),
+        ("settings.fetchAvatars", "アバターをダウンロード"),
+        ("settings.themeDark", "ダーク"),
+        ("settings.themeLight", "ライト"),
+        ("settings.themeSystem", "システム"),
+        ("settings.fetchOnOpen", "開いたときにフェッチ"),
+        ("settings.restoreSession", "前回の状態を復元"),
+        ("settings.fetchPrune", "フェッチ時にプルーン"),
+        ("settings.autoFetch", "自動フェッチ"),
+        ("settings.autostash", "プル・チェックアウト時に自動スタッシュ"),
+        ("settings.previewOperations", "リセット前にプレビュー"),
+        ("settings.trashDiscards", "破棄した変更をバックアップ"),
+        ("settings.controlSocket", "エディタ連携ソケット"),
+        ("settings.toastPosition", "通知の表示位置"),
+        ("settings.stickyErrors", "エラーを閉じるまで表示"),
+        ("settings.maxDiffSize", "差分を表示する最大ファイルサイズ"),
+        ("settings.on", "オン"),
+        ("settings.off", "オフ"),
         ("settings.gitAuth", "Git認証"),
         ("settings.gitAuthMethod", "認証方式"),
         ("settings.gitUsername", "ユーザー名"),
         ("settings.gitUsernamePlaceholder", "ユーザー名を入力"),
         ("settings.gitToken", "トークン"),
         ("settings.gitTokenPlaceholder", "トークンを入力"),
+        ("settings.gitTokenStorage", "保存先"),
+        ("settings.gitTokenPlaintext", "設定ファイル（平文）"),
+        ("settings.gitTokenForget", "削除"),
         ("settings.gitTokenPaste", "貼り付け"),
+        ("settings.gitTokenSaved", "保存済み（新しいトークンを入力すると置き換えます）"),
+        ("settings.gitTestConnection", "接続テスト"),
+        ("settings.gitTestRunning", "{remote} に接続中..."),
+        ("settings.gitTestSuccess", "{remote} に接続できました"),
+        ("settings.gitIdentity", "Git の作成者情報"),
+        ("settings.gitIdentityGlobal", "グローバル"),
+        ("settings.gitIdentityRepository", "このリポジトリ"),
+        ("settings.gitIdentityHint", "コミットには名前とメールアドレスが必要です"),
+        ("settings.hostingToken", "プルリクエスト用APIトークン"),
+        ("settings.hostingTokenNone", "トークン未設定のためプルリクエストは表示されません"),
+        ("settings.hostingTokenHint", "システムの資格情報ストアに保存され、GitHubまたはGitLabにのみ送信されます"),
+        ("settings.editorCommand", "外部エディタ"),
+        ("settings.editorCommandClear", "システムの既定を使用"),
+        ("settings.editorCommandHint", "{file}と{line}が置き換えられます。codeやzedなどのエディタ名だけでも該当行を開きます"),
         ("settings.merge", "マージ戦略"),
         ("settings.mergeLabel", "デフォルトマージモード"),
         ("settings.mergeAuto", "自動"),
         ("settings.mergeFfOnly", "ファストフォワードのみ"),
         ("settings.mergeNoFf", "ファストフォワードなし"),
         ("settings.mergeSquash", "スカッシュ"),
+        ("settings.commitMessages", "コミットメッセージ"),
+        ("settings.commitLint", "メッセージチェック"),
+        ("settings.commitLintOff", "オフ"),
+        ("settings.commitLintWarn", "警告"),
+        ("settings.commitLintBlock", "ブロック"),
+        ("settings.runHooks", "pre-commitとcommit-msgフックを実行"),
+        ("settings.signing", "コミット署名"),
+        ("settings.signingPolicy", "未署名の受信コミット"),
+        ("settings.signingOff", "オフ"),
+        ("settings.signingWarn", "警告"),
+        ("settings.signingBlock", "ブロック"),
+        ("settings.protectedBranches", "保護ブランチ"),
+        ("settings.allowedSigners", "許可された署名者"),
+        ("settings.allowedSignersAny", "有効な署名すべて"),
+        ("settings.signingOffHint", "オフ: 署名を確認しません"),
+        ("settings.signingWarnHint", "警告: フェッチ後、受信コミットが未署名または信頼できない場合に通知します"),
+        ("settings.signingBlockHint", "ブロック: 未署名または信頼できないコミットを保護ブランチへプル・マージしません"),
+        ("settings.signingListsHint", "項目はカンマで区切ります。署名者は鍵の完全なフィンガープリントまたはロング鍵IDで指定します"),
         ("settings.about", "このアプリについて"),
         ("settings.version", "バージョン"),
         ("settings.github", "GitHub"),
@@ -384,7 +559,7 @@ static JA_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
         // Conflict Resolution
         ("conflict.title", "マージの競合"),
         ("conflict.subtitle", "マージを完了するには競合を解決してください"),
-        ("conflict.count", "{count}件の競合"),
+        ("conflict.count.other", "{count}件の競合"),
         ("conflict.bulkResolve", "一括解決"),
         ("conflict.perFile", "ファイル単位"),
         ("conflict.useOurs", "自分の変更を使用"),
@@ -396,10 +571,13 @@ static JA_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
         // Time
         ("time.today", "今日"),
         ("time.yesterday", "昨日"),
-        ("time.daysAgo", "{days}日前"),
-        ("time.weeksAgo", "{weeks}週間前"),
-        ("time.monthsAgo", "{months}ヶ月前"),
-        ("time.yearsAgo", "{years}年前"),
+        ("time.justNow", "たった今"),
+        ("time.minutesAgo.other", "{count}分前"),
+        ("time.hoursAgo.other", "{count}時間前"),
+        ("time.daysAgo.other", "{count}日前"),
+        ("time.weeksAgo.other", "{count}週間前"),
+        ("time.monthsAgo.other", "{count}ヶ月前"),
+        ("time.yearsAgo.other", "{count}年前"),
 
         // Header
         ("header.branch", "ブランチ"),
@@ -407,6 +585,11 @@ static JA_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
         ("header.ahead", "{count}個先行"),
         ("header.behind", "{count}個遅れ"),
         ("header.settings", "設定"),
+
+        // Status Bar
+        ("status.fetched", "最終フェッチ: {time}"),
+        ("status.fetching", "フェッチ中..."),
+        ("status.neverFetched", "未フェッチ"),
     ])
 });
 
@@ -491,7 +674,7 @@ static ZH_HANS_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
 
         // Search
         ("search.placeholder", "搜索提交..."),
-        ("search.results", "{count} 个结果"),
+        ("search.results.other", "{count} 个结果"),
         ("search.none", "未找到结果"),
 
         // Branches & Tags
@@ -503,12 +686,14 @@ static ZH_HANS_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
 
         // Commits
         ("commit.history", "提交历史"),
-        ("commit.count", "{count} 个提交"),
+        ("commit.count.other", "{count} 个提交"),
+        ("commit.countFiltered.other", "{count} 个提交（已筛选）"),
         ("commit.none", "没有提交"),
         ("commit.messagePlaceholder", "输入提交信息..."),
         ("commit.amend", "修改"),
         ("commit.button", "提交"),
-        ("commit.buttonFiles", "提交（{count} 个文件）"),
+        ("commit.buttonFiles.other", "提交（{count} 个文件）"),
+        ("commit.buttonSelectedFiles.other", "提交所选（{count} 个文件）"),
         ("commit.loadMore", "加载更多"),
 
         // File List
@@ -527,8 +712,8 @@ static ZH_HANS_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
         ("diff.title", "差异"),
         ("diff.close", "关闭"),
         ("diff.noDiff", "没有差异"),
-        ("diff.additions", "{count} 处添加"),
-        ("diff.deletions", "{count} 处删除"),
+        ("diff.additions.other", "{count} 处添加"),
+        ("diff.deletions.other", "{count} 处删除"),
 
         // Context Menu
         ("context.checkout", "检出"),
@@ -559,21 +744,86 @@ static ZH_HANS_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
 
         // Settings
         ("settings.title", "设置"),
+        ("settings.tabGeneral", "通用"),
+        ("settings.tabRepository", "仓库"),
+        ("settings.repoHint", "这些设置仅适用于此仓库"),
+        ("settings.repoIdentity", "提交身份"),
+        ("settings.repoName", "名称"),
+        ("settings.repoEmail", "邮箱"),
+        ("settings.repoUseGitConfig", "使用 git config"),
+        ("settings.repoRemote", "默认远程"),
+        ("settings.repoUseGlobal", "全局"),
+        ("settings.repoReset", "恢复全局设置"),
         ("settings.general", "通用"),
         ("settings.language", "语言"),
+        ("settings.theme", "主题"),
+        ("settings.graphPalette", "图形颜色"),
+        ("settings.graphPaletteTheme", "主题"),
+        ("settings.fetchAvatars", "下载头像"),
+        ("settings.themeDark", "深色"),
+        ("settings.themeLight", "浅色"),
+        ("settings.themeSystem", "跟随系统"),
+        ("settings.fetchOnOpen", "打开时获取"),
+        ("settings.restoreSession", "恢复会话"),
+        ("settings.fetchPrune", "获取时清理"),
+        ("settings.autoFetch", "自动获取"),
+        ("settings.autostash", "拉取/检出时自动储藏"),
+        ("settings.previewOperations", "重置前预览"),
+        ("settings.trashDiscards", "备份已丢弃的更改"),
+        ("settings.controlSocket", "编辑器集成套接字"),
+        ("settings.toastPosition", "通知位置"),
+        ("settings.stickyErrors", "错误保持到手动关闭"),
+        ("settings.maxDiffSize", "显示差异的最大文件大小"),
+        ("settings.on", "开启"),
+        ("settings.off", "关闭"),
         ("settings.gitAuth", "Git认证"),
         ("settings.gitAuthMethod", "认证方式"),
         ("settings.gitUsername", "用户名"),
         ("settings.gitUsernamePlaceholder", "输入用户名"),
         ("settings.gitToken", "令牌"),
         ("settings.gitTokenPlaceholder", "输入令牌"),
+        ("settings.gitTokenStorage", "存储位置"),
+        ("settings.gitTokenPlaintext", "设置文件（明文）"),
+        ("settings.gitTokenForget", "清除"),
         ("settings.gitTokenPaste", "粘贴"),
+        ("settings.gitTokenSaved", "已保存；输入新令牌即可替换"),
+        ("settings.gitTestConnection", "测试连接"),
+        ("settings.gitTestRunning", "正在连接 {remote}..."),
+        ("settings.gitTestSuccess", "已连接到 {remote}"),
+        ("settings.gitIdentity", "Git 身份"),
+        ("settings.gitIdentityGlobal", "全局"),
+        ("settings.gitIdentityRepository", "此仓库"),
+        ("settings.gitIdentityHint", "提交需要名称和邮箱"),
+        ("settings.hostingToken", "拉取请求 API 令牌"),
+        ("settings.hostingTokenNone", "未设置令牌，不显示拉取请求"),
+        ("settings.hostingTokenHint", "保存在系统凭据存储中，仅发送到 GitHub 或 GitLab"),
+        ("settings.editorCommand", "外部编辑器"),
+        ("settings.editorCommandClear", "使用系统默认"),
+        ("settings.editorCommandHint", "{file} 和 {line} 会被替换；仅填写 code 或 zed 等编辑器名称也会打开对应行"),
         ("settings.merge", "合并策略"),
         ("settings.mergeLabel", "默认合并模式"),
         ("settings.mergeAuto", "自动"),
         ("settings.mergeFfOnly", "仅快进"),
         ("settings.mergeNoFf", "禁止快进"),
         ("settings.mergeSquash", "压缩"),
+        ("settings.commitMessages", "提交信息"),
+        ("settings.commitLint", "信息检查"),
+        ("settings.commitLintOff", "关闭"),
+        ("settings.commitLintWarn", "警告"),
+        ("settings.commitLintBlock", "阻止"),
+        ("settings.runHooks", "运行 pre-commit 和 commit-msg 钩子"),
+        ("settings.signing", "提交签名"),
+        ("settings.signingPolicy", "未签名的传入提交"),
+        ("settings.signingOff", "关闭"),
+        ("settings.signingWarn", "警告"),
+        ("settings.signingBlock", "阻止"),
+        ("settings.protectedBranches", "受保护分支"),
+        ("settings.allowedSigners", "允许的签名者"),
+        ("settings.allowedSignersAny", "任何有效签名"),
+        ("settings.signingOffHint", "关闭：不检查签名"),
+        ("settings.signingWarnHint", "警告：获取后若传入提交未签名或不受信任则通知"),
+        ("settings.signingBlockHint", "阻止：拒绝将未签名或不受信任的提交拉取或合并到受保护分支"),
+        ("settings.signingListsHint", "以逗号分隔各项。签名者需填写完整的密钥指纹或长密钥 ID"),
         ("settings.about", "关于"),
         ("settings.version", "版本"),
         ("settings.github", "GitHub"),
@@ -586,7 +836,7 @@ static ZH_HANS_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
         // Conflict Resolution
         ("conflict.title", "合并冲突"),
         ("conflict.subtitle", "解决冲突以完成合并"),
-        ("conflict.count", "{count} 个冲突"),
+        ("conflict.count.other", "{count} 个冲突"),
         ("conflict.bulkResolve", "批量解决"),
         ("conflict.perFile", "按文件"),
         ("conflict.useOurs", "使用我们的"),
@@ -598,10 +848,13 @@ static ZH_HANS_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
         // Time
         ("time.today", "今天"),
         ("time.yesterday", "昨天"),
-        ("time.daysAgo", "{days} 天前"),
-        ("time.weeksAgo", "{weeks} 周前"),
-        ("time.monthsAgo", "{months} 个月前"),
-        ("time.yearsAgo", "{years} 年前"),
+        ("time.justNow", "刚刚"),
+        ("time.minutesAgo.other", "{count} 分钟前"),
+        ("time.hoursAgo.other", "{count} 小时前"),
+        ("time.daysAgo.other", "{count} 天前"),
+        ("time.weeksAgo.other", "{count} 周前"),
+        ("time.monthsAgo.other", "{count} 个月前"),
+        ("time.yearsAgo.other", "{count} 年前"),
 
         // Header
         ("header.branch", "分支"),
@@ -609,6 +862,11 @@ static ZH_HANS_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
         ("header.ahead", "领先 {count} 个"),
         ("header.behind", "落后 {count} 个"),
         ("header.settings", "设置"),
+
+        // Status Bar
+        ("status.fetched", "上次获取：{time}"),
+        ("status.fetching", "正在获取..."),
+        ("status.neverFetched", "从未获取"),
     ])
 });
 
@@ -693,7 +951,7 @@ static ZH_HANT_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
 
         // Search
         ("search.placeholder", "搜尋提交..."),
-        ("search.results", "{count} 個結果"),
+        ("search.results.other", "{count} 個結果"),
         ("search.none", "找不到結果"),
 
         // Branches & Tags
@@ -705,12 +963,14 @@ static ZH_HANT_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
 
         // Commits
         ("commit.history", "提交歷史"),
-        ("commit.count", "{count} 個提交"),
+        ("commit.count.other", "{count} 個提交"),
+        ("commit.countFiltered.other", "{count} 個提交（已篩選）"),
         ("commit.none", "沒有提交"),
         ("commit.messagePlaceholder", "輸入提交訊息..."),
         ("commit.amend", "修改"),
         ("commit.button", "提交"),
-        ("commit.buttonFiles", "提交（{count} 個檔案）"),
+        ("commit.buttonFiles.other", "提交（{count} 個檔案）"),
+        ("commit.buttonSelectedFiles.other", "提交所選（{count} 個檔案）"),
         ("commit.loadMore", "載入更多"),
 
         // File List
@@ -729,8 +989,8 @@ static ZH_HANT_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
         ("diff.title", "差異"),
         ("diff.close", "關閉"),
         ("diff.noDiff", "沒有差異"),
-        ("diff.additions", "{count} 處新增"),
-        ("diff.deletions", "{count} 處刪除"),
+        ("diff.additions.other", "{count} 處新增"),
+        ("diff.deletions.other", "{count} 處刪除"),
 
         // Context Menu
         ("context.checkout", "簽出"),
@@ -761,21 +1021,86 @@ static ZH_HANT_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
 
         // Settings
         ("settings.title", "設定"),
+        ("settings.tabGeneral", "一般"),
+        ("settings.tabRepository", "儲存庫"),
+        ("settings.repoHint", "這些設定僅適用於此儲存庫"),
+        ("settings.repoIdentity", "提交身分"),
+        ("settings.repoName", "名稱"),
+        ("settings.repoEmail", "電子郵件"),
+        ("settings.repoUseGitConfig", "使用 git config"),
+        ("settings.repoRemote", "預設遠端"),
+        ("settings.repoUseGlobal", "全域"),
+        ("settings.repoReset", "恢復全域設定"),
         ("settings.general", "一般"),
         ("settings.language", "語言"),
+        ("settings.theme", "主題"),
+        ("settings.graphPalette", "圖形顏色"),
+        ("settings.graphPaletteTheme", "主題"),
+        ("settings.fetchAvatars", "下載頭像"),
+        ("settings.themeDark", "深色"),
+        ("settings.themeLight", "淺色"),
+        ("settings.themeSystem", "跟隨系統"),
+        ("settings.fetchOnOpen", "開啟時擷取"),
+        ("settings.restoreSession", "還原工作階段"),
+        ("settings.fetchPrune", "擷取時清理"),
+        ("settings.autoFetch", "自動擷取"),
+        ("settings.autostash", "拉取/簽出時自動儲藏"),
+        ("settings.previewOperations", "重設前預覽"),
+        ("settings.trashDiscards", "備份已捨棄的變更"),
+        ("settings.controlSocket", "編輯器整合通訊端"),
+        ("settings.toastPosition", "通知位置"),
+        ("settings.stickyErrors", "錯誤保留至手動關閉"),
+        ("settings.maxDiffSize", "顯示差異的最大檔案大小"),
+        ("settings.on", "開啟"),
+        ("settings.off", "關閉"),
         ("settings.gitAuth", "Git驗證"),
         ("settings.gitAuthMethod", "驗證方式"),
         ("settings.gitUsername", "使用者名稱"),
         ("settings.gitUsernamePlaceholder", "輸入使用者名稱"),
         ("settings.gitToken", "權杖"),
         ("settings.gitTokenPlaceholder", "輸入權杖"),
+        ("settings.gitTokenStorage", "儲存位置"),
+        ("settings.gitTokenPlaintext", "設定檔（明文）"),
+        ("settings.gitTokenForget", "清除"),
         ("settings.gitTokenPaste", "貼上"),
+        ("settings.gitTokenSaved", "已儲存；輸入新權杖即可取代"),
+        ("settings.gitTestConnection", "測試連線"),
+        ("settings.gitTestRunning", "正在連線 {remote}..."),
+        ("settings.gitTestSuccess", "已連線到 {remote}"),
+        ("settings.gitIdentity", "Git 身分"),
+        ("settings.gitIdentityGlobal", "全域"),
+        ("settings.gitIdentityRepository", "此儲存庫"),
+        ("settings.gitIdentityHint", "提交需要名稱和電子郵件"),
+        ("settings.hostingToken", "拉取請求 API 權杖"),
+        ("settings.hostingTokenNone", "未設定權杖，不顯示拉取請求"),
+        ("settings.hostingTokenHint", "儲存在系統憑證儲存區中，僅傳送到 GitHub 或 GitLab"),
+        ("settings.editorCommand", "外部編輯器"),
+        ("settings.editorCommandClear", "使用系統預設"),
+        ("settings.editorCommandHint", "{file} 和 {line} 會被取代；僅填寫 code 或 zed 等編輯器名稱也會開啟對應行"),
         ("settings.merge", "合併策略"),
         ("settings.mergeLabel", "預設合併模式"),
         ("settings.mergeAuto", "自動"),
         ("settings.mergeFfOnly", "僅快轉"),
         ("settings.mergeNoFf", "禁止快轉"),
         ("settings.mergeSquash", "壓縮"),
+        ("settings.commitMessages", "提交訊息"),
+        ("settings.commitLint", "訊息檢查"),
+        ("settings.commitLintOff", "關閉"),
+        ("settings.commitLintWarn", "警告"),
+        ("settings.commitLintBlock", "阻擋"),
+        ("settings.runHooks", "執行 pre-commit 和 commit-msg 鉤子"),
+        ("settings.signing", "提交簽章"),
+        ("settings.signingPolicy", "未簽章的傳入提交"),
+        ("settings.signingOff", "關閉"),
+        ("settings.signingWarn", "警告"),
+        ("settings.signingBlock", "阻擋"),
+        ("settings.protectedBranches", "受保護分支"),
+        ("settings.allowedSigners", "允許的簽章者"),
+        ("settings.allowedSignersAny", "任何有效簽章"),
+        ("settings.signingOffHint", "關閉：不檢查簽章"),
+        ("settings.signingWarnHint", "警告：擷取後若傳入提交未簽章或不受信任則通知"),
+        ("settings.signingBlockHint", "阻擋：拒絕將未簽章或不受信任的提交拉取或合併到受保護分支"),
+        ("settings.signingListsHint", "以逗號分隔各項。簽章者需填寫完整的金鑰指紋或長金鑰 ID"),
         ("settings.about", "關於"),
         ("settings.version", "版本"),
         ("settings.github", "GitHub"),
@@ -788,7 +1113,7 @@ static ZH_HANT_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
         // Conflict Resolution
         ("conflict.title", "合併衝突"),
         ("conflict.subtitle", "解決衝突以完成合併"),
-        ("conflict.count", "{count} 個衝突"),
+        ("conflict.count.other", "{count} 個衝突"),
         ("conflict.bulkResolve", "批次解決"),
         ("conflict.perFile", "依檔案"),
         ("conflict.useOurs", "使用我們的"),
@@ -800,10 +1125,13 @@ static ZH_HANT_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
         // Time
         ("time.today", "今天"),
         ("time.yesterday", "昨天"),
-        ("time.daysAgo", "{days} 天前"),
-        ("time.weeksAgo", "{weeks} 週前"),
-        ("time.monthsAgo", "{months} 個月前"),
-        ("time.yearsAgo", "{years} 年前"),
+        ("time.justNow", "剛剛"),
+        ("time.minutesAgo.other", "{count} 分鐘前"),
+        ("time.hoursAgo.other", "{count} 小時前"),
+        ("time.daysAgo.other", "{count} 天前"),
+        ("time.weeksAgo.other", "{count} 週前"),
+        ("time.monthsAgo.other", "{count} 個月前"),
+        ("time.yearsAgo.other", "{count} 年前"),
 
         // Header
         ("header.branch", "分支"),
@@ -811,5 +1139,1187 @@ static ZH_HANT_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = Laz
         ("header.ahead", "領先 {count} 個"),
         ("header.behind", "落後 {count} 個"),
         ("header.settings", "設定"),
+
+        // Status Bar
+        ("status.fetched", "上次擷取：{time}"),
+        ("status.fetching", "正在擷取..."),
+        ("status.neverFetched", "從未擷取"),
+    ])
+});
+
+static KO_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    HashMap::from([
+        // App
+        ("app.name", "Awabancha"),
+        ("app.tagline", "빠른 Git GUI 클라이언트"),
+        ("app.about", "정보"),
+        ("app.version", "버전"),
+
+        // Common
+        ("common.cancel", "취소"),
+        ("common.close", "닫기"),
+        ("common.save", "저장"),
+        ("common.refresh", "새로 고침"),
+        ("common.loading", "불러오는 중..."),
+        ("common.search", "검색"),
+        ("common.clear", "지우기"),
+        ("common.apply", "적용"),
+        ("common.delete", "삭제"),
+        ("common.open", "열기"),
+        ("common.create", "만들기"),
+
+        // Errors
+        ("error.openRepoFailed", "저장소를 열지 못했습니다"),
+        ("error.noRepository", "열린 저장소가 없습니다"),
+        ("error.stageFailed", "파일을 스테이징하지 못했습니다"),
+        ("error.unstageFailed", "파일 스테이징을 취소하지 못했습니다"),
+        ("error.pushFailed", "푸시하지 못했습니다"),
+        ("error.pullFailed", "풀하지 못했습니다"),
+        ("error.fetchFailed", "페치하지 못했습니다"),
+        ("error.commitFailed", "커밋을 만들지 못했습니다"),
+        ("error.stashFailed", "스태시를 저장하지 못했습니다"),
+        ("error.stashApplyFailed", "스태시를 적용하지 못했습니다"),
+        ("error.stashDropFailed", "스태시를 삭제하지 못했습니다"),
+        ("error.mergeFailed", "병합하지 못했습니다"),
+        ("error.revertFailed", "되돌리지 못했습니다"),
+        ("error.branchDeleteFailed", "브랜치를 삭제하지 못했습니다"),
+        ("error.branchCreateFailed", "브랜치를 만들지 못했습니다"),
+        ("error.tagCreateFailed", "태그를 만들지 못했습니다"),
+        ("error.tagDeleteFailed", "태그를 삭제하지 못했습니다"),
+        ("error.checkoutFailed", "체크아웃하지 못했습니다"),
+        ("error.resetFailed", "리셋하지 못했습니다"),
+        ("error.cherryPickFailed", "체리픽하지 못했습니다"),
+
+        // Toast
+        ("toast.refresh", "저장소를 새로 고쳤습니다"),
+        ("toast.push", "푸시했습니다"),
+        ("toast.pull", "풀했습니다"),
+        ("toast.fetch", "페치했습니다"),
+        ("toast.stageAll", "모든 파일을 스테이징했습니다"),
+        ("toast.unstageAll", "모든 파일의 스테이징을 취소했습니다"),
+        ("toast.stashSaved", "스태시를 저장했습니다"),
+        ("toast.stashApplied", "스태시를 적용했습니다"),
+        ("toast.stashDropped", "스태시를 삭제했습니다"),
+        ("toast.repoOpened", "{name}을(를) 열었습니다"),
+        ("toast.commit", "커밋을 만들었습니다"),
+        ("toast.amendCommit", "커밋을 수정했습니다"),
+        ("toast.revertCreated", "되돌리기 커밋을 만들었습니다"),
+        ("toast.cherryPick", "체리픽했습니다"),
+        ("toast.branchCreated", "브랜치 {name}을(를) 만들었습니다"),
+        ("toast.branchDeleted", "브랜치 {name}을(를) 삭제했습니다"),
+        ("toast.tagCreated", "태그 {name}을(를) 만들었습니다"),
+        ("toast.tagDeleted", "태그 {name}을(를) 삭제했습니다"),
+        ("toast.reset", "{sha}(으)로 리셋했습니다 ({mode})"),
+        ("toast.merge", "{name}을(를) 병합했습니다"),
+        ("toast.checkoutBranch", "브랜치 {name}을(를) 체크아웃했습니다"),
+        ("toast.checkoutCommit", "커밋 {sha}을(를) 체크아웃했습니다"),
+        ("toast.checkoutTag", "태그 {name}을(를) 체크아웃했습니다"),
+        ("toast.mergeCompleted", "병합을 완료했습니다"),
+        ("toast.mergeAborted", "병합을 중단했습니다"),
+        ("toast.discardFile", "{path}의 변경 사항을 버렸습니다"),
+        ("toast.discardAll", "모든 변경 사항을 버렸습니다"),
+
+        // Welcome
+        ("welcome.openRepo", "저장소 열기"),
+        ("welcome.dragDrop", "또는 폴더를 여기로 끌어다 놓으세요"),
+        ("welcome.recentProjects", "최근 프로젝트"),
+        ("welcome.noRecent", "최근 프로젝트가 없습니다"),
+        ("welcome.removeHint", "오른쪽 클릭으로 제거"),
+
+        // Search
+        ("search.placeholder", "커밋 검색..."),
+        ("search.results.other", "결과 {count}개"),
+        ("search.none", "결과가 없습니다"),
+
+        // Branches & Tags
+        ("branch.list", "브랜치"),
+        ("branch.local", "로컬"),
+        ("branch.remote", "원격"),
+        ("tag.list", "태그"),
+        ("tag.none", "태그 없음"),
+
+        // Commits
+        ("commit.history", "커밋 기록"),
+        ("commit.count.other", "커밋 {count}개"),
+        ("commit.countFiltered.other", "커밋 {count}개 (필터됨)"),
+        ("commit.none", "커밋 없음"),
+        ("commit.messagePlaceholder", "커밋 메시지 입력..."),
+        ("commit.amend", "수정"),
+        ("commit.button", "커밋"),
+        ("commit.buttonFiles.other", "커밋 (파일 {count}개)"),
+        ("commit.buttonSelectedFiles.other", "선택 항목 커밋 (파일 {count}개)"),
+        ("commit.loadMore", "더 불러오기"),
+
+        // File List
+        ("fileList.title", "변경 사항"),
+        ("fileList.staged", "스테이징됨"),
+        ("fileList.unstaged", "스테이징 안 됨"),
+        ("fileList.noChanges", "변경 사항 없음"),
+        ("fileList.discard", "버리기"),
+        ("fileList.discardAll", "모두 버리기"),
+        ("fileList.stageAll", "모두 스테이징"),
+        ("fileList.unstageAll", "모두 스테이징 취소"),
+        ("fileList.stagedChanges", "스테이징됨 {count}"),
+        ("fileList.unstagedChanges", "스테이징 안 됨 {count}"),
+
+        // Diff Viewer
+        ("diff.title", "차이"),
+        ("diff.close", "닫기"),
+        ("diff.noDiff", "표시할 차이가 없습니다"),
+        ("diff.additions.other", "추가 {count}개"),
+        ("diff.deletions.other", "삭제 {count}개"),
+
+        // Context Menu
+        ("context.checkout", "체크아웃"),
+        ("context.createBranch", "브랜치 만들기"),
+        ("context.createTag", "태그 만들기"),
+        ("context.revert", "되돌리기"),
+        ("context.cherryPick", "체리픽"),
+        ("context.merge", "병합"),
+        ("context.reset", "리셋"),
+        ("context.resetSoft", "Soft (변경 사항을 스테이징된 상태로 유지)"),
+        ("context.resetMixed", "Mixed (변경 사항을 스테이징 안 된 상태로 유지)"),
+        ("context.resetHard", "Hard (모든 변경 사항 버리기)"),
+        ("context.deleteBranch", "브랜치 삭제"),
+        ("context.branchNamePlaceholder", "브랜치 이름"),
+        ("context.tagNamePlaceholder", "태그 이름"),
+
+        // Left Panel
+        ("left.publish", "게시"),
+        ("left.push", "푸시"),
+        ("left.pull", "풀"),
+        ("left.fetch", "페치"),
+        ("left.stash", "스태시"),
+        ("left.stashSave", "스태시 저장"),
+        ("left.stashPop", "팝"),
+        ("left.stashApply", "적용"),
+        ("left.stashDrop", "삭제"),
+        ("left.stashEmpty", "스태시 없음"),
+
+        // Settings
+        ("settings.title", "설정"),
+        ("settings.tabGeneral", "일반"),
+        ("settings.tabRepository", "저장소"),
+        ("settings.repoHint", "이 설정은 이 저장소에만 적용됩니다"),
+        ("settings.repoIdentity", "커밋 작성자"),
+        ("settings.repoName", "이름"),
+        ("settings.repoEmail", "이메일"),
+        ("settings.repoUseGitConfig", "git config 사용"),
+        ("settings.repoRemote", "기본 원격"),
+        ("settings.repoUseGlobal", "전역"),
+        ("settings.repoReset", "전역 설정으로 초기화"),
+        ("settings.general", "일반"),
+        ("settings.language", "언어"),
+        ("settings.theme", "테마"),
+        ("settings.graphPalette", "그래프 색상"),
+        ("settings.graphPaletteTheme", "테마"),
+        ("settings.fetchAvatars", "아바타 다운로드"),
+        ("settings.themeDark", "다크"),
+        ("settings.themeLight", "라이트"),
+        ("settings.themeSystem", "시스템"),
+        ("settings.fetchOnOpen", "열 때 페치"),
+        ("settings.restoreSession", "세션 복원"),
+        ("settings.fetchPrune", "페치 시 정리"),
+        ("settings.autoFetch", "자동 페치"),
+        ("settings.autostash", "풀/체크아웃 시 자동 스태시"),
+        ("settings.previewOperations", "리셋 전에 미리 보기"),
+        ("settings.trashDiscards", "버린 변경 사항 백업"),
+        ("settings.controlSocket", "편집기 연동 소켓"),
+        ("settings.toastPosition", "알림 위치"),
+        ("settings.stickyErrors", "닫을 때까지 오류 표시"),
+        ("settings.maxDiffSize", "차이를 표시할 최대 파일 크기"),
+        ("settings.on", "켜기"),
+        ("settings.off", "끄기"),
+        ("settings.gitAuth", "Git 인증"),
+        ("settings.gitAuthMethod", "인증 방식"),
+        ("settings.gitUsername", "사용자 이름"),
+        ("settings.gitUsernamePlaceholder", "사용자 이름 입력"),
+        ("settings.gitToken", "토큰"),
+        ("settings.gitTokenPlaceholder", "토큰 입력"),
+        ("settings.gitTokenStorage", "저장 위치"),
+        ("settings.gitTokenPlaintext", "설정 파일 (평문)"),
+        ("settings.gitTokenForget", "삭제"),
+        ("settings.gitTokenPaste", "붙여넣기"),
+        ("settings.gitTokenSaved", "저장됨. 바꾸려면 새 토큰을 입력하세요"),
+        ("settings.gitTestConnection", "연결 테스트"),
+        ("settings.gitTestRunning", "{remote}에 연결하는 중..."),
+        ("settings.gitTestSuccess", "{remote}에 연결했습니다"),
+        ("settings.gitIdentity", "Git 사용자 정보"),
+        ("settings.gitIdentityGlobal", "전역"),
+        ("settings.gitIdentityRepository", "이 저장소"),
+        ("settings.gitIdentityHint", "커밋하려면 이름과 이메일이 필요합니다"),
+        ("settings.hostingToken", "풀 리퀘스트용 API 토큰"),
+        ("settings.hostingTokenNone", "토큰 없음. 풀 리퀘스트 배지가 꺼져 있습니다"),
+        (
+            "settings.hostingTokenHint",
+            "시스템 자격 증명 저장소에 보관되며 GitHub 또는 GitLab에만 전송됩니다",
+        ),
+        ("settings.editorCommand", "외부 편집기"),
+        ("settings.editorCommandClear", "시스템 기본값 사용"),
+        (
+            "settings.editorCommandHint",
+            "{file}과 {line}이 바뀝니다. code나 zed처럼 편집기 이름만 쓰면 해당 줄을 엽니다",
+        ),
+        ("settings.merge", "병합 전략"),
+        ("settings.mergeLabel", "기본 병합 방식"),
+        ("settings.mergeAuto", "자동"),
+        ("settings.mergeFfOnly", "빨리 감기만"),
+        ("settings.mergeNoFf", "빨리 감기 안 함"),
+        ("settings.mergeSquash", "스쿼시"),
+        ("settings.commitMessages", "커밋 메시지"),
+        ("settings.commitLint", "메시지 검사"),
+        ("settings.commitLintOff", "끄기"),
+        ("settings.commitLintWarn", "경고"),
+        ("settings.commitLintBlock", "차단"),
+        ("settings.runHooks", "pre-commit 및 commit-msg 훅 실행"),
+        ("settings.signing", "커밋 서명"),
+        ("settings.signingPolicy", "서명되지 않은 수신 커밋"),
+        ("settings.signingOff", "끄기"),
+        ("settings.signingWarn", "경고"),
+        ("settings.signingBlock", "차단"),
+        ("settings.protectedBranches", "보호된 브랜치"),
+        ("settings.allowedSigners", "허용된 서명자"),
+        ("settings.allowedSignersAny", "유효한 모든 서명"),
+        ("settings.signingOffHint", "끄기: 서명을 확인하지 않습니다"),
+        ("settings.signingWarnHint", "경고: 가져온 후 수신 커밋이 서명되지 않았거나 신뢰할 수 없으면 알립니다"),
+        ("settings.signingBlockHint", "차단: 서명되지 않았거나 신뢰할 수 없는 커밋을 보호된 브랜치로 풀하거나 병합하지 않습니다"),
+        ("settings.signingListsHint", "항목은 쉼표로 구분합니다. 서명자는 전체 키 지문 또는 긴 키 ID로 지정합니다"),
+        ("settings.about", "정보"),
+        ("settings.version", "버전"),
+        ("settings.github", "GitHub"),
+        ("settings.keyboard", "키보드 단축키"),
+
+        // Auth
+        ("auth.https", "HTTPS"),
+        ("auth.ssh", "SSH"),
+
+        // Conflict Resolution
+        ("conflict.title", "병합 충돌"),
+        ("conflict.subtitle", "병합을 완료하려면 충돌을 해결하세요"),
+        ("conflict.count.other", "충돌 {count}개"),
+        ("conflict.bulkResolve", "일괄 해결"),
+        ("conflict.perFile", "파일별"),
+        ("conflict.useOurs", "우리 쪽 사용"),
+        ("conflict.useTheirs", "상대 쪽 사용"),
+        ("conflict.completeMerge", "병합 완료"),
+        ("conflict.abortMerge", "병합 중단"),
+        ("conflict.noConflicts", "충돌 없음"),
+
+        // Time
+        ("time.today", "오늘"),
+        ("time.yesterday", "어제"),
+        ("time.justNow", "방금 전"),
+        ("time.minutesAgo.other", "{count}분 전"),
+        ("time.hoursAgo.other", "{count}시간 전"),
+        ("time.daysAgo.other", "{count}일 전"),
+        ("time.weeksAgo.other", "{count}주 전"),
+        ("time.monthsAgo.other", "{count}개월 전"),
+        ("time.yearsAgo.other", "{count}년 전"),
+
+        // Header
+        ("header.branch", "브랜치"),
+        ("header.detachedHead", "분리된 HEAD"),
+        ("header.ahead", "{count}개 앞섬"),
+        ("header.behind", "{count}개 뒤처짐"),
+        ("header.settings", "설정"),
+
+        // Status Bar
+        ("status.fetched", "마지막 페치: {time}"),
+        ("status.fetching", "페치하는 중..."),
+        ("status.neverFetched", "페치한 적 없음"),
+    ])
+});
+
+static DE_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    HashMap::from([
+        // App
+        ("app.name", "Awabancha"),
+        ("app.tagline", "Ein schneller Git-GUI-Client"),
+        ("app.about", "Über"),
+        ("app.version", "Version"),
+
+        // Common
+        ("common.cancel", "Abbrechen"),
+        ("common.close", "Schließen"),
+        ("common.save", "Speichern"),
+        ("common.refresh", "Aktualisieren"),
+        ("common.loading", "Wird geladen..."),
+        ("common.search", "Suchen"),
+        ("common.clear", "Leeren"),
+        ("common.apply", "Anwenden"),
+        ("common.delete", "Löschen"),
+        ("common.open", "Öffnen"),
+        ("common.create", "Erstellen"),
+
+        // Errors
+        ("error.openRepoFailed", "Repository konnte nicht geöffnet werden"),
+        ("error.noRepository", "Kein Repository geöffnet"),
+        ("error.stageFailed", "Datei konnte nicht gestaged werden"),
+        ("error.unstageFailed", "Datei konnte nicht aus dem Index entfernt werden"),
+        ("error.pushFailed", "Push fehlgeschlagen"),
+        ("error.pullFailed", "Pull fehlgeschlagen"),
+        ("error.fetchFailed", "Fetch fehlgeschlagen"),
+        ("error.commitFailed", "Commit konnte nicht erstellt werden"),
+        ("error.stashFailed", "Stash konnte nicht gespeichert werden"),
+        ("error.stashApplyFailed", "Stash konnte nicht angewendet werden"),
+        ("error.stashDropFailed", "Stash konnte nicht verworfen werden"),
+        ("error.mergeFailed", "Merge fehlgeschlagen"),
+        ("error.revertFailed", "Revert fehlgeschlagen"),
+        ("error.branchDeleteFailed", "Branch konnte nicht gelöscht werden"),
+        ("error.branchCreateFailed", "Branch konnte nicht erstellt werden"),
+        ("error.tagCreateFailed", "Tag konnte nicht erstellt werden"),
+        ("error.tagDeleteFailed", "Tag konnte nicht gelöscht werden"),
+        ("error.checkoutFailed", "Checkout fehlgeschlagen"),
+        ("error.resetFailed", "Reset fehlgeschlagen"),
+        ("error.cherryPickFailed", "Cherry-Pick fehlgeschlagen"),
+
+        // Toast
+        ("toast.refresh", "Repository aktualisiert"),
+        ("toast.push", "Push erfolgreich"),
+        ("toast.pull", "Pull erfolgreich"),
+        ("toast.fetch", "Fetch erfolgreich"),
+        ("toast.stageAll", "Alle Dateien gestaged"),
+        ("toast.unstageAll", "Alle Dateien aus dem Index entfernt"),
+        ("toast.stashSaved", "Stash gespeichert"),
+        ("toast.stashApplied", "Stash angewendet"),
+        ("toast.stashDropped", "Stash verworfen"),
+        ("toast.repoOpened", "{name} geöffnet"),
+        ("toast.commit", "Commit erstellt"),
+        ("toast.amendCommit", "Commit geändert"),
+        ("toast.revertCreated", "Revert-Commit erstellt"),
+        ("toast.cherryPick", "Cherry-Pick erfolgreich"),
+        ("toast.branchCreated", "Branch {name} erstellt"),
+        ("toast.branchDeleted", "Branch {name} gelöscht"),
+        ("toast.tagCreated", "Tag {name} erstellt"),
+        ("toast.tagDeleted", "Tag {name} gelöscht"),
+        ("toast.reset", "Auf {sha} zurückgesetzt ({mode})"),
+        ("toast.merge", "{name} gemergt"),
+        ("toast.checkoutBranch", "Branch {name} ausgecheckt"),
+        ("toast.checkoutCommit", "Commit {sha} ausgecheckt"),
+        ("toast.checkoutTag", "Tag {name} ausgecheckt"),
+        ("toast.mergeCompleted", "Merge abgeschlossen"),
+        ("toast.mergeAborted", "Merge abgebrochen"),
+        ("toast.discardFile", "Änderungen an {path} verworfen"),
+        ("toast.discardAll", "Alle Änderungen verworfen"),
+
+        // Welcome
+        ("welcome.openRepo", "Repository öffnen"),
+        ("welcome.dragDrop", "oder einen Ordner hierher ziehen"),
+        ("welcome.recentProjects", "Zuletzt geöffnet"),
+        ("welcome.noRecent", "Keine zuletzt geöffneten Projekte"),
+        ("welcome.removeHint", "Rechtsklick zum Entfernen"),
+
+        // Search
+        ("search.placeholder", "Commits durchsuchen..."),
+        ("search.results.one", "{count} Ergebnis"),
+        ("search.results.other", "{count} Ergebnisse"),
+        ("search.none", "Keine Ergebnisse gefunden"),
+
+        // Branches & Tags
+        ("branch.list", "Branches"),
+        ("branch.local", "Lokal"),
+        ("branch.remote", "Remote"),
+        ("tag.list", "Tags"),
+        ("tag.none", "Keine Tags"),
+
+        // Commits
+        ("commit.history", "Commit-Verlauf"),
+        ("commit.count.one", "{count} Commit"),
+        ("commit.count.other", "{count} Commits"),
+        ("commit.countFiltered.one", "{count} Commit (gefiltert)"),
+        ("commit.countFiltered.other", "{count} Commits (gefiltert)"),
+        ("commit.none", "Keine Commits"),
+        ("commit.messagePlaceholder", "Commit-Nachricht eingeben..."),
+        ("commit.amend", "Ändern"),
+        ("commit.button", "Committen"),
+        ("commit.buttonFiles.one", "Committen ({count} Datei)"),
+        ("commit.buttonFiles.other", "Committen ({count} Dateien)"),
+        ("commit.buttonSelectedFiles.one", "Auswahl committen ({count} Datei)"),
+        ("commit.buttonSelectedFiles.other", "Auswahl committen ({count} Dateien)"),
+        ("commit.loadMore", "Mehr laden"),
+
+        // File List
+        ("fileList.title", "Änderungen"),
+        ("fileList.staged", "Gestaged"),
+        ("fileList.unstaged", "Nicht gestaged"),
+        ("fileList.noChanges", "Keine Änderungen"),
+        ("fileList.discard", "Verwerfen"),
+        ("fileList.discardAll", "Alle verwerfen"),
+        ("fileList.stageAll", "Alle stagen"),
+        ("fileList.unstageAll", "Alle aus dem Index entfernen"),
+        ("fileList.stagedChanges", "{count} gestaged"),
+        ("fileList.unstagedChanges", "{count} nicht gestaged"),
+
+        // Diff Viewer
+        ("diff.title", "Diff"),
+        ("diff.close", "Schließen"),
+        ("diff.noDiff", "Kein Diff verfügbar"),
+        ("diff.additions.one", "{count} Hinzufügung"),
+        ("diff.additions.other", "{count} Hinzufügungen"),
+        ("diff.deletions.one", "{count} Löschung"),
+        ("diff.deletions.other", "{count} Löschungen"),
+
+        // Context Menu
+        ("context.checkout", "Auschecken"),
+        ("context.createBranch", "Branch erstellen"),
+        ("context.createTag", "Tag erstellen"),
+        ("context.revert", "Revert"),
+        ("context.cherryPick", "Cherry-Pick"),
+        ("context.merge", "Mergen"),
+        ("context.reset", "Zurücksetzen"),
+        ("context.resetSoft", "Soft (Änderungen bleiben gestaged)"),
+        ("context.resetMixed", "Mixed (Änderungen bleiben ungestaged)"),
+        ("context.resetHard", "Hard (alle Änderungen verwerfen)"),
+        ("context.deleteBranch", "Branch löschen"),
+        ("context.branchNamePlaceholder", "Branch-Name"),
+        ("context.tagNamePlaceholder", "Tag-Name"),
+
+        // Left Panel
+        ("left.publish", "Veröffentlichen"),
+        ("left.push", "Push"),
+        ("left.pull", "Pull"),
+        ("left.fetch", "Fetch"),
+        ("left.stash", "Stash"),
+        ("left.stashSave", "Stash speichern"),
+        ("left.stashPop", "Pop"),
+        ("left.stashApply", "Anwenden"),
+        ("left.stashDrop", "Verwerfen"),
+        ("left.stashEmpty", "Keine Stashes"),
+
+        // Settings
+        ("settings.title", "Einstellungen"),
+        ("settings.tabGeneral", "Allgemein"),
+        ("settings.tabRepository", "Repository"),
+        ("settings.repoHint", "Diese Einstellungen gelten nur für dieses Repository"),
+        ("settings.repoIdentity", "Commit-Identität"),
+        ("settings.repoName", "Name"),
+        ("settings.repoEmail", "E-Mail"),
+        ("settings.repoUseGitConfig", "Aus git config"),
+        ("settings.repoRemote", "Standard-Remote"),
+        ("settings.repoUseGlobal", "Global"),
+        ("settings.repoReset", "Auf global zurücksetzen"),
+        ("settings.general", "Allgemein"),
+        ("settings.language", "Sprache"),
+        ("settings.theme", "Design"),
+        ("settings.graphPalette", "Graphfarben"),
+        ("settings.graphPaletteTheme", "Design"),
+        ("settings.fetchAvatars", "Avatare herunterladen"),
+        ("settings.themeDark", "Dunkel"),
+        ("settings.themeLight", "Hell"),
+        ("settings.themeSystem", "System"),
+        ("settings.fetchOnOpen", "Beim Öffnen fetchen"),
+        ("settings.restoreSession", "Sitzung wiederherstellen"),
+        ("settings.fetchPrune", "Beim Fetch bereinigen"),
+        ("settings.autoFetch", "Automatisch fetchen"),
+        ("settings.autostash", "Autostash bei Pull/Checkout"),
+        ("settings.previewOperations", "Reset vor dem Ausführen anzeigen"),
+        ("settings.trashDiscards", "Verworfene Änderungen sichern"),
+        ("settings.controlSocket", "Socket für Editor-Integration"),
+        ("settings.toastPosition", "Position der Benachrichtigungen"),
+        ("settings.stickyErrors", "Fehler bis zum Schließen anzeigen"),
+        ("settings.maxDiffSize", "Größte Datei für Diffs"),
+        ("settings.on", "An"),
+        ("settings.off", "Aus"),
+        ("settings.gitAuth", "Git-Authentifizierung"),
+        ("settings.gitAuthMethod", "Authentifizierungsmethode"),
+        ("settings.gitUsername", "Benutzername"),
+        ("settings.gitUsernamePlaceholder", "Benutzername eingeben"),
+        ("settings.gitToken", "Token"),
+        ("settings.gitTokenPlaceholder", "Token eingeben"),
+        ("settings.gitTokenStorage", "Gespeichert in"),
+        ("settings.gitTokenPlaintext", "Einstellungsdatei (Klartext)"),
+        ("settings.gitTokenForget", "Vergessen"),
+        ("settings.gitTokenPaste", "Einfügen"),
+        ("settings.gitTokenSaved", "Gespeichert; zum Ersetzen ein neues Token eingeben"),
+        ("settings.gitTestConnection", "Verbindung testen"),
+        ("settings.gitTestRunning", "Verbindung zu {remote} wird hergestellt..."),
+        ("settings.gitTestSuccess", "Mit {remote} verbunden"),
+        ("settings.gitIdentity", "Git-Identität"),
+        ("settings.gitIdentityGlobal", "Global"),
+        ("settings.gitIdentityRepository", "Dieses Repository"),
+        ("settings.gitIdentityHint", "Commits brauchen einen Namen und eine E-Mail-Adresse"),
+        ("settings.hostingToken", "API-Token für Pull Requests"),
+        ("settings.hostingTokenNone", "Kein Token; Pull-Request-Badges sind aus"),
+        (
+            "settings.hostingTokenHint",
+            "Wird im Schlüsselbund des Systems gespeichert und nur an GitHub oder GitLab gesendet",
+        ),
+        ("settings.editorCommand", "Externer Editor"),
+        ("settings.editorCommandClear", "Systemstandard verwenden"),
+        (
+            "settings.editorCommandHint",
+            "{file} und {line} werden ersetzt; ein Editorname allein, wie code oder zed, öffnet die Zeile",
+        ),
+        ("settings.merge", "Merge-Strategie"),
+        ("settings.mergeLabel", "Standard-Merge-Modus"),
+        ("settings.mergeAuto", "Automatisch"),
+        ("settings.mergeFfOnly", "Nur Fast-Forward"),
+        ("settings.mergeNoFf", "Kein Fast-Forward"),
+        ("settings.mergeSquash", "Squash"),
+        ("settings.commitMessages", "Commit-Nachrichten"),
+        ("settings.commitLint", "Nachrichtenprüfung"),
+        ("settings.commitLintOff", "Aus"),
+        ("settings.commitLintWarn", "Warnen"),
+        ("settings.commitLintBlock", "Blockieren"),
+        ("settings.runHooks", "pre-commit- und commit-msg-Hooks ausführen"),
+        ("settings.signing", "Commit-Signatur"),
+        ("settings.signingPolicy", "Unsignierte eingehende Commits"),
+        ("settings.signingOff", "Aus"),
+        ("settings.signingWarn", "Warnen"),
+        ("settings.signingBlock", "Blockieren"),
+        ("settings.protectedBranches", "Geschützte Branches"),
+        ("settings.allowedSigners", "Zugelassene Signierer"),
+        ("settings.allowedSignersAny", "Jede gültige Signatur"),
+        ("settings.signingOffHint", "Aus: Signaturen werden nicht geprüft"),
+        ("settings.signingWarnHint", "Warnen: Nach dem Fetch benachrichtigen, wenn eingehende Commits unsigniert oder nicht vertrauenswürdig sind"),
+        ("settings.signingBlockHint", "Blockieren: Unsignierte oder nicht vertrauenswürdige Commits nicht in geschützte Branches pullen oder mergen"),
+        ("settings.signingListsHint", "Einträge mit Kommas trennen. Signierer sind vollständige Schlüssel-Fingerprints oder lange Schlüssel-IDs"),
+        ("settings.about", "Über"),
+        ("settings.version", "Version"),
+        ("settings.github", "GitHub"),
+        ("settings.keyboard", "Tastenkürzel"),
+
+        // Auth
+        ("auth.https", "HTTPS"),
+        ("auth.ssh", "SSH"),
+
+        // Conflict Resolution
+        ("conflict.title", "Merge-Konflikte"),
+        ("conflict.subtitle", "Konflikte lösen, um den Merge abzuschließen"),
+        ("conflict.count.one", "{count} Konflikt"),
+        ("conflict.count.other", "{count} Konflikte"),
+        ("conflict.bulkResolve", "Alle lösen"),
+        ("conflict.perFile", "Pro Datei"),
+        ("conflict.useOurs", "Unsere verwenden"),
+        ("conflict.useTheirs", "Ihre verwenden"),
+        ("conflict.completeMerge", "Merge abschließen"),
+        ("conflict.abortMerge", "Merge abbrechen"),
+        ("conflict.noConflicts", "Keine Konflikte"),
+
+        // Time
+        ("time.today", "Heute"),
+        ("time.yesterday", "Gestern"),
+        ("time.justNow", "gerade eben"),
+        ("time.minutesAgo.one", "vor {count} Minute"),
+        ("time.minutesAgo.other", "vor {count} Minuten"),
+        ("time.hoursAgo.one", "vor {count} Stunde"),
+        ("time.hoursAgo.other", "vor {count} Stunden"),
+        ("time.daysAgo.one", "vor {count} Tag"),
+        ("time.daysAgo.other", "vor {count} Tagen"),
+        ("time.weeksAgo.one", "vor {count} Woche"),
+        ("time.weeksAgo.other", "vor {count} Wochen"),
+        ("time.monthsAgo.one", "vor {count} Monat"),
+        ("time.monthsAgo.other", "vor {count} Monaten"),
+        ("time.yearsAgo.one", "vor {count} Jahr"),
+        ("time.yearsAgo.other", "vor {count} Jahren"),
+
+        // Header
+        ("header.branch", "Branch"),
+        ("header.detachedHead", "Losgelöster HEAD"),
+        ("header.ahead", "{count} voraus"),
+        ("header.behind", "{count} zurück"),
+        ("header.settings", "Einstellungen"),
+
+        // Status Bar
+        ("status.fetched", "Letzter Fetch {time}"),
+        ("status.fetching", "Fetch läuft..."),
+        ("status.neverFetched", "Noch nie gefetcht"),
+    ])
+});
+
+static FR_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    HashMap::from([
+        // App
+        ("app.name", "Awabancha"),
+        ("app.tagline", "Un client Git graphique rapide"),
+        ("app.about", "À propos"),
+        ("app.version", "Version"),
+
+        // Common
+        ("common.cancel", "Annuler"),
+        ("common.close", "Fermer"),
+        ("common.save", "Enregistrer"),
+        ("common.refresh", "Actualiser"),
+        ("common.loading", "Chargement..."),
+        ("common.search", "Rechercher"),
+        ("common.clear", "Effacer"),
+        ("common.apply", "Appliquer"),
+        ("common.delete", "Supprimer"),
+        ("common.open", "Ouvrir"),
+        ("common.create", "Créer"),
+
+        // Errors
+        ("error.openRepoFailed", "Impossible d'ouvrir le dépôt"),
+        ("error.noRepository", "Aucun dépôt ouvert"),
+        ("error.stageFailed", "Impossible d'indexer le fichier"),
+        ("error.unstageFailed", "Impossible de désindexer le fichier"),
+        ("error.pushFailed", "Échec du push"),
+        ("error.pullFailed", "Échec du pull"),
+        ("error.fetchFailed", "Échec du fetch"),
+        ("error.commitFailed", "Impossible de créer le commit"),
+        ("error.stashFailed", "Impossible d'enregistrer le remisage"),
+        ("error.stashApplyFailed", "Impossible d'appliquer le remisage"),
+        ("error.stashDropFailed", "Impossible de supprimer le remisage"),
+        ("error.mergeFailed", "Échec de la fusion"),
+        ("error.revertFailed", "Échec de l'annulation"),
+        ("error.branchDeleteFailed", "Impossible de supprimer la branche"),
+        ("error.branchCreateFailed", "Impossible de créer la branche"),
+        ("error.tagCreateFailed", "Impossible de créer l'étiquette"),
+        ("error.tagDeleteFailed", "Impossible de supprimer l'étiquette"),
+        ("error.checkoutFailed", "Échec du checkout"),
+        ("error.resetFailed", "Échec de la réinitialisation"),
+        ("error.cherryPickFailed", "Échec du cherry-pick"),
+
+        // Toast
+        ("toast.refresh", "Dépôt actualisé"),
+        ("toast.push", "Push réussi"),
+        ("toast.pull", "Pull réussi"),
+        ("toast.fetch", "Fetch réussi"),
+        ("toast.stageAll", "Tous les fichiers indexés"),
+        ("toast.unstageAll", "Tous les fichiers désindexés"),
+        ("toast.stashSaved", "Remisage enregistré"),
+        ("toast.stashApplied", "Remisage appliqué"),
+        ("toast.stashDropped", "Remisage supprimé"),
+        ("toast.repoOpened", "{name} ouvert"),
+        ("toast.commit", "Commit créé"),
+        ("toast.amendCommit", "Commit modifié"),
+        ("toast.revertCreated", "Commit d'annulation créé"),
+        ("toast.cherryPick", "Cherry-pick réussi"),
+        ("toast.branchCreated", "Branche {name} créée"),
+        ("toast.branchDeleted", "Branche {name} supprimée"),
+        ("toast.tagCreated", "Étiquette {name} créée"),
+        ("toast.tagDeleted", "Étiquette {name} supprimée"),
+        ("toast.reset", "Réinitialisé à {sha} ({mode})"),
+        ("toast.merge", "{name} fusionné"),
+        ("toast.checkoutBranch", "Branche {name} extraite"),
+        ("toast.checkoutCommit", "Commit {sha} extrait"),
+        ("toast.checkoutTag", "Étiquette {name} extraite"),
+        ("toast.mergeCompleted", "Fusion terminée"),
+        ("toast.mergeAborted", "Fusion abandonnée"),
+        ("toast.discardFile", "Modifications de {path} abandonnées"),
+        ("toast.discardAll", "Toutes les modifications abandonnées"),
+
+        // Welcome
+        ("welcome.openRepo", "Ouvrir un dépôt"),
+        ("welcome.dragDrop", "ou glissez-déposez un dossier ici"),
+        ("welcome.recentProjects", "Projets récents"),
+        ("welcome.noRecent", "Aucun projet récent"),
+        ("welcome.removeHint", "Clic droit pour retirer"),
+
+        // Search
+        ("search.placeholder", "Rechercher des commits..."),
+        ("search.results.one", "{count} résultat"),
+        ("search.results.other", "{count} résultats"),
+        ("search.none", "Aucun résultat"),
+
+        // Branches & Tags
+        ("branch.list", "Branches"),
+        ("branch.local", "Locales"),
+        ("branch.remote", "Distantes"),
+        ("tag.list", "Étiquettes"),
+        ("tag.none", "Aucune étiquette"),
+
+        // Commits
+        ("commit.history", "Historique des commits"),
+        ("commit.count.one", "{count} commit"),
+        ("commit.count.other", "{count} commits"),
+        ("commit.countFiltered.one", "{count} commit (filtré)"),
+        ("commit.countFiltered.other", "{count} commits (filtrés)"),
+        ("commit.none", "Aucun commit"),
+        ("commit.messagePlaceholder", "Saisissez le message de commit..."),
+        ("commit.amend", "Modifier"),
+        ("commit.button", "Valider"),
+        ("commit.buttonFiles.one", "Valider ({count} fichier)"),
+        ("commit.buttonFiles.other", "Valider ({count} fichiers)"),
+        ("commit.buttonSelectedFiles.one", "Valider la sélection ({count} fichier)"),
+        ("commit.buttonSelectedFiles.other", "Valider la sélection ({count} fichiers)"),
+        ("commit.loadMore", "Charger plus"),
+
+        // File List
+        ("fileList.title", "Modifications"),
+        ("fileList.staged", "Indexées"),
+        ("fileList.unstaged", "Non indexées"),
+        ("fileList.noChanges", "Aucune modification"),
+        ("fileList.discard", "Abandonner"),
+        ("fileList.discardAll", "Tout abandonner"),
+        ("fileList.stageAll", "Tout indexer"),
+        ("fileList.unstageAll", "Tout désindexer"),
+        ("fileList.stagedChanges", "{count} indexées"),
+        ("fileList.unstagedChanges", "{count} non indexées"),
+
+        // Diff Viewer
+        ("diff.title", "Diff"),
+        ("diff.close", "Fermer"),
+        ("diff.noDiff", "Aucun diff disponible"),
+        ("diff.additions.one", "{count} ajout"),
+        ("diff.additions.other", "{count} ajouts"),
+        ("diff.deletions.one", "{count} suppression"),
+        ("diff.deletions.other", "{count} suppressions"),
+
+        // Context Menu
+        ("context.checkout", "Extraire"),
+        ("context.createBranch", "Créer une branche"),
+        ("context.createTag", "Créer une étiquette"),
+        ("context.revert", "Annuler le commit"),
+        ("context.cherryPick", "Cherry-pick"),
+        ("context.merge", "Fusionner"),
+        ("context.reset", "Réinitialiser"),
+        ("context.resetSoft", "Soft (garder les modifications indexées)"),
+        ("context.resetMixed", "Mixed (garder les modifications non indexées)"),
+        ("context.resetHard", "Hard (abandonner toutes les modifications)"),
+        ("context.deleteBranch", "Supprimer la branche"),
+        ("context.branchNamePlaceholder", "Nom de la branche"),
+        ("context.tagNamePlaceholder", "Nom de l'étiquette"),
+
+        // Left Panel
+        ("left.publish", "Publier"),
+        ("left.push", "Push"),
+        ("left.pull", "Pull"),
+        ("left.fetch", "Fetch"),
+        ("left.stash", "Remisage"),
+        ("left.stashSave", "Remiser"),
+        ("left.stashPop", "Dépiler"),
+        ("left.stashApply", "Appliquer"),
+        ("left.stashDrop", "Supprimer"),
+        ("left.stashEmpty", "Aucun remisage"),
+
+        // Settings
+        ("settings.title", "Réglages"),
+        ("settings.tabGeneral", "Général"),
+        ("settings.tabRepository", "Dépôt"),
+        ("settings.repoHint", "Ces réglages ne s'appliquent qu'à ce dépôt"),
+        ("settings.repoIdentity", "Identité des commits"),
+        ("settings.repoName", "Nom"),
+        ("settings.repoEmail", "E-mail"),
+        ("settings.repoUseGitConfig", "Depuis git config"),
+        ("settings.repoRemote", "Dépôt distant par défaut"),
+        ("settings.repoUseGlobal", "Global"),
+        ("settings.repoReset", "Revenir aux réglages globaux"),
+        ("settings.general", "Général"),
+        ("settings.language", "Langue"),
+        ("settings.theme", "Thème"),
+        ("settings.graphPalette", "Couleurs du graphe"),
+        ("settings.graphPaletteTheme", "Thème"),
+        ("settings.fetchAvatars", "Télécharger les avatars"),
+        ("settings.themeDark", "Sombre"),
+        ("settings.themeLight", "Clair"),
+        ("settings.themeSystem", "Système"),
+        ("settings.fetchOnOpen", "Fetch à l'ouverture"),
+        ("settings.restoreSession", "Restaurer la session"),
+        ("settings.fetchPrune", "Nettoyer lors du fetch"),
+        ("settings.autoFetch", "Fetch automatique"),
+        ("settings.autostash", "Remisage auto. au pull/checkout"),
+        ("settings.previewOperations", "Prévisualiser avant de réinitialiser"),
+        ("settings.trashDiscards", "Sauvegarder les modifications abandonnées"),
+        ("settings.controlSocket", "Socket d'intégration aux éditeurs"),
+        ("settings.toastPosition", "Position des notifications"),
+        ("settings.stickyErrors", "Garder les erreurs jusqu'à fermeture"),
+        ("settings.maxDiffSize", "Taille maximale des fichiers à comparer"),
+        ("settings.on", "Activé"),
+        ("settings.off", "Désactivé"),
+        ("settings.gitAuth", "Authentification Git"),
+        ("settings.gitAuthMethod", "Méthode d'authentification"),
+        ("settings.gitUsername", "Nom d'utilisateur"),
+        ("settings.gitUsernamePlaceholder", "Saisissez le nom d'utilisateur"),
+        ("settings.gitToken", "Jeton"),
+        ("settings.gitTokenPlaceholder", "Saisissez le jeton"),
+        ("settings.gitTokenStorage", "Stocké dans"),
+        ("settings.gitTokenPlaintext", "Fichier de réglages (en clair)"),
+        ("settings.gitTokenForget", "Oublier"),
+        ("settings.gitTokenPaste", "Coller"),
+        ("settings.gitTokenSaved", "Enregistré ; saisissez un nouveau jeton pour le remplacer"),
+        ("settings.gitTestConnection", "Tester la connexion"),
+        ("settings.gitTestRunning", "Connexion à {remote}..."),
+        ("settings.gitTestSuccess", "Connecté à {remote}"),
+        ("settings.gitIdentity", "Identité Git"),
+        ("settings.gitIdentityGlobal", "Global"),
+        ("settings.gitIdentityRepository", "Ce dépôt"),
+        ("settings.gitIdentityHint", "Les commits nécessitent un nom et un e-mail"),
+        ("settings.hostingToken", "Jeton d'API pour les pull requests"),
+        ("settings.hostingTokenNone", "Aucun jeton ; les badges de pull request sont désactivés"),
+        (
+            "settings.hostingTokenHint",
+            "Stocké dans le trousseau du système et envoyé uniquement à GitHub ou GitLab",
+        ),
+        ("settings.editorCommand", "Éditeur externe"),
+        ("settings.editorCommandClear", "Utiliser l'application par défaut"),
+        (
+            "settings.editorCommandHint",
+            "{file} et {line} sont remplacés ; un nom d'éditeur seul, comme code ou zed, ouvre la ligne",
+        ),
+        ("settings.merge", "Stratégie de fusion"),
+        ("settings.mergeLabel", "Mode de fusion par défaut"),
+        ("settings.mergeAuto", "Automatique"),
+        ("settings.mergeFfOnly", "Avance rapide uniquement"),
+        ("settings.mergeNoFf", "Sans avance rapide"),
+        ("settings.mergeSquash", "Squash"),
+        ("settings.commitMessages", "Messages de commit"),
+        ("settings.commitLint", "Vérification des messages"),
+        ("settings.commitLintOff", "Désactivée"),
+        ("settings.commitLintWarn", "Avertir"),
+        ("settings.commitLintBlock", "Bloquer"),
+        ("settings.runHooks", "Exécuter les hooks pre-commit et commit-msg"),
+        ("settings.signing", "Signature des commits"),
+        ("settings.signingPolicy", "Commits entrants non signés"),
+        ("settings.signingOff", "Désactivé"),
+        ("settings.signingWarn", "Avertir"),
+        ("settings.signingBlock", "Bloquer"),
+        ("settings.protectedBranches", "Branches protégées"),
+        ("settings.allowedSigners", "Signataires autorisés"),
+        ("settings.allowedSignersAny", "Toute signature valide"),
+        ("settings.signingOffHint", "Désactivé : les signatures ne sont pas vérifiées"),
+        ("settings.signingWarnHint", "Avertir : notifier après un fetch si des commits entrants ne sont pas signés ou pas fiables"),
+        ("settings.signingBlockHint", "Bloquer : refuser de tirer ou fusionner des commits non signés ou non fiables dans les branches protégées"),
+        ("settings.signingListsHint", "Séparez les entrées par des virgules. Les signataires sont des empreintes complètes ou des identifiants de clé longs"),
+        ("settings.about", "À propos"),
+        ("settings.version", "Version"),
+        ("settings.github", "GitHub"),
+        ("settings.keyboard", "Raccourcis clavier"),
+
+        // Auth
+        ("auth.https", "HTTPS"),
+        ("auth.ssh", "SSH"),
+
+        // Conflict Resolution
+        ("conflict.title", "Conflits de fusion"),
+        ("conflict.subtitle", "Résolvez les conflits pour terminer la fusion"),
+        ("conflict.count.one", "{count} conflit"),
+        ("conflict.count.other", "{count} conflits"),
+        ("conflict.bulkResolve", "Tout résoudre"),
+        ("conflict.perFile", "Par fichier"),
+        ("conflict.useOurs", "Garder la nôtre"),
+        ("conflict.useTheirs", "Garder la leur"),
+        ("conflict.completeMerge", "Terminer la fusion"),
+        ("conflict.abortMerge", "Abandonner la fusion"),
+        ("conflict.noConflicts", "Aucun conflit"),
+
+        // Time
+        ("time.today", "Aujourd'hui"),
+        ("time.yesterday", "Hier"),
+        ("time.justNow", "à l'instant"),
+        ("time.minutesAgo.one", "il y a {count} minute"),
+        ("time.minutesAgo.other", "il y a {count} minutes"),
+        ("time.hoursAgo.one", "il y a {count} heure"),
+        ("time.hoursAgo.other", "il y a {count} heures"),
+        ("time.daysAgo.one", "il y a {count} jour"),
+        ("time.daysAgo.other", "il y a {count} jours"),
+        ("time.weeksAgo.one", "il y a {count} semaine"),
+        ("time.weeksAgo.other", "il y a {count} semaines"),
+        ("time.monthsAgo.one", "il y a {count} mois"),
+        ("time.monthsAgo.other", "il y a {count} mois"),
+        ("time.yearsAgo.one", "il y a {count} an"),
+        ("time.yearsAgo.other", "il y a {count} ans"),
+
+        // Header
+        ("header.branch", "Branche"),
+        ("header.detachedHead", "HEAD détachée"),
+        ("header.ahead", "{count} en avance"),
+        ("header.behind", "{count} en retard"),
+        ("header.settings", "Réglages"),
+
+        // Status Bar
+        ("status.fetched", "Dernier fetch {time}"),
+        ("status.fetching", "Fetch en cours..."),
+        ("status.neverFetched", "Jamais de fetch"),
+    ])
+});
+
+static ES_TRANSLATIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    HashMap::from([
+        // App
+        ("app.name", "Awabancha"),
+        ("app.tagline", "Un cliente gráfico de Git rápido"),
+        ("app.about", "Acerca de"),
+        ("app.version", "Versión"),
+
+        // Common
+        ("common.cancel", "Cancelar"),
+        ("common.close", "Cerrar"),
+        ("common.save", "Guardar"),
+        ("common.refresh", "Actualizar"),
+        ("common.loading", "Cargando..."),
+        ("common.search", "Buscar"),
+        ("common.clear", "Borrar"),
+        ("common.apply", "Aplicar"),
+        ("common.delete", "Eliminar"),
+        ("common.open", "Abrir"),
+        ("common.create", "Crear"),
+
+        // Errors
+        ("error.openRepoFailed", "No se pudo abrir el repositorio"),
+        ("error.noRepository", "No hay ningún repositorio abierto"),
+        ("error.stageFailed", "No se pudo preparar el archivo"),
+        ("error.unstageFailed", "No se pudo quitar el archivo del área de preparación"),
+        ("error.pushFailed", "Error al hacer push"),
+        ("error.pullFailed", "Error al hacer pull"),
+        ("error.fetchFailed", "Error al hacer fetch"),
+        ("error.commitFailed", "No se pudo crear el commit"),
+        ("error.stashFailed", "No se pudo guardar el stash"),
+        ("error.stashApplyFailed", "No se pudo aplicar el stash"),
+        ("error.stashDropFailed", "No se pudo descartar el stash"),
+        ("error.mergeFailed", "Error al fusionar"),
+        ("error.revertFailed", "Error al revertir"),
+        ("error.branchDeleteFailed", "No se pudo eliminar la rama"),
+        ("error.branchCreateFailed", "No se pudo crear la rama"),
+        ("error.tagCreateFailed", "No se pudo crear la etiqueta"),
+        ("error.tagDeleteFailed", "No se pudo eliminar la etiqueta"),
+        ("error.checkoutFailed", "Error al hacer checkout"),
+        ("error.resetFailed", "Error al restablecer"),
+        ("error.cherryPickFailed", "Error al hacer cherry-pick"),
+
+        // Toast
+        ("toast.refresh", "Repositorio actualizado"),
+        ("toast.push", "Push completado"),
+        ("toast.pull", "Pull completado"),
+        ("toast.fetch", "Fetch completado"),
+        ("toast.stageAll", "Todos los archivos preparados"),
+        ("toast.unstageAll", "Todos los archivos quitados del área de preparación"),
+        ("toast.stashSaved", "Stash guardado"),
+        ("toast.stashApplied", "Stash aplicado"),
+        ("toast.stashDropped", "Stash descartado"),
+        ("toast.repoOpened", "{name} abierto"),
+        ("toast.commit", "Commit creado"),
+        ("toast.amendCommit", "Commit modificado"),
+        ("toast.revertCreated", "Commit de reversión creado"),
+        ("toast.cherryPick", "Cherry-pick completado"),
+        ("toast.branchCreated", "Rama {name} creada"),
+        ("toast.branchDeleted", "Rama {name} eliminada"),
+        ("toast.tagCreated", "Etiqueta {name} creada"),
+        ("toast.tagDeleted", "Etiqueta {name} eliminada"),
+        ("toast.reset", "Restablecido a {sha} ({mode})"),
+        ("toast.merge", "{name} fusionada"),
+        ("toast.checkoutBranch", "Cambiado a la rama {name}"),
+        ("toast.checkoutCommit", "Cambiado al commit {sha}"),
+        ("toast.checkoutTag", "Cambiado a la etiqueta {name}"),
+        ("toast.mergeCompleted", "Fusión completada"),
+        ("toast.mergeAborted", "Fusión cancelada"),
+        ("toast.discardFile", "Cambios en {path} descartados"),
+        ("toast.discardAll", "Todos los cambios descartados"),
+
+        // Welcome
+        ("welcome.openRepo", "Abrir repositorio"),
+        ("welcome.dragDrop", "o arrastra y suelta una carpeta aquí"),
+        ("welcome.recentProjects", "Proyectos recientes"),
+        ("welcome.noRecent", "No hay proyectos recientes"),
+        ("welcome.removeHint", "Clic derecho para quitar"),
+
+        // Search
+        ("search.placeholder", "Buscar commits..."),
+        ("search.results.one", "{count} resultado"),
+        ("search.results.other", "{count} resultados"),
+        ("search.none", "No se encontraron resultados"),
+
+        // Branches & Tags
+        ("branch.list", "Ramas"),
+        ("branch.local", "Locales"),
+        ("branch.remote", "Remotas"),
+        ("tag.list", "Etiquetas"),
+        ("tag.none", "Sin etiquetas"),
+
+        // Commits
+        ("commit.history", "Historial de commits"),
+        ("commit.count.one", "{count} commit"),
+        ("commit.count.other", "{count} commits"),
+        ("commit.countFiltered.one", "{count} commit (filtrado)"),
+        ("commit.countFiltered.other", "{count} commits (filtrados)"),
+        ("commit.none", "Sin commits"),
+        ("commit.messagePlaceholder", "Escribe el mensaje del commit..."),
+        ("commit.amend", "Modificar"),
+        ("commit.button", "Confirmar"),
+        ("commit.buttonFiles.one", "Confirmar ({count} archivo)"),
+        ("commit.buttonFiles.other", "Confirmar ({count} archivos)"),
+        ("commit.buttonSelectedFiles.one", "Confirmar selección ({count} archivo)"),
+        ("commit.buttonSelectedFiles.other", "Confirmar selección ({count} archivos)"),
+        ("commit.loadMore", "Cargar más"),
+
+        // File List
+        ("fileList.title", "Cambios"),
+        ("fileList.staged", "Preparados"),
+        ("fileList.unstaged", "Sin preparar"),
+        ("fileList.noChanges", "Sin cambios"),
+        ("fileList.discard", "Descartar"),
+        ("fileList.discardAll", "Descartar todo"),
+        ("fileList.stageAll", "Preparar todo"),
+        ("fileList.unstageAll", "Quitar todo"),
+        ("fileList.stagedChanges", "{count} preparados"),
+        ("fileList.unstagedChanges", "{count} sin preparar"),
+
+        // Diff Viewer
+        ("diff.title", "Diferencias"),
+        ("diff.close", "Cerrar"),
+        ("diff.noDiff", "No hay diferencias disponibles"),
+        ("diff.additions.one", "{count} adición"),
+        ("diff.additions.other", "{count} adiciones"),
+        ("diff.deletions.one", "{count} eliminación"),
+        ("diff.deletions.other", "{count} eliminaciones"),
+
+        // Context Menu
+        ("context.checkout", "Checkout"),
+        ("context.createBranch", "Crear rama"),
+        ("context.createTag", "Crear etiqueta"),
+        ("context.revert", "Revertir"),
+        ("context.cherryPick", "Cherry-pick"),
+        ("context.merge", "Fusionar"),
+        ("context.reset", "Restablecer"),
+        ("context.resetSoft", "Soft (mantener los cambios preparados)"),
+        ("context.resetMixed", "Mixed (mantener los cambios sin preparar)"),
+        ("context.resetHard", "Hard (descartar todos los cambios)"),
+        ("context.deleteBranch", "Eliminar rama"),
+        ("context.branchNamePlaceholder", "Nombre de la rama"),
+        ("context.tagNamePlaceholder", "Nombre de la etiqueta"),
+
+        // Left Panel
+        ("left.publish", "Publicar"),
+        ("left.push", "Push"),
+        ("left.pull", "Pull"),
+        ("left.fetch", "Fetch"),
+        ("left.stash", "Stash"),
+        ("left.stashSave", "Guardar stash"),
+        ("left.stashPop", "Extraer"),
+        ("left.stashApply", "Aplicar"),
+        ("left.stashDrop", "Descartar"),
+        ("left.stashEmpty", "Sin stashes"),
+
+        // Settings
+        ("settings.title", "Ajustes"),
+        ("settings.tabGeneral", "General"),
+        ("settings.tabRepository", "Repositorio"),
+        ("settings.repoHint", "Estos ajustes solo se aplican a este repositorio"),
+        ("settings.repoIdentity", "Identidad de los commits"),
+        ("settings.repoName", "Nombre"),
+        ("settings.repoEmail", "Correo"),
+        ("settings.repoUseGitConfig", "Desde git config"),
+        ("settings.repoRemote", "Remoto predeterminado"),
+        ("settings.repoUseGlobal", "Global"),
+        ("settings.repoReset", "Restablecer a global"),
+        ("settings.general", "General"),
+        ("settings.language", "Idioma"),
+        ("settings.theme", "Tema"),
+        ("settings.graphPalette", "Colores del grafo"),
+        ("settings.graphPaletteTheme", "Tema"),
+        ("settings.fetchAvatars", "Descargar avatares"),
+        ("settings.themeDark", "Oscuro"),
+        ("settings.themeLight", "Claro"),
+        ("settings.themeSystem", "Sistema"),
+        ("settings.fetchOnOpen", "Hacer fetch al abrir"),
+        ("settings.restoreSession", "Restaurar sesión"),
+        ("settings.fetchPrune", "Limpiar al hacer fetch"),
+        ("settings.autoFetch", "Fetch automático"),
+        ("settings.autostash", "Stash automático en pull/checkout"),
+        ("settings.previewOperations", "Previsualizar antes de restablecer"),
+        ("settings.trashDiscards", "Respaldar los cambios descartados"),
+        ("settings.controlSocket", "Socket de integración con editores"),
+        ("settings.toastPosition", "Posición de las notificaciones"),
+        ("settings.stickyErrors", "Mantener los errores hasta cerrarlos"),
+        ("settings.maxDiffSize", "Archivo más grande a comparar"),
+        ("settings.on", "Activado"),
+        ("settings.off", "Desactivado"),
+        ("settings.gitAuth", "Autenticación de Git"),
+        ("settings.gitAuthMethod", "Método de autenticación"),
+        ("settings.gitUsername", "Usuario"),
+        ("settings.gitUsernamePlaceholder", "Escribe el usuario"),
+        ("settings.gitToken", "Token"),
+        ("settings.gitTokenPlaceholder", "Escribe el token"),
+        ("settings.gitTokenStorage", "Guardado en"),
+        ("settings.gitTokenPlaintext", "Archivo de ajustes (texto plano)"),
+        ("settings.gitTokenForget", "Olvidar"),
+        ("settings.gitTokenPaste", "Pegar"),
+        ("settings.gitTokenSaved", "Guardado; escribe un token nuevo para reemplazarlo"),
+        ("settings.gitTestConnection", "Probar conexión"),
+        ("settings.gitTestRunning", "Conectando con {remote}..."),
+        ("settings.gitTestSuccess", "Conectado con {remote}"),
+        ("settings.gitIdentity", "Identidad de Git"),
+        ("settings.gitIdentityGlobal", "Global"),
+        ("settings.gitIdentityRepository", "Este repositorio"),
+        ("settings.gitIdentityHint", "Los commits necesitan un nombre y un correo"),
+        ("settings.hostingToken", "Token de API para pull requests"),
+        (
+            "settings.hostingTokenNone",
+            "Sin token; las insignias de pull request están desactivadas",
+        ),
+        (
+            "settings.hostingTokenHint",
+            "Se guarda en el almacén de credenciales del sistema y solo se envía a GitHub o GitLab",
+        ),
+        ("settings.editorCommand", "Editor externo"),
+        ("settings.editorCommandClear", "Usar el predeterminado del sistema"),
+        (
+            "settings.editorCommandHint",
+            "{file} y {line} se reemplazan; solo el nombre de un editor, como code o zed, abre la línea",
+        ),
+        ("settings.merge", "Estrategia de fusión"),
+        ("settings.mergeLabel", "Modo de fusión predeterminado"),
+        ("settings.mergeAuto", "Automático"),
+        ("settings.mergeFfOnly", "Solo avance rápido"),
+        ("settings.mergeNoFf", "Sin avance rápido"),
+        ("settings.mergeSquash", "Squash"),
+        ("settings.commitMessages", "Mensajes de commit"),
+        ("settings.commitLint", "Revisión de mensajes"),
+        ("settings.commitLintOff", "Desactivada"),
+        ("settings.commitLintWarn", "Avisar"),
+        ("settings.commitLintBlock", "Bloquear"),
+        ("settings.runHooks", "Ejecutar los hooks pre-commit y commit-msg"),
+        ("settings.signing", "Firma de commits"),
+        ("settings.signingPolicy", "Commits entrantes sin firmar"),
+        ("settings.signingOff", "Desactivado"),
+        ("settings.signingWarn", "Avisar"),
+        ("settings.signingBlock", "Bloquear"),
+        ("settings.protectedBranches", "Ramas protegidas"),
+        ("settings.allowedSigners", "Firmantes permitidos"),
+        ("settings.allowedSignersAny", "Cualquier firma válida"),
+        ("settings.signingOffHint", "Desactivado: no se comprueban las firmas"),
+        ("settings.signingWarnHint", "Avisar: notificar tras el fetch si hay commits entrantes sin firmar o no fiables"),
+        ("settings.signingBlockHint", "Bloquear: no traer ni fusionar commits sin firmar o no fiables en ramas protegidas"),
+        ("settings.signingListsHint", "Separa las entradas con comas. Los firmantes son huellas completas o ID de clave largos"),
+        ("settings.about", "Acerca de"),
+        ("settings.version", "Versión"),
+        ("settings.github", "GitHub"),
+        ("settings.keyboard", "Atajos de teclado"),
+
+        // Auth
+        ("auth.https", "HTTPS"),
+        ("auth.ssh", "SSH"),
+
+        // Conflict Resolution
+        ("conflict.title", "Conflictos de fusión"),
+        ("conflict.subtitle", "Resuelve los conflictos para completar la fusión"),
+        ("conflict.count.one", "{count} conflicto"),
+        ("conflict.count.other", "{count} conflictos"),
+        ("conflict.bulkResolve", "Resolver todos"),
+        ("conflict.perFile", "Por archivo"),
+        ("conflict.useOurs", "Usar la nuestra"),
+        ("conflict.useTheirs", "Usar la suya"),
+        ("conflict.completeMerge", "Completar fusión"),
+        ("conflict.abortMerge", "Cancelar fusión"),
+        ("conflict.noConflicts", "Sin conflictos"),
+
+        // Time
+        ("time.today", "Hoy"),
+        ("time.yesterday", "Ayer"),
+        ("time.justNow", "justo ahora"),
+        ("time.minutesAgo.one", "hace {count} minuto"),
+        ("time.minutesAgo.other", "hace {count} minutos"),
+        ("time.hoursAgo.one", "hace {count} hora"),
+        ("time.hoursAgo.other", "hace {count} horas"),
+        ("time.daysAgo.one", "hace {count} día"),
+        ("time.daysAgo.other", "hace {count} días"),
+        ("time.weeksAgo.one", "hace {count} semana"),
+        ("time.weeksAgo.other", "hace {count} semanas"),
+        ("time.monthsAgo.one", "hace {count} mes"),
+        ("time.monthsAgo.other", "hace {count} meses"),
+        ("time.yearsAgo.one", "hace {count} año"),
+        ("time.yearsAgo.other", "hace {count} años"),
+
+        // Header
+        ("header.branch", "Rama"),
+        ("header.detachedHead", "HEAD separado"),
+        ("header.ahead", "{count} por delante"),
+        ("header.behind", "{count} por detrás"),
+        ("header.settings", "Ajustes"),
+
+        // Status Bar
+        ("status.fetched", "Último fetch {time}"),
+        ("status.fetching", "Haciendo fetch..."),
+        ("status.neverFetched", "Nunca se hizo fetch"),
     ])
 });
@@ -3,19 +3,48 @@ mod app;
 mod components;
 mod git;
 mod i18n;
+mod menus;
 mod state;
+mod theme;
 mod views;
 
 use app::Awabancha;
 use gpui::*;
+use std::path::PathBuf;
+
+/// Repository given on the command line, as in `awabancha /path/to/repo`
+fn path_argument() -> Option<PathBuf> {
+    // Skip flags, e.g. the `-psn_…` process serial number older macOS
+    // versions pass to apps launched from Finder
+    let arg = std::env::args_os()
+        .skip(1)
+        .find(|arg| !arg.to_string_lossy().starts_with('-'))?;
+    let path = PathBuf::from(arg);
+    // Resolve against the terminal's directory before anything changes it
+    Some(path.canonicalize().unwrap_or(path))
+}
 
 fn main() {
-    env_logger::init();
+    let initial_path = path_argument();
+    // Before forwarding, so errors from the running instance are printed
+    let log_receiver = state::init_logging();
+    // A window is already open: hand it the repository instead
+    if let Some(path) = &initial_path {
+        if state::forward_to_running_instance(path) {
+            return;
+        }
+    }
 
     Application::new().run(|cx: &mut App| {
         // Load assets
         cx.set_global(Awabancha::load_assets());
 
+        // Install the default theme and locale; the app switches them once
+        // settings load
+        cx.set_global(theme::Theme::dark());
+        cx.set_global(theme::ThemeRegistry::load());
+        cx.set_global(i18n::Locale::default());
+
         // Register actions
         actions::register_actions(cx);
 
@@ -33,7 +62,7 @@ fn main() {
                 }),
                 ..Default::default()
             },
-            |window, cx| cx.new(|cx| Awabancha::new(window, cx)),
+            |window, cx| cx.new(|cx| Awabancha::new(log_receiver, initial_path, window, cx)),
         )
         .expect("Failed to open window");
 
@@ -0,0 +1,76 @@
+use crate::actions::*;
+use crate::state::RecentProject;
+use gpui::*;
+
+/// Install the application menu bar and the dock menu. Called again whenever
+/// the recent projects change, since both list them.
+pub fn set_menus(recent_projects: &[RecentProject], cx: &mut App) {
+    cx.set_menus(app_menus(recent_projects));
+    cx.set_dock_menu(recent_items(recent_projects));
+}
+
+fn app_menus(recent_projects: &[RecentProject]) -> Vec<Menu> {
+    let mut open_recent = recent_items(recent_projects);
+    if !open_recent.is_empty() {
+        open_recent.push(MenuItem::separator());
+        open_recent.push(MenuItem::action("Clear Menu", ClearRecentProjects));
+    }
+
+    vec![
+        Menu {
+            name: "Awabancha".into(),
+            items: vec![
+                MenuItem::action("Settings...", OpenSettings),
+                MenuItem::separator(),
+                MenuItem::action("Quit Awabancha", Quit),
+            ],
+        },
+        Menu {
+            name: "File".into(),
+            items: vec![
+                MenuItem::action("Open Repository...", OpenRepository),
+                MenuItem::submenu(Menu {
+                    name: "Open Recent".into(),
+                    items: open_recent,
+                }),
+                MenuItem::action("Clone Repository...", CloneRepository),
+                MenuItem::separator(),
+                MenuItem::action("Close Repository", CloseRepository),
+            ],
+        },
+        Menu {
+            name: "Edit".into(),
+            items: vec![
+                MenuItem::action("Cut", Cut),
+                MenuItem::action("Copy", Copy),
+                MenuItem::action("Paste", Paste),
+                MenuItem::action("Select All", SelectAll),
+                MenuItem::separator(),
+                MenuItem::action("Find in Files...", ShowSearch),
+            ],
+        },
+        Menu {
+            name: "View".into(),
+            items: vec![
+                MenuItem::action("Project Tree", ToggleProjectTree),
+                MenuItem::action("Logging Console", ToggleLogConsole),
+                MenuItem::action("Performance Overlay", TogglePerfOverlay),
+            ],
+        },
+    ]
+}
+
+/// One item per recent project, pinned projects first
+fn recent_items(recent_projects: &[RecentProject]) -> Vec<MenuItem> {
+    recent_projects
+        .iter()
+        .map(|project| {
+            MenuItem::action(
+                project.name.clone(),
+                OpenRecentProject {
+                    path: project.path.clone(),
+                },
+            )
+        })
+        .collect()
+}
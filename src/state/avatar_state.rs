@@ -0,0 +1,173 @@
+#![allow(dead_code)]
+
+use anyhow::{Context as _, Result};
+use gpui::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Pixel size avatars are requested at, enough for 2x displays
+const AVATAR_SIZE: u32 = 64;
+
+/// Give up on an avatar download after this long
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest avatar image accepted
+const MAX_AVATAR_BYTES: u64 = 1024 * 1024;
+
+/// Where an author's avatar stands
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Avatar {
+    Loading,
+    /// Image file in the on-disk cache
+    Ready(PathBuf),
+    /// No avatar for the email, or fetching is off and none is cached
+    Missing,
+}
+
+/// Cache key of an email: the SHA-256 of the trimmed, lowercased address,
+/// as Gravatar expects
+pub fn avatar_hash(email: &str) -> String {
+    let digest = Sha256::digest(email.trim().to_lowercase().as_bytes());
+    format!("{:x}", digest)
+}
+
+/// Where an email's avatar is downloaded from. GitHub noreply addresses use
+/// the GitHub account's avatar, other addresses Gravatar.
+pub fn avatar_url(email: &str) -> String {
+    let email = email.trim().to_lowercase();
+    if let Some(user) = email.strip_suffix("@users.noreply.github.com") {
+        // Either "12345+login" or, for older accounts, just "login"
+        return match user.split_once('+') {
+            Some((id, _)) if id.chars().all(|c| c.is_ascii_digit()) => format!(
+                "https://avatars.githubusercontent.com/u/{}?s={}",
+                id, AVATAR_SIZE
+            ),
+            _ => format!("https://github.com/{}.png?size={}", user, AVATAR_SIZE),
+        };
+    }
+    // d=404 answers unknown emails with a 404 instead of a generated image
+    format!(
+        "https://gravatar.com/avatar/{}?s={}&d=404",
+        avatar_hash(&email),
+        AVATAR_SIZE
+    )
+}
+
+/// Author avatars, loaded in the background and kept in memory and in the
+/// user's cache directory
+pub struct AvatarState {
+    avatars: HashMap<String, Avatar>,
+    cache_dir: Option<PathBuf>,
+    /// Download avatars that aren't cached yet
+    fetch: bool,
+}
+
+impl AvatarState {
+    pub fn new(fetch: bool, _cx: &mut Context<Self>) -> Self {
+        Self {
+            avatars: HashMap::new(),
+            cache_dir: dirs::cache_dir().map(|p| p.join("awabancha").join("avatars")),
+            fetch,
+        }
+    }
+
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
+    pub fn set_fetch(&mut self, fetch: bool, cx: &mut Context<Self>) {
+        if self.fetch == fetch {
+            return;
+        }
+        self.fetch = fetch;
+        // Emails found missing while fetching was off get another chance
+        if fetch {
+            self.avatars.retain(|_, avatar| *avatar != Avatar::Missing);
+            cx.notify();
+        }
+    }
+
+    /// The avatar image of an email, starting to load it if it isn't known
+    /// yet. Observers are notified once it has loaded.
+    pub fn avatar(&mut self, email: &str, cx: &mut Context<Self>) -> Option<PathBuf> {
+        if email.is_empty() {
+            return None;
+        }
+        let key = avatar_hash(email);
+        match self.avatars.get(&key) {
+            Some(Avatar::Ready(path)) => return Some(path.clone()),
+            Some(_) => return None,
+            None => {}
+        }
+        let Some(cache_dir) = self.cache_dir.clone() else {
+            self.avatars.insert(key, Avatar::Missing);
+            return None;
+        };
+
+        self.avatars.insert(key.clone(), Avatar::Loading);
+        let url = avatar_url(email);
+        let fetch = self.fetch;
+        cx.spawn(async move |this, cx| {
+            let avatar = cx
+                .background_executor()
+                .spawn(async move {
+                    load_avatar(&cache_dir, &key, &url, fetch).map(|avatar| (key, avatar))
+                })
+                .await;
+
+            this.update(cx, |state, cx| match avatar {
+                Ok((key, avatar)) => {
+                    state.avatars.insert(key, avatar);
+                    cx.notify();
+                }
+                Err(e) => log::debug!("Failed to load avatar: {:#}", e),
+            })
+            .ok();
+        })
+        .detach();
+        None
+    }
+}
+
+/// Read an avatar from the cache directory, downloading it first if it isn't
+/// there and `fetch` is on. An empty file records an email without avatar.
+fn load_avatar(cache_dir: &Path, key: &str, url: &str, fetch: bool) -> Result<Avatar> {
+    let path = cache_dir.join(key);
+    if let Ok(metadata) = fs::metadata(&path) {
+        return Ok(if metadata.len() == 0 {
+            Avatar::Missing
+        } else {
+            Avatar::Ready(path)
+        });
+    }
+    if !fetch {
+        return Ok(Avatar::Missing);
+    }
+
+    let bytes = match ureq::get(url).timeout(DOWNLOAD_TIMEOUT).call() {
+        Ok(response) => {
+            let mut bytes = Vec::new();
+            response
+                .into_reader()
+                .take(MAX_AVATAR_BYTES)
+                .read_to_end(&mut bytes)
+                .context("reading avatar")?;
+            bytes
+        }
+        Err(ureq::Error::Status(404, _)) => Vec::new(),
+        Err(e) => return Err(e).with_context(|| format!("downloading {}", url)),
+    };
+
+    fs::create_dir_all(cache_dir).context("creating avatar cache")?;
+    fs::write(&path, &bytes).context("writing avatar")?;
+    Ok(if bytes.is_empty() {
+        Avatar::Missing
+    } else {
+        Avatar::Ready(path)
+    })
+}
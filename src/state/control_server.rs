@@ -0,0 +1,330 @@
+#![allow(dead_code)]
+
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::channel::oneshot;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// The app could not carry out a valid request
+const APP_ERROR: i64 = -32000;
+
+/// Request from an external tool, e.g. an editor plugin
+#[derive(Clone, Debug)]
+pub enum ControlCommand {
+    /// Open the repository containing `path`
+    OpenRepository { path: PathBuf },
+    /// Show the diff of a file. Relative paths are resolved against
+    /// `repository`, or the open repository when omitted.
+    ShowDiff {
+        path: PathBuf,
+        repository: Option<PathBuf>,
+    },
+    /// Scroll the graph to a commit (SHA, short SHA or ref name)
+    ShowCommit {
+        rev: String,
+        repository: Option<PathBuf>,
+    },
+}
+
+#[derive(Deserialize)]
+struct PathParams {
+    path: PathBuf,
+    repository: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct CommitParams {
+    #[serde(alias = "sha")]
+    rev: String,
+    repository: Option<PathBuf>,
+}
+
+impl ControlCommand {
+    fn parse(method: &str, params: Value) -> Result<Self, (i64, String)> {
+        let invalid = |e: serde_json::Error| (INVALID_PARAMS, e.to_string());
+        match method {
+            "openRepository" => {
+                let params: PathParams = serde_json::from_value(params).map_err(invalid)?;
+                Ok(ControlCommand::OpenRepository { path: params.path })
+            }
+            "showDiff" => {
+                let params: PathParams = serde_json::from_value(params).map_err(invalid)?;
+                Ok(ControlCommand::ShowDiff {
+                    path: params.path,
+                    repository: params.repository,
+                })
+            }
+            "showCommit" => {
+                let params: CommitParams = serde_json::from_value(params).map_err(invalid)?;
+                Ok(ControlCommand::ShowCommit {
+                    rev: params.rev,
+                    repository: params.repository,
+                })
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+        }
+    }
+}
+
+/// A command waiting for the app to handle it and reply
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    reply: oneshot::Sender<Result<Value, String>>,
+}
+
+impl ControlRequest {
+    pub fn respond(self, result: Result<Value, String>) {
+        let _ = self.reply.send(result);
+    }
+}
+
+/// Local socket accepting newline-delimited JSON-RPC 2.0 requests, so
+/// editors and scripts can drive the running app
+pub struct ControlServer {
+    socket_path: Option<PathBuf>,
+    shutdown: Option<Arc<AtomicBool>>,
+}
+
+impl ControlServer {
+    pub fn new() -> Self {
+        Self {
+            socket_path: None,
+            shutdown: None,
+        }
+    }
+
+    /// Where the socket is created
+    pub fn socket_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("awabancha").join("control.sock"))
+    }
+
+    /// Where a running app listens for other invocations handing it a
+    /// repository to open
+    pub fn instance_socket_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("awabancha").join("instance.sock"))
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.shutdown.is_some()
+    }
+
+    /// Start listening on the control socket. Returns a stream of requests
+    /// for the app to handle; the stream ends when the server is stopped.
+    pub fn start(&mut self) -> anyhow::Result<UnboundedReceiver<ControlRequest>> {
+        let path = Self::socket_path()
+            .ok_or_else(|| anyhow::anyhow!("No config directory for the control socket"))?;
+        self.start_at(path)
+    }
+
+    /// Start listening on `path`, like `start`
+    #[cfg(unix)]
+    pub fn start_at(
+        &mut self,
+        path: PathBuf,
+    ) -> anyhow::Result<UnboundedReceiver<ControlRequest>> {
+        use std::os::unix::net::UnixListener;
+
+        self.stop();
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // A socket left behind by a crashed instance blocks binding
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)?;
+
+        let (tx, rx) = mpsc::unbounded::<ControlRequest>();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stopped = shutdown.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                match stream {
+                    Ok(stream) => {
+                        let tx = tx.clone();
+                        std::thread::spawn(move || serve_connection(stream, tx));
+                    }
+                    Err(e) => log::warn!("Control socket connection failed: {}", e),
+                }
+            }
+        });
+
+        log::info!("Control socket listening at {}", path.display());
+        self.socket_path = Some(path);
+        self.shutdown = Some(shutdown);
+        Ok(rx)
+    }
+
+    #[cfg(not(unix))]
+    pub fn start_at(
+        &mut self,
+        _path: PathBuf,
+    ) -> anyhow::Result<UnboundedReceiver<ControlRequest>> {
+        anyhow::bail!("The control socket is only supported on Unix platforms")
+    }
+
+    pub fn stop(&mut self) {
+        let Some(shutdown) = self.shutdown.take() else {
+            return;
+        };
+        shutdown.store(true, Ordering::SeqCst);
+
+        if let Some(path) = self.socket_path.take() {
+            // Wake the accept loop so it sees the shutdown flag
+            #[cfg(unix)]
+            let _ = std::os::unix::net::UnixStream::connect(&path);
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
+
+impl Default for ControlServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Ask an already running app to open the repository containing `path`.
+///
+/// Returns false when no app is listening, in which case this process should
+/// start the app itself. A running app that can't open the path still counts
+/// as handled; its error is printed for the terminal that invoked us.
+#[cfg(unix)]
+pub fn forward_to_running_instance(path: &Path) -> bool {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    let Some(socket) = ControlServer::instance_socket_path() else {
+        return false;
+    };
+    // Nothing listening, or a socket left behind by a crashed instance
+    let Ok(mut stream) = UnixStream::connect(&socket) else {
+        return false;
+    };
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "openRepository",
+        "params": { "path": path },
+    });
+    if writeln!(stream, "{}", request).is_err() {
+        return false;
+    }
+
+    let mut line = String::new();
+    if BufReader::new(stream).read_line(&mut line).is_err() {
+        return false;
+    }
+    let Ok(response) = serde_json::from_str::<Value>(&line) else {
+        return false;
+    };
+    if let Some(message) = response.pointer("/error/message").and_then(|m| m.as_str()) {
+        log::error!("{}: {}", path.display(), message);
+    }
+    true
+}
+
+#[cfg(not(unix))]
+pub fn forward_to_running_instance(_path: &Path) -> bool {
+    false
+}
+
+/// Answer requests on one connection until the client disconnects
+#[cfg(unix)]
+fn serve_connection(
+    stream: std::os::unix::net::UnixStream,
+    tx: UnboundedSender<ControlRequest>,
+) {
+    use std::io::{BufRead, BufReader, Write};
+
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(e) => {
+            log::warn!("Control socket connection failed: {}", e);
+            return;
+        }
+    };
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = handle_message(&line, &tx);
+        let Some(response) = response else {
+            // Notifications get no reply
+            continue;
+        };
+        if writeln!(writer, "{}", response).is_err() {
+            break;
+        }
+    }
+}
+
+/// Parse one JSON-RPC message, hand it to the app and wait for the result
+fn handle_message(line: &str, tx: &UnboundedSender<ControlRequest>) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, e.to_string())),
+    };
+    let id = message.get("id").cloned();
+    let method = message.get("method").and_then(|m| m.as_str()).unwrap_or("");
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    log::debug!("Control request: {}", method);
+
+    let result = if method == "ping" {
+        Ok(json!({ "version": env!("CARGO_PKG_VERSION") }))
+    } else {
+        match ControlCommand::parse(method, params) {
+            Ok(command) => {
+                let (reply, response) = oneshot::channel();
+                if tx.unbounded_send(ControlRequest { command, reply }).is_err() {
+                    Err((APP_ERROR, "The app is shutting down".to_string()))
+                } else {
+                    match futures::executor::block_on(response) {
+                        Ok(Ok(value)) => Ok(value),
+                        Ok(Err(message)) => Err((APP_ERROR, message)),
+                        Err(_) => Err((APP_ERROR, "The request was dropped".to_string())),
+                    }
+                }
+            }
+            Err(error) => Err(error),
+        }
+    };
+
+    let id = id?;
+    Some(match result {
+        Ok(value) => json!({ "jsonrpc": "2.0", "id": id, "result": value }),
+        Err((code, message)) => error_response(id, code, message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
@@ -0,0 +1,178 @@
+#![allow(dead_code)]
+
+use crate::git::ConflictStrategy;
+use chrono::{DateTime, Utc};
+use gpui::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How often unsaved drafts are written to disk
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Work in progress that would be lost if the app crashed
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DraftData {
+    /// Repository the drafts belong to
+    pub repository: Option<PathBuf>,
+    pub commit_message: String,
+    /// Per-file strategies picked in the conflict dialog but not yet applied
+    pub conflict_selections: Vec<(String, ConflictStrategy)>,
+    pub saved_at: Option<DateTime<Utc>>,
+}
+
+impl DraftData {
+    pub fn is_empty(&self) -> bool {
+        self.commit_message.trim().is_empty() && self.conflict_selections.is_empty()
+    }
+}
+
+/// Periodically persists drafts so they survive a crash or forced quit.
+///
+/// The drafts file is removed on a clean quit, so a file found at launch
+/// means the previous session ended unexpectedly.
+pub struct DraftState {
+    data: DraftData,
+    /// Drafts left behind by a session that did not quit cleanly
+    recovered: Option<DraftData>,
+    /// Recovered drafts the user chose to restore, waiting for the commit
+    /// form and conflict dialog to pick them up
+    restored: Option<DraftData>,
+    /// Changed since the last write
+    dirty: bool,
+}
+
+impl DraftState {
+    fn storage_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("awabancha").join("drafts.json"))
+    }
+
+    pub fn load(cx: &mut Context<Self>) -> Self {
+        let recovered = Self::storage_path()
+            .and_then(|path| fs::read_to_string(&path).ok())
+            .and_then(|content| serde_json::from_str::<DraftData>(&content).ok())
+            .filter(|data| !data.is_empty());
+
+        cx.spawn(async move |this, cx| loop {
+            cx.background_executor().timer(AUTOSAVE_INTERVAL).await;
+            let result = this.update(cx, |state, _cx| state.save_if_dirty());
+            if result.is_err() {
+                break;
+            }
+        })
+        .detach();
+
+        cx.on_app_quit(|_state, _cx| {
+            Self::remove_file();
+            async {}
+        })
+        .detach();
+
+        Self {
+            data: DraftData::default(),
+            recovered,
+            restored: None,
+            dirty: false,
+        }
+    }
+
+    fn save_if_dirty(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+
+        if self.data.is_empty() {
+            // Keep the previous session's drafts until the user decides
+            if self.recovered.is_none() {
+                Self::remove_file();
+            }
+            return;
+        }
+        if let Some(path) = Self::storage_path() {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            self.data.saved_at = Some(Utc::now());
+            if let Ok(content) = serde_json::to_string_pretty(&self.data) {
+                let _ = fs::write(&path, content);
+            }
+        }
+    }
+
+    fn remove_file() {
+        if let Some(path) = Self::storage_path() {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// Recovered drafts for `repository`, if the last session left any
+    pub fn recovered_for(&self, repository: &Path) -> Option<&DraftData> {
+        self.recovered
+            .as_ref()
+            .filter(|data| data.repository.as_deref() == Some(repository))
+    }
+
+    /// Hand the recovered drafts to the views and keep saving them
+    pub fn restore(&mut self, cx: &mut Context<Self>) {
+        if let Some(recovered) = self.recovered.take() {
+            self.data = recovered.clone();
+            self.restored = Some(recovered);
+            self.dirty = true;
+            cx.notify();
+        }
+    }
+
+    pub fn discard_recovered(&mut self, cx: &mut Context<Self>) {
+        self.recovered = None;
+        self.dirty = true;
+        cx.notify();
+    }
+
+    /// Start collecting drafts for a newly opened repository
+    pub fn set_repository(&mut self, repository: Option<PathBuf>) {
+        if self.data.repository != repository {
+            self.data = DraftData {
+                repository,
+                ..Default::default()
+            };
+            self.restored = None;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_commit_message(&mut self, message: &str) {
+        if self.data.commit_message != message {
+            self.data.commit_message = message.to_string();
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_conflict_selection(&mut self, path: &str, strategy: ConflictStrategy) {
+        self.data.conflict_selections.retain(|(p, _)| p != path);
+        self.data.conflict_selections.push((path.to_string(), strategy));
+        self.dirty = true;
+    }
+
+    pub fn clear_conflict_selections(&mut self) {
+        if !self.data.conflict_selections.is_empty() {
+            self.data.conflict_selections.clear();
+            self.dirty = true;
+        }
+    }
+
+    pub fn take_restored_commit_message(&mut self) -> Option<String> {
+        let restored = self.restored.as_mut()?;
+        let message = std::mem::take(&mut restored.commit_message);
+        (!message.is_empty()).then_some(message)
+    }
+
+    pub fn take_restored_conflict_selections(&mut self) -> Vec<(String, ConflictStrategy)> {
+        self.restored
+            .as_mut()
+            .map(|restored| std::mem::take(&mut restored.conflict_selections))
+            .unwrap_or_default()
+    }
+}
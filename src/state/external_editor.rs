@@ -0,0 +1,141 @@
+#![allow(dead_code)]
+
+use anyhow::{bail, Context as _, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Editors recognised by program name when the command has no arguments,
+/// with the arguments that open a file at a line
+const KNOWN_EDITORS: &[(&str, &str)] = &[
+    ("code", "--goto {file}:{line}"),
+    ("code-insiders", "--goto {file}:{line}"),
+    ("codium", "--goto {file}:{line}"),
+    ("cursor", "--goto {file}:{line}"),
+    ("windsurf", "--goto {file}:{line}"),
+    ("zed", "{file}:{line}"),
+    ("subl", "{file}:{line}"),
+    ("idea", "--line {line} {file}"),
+    ("clion", "--line {line} {file}"),
+    ("goland", "--line {line} {file}"),
+    ("pycharm", "--line {line} {file}"),
+    ("rustrover", "--line {line} {file}"),
+    ("webstorm", "--line {line} {file}"),
+    ("mate", "-l {line} {file}"),
+    ("gvim", "+{line} {file}"),
+    ("mvim", "+{line} {file}"),
+    ("emacsclient", "-n +{line} {file}"),
+];
+
+/// Program and arguments that open `file` at `line` with an editor command
+/// template. `{file}` and `{line}` are replaced in each argument; the file is
+/// appended when the template doesn't mention it, and a bare known editor
+/// gets its own deep link arguments. Quoted words may contain spaces.
+pub fn editor_command(template: &str, file: &Path, line: Option<u32>) -> Option<Vec<String>> {
+    let mut words = split_words(template);
+    let program = words.first()?.clone();
+
+    if words.len() == 1 {
+        let name = Path::new(&program)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if let Some((_, args)) = KNOWN_EDITORS.iter().find(|(editor, _)| *editor == name) {
+            words.extend(args.split_whitespace().map(str::to_string));
+        }
+    }
+    if !words.iter().skip(1).any(|word| word.contains("{file}")) {
+        words.push("{file}".to_string());
+    }
+
+    let file = file.to_string_lossy();
+    let line = line.unwrap_or(1).max(1).to_string();
+    Some(
+        words
+            .into_iter()
+            .map(|word| word.replace("{file}", &file).replace("{line}", &line))
+            .collect(),
+    )
+}
+
+/// Split a command line on whitespace, keeping single- or double-quoted
+/// text together
+fn split_words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+
+    for c in command.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => word.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            None => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+/// Open a file at a line with the editor command template
+pub fn open_in_editor(template: &str, file: &Path, line: Option<u32>) -> Result<()> {
+    let Some(words) = editor_command(template, file, line) else {
+        bail!("No editor command is set");
+    };
+    Command::new(&words[0])
+        .args(&words[1..])
+        .spawn()
+        .with_context(|| format!("running {}", words[0]))?;
+    Ok(())
+}
+
+/// Show a file selected in the system file manager. Linux file managers have
+/// no common way to select a file, so its folder is opened instead.
+pub fn reveal_in_file_manager(path: &Path) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg("-R").arg(path);
+        command
+    };
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("explorer");
+        command.arg(format!("/select,{}", path.display()));
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(path.parent().unwrap_or(path));
+        command
+    };
+
+    command.spawn().context("opening the file manager")?;
+    Ok(())
+}
+
+/// Name of the reveal action on this platform
+pub fn reveal_label() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "Reveal in Finder"
+    } else if cfg!(target_os = "windows") {
+        "Show in Explorer"
+    } else {
+        "Open Containing Folder"
+    }
+}
@@ -1,12 +1,128 @@
 #![allow(dead_code)]
 
 use crate::git::{
-    self, BranchInfo, CommitGraphData, CommitInfo, ConflictInfo, ConflictStrategy, FileDiff,
-    FileStatus, ResetMode, RepositoryInfo, StashEntry, TagInfo,
+    self, AuthorSummary, BranchInfo, BranchKind, CherryPickSequence, CommitGraphData, CommitInfo,
+    CommitSignature, CompareMode, Comparison, ConflictDocument, ConflictInfo, ConflictStrategy,
+    DiffDisplayOptions, DiffLine, DiffLineId, DiscardRequest, DiscardScope, FileBlame, FileDiff,
+    FileStatus, Git2Backend, GraphFilter, HexPreview, Identity, IdentityScope, IgnorePattern,
+    InterruptedOperation, InterruptedPick, LfsFile, LfsPointer, LineAction, LintWarning,
+    MboxOptions, MergeMode, MergeRequest, MessageLinter, OperationPreview, OperationSnapshot,
+    PickStep, PreviewOperation, ProjectFile, PullRequest, RefMapCache, ReflogEntry, RemoteWebUrl,
+    RepositoryBackend, RepositoryInfo, ResetMode, SearchMatch, SearchQuery, SearchSummary,
+    SigningViolation, Snapshot, StashBranchRequest, StashEntry, TagInfo, TrashEntry, VcsBackend,
+    WorktreeInfo,
+};
+use crate::state::{
+    open_in_editor, reveal_in_file_manager, CommitLintMode, DiffSizeLimit, ErrorDetails,
+    SigningPolicy, WatcherEvent,
 };
 use anyhow::Result;
+use futures::channel::mpsc;
+use futures::StreamExt;
 use gpui::*;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Most commits loaded while looking for a commit to focus in the graph
+const FOCUS_COMMIT_LOAD_LIMIT: usize = 5000;
+
+/// How long an undone commit can be restored with redo
+pub const REDO_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Most undone commits remembered for redo
+const UNDO_STACK_LIMIT: usize = 10;
+
+/// Most destructive operations remembered for undo
+const OPERATION_HISTORY_LIMIT: usize = 20;
+
+/// Most lines of a hunk shown when hovering a file
+const HUNK_PREVIEW_LINES: usize = 12;
+
+/// Diff lines rendered at first, and added by each "Load more"
+pub const DIFF_PAGE_LINES: usize = 2000;
+
+/// Commits searched per background batch of a pickaxe search; progress is
+/// reported between batches
+const PICKAXE_BATCH: usize = 200;
+
+/// Matches after which a pickaxe search stops
+pub const PICKAXE_RESULT_LIMIT: usize = 100;
+
+/// Most touched files listed in an author summary
+const AUTHOR_TOP_PATHS: usize = 5;
+
+/// Counters bumped whenever a slice of the state changes, so views can
+/// skip re-rendering on notifies that don't touch what they show
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Revisions {
+    /// Changed files, the file selection and the ignored file list
+    pub status: u64,
+    /// Branches and tags
+    pub refs: u64,
+    /// Loaded history, the focused commit, HEAD and signature checks
+    pub graph: u64,
+}
+
+/// First diff hunk of a changed file, shown when hovering it in the file list
+#[derive(Clone, Debug)]
+pub enum HunkPreview {
+    Loading,
+    /// Up to `HUNK_PREVIEW_LINES` lines, starting with the `@@` header
+    Ready(Vec<DiffLine>),
+    /// Binary files, and diffs that failed to load
+    Unavailable,
+}
+
+/// Signature verification of a commit, shown as a badge in the history
+#[derive(Clone, Debug)]
+pub enum SignatureCheck {
+    Loading,
+    Ready(CommitSignature),
+    /// The git CLI couldn't verify it
+    Unavailable,
+}
+
+impl SignatureCheck {
+    /// The signature, once verified
+    pub fn ready(self) -> Option<CommitSignature> {
+        match self {
+            Self::Ready(signature) => Some(signature),
+            Self::Loading | Self::Unavailable => None,
+        }
+    }
+}
+
+/// What the current diff shows, so it can be loaded again when the diff
+/// options change
+#[derive(Clone, Debug)]
+enum DiffSource {
+    /// Staged and unstaged changes of a file against HEAD
+    WorkingTree(String),
+    /// Unstaged changes of a file
+    Unstaged(String),
+    /// Files changed by a commit
+    Commit(String),
+    /// Files changed between two revisions
+    Comparison {
+        base: String,
+        head: String,
+        mode: CompareMode,
+    },
+}
+
+/// A commit removed from HEAD by "Undo commit"
+#[derive(Clone, Debug)]
+pub struct UndoneCommit {
+    pub sha: String,
+    pub summary: String,
+    /// Commit HEAD was reset to; redo only applies while HEAD is still here
+    pub parent: String,
+    pub undone_at: Instant,
+}
 
 /// Credentials for git operations
 #[derive(Clone)]
@@ -15,8 +131,111 @@ pub struct GitCredentials {
     pub password: String,
 }
 
+/// Remote operation that can be retried after re-authentication
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RemoteOperation {
+    Push,
+    /// Push the current branch and make it track the pushed remote branch
+    PushSetUpstream,
+    /// Overwrite the remote branch, provided it is still at `expected`
+    ForcePush { expected: Option<String> },
+    Pull,
+    Fetch,
+    /// Deepen a shallow clone and fetch every branch of a single-branch one
+    FetchFullHistory,
+    /// Push a single tag to origin
+    PushTag(String),
+}
+
+impl RemoteOperation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Push | Self::PushSetUpstream => "Push",
+            Self::ForcePush { .. } => "Force Push",
+            Self::Pull => "Pull",
+            Self::Fetch => "Fetch",
+            Self::FetchFullHistory => "Fetch Full History",
+            Self::PushTag(_) => "Push Tag",
+        }
+    }
+}
+
+/// Progress of a sequenced cherry-pick
+#[derive(Clone, Debug)]
+pub struct CherryPickProgress {
+    pub total: usize,
+    pub completed: usize,
+    /// Short SHA of the commit being applied
+    pub current: Option<String>,
+    /// Short SHA of the commit whose pick stopped on conflicts, waiting for
+    /// continue or abort
+    pub conflicted: Option<String>,
+    /// Error that stopped the sequence
+    pub error: Option<String>,
+}
+
+impl CherryPickProgress {
+    /// Still picking, or waiting on conflicts to pick the rest
+    pub fn is_running(&self) -> bool {
+        self.error.is_none() && self.completed < self.total
+    }
+}
+
+/// Most hook output lines kept; older lines are dropped
+const HOOK_OUTPUT_LIMIT: usize = 2000;
+
+/// Output of the hooks run before a commit
+#[derive(Clone, Debug, Default)]
+pub struct HookRun {
+    /// Lines of stdout and stderr, in the order they were written
+    pub output: Vec<String>,
+    /// Earlier lines dropped to stay under `HOOK_OUTPUT_LIMIT`
+    pub dropped: usize,
+    pub running: bool,
+    /// The hooks passed and the commit was created
+    pub committed: bool,
+    /// Why the hooks or the commit failed
+    pub error: Option<String>,
+}
+
+impl HookRun {
+    fn push_line(&mut self, line: String) {
+        if self.output.len() == HOOK_OUTPUT_LIMIT {
+            self.output.remove(0);
+            self.dropped += 1;
+        }
+        self.output.push(line);
+    }
+}
+
+/// How a commit request ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitOutcome {
+    Committed,
+    /// Hooks are running; the commit is created if they pass
+    Pending,
+}
+
+/// Search for commits that add or remove a string, like `git log -S`
+#[derive(Clone, Debug, Default)]
+pub struct PickaxeSearch {
+    pub query: String,
+    /// Matching commits found so far, newest first
+    pub results: Vec<CommitInfo>,
+    /// Commits searched so far
+    pub scanned: usize,
+    /// Commits to search, known once the history has been walked
+    pub total: Option<usize>,
+    pub running: bool,
+    /// Stopped at `PICKAXE_RESULT_LIMIT` matches before the end of history
+    pub truncated: bool,
+    pub error: Option<String>,
+}
+
 /// Main git state for the application
 pub struct GitState {
+    /// Reads status, history, diffs and refs
+    backend: Arc<dyn VcsBackend>,
     /// Path to the repository
     pub path: Option<PathBuf>,
     /// Repository info (HEAD, branches, etc.)
@@ -25,45 +244,217 @@ pub struct GitState {
     pub files: Vec<FileStatus>,
     /// Selected files in the file list
     pub selected_files: Vec<String>,
+    /// Git LFS details of the changed files stored with LFS, by path
+    pub lfs_files: HashMap<String, LfsFile>,
+    /// Tracked files still holding LFS pointers whose content was never
+    /// downloaded
+    pub lfs_missing: Vec<String>,
+    /// `git lfs pull` is running
+    pub lfs_pulling: bool,
+    /// Changed files with binary content
+    pub binary_files: HashSet<String>,
+    /// List ignored paths under the file list
+    pub show_ignored: bool,
+    /// Ignored paths, loaded only while `show_ignored` is on
+    pub ignored_files: Vec<String>,
+    /// git2 handle kept between operations, with the identity override it
+    /// was opened with
+    repo_handle: RefCell<Option<(Identity, git2::Repository)>>,
+    /// Backend handle kept between operations
+    backend_handle: RefCell<Option<Box<dyn RepositoryBackend>>>,
     /// Commit graph data
     pub commits: Option<CommitGraphData>,
+    /// Branch and tag labels of the graph, reused until a ref changes
+    ref_maps: RefMapCache,
+    /// Restricts the commits loaded into the graph
+    pub graph_filter: GraphFilter,
+    /// Contribution of the author the graph is filtered to, read in the
+    /// background whenever the author filter changes
+    pub author_summary: Option<AuthorSummary>,
     /// Currently selected commit
     pub selected_commit: Option<CommitInfo>,
+    /// Commit the graph scrolls to and highlights
+    pub focused_commit: Option<String>,
     /// Current diff being viewed
     pub current_diff: Option<FileDiff>,
+    /// Every file of a commit or comparison the current diff belongs to,
+    /// for stepping between them; empty for a single file
+    pub diff_files: Vec<FileDiff>,
+    /// Index into `diff_files` of the current diff
+    pub diff_file_index: usize,
+    /// Hunk of the current diff last jumped to
+    pub diff_hunk: Option<usize>,
+    /// Lines of the current diff rendered so far
+    pub diff_line_limit: usize,
+    /// Hex dump shown for the current diff when it's binary
+    pub hex_preview: Option<HexPreview>,
+    /// Files larger than this many bytes are summarized instead of diffed,
+    /// mirrored from settings
+    pub max_diff_size: u64,
+    /// Whitespace and context options diffs are computed with, mirrored
+    /// from settings
+    pub diff_options: DiffDisplayOptions,
+    diff_source: Option<DiffSource>,
     /// List of branches
     pub branches: Vec<BranchInfo>,
     /// List of tags
     pub tags: Vec<TagInfo>,
     /// List of stashes
     pub stashes: Vec<StashEntry>,
+    /// Main and linked worktrees
+    pub worktrees: Vec<WorktreeInfo>,
     /// Merge conflict info
     pub conflict_info: Option<ConflictInfo>,
+    /// Cherry-pick or revert stopped on conflicts, until it is continued or
+    /// aborted
+    pub interrupted_pick: Option<InterruptedPick>,
+    /// Merge, rebase, bisect or `git am` left running, found when the
+    /// repository is opened or refreshed
+    pub interrupted_operation: Option<InterruptedOperation>,
     /// Is loading
     pub is_loading: bool,
+    /// Background fetch in progress
+    pub is_fetching: bool,
+    /// Progress of a multi-commit cherry-pick
+    pub cherry_pick_progress: Option<CherryPickProgress>,
+    /// Commits the multi-commit cherry-pick has left to pick
+    cherry_pick_sequence: Option<CherryPickSequence>,
+    /// Search of the history by changed content
+    pub pickaxe: Option<PickaxeSearch>,
+    /// Bumped to stop the running pickaxe search
+    pickaxe_generation: u64,
+    /// Stash local changes around pull and checkout, mirrored from settings
+    pub autostash: bool,
+    /// Show a preview before destructive operations, mirrored from settings
+    pub preview_operations: bool,
+    /// How merges started from the app are performed, mirrored from settings
+    pub merge_mode: MergeMode,
+    /// Remote pushed to and pulled from, mirrored from settings
+    pub remote_name: String,
+    /// Author identity overriding git config, mirrored from repository settings
+    pub identity: Identity,
+    /// GitHub/GitLab API token, mirrored from settings; pull requests are
+    /// only looked up when set
+    pub hosting_token: Option<String>,
+    /// Open pull requests of the primary remote's repository, by branch
+    pub pull_requests: HashMap<String, PullRequest>,
+    /// Command template files are opened with, mirrored from settings; the
+    /// system's default application is used when unset
+    pub editor_command: Option<String>,
+    /// Operation preview awaiting confirmation
+    pub pending_preview: Option<OperationPreview>,
+    /// Branch merge awaiting a mode choice and confirmation
+    pub pending_merge: Option<MergeRequest>,
+    /// Stash awaiting a branch to be applied onto
+    pub pending_stash_branch: Option<StashBranchRequest>,
+    /// Back up discarded changes to the trash by default, mirrored from settings
+    pub trash_discards: bool,
+    /// How commit message problems are treated, mirrored from settings
+    pub commit_lint: CommitLintMode,
+    /// Run the pre-commit and commit-msg hooks before committing, mirrored
+    /// from settings
+    pub run_hooks: bool,
+    /// Whether merges into protected branches are checked for unsigned or
+    /// untrusted commits, mirrored from settings
+    pub signing_policy: SigningPolicy,
+    /// Branches the signing policy guards, mirrored from settings
+    pub protected_branches: Vec<String>,
+    /// Keys the signing policy trusts, mirrored from settings
+    pub allowed_signers: Vec<String>,
+    /// Output of the hooks run for the last commit
+    pub hook_run: Option<HookRun>,
+    /// Discard awaiting confirmation
+    pub pending_discard: Option<DiscardRequest>,
+    /// Hunk previews by path and staged/unstaged, dropped when the status changes
+    hunk_previews: HashMap<(String, bool), HunkPreview>,
+    /// Signature checks by SHA, kept until the repository is closed since a
+    /// commit's signature never changes
+    signature_checks: HashMap<String, SignatureCheck>,
+    /// Commits removed by "Undo commit", most recent last
+    undo_stack: Vec<UndoneCommit>,
+    /// Repository positions before destructive operations, most recent last
+    operation_history: Vec<OperationSnapshot>,
     /// Error message
     pub error: Option<String>,
     /// Refresh trigger counter
     refresh_trigger: u32,
+    revisions: Revisions,
 }
 
 impl GitState {
     pub fn new() -> Self {
+        Self::with_backend(Arc::new(Git2Backend))
+    }
+
+    pub fn with_backend(backend: Arc<dyn VcsBackend>) -> Self {
         Self {
+            backend,
             path: None,
             repository_info: None,
             files: Vec::new(),
             selected_files: Vec::new(),
+            lfs_files: HashMap::new(),
+            lfs_missing: Vec::new(),
+            lfs_pulling: false,
+            binary_files: HashSet::new(),
+            show_ignored: false,
+            ignored_files: Vec::new(),
+            repo_handle: RefCell::new(None),
+            backend_handle: RefCell::new(None),
             commits: None,
+            ref_maps: RefMapCache::default(),
+            graph_filter: GraphFilter::default(),
+            author_summary: None,
             selected_commit: None,
+            focused_commit: None,
             current_diff: None,
+            diff_files: Vec::new(),
+            diff_file_index: 0,
+            diff_hunk: None,
+            diff_line_limit: DIFF_PAGE_LINES,
+            hex_preview: None,
+            max_diff_size: DiffSizeLimit::default().bytes(),
+            diff_options: DiffDisplayOptions::default(),
+            diff_source: None,
             branches: Vec::new(),
             tags: Vec::new(),
             stashes: Vec::new(),
+            worktrees: Vec::new(),
             conflict_info: None,
+            interrupted_pick: None,
+            interrupted_operation: None,
             is_loading: false,
+            is_fetching: false,
+            cherry_pick_progress: None,
+            cherry_pick_sequence: None,
+            pickaxe: None,
+            pickaxe_generation: 0,
+            autostash: false,
+            preview_operations: false,
+            merge_mode: MergeMode::Auto,
+            remote_name: "origin".to_string(),
+            identity: Identity::default(),
+            hosting_token: None,
+            pull_requests: HashMap::new(),
+            editor_command: None,
+            pending_preview: None,
+            pending_merge: None,
+            pending_stash_branch: None,
+            trash_discards: true,
+            commit_lint: CommitLintMode::default(),
+            run_hooks: true,
+            signing_policy: SigningPolicy::default(),
+            protected_branches: Vec::new(),
+            allowed_signers: Vec::new(),
+            hook_run: None,
+            pending_discard: None,
+            hunk_previews: HashMap::new(),
+            signature_checks: HashMap::new(),
+            undo_stack: Vec::new(),
+            operation_history: Vec::new(),
             error: None,
             refresh_trigger: 0,
+            revisions: Revisions::default(),
         }
     }
 
@@ -71,49 +462,102 @@ impl GitState {
         self.is_loading = true;
         cx.notify();
 
-        // Open the repository using git2
-        let mut repo = git2::Repository::open(path)?;
+        // Open the repository with the backend, and with git2 for the rest.
+        // Refreshing the open repository reuses its handles.
+        if self.path.as_deref() != Some(path) {
+            let backend = self.backend.open(path)?;
+            let repo = self.open_git2(path)?;
+            *self.backend_handle.get_mut() = Some(backend);
+            *self.repo_handle.get_mut() = Some((self.identity.clone(), repo));
+        }
+        self.undo_stack.clear();
+        self.operation_history.clear();
 
         // Get repository info
         self.path = Some(path.to_path_buf());
-        self.repository_info = Some(RepositoryInfo::from_repo(&repo)?);
+        self.repository_info = Some(self.with_backend_repo(|backend| backend.info())?);
 
         // Get file status
-        self.files = FileStatus::get_all(&repo)?;
+        self.files = self.with_backend_repo(|backend| backend.status())?;
+        self.prune_selection();
+        self.hunk_previews.clear();
+        self.load_ignored_files();
+        self.load_lfs_info();
+        self.load_binary_files();
 
         // Get branches
-        self.branches = BranchInfo::get_all(&repo)?;
+        self.branches = self.with_backend_repo(|backend| backend.branches())?;
 
         // Get tags
-        self.tags = TagInfo::get_all(&repo)?;
+        self.tags = self.with_backend_repo(|backend| backend.tags())?;
 
         // Get stashes
-        self.stashes = StashEntry::get_all(&mut repo)?;
+        self.stashes = self.with_repo_handle_mut(StashEntry::get_all)?;
+
+        // Get worktrees (not fatal; bare or unusual layouts may not list them)
+        self.worktrees = self.with_repo(WorktreeInfo::get_all).unwrap_or_else(|e| {
+            log::warn!("Failed to list worktrees: {}", e);
+            Vec::new()
+        });
 
         // Get commit graph (first 100 commits)
-        self.commits = Some(CommitGraphData::build(&repo, 100, 0)?);
+        let (filter, refs) = (&self.graph_filter, &self.ref_maps);
+        let commits = self.with_backend_repo(|backend| backend.commit_graph(filter, 100, 0, refs))?;
+        self.commits = Some(commits);
+        self.bump_revisions(true, true, true);
 
         // Check for conflicts
-        self.conflict_info = ConflictInfo::get(&repo)?;
+        self.conflict_info = self.with_repo(ConflictInfo::get)?;
+        self.interrupted_pick = self.with_repo(InterruptedPick::get)?;
+        self.interrupted_operation = self.with_repo(InterruptedOperation::get)?;
 
         self.is_loading = false;
         self.error = None;
+        self.pull_requests.clear();
+        self.load_pull_requests(cx);
         cx.notify();
         Ok(())
     }
 
     pub fn close_repository(&mut self, cx: &mut Context<Self>) {
         self.path = None;
+        self.invalidate_repo_handles();
         self.repository_info = None;
         self.files.clear();
         self.selected_files.clear();
+        self.ignored_files.clear();
+        self.lfs_files.clear();
+        self.lfs_missing.clear();
+        self.binary_files.clear();
         self.commits = None;
+        self.ref_maps.clear();
+        self.graph_filter = GraphFilter::default();
+        self.author_summary = None;
+        self.pull_requests.clear();
         self.selected_commit = None;
+        self.focused_commit = None;
+        self.bump_revisions(true, true, true);
         self.current_diff = None;
+        self.diff_files.clear();
         self.branches.clear();
         self.tags.clear();
         self.stashes.clear();
+        self.worktrees.clear();
         self.conflict_info = None;
+        self.interrupted_pick = None;
+        self.interrupted_operation = None;
+        self.cherry_pick_progress = None;
+        self.cherry_pick_sequence = None;
+        self.pending_preview = None;
+        self.pending_merge = None;
+        self.pending_stash_branch = None;
+        self.pending_discard = None;
+        self.hunk_previews.clear();
+        self.signature_checks.clear();
+        self.undo_stack.clear();
+        self.operation_history.clear();
+        self.pickaxe_generation += 1;
+        self.pickaxe = None;
         self.is_loading = false;
         self.error = None;
         cx.notify();
@@ -130,16 +574,274 @@ impl GitState {
         cx.notify();
     }
 
+    /// Refresh only the working tree status and conflict info
+    pub fn refresh_status(&mut self, cx: &mut Context<Self>) {
+        let result = self
+            .with_backend_repo(|backend| backend.status())
+            .and_then(|files| {
+                let conflict_info = self.with_repo(ConflictInfo::get)?;
+                let interrupted_pick = self.with_repo(InterruptedPick::get)?;
+                let interrupted_operation = self.with_repo(InterruptedOperation::get)?;
+                Ok((
+                    files,
+                    conflict_info,
+                    interrupted_pick,
+                    interrupted_operation,
+                ))
+            });
+
+        match result {
+            Ok((files, conflict_info, interrupted_pick, interrupted_operation)) => {
+                self.files = files;
+                self.conflict_info = conflict_info;
+                self.interrupted_pick = interrupted_pick;
+                self.interrupted_operation = interrupted_operation;
+                self.prune_selection();
+                self.hunk_previews.clear();
+                self.load_ignored_files();
+                self.load_lfs_info();
+                self.load_binary_files();
+                self.bump_revisions(true, false, false);
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        cx.notify();
+    }
+
+    pub fn set_show_ignored(&mut self, show_ignored: bool, cx: &mut Context<Self>) {
+        self.show_ignored = show_ignored;
+        self.load_ignored_files();
+        self.bump_revisions(true, false, false);
+        cx.notify();
+    }
+
+    fn load_ignored_files(&mut self) {
+        self.ignored_files = if self.show_ignored {
+            self.with_repo(FileStatus::get_ignored).unwrap_or_else(|e| {
+                log::warn!("Failed to list ignored files: {}", e);
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Read the LFS details of the changed files and look for pointers whose
+    /// content was never downloaded
+    fn load_lfs_info(&mut self) {
+        let result = self.with_repo(|repo| {
+            if !git::uses_lfs(repo) {
+                return Ok((HashMap::new(), Vec::new()));
+            }
+            let files = self
+                .files
+                .iter()
+                .filter_map(|file| Some((file.path.clone(), LfsFile::get(repo, &file.path)?)))
+                .collect();
+            Ok((files, git::missing_lfs_objects(repo)?))
+        });
+        match result {
+            Ok((files, missing)) => {
+                self.lfs_files = files;
+                self.lfs_missing = missing;
+            }
+            Err(e) => log::warn!("Failed to read Git LFS details: {}", e),
+        }
+    }
+
+    fn load_binary_files(&mut self) {
+        let paths: Vec<_> = self.files.iter().map(|file| file.path.clone()).collect();
+        self.binary_files = self
+            .with_repo(|repo| {
+                Ok(paths
+                    .into_iter()
+                    .filter(|path| git::is_binary_path(repo, path))
+                    .collect())
+            })
+            .unwrap_or_default();
+    }
+
+    /// Warnings for the files among `paths` that match an LFS pattern but
+    /// would be staged as regular blobs
+    pub fn lfs_staging_warnings(&self, paths: &[String]) -> Vec<String> {
+        self.with_repo(|repo| {
+            if !git::uses_lfs(repo) {
+                return Ok(Vec::new());
+            }
+            Ok(paths
+                .iter()
+                .filter_map(|path| git::lfs_staging_warning(repo, path))
+                .collect())
+        })
+        .unwrap_or_default()
+    }
+
+    /// Stage files or directories with `git add`, so the LFS filter stores
+    /// pointers instead of their content
+    pub fn stage_with_lfs(&mut self, paths: &[String], cx: &mut Context<Self>) -> Result<()> {
+        self.with_repo_mut(|repo| git::stage_with_lfs(repo, paths), cx)
+    }
+
+    /// The object of an LFS pointer is in the local LFS store
+    pub fn is_lfs_object_downloaded(&self, pointer: &LfsPointer) -> bool {
+        self.with_repo(|repo| Ok(pointer.is_downloaded(repo))).unwrap_or(false)
+    }
+
+    /// Run `git lfs pull` in the background to download missing objects,
+    /// refreshing once it's done
+    pub fn pull_lfs_objects(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let Some(path) = self.path.clone() else {
+            return Task::ready(Err(anyhow::anyhow!("No repository open")));
+        };
+        self.lfs_pulling = true;
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    let repo = git2::Repository::open(&path)?;
+                    git::lfs_pull(&repo)
+                })
+                .await;
+            this.update(cx, |state, cx| {
+                state.lfs_pulling = false;
+                state.invalidate_repo_handles();
+                state.refresh(cx);
+            })
+            .ok();
+            result
+        })
+    }
+
+    /// Add `pattern` to the root `.gitignore`; the files it covers drop out
+    /// of the untracked list
+    pub fn ignore(&mut self, pattern: &IgnorePattern, cx: &mut Context<Self>) -> Result<()> {
+        self.with_repo(|repo| git::add_to_gitignore(repo, pattern))?;
+        self.refresh_status(cx);
+        Ok(())
+    }
+
+    /// Apply a batch of file watcher events with the narrowest refresh needed.
+    ///
+    /// Returns the new HEAD label when the checked out branch changed outside
+    /// the app. Operations made in the app refresh immediately, so by the time
+    /// their watcher event arrives the branch is already up to date.
+    pub fn handle_watcher_events(
+        &mut self,
+        events: &[WatcherEvent],
+        cx: &mut Context<Self>,
+    ) -> Option<String> {
+        // Another tool may have rewritten what libgit2 keeps in memory
+        if events.iter().any(|event| *event != WatcherEvent::Workdir) {
+            self.invalidate_repo_handles();
+        }
+
+        if events.contains(&WatcherEvent::Head)
+            || events.contains(&WatcherEvent::Refs)
+        {
+            // Ref changes can affect HEAD, branches, tags, stashes and the graph
+            let previous = self.head_label();
+            self.refresh(cx);
+            let current = self.head_label();
+
+            if events.contains(&WatcherEvent::Head) && previous.is_some() {
+                return current.filter(|current| previous.as_ref() != Some(current));
+            }
+        } else if events.contains(&WatcherEvent::Index)
+            || events.contains(&WatcherEvent::Workdir)
+        {
+            self.refresh_status(cx);
+        }
+
+        None
+    }
+
+    /// Run `f` against the open repository's git2 handle, which is kept
+    /// between operations and reopened only after `invalidate_repo_handles`
+    /// or when the identity override changes
     fn with_repo<F, T>(&self, f: F) -> Result<T>
     where
         F: FnOnce(&git2::Repository) -> Result<T>,
+    {
+        self.with_repo_handle_mut(|repo| f(repo))
+    }
+
+    /// Like `with_repo`, for the git2 calls that need a mutable handle.
+    ///
+    /// The handle is taken out of its cell while `f` runs, so `f` can reach
+    /// the repository through `with_repo` again; the nested call then opens
+    /// a handle of its own.
+    fn with_repo_handle_mut<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut git2::Repository) -> Result<T>,
     {
         let path = self
             .path
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+        let kept = self
+            .repo_handle
+            .take()
+            .filter(|(identity, _)| *identity == self.identity);
+        let (identity, mut repo) = match kept {
+            Some(handle) => {
+                // libgit2 caches the index; pick up what other git processes
+                // wrote to it since the handle was last used
+                handle.1.index()?.read(false)?;
+                handle
+            }
+            None => (self.identity.clone(), self.open_git2(path)?),
+        };
+
+        let result = f(&mut repo);
+
+        // A nested call may have kept its own handle meanwhile
+        let mut handle = self.repo_handle.borrow_mut();
+        if handle.is_none() {
+            *handle = Some((identity, repo));
+        }
+        result
+    }
+
+    /// Drop the kept repository handles so the next operation reopens the
+    /// repository, for when something outside the app may have changed
+    /// state libgit2 caches, like the config
+    pub fn invalidate_repo_handles(&mut self) {
+        *self.repo_handle.get_mut() = None;
+        *self.backend_handle.get_mut() = None;
+    }
+
+    /// Open `path` with git2, applying the identity override so commits made
+    /// through the handle are signed with it
+    fn open_git2(&self, path: &Path) -> Result<git2::Repository> {
         let repo = git2::Repository::open(path)?;
-        f(&repo)
+        self.identity.apply(&repo)?;
+        Ok(repo)
+    }
+
+    /// Run `f` against the open repository through the backend, keeping
+    /// the backend's handle like `with_repo` does
+    fn with_backend_repo<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&dyn RepositoryBackend) -> Result<T>,
+    {
+        let path = self
+            .path
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+        if self.backend_handle.borrow().is_none() {
+            let repo = self.backend.open(path)?;
+            *self.backend_handle.borrow_mut() = Some(repo);
+        }
+        let handle = self.backend_handle.borrow();
+        let repo = handle.as_ref().expect("backend handle was just opened");
+        f(repo.as_ref())
+    }
+
+    /// Name of the backend reading the repository
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
     }
 
     fn with_repo_mut<F, T>(&mut self, f: F, cx: &mut Context<Self>) -> Result<T>
@@ -151,6 +853,50 @@ impl GitState {
         Ok(result)
     }
 
+    /// Like `with_repo_mut`, but when autostash is enabled local changes are
+    /// stashed before `f` runs and reapplied afterwards. Reapply conflicts are
+    /// left for the conflict dialog, and the stash is kept until they're resolved.
+    fn with_autostash<F, T>(&mut self, f: F, cx: &mut Context<Self>) -> Result<T>
+    where
+        F: FnOnce(&git2::Repository) -> Result<T>,
+    {
+        if !self.autostash {
+            return self.with_repo_mut(f, cx);
+        }
+
+        let path = self
+            .path
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+        let mut repo = self.open_git2(&path)?;
+
+        let stash = StashEntry::autostash_save(&mut repo)?;
+        let result = f(&repo);
+
+        if let Some(oid) = stash {
+            // A pull that stopped on a merge keeps the stash until it's finished
+            if repo.state() == git2::RepositoryState::Clean {
+                match StashEntry::autostash_apply(&mut repo, oid) {
+                    Ok(true) => log::warn!("Reapplying autostashed changes caused conflicts"),
+                    Ok(false) => {}
+                    Err(e) => log::error!("Failed to reapply autostash, changes kept: {}", e),
+                }
+            } else {
+                log::warn!("Autostashed changes kept in the stash list until the merge is done");
+            }
+        }
+
+        self.refresh(cx);
+        result
+    }
+
+    /// Whether the current conflicts come from reapplying stashed changes
+    pub fn is_stash_conflict(&self) -> bool {
+        self.conflict_info
+            .as_ref()
+            .is_some_and(|info| info.is_stash_conflict)
+    }
+
     // File operations
     pub fn stage_file(&mut self, path: &str, cx: &mut Context<Self>) -> Result<()> {
         self.with_repo_mut(
@@ -211,484 +957,2640 @@ impl GitState {
         )
     }
 
-    pub fn discard_all(&mut self, cx: &mut Context<Self>) -> Result<()> {
-        self.with_repo_mut(
-            |repo| {
-                let mut checkout_opts = git2::build::CheckoutBuilder::new();
-                checkout_opts.force();
-                repo.checkout_head(Some(&mut checkout_opts))?;
-                Ok(())
-            },
-            cx,
-        )
-    }
-
-    // Commit operations
-    pub fn create_commit(&mut self, message: &str, cx: &mut Context<Self>) -> Result<()> {
+    /// Stage every change under `dir`, including deletions
+    pub fn stage_directory(&mut self, dir: &str, cx: &mut Context<Self>) -> Result<()> {
         self.with_repo_mut(
             |repo| {
-                let sig = repo.signature()?;
                 let mut index = repo.index()?;
-                let tree_id = index.write_tree()?;
-                let tree = repo.find_tree(tree_id)?;
-                let parent = repo.head()?.peel_to_commit()?;
-                repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])?;
+                index.add_all([dir], git2::IndexAddOption::DEFAULT, None)?;
+                index.update_all([dir], None)?;
+                index.write()?;
                 Ok(())
             },
             cx,
         )
     }
 
-    pub fn amend_commit(&mut self, message: &str, cx: &mut Context<Self>) -> Result<()> {
+    pub fn unstage_directory(&mut self, dir: &str, cx: &mut Context<Self>) -> Result<()> {
         self.with_repo_mut(
             |repo| {
-                let mut index = repo.index()?;
-                let tree_id = index.write_tree()?;
-                let tree = repo.find_tree(tree_id)?;
                 let head = repo.head()?.peel_to_commit()?;
-                head.amend(Some("HEAD"), None, None, None, Some(message), Some(&tree))?;
+                repo.reset_default(Some(&head.into_object()), [dir])?;
                 Ok(())
             },
             cx,
         )
     }
 
-    // Remote operations
-    pub fn push(&mut self, auth: Option<&GitCredentials>, cx: &mut Context<Self>) -> Result<()> {
+    /// Discard changes to tracked files under `dir`; untracked files are kept
+    pub fn discard_directory(&mut self, dir: &str, cx: &mut Context<Self>) -> Result<()> {
         self.with_repo_mut(
             |repo| {
-                let mut remote = repo.find_remote("origin")?;
-                let head = repo.head()?;
-                let branch_name = head.shorthand().unwrap_or("HEAD");
-
-                let mut callbacks = git2::RemoteCallbacks::new();
-                if let Some(creds) = auth {
-                    let username = creds.username.clone();
-                    let password = creds.password.clone();
-                    callbacks.credentials(move |_url, _username_from_url, _allowed_types| {
-                        git2::Cred::userpass_plaintext(&username, &password)
-                    });
-                }
-
-                let mut push_opts = git2::PushOptions::new();
-                push_opts.remote_callbacks(callbacks);
-
-                let refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
-                remote.push(&[&refspec], Some(&mut push_opts))?;
+                let mut checkout_opts = git2::build::CheckoutBuilder::new();
+                checkout_opts.force();
+                checkout_opts.path(dir);
+                repo.checkout_head(Some(&mut checkout_opts))?;
                 Ok(())
             },
             cx,
         )
     }
 
-    pub fn pull(&mut self, auth: Option<&GitCredentials>, cx: &mut Context<Self>) -> Result<()> {
+    /// Stage several files at once, including deletions
+    pub fn stage_files(&mut self, paths: &[String], cx: &mut Context<Self>) -> Result<()> {
         self.with_repo_mut(
             |repo| {
-                let mut remote = repo.find_remote("origin")?;
-                let head = repo.head()?;
-                let branch_name = head.shorthand().unwrap_or("HEAD");
-
-                let mut callbacks = git2::RemoteCallbacks::new();
-                if let Some(creds) = auth {
-                    let username = creds.username.clone();
-                    let password = creds.password.clone();
-                    callbacks.credentials(move |_url, _username_from_url, _allowed_types| {
-                        git2::Cred::userpass_plaintext(&username, &password)
-                    });
-                }
-
-                let mut fetch_opts = git2::FetchOptions::new();
-                fetch_opts.remote_callbacks(callbacks);
-
-                remote.fetch(&[branch_name], Some(&mut fetch_opts), None)?;
-
-                // Merge
-                let fetch_head = repo.find_reference("FETCH_HEAD")?;
-                let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
-                let (analysis, _) = repo.merge_analysis(&[&fetch_commit])?;
-
-                if analysis.is_fast_forward() {
-                    let mut reference =
-                        repo.find_reference(&format!("refs/heads/{}", branch_name))?;
-                    reference.set_target(fetch_commit.id(), "Fast-forward")?;
-                    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
-                } else if analysis.is_normal() {
-                    repo.merge(&[&fetch_commit], None, None)?;
+                let mut index = repo.index()?;
+                for path in paths {
+                    if repo.workdir().is_some_and(|dir| dir.join(path).exists()) {
+                        index.add_path(Path::new(path))?;
+                    } else {
+                        index.remove_path(Path::new(path))?;
+                    }
                 }
-
+                index.write()?;
                 Ok(())
             },
             cx,
         )
     }
 
-    pub fn fetch(&mut self, auth: Option<&GitCredentials>, cx: &mut Context<Self>) -> Result<()> {
+    pub fn unstage_files(&mut self, paths: &[String], cx: &mut Context<Self>) -> Result<()> {
         self.with_repo_mut(
             |repo| {
-                let mut remote = repo.find_remote("origin")?;
-
-                let mut callbacks = git2::RemoteCallbacks::new();
-                if let Some(creds) = auth {
-                    let username = creds.username.clone();
-                    let password = creds.password.clone();
-                    callbacks.credentials(move |_url, _username_from_url, _allowed_types| {
-                        git2::Cred::userpass_plaintext(&username, &password)
-                    });
-                }
-
-                let mut fetch_opts = git2::FetchOptions::new();
-                fetch_opts.remote_callbacks(callbacks);
-
-                remote.fetch::<&str>(&[], Some(&mut fetch_opts), None)?;
+                let head = repo.head()?.peel_to_commit()?;
+                repo.reset_default(Some(&head.into_object()), paths.iter().map(Path::new))?;
                 Ok(())
             },
             cx,
         )
     }
 
-    // Branch operations
-    pub fn checkout_branch(&mut self, name: &str, cx: &mut Context<Self>) -> Result<()> {
+    /// Discard changes to the tracked files among `paths`
+    pub fn discard_files(&mut self, paths: &[String], cx: &mut Context<Self>) -> Result<()> {
         self.with_repo_mut(
             |repo| {
-                let obj = repo.revparse_single(&format!("refs/heads/{}", name))?;
-                repo.checkout_tree(&obj, None)?;
-                repo.set_head(&format!("refs/heads/{}", name))?;
+                let mut checkout_opts = git2::build::CheckoutBuilder::new();
+                checkout_opts.force();
+                for path in paths {
+                    checkout_opts.path(path);
+                }
+                repo.checkout_head(Some(&mut checkout_opts))?;
                 Ok(())
             },
             cx,
         )
     }
 
-    pub fn checkout_commit(&mut self, sha: &str, cx: &mut Context<Self>) -> Result<()> {
-        self.with_repo_mut(
-            |repo| {
-                let oid = git2::Oid::from_str(sha)?;
-                let commit = repo.find_commit(oid)?;
-                repo.checkout_tree(&commit.into_object(), None)?;
-                repo.set_head_detached(oid)?;
-                Ok(())
-            },
-            cx,
-        )
+    /// Tracked files under `dir` with changes that `discard_directory` would lose
+    pub fn discardable_files_in(&self, dir: &str) -> Vec<String> {
+        let prefix = format!("{}/", dir);
+        self.discardable_files(|path| path.starts_with(&prefix))
     }
 
-    pub fn create_branch(&mut self, name: &str, cx: &mut Context<Self>) -> Result<()> {
-        self.with_repo_mut(
-            |repo| {
-                let head = repo.head()?.peel_to_commit()?;
-                repo.branch(name, &head, false)?;
-                Ok(())
-            },
-            cx,
-        )
+    fn discardable_files(&self, filter: impl Fn(&str) -> bool) -> Vec<String> {
+        let mut paths: Vec<String> = self
+            .files
+            .iter()
+            .filter(|f| f.status != git::FileStatusType::Untracked && filter(&f.path))
+            .map(|f| f.path.clone())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
     }
 
-    pub fn delete_branch(&mut self, name: &str, force: bool, cx: &mut Context<Self>) -> Result<()> {
+    pub fn discard_all(&mut self, cx: &mut Context<Self>) -> Result<()> {
         self.with_repo_mut(
             |repo| {
-                let mut branch = repo.find_branch(name, git2::BranchType::Local)?;
-                if force || !branch.is_head() {
-                    branch.delete()?;
-                } else {
-                    anyhow::bail!("Cannot delete current branch");
-                }
+                let mut checkout_opts = git2::build::CheckoutBuilder::new();
+                checkout_opts.force();
+                repo.checkout_head(Some(&mut checkout_opts))?;
                 Ok(())
             },
             cx,
         )
     }
 
-    // Tag operations
-    pub fn create_tag(
-        &mut self,
-        name: &str,
-        sha: &str,
-        message: Option<&str>,
-        cx: &mut Context<Self>,
-    ) -> Result<()> {
-        self.with_repo_mut(
-            |repo| {
-                if let Some(msg) = message {
-                    TagInfo::create_annotated(repo, name, Some(sha), msg)?;
-                } else {
-                    TagInfo::create_lightweight(repo, name, Some(sha))?;
-                }
-                Ok(())
-            },
-            cx,
-        )?;
-        // Refresh tag list
-        if let Some(path) = &self.path {
-            let repo = git2::Repository::open(path)?;
-            self.tags = TagInfo::get_all(&repo)?;
-            cx.notify();
+    /// Hold a discard for confirmation, listing the files it would affect
+    pub fn request_discard(&mut self, scope: DiscardScope, cx: &mut Context<Self>) {
+        let files = match &scope {
+            DiscardScope::File(path) => self.discardable_files(|p| p == path),
+            DiscardScope::Directory(dir) => self.discardable_files_in(dir),
+            DiscardScope::Files(paths) => self.discardable_files(|p| paths.iter().any(|f| f == p)),
+            DiscardScope::All => self.discardable_files(|_| true),
+        };
+        if files.is_empty() {
+            return;
         }
-        Ok(())
-    }
 
-    pub fn delete_tag(&mut self, name: &str, cx: &mut Context<Self>) -> Result<()> {
-        self.with_repo_mut(
-            |repo| {
-                TagInfo::delete(repo, name)?;
-                Ok(())
-            },
-            cx,
-        )?;
-        // Refresh tag list
-        if let Some(path) = &self.path {
-            let repo = git2::Repository::open(path)?;
-            self.tags = TagInfo::get_all(&repo)?;
-            cx.notify();
-        }
-        Ok(())
+        self.pending_discard = Some(DiscardRequest {
+            scope,
+            files,
+            backup: self.trash_discards,
+            error: None,
+        });
+        cx.notify();
     }
 
-    // Stash operations
-    pub fn stash_save(&mut self, message: Option<&str>, cx: &mut Context<Self>) -> Result<()> {
-        if let Some(path) = &self.path {
-            let mut repo = git2::Repository::open(path)?;
-            StashEntry::save(&mut repo, message)?;
-            // Refresh stash list
-            self.stashes = StashEntry::get_all(&mut repo)?;
+    pub fn set_discard_backup(&mut self, backup: bool, cx: &mut Context<Self>) {
+        if let Some(request) = self.pending_discard.as_mut() {
+            request.backup = backup;
             cx.notify();
         }
-        Ok(())
     }
 
-    pub fn stash_pop(&mut self, index: usize, cx: &mut Context<Self>) -> Result<()> {
-        if let Some(path) = &self.path {
-            let mut repo = git2::Repository::open(path)?;
-            StashEntry::pop(&mut repo, index)?;
-            // Refresh stash list and files
-            self.stashes = StashEntry::get_all(&mut repo)?;
-            self.files = FileStatus::get_all(&repo)?;
-            cx.notify();
-        }
-        Ok(())
+    pub fn cancel_discard(&mut self, cx: &mut Context<Self>) {
+        self.pending_discard = None;
+        cx.notify();
     }
 
-    pub fn stash_apply(&mut self, index: usize, cx: &mut Context<Self>) -> Result<()> {
-        if let Some(path) = &self.path {
-            let mut repo = git2::Repository::open(path)?;
-            StashEntry::apply(&mut repo, index)?;
-            // Refresh files (stash list stays the same)
-            self.files = FileStatus::get_all(&repo)?;
-            cx.notify();
+    /// Run the pending discard, backing the files up first if requested.
+    ///
+    /// If the backup fails nothing is discarded and the request stays open.
+    pub fn confirm_discard(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        let Some(mut request) = self.pending_discard.take() else {
+            return Ok(());
+        };
+
+        let result = self.run_discard(&request, cx);
+        if let Err(e) = &result {
+            request.error = Some(e.to_string());
+            self.pending_discard = Some(request);
         }
-        Ok(())
+        cx.notify();
+        result
     }
 
-    pub fn stash_drop(&mut self, index: usize, cx: &mut Context<Self>) -> Result<()> {
-        if let Some(path) = &self.path {
-            let mut repo = git2::Repository::open(path)?;
-            StashEntry::drop(&mut repo, index)?;
-            // Refresh stash list
-            self.stashes = StashEntry::get_all(&mut repo)?;
-            cx.notify();
+    fn run_discard(&mut self, request: &DiscardRequest, cx: &mut Context<Self>) -> Result<()> {
+        if request.backup {
+            let label = request.scope.title();
+            self.with_repo(|repo| TrashEntry::backup(repo, &label, &request.files))?;
+        }
+        match &request.scope {
+            DiscardScope::File(path) => self.discard_file(path, cx),
+            DiscardScope::Directory(dir) => self.discard_directory(dir, cx),
+            DiscardScope::Files(_) => self.discard_files(&request.files, cx),
+            DiscardScope::All => self.discard_all(cx),
         }
-        Ok(())
     }
 
-    // Selection
-    pub fn toggle_file_selection(&mut self, path: &str, cx: &mut Context<Self>) {
-        if let Some(pos) = self.selected_files.iter().position(|p| p == path) {
-            self.selected_files.remove(pos);
-        } else {
-            self.selected_files.push(path.to_string());
-        }
-        cx.notify();
+    /// Discard a file's changes without a confirmation dialog, still backing
+    /// it up to the trash when that is enabled
+    pub fn discard_file_now(&mut self, path: &str, cx: &mut Context<Self>) -> Result<()> {
+        let request = DiscardRequest {
+            scope: DiscardScope::File(path.to_string()),
+            files: vec![path.to_string()],
+            backup: self.trash_discards,
+            error: None,
+        };
+        self.run_discard(&request, cx)
     }
 
-    pub fn select_all_files(&mut self, cx: &mut Context<Self>) {
-        self.selected_files = self.files.iter().map(|f| f.path.clone()).collect();
-        cx.notify();
+    /// Discards backed up to the trash, newest first
+    pub fn trash_entries(&self) -> Result<Vec<TrashEntry>> {
+        self.with_repo(TrashEntry::list)
     }
 
-    pub fn deselect_all_files(&mut self, cx: &mut Context<Self>) {
-        self.selected_files.clear();
-        cx.notify();
+    pub fn restore_trash_entry(
+        &mut self,
+        entry: &TrashEntry,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        self.with_repo_mut(|repo| entry.restore(repo), cx)
     }
 
-    pub fn set_selected_commit(&mut self, commit: Option<CommitInfo>, cx: &mut Context<Self>) {
-        self.selected_commit = commit;
+    pub fn delete_trash_entry(&mut self, entry: &TrashEntry, cx: &mut Context<Self>) -> Result<()> {
+        self.with_repo(|repo| entry.delete(repo))?;
         cx.notify();
+        Ok(())
     }
 
-    pub fn set_current_diff(&mut self, diff: Option<FileDiff>, cx: &mut Context<Self>) {
-        self.current_diff = diff;
-        cx.notify();
+    // Commit operations
+    pub fn create_commit(&mut self, message: &str, cx: &mut Context<Self>) -> Result<()> {
+        self.with_repo_mut(
+            |repo| {
+                let sig = git::commit_signature(repo)?;
+                let mut index = repo.index()?;
+                let tree_id = index.write_tree()?;
+                let tree = repo.find_tree(tree_id)?;
+                let parent = repo.head()?.peel_to_commit()?;
+                repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])?;
+                Ok(())
+            },
+            cx,
+        )
     }
 
-    pub fn load_file_diff(&mut self, path: &str, cx: &mut Context<Self>) -> Result<()> {
-        let diff = self.with_repo(|repo| FileDiff::get_file_diff(repo, path))?;
-        self.current_diff = Some(diff);
+    /// Commit only the selected files, leaving the rest of the index staged
+    pub fn commit_selected_files(&mut self, message: &str, cx: &mut Context<Self>) -> Result<()> {
+        let paths = self.selected_files.clone();
+        self.with_repo_mut(|repo| git::commit_paths(repo, &paths, message).map(|_| ()), cx)?;
+        self.selected_files.clear();
         cx.notify();
         Ok(())
     }
 
-    pub fn clear_diff(&mut self, cx: &mut Context<Self>) {
-        self.current_diff = None;
-        cx.notify();
+    /// Problems with a commit message, unless checks are off
+    pub fn lint_message(&self, message: &str) -> Vec<LintWarning> {
+        if self.commit_lint == CommitLintMode::Off {
+            return Vec::new();
+        }
+        MessageLinter::default().lint(message)
     }
 
-    // Load more commits
-    pub fn load_more_commits(&mut self, cx: &mut Context<Self>) -> Result<()> {
-        if let Some(path) = &self.path {
-            let repo = git2::Repository::open(path)?;
-            let current_count = self.commits.as_ref().map(|c| c.nodes.len()).unwrap_or(0);
-            let more_commits = CommitGraphData::build(&repo, 100, current_count)?;
-
-            if let Some(ref mut commits) = self.commits {
-                commits.nodes.extend(more_commits.nodes);
-                commits.edges.extend(more_commits.edges);
-            }
-            cx.notify();
+    /// Commit the index, or just the selected files, or amend HEAD
+    pub fn commit(&mut self, message: &str, amend: bool, cx: &mut Context<Self>) -> Result<()> {
+        if amend {
+            self.amend_commit(message, cx)
+        } else if !self.selected_files.is_empty() {
+            self.commit_selected_files(message, cx)
+        } else {
+            self.create_commit(message, cx)
         }
-        Ok(())
     }
 
-    // Getters
-    pub fn staged_files(&self) -> Vec<&FileStatus> {
-        self.files.iter().filter(|f| f.staged).collect()
-    }
+    /// Commit like `commit`, running the pre-commit and commit-msg hooks
+    /// first when that is turned on. With hooks installed they run in the
+    /// background, streaming their output into `hook_run`, and the commit
+    /// follows only if they all pass.
+    pub fn commit_with_hooks(
+        &mut self,
+        message: &str,
+        amend: bool,
+        cx: &mut Context<Self>,
+    ) -> Result<CommitOutcome> {
+        let Some(path) = self.path.clone() else {
+            anyhow::bail!("No repository open");
+        };
+        if self.hook_run.as_ref().is_some_and(|run| run.running) {
+            anyhow::bail!("Hooks are still running");
+        }
+        if !self.run_hooks || !self.with_repo(|repo| Ok(git::has_commit_hooks(repo)))? {
+            self.hook_run = None;
+            self.commit(message, amend, cx)?;
+            return Ok(CommitOutcome::Committed);
+        }
 
-    pub fn unstaged_files(&self) -> Vec<&FileStatus> {
-        self.files.iter().filter(|f| !f.staged).collect()
-    }
+        self.hook_run = Some(HookRun {
+            running: true,
+            ..HookRun::default()
+        });
+        cx.notify();
 
-    pub fn is_detached(&self) -> bool {
-        self.repository_info
-            .as_ref()
-            .map(|r| r.is_detached)
-            .unwrap_or(false)
-    }
+        let message = message.to_string();
+        cx.spawn(async move |this, cx| {
+            let (tx, mut rx) = mpsc::unbounded::<String>();
+            let hooks = cx.background_executor().spawn(async move {
+                let repo = git2::Repository::open(&path)?;
+                git::run_commit_hooks(&repo, &message, &mut |line| {
+                    let _ = tx.unbounded_send(line);
+                })
+            });
 
-    pub fn current_branch(&self) -> Option<&str> {
-        self.repository_info
-            .as_ref()
-            .and_then(|r| r.current_branch.as_deref())
+            while let Some(line) = rx.next().await {
+                let appended = this.update(cx, |state, cx| {
+                    if let Some(run) = state.hook_run.as_mut() {
+                        run.push_line(line);
+                    }
+                    cx.notify();
+                });
+                if appended.is_err() {
+                    return;
+                }
+            }
+            let result = hooks.await;
+
+            this.update(cx, |state, cx| {
+                // Hooks often restage files, e.g. after formatting them
+                state.invalidate_repo_handles();
+                let result = result.and_then(|message| state.commit(&message, amend, cx));
+                if let Some(run) = state.hook_run.as_mut() {
+                    run.running = false;
+                    match result {
+                        Ok(()) => run.committed = true,
+                        Err(e) => {
+                            log::error!("Commit stopped by hooks: {:#}", e);
+                            run.error = Some(format!("{:#}", e));
+                        }
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+        Ok(CommitOutcome::Pending)
     }
 
-    /// Get the message of the last commit (for amend)
-    pub fn get_last_commit_message(&self) -> Option<String> {
-        self.with_repo(|repo| {
-            let head = repo.head()?;
-            let commit = head.peel_to_commit()?;
-            Ok(commit.message().map(|s| s.to_string()))
-        })
-        .ok()
-        .flatten()
+    pub fn clear_hook_run(&mut self, cx: &mut Context<Self>) {
+        if self.hook_run.as_ref().is_some_and(|run| !run.running) {
+            self.hook_run = None;
+            cx.notify();
+        }
     }
 
-    // Conflict resolution
-    pub fn resolve_all_conflicts(
-        &mut self,
-        strategy: ConflictStrategy,
-        cx: &mut Context<Self>,
-    ) -> Result<()> {
+    pub fn amend_commit(&mut self, message: &str, cx: &mut Context<Self>) -> Result<()> {
         self.with_repo_mut(
             |repo| {
-                ConflictInfo::resolve_all(repo, strategy)?;
+                let mut index = repo.index()?;
+                let tree_id = index.write_tree()?;
+                let tree = repo.find_tree(tree_id)?;
+                let head = repo.head()?.peel_to_commit()?;
+                head.amend(Some("HEAD"), None, None, None, Some(message), Some(&tree))?;
                 Ok(())
             },
             cx,
         )
     }
 
-    pub fn resolve_conflicts_per_file(
+    // Identity
+
+    /// `user.name` and `user.email` as set at `scope`, ignoring other levels
+    pub fn git_identity(&self, scope: IdentityScope) -> Result<Identity> {
+        Ok(Identity::read_config(&self.identity_config(scope)?))
+    }
+
+    /// Write `user.name` and `user.email` to `scope`'s config file
+    pub fn set_git_identity(
         &mut self,
-        resolutions: Vec<(String, ConflictStrategy)>,
+        identity: &Identity,
+        scope: IdentityScope,
         cx: &mut Context<Self>,
     ) -> Result<()> {
-        self.with_repo_mut(
-            |repo| {
-                let mut index = repo.index()?;
-                let conflicts: Vec<_> = index.conflicts()?.collect::<Result<Vec<_>, _>>()?;
+        identity.write_config(&mut self.identity_config(scope)?)?;
+        cx.notify();
+        Ok(())
+    }
 
-                for (path, strategy) in resolutions {
-                    if let Some(conflict) = conflicts.iter().find(|c| {
-                        c.our
-                            .as_ref()
-                            .or(c.their.as_ref())
-                            .and_then(|e| std::str::from_utf8(&e.path).ok())
-                            == Some(&path)
-                    }) {
-                        ConflictInfo::resolve_file(repo, &mut index, &path, conflict, strategy)?;
-                    }
-                }
-                index.write()?;
-                Ok(())
-            },
-            cx,
-        )
+    fn identity_config(&self, scope: IdentityScope) -> Result<git2::Config> {
+        match scope {
+            IdentityScope::Global => scope.open(None),
+            IdentityScope::Repository => self.with_repo(|repo| scope.open(Some(repo))),
+        }
     }
 
-    pub fn complete_merge(
+    // Remote operations
+    pub fn run_remote_operation(
         &mut self,
-        message: Option<&str>,
+        operation: RemoteOperation,
+        auth: Option<&GitCredentials>,
         cx: &mut Context<Self>,
     ) -> Result<()> {
-        self.with_repo_mut(
-            |repo| {
-                ConflictInfo::complete_merge(repo, message)?;
-                Ok(())
-            },
-            cx,
-        )
+        let result = match operation {
+            RemoteOperation::Push => self.push(auth, cx),
+            RemoteOperation::PushSetUpstream => self.push_set_upstream(auth, cx),
+            RemoteOperation::ForcePush { expected } => {
+                self.force_push_with_lease(expected.as_deref(), auth, cx)
+            }
+            RemoteOperation::Pull => self.pull(auth, cx),
+            RemoteOperation::Fetch => self.fetch(auth, cx),
+            RemoteOperation::FetchFullHistory => self.fetch_full_history(auth, cx),
+            RemoteOperation::PushTag(name) => self.push_tag(&name, auth, cx),
+        };
+        // Pushed branches may have gained or lost pull requests meanwhile
+        if result.is_ok() {
+            self.load_pull_requests(cx);
+        }
+        result
     }
 
-    pub fn abort_merge(&mut self, cx: &mut Context<Self>) -> Result<()> {
+    /// Build remote callbacks for the given credentials.
+    ///
+    /// libgit2 keeps asking for credentials while the server rejects them, so
+    /// the second request fails with an auth error instead of looping.
+    fn remote_callbacks(auth: Option<&GitCredentials>) -> git2::RemoteCallbacks<'static> {
+        let mut callbacks = git2::RemoteCallbacks::new();
+        if let Some(creds) = auth {
+            let username = creds.username.clone();
+            let password = creds.password.clone();
+            let mut attempts = 0;
+            callbacks.credentials(move |_url, _username_from_url, _allowed_types| {
+                attempts += 1;
+                if attempts > 1 {
+                    return Err(git2::Error::new(
+                        git2::ErrorCode::Auth,
+                        git2::ErrorClass::Http,
+                        "authentication failed: credentials were rejected",
+                    ));
+                }
+                git2::Cred::userpass_plaintext(&username, &password)
+            });
+        }
+        callbacks
+    }
+
+    /// Check that the default remote accepts `auth` by listing its refs on
+    /// a background thread. Resolves to the number of refs listed.
+    pub fn test_connection(&self, auth: Option<GitCredentials>, cx: &App) -> Task<Result<usize>> {
+        let repo_path = self.path.clone();
+        let remote_name = self.remote_name.clone();
+        cx.background_executor().spawn(async move {
+            let repo_path = repo_path.ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+            let repo = git2::Repository::open(&repo_path)?;
+            let callbacks = Self::remote_callbacks(auth.as_ref());
+            git::remote::ls_remote(&repo, &remote_name, callbacks)
+        })
+    }
+
+    pub fn push(&mut self, auth: Option<&GitCredentials>, cx: &mut Context<Self>) -> Result<()> {
+        let remote_name = self.remote_name.clone();
         self.with_repo_mut(
             |repo| {
-                ConflictInfo::abort_merge(repo)?;
+                let mut remote = repo.find_remote(&remote_name)?;
+                let head = repo.head()?;
+                let branch_name = head.shorthand().unwrap_or("HEAD");
+
+                let callbacks = Self::remote_callbacks(auth);
+
+                let mut push_opts = git2::PushOptions::new();
+                push_opts.remote_callbacks(callbacks);
+
+                let refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
+                remote.push(&[&refspec], Some(&mut push_opts))?;
                 Ok(())
             },
             cx,
         )
     }
 
-    // Advanced operations
-    pub fn revert_commit(
+    /// Push the current branch, then make it track the branch it was pushed to
+    pub fn push_set_upstream(
         &mut self,
-        sha: &str,
-        mainline: Option<u32>,
+        auth: Option<&GitCredentials>,
         cx: &mut Context<Self>,
     ) -> Result<()> {
-        self.with_repo_mut(
-            |repo| {
-                git::revert_commit(repo, sha, mainline)?;
-                Ok(())
-            },
-            cx,
-        )
+        let branch = self
+            .current_branch()
+            .map(|b| b.to_string())
+            .ok_or_else(|| anyhow::anyhow!("HEAD is not on a branch"))?;
+        self.push(auth, cx)?;
+        let upstream = format!("{}/{}", self.remote_name, branch);
+        self.set_upstream(&branch, Some(&upstream), cx)
+    }
+
+    /// What force-pushing the current branch would overwrite on the remote
+    pub fn force_push_plan(&self) -> Result<git::remote::ForcePushPlan> {
+        let remote_name = self.remote_name.clone();
+        self.with_repo(|repo| git::remote::ForcePushPlan::read(repo, &remote_name))
     }
 
-    pub fn cherry_pick(&mut self, sha: &str, cx: &mut Context<Self>) -> Result<()> {
+    /// Force-push the current branch unless the remote branch moved away from
+    /// `expected` (the SHA shown when the push was confirmed)
+    pub fn force_push_with_lease(
+        &mut self,
+        expected: Option<&str>,
+        auth: Option<&GitCredentials>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let remote_name = self.remote_name.clone();
+        let branch = self
+            .current_branch()
+            .map(|b| b.to_string())
+            .ok_or_else(|| anyhow::anyhow!("HEAD is not on a branch"))?;
+        let expected = expected.map(git2::Oid::from_str).transpose()?;
         self.with_repo_mut(
             |repo| {
-                git::cherry_pick(repo, sha)?;
-                Ok(())
+                git::remote::force_push_with_lease(
+                    repo,
+                    &remote_name,
+                    &branch,
+                    expected,
+                    Self::remote_callbacks(auth),
+                )
             },
             cx,
         )
     }
 
-    pub fn reset_to_commit(
+    /// The current branch, if it is a local branch with no upstream to push to
+    pub fn current_branch_without_upstream(&self) -> Option<String> {
+        self.branches
+            .iter()
+            .find(|b| b.is_head && b.branch_type == BranchKind::Local && b.upstream.is_none())
+            .map(|b| b.name.clone())
+    }
+
+    pub fn push_tag(
         &mut self,
-        sha: &str,
-        mode: ResetMode,
+        name: &str,
+        auth: Option<&GitCredentials>,
         cx: &mut Context<Self>,
     ) -> Result<()> {
+        let remote_name = self.remote_name.clone();
         self.with_repo_mut(
-            |repo| {
-                git::reset_to_commit(repo, sha, mode)?;
-                Ok(())
-            },
+            |repo| TagInfo::push(repo, &remote_name, name, Self::remote_callbacks(auth)),
             cx,
         )
     }
 
-    /// Search commits by message, author, or SHA
+    pub fn pull(&mut self, auth: Option<&GitCredentials>, cx: &mut Context<Self>) -> Result<()> {
+        let remote_name = self.remote_name.clone();
+        self.with_autostash(
+            |repo| {
+                let mut remote = repo.find_remote(&remote_name)?;
+                let head = repo.head()?;
+                let branch_name = head.shorthand().unwrap_or("HEAD");
+
+                let callbacks = Self::remote_callbacks(auth);
+
+                let mut fetch_opts = git2::FetchOptions::new();
+                fetch_opts.remote_callbacks(callbacks);
+
+                remote.fetch(&[branch_name], Some(&mut fetch_opts), None)?;
+
+                // Merge
+                let fetch_head = repo.find_reference("FETCH_HEAD")?;
+                let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
+                let (analysis, _) = repo.merge_analysis(&[&fetch_commit])?;
+
+                if analysis.is_fast_forward() {
+                    let mut reference =
+                        repo.find_reference(&format!("refs/heads/{}", branch_name))?;
+                    reference.set_target(fetch_commit.id(), "Fast-forward")?;
+                    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+                } else if analysis.is_normal() {
+                    repo.merge(&[&fetch_commit], None, None)?;
+                }
+
+                Ok(())
+            },
+            cx,
+        )
+    }
+
+    pub fn fetch(&mut self, auth: Option<&GitCredentials>, cx: &mut Context<Self>) -> Result<()> {
+        let remote_name = self.remote_name.clone();
+        self.with_repo_mut(
+            |repo| {
+                let mut remote = repo.find_remote(&remote_name)?;
+
+                let callbacks = Self::remote_callbacks(auth);
+
+                let mut fetch_opts = git2::FetchOptions::new();
+                fetch_opts.remote_callbacks(callbacks);
+
+                remote.fetch::<&str>(&[], Some(&mut fetch_opts), None)?;
+                Ok(())
+            },
+            cx,
+        )
+    }
+
+    pub fn fetch_full_history(
+        &mut self,
+        auth: Option<&GitCredentials>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let remote_name = self.remote_name.clone();
+        self.with_repo_mut(
+            |repo| {
+                git::remote::fetch_full_history(repo, &remote_name, Self::remote_callbacks(auth))
+            },
+            cx,
+        )
+    }
+
+    /// Fetch the default remote with pruning, reporting removed remote-tracking branches
+    pub fn fetch_prune(
+        &mut self,
+        auth: Option<&GitCredentials>,
+        cx: &mut Context<Self>,
+    ) -> Result<git::remote::PruneReport> {
+        let remote_name = self.remote_name.clone();
+        self.with_repo_mut(
+            |repo| git::remote::fetch_with_prune(repo, &remote_name, Self::remote_callbacks(auth)),
+            cx,
+        )
+    }
+
+    /// Fetch all remotes on a background thread, refreshing when done. Only
+    /// remote-tracking refs change, so the working tree is never touched.
+    pub fn fetch_all_in_background(
+        &mut self,
+        auth: Option<GitCredentials>,
+        prune: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        if self.is_fetching {
+            return;
+        }
+
+        self.is_fetching = true;
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            let repo_path = path.clone();
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    let repo = git2::Repository::open(&repo_path)?;
+                    let auth = auth.map(|creds| git::remote::RemoteAuth {
+                        username: creds.username,
+                        password: creds.password,
+                    });
+                    git::remote::fetch_all_remotes(&repo, auth.as_ref(), prune)
+                })
+                .await;
+
+            this.update(cx, |state, cx| {
+                state.is_fetching = false;
+
+                // Ignore results for a repository that was closed meanwhile
+                if state.path.as_ref() != Some(&path) {
+                    cx.notify();
+                    return;
+                }
+
+                match result {
+                    Ok(report) => {
+                        for (remote, e) in &report.failed {
+                            log::warn!("Background fetch from {} failed: {}", remote, e);
+                        }
+                        log::info!("Fetched remotes: {}", report.fetched.join(", "));
+                        state.refresh(cx);
+                    }
+                    Err(e) => {
+                        log::warn!("Background fetch failed: {}", e);
+                        cx.notify();
+                    }
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Check commits fetched for protected branches against the signing policy
+    pub fn check_incoming_signatures(
+        &self,
+        protected_branches: &[String],
+        allowed_signers: &[String],
+    ) -> Result<Vec<SigningViolation>> {
+        self.with_repo(|repo| {
+            SigningViolation::check_incoming(repo, protected_branches, allowed_signers)
+        })
+    }
+
+    // Branch operations
+    pub fn checkout_branch(&mut self, name: &str, cx: &mut Context<Self>) -> Result<()> {
+        self.with_autostash(
+            |repo| {
+                let obj = repo.revparse_single(&format!("refs/heads/{}", name))?;
+                repo.checkout_tree(&obj, None)?;
+                repo.set_head(&format!("refs/heads/{}", name))?;
+                Ok(())
+            },
+            cx,
+        )
+    }
+
+    pub fn checkout_commit(&mut self, sha: &str, cx: &mut Context<Self>) -> Result<()> {
+        self.with_autostash(
+            |repo| {
+                let oid = git2::Oid::from_str(sha)?;
+                let commit = repo.find_commit(oid)?;
+                repo.checkout_tree(&commit.into_object(), None)?;
+                repo.set_head_detached(oid)?;
+                Ok(())
+            },
+            cx,
+        )
+    }
+
+    pub fn create_branch(&mut self, name: &str, cx: &mut Context<Self>) -> Result<()> {
+        BranchInfo::validate_name(name)?;
+        self.with_repo_mut(
+            |repo| {
+                let head = repo.head()?.peel_to_commit()?;
+                repo.branch(name, &head, false)?;
+                Ok(())
+            },
+            cx,
+        )
+    }
+
+    /// Create a branch at any commit, leaving the working tree alone unless
+    /// `checkout` asks to switch to the new branch
+    pub fn create_branch_at(
+        &mut self,
+        name: &str,
+        sha: &str,
+        checkout: bool,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        BranchInfo::validate_name(name)?;
+        self.with_repo_mut(
+            |repo| {
+                let commit = repo.find_commit(git2::Oid::from_str(sha)?)?;
+                repo.branch(name, &commit, false)?;
+                Ok(())
+            },
+            cx,
+        )?;
+        if checkout {
+            self.checkout_branch(name, cx)?;
+        }
+        Ok(())
+    }
+
+    /// Make `branch` track the remote-tracking branch `upstream`, or stop
+    /// tracking with `None`
+    pub fn set_upstream(
+        &mut self,
+        branch: &str,
+        upstream: Option<&str>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        self.with_repo_mut(|repo| BranchInfo::set_upstream(repo, branch, upstream), cx)
+    }
+
+    /// Check out a remote-tracking branch (e.g. "origin/feature") as a local
+    /// branch tracking it, returning the local branch name
+    pub fn checkout_remote_branch(
+        &mut self,
+        remote_branch: &str,
+        cx: &mut Context<Self>,
+    ) -> Result<String> {
+        let local =
+            self.with_repo_mut(|repo| BranchInfo::create_tracking(repo, remote_branch), cx)?;
+        self.checkout_branch(&local, cx)?;
+        Ok(local)
+    }
+
+    /// Create a branch at HEAD and switch to it, e.g. to keep commits made
+    /// on a detached HEAD. The working tree is left as it is.
+    pub fn create_branch_at_head(&mut self, name: &str, cx: &mut Context<Self>) -> Result<()> {
+        BranchInfo::validate_name(name)?;
+        self.with_repo_mut(
+            |repo| {
+                let head = repo.head()?.peel_to_commit()?;
+                repo.branch(name, &head, false)?;
+                repo.set_head(&format!("refs/heads/{}", name))?;
+                Ok(())
+            },
+            cx,
+        )
+    }
+
+    /// Leave a detached HEAD for the branch checked out before it,
+    /// returning the branch name
+    pub fn return_to_previous_branch(&mut self, cx: &mut Context<Self>) -> Result<String> {
+        let branch = self
+            .repository_info
+            .as_ref()
+            .and_then(|info| info.previous_branch.clone())
+            .ok_or_else(|| anyhow::anyhow!("No previous branch to return to"))?;
+        self.checkout_branch(&branch, cx)?;
+        Ok(branch)
+    }
+
+    pub fn delete_branch(&mut self, name: &str, force: bool, cx: &mut Context<Self>) -> Result<()> {
+        let label = format!("Delete branch '{}'", name);
+        self.recording_operation(&label, &[format!("refs/heads/{}", name)], cx, |this, cx| {
+            this.with_repo_mut(
+                |repo| {
+                    let mut branch = repo.find_branch(name, git2::BranchType::Local)?;
+                    if force || !branch.is_head() {
+                        branch.delete()?;
+                    } else {
+                        anyhow::bail!("Cannot delete current branch");
+                    }
+                    Ok(())
+                },
+                cx,
+            )
+        })
+    }
+
+    // Worktree operations
+    pub fn add_worktree(
+        &mut self,
+        path: &Path,
+        branch: &str,
+        create_branch: bool,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        self.with_repo_mut(|repo| WorktreeInfo::add(repo, path, branch, create_branch), cx)
+    }
+
+    pub fn remove_worktree(
+        &mut self,
+        name: &str,
+        force: bool,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        if self
+            .worktrees
+            .iter()
+            .any(|w| w.is_current && w.name.as_deref() == Some(name))
+        {
+            anyhow::bail!("Cannot remove the worktree that is currently open");
+        }
+
+        self.with_repo_mut(|repo| WorktreeInfo::remove(repo, name, force), cx)
+    }
+
+    pub fn create_tag(
+        &mut self,
+        name: &str,
+        sha: &str,
+        message: Option<&str>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        self.with_repo_mut(
+            |repo| {
+                if let Some(msg) = message {
+                    TagInfo::create_annotated(repo, name, Some(sha), msg)?;
+                } else {
+                    TagInfo::create_lightweight(repo, name, Some(sha))?;
+                }
+                Ok(())
+            },
+            cx,
+        )?;
+        // Refresh tag list
+        self.tags = self.with_backend_repo(|backend| backend.tags())?;
+        self.bump_revisions(false, true, false);
+        cx.notify();
+        Ok(())
+    }
+
+    /// Draft release notes for a tag from the commits since the previous tag
+    pub fn release_notes(&self, name: &str, sha: &str) -> Result<String> {
+        self.with_repo(|repo| TagInfo::release_notes(repo, name, sha))
+    }
+
+    pub fn delete_tag(&mut self, name: &str, cx: &mut Context<Self>) -> Result<()> {
+        self.with_repo_mut(
+            |repo| {
+                TagInfo::delete(repo, name)?;
+                Ok(())
+            },
+            cx,
+        )?;
+        // Refresh tag list
+        self.tags = self.with_backend_repo(|backend| backend.tags())?;
+        self.bump_revisions(false, true, false);
+        cx.notify();
+        Ok(())
+    }
+
+    // Stash operations
+    pub fn stash_save(
+        &mut self,
+        message: Option<&str>,
+        flags: git2::StashFlags,
+        paths: &[String],
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        if self.path.is_some() {
+            // Refresh stash list and files
+            self.stashes = self.with_repo_handle_mut(|repo| {
+                StashEntry::save(repo, message, flags, paths)?;
+                StashEntry::get_all(repo)
+            })?;
+            self.files = self.with_backend_repo(|backend| backend.status())?;
+            self.prune_selection();
+            self.bump_revisions(true, false, false);
+            cx.notify();
+        }
+        Ok(())
+    }
+
+    pub fn stash_pop(&mut self, index: usize, cx: &mut Context<Self>) -> Result<()> {
+        if self.path.is_some() {
+            // Refresh stash list and files
+            self.stashes = self.with_repo_handle_mut(|repo| {
+                StashEntry::pop(repo, index)?;
+                StashEntry::get_all(repo)
+            })?;
+            self.files = self.with_backend_repo(|backend| backend.status())?;
+            self.bump_revisions(true, false, false);
+            cx.notify();
+        }
+        Ok(())
+    }
+
+    pub fn stash_apply(&mut self, index: usize, cx: &mut Context<Self>) -> Result<()> {
+        if self.path.is_some() {
+            self.with_repo_handle_mut(|repo| StashEntry::apply(repo, index))?;
+            // Refresh files (stash list stays the same)
+            self.files = self.with_backend_repo(|backend| backend.status())?;
+            self.bump_revisions(true, false, false);
+            cx.notify();
+        }
+        Ok(())
+    }
+
+    pub fn stash_drop(&mut self, index: usize, cx: &mut Context<Self>) -> Result<()> {
+        if self.path.is_some() {
+            // Refresh stash list
+            self.stashes = self.with_repo_handle_mut(|repo| {
+                StashEntry::drop(repo, index)?;
+                StashEntry::get_all(repo)
+            })?;
+            cx.notify();
+        }
+        Ok(())
+    }
+
+    /// Ask which branch to apply a stash onto
+    pub fn request_stash_onto_branch(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(stash) = self.stashes.iter().find(|s| s.index == index) else {
+            return;
+        };
+        self.pending_stash_branch = Some(StashBranchRequest {
+            index,
+            message: stash.message.clone(),
+            branch: None,
+            keep_stash: false,
+            error: None,
+        });
+        cx.notify();
+    }
+
+    pub fn set_stash_branch_target(&mut self, branch: &str, cx: &mut Context<Self>) {
+        if let Some(request) = self.pending_stash_branch.as_mut() {
+            request.branch = Some(branch.to_string());
+            request.error = None;
+            cx.notify();
+        }
+    }
+
+    pub fn set_stash_branch_keep(&mut self, keep_stash: bool, cx: &mut Context<Self>) {
+        if let Some(request) = self.pending_stash_branch.as_mut() {
+            request.keep_stash = keep_stash;
+            cx.notify();
+        }
+    }
+
+    pub fn cancel_stash_branch(&mut self, cx: &mut Context<Self>) {
+        self.pending_stash_branch = None;
+        cx.notify();
+    }
+
+    /// Check out the chosen branch and apply the pending stash there.
+    /// Returns whether the apply stopped on conflicts.
+    ///
+    /// If it fails the request stays open with the error.
+    pub fn confirm_stash_branch(&mut self, cx: &mut Context<Self>) -> Result<bool> {
+        let Some(mut request) = self.pending_stash_branch.take() else {
+            return Ok(false);
+        };
+        let Some(branch) = request.branch.clone() else {
+            self.pending_stash_branch = Some(request);
+            return Ok(false);
+        };
+
+        let (index, keep_stash) = (request.index, request.keep_stash);
+        let result = self
+            .path
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No repository open"))
+            .and_then(|path| {
+                let mut repo = self.open_git2(&path)?;
+                StashEntry::apply_onto_branch(&mut repo, index, &branch, keep_stash)
+            });
+        if let Err(e) = &result {
+            request.error = Some(e.to_string());
+            self.pending_stash_branch = Some(request);
+        }
+        self.refresh(cx);
+        result
+    }
+
+    // Selection
+    pub fn toggle_file_selection(&mut self, path: &str, cx: &mut Context<Self>) {
+        if let Some(pos) = self.selected_files.iter().position(|p| p == path) {
+            self.selected_files.remove(pos);
+        } else {
+            self.selected_files.push(path.to_string());
+        }
+        self.bump_revisions(true, false, false);
+        cx.notify();
+    }
+
+    /// Add files to the selection, e.g. a shift-clicked range
+    pub fn select_files(&mut self, paths: &[String], cx: &mut Context<Self>) {
+        for path in paths {
+            if !self.is_file_selected(path) {
+                self.selected_files.push(path.clone());
+            }
+        }
+        self.bump_revisions(true, false, false);
+        cx.notify();
+    }
+
+    pub fn select_all_files(&mut self, cx: &mut Context<Self>) {
+        self.selected_files = self.files.iter().map(|f| f.path.clone()).collect();
+        self.bump_revisions(true, false, false);
+        cx.notify();
+    }
+
+    pub fn deselect_all_files(&mut self, cx: &mut Context<Self>) {
+        self.selected_files.clear();
+        self.bump_revisions(true, false, false);
+        cx.notify();
+    }
+
+    pub fn is_file_selected(&self, path: &str) -> bool {
+        self.selected_files.iter().any(|p| p == path)
+    }
+
+    /// Drop selected paths that no longer have changes
+    fn prune_selection(&mut self) {
+        let files = &self.files;
+        self.selected_files.retain(|p| files.iter().any(|f| &f.path == p));
+    }
+
+    pub fn set_selected_commit(&mut self, commit: Option<CommitInfo>, cx: &mut Context<Self>) {
+        self.selected_commit = commit;
+        cx.notify();
+    }
+
+    pub fn set_current_diff(&mut self, diff: Option<FileDiff>, cx: &mut Context<Self>) {
+        self.show_diff(diff);
+        cx.notify();
+    }
+
+    /// Make `diff` the current diff, rendering its first page of lines
+    fn show_diff(&mut self, diff: Option<FileDiff>) {
+        self.diff_files.clear();
+        self.diff_file_index = 0;
+        self.diff_source = None;
+        self.show_diff_file(diff);
+    }
+
+    /// Switch the current diff without leaving the files it belongs to
+    fn show_diff_file(&mut self, diff: Option<FileDiff>) {
+        self.current_diff = diff;
+        self.diff_hunk = None;
+        self.diff_line_limit = DIFF_PAGE_LINES;
+        self.hex_preview = None;
+    }
+
+    /// Show one of several files changed together, such as by a commit or
+    /// between two revisions, so the diff viewer can step between them
+    fn show_diff_files(&mut self, files: Vec<FileDiff>, index: usize, source: DiffSource) {
+        let index = index.min(files.len().saturating_sub(1));
+        self.show_diff_file(files.get(index).cloned());
+        self.diff_files = files;
+        self.diff_file_index = index;
+        self.diff_source = Some(source);
+    }
+
+    /// Show a file of a comparison, with its other files a step away
+    pub fn show_comparison_diff(
+        &mut self,
+        comparison: &Comparison,
+        index: usize,
+        cx: &mut Context<Self>,
+    ) {
+        let source = DiffSource::Comparison {
+            base: comparison.base.clone(),
+            head: comparison.head.clone(),
+            mode: comparison.mode,
+        };
+        self.show_diff_files(comparison.files.clone(), index, source);
+        cx.notify();
+    }
+
+    /// Compute diffs with other whitespace and context options, reloading
+    /// the current diff
+    pub fn set_diff_options(&mut self, options: DiffDisplayOptions, cx: &mut Context<Self>) {
+        if self.diff_options == options {
+            return;
+        }
+        self.diff_options = options;
+        // Previews were computed with the old options
+        self.hunk_previews.clear();
+        if let Err(e) = self.reload_diff() {
+            log::warn!("Failed to reload the diff: {}", e);
+        }
+        cx.notify();
+    }
+
+    /// Load the current diff again, staying on the same file
+    fn reload_diff(&mut self) -> Result<()> {
+        let Some(source) = self.diff_source.clone() else {
+            return Ok(());
+        };
+        let (max_size, options) = (self.max_diff_size, self.diff_options);
+        let files = match &source {
+            DiffSource::WorkingTree(path) => {
+                vec![self.with_backend_repo(|backend| backend.file_diff(path, max_size, options))?]
+            }
+            DiffSource::Unstaged(path) => vec![self.working_diff(path)?],
+            DiffSource::Commit(sha) => {
+                self.with_backend_repo(|backend| backend.commit_diff(sha, max_size, options))?
+            }
+            DiffSource::Comparison { base, head, mode } => {
+                // Only the files are needed, not the commits on each side
+                let comparison = self.with_repo(|repo| {
+                    Comparison::get(repo, base, head, *mode, 0, max_size, options)
+                })?;
+                comparison.files
+            }
+        };
+
+        let path = self.current_diff.as_ref().map(|diff| diff.path.clone());
+        let index = files
+            .iter()
+            .position(|file| Some(&file.path) == path.as_ref())
+            .unwrap_or(0);
+        if self.diff_files.is_empty() {
+            self.show_diff_file(files.into_iter().next());
+        } else {
+            self.show_diff_files(files, index, source);
+        }
+        Ok(())
+    }
+
+    /// Show another of the files the current diff belongs to
+    pub fn select_diff_file(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(diff) = self.diff_files.get(index).cloned() {
+            self.diff_file_index = index;
+            self.show_diff_file(Some(diff));
+            cx.notify();
+        }
+    }
+
+    /// Show the next or previous file, returning false at either end
+    pub fn step_diff_file(&mut self, forward: bool, cx: &mut Context<Self>) -> bool {
+        let index = if forward {
+            self.diff_file_index + 1
+        } else {
+            match self.diff_file_index.checked_sub(1) {
+                Some(index) => index,
+                None => return false,
+            }
+        };
+        if index >= self.diff_files.len() {
+            return false;
+        }
+        self.select_diff_file(index, cx);
+        true
+    }
+
+    /// Jump to the next or previous hunk of the current diff, carrying on
+    /// into the neighbouring file at either end. Returns the line to scroll
+    /// to, after rendering enough of the diff to include it.
+    pub fn step_diff_hunk(&mut self, forward: bool, cx: &mut Context<Self>) -> Option<usize> {
+        let hunks = self.current_diff.as_ref()?.hunk_starts();
+        let target = if forward {
+            self.diff_hunk.map_or(0, |hunk| hunk + 1)
+        } else {
+            // Nothing comes before the first hunk in this file
+            self.diff_hunk.and_then(|hunk| hunk.checked_sub(1)).unwrap_or(usize::MAX)
+        };
+
+        let (hunk, line) = match hunks.get(target) {
+            Some(&line) => (Some(target), line),
+            None => {
+                if !self.step_diff_file(forward, cx) {
+                    return None;
+                }
+                let hunks = self.current_diff.as_ref()?.hunk_starts();
+                let hunk = if forward { 0 } else { hunks.len().saturating_sub(1) };
+                // Binary and oversized files have no hunks to land on
+                match hunks.get(hunk) {
+                    Some(&line) => (Some(hunk), line),
+                    None => (None, 0),
+                }
+            }
+        };
+        self.diff_hunk = hunk;
+        if line >= self.diff_line_limit {
+            self.diff_line_limit = (line / DIFF_PAGE_LINES + 1) * DIFF_PAGE_LINES;
+        }
+        cx.notify();
+        Some(line)
+    }
+
+    /// Show or hide the start of both sides of the current binary diff as
+    /// hex
+    pub fn toggle_hex_preview(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        if self.hex_preview.take().is_none() {
+            let Some(diff) = &self.current_diff else {
+                return Ok(());
+            };
+            let preview = self.with_repo(|repo| {
+                let old = diff.read_side(repo, false, git::HEX_PREVIEW_BYTES)?;
+                let new = diff.read_side(repo, true, git::HEX_PREVIEW_BYTES)?;
+                Ok(HexPreview::new(&old, &new))
+            })?;
+            self.hex_preview = Some(preview);
+        }
+        cx.notify();
+        Ok(())
+    }
+
+    /// Render the next page of lines of the current diff
+    pub fn show_more_diff_lines(&mut self, cx: &mut Context<Self>) {
+        self.diff_line_limit += DIFF_PAGE_LINES;
+        cx.notify();
+    }
+
+    /// First hunk of `path`'s staged or unstaged changes. It's loaded on a
+    /// background thread the first time it's asked for, then cached until
+    /// the status is next refreshed.
+    pub fn hunk_preview(
+        &mut self,
+        path: &str,
+        staged: bool,
+        cx: &mut Context<Self>,
+    ) -> HunkPreview {
+        let key = (path.to_string(), staged);
+        if let Some(preview) = self.hunk_previews.get(&key) {
+            return preview.clone();
+        }
+        let Some(repo_path) = self.path.clone() else {
+            return HunkPreview::Unavailable;
+        };
+
+        // Untracked files have no unstaged diff against the index
+        let untracked = self
+            .files
+            .iter()
+            .any(|f| f.path == path && f.status == git::FileStatusType::Untracked);
+        let backend = self.backend.clone();
+        let file = path.to_string();
+        let (max_size, options) = (self.max_diff_size, self.diff_options);
+        self.hunk_previews.insert(key.clone(), HunkPreview::Loading);
+
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    let repo = backend.open(&repo_path)?;
+                    if staged || untracked {
+                        repo.file_diff(&file, max_size, options)
+                    } else {
+                        repo.unstaged_diff(&file, max_size, options)
+                    }
+                })
+                .await;
+
+            this.update(cx, |state, cx| {
+                // A refresh while loading already dropped the entry
+                if !state.hunk_previews.contains_key(&key) {
+                    return;
+                }
+                let preview = match result {
+                    Ok(diff) if !diff.first_hunk().is_empty() => {
+                        let hunk = diff.first_hunk();
+                        HunkPreview::Ready(hunk[..hunk.len().min(HUNK_PREVIEW_LINES)].to_vec())
+                    }
+                    Ok(_) => HunkPreview::Unavailable,
+                    Err(e) => {
+                        log::warn!("Failed to load hunk preview for {}: {}", key.0, e);
+                        HunkPreview::Unavailable
+                    }
+                };
+                state.hunk_previews.insert(key, preview);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+
+        HunkPreview::Loading
+    }
+
+    /// Signature checks of `shas`, verifying the ones not seen yet with one
+    /// `git log` in the background. Observers are notified once it finishes.
+    pub fn signature_checks(
+        &mut self,
+        shas: &[String],
+        cx: &mut Context<Self>,
+    ) -> Vec<SignatureCheck> {
+        let unchecked: Vec<String> = shas
+            .iter()
+            .filter(|sha| !self.signature_checks.contains_key(*sha))
+            .cloned()
+            .collect();
+        if !unchecked.is_empty() {
+            self.verify_signatures(unchecked, cx);
+        }
+
+        shas.iter()
+            .map(|sha| {
+                self.signature_checks
+                    .get(sha)
+                    .cloned()
+                    .unwrap_or(SignatureCheck::Unavailable)
+            })
+            .collect()
+    }
+
+    fn verify_signatures(&mut self, unchecked: Vec<String>, cx: &mut Context<Self>) {
+        let Some(repo_path) = self.path.clone() else {
+            return;
+        };
+        for sha in &unchecked {
+            self.signature_checks.insert(sha.clone(), SignatureCheck::Loading);
+        }
+
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_executor()
+                .spawn({
+                    let unchecked = unchecked.clone();
+                    async move { CommitSignature::verify(&repo_path, &unchecked) }
+                })
+                .await;
+
+            this.update(cx, |state, cx| {
+                let mut verified: HashMap<String, CommitSignature> = match result {
+                    Ok(signatures) => signatures
+                        .into_iter()
+                        .map(|signature| (signature.sha.clone(), signature))
+                        .collect(),
+                    Err(e) => {
+                        log::warn!("Failed to verify commit signatures: {}", e);
+                        HashMap::new()
+                    }
+                };
+                for sha in unchecked {
+                    // Closing the repository while verifying dropped the entry
+                    let Some(check) = state.signature_checks.get_mut(&sha) else {
+                        continue;
+                    };
+                    *check = match verified.remove(&sha) {
+                        Some(signature) => SignatureCheck::Ready(signature),
+                        None => SignatureCheck::Unavailable,
+                    };
+                }
+                state.bump_revisions(false, false, true);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    pub fn load_file_diff(&mut self, path: &str, cx: &mut Context<Self>) -> Result<()> {
+        let (max_size, options) = (self.max_diff_size, self.diff_options);
+        let diff = self.with_backend_repo(|backend| backend.file_diff(path, max_size, options))?;
+        self.show_diff(Some(diff));
+        self.diff_source = Some(DiffSource::WorkingTree(path.to_string()));
+        cx.notify();
+        Ok(())
+    }
+
+    /// Load only the unstaged changes of a file, so lines can be staged or
+    /// discarded from the diff. Untracked files show their full content.
+    pub fn load_unstaged_diff(&mut self, path: &str, cx: &mut Context<Self>) -> Result<()> {
+        let diff = self.working_diff(path)?;
+        self.show_diff(Some(diff));
+        self.diff_source = Some(DiffSource::Unstaged(path.to_string()));
+        cx.notify();
+        Ok(())
+    }
+
+    /// Whether the current diff shows only the unstaged changes of a file
+    pub fn diff_is_unstaged(&self) -> bool {
+        matches!(self.diff_source, Some(DiffSource::Unstaged(_)))
+    }
+
+    /// Unstaged changes of a file, or its full content if untracked, without
+    /// making it the current diff
+    pub fn working_diff(&self, path: &str) -> Result<FileDiff> {
+        let untracked = self
+            .files
+            .iter()
+            .any(|f| f.path == path && f.status == git::FileStatusType::Untracked);
+        let (max_size, options) = (self.max_diff_size, self.diff_options);
+        if untracked {
+            self.with_backend_repo(|backend| backend.file_diff(path, max_size, options))
+        } else {
+            self.with_backend_repo(|backend| backend.unstaged_diff(path, max_size, options))
+        }
+    }
+
+    /// Stage or discard lines of the current unstaged diff, then reload it.
+    /// Discarded lines are backed up to the trash when enabled.
+    pub fn apply_diff_lines(
+        &mut self,
+        lines: &[DiffLineId],
+        action: LineAction,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let path = match &self.current_diff {
+            Some(diff) if diff.unstaged => diff.path.clone(),
+            _ => anyhow::bail!("Lines can only be staged from an unstaged diff"),
+        };
+
+        if action == LineAction::Discard && self.trash_discards {
+            let label = format!("Discard lines in {}", path);
+            self.with_repo(|repo| TrashEntry::backup(repo, &label, std::slice::from_ref(&path)))?;
+        }
+        self.with_repo_mut(|repo| FileDiff::apply_lines(repo, &path, lines, action), cx)?;
+
+        // The file has no unstaged changes left once every line is handled
+        let (max_size, options) = (self.max_diff_size, self.diff_options);
+        self.current_diff = self
+            .with_backend_repo(|backend| backend.unstaged_diff(&path, max_size, options))
+            .ok();
+        cx.notify();
+        Ok(())
+    }
+
+    pub fn clear_diff(&mut self, cx: &mut Context<Self>) {
+        self.current_diff = None;
+        self.diff_files.clear();
+        self.diff_source = None;
+        cx.notify();
+    }
+
+    // Load more commits
+    pub fn load_more_commits(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        if self.path.is_some() {
+            let current_count = self.commits.as_ref().map(|c| c.nodes.len()).unwrap_or(0);
+            let (filter, refs) = (&self.graph_filter, &self.ref_maps);
+            let more_commits = self.with_backend_repo(|backend| {
+                backend.commit_graph(filter, 100, current_count, refs)
+            })?;
+
+            if let Some(ref mut commits) = self.commits {
+                commits.append(more_commits);
+            }
+            self.bump_revisions(false, false, true);
+            cx.notify();
+        }
+        Ok(())
+    }
+
+    /// Reload the graph showing only commits that match `filter`. The
+    /// previous filter is kept if the new one can't be applied, e.g. an
+    /// unknown branch.
+    pub fn set_graph_filter(&mut self, filter: GraphFilter, cx: &mut Context<Self>) -> Result<()> {
+        if self.path.is_some() {
+            let refs = &self.ref_maps;
+            let commits =
+                self.with_backend_repo(|backend| backend.commit_graph(&filter, 100, 0, refs))?;
+            self.commits = Some(commits);
+            self.focused_commit = None;
+        }
+        let author_changed = filter.author != self.graph_filter.author;
+        self.graph_filter = filter;
+        if author_changed {
+            self.load_author_summary(cx);
+        }
+        self.bump_revisions(false, false, true);
+        cx.notify();
+        Ok(())
+    }
+
+    /// Show only the commits of one author, keeping the rest of the filter
+    pub fn filter_by_author(&mut self, author: &str, cx: &mut Context<Self>) -> Result<()> {
+        let filter = GraphFilter {
+            author: Some(author.to_string()),
+            ..self.graph_filter.clone()
+        };
+        self.set_graph_filter(filter, cx)
+    }
+
+    /// Read the commit count and most touched files of the filtered author
+    fn load_author_summary(&mut self, cx: &mut Context<Self>) {
+        self.author_summary = None;
+        let (Some(path), Some(author)) = (self.path.clone(), self.graph_filter.author.clone())
+        else {
+            return;
+        };
+
+        cx.spawn(async move |this, cx| {
+            let query = author.clone();
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    let repo = git2::Repository::open(&path)?;
+                    AuthorSummary::read(&repo, &query, AUTHOR_TOP_PATHS)
+                })
+                .await;
+
+            this.update(cx, |state, cx| {
+                // The filter moved on to another author meanwhile
+                if state.graph_filter.author.as_ref() != Some(&author) {
+                    return;
+                }
+                match result {
+                    Ok(summary) => state.author_summary = Some(summary),
+                    Err(e) => log::warn!("Failed to summarize author {}: {}", author, e),
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Scroll the graph to a commit and highlight it, loading more history
+    /// until the commit is in the graph
+    pub fn focus_commit(&mut self, sha: &str, cx: &mut Context<Self>) -> Result<()> {
+        loop {
+            let Some(commits) = &self.commits else {
+                anyhow::bail!("No commit history loaded");
+            };
+            if commits.nodes.iter().any(|node| node.commit.sha == sha) {
+                break;
+            }
+
+            let loaded = commits.nodes.len();
+            if loaded >= FOCUS_COMMIT_LOAD_LIMIT {
+                anyhow::bail!("Commit is not within the first {} commits", loaded);
+            }
+            self.load_more_commits(cx)?;
+            if self.commits.as_ref().map_or(0, |c| c.nodes.len()) == loaded {
+                anyhow::bail!("Commit is not reachable from the graph");
+            }
+        }
+
+        self.focused_commit = Some(sha.to_string());
+        self.bump_revisions(false, false, true);
+        cx.notify();
+        Ok(())
+    }
+
+    /// Select a commit, such as a search result, and focus it in the graph
+    pub fn select_commit(&mut self, commit: CommitInfo, cx: &mut Context<Self>) -> Result<()> {
+        self.focus_commit(&commit.sha, cx)?;
+        self.selected_commit = Some(commit);
+        cx.notify();
+        Ok(())
+    }
+
+    /// Select a commit by SHA, such as the one selected when the app last
+    /// quit, and focus it in the graph
+    pub fn select_commit_by_sha(&mut self, sha: &str, cx: &mut Context<Self>) -> Result<()> {
+        self.focus_commit(sha, cx)?;
+        self.selected_commit = self
+            .commits
+            .as_ref()
+            .and_then(|commits| commits.nodes.iter().find(|node| node.commit.sha == sha))
+            .map(|node| node.commit.clone());
+        cx.notify();
+        Ok(())
+    }
+
+    /// Focus the commit a branch, tag or (abbreviated) SHA points to
+    pub fn focus_ref(&mut self, name: &str, cx: &mut Context<Self>) -> Result<()> {
+        let sha = self
+            .with_repo(|repo| {
+                let commit = repo.revparse_single(name)?.peel_to_commit()?;
+                Ok(commit.id().to_string())
+            })
+            .map_err(|_| anyhow::anyhow!("No branch, tag or commit named '{}'", name))?;
+        self.focus_commit(&sha, cx)
+    }
+
+    fn bump_revisions(&mut self, status: bool, refs: bool, graph: bool) {
+        self.revisions.status += status as u64;
+        self.revisions.refs += refs as u64;
+        self.revisions.graph += graph as u64;
+    }
+
+    // Getters
+    pub fn revisions(&self) -> Revisions {
+        self.revisions
+    }
+
+    pub fn staged_files(&self) -> Vec<&FileStatus> {
+        self.files.iter().filter(|f| f.staged).collect()
+    }
+
+    pub fn unstaged_files(&self) -> Vec<&FileStatus> {
+        self.files.iter().filter(|f| !f.staged).collect()
+    }
+
+    pub fn is_detached(&self) -> bool {
+        self.repository_info
+            .as_ref()
+            .map(|r| r.is_detached)
+            .unwrap_or(false)
+    }
+
+    /// Branch name, or "detached HEAD" when no branch is checked out
+    pub fn head_label(&self) -> Option<String> {
+        if self.is_detached() {
+            Some("detached HEAD".to_string())
+        } else {
+            self.current_branch().map(|s| s.to_string())
+        }
+    }
+
+    pub fn has_local_branch(&self, name: &str) -> bool {
+        self.branches
+            .iter()
+            .any(|b| b.branch_type == BranchKind::Local && b.name == name)
+    }
+
+    pub fn current_branch(&self) -> Option<&str> {
+        self.repository_info
+            .as_ref()
+            .and_then(|r| r.current_branch.as_deref())
+    }
+
+    /// Get the message of the last commit (for amend)
+    pub fn get_last_commit_message(&self) -> Option<String> {
+        self.with_repo(|repo| {
+            let head = repo.head()?;
+            let commit = head.peel_to_commit()?;
+            Ok(commit.message().map(|s| s.to_string()))
+        })
+        .ok()
+        .flatten()
+    }
+
+    /// Describe a failed operation for a bug report
+    pub fn error_details(&self, operation: &str, error: &anyhow::Error) -> ErrorDetails {
+        ErrorDetails::new(operation, self.path.as_deref(), error)
+    }
+
+    /// Whether HEAD has already been pushed to a remote, so amending or
+    /// rewriting it would diverge from what others have
+    pub fn is_head_pushed(&self) -> bool {
+        self.with_repo(|repo| {
+            let head = repo.head()?.peel_to_commit()?;
+            git::is_pushed(repo, head.id())
+        })
+        .unwrap_or(false)
+    }
+
+    /// Resolve a revision to its full SHA and summary line
+    pub fn resolve_commit(&self, rev: &str) -> Result<(String, String)> {
+        self.with_repo(|repo| {
+            let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+            let summary = commit.summary().unwrap_or_default().to_string();
+            Ok((commit.id().to_string(), summary))
+        })
+    }
+
+    /// Web URL of the primary remote, if it points at a known hosting service
+    pub fn remote_web_url(&self) -> Option<RemoteWebUrl> {
+        self.repository_info
+            .as_ref()?
+            .remote_url
+            .as_deref()
+            .and_then(RemoteWebUrl::parse)
+    }
+
+    /// Link to a commit on the primary remote's hosting service
+    pub fn commit_web_url(&self, sha: &str) -> Option<String> {
+        Some(self.remote_web_url()?.commit_url(sha))
+    }
+
+    /// Link to the open pull request of `branch`, or else to the page
+    /// creating one against the remote's default branch
+    pub fn pull_request_url(&self, branch: &str) -> Result<String> {
+        if let Some(pull_request) = self.pull_requests.get(branch) {
+            return Ok(pull_request.url.clone());
+        }
+        let web_url = self
+            .remote_web_url()
+            .ok_or_else(|| anyhow::anyhow!("Remote URL is not a recognized hosting service"))?;
+        let base = self
+            .with_repo(|repo| Ok(git::remote::default_branch(repo, &self.remote_name)))?
+            .filter(|base| base != branch);
+        Ok(web_url.compare_url(branch, base.as_deref()))
+    }
+
+    /// Use a new API token, looking the pull requests up again
+    pub fn set_hosting_token(&mut self, token: Option<String>, cx: &mut Context<Self>) {
+        if self.hosting_token == token {
+            return;
+        }
+        self.hosting_token = token;
+        self.pull_requests.clear();
+        self.bump_revisions(false, true, true);
+        self.load_pull_requests(cx);
+        cx.notify();
+    }
+
+    /// Look up the open pull requests of the primary remote in the
+    /// background, if an API token is configured
+    pub fn load_pull_requests(&mut self, cx: &mut Context<Self>) {
+        let (Some(web_url), Some(token)) = (self.remote_web_url(), self.hosting_token.clone())
+        else {
+            return;
+        };
+        if web_url.pull_requests_api_url().is_none() {
+            return;
+        }
+        let path = self.path.clone();
+
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move { web_url.fetch_pull_requests(&token) })
+                .await;
+
+            this.update(cx, |state, cx| {
+                // Another repository was opened meanwhile
+                if state.path != path {
+                    return;
+                }
+                match result {
+                    Ok(pull_requests) => {
+                        state.pull_requests = pull_requests
+                            .into_iter()
+                            .map(|pull_request| (pull_request.branch.clone(), pull_request))
+                            .collect();
+                        // Badges are drawn in the graph
+                        state.bump_revisions(false, true, true);
+                        cx.notify();
+                    }
+                    Err(e) => log::warn!("Failed to load pull requests: {:#}", e),
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Open a file of the working tree at a line in the configured editor,
+    /// or with the system's default application when none is set
+    pub fn open_in_editor(&self, path: &str, line: Option<u32>, cx: &mut App) -> Result<()> {
+        let repo_path = self
+            .path
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+        let file = repo_path.join(path);
+        match self.editor_command.as_deref() {
+            Some(template) => open_in_editor(template, &file, line),
+            None => {
+                cx.open_with_system(&file);
+                Ok(())
+            }
+        }
+    }
+
+    /// Show a file of the working tree in the system file manager
+    pub fn reveal_in_file_manager(&self, path: &str) -> Result<()> {
+        let repo_path = self
+            .path
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+        reveal_in_file_manager(&repo_path.join(path))
+    }
+
+    /// Names of the configured remotes
+    pub fn remote_names(&self) -> Vec<String> {
+        self.with_repo(git::remote::RemoteInfo::get_all)
+            .map(|remotes| remotes.into_iter().map(|r| r.name).collect())
+            .unwrap_or_default()
+    }
+
+    /// Build a link to `path` (and optionally a line range) pinned to the
+    /// HEAD commit
+    pub fn permalink(&self, path: &str, lines: Option<RangeInclusive<u32>>) -> Result<String> {
+        let web_url = self
+            .remote_web_url()
+            .ok_or_else(|| anyhow::anyhow!("Remote URL is not a recognized hosting service"))?;
+        let sha = self.with_repo(|repo| Ok(repo.head()?.peel_to_commit()?.id().to_string()))?;
+        Ok(web_url.permalink(&sha, path, lines))
+    }
+
+    // Conflict resolution
+
+    /// Resolve all conflicts with one strategy. Returns the number of files
+    /// the strategy could not resolve, which stay conflicted.
+    pub fn resolve_all_conflicts(
+        &mut self,
+        strategy: ConflictStrategy,
+        cx: &mut Context<Self>,
+    ) -> Result<usize> {
+        let was_stash_conflict = self.is_stash_conflict();
+        let skipped =
+            self.with_repo_mut(|repo| ConflictInfo::resolve_all(repo, strategy), cx)?;
+
+        if was_stash_conflict && self.conflict_info.is_none() {
+            self.complete_stash_apply(cx)?;
+        }
+        Ok(skipped)
+    }
+
+    pub fn resolve_conflicts_per_file(
+        &mut self,
+        resolutions: Vec<(String, ConflictStrategy)>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let was_stash_conflict = self.is_stash_conflict();
+        self.with_repo_mut(
+            |repo| {
+                let mut index = repo.index()?;
+                let conflicts: Vec<_> = index.conflicts()?.collect::<Result<Vec<_>, _>>()?;
+
+                for (path, strategy) in resolutions {
+                    if let Some(conflict) = conflicts.iter().find(|c| {
+                        c.our
+                            .as_ref()
+                            .or(c.their.as_ref())
+                            .and_then(|e| std::str::from_utf8(&e.path).ok())
+                            == Some(&path)
+                    }) {
+                        ConflictInfo::resolve_file(repo, &mut index, &path, conflict, strategy)?;
+                    }
+                }
+                index.write()?;
+                Ok(())
+            },
+            cx,
+        )?;
+
+        if was_stash_conflict && self.conflict_info.is_none() {
+            self.complete_stash_apply(cx)?;
+        }
+        Ok(())
+    }
+
+    /// Split a conflicted file in the working tree into conflict hunks
+    pub fn conflict_document(&self, path: &str) -> Result<ConflictDocument> {
+        self.with_repo(|repo| {
+            let workdir = repo.workdir().ok_or_else(|| anyhow::anyhow!("No workdir"))?;
+            let content = std::fs::read(workdir.join(path))?;
+            let content = String::from_utf8(content)
+                .map_err(|_| anyhow::anyhow!("{} is not a text file", path))?;
+            ConflictDocument::parse(&content)
+        })
+    }
+
+    /// Resolve a conflicted file with content merged in the conflict editor
+    pub fn resolve_conflict_with_content(
+        &mut self,
+        path: &str,
+        content: &str,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let was_stash_conflict = self.is_stash_conflict();
+        self.with_repo_mut(|repo| ConflictInfo::resolve_with_content(repo, path, content), cx)?;
+
+        if was_stash_conflict && self.conflict_info.is_none() {
+            self.complete_stash_apply(cx)?;
+        }
+        Ok(())
+    }
+
+    /// Unstage the resolved stash changes and drop the autostash
+    fn complete_stash_apply(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        if let Some(path) = &self.path {
+            let mut repo = self.open_git2(path)?;
+            ConflictInfo::complete_stash_apply(&mut repo)?;
+        }
+        self.refresh(cx);
+        Ok(())
+    }
+
+    pub fn complete_merge(
+        &mut self,
+        message: Option<&str>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        self.with_repo_mut(
+            |repo| {
+                ConflictInfo::complete_merge(repo, message)?;
+                Ok(())
+            },
+            cx,
+        )
+    }
+
+    /// Merge a commit into HEAD using `merge_mode`. Returns whether the
+    /// merge stopped on conflicts, which then show up in `conflict_info`.
+    pub fn merge_commit(&mut self, sha: &str, cx: &mut Context<Self>) -> Result<bool> {
+        self.check_merge_signatures(sha)?;
+        let mode = self.merge_mode;
+        self.with_repo_mut(
+            |repo| {
+                let oid = git2::Oid::from_str(sha)?;
+                // Name the merge after a branch at the commit when there is one
+                let label = repo
+                    .branches(Some(git2::BranchType::Local))?
+                    .filter_map(|b| b.ok())
+                    .find(|(b, _)| !b.is_head() && b.get().target() == Some(oid))
+                    .and_then(|(b, _)| b.name().ok().flatten().map(|n| format!("branch '{}'", n)))
+                    .unwrap_or_else(|| format!("commit '{}'", &sha[..sha.len().min(7)]));
+                MergeMode::merge_commit(repo, oid, &label, mode)
+            },
+            cx,
+        )
+    }
+
+    /// Merge a local branch into HEAD. Returns whether the merge stopped
+    /// on conflicts.
+    pub fn merge_branch(
+        &mut self,
+        name: &str,
+        mode: MergeMode,
+        cx: &mut Context<Self>,
+    ) -> Result<bool> {
+        self.check_merge_signatures(&format!("refs/heads/{}", name))?;
+        self.with_repo_mut(|repo| MergeMode::merge_branch(repo, name, mode), cx)
+    }
+
+    /// Refuse a merge of `incoming` into a protected branch when the signing
+    /// policy blocks commits it would bring in
+    fn check_merge_signatures(&self, incoming: &str) -> Result<()> {
+        if self.signing_policy != SigningPolicy::Block {
+            return Ok(());
+        }
+        let violations = self.with_repo(|repo| {
+            SigningViolation::check_merge(
+                repo,
+                incoming,
+                &self.protected_branches,
+                &self.allowed_signers,
+            )
+        })?;
+        if !violations.is_empty() {
+            anyhow::bail!("Merge blocked: {}", SigningViolation::describe(&violations));
+        }
+        Ok(())
+    }
+
+    /// Hold a merge of `branch` into HEAD for confirmation, starting from
+    /// the configured merge mode
+    pub fn request_merge_branch(&mut self, branch: &str, cx: &mut Context<Self>) {
+        self.pending_merge = Some(MergeRequest {
+            branch: branch.to_string(),
+            mode: self.merge_mode,
+            error: None,
+        });
+        cx.notify();
+    }
+
+    pub fn set_merge_request_mode(&mut self, mode: MergeMode, cx: &mut Context<Self>) {
+        if let Some(request) = self.pending_merge.as_mut() {
+            request.mode = mode;
+            request.error = None;
+            cx.notify();
+        }
+    }
+
+    pub fn cancel_merge(&mut self, cx: &mut Context<Self>) {
+        self.pending_merge = None;
+        cx.notify();
+    }
+
+    /// Run the pending merge. Returns whether it stopped on conflicts.
+    ///
+    /// If the merge fails the request stays open with the error.
+    pub fn confirm_merge(&mut self, cx: &mut Context<Self>) -> Result<bool> {
+        let Some(mut request) = self.pending_merge.take() else {
+            return Ok(false);
+        };
+
+        let result = self.merge_branch(&request.branch, request.mode, cx);
+        if let Err(e) = &result {
+            request.error = Some(e.to_string());
+            self.pending_merge = Some(request);
+        }
+        cx.notify();
+        result
+    }
+
+    pub fn abort_merge(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        self.with_repo_mut(
+            |repo| {
+                ConflictInfo::abort_merge(repo)?;
+                Ok(())
+            },
+            cx,
+        )
+    }
+
+    // Advanced operations
+
+    /// Revert a commit. Returns whether the revert stopped on conflicts,
+    /// which then show up in `conflict_info` and `interrupted_pick`.
+    pub fn revert_commit(
+        &mut self,
+        sha: &str,
+        mainline: Option<u32>,
+        cx: &mut Context<Self>,
+    ) -> Result<bool> {
+        self.with_repo_mut(|repo| Ok(git::revert_commit(repo, sha, mainline)?.is_none()), cx)
+    }
+
+    /// Cherry-pick a commit. Returns whether the pick stopped on conflicts,
+    /// which then show up in `conflict_info` and `interrupted_pick`.
+    pub fn cherry_pick(&mut self, sha: &str, cx: &mut Context<Self>) -> Result<bool> {
+        self.with_repo_mut(|repo| Ok(git::cherry_pick(repo, sha)?.is_none()), cx)
+    }
+
+    /// Commit the cherry-pick or revert that stopped on conflicts once they
+    /// are resolved, keeping the message it would have had. A stopped
+    /// sequence goes on to pick the rest of its commits.
+    pub fn continue_pick(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        if self.cherry_pick_sequence.is_some() {
+            return self.continue_cherry_pick(cx);
+        }
+        let pick = self
+            .interrupted_pick
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No cherry-pick or revert is in progress"))?;
+        self.with_repo_mut(|repo| pick.continue_pick(repo), cx)?;
+        Ok(())
+    }
+
+    /// Throw away the cherry-pick or revert that stopped on conflicts. A
+    /// stopped sequence also drops the commits it picked before.
+    pub fn abort_pick(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        if self.cherry_pick_sequence.is_some() {
+            return self.abort_cherry_pick(cx);
+        }
+        let pick = self
+            .interrupted_pick
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No cherry-pick or revert is in progress"))?;
+        self.with_repo_mut(|repo| pick.abort(repo), cx)
+    }
+
+    /// Commit the merge that was left with its conflicts resolved, using
+    /// the message git saved for it
+    pub fn commit_interrupted_operation(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        let operation = self
+            .interrupted_operation
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No operation is in progress"))?;
+        self.with_repo_mut(|repo| operation.commit(repo), cx)
+    }
+
+    /// Abort the merge, rebase, bisect or `git am` that was left running
+    pub fn abort_interrupted_operation(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        let operation = self
+            .interrupted_operation
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No operation is in progress"))?;
+        self.with_repo_mut(|repo| operation.abort(repo), cx)
+    }
+
+    /// Tracked files, and optionally untracked files that aren't ignored
+    pub fn project_files(&self, include_untracked: bool) -> Result<Vec<ProjectFile>> {
+        self.with_repo(|repo| ProjectFile::list(repo, include_untracked))
+    }
+
+    /// Commits from HEAD that changed `path`, newest first
+    pub fn file_history(&self, path: &str, limit: usize) -> Result<Vec<CommitInfo>> {
+        self.with_repo(|repo| CommitInfo::file_history(repo, path, limit))
+    }
+
+    /// Show the changes a commit made to one file, with the rest of the
+    /// commit's files a step away
+    pub fn load_commit_file_diff(
+        &mut self,
+        sha: &str,
+        path: &str,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let (max_size, options) = (self.max_diff_size, self.diff_options);
+        let files = self.with_backend_repo(|backend| backend.commit_diff(sha, max_size, options))?;
+        let index = files
+            .iter()
+            .position(|diff| diff.path == path)
+            .ok_or_else(|| anyhow::anyhow!("{} is not changed in this commit", path))?;
+        self.show_diff_files(files, index, DiffSource::Commit(sha.to_string()));
+        cx.notify();
+        Ok(())
+    }
+
+    /// Write a snapshot archive on a background thread, resolving to the
+    /// number of files written
+    pub fn export_snapshot(
+        &self,
+        snapshot: Snapshot,
+        archive_path: PathBuf,
+        cx: &App,
+    ) -> Task<Result<usize>> {
+        let repo_path = self.path.clone();
+        cx.background_executor().spawn(async move {
+            let repo_path = repo_path.ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+            let repo = git2::Repository::open(&repo_path)?;
+            snapshot.write(&repo, &archive_path)
+        })
+    }
+
+    /// Blame the working tree copy of `path` on a background thread
+    pub fn file_blame(&self, path: &str, cx: &App) -> Task<Result<FileBlame>> {
+        let repo_path = self.path.clone();
+        let path = path.to_string();
+        cx.background_executor().spawn(async move {
+            let repo_path = repo_path.ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+            let repo = git2::Repository::open(&repo_path)?;
+            FileBlame::get(&repo, &path)
+        })
+    }
+
+    /// Compare two revisions on a background thread, listing at most
+    /// `limit` commits per side
+    pub fn compare(
+        &self,
+        base: &str,
+        head: &str,
+        mode: CompareMode,
+        limit: usize,
+        cx: &App,
+    ) -> Task<Result<Comparison>> {
+        let repo_path = self.path.clone();
+        let (base, head) = (base.to_string(), head.to_string());
+        let (max_size, options) = (self.max_diff_size, self.diff_options);
+        cx.background_executor().spawn(async move {
+            let repo_path = repo_path.ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+            let repo = git2::Repository::open(&repo_path)?;
+            Comparison::get(&repo, &base, &head, mode, limit, max_size, options)
+        })
+    }
+
+    /// Search tracked files on a background thread. Each file's matches are
+    /// sent as soon as it has been searched; dropping the receiver stops
+    /// the search.
+    pub fn search_files(
+        &self,
+        query: SearchQuery,
+        limit: usize,
+        cx: &App,
+    ) -> (mpsc::UnboundedReceiver<Vec<SearchMatch>>, Task<Result<SearchSummary>>) {
+        let repo_path = self.path.clone();
+        let (tx, rx) = mpsc::unbounded();
+        let task = cx.background_executor().spawn(async move {
+            let repo_path = repo_path.ok_or_else(|| anyhow::anyhow!("No repository open"))?;
+            let repo = git2::Repository::open(&repo_path)?;
+            git::search_files(&repo, &query, limit, &mut |matches| {
+                tx.unbounded_send(matches).is_ok()
+            })
+        });
+        (rx, task)
+    }
+
+    /// Commits (oldest first) as an mbox of email patches for `git am`
+    pub fn export_mbox(&self, shas: &[String], options: &MboxOptions) -> Result<String> {
+        self.with_repo(|repo| options.export(repo, shas))
+    }
+
+    /// Commits on `branch` that are not reachable from HEAD, newest first
+    pub fn commits_not_in_head(&self, branch: &str, limit: usize) -> Result<Vec<CommitInfo>> {
+        self.with_repo(|repo| CommitInfo::get_range(repo, "HEAD", branch, limit))
+    }
+
+    /// Cherry-pick commits onto HEAD one at a time (in the given order),
+    /// stopping at the first failure. A commit that conflicts pauses the
+    /// sequence until `continue_cherry_pick` or `abort_cherry_pick`.
+    /// Progress is tracked in `cherry_pick_progress`.
+    pub fn cherry_pick_sequence(&mut self, shas: Vec<String>, cx: &mut Context<Self>) {
+        if shas.is_empty()
+            || self
+                .cherry_pick_progress
+                .as_ref()
+                .is_some_and(|p| p.is_running())
+        {
+            return;
+        }
+
+        let total = shas.len();
+        let sequence = self.with_repo(|repo| CherryPickSequence::new(repo, shas));
+        self.cherry_pick_progress = Some(CherryPickProgress {
+            total,
+            completed: 0,
+            current: None,
+            conflicted: None,
+            error: sequence.as_ref().err().map(|e| e.to_string()),
+        });
+        self.cherry_pick_sequence = sequence.ok();
+        self.run_cherry_pick_sequence(cx);
+    }
+
+    /// Pick the rest of the sequence on a background thread, refreshing
+    /// after each commit
+    fn run_cherry_pick_sequence(&mut self, cx: &mut Context<Self>) {
+        cx.notify();
+        let (Some(path), Some(mut sequence)) =
+            (self.path.clone(), self.cherry_pick_sequence.clone())
+        else {
+            return;
+        };
+
+        cx.spawn(async move |this, cx| {
+            while let Some(sha) = sequence.next_pick() {
+                let short_sha: String = sha.chars().take(7).collect();
+
+                let started = this.update(cx, |state, cx| {
+                    if let Some(progress) = state.cherry_pick_progress.as_mut() {
+                        progress.current = Some(short_sha.clone());
+                    }
+                    cx.notify();
+                });
+                if started.is_err() {
+                    return;
+                }
+
+                let repo_path = path.clone();
+                let (result, picked) = cx
+                    .background_executor()
+                    .spawn(async move {
+                        let result = git2::Repository::open(&repo_path)
+                            .map_err(anyhow::Error::from)
+                            .and_then(|repo| sequence.step(&repo));
+                        (result, sequence)
+                    })
+                    .await;
+                sequence = picked;
+
+                let stopped = this
+                    .update(cx, |state, cx| {
+                        state.cherry_pick_sequence = Some(sequence.clone());
+                        let mut stopped = true;
+                        if let Some(progress) = state.cherry_pick_progress.as_mut() {
+                            progress.completed = sequence.completed();
+                            match &result {
+                                Ok(PickStep::Conflicted(_)) => {
+                                    log::warn!("Cherry-picking {} stopped on conflicts", short_sha);
+                                    progress.conflicted = Some(short_sha.clone());
+                                }
+                                Ok(_) => stopped = false,
+                                Err(e) => {
+                                    log::error!("Failed to cherry-pick {}: {}", short_sha, e);
+                                    progress.error = Some(format!("{}: {}", short_sha, e));
+                                }
+                            }
+                        }
+                        state.refresh(cx);
+                        stopped
+                    })
+                    .unwrap_or(true);
+
+                if stopped {
+                    break;
+                }
+            }
+
+            this.update(cx, |state, cx| {
+                if let Some(progress) = state.cherry_pick_progress.as_mut() {
+                    progress.current = None;
+                }
+                // The sequence is only kept while it waits on conflicts
+                if state
+                    .cherry_pick_progress
+                    .as_ref()
+                    .is_none_or(|p| p.conflicted.is_none())
+                {
+                    state.cherry_pick_sequence = None;
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Commit the resolved pick the sequence stopped on, with the original
+    /// message, and pick the commits after it
+    pub fn continue_cherry_pick(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        let mut sequence = self
+            .cherry_pick_sequence
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No cherry-pick is waiting on conflicts"))?;
+        self.with_repo_mut(|repo| sequence.continue_pick(repo), cx)?;
+
+        if let Some(progress) = self.cherry_pick_progress.as_mut() {
+            progress.completed = sequence.completed();
+            progress.conflicted = None;
+        }
+        self.cherry_pick_sequence = Some(sequence);
+        self.run_cherry_pick_sequence(cx);
+        Ok(())
+    }
+
+    /// Stop the sequence and put HEAD back where it was before the first
+    /// pick, dropping the commits picked so far
+    pub fn abort_cherry_pick(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        let sequence = self
+            .cherry_pick_sequence
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No cherry-pick is waiting on conflicts"))?;
+        self.with_repo_mut(|repo| sequence.abort(repo), cx)?;
+
+        self.cherry_pick_sequence = None;
+        self.cherry_pick_progress = None;
+        cx.notify();
+        Ok(())
+    }
+
+    pub fn clear_cherry_pick_progress(&mut self, cx: &mut Context<Self>) {
+        self.cherry_pick_progress = None;
+        cx.notify();
+    }
+
+    pub fn reset_to_commit(
+        &mut self,
+        sha: &str,
+        mode: ResetMode,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let reset = |this: &mut Self, cx: &mut Context<Self>| {
+            this.with_repo_mut(|repo| git::reset_to_commit(repo, sha, mode), cx)
+        };
+        if mode == ResetMode::Hard {
+            let label = format!("Reset --hard to {}", &sha[..7.min(sha.len())]);
+            self.recording_operation(&label, &[], cx, reset)
+        } else {
+            reset(self, cx)
+        }
+    }
+
+    /// Run a destructive operation, remembering HEAD and `refs` beforehand so
+    /// it can be undone with `undo_operation`
+    fn recording_operation<T>(
+        &mut self,
+        label: &str,
+        refs: &[String],
+        cx: &mut Context<Self>,
+        operation: impl FnOnce(&mut Self, &mut Context<Self>) -> Result<T>,
+    ) -> Result<T> {
+        let snapshot = self.with_repo(|repo| OperationSnapshot::capture(repo, label, refs));
+        let result = operation(self, cx)?;
+        match snapshot {
+            Ok(snapshot) => {
+                self.operation_history.push(snapshot);
+                if self.operation_history.len() > OPERATION_HISTORY_LIMIT {
+                    self.operation_history.remove(0);
+                }
+            }
+            Err(e) => log::warn!("Could not record '{}' for undo: {}", label, e),
+        }
+        Ok(result)
+    }
+
+    /// Most recent operation that can be undone
+    pub fn last_operation(&self) -> Option<&OperationSnapshot> {
+        self.operation_history.last()
+    }
+
+    /// Restore HEAD and the references touched by the most recent
+    /// destructive operation
+    pub fn undo_operation(&mut self, cx: &mut Context<Self>) -> Result<OperationSnapshot> {
+        let Some(snapshot) = self.operation_history.pop() else {
+            anyhow::bail!("Nothing to undo");
+        };
+        if let Err(e) = self.with_repo_mut(|repo| snapshot.restore(repo), cx) {
+            self.operation_history.push(snapshot);
+            return Err(e);
+        }
+        Ok(snapshot)
+    }
+
+    /// Recent movements of HEAD, newest first
+    pub fn head_reflog(&self, limit: usize) -> Result<Vec<ReflogEntry>> {
+        self.with_repo(|repo| ReflogEntry::get(repo, "HEAD", limit))
+    }
+
+    /// Soft-reset HEAD to its parent, keeping the commit's changes staged.
+    /// The commit is remembered so it can be restored with `redo_commit`.
+    pub fn undo_last_commit(&mut self, cx: &mut Context<Self>) -> Result<UndoneCommit> {
+        let undone = self.with_repo(|repo| {
+            let head = repo.head()?.peel_to_commit()?;
+            let parent = head
+                .parent(0)
+                .map_err(|_| anyhow::anyhow!("The initial commit cannot be undone"))?;
+            Ok(UndoneCommit {
+                sha: head.id().to_string(),
+                summary: head.summary().unwrap_or("").to_string(),
+                parent: parent.id().to_string(),
+                undone_at: Instant::now(),
+            })
+        })?;
+
+        self.reset_to_commit(&undone.parent, ResetMode::Soft, cx)?;
+        self.undo_stack.push(undone.clone());
+        if self.undo_stack.len() > UNDO_STACK_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        Ok(undone)
+    }
+
+    /// Whether the most recently undone commit can still be restored
+    pub fn can_redo_commit(&self) -> bool {
+        self.undo_stack
+            .last()
+            .is_some_and(|undone| undone.undone_at.elapsed() < REDO_GRACE_PERIOD)
+    }
+
+    /// Restore the most recently undone commit by soft-resetting back to it
+    pub fn redo_commit(&mut self, cx: &mut Context<Self>) -> Result<UndoneCommit> {
+        if !self.can_redo_commit() {
+            anyhow::bail!("Nothing to redo");
+        }
+        let Some(undone) = self.undo_stack.pop() else {
+            anyhow::bail!("Nothing to redo");
+        };
+
+        let head = self.with_repo(|repo| Ok(repo.head()?.peel_to_commit()?.id().to_string()))?;
+        if head != undone.parent {
+            anyhow::bail!("HEAD has moved since the commit was undone");
+        }
+
+        self.reset_to_commit(&undone.sha, ResetMode::Soft, cx)?;
+        Ok(undone)
+    }
+
+    /// Reset to a commit, or preview the reset first when preview mode is on
+    pub fn reset_or_preview(
+        &mut self,
+        sha: &str,
+        mode: ResetMode,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        if self.preview_operations {
+            let operation = PreviewOperation::Reset {
+                sha: sha.to_string(),
+                mode,
+            };
+            self.preview_operation(operation, cx)
+        } else {
+            self.reset_to_commit(sha, mode, cx)
+        }
+    }
+
+    pub fn clean_untracked(&mut self, cx: &mut Context<Self>) -> Result<usize> {
+        self.with_repo_mut(FileStatus::clean_untracked, cx)
+    }
+
+    // Operation previews
+    /// Compute the effect of an operation and hold it for confirmation
+    pub fn preview_operation(
+        &mut self,
+        operation: PreviewOperation,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        if let PreviewOperation::Merge { sha } = &operation {
+            self.check_merge_signatures(sha)?;
+        }
+        let preview = self.with_repo(|repo| OperationPreview::compute(repo, operation))?;
+        self.pending_preview = Some(preview);
+        cx.notify();
+        Ok(())
+    }
+
+    pub fn dismiss_preview(&mut self, cx: &mut Context<Self>) {
+        self.pending_preview = None;
+        cx.notify();
+    }
+
+    /// Run the operation of the pending preview
+    pub fn confirm_preview(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        let Some(preview) = self.pending_preview.take() else {
+            return Ok(());
+        };
+
+        match preview.operation {
+            PreviewOperation::Reset { sha, mode } => self.reset_to_commit(&sha, mode, cx),
+            PreviewOperation::Merge { sha } => self.merge_commit(&sha, cx).map(|_| ()),
+            PreviewOperation::Clean => self.clean_untracked(cx).map(|_| ()),
+            operation => {
+                anyhow::bail!("{} can only be previewed", operation.title())
+            }
+        }
+    }
+
+    /// Search commits by message, author, or SHA
+    /// Search the whole history for commits adding or removing `query` in
+    /// the background, replacing any search in progress. Follows the
+    /// graph's branch filter.
+    pub fn start_pickaxe_search(&mut self, query: &str, cx: &mut Context<Self>) {
+        self.pickaxe_generation += 1;
+        let generation = self.pickaxe_generation;
+        let Some(repo_path) = self.path.clone() else {
+            return;
+        };
+        if query.is_empty() {
+            self.pickaxe = None;
+            cx.notify();
+            return;
+        }
+
+        self.pickaxe = Some(PickaxeSearch {
+            query: query.to_string(),
+            running: true,
+            ..Default::default()
+        });
+        cx.notify();
+
+        let filter = self.graph_filter.clone();
+        let needle = query.to_string();
+        cx.spawn(async move |this, cx| {
+            let path = repo_path.clone();
+            let candidates = cx
+                .background_executor()
+                .spawn(async move {
+                    let repo = git2::Repository::open(&path)?;
+                    git::pickaxe_candidates(&repo, &filter)
+                })
+                .await;
+
+            let candidates = this
+                .update(cx, |state, cx| {
+                    if state.pickaxe_generation != generation {
+                        return None;
+                    }
+                    let search = state.pickaxe.as_mut()?;
+                    match candidates {
+                        Ok(candidates) => {
+                            search.total = Some(candidates.len());
+                            cx.notify();
+                            Some(candidates)
+                        }
+                        Err(e) => {
+                            log::error!("Pickaxe search failed: {}", e);
+                            search.error = Some(e.to_string());
+                            search.running = false;
+                            cx.notify();
+                            None
+                        }
+                    }
+                })
+                .ok()
+                .flatten();
+            let Some(candidates) = candidates else {
+                return;
+            };
+
+            for batch in candidates.chunks(PICKAXE_BATCH) {
+                let batch = batch.to_vec();
+                let batch_len = batch.len();
+                let path = repo_path.clone();
+                let needle = needle.clone();
+                let result = cx
+                    .background_executor()
+                    .spawn(async move {
+                        let repo = git2::Repository::open(&path)?;
+                        git::pickaxe_matches(&repo, &batch, &needle)
+                    })
+                    .await;
+
+                let keep_going = this
+                    .update(cx, |state, cx| {
+                        if state.pickaxe_generation != generation {
+                            return false;
+                        }
+                        let Some(search) = state.pickaxe.as_mut() else {
+                            return false;
+                        };
+                        match result {
+                            Ok(found) => {
+                                search.scanned += batch_len;
+                                search.results.extend(found);
+                                if search.results.len() >= PICKAXE_RESULT_LIMIT {
+                                    search.results.truncate(PICKAXE_RESULT_LIMIT);
+                                    search.truncated = search.scanned < candidates.len();
+                                    search.running = false;
+                                }
+                            }
+                            Err(e) => {
+                                log::error!("Pickaxe search failed: {}", e);
+                                search.error = Some(e.to_string());
+                                search.running = false;
+                            }
+                        }
+                        cx.notify();
+                        search.running
+                    })
+                    .unwrap_or(false);
+                if !keep_going {
+                    return;
+                }
+            }
+
+            this.update(cx, |state, cx| {
+                if state.pickaxe_generation == generation {
+                    if let Some(search) = state.pickaxe.as_mut() {
+                        search.running = false;
+                    }
+                    cx.notify();
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Stop the running pickaxe search, keeping what it found so far
+    pub fn stop_pickaxe_search(&mut self, cx: &mut Context<Self>) {
+        self.pickaxe_generation += 1;
+        if let Some(search) = self.pickaxe.as_mut() {
+            search.running = false;
+        }
+        cx.notify();
+    }
+
+    pub fn clear_pickaxe_search(&mut self, cx: &mut Context<Self>) {
+        self.pickaxe_generation += 1;
+        self.pickaxe = None;
+        cx.notify();
+    }
+
     pub fn search_commits(&self, query: &str, limit: usize) -> Vec<CommitInfo> {
         let query = query.to_lowercase();
 
@@ -0,0 +1,80 @@
+#![allow(dead_code)]
+
+use anyhow::Result;
+
+/// Keychain service the app's secrets are filed under
+const SERVICE: &str = "awabancha";
+
+/// A secret kept out of the settings file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SecretKind {
+    /// HTTPS access token or password for remote operations
+    GitToken,
+    /// Access token for the GitHub or GitLab API
+    HostingToken,
+}
+
+impl SecretKind {
+    fn account(self) -> &'static str {
+        match self {
+            SecretKind::GitToken => "git-token",
+            SecretKind::HostingToken => "hosting-token",
+        }
+    }
+}
+
+/// Secure storage for secrets such as access tokens
+pub trait CredentialStore: Send + Sync {
+    /// Name shown in settings, e.g. "Keychain"
+    fn name(&self) -> &'static str;
+
+    fn get(&self, kind: SecretKind) -> Result<Option<String>>;
+
+    fn set(&self, kind: SecretKind, secret: &str) -> Result<()>;
+
+    /// Forget the secret; succeeds if there was none
+    fn delete(&self, kind: SecretKind) -> Result<()>;
+}
+
+/// The OS credential store: the macOS Keychain, Windows Credential Manager,
+/// or the Secret Service on Linux
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemKeychain;
+
+impl SystemKeychain {
+    fn entry(kind: SecretKind) -> Result<keyring::Entry> {
+        Ok(keyring::Entry::new(SERVICE, kind.account())?)
+    }
+}
+
+impl CredentialStore for SystemKeychain {
+    fn name(&self) -> &'static str {
+        if cfg!(target_os = "macos") {
+            "Keychain"
+        } else if cfg!(target_os = "windows") {
+            "Credential Manager"
+        } else {
+            "Secret Service"
+        }
+    }
+
+    fn get(&self, kind: SecretKind) -> Result<Option<String>> {
+        match Self::entry(kind)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set(&self, kind: SecretKind, secret: &str) -> Result<()> {
+        Self::entry(kind)?.set_password(secret)?;
+        Ok(())
+    }
+
+    fn delete(&self, kind: SecretKind) -> Result<()> {
+        match Self::entry(kind)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+#![allow(dead_code)]
+
+use chrono::{DateTime, Local};
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::StreamExt;
+use gpui::*;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+
+/// Most log entries kept for the console
+const MAX_ENTRIES: usize = 5000;
+
+/// Crate target whose debug logs are captured even when RUST_LOG is unset
+const APP_TARGET: &str = "awabancha";
+
+/// A captured log record
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: Level,
+    /// Module path the record was logged from
+    pub target: String,
+    pub message: String,
+}
+
+/// Forwards records to env_logger for terminal output and copies them into a
+/// channel for the in-app console.
+///
+/// The console captures info and above from every crate and debug from the
+/// app itself, independently of the RUST_LOG filter.
+struct CaptureLogger {
+    inner: env_logger::Logger,
+    sender: UnboundedSender<LogEntry>,
+}
+
+impl CaptureLogger {
+    fn captures(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info
+            || (metadata.level() <= Level::Debug && metadata.target().starts_with(APP_TARGET))
+    }
+}
+
+impl Log for CaptureLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata) || self.captures(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.inner.matches(record) {
+            self.inner.log(record);
+        }
+        if self.captures(record.metadata()) {
+            let _ = self.sender.unbounded_send(LogEntry {
+                timestamp: Local::now(),
+                level: record.level(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            });
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Install the logger, returning the stream of records for the console
+pub fn init_logging() -> UnboundedReceiver<LogEntry> {
+    let (sender, receiver) = mpsc::unbounded();
+    let inner = env_logger::Builder::from_default_env().build();
+    let max_level = inner.filter().max(LevelFilter::Debug);
+
+    if log::set_boxed_logger(Box::new(CaptureLogger { inner, sender })).is_ok() {
+        log::set_max_level(max_level);
+    }
+    receiver
+}
+
+/// Recent log records shown in the logging console
+pub struct LogState {
+    entries: VecDeque<LogEntry>,
+}
+
+impl LogState {
+    /// Collect records from `receiver` until the app exits
+    pub fn new(mut receiver: UnboundedReceiver<LogEntry>, cx: &mut Context<Self>) -> Self {
+        cx.spawn(async move |this, cx| {
+            while let Some(entry) = receiver.next().await {
+                let result = this.update(cx, |state, cx| {
+                    state.push(entry);
+                    cx.notify();
+                });
+                if result.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+
+        Self {
+            entries: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, entry: LogEntry) {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn entries(&self) -> &VecDeque<LogEntry> {
+        &self.entries
+    }
+
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.entries.clear();
+        cx.notify();
+    }
+}
@@ -1,11 +1,32 @@
+pub mod avatar_state;
+pub mod control_server;
+pub mod draft_state;
+pub mod external_editor;
 pub mod git_state;
+pub mod keychain;
+pub mod log_state;
 pub mod recent_projects;
+pub mod review_notes;
+pub mod session_state;
 pub mod settings_state;
 pub mod toast_state;
+pub mod todo_state;
 pub mod watcher;
 
+pub use avatar_state::*;
+pub use control_server::*;
+pub use draft_state::*;
+pub use external_editor::*;
 pub use git_state::*;
+pub use keychain::*;
+pub use log_state::*;
 pub use recent_projects::*;
+pub use review_notes::*;
+pub use session_state::*;
 pub use settings_state::*;
 pub use toast_state::*;
+pub use todo_state::*;
 pub use watcher::*;
+
+#[cfg(test)]
+mod tests;
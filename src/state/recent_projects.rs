@@ -1,11 +1,14 @@
 #![allow(dead_code)]
 
+use crate::git::RepositorySummary;
 use chrono::{DateTime, Utc};
 use gpui::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+/// Most unpinned projects remembered; pinned projects are always kept
 const MAX_RECENT_PROJECTS: usize = 10;
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -13,6 +16,9 @@ pub struct RecentProject {
     pub path: PathBuf,
     pub name: String,
     pub last_opened: DateTime<Utc>,
+    /// Listed above the unpinned projects and never dropped for newer ones
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -20,8 +26,19 @@ pub struct RecentProjectsData {
     pub projects: Vec<RecentProject>,
 }
 
+/// What is known about a recent project's repository on disk
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProjectStatus {
+    /// Deleted, moved, or no longer a repository
+    Missing,
+    Available(RepositorySummary),
+}
+
 pub struct RecentProjects {
     pub data: RecentProjectsData,
+    /// Read in the background by `refresh_statuses`; projects not read yet
+    /// have no entry
+    statuses: HashMap<PathBuf, ProjectStatus>,
 }
 
 impl RecentProjects {
@@ -35,7 +52,12 @@ impl RecentProjects {
             .and_then(|content| serde_json::from_str(&content).ok())
             .unwrap_or_default();
 
-        Self { data }
+        let mut recent = Self {
+            data,
+            statuses: HashMap::new(),
+        };
+        recent.arrange();
+        recent
     }
 
     pub fn save(&self, _cx: &mut Context<Self>) {
@@ -50,7 +72,8 @@ impl RecentProjects {
     }
 
     pub fn add_project(&mut self, path: PathBuf, name: String, cx: &mut Context<Self>) {
-        // Remove if already exists
+        // Remove if already exists, keeping whether it was pinned
+        let pinned = self.is_pinned(&path);
         self.data.projects.retain(|p| p.path != path);
 
         // Add to front
@@ -60,24 +83,52 @@ impl RecentProjects {
                 path,
                 name,
                 last_opened: Utc::now(),
+                pinned,
             },
         );
 
-        // Trim to max size
-        self.data.projects.truncate(MAX_RECENT_PROJECTS);
-
+        self.arrange();
         self.save(cx);
         cx.notify();
     }
 
     pub fn remove_project(&mut self, path: &PathBuf, cx: &mut Context<Self>) {
         self.data.projects.retain(|p| &p.path != path);
+        self.statuses.remove(path);
         self.save(cx);
         cx.notify();
     }
 
+    pub fn toggle_pinned(&mut self, path: &Path, cx: &mut Context<Self>) {
+        if let Some(project) = self.data.projects.iter_mut().find(|p| p.path == path) {
+            project.pinned = !project.pinned;
+        }
+        self.arrange();
+        self.save(cx);
+        cx.notify();
+    }
+
+    /// Point a project that was moved at its new location
+    pub fn relocate_project(&mut self, old: &Path, new: PathBuf, cx: &mut Context<Self>) {
+        let pinned = self.is_pinned(old) || self.is_pinned(&new);
+        self.data.projects.retain(|p| p.path != new);
+        if let Some(project) = self.data.projects.iter_mut().find(|p| p.path == old) {
+            project.name = new
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Unknown".to_string());
+            project.path = new;
+            project.pinned = pinned;
+        }
+        self.statuses.remove(old);
+        self.arrange();
+        self.save(cx);
+        self.refresh_statuses(cx);
+    }
+
     pub fn clear_all(&mut self, cx: &mut Context<Self>) {
         self.data.projects.clear();
+        self.statuses.clear();
         self.save(cx);
         cx.notify();
     }
@@ -85,4 +136,65 @@ impl RecentProjects {
     pub fn projects(&self) -> &[RecentProject] {
         &self.data.projects
     }
+
+    pub fn status(&self, path: &Path) -> Option<&ProjectStatus> {
+        self.statuses.get(path)
+    }
+
+    /// Read the branch and dirty state of every project in the background
+    pub fn refresh_statuses(&mut self, cx: &mut Context<Self>) {
+        let paths: Vec<PathBuf> = self.data.projects.iter().map(|p| p.path.clone()).collect();
+        cx.spawn(async move |this, cx| {
+            let statuses = cx
+                .background_executor()
+                .spawn(async move {
+                    paths
+                        .into_iter()
+                        .map(|path| {
+                            let status = match RepositorySummary::read(&path) {
+                                Ok(summary) => ProjectStatus::Available(summary),
+                                Err(e) => {
+                                    log::debug!(
+                                        "Recent project {} unavailable: {}",
+                                        path.display(),
+                                        e
+                                    );
+                                    ProjectStatus::Missing
+                                }
+                            };
+                            (path, status)
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await;
+
+            this.update(cx, |recent, cx| {
+                recent.statuses.extend(statuses);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn is_pinned(&self, path: &Path) -> bool {
+        self.data.projects.iter().any(|p| p.path == path && p.pinned)
+    }
+
+    /// Pinned projects first, each group most recently opened first, then
+    /// drop the oldest unpinned projects over the limit
+    fn arrange(&mut self) {
+        self.data.projects.sort_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then_with(|| b.last_opened.cmp(&a.last_opened))
+        });
+        let mut unpinned = 0;
+        self.data.projects.retain(|p| {
+            if !p.pinned {
+                unpinned += 1;
+            }
+            p.pinned || unpinned <= MAX_RECENT_PROJECTS
+        });
+    }
 }
@@ -0,0 +1,172 @@
+#![allow(dead_code)]
+
+use chrono::{DateTime, Utc};
+use gpui::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What a review note refers to
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NoteAnchor {
+    General,
+    File { path: String, line: Option<u32> },
+    Commit { sha: String, summary: String },
+}
+
+impl NoteAnchor {
+    /// Heading used to group notes in the panel and the Markdown export
+    pub fn heading(&self) -> String {
+        match self {
+            NoteAnchor::General => "General".to_string(),
+            NoteAnchor::File { path, .. } => format!("`{}`", path),
+            NoteAnchor::Commit { sha, summary } => {
+                format!("Commit `{}` {}", &sha[..sha.len().min(7)], summary)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReviewNote {
+    pub id: u64,
+    pub anchor: NoteAnchor,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ReviewNotesData {
+    /// Notes keyed by repository path
+    pub repositories: HashMap<PathBuf, Vec<ReviewNote>>,
+    pub next_id: u64,
+}
+
+/// Local review scratchpad, stored in the app config rather than the repository
+pub struct ReviewNotes {
+    pub data: ReviewNotesData,
+}
+
+impl ReviewNotes {
+    fn storage_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("awabancha").join("review_notes.json"))
+    }
+
+    pub fn load(_cx: &mut Context<Self>) -> Self {
+        let data = Self::storage_path()
+            .and_then(|path| fs::read_to_string(&path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self { data }
+    }
+
+    pub fn save(&self, _cx: &mut Context<Self>) {
+        if let Some(path) = Self::storage_path() {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let Ok(content) = serde_json::to_string_pretty(&self.data) {
+                let _ = fs::write(&path, content);
+            }
+        }
+    }
+
+    pub fn notes(&self, repo: &Path) -> &[ReviewNote] {
+        self.data
+            .repositories
+            .get(repo)
+            .map(|notes| notes.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn add_note(
+        &mut self,
+        repo: &Path,
+        anchor: NoteAnchor,
+        text: String,
+        cx: &mut Context<Self>,
+    ) {
+        let id = self.data.next_id;
+        self.data.next_id += 1;
+        self.data
+            .repositories
+            .entry(repo.to_path_buf())
+            .or_default()
+            .push(ReviewNote {
+                id,
+                anchor,
+                text,
+                created_at: Utc::now(),
+            });
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn remove_note(&mut self, repo: &Path, id: u64, cx: &mut Context<Self>) {
+        if let Some(notes) = self.data.repositories.get_mut(repo) {
+            notes.retain(|n| n.id != id);
+            if notes.is_empty() {
+                self.data.repositories.remove(repo);
+            }
+        }
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn clear(&mut self, repo: &Path, cx: &mut Context<Self>) {
+        self.data.repositories.remove(repo);
+        self.save(cx);
+        cx.notify();
+    }
+
+    /// Notes grouped by anchor, in the order each group first appeared
+    pub fn grouped(&self, repo: &Path) -> Vec<(NoteAnchor, Vec<ReviewNote>)> {
+        let mut groups: Vec<(NoteAnchor, Vec<ReviewNote>)> = Vec::new();
+
+        for note in self.notes(repo) {
+            // File notes group by path regardless of line
+            let key = match &note.anchor {
+                NoteAnchor::File { path, .. } => NoteAnchor::File {
+                    path: path.clone(),
+                    line: None,
+                },
+                anchor => anchor.clone(),
+            };
+            match groups.iter_mut().find(|(anchor, _)| *anchor == key) {
+                Some((_, notes)) => notes.push(note.clone()),
+                None => groups.push((key, vec![note.clone()])),
+            }
+        }
+
+        for (_, notes) in &mut groups {
+            notes.sort_by_key(|n| match n.anchor {
+                NoteAnchor::File { line, .. } => line.unwrap_or(0),
+                _ => 0,
+            });
+        }
+
+        groups
+    }
+
+    /// Render the notes for a repository as Markdown for pasting into a PR
+    pub fn to_markdown(&self, repo: &Path) -> String {
+        let mut out = String::from("## Review notes\n");
+
+        for (anchor, notes) in self.grouped(repo) {
+            out.push_str(&format!("\n### {}\n\n", anchor.heading()));
+            for note in notes {
+                let text = note.text.trim().replace('\n', "\n  ");
+                match note.anchor {
+                    NoteAnchor::File {
+                        line: Some(line), ..
+                    } => out.push_str(&format!("- **L{}**: {}\n", line, text)),
+                    _ => out.push_str(&format!("- {}\n", text)),
+                }
+            }
+        }
+
+        out
+    }
+}
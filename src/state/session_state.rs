@@ -0,0 +1,168 @@
+#![allow(dead_code)]
+
+use gpui::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Default width of the left panel in pixels
+pub const DEFAULT_LEFT_PANEL_WIDTH: f32 = 320.0;
+pub const MIN_LEFT_PANEL_WIDTH: f32 = 240.0;
+pub const MAX_LEFT_PANEL_WIDTH: f32 = 640.0;
+
+/// UI state restored on the next launch
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionData {
+    /// Repository that was open when the app last quit
+    pub last_repository: Option<PathBuf>,
+    pub left_panel_width: f32,
+    pub stash_expanded: bool,
+    /// Show changed files grouped into a directory tree
+    pub file_tree_view: bool,
+    /// File whose diff was open in the diff viewer
+    pub open_diff: Option<String>,
+    /// Whether that diff showed only the unstaged changes, which can be
+    /// staged line by line
+    pub diff_unstaged: bool,
+    /// Commit selected in the history
+    pub selected_commit: Option<String>,
+    /// Show the project file tree sidebar
+    pub project_tree_visible: bool,
+    /// List untracked files that aren't ignored in the project tree
+    pub project_tree_untracked: bool,
+}
+
+impl Default for SessionData {
+    fn default() -> Self {
+        Self {
+            last_repository: None,
+            left_panel_width: DEFAULT_LEFT_PANEL_WIDTH,
+            stash_expanded: false,
+            file_tree_view: false,
+            open_diff: None,
+            diff_unstaged: false,
+            selected_commit: None,
+            project_tree_visible: false,
+            project_tree_untracked: false,
+        }
+    }
+}
+
+pub struct SessionState {
+    pub data: SessionData,
+}
+
+impl SessionState {
+    fn storage_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("awabancha").join("session.json"))
+    }
+
+    pub fn load(_cx: &mut Context<Self>) -> Self {
+        let data = Self::storage_path()
+            .and_then(|path| fs::read_to_string(&path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self { data }
+    }
+
+    pub fn save(&self, _cx: &mut Context<Self>) {
+        if let Some(path) = Self::storage_path() {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let Ok(content) = serde_json::to_string_pretty(&self.data) {
+                let _ = fs::write(&path, content);
+            }
+        }
+    }
+
+    /// Repository to reopen on launch, if it still exists
+    pub fn restorable_repository(&self) -> Option<PathBuf> {
+        self.data.last_repository.clone().filter(|path| path.exists())
+    }
+
+    pub fn left_panel_width(&self) -> f32 {
+        self.data.left_panel_width.clamp(MIN_LEFT_PANEL_WIDTH, MAX_LEFT_PANEL_WIDTH)
+    }
+
+    // Setters
+    pub fn set_last_repository(&mut self, path: Option<PathBuf>, cx: &mut Context<Self>) {
+        if self.data.last_repository == path {
+            return;
+        }
+        self.data.last_repository = path;
+        // A diff and a selected commit belong to the repository they were
+        // opened in
+        self.data.open_diff = None;
+        self.data.diff_unstaged = false;
+        self.data.selected_commit = None;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_left_panel_width(&mut self, width: f32, cx: &mut Context<Self>) {
+        self.data.left_panel_width = width.clamp(MIN_LEFT_PANEL_WIDTH, MAX_LEFT_PANEL_WIDTH);
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_stash_expanded(&mut self, expanded: bool, cx: &mut Context<Self>) {
+        self.data.stash_expanded = expanded;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_file_tree_view(&mut self, tree_view: bool, cx: &mut Context<Self>) {
+        self.data.file_tree_view = tree_view;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_open_diff(&mut self, path: Option<String>, cx: &mut Context<Self>) {
+        if self.data.open_diff == path {
+            return;
+        }
+        self.data.open_diff = path;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_diff_unstaged(&mut self, unstaged: bool, cx: &mut Context<Self>) {
+        if self.data.diff_unstaged == unstaged {
+            return;
+        }
+        self.data.diff_unstaged = unstaged;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_selected_commit(&mut self, sha: Option<String>, cx: &mut Context<Self>) {
+        if self.data.selected_commit == sha {
+            return;
+        }
+        self.data.selected_commit = sha;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_project_tree_visible(&mut self, visible: bool, cx: &mut Context<Self>) {
+        self.data.project_tree_visible = visible;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_project_tree_untracked(&mut self, untracked: bool, cx: &mut Context<Self>) {
+        self.data.project_tree_untracked = untracked;
+        self.save(cx);
+        cx.notify();
+    }
+
+    /// Forget everything, e.g. when session restore is turned off
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.data = SessionData::default();
+        self.save(cx);
+        cx.notify();
+    }
+}
@@ -1,11 +1,18 @@
 #![allow(dead_code)]
 
+use crate::git::{DiffDisplayOptions, Identity};
 use crate::i18n::Locale;
-use crate::state::GitCredentials;
+use crate::state::{
+    CredentialStore, GitCredentials, SecretKind, SystemKeychain, ToastDurations, ToastPosition,
+};
+use crate::theme::{Appearance, GraphPalette};
 use gpui::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AuthMode {
@@ -33,26 +40,219 @@ impl Default for MergeMode {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Theme {
+impl From<MergeMode> for crate::git::MergeMode {
+    fn from(mode: MergeMode) -> Self {
+        match mode {
+            MergeMode::Auto => Self::Auto,
+            MergeMode::FfOnly => Self::FfOnly,
+            MergeMode::NoFf => Self::NoFf,
+            MergeMode::Squash => Self::Squash,
+        }
+    }
+}
+
+/// What to do when incoming commits on protected branches fail signature checks
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SigningPolicy {
+    #[default]
+    Off,
+    Warn,
+    Block,
+}
+
+/// What to do when a commit message breaks the message rules
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CommitLintMode {
+    Off,
+    /// Show the problems under the message
+    #[default]
+    Warn,
+    /// Show the problems and refuse to commit until they are fixed
+    Block,
+}
+
+/// How often all remotes are fetched in the background while a repository is open
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum AutoFetchInterval {
+    #[default]
+    Off,
+    FiveMinutes,
+    FifteenMinutes,
+    ThirtyMinutes,
+}
+
+impl AutoFetchInterval {
+    pub const ALL: [Self; 4] = [
+        Self::Off,
+        Self::FiveMinutes,
+        Self::FifteenMinutes,
+        Self::ThirtyMinutes,
+    ];
+
+    /// Time between fetches, or `None` when auto-fetch is off
+    pub fn duration(self) -> Option<Duration> {
+        let minutes = match self {
+            Self::Off => return None,
+            Self::FiveMinutes => 5,
+            Self::FifteenMinutes => 15,
+            Self::ThirtyMinutes => 30,
+        };
+        Some(Duration::from_secs(minutes * 60))
+    }
+}
+
+/// Largest file shown as a diff; bigger files are summarized by size
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DiffSizeLimit {
+    QuarterMegabyte,
+    #[default]
+    OneMegabyte,
+    FiveMegabytes,
+    TwentyMegabytes,
+}
+
+impl DiffSizeLimit {
+    pub const ALL: [Self; 4] = [
+        Self::QuarterMegabyte,
+        Self::OneMegabyte,
+        Self::FiveMegabytes,
+        Self::TwentyMegabytes,
+    ];
+
+    pub fn bytes(self) -> u64 {
+        let kilobytes = match self {
+            Self::QuarterMegabyte => 256,
+            Self::OneMegabyte => 1024,
+            Self::FiveMegabytes => 5 * 1024,
+            Self::TwentyMegabytes => 20 * 1024,
+        };
+        kilobytes * 1024
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ThemeMode {
+    #[default]
     Dark,
     Light,
+    /// Follow the system appearance
+    System,
 }
 
-impl Default for Theme {
-    fn default() -> Self {
-        Self::Dark
+impl ThemeMode {
+    /// Resolve the mode against the current system appearance
+    pub fn appearance(self, system: Appearance) -> Appearance {
+        match self {
+            ThemeMode::Dark => Appearance::Dark,
+            ThemeMode::Light => Appearance::Light,
+            ThemeMode::System => system,
+        }
     }
 }
 
+/// Overrides for one repository; unset fields fall back to the global settings
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepoSettings {
+    /// Commit author name, instead of `user.name` from git config
+    pub user_name: Option<String>,
+    /// Commit author email, instead of `user.email` from git config
+    pub user_email: Option<String>,
+    /// Remote to push to, pull from and fetch, instead of "origin"
+    pub default_remote: Option<String>,
+    pub merge_mode: Option<MergeMode>,
+    pub git_auth_mode: Option<AuthMode>,
+}
+
+impl RepoSettings {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn identity(&self) -> Identity {
+        Identity {
+            name: self.user_name.clone(),
+            email: self.user_email.clone(),
+        }
+    }
+}
+
+/// Page shown in the settings view
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SettingsTab {
+    #[default]
+    General,
+    /// Overrides for the open repository
+    Repository,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SettingsData {
     pub git_auth_mode: AuthMode,
     pub git_username: Option<String>,
+    /// Plaintext token, only kept here when the credential store is
+    /// unavailable. Tokens saved by older versions are moved out on load.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub git_token: Option<String>,
+    /// Plaintext GitHub/GitLab API token, only kept here when the credential
+    /// store is unavailable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hosting_token: Option<String>,
     pub merge_mode: MergeMode,
-    pub theme: Theme,
+    pub theme: ThemeMode,
+    /// Name of a theme from the user themes directory, overriding `theme`
+    pub user_theme: Option<String>,
+    /// Commit graph lane colors, with color-blind safe alternatives
+    pub graph_palette: GraphPalette,
+    /// Download author avatars from Gravatar and GitHub; cached ones are
+    /// still shown when off
+    pub fetch_avatars: bool,
+    /// Command files are opened with, where `{file}` and `{line}` are
+    /// replaced (e.g. `code --goto {file}:{line}`); unset opens them with the
+    /// system's default application
+    pub editor_command: Option<String>,
+    /// Detected from the system language on first run
     pub locale: Locale,
+    /// Reopen the last repository and restore panel state on launch
+    pub restore_session: bool,
+    /// Fetch all remotes in the background when a repository is opened
+    pub fetch_on_open: bool,
+    /// Remove remote-tracking branches that no longer exist on the remote when fetching
+    pub fetch_prune: bool,
+    /// Fetch all remotes periodically so ahead/behind counts stay current
+    pub auto_fetch_interval: AutoFetchInterval,
+    /// Stash local changes before pull and checkout, reapplying them afterwards
+    pub autostash: bool,
+    /// Show what reset and clean would change before running them
+    pub preview_operations: bool,
+    /// Back up discarded changes to .git/awabancha-trash so they can be restored
+    pub trash_discards: bool,
+    /// Accept JSON-RPC requests from editors and scripts on a local socket
+    pub control_socket: bool,
+    /// Corner notifications are shown in
+    pub toast_position: ToastPosition,
+    /// Keep error notifications on screen until dismissed
+    pub sticky_errors: bool,
+    /// How long each kind of notification is shown
+    pub toast_durations: ToastDurations,
+    /// Largest file the diff viewer loads
+    pub max_diff_size: DiffSizeLimit,
+    /// Whitespace and context options, toggled from the diff viewer
+    pub diff_options: DiffDisplayOptions,
+    pub signing_policy: SigningPolicy,
+    /// Check commit messages for subject length, trailing periods, mood and
+    /// a blank line before the body
+    pub commit_lint: CommitLintMode,
+    /// Run the repository's pre-commit and commit-msg hooks before
+    /// committing, which committing through libgit2 would skip
+    pub run_hooks: bool,
+    /// Branches whose incoming commits are checked against the signing policy
+    pub protected_branches: Vec<String>,
+    /// Key IDs or fingerprints allowed to sign protected branches (empty = any valid signature)
+    pub allowed_signers: Vec<String>,
+    /// Per-repository overrides, keyed by repository path
+    pub repositories: BTreeMap<String, RepoSettings>,
 }
 
 impl Default for SettingsData {
@@ -61,15 +261,60 @@ impl Default for SettingsData {
             git_auth_mode: AuthMode::default(),
             git_username: None,
             git_token: None,
+            hosting_token: None,
             merge_mode: MergeMode::default(),
-            theme: Theme::default(),
-            locale: Locale::default(),
+            theme: ThemeMode::default(),
+            user_theme: None,
+            graph_palette: GraphPalette::default(),
+            fetch_avatars: true,
+            editor_command: None,
+            locale: Locale::system(),
+            restore_session: true,
+            fetch_on_open: false,
+            fetch_prune: false,
+            auto_fetch_interval: AutoFetchInterval::default(),
+            autostash: false,
+            preview_operations: false,
+            trash_discards: true,
+            control_socket: false,
+            toast_position: ToastPosition::default(),
+            sticky_errors: false,
+            toast_durations: ToastDurations::default(),
+            max_diff_size: DiffSizeLimit::default(),
+            diff_options: DiffDisplayOptions::default(),
+            signing_policy: SigningPolicy::default(),
+            commit_lint: CommitLintMode::default(),
+            run_hooks: true,
+            protected_branches: vec!["main".to_string(), "master".to_string()],
+            allowed_signers: Vec::new(),
+            repositories: BTreeMap::new(),
         }
     }
 }
 
+/// Where the HTTPS token is kept
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenStorage {
+    None,
+    /// In the OS credential store
+    Keychain,
+    /// In settings.json as plaintext, because the credential store failed
+    SettingsFile,
+}
+
 pub struct SettingsState {
     pub data: SettingsData,
+    /// Whether the theme picker in the settings view is expanded
+    pub theme_picker_open: bool,
+    pub tab: SettingsTab,
+    /// Key of the open repository in `data.repositories`
+    current_repo: Option<String>,
+    credentials: Arc<dyn CredentialStore>,
+    /// Token read from wherever `token_storage` says
+    git_token: Option<String>,
+    pub token_storage: TokenStorage,
+    /// API token for pull request status, read from the credential store
+    hosting_token: Option<String>,
 }
 
 impl SettingsState {
@@ -77,13 +322,130 @@ impl SettingsState {
         dirs::config_dir().map(|p| p.join("awabancha").join("settings.json"))
     }
 
-    pub fn load(_cx: &mut Context<Self>) -> Self {
+    pub fn load(cx: &mut Context<Self>) -> Self {
         let data = Self::settings_path()
             .and_then(|path| fs::read_to_string(&path).ok())
             .and_then(|content| serde_json::from_str(&content).ok())
             .unwrap_or_default();
 
-        Self { data }
+        let mut state = Self {
+            data,
+            theme_picker_open: false,
+            tab: SettingsTab::General,
+            current_repo: None,
+            credentials: Arc::new(SystemKeychain),
+            git_token: None,
+            token_storage: TokenStorage::None,
+            hosting_token: None,
+        };
+        state.load_token(cx);
+        state.load_hosting_token();
+        state
+    }
+
+    /// Read the token from the credential store, first moving a plaintext
+    /// token out of the settings file
+    fn load_token(&mut self, cx: &mut Context<Self>) {
+        if let Some(token) = self.data.git_token.take() {
+            self.store_token(Some(token));
+            self.save(cx);
+            if self.token_storage == TokenStorage::Keychain {
+                log::info!("Moved the git token to the {}", self.credentials.name());
+            }
+            return;
+        }
+
+        match self.credentials.get(SecretKind::GitToken) {
+            Ok(token) => {
+                self.token_storage = match token {
+                    Some(_) => TokenStorage::Keychain,
+                    None => TokenStorage::None,
+                };
+                self.git_token = token;
+            }
+            Err(e) => log::warn!(
+                "Failed to read the git token from the {}: {}",
+                self.credentials.name(),
+                e
+            ),
+        }
+    }
+
+    /// Keep the token in the credential store, falling back to the settings
+    /// file when the store fails so credentials still work
+    fn store_token(&mut self, token: Option<String>) {
+        let name = self.credentials.name();
+        match &token {
+            Some(secret) => match self.credentials.set(SecretKind::GitToken, secret) {
+                Ok(()) => {
+                    self.data.git_token = None;
+                    self.token_storage = TokenStorage::Keychain;
+                }
+                Err(e) => {
+                    log::warn!("{} unavailable, saving the token in settings: {}", name, e);
+                    self.data.git_token = Some(secret.clone());
+                    self.token_storage = TokenStorage::SettingsFile;
+                }
+            },
+            None => {
+                if let Err(e) = self.credentials.delete(SecretKind::GitToken) {
+                    log::warn!("Failed to remove the git token from the {}: {}", name, e);
+                }
+                self.data.git_token = None;
+                self.token_storage = TokenStorage::None;
+            }
+        }
+        self.git_token = token;
+    }
+
+    fn load_hosting_token(&mut self) {
+        if self.data.hosting_token.is_some() {
+            self.hosting_token = self.data.hosting_token.clone();
+            return;
+        }
+        match self.credentials.get(SecretKind::HostingToken) {
+            Ok(token) => self.hosting_token = token,
+            Err(e) => log::warn!(
+                "Failed to read the hosting token from the {}: {}",
+                self.credentials.name(),
+                e
+            ),
+        }
+    }
+
+    /// Keep the API token in the credential store, or in the settings file
+    /// when the store fails
+    fn store_hosting_token(&mut self, token: Option<String>) {
+        let name = self.credentials.name();
+        self.data.hosting_token = None;
+        match &token {
+            Some(secret) => {
+                if let Err(e) = self.credentials.set(SecretKind::HostingToken, secret) {
+                    log::warn!("{} unavailable, saving the token in settings: {}", name, e);
+                    self.data.hosting_token = Some(secret.clone());
+                }
+            }
+            None => {
+                if let Err(e) = self.credentials.delete(SecretKind::HostingToken) {
+                    log::warn!("Failed to remove the hosting token from the {}: {}", name, e);
+                }
+            }
+        }
+        self.hosting_token = token;
+    }
+
+    /// Name of the credential store, for the settings view
+    pub fn credential_store_name(&self) -> &'static str {
+        self.credentials.name()
+    }
+
+    pub fn has_token(&self) -> bool {
+        self.git_token.is_some()
+    }
+
+    /// Token for the GitHub or GitLab API, used to show pull requests
+    pub fn hosting_token(&self) -> Option<String> {
+        self.hosting_token.clone()
     }
 
     pub fn save(&self, _cx: &mut Context<Self>) {
@@ -97,11 +459,74 @@ impl SettingsState {
         }
     }
 
+    /// Set the repository whose overrides apply, or `None` when it's closed
+    pub fn set_current_repo(&mut self, path: Option<&Path>, cx: &mut Context<Self>) {
+        self.current_repo = path.map(|p| p.to_string_lossy().to_string());
+        if self.current_repo.is_none() {
+            self.tab = SettingsTab::General;
+        }
+        cx.notify();
+    }
+
+    pub fn has_current_repo(&self) -> bool {
+        self.current_repo.is_some()
+    }
+
+    /// Overrides for the open repository
+    pub fn repo_settings(&self) -> RepoSettings {
+        self.current_repo
+            .as_ref()
+            .and_then(|key| self.data.repositories.get(key))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Change the open repository's overrides, dropping the entry once
+    /// nothing is overridden
+    pub fn update_repo_settings(
+        &mut self,
+        f: impl FnOnce(&mut RepoSettings),
+        cx: &mut Context<Self>,
+    ) {
+        let Some(key) = self.current_repo.clone() else {
+            return;
+        };
+        let repo = self.data.repositories.entry(key.clone()).or_default();
+        f(repo);
+        if repo.is_empty() {
+            self.data.repositories.remove(&key);
+        }
+        self.save(cx);
+        cx.notify();
+    }
+
+    // Effective values: the open repository's override, else the global setting
+
+    pub fn merge_mode(&self) -> MergeMode {
+        self.repo_settings().merge_mode.unwrap_or(self.data.merge_mode)
+    }
+
+    pub fn auth_mode(&self) -> AuthMode {
+        self.repo_settings()
+            .git_auth_mode
+            .unwrap_or(self.data.git_auth_mode)
+    }
+
+    pub fn default_remote(&self) -> String {
+        self.repo_settings()
+            .default_remote
+            .unwrap_or_else(|| "origin".to_string())
+    }
+
+    pub fn identity(&self) -> Identity {
+        self.repo_settings().identity()
+    }
+
     pub fn get_auth_credentials(&self) -> Option<GitCredentials> {
-        match self.data.git_auth_mode {
+        match self.auth_mode() {
             AuthMode::Https => {
                 let username = self.data.git_username.clone()?;
-                let password = self.data.git_token.clone()?;
+                let password = self.git_token.clone()?;
                 Some(GitCredentials { username, password })
             }
             AuthMode::Ssh => None, // SSH uses agent
@@ -122,7 +547,13 @@ impl SettingsState {
     }
 
     pub fn set_token(&mut self, token: Option<String>, cx: &mut Context<Self>) {
-        self.data.git_token = token;
+        self.store_token(token);
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_hosting_token(&mut self, token: Option<String>, cx: &mut Context<Self>) {
+        self.store_hosting_token(token);
         self.save(cx);
         cx.notify();
     }
@@ -133,15 +564,153 @@ impl SettingsState {
         cx.notify();
     }
 
-    pub fn set_theme(&mut self, theme: Theme, cx: &mut Context<Self>) {
+    /// Switch to a built-in theme, clearing any user theme
+    pub fn set_theme(&mut self, theme: ThemeMode, cx: &mut Context<Self>) {
         self.data.theme = theme;
+        self.data.user_theme = None;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_user_theme(&mut self, name: String, cx: &mut Context<Self>) {
+        self.data.user_theme = Some(name);
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_graph_palette(&mut self, palette: GraphPalette, cx: &mut Context<Self>) {
+        self.data.graph_palette = palette;
         self.save(cx);
         cx.notify();
     }
 
+    pub fn set_tab(&mut self, tab: SettingsTab, cx: &mut Context<Self>) {
+        self.tab = tab;
+        cx.notify();
+    }
+
+    pub fn set_theme_picker_open(&mut self, open: bool, cx: &mut Context<Self>) {
+        self.theme_picker_open = open;
+        cx.notify();
+    }
+
     pub fn set_locale(&mut self, locale: Locale, cx: &mut Context<Self>) {
         self.data.locale = locale;
         self.save(cx);
         cx.notify();
     }
+
+    pub fn set_restore_session(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.data.restore_session = enabled;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_fetch_on_open(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.data.fetch_on_open = enabled;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_fetch_prune(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.data.fetch_prune = enabled;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_auto_fetch_interval(&mut self, interval: AutoFetchInterval, cx: &mut Context<Self>) {
+        self.data.auto_fetch_interval = interval;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_max_diff_size(&mut self, limit: DiffSizeLimit, cx: &mut Context<Self>) {
+        self.data.max_diff_size = limit;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_diff_options(&mut self, options: DiffDisplayOptions, cx: &mut Context<Self>) {
+        self.data.diff_options = options;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_autostash(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.data.autostash = enabled;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_preview_operations(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.data.preview_operations = enabled;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_fetch_avatars(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.data.fetch_avatars = enabled;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_editor_command(&mut self, command: Option<String>, cx: &mut Context<Self>) {
+        self.data.editor_command = command;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_trash_discards(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.data.trash_discards = enabled;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_control_socket(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.data.control_socket = enabled;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_toast_position(&mut self, position: ToastPosition, cx: &mut Context<Self>) {
+        self.data.toast_position = position;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_sticky_errors(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.data.sticky_errors = enabled;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_signing_policy(&mut self, policy: SigningPolicy, cx: &mut Context<Self>) {
+        self.data.signing_policy = policy;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_commit_lint(&mut self, mode: CommitLintMode, cx: &mut Context<Self>) {
+        self.data.commit_lint = mode;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_run_hooks(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.data.run_hooks = enabled;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_protected_branches(&mut self, branches: Vec<String>, cx: &mut Context<Self>) {
+        self.data.protected_branches = branches;
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn set_allowed_signers(&mut self, signers: Vec<String>, cx: &mut Context<Self>) {
+        self.data.allowed_signers = signers;
+        self.save(cx);
+        cx.notify();
+    }
 }
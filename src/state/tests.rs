@@ -0,0 +1,1628 @@
+//! Headless tests for `GitState` against throwaway repositories, driven
+//! through gpui's test context the way views drive it
+
+use crate::git::test_repo::TestRepo;
+use crate::git::{
+    BranchInfo, CommitGraphData, ConflictStrategy, DiffDisplayOptions, DiffLineType, DiscardScope,
+    FileDiff, FileFilter, FileStatus, Git2Backend, GraphFilter, HunkResolution, Identity,
+    IdentityScope, IgnorePattern, InterruptedKind, LfsChange, MboxOptions, MergeMode, PickKind,
+    RefMapCache, RepositoryBackend, RepositoryInfo, SignatureStatus, StatusGroup, TagInfo,
+    VcsBackend,
+};
+use crate::state::{
+    avatar_hash, avatar_url, editor_command, AvatarState, CommitOutcome, GitState, HunkPreview,
+    SignatureCheck, WatcherEvent, DIFF_PAGE_LINES,
+};
+use git2::{Oid, Repository};
+use gpui::{AppContext, Entity, TestAppContext};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn open(repo: &TestRepo, cx: &mut TestAppContext) -> Entity<GitState> {
+    let state = cx.new(|_| GitState::new());
+    state.update(cx, |state, cx| {
+        state
+            .open_repository(repo.path(), cx)
+            .expect("open repository")
+    });
+    state
+}
+
+#[gpui::test]
+fn refreshes_keep_the_selection_of_files_that_still_have_changes(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file("a.txt", "a\n", "Add a");
+    repo.commit_file("b.txt", "b\n", "Add b");
+    repo.write("a.txt", "a changed\n");
+    repo.write("b.txt", "b changed\n");
+    let state = open(&repo, cx);
+
+    state.update(cx, |state, cx| {
+        state.toggle_file_selection("a.txt", cx);
+        state.toggle_file_selection("b.txt", cx);
+    });
+    // b.txt goes back to its committed content outside the app
+    repo.write("b.txt", "b\n");
+    state.update(cx, |state, cx| state.refresh(cx));
+    state.read_with(cx, |state, _| {
+        assert_eq!(state.selected_files, vec!["a.txt".to_string()]);
+    });
+
+    // The watcher refreshes the same way
+    repo.write("a.txt", "a\n");
+    repo.write("new.txt", "new\n");
+    let head_change = state.update(cx, |state, cx| {
+        state.handle_watcher_events(&[WatcherEvent::Workdir], cx)
+    });
+    assert_eq!(head_change, None);
+    state.read_with(cx, |state, _| {
+        let paths: Vec<_> = state.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["new.txt"]);
+        assert!(state.selected_files.is_empty());
+    });
+}
+
+#[gpui::test]
+fn changes_made_outside_the_app_are_picked_up(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file("a.txt", "one\n", "Add a");
+    repo.create_branch("topic");
+    let state = open(&repo, cx);
+
+    repo.checkout("topic");
+    let head_change = state.update(cx, |state, cx| {
+        state.handle_watcher_events(&[WatcherEvent::Head], cx)
+    });
+    assert_eq!(head_change.as_deref(), Some("topic"));
+    state.read_with(cx, |state, _| {
+        assert_eq!(state.current_branch(), Some("topic"));
+    });
+
+    // Staged by another git process while the app keeps its handle open
+    repo.write("a.txt", "two\n");
+    repo.write("b.txt", "new\n");
+    let other = Repository::open(repo.path()).unwrap();
+    let mut index = other.index().unwrap();
+    index.add_path(Path::new("b.txt")).unwrap();
+    index.write().unwrap();
+
+    // Staging in the app keeps the outside change instead of overwriting it
+    state.update(cx, |state, cx| state.stage_file("a.txt", cx).unwrap());
+    state.read_with(cx, |state, _| {
+        let mut staged: Vec<_> = state
+            .staged_files()
+            .iter()
+            .map(|f| f.path.clone())
+            .collect();
+        staged.sort();
+        assert_eq!(staged, ["a.txt", "b.txt"]);
+    });
+
+    // New refs show up after the watcher reports them
+    let head = other.head().unwrap().peel_to_commit().unwrap();
+    other.branch("outside", &head, false).unwrap();
+    other
+        .tag_lightweight("v2", head.as_object(), false)
+        .unwrap();
+    state.update(cx, |state, cx| {
+        state.handle_watcher_events(&[WatcherEvent::Refs], cx);
+    });
+    state.read_with(cx, |state, _| {
+        assert!(state.branches.iter().any(|branch| branch.name == "outside"));
+        let top = &state.commits.as_ref().unwrap().nodes[0].commit;
+        assert_eq!(top.tags, ["v2"]);
+    });
+}
+
+#[gpui::test]
+fn commit_graph_loads_in_pages_that_continue_lanes_and_edges(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let main = repo.default_branch();
+    repo.commit_file("base.txt", "base\n", "Base");
+    repo.create_branch("side");
+    repo.checkout("side");
+    repo.commit_file("side.txt", "side\n", "Side");
+    repo.checkout(&main);
+    for n in 0..110 {
+        repo.commit_file("counter.txt", &format!("{}\n", n), &format!("Commit {}", n));
+    }
+    let state = open(&repo, cx);
+    let node_count = |state: &GitState| state.commits.as_ref().unwrap().nodes.len();
+    state.read_with(cx, |state, _| assert_eq!(node_count(state), 100));
+
+    // The side branch lane stays open past the end of the first page
+    state.update(cx, |state, cx| {
+        assert!(!state.merge_branch("side", MergeMode::NoFf, cx).unwrap());
+        state.load_more_commits(cx).unwrap();
+    });
+
+    let whole = CommitGraphData::build(&repo.repo, &GraphFilter::default(), 200, 0).unwrap();
+    state.read_with(cx, |state, _| {
+        let paged = state.commits.as_ref().unwrap();
+        let layout = |graph: &CommitGraphData| -> Vec<_> {
+            graph
+                .nodes
+                .iter()
+                .map(|node| (node.commit.sha.clone(), node.row, node.column))
+                .collect()
+        };
+        assert_eq!(node_count(state), 114);
+        assert_eq!(layout(paged), layout(&whole));
+        assert_eq!(paged.edges, whole.edges);
+        assert_eq!(paged.max_column, 1);
+    });
+}
+
+#[gpui::test]
+fn refs_and_search_results_focus_their_commit_in_the_graph(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let tagged = repo.commit_file("notes.txt", "one\n", "Write the first note");
+    let target = repo.repo.find_object(tagged, None).unwrap();
+    repo.repo.tag_lightweight("v1.0", &target, false).unwrap();
+    drop(target);
+    repo.create_branch("feature");
+    for n in 0..120 {
+        repo.commit_file("counter.txt", &format!("{}\n", n), &format!("Commit {}", n));
+    }
+    let head = repo.commit_file("notes.txt", "two\n", "Write the second note");
+    let state = open(&repo, cx);
+    let sha = tagged.to_string();
+
+    // Focusing a commit past the first page loads history until it's shown
+    state.update(cx, |state, cx| {
+        state.focus_ref("refs/tags/v1.0", cx).unwrap()
+    });
+    state.read_with(cx, |state, _| {
+        assert_eq!(state.focused_commit.as_deref(), Some(sha.as_str()));
+        assert!(state.commits.as_ref().unwrap().nodes.len() > 100);
+    });
+
+    state.update(cx, |state, cx| {
+        state.focus_ref(&head.to_string()[..7], cx).unwrap();
+        assert_eq!(state.focused_commit, Some(head.to_string()));
+        state.focus_ref("feature", cx).unwrap();
+        assert_eq!(state.focused_commit.as_deref(), Some(sha.as_str()));
+        let error = state.focus_ref("missing", cx).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No branch, tag or commit named 'missing'"
+        );
+
+        let results = state.search_commits("first note", 50);
+        assert_eq!(results.len(), 1);
+        state.select_commit(results[0].clone(), cx).unwrap();
+        assert_eq!(state.focused_commit.as_deref(), Some(sha.as_str()));
+        assert_eq!(state.selected_commit.as_ref().map(|c| &c.sha), Some(&sha));
+    });
+}
+
+#[gpui::test]
+fn merge_request_defaults_to_configured_mode_and_keeps_errors(cx: &mut TestAppContext) {
+    let repo = TestRepo::new().with_conflicting_branches();
+    let state = open(&repo, cx);
+
+    state.update(cx, |state, cx| {
+        state.merge_mode = MergeMode::NoFf;
+        state.request_merge_branch("feature", cx);
+    });
+    state.read_with(cx, |state, _| {
+        let request = state.pending_merge.as_ref().unwrap();
+        assert_eq!(request.branch, "feature");
+        assert_eq!(request.mode, MergeMode::NoFf);
+    });
+    state.update(cx, |state, cx| state.cancel_merge(cx));
+    state.read_with(cx, |state, _| assert!(state.pending_merge.is_none()));
+
+    // A failed merge keeps the request open with the error
+    let result = state.update(cx, |state, cx| {
+        state.request_merge_branch("feature", cx);
+        state.set_merge_request_mode(MergeMode::FfOnly, cx);
+        state.confirm_merge(cx)
+    });
+    assert!(result.is_err());
+    state.read_with(cx, |state, _| {
+        let request = state.pending_merge.as_ref().unwrap();
+        assert!(request.error.is_some());
+        assert!(state.conflict_info.is_none());
+    });
+}
+
+#[gpui::test]
+fn conflicted_merge_resolves_and_completes(cx: &mut TestAppContext) {
+    let repo = TestRepo::new().with_conflicting_branches();
+    let state = open(&repo, cx);
+
+    let conflicted = state.update(cx, |state, cx| {
+        state.request_merge_branch("feature", cx);
+        state.set_merge_request_mode(MergeMode::NoFf, cx);
+        state.confirm_merge(cx).unwrap()
+    });
+
+    assert!(conflicted);
+    state.read_with(cx, |state, _| {
+        assert!(state.pending_merge.is_none());
+        let info = state.conflict_info.as_ref().unwrap();
+        assert!(info.is_merging);
+        assert_eq!(info.conflicted_files.len(), 1);
+        assert_eq!(
+            info.merge_message.as_deref(),
+            Some(format!("Merge branch 'feature' into {}", repo.default_branch()).as_str())
+        );
+    });
+
+    state.update(cx, |state, cx| {
+        let skipped = state
+            .resolve_all_conflicts(ConflictStrategy::Theirs, cx)
+            .unwrap();
+        assert_eq!(skipped, 0);
+        state
+            .complete_merge(Some("Merge feature work"), cx)
+            .unwrap();
+    });
+
+    state.read_with(cx, |state, _| assert!(state.conflict_info.is_none()));
+    assert_eq!(repo.read("shared.txt"), "theirs\n");
+    let head = repo.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.parent_count(), 2);
+    assert_eq!(head.message(), Some("Merge feature work"));
+}
+
+/// Feature commits picked onto the default branch, the second of which
+/// conflicts with it
+fn cherry_pick_commits(repo: &mut TestRepo) -> Vec<String> {
+    let main = repo.default_branch();
+    let conflicting = repo.repo.revparse_single("feature").unwrap().id();
+    repo.checkout("feature");
+    let before = repo.commit_file("a.txt", "a\n", "Add a");
+    let after = repo.commit_file("b.txt", "b\n", "Add b");
+    repo.checkout(&main);
+    [before, conflicting, after]
+        .iter()
+        .map(|oid| oid.to_string())
+        .collect()
+}
+
+#[gpui::test]
+fn cherry_pick_sequence_stops_on_conflicts_and_aborts_or_continues(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new().with_conflicting_branches();
+    let shas = cherry_pick_commits(&mut repo);
+    let original = repo.repo.head().unwrap().target().unwrap();
+    let state = open(&repo, cx);
+
+    // Aborting goes back to where the sequence started
+    state.update(cx, |state, cx| state.cherry_pick_sequence(shas.clone(), cx));
+    cx.run_until_parked();
+    state.update(cx, |state, cx| state.abort_cherry_pick(cx).unwrap());
+    state.read_with(cx, |state, _| {
+        assert!(state.cherry_pick_progress.is_none());
+        assert!(state.conflict_info.is_none());
+    });
+    assert_eq!(repo.repo.state(), git2::RepositoryState::Clean);
+    assert_eq!(repo.repo.head().unwrap().target(), Some(original));
+    assert_eq!(repo.read("shared.txt"), "ours\n");
+    assert!(!repo.path().join("a.txt").exists());
+
+    state.update(cx, |state, cx| state.cherry_pick_sequence(shas, cx));
+    cx.run_until_parked();
+    state.read_with(cx, |state, _| {
+        let progress = state.cherry_pick_progress.as_ref().unwrap();
+        assert_eq!(progress.completed, 1);
+        assert!(progress.conflicted.is_some());
+        assert!(progress.is_running());
+        assert!(state.conflict_info.is_some());
+    });
+    assert_eq!(repo.repo.state(), git2::RepositoryState::CherryPick);
+
+    // Continuing needs the conflicts resolved first
+    state.update(cx, |state, cx| {
+        assert!(state.continue_cherry_pick(cx).is_err());
+        state
+            .resolve_all_conflicts(ConflictStrategy::Theirs, cx)
+            .unwrap();
+        state.continue_cherry_pick(cx).unwrap();
+    });
+    cx.run_until_parked();
+
+    state.read_with(cx, |state, _| {
+        let progress = state.cherry_pick_progress.as_ref().unwrap();
+        assert_eq!((progress.completed, progress.total), (3, 3));
+        assert!(progress.conflicted.is_none() && progress.error.is_none());
+    });
+    assert_eq!(repo.repo.state(), git2::RepositoryState::Clean);
+    assert_eq!(repo.read("shared.txt"), "theirs\n");
+    let head = repo.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.summary(), Some("Add b"));
+    let resolved = head.parent(0).unwrap();
+    assert!(resolved.message().unwrap().starts_with("Change on feature"));
+    assert_eq!(resolved.parent(0).unwrap().summary(), Some("Add a"));
+}
+
+#[gpui::test]
+fn conflicted_picks_continue_with_the_original_message_or_abort(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new().with_conflicting_branches();
+    let feature = repo
+        .repo
+        .revparse_single("feature")
+        .unwrap()
+        .id()
+        .to_string();
+    let first = repo.commit_file("a.txt", "2\n", "Change a");
+    repo.commit_file("a.txt", "3\n", "Change a again");
+    let state = open(&repo, cx);
+
+    // A reverted commit that later ones built on conflicts, and aborting
+    // leaves HEAD as it was
+    let conflicted = state.update(cx, |state, cx| {
+        state.revert_commit(&first.to_string(), None, cx).unwrap()
+    });
+    assert!(conflicted);
+    state.read_with(cx, |state, _| {
+        assert_eq!(
+            state.interrupted_pick.as_ref().unwrap().kind,
+            PickKind::Revert
+        );
+        assert_eq!(
+            state.conflict_info.as_ref().unwrap().pick,
+            Some(PickKind::Revert)
+        );
+    });
+    state.update(cx, |state, cx| state.abort_pick(cx).unwrap());
+    state.read_with(cx, |state, _| {
+        assert!(state.interrupted_pick.is_none());
+        assert!(state.conflict_info.is_none());
+    });
+    assert_eq!(repo.repo.state(), git2::RepositoryState::Clean);
+    assert_eq!(repo.read("a.txt"), "3\n");
+
+    let conflicted = state.update(cx, |state, cx| state.cherry_pick(&feature, cx).unwrap());
+    assert!(conflicted);
+    state.read_with(cx, |state, _| {
+        let pick = state.interrupted_pick.as_ref().unwrap();
+        assert_eq!(
+            (pick.kind, pick.sha.as_str()),
+            (PickKind::CherryPick, feature.as_str())
+        );
+        assert_eq!(pick.summary, "Change on feature");
+        let info = state.conflict_info.as_ref().unwrap();
+        assert_eq!(info.pick, Some(PickKind::CherryPick));
+        assert_eq!(info.source_branch.as_deref(), Some(&feature[..7]));
+    });
+
+    state.update(cx, |state, cx| {
+        state
+            .resolve_all_conflicts(ConflictStrategy::Theirs, cx)
+            .unwrap();
+        // Resolved, but still waiting to be committed
+        assert!(state.interrupted_pick.is_some());
+        state.continue_pick(cx).unwrap();
+        assert!(state.interrupted_pick.is_none());
+    });
+    assert_eq!(repo.repo.state(), git2::RepositoryState::Clean);
+    let head = repo.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(
+        head.message(),
+        Some(
+            format!(
+                "Change on feature\n\n(cherry picked from commit {})",
+                &feature[..7]
+            )
+            .as_str()
+        )
+    );
+}
+
+#[gpui::test]
+fn operations_left_running_by_the_cli_can_be_finished_or_aborted(cx: &mut TestAppContext) {
+    let repo = TestRepo::new().with_conflicting_branches();
+    let feature = repo.repo.find_reference("refs/heads/feature").unwrap();
+    let feature = repo.repo.reference_to_annotated_commit(&feature).unwrap();
+    repo.repo.merge(&[&feature], None, None).unwrap();
+    repo.write("shared.txt", "both\n");
+    let mut index = repo.repo.index().unwrap();
+    index.add_path(Path::new("shared.txt")).unwrap();
+    index.write().unwrap();
+    let state = open(&repo, cx);
+
+    // A merge resolved with the CLI commits with the message git saved
+    state.read_with(cx, |state, _| {
+        assert!(state.conflict_info.is_none());
+        let operation = state.interrupted_operation.as_ref().unwrap();
+        assert_eq!(operation.kind, InterruptedKind::Merge);
+        assert!(operation.can_commit());
+    });
+    state.update(cx, |state, cx| {
+        state.commit_interrupted_operation(cx).unwrap()
+    });
+    state.read_with(cx, |state, _| {
+        assert!(state.interrupted_operation.is_none())
+    });
+    assert_eq!(repo.repo.state(), git2::RepositoryState::Clean);
+    let head = repo.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.parent_count(), 2);
+    assert!(head
+        .message()
+        .unwrap()
+        .starts_with("Merge branch 'feature'"));
+    assert!(!head.message().unwrap().contains('#'));
+
+    // What `git rebase` leaves behind when it stops part way
+    let main = repo.default_branch();
+    let orig_head = head.id();
+    let onto = head.parent_id(0).unwrap();
+    let rebase_dir = repo.repo.path().join("rebase-merge");
+    fs::create_dir_all(&rebase_dir).unwrap();
+    fs::write(
+        rebase_dir.join("head-name"),
+        format!("refs/heads/{}\n", main),
+    )
+    .unwrap();
+    fs::write(rebase_dir.join("orig-head"), format!("{}\n", orig_head)).unwrap();
+    repo.repo.set_head_detached(onto).unwrap();
+    repo.repo
+        .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+        .unwrap();
+    state.update(cx, |state, cx| state.refresh(cx));
+
+    state.read_with(cx, |state, _| {
+        let operation = state.interrupted_operation.as_ref().unwrap();
+        assert_eq!(operation.kind, InterruptedKind::Rebase);
+        assert_eq!(operation.head_name.as_deref(), Some(main.as_str()));
+        assert!(!operation.can_commit());
+    });
+    state.update(cx, |state, cx| {
+        state.abort_interrupted_operation(cx).unwrap()
+    });
+    state.read_with(cx, |state, _| {
+        assert!(state.interrupted_operation.is_none())
+    });
+    assert_eq!(repo.repo.state(), git2::RepositoryState::Clean);
+    assert_eq!(repo.default_branch(), main);
+    assert_eq!(repo.repo.head().unwrap().target(), Some(orig_head));
+    assert_eq!(repo.read("shared.txt"), "both\n");
+}
+
+#[gpui::test]
+fn signatures_and_hunk_previews_load_in_the_background_and_are_cached(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let sha = repo
+        .commit_file("a.txt", "one\ntwo\nthree\n", "Add a")
+        .to_string();
+    repo.write("a.txt", "one\n2\nthree\n");
+    let state = open(&repo, cx);
+    let graph_revision = state.read_with(cx, |state, _| state.revisions().graph);
+
+    state.update(cx, |state, cx| {
+        let checks = state.signature_checks(std::slice::from_ref(&sha), cx);
+        assert!(matches!(checks[..], [SignatureCheck::Loading]));
+        let preview = state.hunk_preview("a.txt", false, cx);
+        assert!(matches!(preview, HunkPreview::Loading));
+    });
+    cx.run_until_parked();
+
+    state.update(cx, |state, cx| {
+        let checks = state.signature_checks(std::slice::from_ref(&sha), cx);
+        let signature = checks.into_iter().next().unwrap().ready().unwrap();
+        assert_eq!(signature.sha, sha);
+        assert_eq!(signature.status, SignatureStatus::Unsigned);
+        assert_eq!(signature.status.trust(), None);
+        assert!(state.revisions().graph > graph_revision);
+
+        let HunkPreview::Ready(lines) = state.hunk_preview("a.txt", false, cx) else {
+            panic!("preview not loaded");
+        };
+        assert!(lines[0].content.starts_with("@@"));
+        assert!(lines.iter().any(|l| l.content == "2\n"));
+
+        // A status refresh drops the cached preview
+        state.refresh_status(cx);
+        let preview = state.hunk_preview("a.txt", false, cx);
+        assert!(matches!(preview, HunkPreview::Loading));
+    });
+}
+
+#[gpui::test]
+fn discards_back_up_to_the_trash_with_or_without_the_dialog(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file("a.txt", "a\n", "Add a");
+    repo.write("a.txt", "work in progress\n");
+    repo.write("new.txt", "new\n");
+    let state = open(&repo, cx);
+
+    state.update(cx, |state, cx| {
+        state.request_discard(DiscardScope::File("a.txt".to_string()), cx);
+    });
+    state.read_with(cx, |state, _| {
+        let request = state.pending_discard.as_ref().unwrap();
+        assert_eq!(request.files, vec!["a.txt".to_string()]);
+        assert!(request.backup);
+    });
+    state.update(cx, |state, cx| state.confirm_discard(cx).unwrap());
+    assert_eq!(repo.read("a.txt"), "a\n");
+
+    let entries = state.read_with(cx, |state, _| state.trash_entries().unwrap());
+    assert_eq!(entries.len(), 1);
+    state.update(cx, |state, cx| {
+        state.restore_trash_entry(&entries[0], cx).unwrap()
+    });
+    assert_eq!(repo.read("a.txt"), "work in progress\n");
+    assert!(state.read_with(cx, |state, _| state.trash_entries().unwrap().is_empty()));
+
+    // Review mode discards right away, from the diff it shows
+    state.read_with(cx, |state, _| {
+        let diff = state.working_diff("a.txt").unwrap();
+        assert!(diff.lines.iter().any(|l| l.content == "work in progress\n"));
+        // Untracked files show their whole content
+        let diff = state.working_diff("new.txt").unwrap();
+        assert!(diff.lines.iter().any(|l| l.content == "new\n"));
+    });
+    state.update(cx, |state, cx| {
+        state.discard_file_now("a.txt", cx).unwrap();
+        assert!(state.pending_discard.is_none());
+    });
+    assert_eq!(repo.read("a.txt"), "a\n");
+    let entries = state.read_with(cx, |state, _| state.trash_entries().unwrap());
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].files, vec!["a.txt".to_string()]);
+}
+
+#[gpui::test]
+fn selected_files_and_directories_are_staged_and_discarded_together(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file("a.txt", "a\n", "Add a");
+    repo.commit_file("b.txt", "b\n", "Add b");
+    repo.commit_file("src/a.rs", "a\n", "Add src/a");
+    repo.commit_file("src/b.rs", "b\n", "Add src/b");
+    repo.commit_file("srcfile.txt", "s\n", "Add srcfile");
+    repo.write("a.txt", "changed a\n");
+    fs::remove_file(repo.path().join("b.txt")).unwrap();
+    repo.write("c.txt", "new\n");
+    repo.write("src/a.rs", "changed a\n");
+    fs::remove_file(repo.path().join("src/b.rs")).unwrap();
+    repo.write("src/new.rs", "new\n");
+    repo.write("srcfile.txt", "changed s\n");
+    let state = open(&repo, cx);
+    let staged = |state: &GitState| -> Vec<String> {
+        let mut paths: Vec<_> = state
+            .staged_files()
+            .iter()
+            .map(|f| f.path.clone())
+            .collect();
+        paths.sort();
+        paths
+    };
+
+    let paths = ["a.txt", "b.txt", "c.txt"].map(str::to_string);
+    state.update(cx, |state, cx| {
+        state.select_files(&paths, cx);
+        state.stage_files(&paths, cx).unwrap();
+    });
+    state.read_with(cx, |state, _| assert_eq!(staged(state), paths));
+    state.update(cx, |state, cx| state.unstage_files(&paths, cx).unwrap());
+    state.read_with(cx, |state, _| assert!(state.staged_files().is_empty()));
+
+    // The untracked file isn't part of the discard and stays selected
+    state.update(cx, |state, cx| {
+        state.request_discard(DiscardScope::Files(paths.to_vec()), cx);
+        assert_eq!(
+            state.pending_discard.as_ref().unwrap().files,
+            ["a.txt", "b.txt"]
+        );
+        state.set_discard_backup(false, cx);
+        state.confirm_discard(cx).unwrap();
+    });
+    assert_eq!(repo.read("a.txt"), "a\n");
+    assert_eq!(repo.read("b.txt"), "b\n");
+    assert_eq!(repo.read("c.txt"), "new\n");
+    state.read_with(cx, |state, _| assert_eq!(state.selected_files, ["c.txt"]));
+
+    // Additions and deletions under a directory, and nothing beside it
+    state.update(cx, |state, cx| state.stage_directory("src", cx).unwrap());
+    state.read_with(cx, |state, _| {
+        assert_eq!(staged(state), ["src/a.rs", "src/b.rs", "src/new.rs"]);
+    });
+    state.update(cx, |state, cx| {
+        state.unstage_directory("src", cx).unwrap();
+        state.request_discard(DiscardScope::Directory("src".to_string()), cx);
+        assert_eq!(
+            state.pending_discard.as_ref().unwrap().files,
+            ["src/a.rs", "src/b.rs"]
+        );
+        state.confirm_discard(cx).unwrap();
+    });
+    assert_eq!(repo.read("src/a.rs"), "a\n");
+    assert_eq!(repo.read("src/b.rs"), "b\n");
+    assert_eq!(repo.read("src/new.rs"), "new\n");
+    assert_eq!(repo.read("srcfile.txt"), "changed s\n");
+}
+
+#[gpui::test]
+fn conflict_hunks_resolve_with_chosen_sides(cx: &mut TestAppContext) {
+    let repo = TestRepo::new().with_conflicting_branches();
+    let state = open(&repo, cx);
+    state.update(cx, |state, cx| {
+        assert!(state.merge_branch("feature", MergeMode::NoFf, cx).unwrap());
+    });
+
+    let document = state.read_with(cx, |state, _| {
+        state.conflict_document("shared.txt").unwrap()
+    });
+    assert_eq!(document.hunk_count(), 1);
+    let hunk = document.hunks().next().unwrap();
+    assert_eq!(hunk.ours, vec!["ours\n".to_string()]);
+    assert_eq!(hunk.theirs, vec!["theirs\n".to_string()]);
+
+    let content = document.resolve(&[Some(HunkResolution::Both)]).unwrap();
+    state.update(cx, |state, cx| {
+        state
+            .resolve_conflict_with_content("shared.txt", &content, cx)
+            .unwrap();
+    });
+
+    assert_eq!(repo.read("shared.txt"), "ours\ntheirs\n");
+    state.read_with(cx, |state, _| assert!(state.conflict_info.is_none()));
+}
+
+/// Reads through libgit2 but reports a clean working tree
+struct CleanStatusBackend;
+
+struct CleanStatusRepository(Box<dyn RepositoryBackend>);
+
+impl VcsBackend for CleanStatusBackend {
+    fn name(&self) -> &'static str {
+        "clean-status"
+    }
+
+    fn open(&self, path: &Path) -> anyhow::Result<Box<dyn RepositoryBackend>> {
+        Ok(Box::new(CleanStatusRepository(Git2Backend.open(path)?)))
+    }
+}
+
+impl RepositoryBackend for CleanStatusRepository {
+    fn info(&self) -> anyhow::Result<RepositoryInfo> {
+        self.0.info()
+    }
+
+    fn status(&self) -> anyhow::Result<Vec<FileStatus>> {
+        Ok(Vec::new())
+    }
+
+    fn commit_graph(
+        &self,
+        filter: &GraphFilter,
+        limit: usize,
+        offset: usize,
+        refs: &RefMapCache,
+    ) -> anyhow::Result<CommitGraphData> {
+        self.0.commit_graph(filter, limit, offset, refs)
+    }
+
+    fn file_diff(
+        &self,
+        path: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> anyhow::Result<FileDiff> {
+        self.0.file_diff(path, max_size, options)
+    }
+
+    fn unstaged_diff(
+        &self,
+        path: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> anyhow::Result<FileDiff> {
+        self.0.unstaged_diff(path, max_size, options)
+    }
+
+    fn commit_diff(
+        &self,
+        sha: &str,
+        max_size: u64,
+        options: DiffDisplayOptions,
+    ) -> anyhow::Result<Vec<FileDiff>> {
+        self.0.commit_diff(sha, max_size, options)
+    }
+
+    fn branches(&self) -> anyhow::Result<Vec<BranchInfo>> {
+        self.0.branches()
+    }
+
+    fn tags(&self) -> anyhow::Result<Vec<TagInfo>> {
+        self.0.tags()
+    }
+}
+
+#[gpui::test]
+fn state_reads_through_the_configured_backend(cx: &mut TestAppContext) {
+    let repo = TestRepo::new();
+    repo.write("untracked.txt", "new\n");
+
+    let state = cx.new(|_| GitState::with_backend(Arc::new(CleanStatusBackend)));
+    state.update(cx, |state, cx| {
+        state.open_repository(repo.path(), cx).unwrap()
+    });
+
+    state.read_with(cx, |state, _| {
+        assert_eq!(state.backend_name(), "clean-status");
+        assert!(state.files.is_empty());
+        assert_eq!(state.commits.as_ref().unwrap().nodes.len(), 1);
+    });
+}
+
+#[gpui::test]
+fn mbox_export_numbers_patches_after_cover_letter(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let first = repo.commit_file("a.txt", "a\n", "Add a");
+    let second = repo.commit_file("b.txt", "b\n", "Add b\n\nWith a body.");
+    let state = open(&repo, cx);
+
+    let options = MboxOptions {
+        cover_letter: true,
+        ..MboxOptions::default()
+    };
+    let shas = vec![first.to_string(), second.to_string()];
+    let mbox = state.read_with(cx, |state, _| state.export_mbox(&shas, &options).unwrap());
+
+    assert!(mbox.starts_with(&format!("From {} ", Oid::zero())));
+    assert!(mbox.contains("Subject: [PATCH 0/2] *** SUBJECT HERE ***"));
+    assert!(mbox.contains("Test (2):\n  Add a\n  Add b\n"));
+    assert!(mbox.contains(&format!("From {} Mon Sep 17", first)));
+    assert!(mbox.contains("Subject: [PATCH 1/2] Add a\n"));
+    assert!(mbox.contains("Subject: [PATCH 2/2] Add b\n"));
+    assert!(mbox.contains("With a body.\n---\n"));
+    assert!(mbox.contains("+++ b/b.txt\n@@ -0,0 +1 @@\n+b\n"));
+}
+
+#[gpui::test]
+fn stash_applies_onto_another_branch_only_from_a_clean_tree(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file("a.txt", "a\n", "Add a");
+    repo.create_branch("topic");
+    repo.write("a.txt", "work in progress\n");
+    let state = open(&repo, cx);
+    let stash_onto_topic = |cx: &mut TestAppContext| {
+        state.update(cx, |state, cx| {
+            state.refresh(cx);
+            state.request_stash_onto_branch(0, cx);
+            state.set_stash_branch_target("topic", cx);
+            state.confirm_stash_branch(cx)
+        })
+    };
+
+    state.update(cx, |state, cx| {
+        state
+            .stash_save(Some("wip"), git2::StashFlags::DEFAULT, &[], cx)
+            .unwrap();
+    });
+    repo.write("a.txt", "still editing\n");
+    assert!(stash_onto_topic(cx).is_err());
+    state.read_with(cx, |state, _| {
+        assert!(state.pending_stash_branch.as_ref().unwrap().error.is_some());
+        assert_ne!(state.current_branch(), Some("topic"));
+        assert_eq!(state.stashes.len(), 1);
+    });
+    assert_eq!(repo.read("a.txt"), "still editing\n");
+
+    repo.write("a.txt", "a\n");
+    assert!(!stash_onto_topic(cx).unwrap());
+    state.read_with(cx, |state, _| {
+        assert!(state.pending_stash_branch.is_none());
+        assert_eq!(state.current_branch(), Some("topic"));
+        assert!(state.stashes.is_empty());
+    });
+    assert_eq!(repo.read("a.txt"), "work in progress\n");
+}
+
+#[gpui::test]
+fn identities_sign_commits_or_write_the_repository_config(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file("a.txt", "a\n", "Add a");
+    repo.write("a.txt", "b\n");
+    let state = open(&repo, cx);
+    let config = || {
+        repo.repo
+            .config()
+            .unwrap()
+            .open_level(git2::ConfigLevel::Local)
+            .unwrap()
+    };
+
+    // An override signs commits without touching the config
+    state.update(cx, |state, cx| {
+        state.identity = Identity {
+            name: Some("Work Name".to_string()),
+            email: None,
+        };
+        state.stage_file("a.txt", cx).unwrap();
+        state.create_commit("Change a", cx).unwrap();
+    });
+    let head = repo.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.author().name(), Some("Work Name"));
+    assert_eq!(head.author().email(), Some("test@example.com"));
+    assert_eq!(config().get_string("user.name").unwrap(), "Test");
+
+    let identity = Identity::new(" New Name ", "");
+    state.update(cx, |state, cx| {
+        state
+            .set_git_identity(&identity, IdentityScope::Repository, cx)
+            .unwrap();
+    });
+    state.read_with(cx, |state, _| {
+        let saved = state.git_identity(IdentityScope::Repository).unwrap();
+        assert_eq!(saved.name.as_deref(), Some("New Name"));
+        assert_eq!(saved.email, None);
+    });
+    assert_eq!(config().get_string("user.name").unwrap(), "New Name");
+    assert!(config().get_string("user.email").is_err());
+}
+
+#[gpui::test]
+fn ignoring_an_untracked_file_appends_to_gitignore(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file(".gitignore", "target/", "Add gitignore");
+    repo.write("debug.log", "noise\n");
+    repo.write("notes.txt", "todo\n");
+    let state = open(&repo, cx);
+
+    state.update(cx, |state, cx| {
+        state
+            .ignore(&IgnorePattern::Extension("log".to_string()), cx)
+            .unwrap();
+        // Adding the same rule twice leaves one copy
+        state
+            .ignore(&IgnorePattern::Extension("log".to_string()), cx)
+            .unwrap();
+        assert!(state.files.iter().all(|f| f.path != "debug.log"));
+        assert!(state.files.iter().any(|f| f.path == "notes.txt"));
+
+        assert!(state.ignored_files.is_empty());
+        state.set_show_ignored(true, cx);
+        assert_eq!(state.ignored_files, vec!["debug.log".to_string()]);
+    });
+    assert_eq!(repo.read(".gitignore"), "target/\n*.log\n");
+}
+
+#[gpui::test]
+fn file_filter_matches_substrings_globs_and_status_groups(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file("src/main.rs", "fn main() {}\n", "Add main");
+    repo.write("src/main.rs", "fn main() { run() }\n");
+    repo.write("src/lib.rs", "pub fn run() {}\n");
+    repo.write("notes.md", "# Demo\n");
+    let state = open(&repo, cx);
+
+    let shown = |filter: &FileFilter| {
+        state.read_with(cx, |state, _| {
+            let mut paths: Vec<String> = state
+                .files
+                .iter()
+                .filter(|f| filter.matches(f))
+                .map(|f| f.path.clone())
+                .collect();
+            paths.sort();
+            paths
+        })
+    };
+
+    let mut filter = FileFilter {
+        query: "MAIN".to_string(),
+        groups: Vec::new(),
+    };
+    assert_eq!(shown(&filter), vec!["src/main.rs"]);
+
+    filter.query = "*.rs".to_string();
+    assert_eq!(shown(&filter), vec!["src/lib.rs", "src/main.rs"]);
+    filter.query = "src/?ib.*".to_string();
+    assert_eq!(shown(&filter), vec!["src/lib.rs"]);
+
+    filter.query.clear();
+    filter.toggle_group(StatusGroup::Untracked);
+    assert_eq!(shown(&filter), vec!["notes.md", "src/lib.rs"]);
+    filter.toggle_group(StatusGroup::Untracked);
+    filter.toggle_group(StatusGroup::Modified);
+    assert_eq!(shown(&filter), vec!["src/main.rs"]);
+}
+
+#[gpui::test]
+fn revisions_only_advance_for_the_slice_that_changed(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let first = repo.commit_file("a.txt", "one\n", "First");
+    repo.commit_file("a.txt", "two\n", "Second");
+    repo.write("a.txt", "three\n");
+    let state = open(&repo, cx);
+
+    state.update(cx, |state, cx| {
+        let before = state.revisions();
+        state.toggle_file_selection("a.txt", cx);
+        let after = state.revisions();
+        assert_eq!(after.status, before.status + 1);
+        assert_eq!((after.refs, after.graph), (before.refs, before.graph));
+
+        state.focus_commit(&first.to_string(), cx).unwrap();
+        let focused = state.revisions();
+        assert_eq!(focused.graph, after.graph + 1);
+        assert_eq!(focused.status, after.status);
+    });
+}
+
+#[gpui::test]
+fn graph_filter_limits_history_to_a_branch_path_and_author(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let main = repo.default_branch();
+    repo.commit_file("src/lib.rs", "1\n", "Add lib");
+    repo.create_branch("feature");
+    repo.checkout("feature");
+    repo.commit_file("docs/guide.md", "guide\n", "Write guide");
+    repo.checkout(&main);
+    repo.commit_file("src/lib.rs", "2\n", "Change lib");
+    let state = open(&repo, cx);
+    let messages = |state: &GitState| -> Vec<String> {
+        let nodes = &state.commits.as_ref().unwrap().nodes;
+        nodes
+            .iter()
+            .map(|node| node.commit.message.clone())
+            .collect()
+    };
+    let filter_by = |filter: GraphFilter, cx: &mut TestAppContext| {
+        state.update(cx, |state, cx| {
+            state.set_graph_filter(filter, cx).unwrap();
+            messages(state)
+        })
+    };
+
+    assert_eq!(
+        filter_by(
+            GraphFilter {
+                path: Some("src".to_string()),
+                ..Default::default()
+            },
+            cx
+        ),
+        ["Change lib", "Add lib"]
+    );
+    assert_eq!(
+        filter_by(
+            GraphFilter {
+                branch: Some("feature".to_string()),
+                ..Default::default()
+            },
+            cx
+        ),
+        ["Write guide", "Add lib", "Initial commit"]
+    );
+    assert_eq!(
+        filter_by(
+            GraphFilter {
+                branch: Some("feature".to_string()),
+                path: Some("docs".to_string()),
+                ..Default::default()
+            },
+            cx
+        ),
+        ["Write guide"]
+    );
+    assert!(filter_by(
+        GraphFilter {
+            author: Some("nobody".to_string()),
+            ..Default::default()
+        },
+        cx
+    )
+    .is_empty());
+
+    // An unknown branch keeps the filter that was applied before
+    state.update(cx, |state, cx| {
+        let unknown = GraphFilter {
+            branch: Some("missing".to_string()),
+            ..Default::default()
+        };
+        assert!(state.set_graph_filter(unknown, cx).is_err());
+        assert_eq!(state.graph_filter.author.as_deref(), Some("nobody"));
+    });
+}
+
+#[gpui::test]
+fn filtering_by_author_keeps_the_filter_and_summarizes_their_commits(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file("a.txt", "1\n", "Add a");
+    repo.commit_file("b.txt", "1\n", "Add b");
+    repo.commit_file("a.txt", "2\n", "Change a");
+    let state = open(&repo, cx);
+
+    state.update(cx, |state, cx| {
+        let filter = GraphFilter {
+            path: Some("a.txt".to_string()),
+            ..Default::default()
+        };
+        state.set_graph_filter(filter, cx).unwrap();
+        state.filter_by_author("test@example.com", cx).unwrap();
+        assert_eq!(state.graph_filter.path.as_deref(), Some("a.txt"));
+        assert_eq!(state.commits.as_ref().unwrap().nodes.len(), 2);
+    });
+    cx.run_until_parked();
+
+    state.read_with(cx, |state, _| {
+        let summary = state.author_summary.as_ref().expect("author summary");
+        assert_eq!(summary.name, "Test");
+        // The summary covers all of the author's commits, not just the
+        // filtered ones
+        assert_eq!(summary.commit_count, 4);
+        assert_eq!(
+            summary.top_paths,
+            [
+                ("a.txt".to_string(), 2),
+                ("README.md".to_string(), 1),
+                ("b.txt".to_string(), 1)
+            ]
+        );
+    });
+
+    state.update(cx, |state, cx| {
+        state.set_graph_filter(GraphFilter::default(), cx).unwrap();
+        assert!(state.author_summary.is_none());
+    });
+}
+
+#[gpui::test]
+fn pickaxe_search_finds_commits_adding_or_removing_a_string(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file("a.txt", "let needle = 1;\n", "Add needle");
+    repo.commit_file("a.txt", "let needle = 1;\nother\n", "Unrelated change");
+    repo.commit_file("b.txt", "needle\n", "Copy needle to b");
+    repo.commit_file("a.txt", "other\n", "Remove needle");
+    let state = open(&repo, cx);
+
+    state.update(cx, |state, cx| state.start_pickaxe_search("needle", cx));
+    cx.run_until_parked();
+
+    state.read_with(cx, |state, _| {
+        let search = state.pickaxe.as_ref().unwrap();
+        assert!(!search.running);
+        assert_eq!(search.total, Some(5));
+        assert_eq!(search.scanned, 5);
+        let messages: Vec<_> = search.results.iter().map(|c| c.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            ["Remove needle", "Copy needle to b", "Add needle"]
+        );
+    });
+}
+
+#[gpui::test]
+fn detached_head_can_return_to_previous_branch_or_get_a_new_one(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let first = repo.commit_file("a.txt", "a\n", "Add a");
+    repo.commit_file("b.txt", "b\n", "Add b");
+    repo.create_branch("feature");
+    repo.checkout("feature");
+    let state = open(&repo, cx);
+
+    state
+        .update(cx, |state, cx| {
+            state.checkout_commit(&first.to_string(), cx)
+        })
+        .unwrap();
+    state.read_with(cx, |state, _| {
+        assert!(state.is_detached());
+        let info = state.repository_info.as_ref().unwrap();
+        assert_eq!(info.head_sha.as_deref(), Some(first.to_string().as_str()));
+        assert_eq!(info.previous_branch.as_deref(), Some("feature"));
+    });
+
+    let branch = state
+        .update(cx, |state, cx| state.return_to_previous_branch(cx))
+        .unwrap();
+    assert_eq!(branch, "feature");
+    state.read_with(cx, |state, _| {
+        assert!(!state.is_detached());
+        assert_eq!(state.current_branch(), Some("feature"));
+    });
+
+    state
+        .update(cx, |state, cx| {
+            state.checkout_commit(&first.to_string(), cx)
+        })
+        .unwrap();
+    state
+        .update(cx, |state, cx| state.create_branch_at_head("rescue", cx))
+        .unwrap();
+    state.read_with(cx, |state, _| {
+        assert!(!state.is_detached());
+        assert_eq!(state.current_branch(), Some("rescue"));
+    });
+    let head = repo.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.id(), first);
+}
+
+#[gpui::test]
+fn branch_created_at_a_commit_leaves_head_unless_checked_out(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let main = repo.default_branch();
+    let first = repo.commit_file("a.txt", "a\n", "Add a");
+    let second = repo.commit_file("a.txt", "b\n", "Change a");
+    let state = open(&repo, cx);
+
+    state
+        .update(cx, |state, cx| {
+            state.create_branch_at("old", &first.to_string(), false, cx)
+        })
+        .unwrap();
+    state.read_with(cx, |state, _| {
+        assert!(state.has_local_branch("old"));
+        assert_eq!(state.current_branch(), Some(main.as_str()));
+    });
+    assert_eq!(repo.read("a.txt"), "b\n");
+    let old = repo
+        .repo
+        .find_branch("old", git2::BranchType::Local)
+        .unwrap();
+    assert_eq!(old.get().target(), Some(first));
+
+    state
+        .update(cx, |state, cx| {
+            state.create_branch_at("older", &first.to_string(), true, cx)
+        })
+        .unwrap();
+    state.read_with(cx, |state, _| {
+        assert_eq!(state.current_branch(), Some("older"))
+    });
+    assert_eq!(repo.read("a.txt"), "a\n");
+
+    let error = state
+        .update(cx, |state, cx| {
+            state.create_branch_at("bad name", &second.to_string(), false, cx)
+        })
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Branch names can't contain spaces");
+    assert!(repo
+        .repo
+        .find_branch("bad name", git2::BranchType::Local)
+        .is_err());
+}
+
+#[gpui::test]
+fn upstream_can_be_set_and_reports_tracking_counts(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let main = repo.default_branch();
+    let base = repo.commit_file("a.txt", "a\n", "Add a");
+    repo.repo
+        .remote("origin", "https://example.com/repo.git")
+        .unwrap();
+    repo.repo
+        .reference(
+            &format!("refs/remotes/origin/{}", main),
+            base,
+            false,
+            "test",
+        )
+        .unwrap();
+    repo.commit_file("a.txt", "b\n", "Change a");
+    let state = open(&repo, cx);
+
+    state.read_with(cx, |state, _| {
+        assert_eq!(state.current_branch_without_upstream(), Some(main.clone()));
+    });
+
+    let upstream = format!("origin/{}", main);
+    state
+        .update(cx, |state, cx| {
+            state.set_upstream(&main, Some(&upstream), cx)
+        })
+        .unwrap();
+    state.read_with(cx, |state, _| {
+        assert_eq!(state.current_branch_without_upstream(), None);
+        let branch = state.branches.iter().find(|b| b.name == main).unwrap();
+        assert_eq!(branch.upstream.as_deref(), Some(upstream.as_str()));
+        assert_eq!(branch.ahead_behind, Some((1, 0)));
+        assert_eq!(branch.tracking_label(), Some(format!("{} ↑1", upstream)));
+    });
+
+    state
+        .update(cx, |state, cx| state.set_upstream(&main, None, cx))
+        .unwrap();
+    state.read_with(cx, |state, _| {
+        assert_eq!(state.current_branch_without_upstream(), Some(main.clone()));
+    });
+}
+
+#[gpui::test]
+fn remote_branches_check_out_as_local_tracking_branches(cx: &mut TestAppContext) {
+    let mut upstream = TestRepo::new();
+    let head = upstream.commit_file("a.txt", "a\n", "Add a");
+    let commit = upstream.repo.find_commit(head).unwrap();
+    upstream.repo.branch("feature", &commit, false).unwrap();
+
+    let mut local = TestRepo::new();
+    local.commit_file("local.txt", "local\n", "Local work");
+    local
+        .repo
+        .remote("origin", upstream.path().to_str().unwrap())
+        .unwrap();
+    crate::git::remote::fetch_all_remotes(&local.repo, None, false).unwrap();
+    let main = format!("origin/{}", local.default_branch());
+    let state = open(&local, cx);
+
+    state.update(cx, |state, cx| {
+        assert_eq!(
+            state.checkout_remote_branch("origin/feature", cx).unwrap(),
+            "feature"
+        );
+    });
+    state.read_with(cx, |state, _| {
+        assert_eq!(state.current_branch(), Some("feature"));
+        let feature = state.branches.iter().find(|b| b.name == "feature").unwrap();
+        assert_eq!(feature.upstream.as_deref(), Some("origin/feature"));
+    });
+    assert_eq!(local.read("a.txt"), "a\n");
+
+    // A branch already tracking it is reused; one that doesn't is left alone
+    state.update(cx, |state, cx| {
+        assert_eq!(
+            state.checkout_remote_branch("origin/feature", cx).unwrap(),
+            "feature"
+        );
+        assert!(state.checkout_remote_branch(&main, cx).is_err());
+    });
+}
+
+#[gpui::test]
+fn avatars_load_from_github_gravatar_or_the_disk_cache(cx: &mut TestAppContext) {
+    assert_eq!(
+        avatar_url("12345+octocat@users.noreply.github.com"),
+        "https://avatars.githubusercontent.com/u/12345?s=64"
+    );
+    assert_eq!(
+        avatar_url("octocat@users.noreply.github.com"),
+        "https://github.com/octocat.png?size=64"
+    );
+    // Gravatar hashes the trimmed, lowercased address
+    assert_eq!(
+        avatar_hash(" Test@Example.com "),
+        avatar_hash("test@example.com")
+    );
+    assert_eq!(
+        avatar_url("test@example.com"),
+        format!(
+            "https://gravatar.com/avatar/{}?s=64&d=404",
+            avatar_hash("test@example.com")
+        )
+    );
+
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join(avatar_hash("cached@example.com")), b"png").unwrap();
+    // An empty file records an email without avatar
+    fs::write(dir.path().join(avatar_hash("none@example.com")), b"").unwrap();
+    let cache_dir = dir.path().to_path_buf();
+    let avatars = cx.new(|cx| AvatarState::new(false, cx).with_cache_dir(cache_dir));
+
+    avatars.update(cx, |avatars, cx| {
+        for email in ["cached@example.com", "none@example.com", "new@example.com"] {
+            assert_eq!(avatars.avatar(email, cx), None);
+        }
+    });
+    cx.run_until_parked();
+
+    avatars.update(cx, |avatars, cx| {
+        assert_eq!(
+            avatars.avatar("cached@example.com", cx),
+            Some(dir.path().join(avatar_hash("cached@example.com")))
+        );
+        assert_eq!(avatars.avatar("none@example.com", cx), None);
+        // Not downloaded while fetching is off
+        assert_eq!(avatars.avatar("new@example.com", cx), None);
+    });
+    assert!(!dir.path().join(avatar_hash("new@example.com")).exists());
+}
+
+#[test]
+fn editor_command_fills_in_the_file_and_line() {
+    let file = Path::new("/repo/My Notes/a.rs");
+    let words = |template: &str, line| editor_command(template, file, line).unwrap();
+
+    // Known editors get their deep link arguments
+    assert_eq!(
+        words("code", Some(12)),
+        ["code", "--goto", "/repo/My Notes/a.rs:12"]
+    );
+    assert_eq!(
+        words("/usr/local/bin/idea", Some(3)),
+        ["/usr/local/bin/idea", "--line", "3", "/repo/My Notes/a.rs"]
+    );
+    // Templates are used as written, quoted words staying together
+    assert_eq!(
+        words("\"/opt/My Editor/edit\" -n +{line} {file}", Some(7)),
+        ["/opt/My Editor/edit", "-n", "+7", "/repo/My Notes/a.rs"]
+    );
+    // The file is appended when missing, and the line defaults to 1
+    assert_eq!(
+        words("unknown-editor --wait", None),
+        ["unknown-editor", "--wait", "/repo/My Notes/a.rs"]
+    );
+    assert_eq!(words("zed", None), ["zed", "/repo/My Notes/a.rs:1"]);
+    assert_eq!(editor_command("   ", file, None), None);
+}
+
+#[cfg(unix)]
+#[gpui::test]
+fn commit_hooks_run_before_committing_and_can_reject_it(cx: &mut TestAppContext) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut repo = TestRepo::new();
+    repo.commit_file("a.txt", "one\n", "Initial");
+    let hooks = repo.path().join(".git/hooks");
+    fs::create_dir_all(&hooks).unwrap();
+    for (name, script) in [
+        ("pre-commit", "#!/bin/sh\necho 'checking files'\n"),
+        (
+            "commit-msg",
+            "#!/bin/sh\ngrep -q WIP \"$1\" && { echo 'no WIP commits' >&2; exit 1; }\n\
+             echo 'Refs: #42' >> \"$1\"\n",
+        ),
+    ] {
+        fs::write(hooks.join(name), script).unwrap();
+        fs::set_permissions(hooks.join(name), fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    let state = open(&repo, cx);
+
+    // Turned off, the commit is created right away without the hooks
+    repo.write("a.txt", "two\n");
+    let outcome = state.update(cx, |state, cx| {
+        state.run_hooks = false;
+        state.stage_file("a.txt", cx).unwrap();
+        state
+            .commit_with_hooks("WIP skip hooks", false, cx)
+            .unwrap()
+    });
+    assert_eq!(outcome, CommitOutcome::Committed);
+    assert!(state.read_with(cx, |state, _| state.hook_run.is_none()));
+
+    // The commit-msg hook can rewrite the message
+    repo.write("a.txt", "three\n");
+    let outcome = state.update(cx, |state, cx| {
+        state.run_hooks = true;
+        state.stage_file("a.txt", cx).unwrap();
+        state.commit_with_hooks("Change a\n", false, cx).unwrap()
+    });
+    assert_eq!(outcome, CommitOutcome::Pending);
+    cx.run_until_parked();
+    state.read_with(cx, |state, _| {
+        let run = state.hook_run.as_ref().unwrap();
+        assert!(!run.running && run.committed && run.error.is_none());
+        assert_eq!(run.output, ["checking files"]);
+        assert_eq!(
+            state.get_last_commit_message().unwrap(),
+            "Change a\nRefs: #42\n"
+        );
+    });
+
+    // A failing hook stops the commit and its output is kept
+    repo.write("a.txt", "four\n");
+    state.update(cx, |state, cx| {
+        state.stage_file("a.txt", cx).unwrap();
+        state.commit_with_hooks("WIP stuff", false, cx).unwrap();
+    });
+    cx.run_until_parked();
+    state.read_with(cx, |state, _| {
+        let run = state.hook_run.as_ref().unwrap();
+        assert!(!run.committed);
+        assert!(run
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("commit-msg hook failed"));
+        assert!(run.output.iter().any(|line| line == "no WIP commits"));
+        assert_eq!(
+            state.get_last_commit_message().unwrap(),
+            "Change a\nRefs: #42\n"
+        );
+    });
+}
+
+#[gpui::test]
+fn lfs_pointers_are_detected_and_large_files_warned_about(cx: &mut TestAppContext) {
+    let pointer = |oid: char, size: u64| {
+        format!(
+            "version https://git-lfs.github.com/spec/v1\noid sha256:{}\nsize {}\n",
+            oid.to_string().repeat(64),
+            size
+        )
+    };
+    let mut repo = TestRepo::new();
+    repo.commit_file(
+        ".gitattributes",
+        "*.bin filter=lfs diff=lfs merge=lfs -text\n",
+        "LFS",
+    );
+    repo.commit_file("model.bin", &pointer('a', 5_000_000), "Add model");
+    let state = open(&repo, cx);
+
+    // Checked out without Git LFS, so only the pointer is there
+    state.read_with(cx, |state, _| assert_eq!(state.lfs_missing, ["model.bin"]));
+
+    let object = repo
+        .path()
+        .join(".git/lfs/objects/aa/aa")
+        .join("a".repeat(64));
+    fs::create_dir_all(object.parent().unwrap()).unwrap();
+    fs::write(&object, "content").unwrap();
+    state.update(cx, |state, cx| state.refresh_status(cx));
+    state.read_with(cx, |state, _| assert!(state.lfs_missing.is_empty()));
+
+    // A changed pointer is shown as one, with the size of the content
+    repo.write("model.bin", &pointer('b', 6_000_000));
+    state.update(cx, |state, cx| state.refresh_status(cx));
+    state.read_with(cx, |state, _| {
+        let file = &state.lfs_files["model.bin"];
+        assert_eq!(file.size, 6_000_000);
+        assert!(!file.downloaded);
+
+        let change = LfsChange::from_diff(&state.working_diff("model.bin").unwrap()).unwrap();
+        assert_eq!(change.old.unwrap().oid, "a".repeat(64));
+        assert_eq!(change.new.unwrap().size, 6_000_000);
+
+        // Binary content matching an LFS pattern would be stored as a blob
+        repo.write("texture.bin", "\0\u{1}\u{2}");
+        repo.write("notes.txt", "\0\u{1}\u{2}");
+        let paths = ["texture.bin".to_string(), "notes.txt".to_string()];
+        let warnings = state.lfs_staging_warnings(&paths);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("texture.bin"));
+    });
+}
+
+#[gpui::test]
+fn large_and_binary_files_are_summarized_instead_of_diffed(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let old = "line\n".repeat(3000);
+    let new = "line\n".repeat(1000);
+    let sha = repo.commit_file("big.txt", &old, "Add big").to_string();
+    repo.commit_file("logo.gif", "GIF89a\0\u{1}", "Add logo");
+    repo.write("big.txt", &new);
+    fs::write(repo.path().join("logo.gif"), b"GIF89a\0\x01\x02\xff").unwrap();
+    let state = open(&repo, cx);
+
+    state.update(cx, |state, cx| {
+        state.max_diff_size = 10_000;
+        state.load_file_diff("big.txt", cx).unwrap();
+        let diff = state.current_diff.as_ref().unwrap();
+        assert!(diff.too_large);
+        assert!(diff.lines.is_empty());
+        assert_eq!((diff.old_size, diff.new_size), (15_000, 5_000));
+        assert_eq!(diff.size_change(), -10_000);
+
+        // Commits are checked against the limit too
+        state.load_commit_file_diff(&sha, "big.txt", cx).unwrap();
+        let diff = state.current_diff.as_ref().unwrap();
+        assert!(diff.too_large);
+        assert_eq!((diff.old_size, diff.new_size), (0, 15_000));
+
+        // Under the limit the lines load, and are rendered a page at a time
+        state.max_diff_size = 20_000;
+        state.load_unstaged_diff("big.txt", cx).unwrap();
+        let diff = state.current_diff.as_ref().unwrap();
+        assert!(!diff.too_large && !diff.is_binary);
+        assert_eq!(diff.deletions, 2000);
+        assert_eq!(state.diff_line_limit, DIFF_PAGE_LINES);
+        state.show_more_diff_lines(cx);
+        assert_eq!(state.diff_line_limit, 2 * DIFF_PAGE_LINES);
+        state.load_file_diff("big.txt", cx).unwrap();
+        assert_eq!(state.diff_line_limit, DIFF_PAGE_LINES);
+
+        // Binary files have no lines, only sizes and a hex preview
+        assert!(state.binary_files.contains("logo.gif"));
+        assert!(!state.binary_files.contains("big.txt"));
+        state.load_unstaged_diff("logo.gif", cx).unwrap();
+        let diff = state.current_diff.as_ref().unwrap();
+        assert!(diff.is_binary);
+        assert_eq!((diff.additions, diff.deletions), (0, 0));
+        assert_eq!((diff.old_size, diff.new_size), (8, 10));
+
+        // The old side comes from the index, the new one from the file
+        state.toggle_hex_preview(cx).unwrap();
+        let preview = state.hex_preview.clone().unwrap();
+        assert!(preview.old[0].ends_with("|GIF89a..|"));
+        assert!(preview.new[0].ends_with("|GIF89a....|"));
+        state.toggle_hex_preview(cx).unwrap();
+        assert!(state.hex_preview.is_none());
+    });
+}
+
+#[gpui::test]
+fn diff_navigation_steps_through_hunks_and_files(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    let lines = |changed: &[usize]| -> String {
+        (0..40)
+            .map(|n| {
+                if changed.contains(&n) {
+                    format!("changed {}\n", n)
+                } else {
+                    format!("line {}\n", n)
+                }
+            })
+            .collect()
+    };
+    repo.commit_file("a.txt", &lines(&[]), "Add a");
+    repo.commit_file("b.txt", &lines(&[]), "Add b");
+    // Changes far enough apart to land in separate hunks, committed together
+    repo.write("a.txt", &lines(&[2, 30]));
+    let mut index = repo.repo.index().unwrap();
+    index.add_path(Path::new("a.txt")).unwrap();
+    index.write().unwrap();
+    let sha = repo
+        .commit_file("b.txt", &lines(&[20]), "Change both")
+        .to_string();
+    let state = open(&repo, cx);
+
+    state.update(cx, |state, cx| {
+        state.load_commit_file_diff(&sha, "a.txt", cx).unwrap();
+        assert_eq!(state.diff_files.len(), 2);
+        assert_eq!(state.diff_file_index, 0);
+        let hunks = state.current_diff.as_ref().unwrap().hunk_starts();
+        assert_eq!(hunks.len(), 2);
+
+        assert_eq!(state.step_diff_hunk(true, cx), Some(hunks[0]));
+        assert_eq!(state.step_diff_hunk(true, cx), Some(hunks[1]));
+        assert_eq!(state.step_diff_hunk(false, cx), Some(hunks[0]));
+        // Nothing comes before the first file
+        assert_eq!(state.step_diff_hunk(false, cx), None);
+        assert_eq!(state.diff_hunk, Some(0));
+
+        // Past the last hunk of a file comes the first of the next
+        state.step_diff_hunk(true, cx);
+        let line = state.step_diff_hunk(true, cx);
+        let diff = state.current_diff.as_ref().unwrap();
+        assert_eq!(diff.path, "b.txt");
+        assert_eq!(line, diff.hunk_starts().first().copied());
+        assert_eq!(state.step_diff_hunk(true, cx), None);
+        // and going back lands on the last hunk of the previous file
+        assert_eq!(state.step_diff_hunk(false, cx), Some(hunks[1]));
+        assert_eq!(state.current_diff.as_ref().unwrap().path, "a.txt");
+
+        assert!(!state.step_diff_file(false, cx));
+        assert!(state.step_diff_file(true, cx));
+        assert_eq!(state.diff_hunk, None);
+        assert!(!state.step_diff_file(true, cx));
+
+        state.clear_diff(cx);
+        assert!(state.diff_files.is_empty());
+    });
+}
+
+#[gpui::test]
+fn diff_options_hide_whitespace_and_blank_line_changes(cx: &mut TestAppContext) {
+    let mut repo = TestRepo::new();
+    repo.commit_file("letters.txt", "a\nb\nc\nd\ne\nf\ng\nh\n", "Add letters");
+    // A reindented line, an added blank line and one real change
+    repo.write("letters.txt", "a\n  b\nc\n\nd\ne\nf\ng\nH\n");
+    let state = open(&repo, cx);
+
+    state.update(cx, |state, cx| {
+        state.load_file_diff("letters.txt", cx).unwrap();
+        assert_eq!(state.current_diff.as_ref().unwrap().additions, 3);
+
+        // Changing the options reloads the open diff
+        let ignore_whitespace = DiffDisplayOptions {
+            ignore_whitespace: true,
+            ..Default::default()
+        };
+        state.set_diff_options(ignore_whitespace, cx);
+        assert_eq!(state.current_diff.as_ref().unwrap().additions, 2);
+
+        state.set_diff_options(
+            DiffDisplayOptions {
+                ignore_blank_lines: true,
+                context_lines: 0,
+                ..ignore_whitespace
+            },
+            cx,
+        );
+        let diff = state.current_diff.as_ref().unwrap();
+        assert_eq!((diff.additions, diff.deletions), (1, 1));
+        assert!(diff
+            .lines
+            .iter()
+            .all(|line| line.line_type != DiffLineType::Context));
+    });
+}
@@ -1,6 +1,15 @@
 use gpui::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::rc::Rc;
 use std::time::Duration;
 
+/// Toasts with action buttons stay at least this long so they can be reached
+const ACTION_TOAST_MIN_DURATION: Duration = Duration::from_secs(10);
+
+/// Runs when the action button on a toast is clicked
+type ActionHandler = Rc<dyn Fn(&mut Window, &mut App)>;
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum ToastType {
     Success,
@@ -9,17 +18,139 @@ pub enum ToastType {
     Info,
 }
 
+/// Screen corner the toasts are stacked in
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ToastPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl ToastPosition {
+    pub const ALL: [Self; 4] = [
+        Self::TopLeft,
+        Self::TopRight,
+        Self::BottomLeft,
+        Self::BottomRight,
+    ];
+
+    pub fn is_top(&self) -> bool {
+        matches!(self, Self::TopLeft | Self::TopRight)
+    }
+
+    pub fn is_left(&self) -> bool {
+        matches!(self, Self::TopLeft | Self::BottomLeft)
+    }
+}
+
+/// How long each kind of toast stays on screen, in seconds
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToastDurations {
+    pub success: u64,
+    pub info: u64,
+    pub warning: u64,
+    pub error: u64,
+}
+
+impl Default for ToastDurations {
+    fn default() -> Self {
+        Self {
+            success: 3,
+            info: 4,
+            warning: 6,
+            error: 8,
+        }
+    }
+}
+
+impl ToastDurations {
+    pub fn get(&self, toast_type: ToastType) -> Duration {
+        let secs = match toast_type {
+            ToastType::Success => self.success,
+            ToastType::Info => self.info,
+            ToastType::Warning => self.warning,
+            ToastType::Error => self.error,
+        };
+        Duration::from_secs(secs)
+    }
+}
+
+/// An error's short message plus a report with the full context chain, the
+/// operation that failed and the repository, for pasting into bug reports
+#[derive(Clone, Debug)]
+pub struct ErrorDetails {
+    pub message: String,
+    pub report: String,
+}
+
+impl ErrorDetails {
+    pub fn new(operation: &str, repo: Option<&Path>, error: &anyhow::Error) -> Self {
+        let repo = repo
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "(none)".to_string());
+        Self {
+            message: error.to_string(),
+            // anyhow's Debug output lists every context layer under "Caused by"
+            report: format!(
+                "Operation: {}\nRepository: {}\nVersion: {}\n\n{:?}",
+                operation,
+                repo,
+                env!("CARGO_PKG_VERSION"),
+                error
+            ),
+        }
+    }
+}
+
+/// A button shown on a toast, e.g. "Redo" after undoing a commit or "Retry"
+/// after a failed push
+#[derive(Clone)]
+pub struct ToastAction {
+    pub label: SharedString,
+    pub handler: ActionHandler,
+}
+
+impl ToastAction {
+    pub fn new(
+        label: impl Into<SharedString>,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            handler: Rc::new(handler),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct ToastMessage {
     pub id: usize,
     pub message: String,
     pub toast_type: ToastType,
+    /// Stays until dismissed instead of timing out
+    pub sticky: bool,
+    /// Full error report, expandable under the message and copyable
+    pub details: Option<String>,
+    /// Whether the details are shown; an expanded toast stays until
+    /// dismissed so the report can be read
+    pub expanded: bool,
+    /// Buttons in display order; each dismisses the toast when clicked
+    pub actions: Vec<ToastAction>,
 }
 
 /// Global toast notification state
 pub struct ToastState {
     toasts: Vec<ToastMessage>,
     next_id: usize,
+    /// Display durations, mirrored from settings
+    pub durations: ToastDurations,
+    /// Keep error toasts until dismissed, mirrored from settings
+    pub sticky_errors: bool,
+    /// Corner the toasts are shown in, mirrored from settings
+    pub position: ToastPosition,
 }
 
 impl ToastState {
@@ -27,6 +158,9 @@ impl ToastState {
         Self {
             toasts: Vec::new(),
             next_id: 0,
+            durations: ToastDurations::default(),
+            sticky_errors: false,
+            position: ToastPosition::default(),
         }
     }
 
@@ -35,29 +169,79 @@ impl ToastState {
     }
 
     pub fn show(&mut self, message: impl Into<String>, toast_type: ToastType, cx: &mut Context<Self>) {
+        self.push(message.into(), toast_type, None, Vec::new(), cx);
+    }
+
+    /// Show a toast with a button that runs `action` and dismisses the toast
+    pub fn show_with_action(
+        &mut self,
+        message: impl Into<String>,
+        toast_type: ToastType,
+        action: ToastAction,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_with_actions(message, toast_type, vec![action], cx);
+    }
+
+    /// Show a toast with several buttons, e.g. "Pull" and "Retry"
+    pub fn show_with_actions(
+        &mut self,
+        message: impl Into<String>,
+        toast_type: ToastType,
+        actions: Vec<ToastAction>,
+        cx: &mut Context<Self>,
+    ) {
+        self.push(message.into(), toast_type, None, actions, cx);
+    }
+
+    fn push(
+        &mut self,
+        message: String,
+        toast_type: ToastType,
+        details: Option<String>,
+        actions: Vec<ToastAction>,
+        cx: &mut Context<Self>,
+    ) {
         let id = self.next_id;
         self.next_id += 1;
 
+        let sticky = toast_type == ToastType::Error && self.sticky_errors;
+        let has_actions = !actions.is_empty();
         self.toasts.push(ToastMessage {
             id,
-            message: message.into(),
+            message,
             toast_type,
+            sticky,
+            details,
+            expanded: false,
+            actions,
         });
 
-        // Auto-dismiss after 3 seconds
-        cx.spawn(async move |this, cx| {
-            cx.background_executor()
-                .timer(Duration::from_secs(3))
-                .await;
-            let _ = this.update(cx, |state, cx| {
-                state.dismiss(id, cx);
-            });
-        })
-        .detach();
+        if !sticky {
+            let mut duration = self.durations.get(toast_type);
+            if has_actions {
+                duration = duration.max(ACTION_TOAST_MIN_DURATION);
+            }
+            cx.spawn(async move |this, cx| {
+                cx.background_executor().timer(duration).await;
+                let _ = this.update(cx, |state, cx| {
+                    state.expire(id, cx);
+                });
+            })
+            .detach();
+        }
 
         cx.notify();
     }
 
+    /// Time a toast out, unless it was made sticky since it was shown
+    fn expire(&mut self, id: usize, cx: &mut Context<Self>) {
+        let sticky = self.toasts.iter().any(|t| t.id == id && t.sticky);
+        if !sticky {
+            self.dismiss(id, cx);
+        }
+    }
+
     pub fn success(&mut self, message: impl Into<String>, cx: &mut Context<Self>) {
         self.show(message, ToastType::Success, cx);
     }
@@ -66,6 +250,29 @@ impl ToastState {
         self.show(message, ToastType::Error, cx);
     }
 
+    /// Show an error toast whose full report can be expanded and copied
+    pub fn error_with_details(
+        &mut self,
+        message: impl Into<String>,
+        details: &ErrorDetails,
+        cx: &mut Context<Self>,
+    ) {
+        self.error_with_actions(message, details, Vec::new(), cx);
+    }
+
+    /// Show an error toast with its report and buttons to recover, e.g.
+    /// "Retry" after a failed push
+    pub fn error_with_actions(
+        &mut self,
+        message: impl Into<String>,
+        details: &ErrorDetails,
+        actions: Vec<ToastAction>,
+        cx: &mut Context<Self>,
+    ) {
+        let report = Some(details.report.clone());
+        self.push(message.into(), ToastType::Error, report, actions, cx);
+    }
+
     pub fn warning(&mut self, message: impl Into<String>, cx: &mut Context<Self>) {
         self.show(message, ToastType::Warning, cx);
     }
@@ -74,6 +281,15 @@ impl ToastState {
         self.show(message, ToastType::Info, cx);
     }
 
+    /// Show or hide a toast's details, keeping it on screen once opened
+    pub fn toggle_details(&mut self, id: usize, cx: &mut Context<Self>) {
+        if let Some(toast) = self.toasts.iter_mut().find(|t| t.id == id) {
+            toast.expanded = !toast.expanded;
+            toast.sticky |= toast.expanded;
+        }
+        cx.notify();
+    }
+
     pub fn dismiss(&mut self, id: usize, cx: &mut Context<Self>) {
         self.toasts.retain(|t| t.id != id);
         cx.notify();
@@ -0,0 +1,106 @@
+#![allow(dead_code)]
+
+use crate::git::{TodoCache, TodoItem};
+use gpui::*;
+use std::path::PathBuf;
+
+/// TODO/FIXME comments found in the open repository.
+///
+/// Scans run in the background and reuse the results for files that have
+/// not changed since the previous scan.
+pub struct TodoState {
+    pub items: Vec<TodoItem>,
+    pub is_scanning: bool,
+    pub error: Option<String>,
+    /// Repository the items and cache belong to
+    path: Option<PathBuf>,
+    /// Taken by a running scan and handed back when it finishes
+    cache: Option<TodoCache>,
+    /// A scan was requested while one was running
+    rescan: bool,
+}
+
+impl TodoState {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            is_scanning: false,
+            error: None,
+            path: None,
+            cache: Some(TodoCache::default()),
+            rescan: false,
+        }
+    }
+
+    /// Scan the repository at `path`, or queue another scan if one is running
+    pub fn scan(&mut self, path: PathBuf, cx: &mut Context<Self>) {
+        if self.path.as_ref() != Some(&path) {
+            self.items.clear();
+            self.error = None;
+            self.path = Some(path.clone());
+            if !self.is_scanning {
+                self.cache = Some(TodoCache::default());
+            }
+        }
+        if self.is_scanning {
+            self.rescan = true;
+            return;
+        }
+
+        let mut cache = self.cache.take().unwrap_or_default();
+        self.is_scanning = true;
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            let repo_path = path.clone();
+            let (cache, result) = cx
+                .background_executor()
+                .spawn(async move {
+                    let result = git2::Repository::open(&repo_path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|repo| cache.scan(&repo));
+                    (cache, result)
+                })
+                .await;
+
+            this.update(cx, |state, cx| {
+                state.is_scanning = false;
+
+                // Another repository was opened meanwhile; start over for it
+                if state.path.as_ref() != Some(&path) {
+                    state.cache = Some(TodoCache::default());
+                    state.rescan = false;
+                    if let Some(path) = state.path.clone() {
+                        state.scan(path, cx);
+                    }
+                    return;
+                }
+
+                state.cache = Some(cache);
+                match result {
+                    Ok(items) => {
+                        state.items = items;
+                        state.error = None;
+                    }
+                    Err(e) => {
+                        log::warn!("TODO scan failed: {}", e);
+                        state.error = Some(e.to_string());
+                    }
+                }
+
+                if std::mem::take(&mut state.rescan) {
+                    state.scan(path, cx);
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+}
+
+impl Default for TodoState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,186 +1,168 @@
 #![allow(dead_code)]
 
-use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::PathBuf;
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    mpsc, Arc,
-};
+use futures::channel::mpsc::{self, UnboundedReceiver};
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+/// Debounce window for file system events
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(300);
+
+/// Kind of repository change detected by the watcher
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum WatcherEvent {
+    /// Files in the working directory changed
+    Workdir,
+    /// HEAD was rewritten (checkout, detach), possibly by another tool
+    Head,
+    /// Branches, tags or stashes changed
+    Refs,
+    /// The index (staging area) changed
+    Index,
+}
+
 /// File system watcher for repository changes
 pub struct RepositoryWatcher {
-    watcher: Option<RecommendedWatcher>,
-    stop_flag: Arc<AtomicBool>,
-    receiver: Option<mpsc::Receiver<()>>,
+    debouncer: Option<Debouncer<RecommendedWatcher>>,
     watched_path: Option<PathBuf>,
 }
 
 impl RepositoryWatcher {
     pub fn new() -> Self {
         Self {
-            watcher: None,
-            stop_flag: Arc::new(AtomicBool::new(false)),
-            receiver: None,
+            debouncer: None,
             watched_path: None,
         }
     }
 
-    /// Start watching a repository path
-    pub fn watch(&mut self, path: PathBuf) -> anyhow::Result<()> {
+    /// Start watching a repository path.
+    ///
+    /// Returns a stream of debounced event batches. The stream ends when the
+    /// watcher is stopped or replaced.
+    pub fn watch(&mut self, path: PathBuf) -> anyhow::Result<UnboundedReceiver<Vec<WatcherEvent>>> {
         // Stop any existing watcher
         self.stop();
 
-        // Create channels for event passing
-        let (event_tx, event_rx) = mpsc::channel::<()>();
-        let (debounced_tx, debounced_rx) = mpsc::channel::<()>();
-
-        self.stop_flag.store(false, Ordering::SeqCst);
-        let stop_flag = self.stop_flag.clone();
-
-        // Create the watcher
-        let watcher = RecommendedWatcher::new(
-            move |res: Result<notify::Event, notify::Error>| {
-                if let Ok(event) = res {
-                    if Self::should_notify(&event) {
-                        let _ = event_tx.send(());
-                    }
-                }
-            },
-            Config::default().with_poll_interval(Duration::from_secs(1)),
-        )?;
-
-        // Spawn debounce thread
-        std::thread::spawn(move || {
-            let debounce_duration = Duration::from_millis(500);
-            let mut last_event = None;
-
-            loop {
-                if stop_flag.load(Ordering::SeqCst) {
-                    break;
-                }
-
-                match event_rx.recv_timeout(Duration::from_millis(100)) {
-                    Ok(()) => {
-                        last_event = Some(std::time::Instant::now());
-                    }
-                    Err(mpsc::RecvTimeoutError::Timeout) => {
-                        if let Some(last) = last_event {
-                            if last.elapsed() >= debounce_duration {
-                                let _ = debounced_tx.send(());
-                                last_event = None;
+        let (tx, rx) = mpsc::unbounded::<Vec<WatcherEvent>>();
+
+        // Resolve the git directory (handles worktrees where .git is a file)
+        let repo = git2::Repository::open(&path)?;
+        let git_dir = repo.path().to_path_buf();
+        let workdir = repo.workdir().map(|p| p.to_path_buf());
+
+        let mut debouncer = new_debouncer(DEBOUNCE_DURATION, {
+            let git_dir = git_dir.clone();
+            move |result: DebounceEventResult| match result {
+                Ok(events) => {
+                    let mut kinds: Vec<WatcherEvent> = Vec::new();
+                    for event in events {
+                        if let Some(kind) = Self::classify(&event.path, &git_dir, &repo) {
+                            if !kinds.contains(&kind) {
+                                kinds.push(kind);
                             }
                         }
                     }
-                    Err(mpsc::RecvTimeoutError::Disconnected) => {
-                        break;
+                    if !kinds.is_empty() {
+                        let _ = tx.unbounded_send(kinds);
                     }
                 }
+                Err(e) => log::warn!("File watcher error: {}", e),
             }
-        });
-
-        let mut watcher = watcher;
-
-        // Watch the .git directory
-        let git_path = path.join(".git");
-        if git_path.exists() {
-            watcher.watch(&git_path, RecursiveMode::Recursive)?;
+        })?;
+
+        // Watch the git directory for refs/index changes
+        debouncer
+            .watcher()
+            .watch(&git_dir, RecursiveMode::Recursive)?;
+
+        // Watch the working directory; ignored paths are filtered in `classify`
+        if let Some(workdir) = workdir {
+            debouncer
+                .watcher()
+                .watch(&workdir, RecursiveMode::Recursive)?;
         }
 
-        // Watch the working directory for file changes (non-recursive to avoid noise)
-        watcher.watch(&path, RecursiveMode::NonRecursive)?;
-
-        self.watcher = Some(watcher);
-        self.receiver = Some(debounced_rx);
+        self.debouncer = Some(debouncer);
         self.watched_path = Some(path);
 
-        Ok(())
-    }
-
-    /// Check if there's a pending refresh notification
-    /// This should be called periodically (e.g., in a background task)
-    pub fn poll(&self) -> bool {
-        if let Some(ref rx) = self.receiver {
-            matches!(rx.try_recv(), Ok(()))
-        } else {
-            false
-        }
+        Ok(rx)
     }
 
     /// Stop watching
     pub fn stop(&mut self) {
-        self.stop_flag.store(true, Ordering::SeqCst);
-        self.watcher = None;
-        self.receiver = None;
+        self.debouncer = None;
         self.watched_path = None;
     }
 
     /// Check if currently watching
     pub fn is_watching(&self) -> bool {
-        self.watcher.is_some()
+        self.debouncer.is_some()
     }
 
-    /// Check if an event should trigger a notification
-    fn should_notify(event: &notify::Event) -> bool {
-        use notify::EventKind;
-
-        match &event.kind {
-            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
-                for path in &event.paths {
-                    // Skip if it's in the .git directory but not a relevant file
-                    let path_str = path.to_string_lossy();
-
-                    // Skip pack files, logs, etc. that change frequently
-                    if path_str.contains(".git/objects/pack")
-                        || path_str.contains(".git/logs")
-                        || path_str.contains(".git/COMMIT_EDITMSG")
-                        || path_str.contains(".git/FETCH_HEAD")
-                    {
-                        continue;
-                    }
+    /// Map a changed path to the kind of refresh it requires, or None if it
+    /// should be ignored
+    fn classify(path: &Path, git_dir: &Path, repo: &git2::Repository) -> Option<WatcherEvent> {
+        let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
-                    // Check for important .git changes
-                    if path_str.contains(".git/") {
-                        // These indicate actual git state changes
-                        if path_str.contains(".git/index")
-                            || path_str.contains(".git/HEAD")
-                            || path_str.contains(".git/refs/")
-                            || path_str.contains(".git/MERGE_HEAD")
-                            || path_str.contains(".git/CHERRY_PICK_HEAD")
-                        {
-                            return true;
-                        }
-                        continue;
-                    }
+        // Lock files are transient and always followed by the real file
+        if filename.ends_with(".lock") {
+            return None;
+        }
 
-                    if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                        // Skip swap files, backup files, IDE files
-                        if filename.ends_with('~')
-                            || filename.ends_with(".swp")
-                            || filename.ends_with(".swx")
-                            || filename.starts_with(".#")
-                            || filename == "4913"
-                        {
-                            continue;
-                        }
+        if let Ok(relative) = path.strip_prefix(git_dir) {
+            let relative = relative.to_string_lossy();
 
-                        // Skip build directories
-                        if path_str.contains("node_modules")
-                            || path_str.contains("target/debug")
-                            || path_str.contains("target/release")
-                            || path_str.contains(".next")
-                            || path_str.contains(".nuxt")
-                        {
-                            continue;
-                        }
+            // Object database and logs churn on every operation
+            if relative.starts_with("objects")
+                || relative.starts_with("logs")
+                || relative == "COMMIT_EDITMSG"
+                || relative == "FETCH_HEAD"
+            {
+                return None;
+            }
 
-                        return true;
-                    }
+            if relative == "index" {
+                return Some(WatcherEvent::Index);
+            }
+
+            if relative == "HEAD" {
+                return Some(WatcherEvent::Head);
+            }
+
+            if relative == "ORIG_HEAD"
+                || relative == "MERGE_HEAD"
+                || relative == "CHERRY_PICK_HEAD"
+                || relative == "REVERT_HEAD"
+                || relative == "packed-refs"
+                || relative.starts_with("refs")
+            {
+                return Some(WatcherEvent::Refs);
+            }
+
+            return None;
+        }
+
+        // Skip swap files, backup files, IDE files
+        if filename.ends_with('~')
+            || filename.ends_with(".swp")
+            || filename.ends_with(".swx")
+            || filename.starts_with(".#")
+            || filename == "4913"
+        {
+            return None;
+        }
+
+        // Skip anything git itself ignores (build output, node_modules, ...)
+        if let Some(workdir) = repo.workdir() {
+            if let Ok(relative) = path.strip_prefix(workdir) {
+                if repo.is_path_ignored(relative).unwrap_or(false) {
+                    return None;
                 }
-                false
             }
-            _ => false,
         }
+
+        Some(WatcherEvent::Workdir)
     }
 }
 
@@ -0,0 +1,287 @@
+#![allow(dead_code)]
+
+mod registry;
+
+pub use registry::*;
+
+use crate::git::FileStatusType;
+use gpui::*;
+use serde::{Deserialize, Serialize};
+
+/// Light or dark variant of the theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Appearance {
+    Dark,
+    Light,
+}
+
+impl From<WindowAppearance> for Appearance {
+    fn from(appearance: WindowAppearance) -> Self {
+        match appearance {
+            WindowAppearance::Light | WindowAppearance::VibrantLight => Appearance::Light,
+            WindowAppearance::Dark | WindowAppearance::VibrantDark => Appearance::Dark,
+        }
+    }
+}
+
+/// Lane colors of the commit graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GraphPalette {
+    /// The theme's accent colors
+    #[default]
+    Theme,
+    /// Okabe-Ito, distinguishable with deuteranopia and protanopia
+    OkabeIto,
+    /// Paul Tol's "bright" scheme, also safe for red-green color blindness
+    TolBright,
+}
+
+impl GraphPalette {
+    pub const ALL: [GraphPalette; 3] = [
+        GraphPalette::Theme,
+        GraphPalette::OkabeIto,
+        GraphPalette::TolBright,
+    ];
+}
+
+/// Okabe-Ito with grey in place of black, which vanishes on dark backgrounds
+const OKABE_ITO_DARK: [u32; 8] = [
+    0x56b4e9, 0xe69f00, 0x009e73, 0xf0e442, 0xcc79a7, 0xd55e00, 0x0072b2, 0x999999,
+];
+/// Okabe-Ito with black in place of yellow, which vanishes on light backgrounds
+const OKABE_ITO_LIGHT: [u32; 8] = [
+    0x0072b2, 0xe69f00, 0x009e73, 0xd55e00, 0xcc79a7, 0x56b4e9, 0x000000, 0x999999,
+];
+const TOL_BRIGHT: [u32; 7] = [
+    0x4477aa, 0xee6677, 0x228833, 0xccbb44, 0x66ccee, 0xaa3377, 0xbbbbbb,
+];
+
+/// Application colors, provided as a global. Named after the Catppuccin
+/// palette: Mocha for the dark variant, Latte for the light one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub appearance: Appearance,
+    /// Lane colors of the commit graph, chosen in settings
+    pub graph_palette: GraphPalette,
+
+    // Backgrounds and surfaces
+    pub base: Rgba,
+    pub mantle: Rgba,
+    pub surface0: Rgba,
+    pub surface1: Rgba,
+
+    // Text
+    pub text: Rgba,
+    /// Secondary text, labels
+    pub overlay2: Rgba,
+    /// Placeholder and disabled text
+    pub overlay0: Rgba,
+
+    // Accents
+    pub blue: Rgba,
+    pub lavender: Rgba,
+    pub sky: Rgba,
+    pub green: Rgba,
+    pub teal: Rgba,
+    pub yellow: Rgba,
+    pub peach: Rgba,
+    pub red: Rgba,
+    pub maroon: Rgba,
+    pub mauve: Rgba,
+    pub pink: Rgba,
+
+    // Tinted backgrounds for errors, diffs and toasts
+    pub red_bg: Rgba,
+    pub green_bg: Rgba,
+    pub yellow_bg: Rgba,
+    pub blue_bg: Rgba,
+    /// Changed words inside a deleted or added line
+    pub red_highlight: Rgba,
+    pub green_highlight: Rgba,
+    /// Pressed primary buttons
+    pub blue_active: Rgba,
+
+    /// Dimmed layer behind modals
+    pub backdrop: Rgba,
+    /// Text selection in inputs
+    pub selection: Rgba,
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            appearance: Appearance::Dark,
+            graph_palette: GraphPalette::Theme,
+            base: rgb(0x1e1e2e),
+            mantle: rgb(0x181825),
+            surface0: rgb(0x313244),
+            surface1: rgb(0x45475a),
+            text: rgb(0xcdd6f4),
+            overlay2: rgb(0x9399b2),
+            overlay0: rgb(0x6c7086),
+            blue: rgb(0x89b4fa),
+            lavender: rgb(0xb4befe),
+            sky: rgb(0x89dceb),
+            green: rgb(0xa6e3a1),
+            teal: rgb(0x94e2d5),
+            yellow: rgb(0xf9e2af),
+            peach: rgb(0xfab387),
+            red: rgb(0xf38ba8),
+            maroon: rgb(0xeba0ac),
+            mauve: rgb(0xcba6f7),
+            pink: rgb(0xf5c2e7),
+            red_bg: rgb(0x3d1a1a),
+            green_bg: rgb(0x1a3d2e),
+            yellow_bg: rgb(0x3d3d1a),
+            blue_bg: rgb(0x1a2a3d),
+            red_highlight: rgb(0x6b2f3a),
+            green_highlight: rgb(0x2f6b47),
+            blue_active: rgb(0x7287fd),
+            backdrop: rgba(0x00000088),
+            selection: rgba(0x89b4fa40),
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            appearance: Appearance::Light,
+            graph_palette: GraphPalette::Theme,
+            base: rgb(0xeff1f5),
+            mantle: rgb(0xe6e9ef),
+            surface0: rgb(0xccd0da),
+            surface1: rgb(0xbcc0cc),
+            text: rgb(0x4c4f69),
+            overlay2: rgb(0x7c7f93),
+            overlay0: rgb(0x9ca0b0),
+            blue: rgb(0x1e66f5),
+            lavender: rgb(0x7287fd),
+            sky: rgb(0x04a5e5),
+            green: rgb(0x40a02b),
+            teal: rgb(0x179299),
+            yellow: rgb(0xdf8e1d),
+            peach: rgb(0xfe640b),
+            red: rgb(0xd20f39),
+            maroon: rgb(0xe64553),
+            mauve: rgb(0x8839ef),
+            pink: rgb(0xea76cb),
+            red_bg: rgb(0xf6d9de),
+            green_bg: rgb(0xdcf0d9),
+            yellow_bg: rgb(0xf7ecd3),
+            blue_bg: rgb(0xd9e4fb),
+            red_highlight: rgb(0xeeb0bc),
+            green_highlight: rgb(0xb5deb0),
+            blue_active: rgb(0x1a55d6),
+            backdrop: rgba(0x4c4f6966),
+            selection: rgba(0x1e66f533),
+        }
+    }
+
+    pub fn for_appearance(appearance: Appearance) -> Self {
+        match appearance {
+            Appearance::Dark => Self::dark(),
+            Appearance::Light => Self::light(),
+        }
+    }
+
+    /// Set a color by field name, as used in user theme files. Returns false
+    /// for unknown names.
+    pub fn set_color(&mut self, name: &str, color: Rgba) -> bool {
+        let field = match name {
+            "base" => &mut self.base,
+            "mantle" => &mut self.mantle,
+            "surface0" => &mut self.surface0,
+            "surface1" => &mut self.surface1,
+            "text" => &mut self.text,
+            "overlay2" => &mut self.overlay2,
+            "overlay0" => &mut self.overlay0,
+            "blue" => &mut self.blue,
+            "lavender" => &mut self.lavender,
+            "sky" => &mut self.sky,
+            "green" => &mut self.green,
+            "teal" => &mut self.teal,
+            "yellow" => &mut self.yellow,
+            "peach" => &mut self.peach,
+            "red" => &mut self.red,
+            "maroon" => &mut self.maroon,
+            "mauve" => &mut self.mauve,
+            "pink" => &mut self.pink,
+            "red_bg" => &mut self.red_bg,
+            "green_bg" => &mut self.green_bg,
+            "yellow_bg" => &mut self.yellow_bg,
+            "blue_bg" => &mut self.blue_bg,
+            "red_highlight" => &mut self.red_highlight,
+            "green_highlight" => &mut self.green_highlight,
+            "blue_active" => &mut self.blue_active,
+            "backdrop" => &mut self.backdrop,
+            "selection" => &mut self.selection,
+            _ => return false,
+        };
+        *field = color;
+        true
+    }
+
+    /// Color of a commit graph lane
+    pub fn graph_color(&self, lane: usize) -> Rgba {
+        let fixed: &[u32] = match (self.graph_palette, self.appearance) {
+            (GraphPalette::Theme, _) => {
+                let palette = [
+                    self.blue,
+                    self.green,
+                    self.yellow,
+                    self.peach,
+                    self.red,
+                    self.mauve,
+                    self.teal,
+                    self.pink,
+                ];
+                return palette[lane % palette.len()];
+            }
+            (GraphPalette::OkabeIto, Appearance::Dark) => &OKABE_ITO_DARK,
+            (GraphPalette::OkabeIto, Appearance::Light) => &OKABE_ITO_LIGHT,
+            (GraphPalette::TolBright, _) => &TOL_BRIGHT,
+        };
+        rgb(fixed[lane % fixed.len()])
+    }
+
+    /// Color of a file status letter
+    pub fn file_status_color(&self, status: FileStatusType) -> Rgba {
+        match status {
+            FileStatusType::Added => self.green,
+            FileStatusType::Modified => self.peach,
+            FileStatusType::Deleted => self.red,
+            FileStatusType::Renamed => self.blue,
+            FileStatusType::Untracked => self.overlay2,
+            FileStatusType::Conflicted => self.yellow,
+        }
+    }
+}
+
+impl Global for Theme {}
+
+/// Access to the current theme from any context
+pub trait ActiveTheme {
+    fn theme(&self) -> &Theme;
+}
+
+impl ActiveTheme for App {
+    fn theme(&self) -> &Theme {
+        self.global::<Theme>()
+    }
+}
+
+/// Install `theme`, replacing the current one
+pub fn set_theme(theme: Theme, cx: &mut App) {
+    if cx.has_global::<Theme>() && *cx.theme() == theme {
+        return;
+    }
+    cx.set_global(theme);
+    cx.refresh_windows();
+}
+
+/// Pick the user theme named `user_theme` if it is installed, otherwise the
+/// built-in theme for `appearance`
+pub fn resolve_theme(user_theme: Option<&str>, appearance: Appearance, cx: &App) -> Theme {
+    user_theme
+        .and_then(|name| cx.try_global::<ThemeRegistry>()?.get(name).copied())
+        .unwrap_or_else(|| Theme::for_appearance(appearance))
+}
@@ -0,0 +1,157 @@
+#![allow(dead_code)]
+
+use super::{Appearance, Theme};
+use anyhow::{Context as _, Result};
+use futures::channel::mpsc::{self, UnboundedReceiver};
+use gpui::*;
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Debounce window for theme file changes
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(300);
+
+/// On-disk format of a user theme.
+///
+/// Colors are hex strings (`#rrggbb` or `#rrggbbaa`) keyed by theme field
+/// name. Missing colors fall back to the built-in theme for `appearance`.
+#[derive(Deserialize)]
+struct ThemeFile {
+    name: String,
+    #[serde(default)]
+    appearance: Option<String>,
+    #[serde(default)]
+    colors: HashMap<String, String>,
+}
+
+/// A theme loaded from the user's themes directory
+#[derive(Clone)]
+pub struct UserTheme {
+    pub name: String,
+    pub theme: Theme,
+}
+
+/// User themes from `<config>/awabancha/themes/*.json`, provided as a global
+#[derive(Default)]
+pub struct ThemeRegistry {
+    pub themes: Vec<UserTheme>,
+}
+
+impl Global for ThemeRegistry {}
+
+impl ThemeRegistry {
+    pub fn themes_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("awabancha").join("themes"))
+    }
+
+    /// Load every theme in the themes directory. Invalid files are logged
+    /// and skipped.
+    pub fn load() -> Self {
+        let Some(dir) = Self::themes_dir() else {
+            return Self::default();
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Self::default();
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        let mut themes: Vec<UserTheme> = Vec::new();
+        for path in paths {
+            match Self::load_file(&path) {
+                Ok(theme) => {
+                    // Later files replace earlier ones with the same name
+                    themes.retain(|t| t.name != theme.name);
+                    themes.push(theme);
+                }
+                Err(e) => log::warn!("Failed to load theme {}: {:#}", path.display(), e),
+            }
+        }
+
+        Self { themes }
+    }
+
+    fn load_file(path: &PathBuf) -> Result<UserTheme> {
+        let content = fs::read_to_string(path)?;
+        let file: ThemeFile = serde_json::from_str(&content)?;
+
+        let appearance = match file.appearance.as_deref() {
+            None | Some("dark") => Appearance::Dark,
+            Some("light") => Appearance::Light,
+            Some(other) => anyhow::bail!("Unknown appearance \"{}\"", other),
+        };
+
+        let mut theme = Theme::for_appearance(appearance);
+        for (key, value) in &file.colors {
+            let color = Rgba::try_from(value.as_str())
+                .with_context(|| format!("Invalid color \"{}\" for {}", value, key))?;
+            if !theme.set_color(key, color) {
+                log::warn!("Unknown color \"{}\" in theme {}", key, file.name);
+            }
+        }
+
+        Ok(UserTheme {
+            name: file.name,
+            theme,
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Theme> {
+        self.themes.iter().find(|t| t.name == name).map(|t| &t.theme)
+    }
+
+    /// Re-read the themes directory, replacing the global registry
+    pub fn reload(cx: &mut App) {
+        cx.set_global(Self::load());
+    }
+}
+
+/// Watches the themes directory so edited themes apply without a restart
+pub struct ThemeWatcher {
+    debouncer: Option<Debouncer<RecommendedWatcher>>,
+}
+
+impl ThemeWatcher {
+    pub fn new() -> Self {
+        Self { debouncer: None }
+    }
+
+    /// Start watching the themes directory, creating it if needed.
+    ///
+    /// Returns a stream that yields once per debounced batch of changes.
+    pub fn watch(&mut self) -> Result<UnboundedReceiver<()>> {
+        let dir = ThemeRegistry::themes_dir()
+            .ok_or_else(|| anyhow::anyhow!("No config directory"))?;
+        fs::create_dir_all(&dir)?;
+
+        let (tx, rx) = mpsc::unbounded::<()>();
+        let mut debouncer = new_debouncer(DEBOUNCE_DURATION, move |result: DebounceEventResult| {
+            match result {
+                Ok(events) => {
+                    if !events.is_empty() {
+                        let _ = tx.unbounded_send(());
+                    }
+                }
+                Err(e) => log::warn!("Theme watcher error: {}", e),
+            }
+        })?;
+        debouncer.watcher().watch(&dir, RecursiveMode::NonRecursive)?;
+
+        self.debouncer = Some(debouncer);
+        Ok(rx)
+    }
+}
+
+impl Default for ThemeWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
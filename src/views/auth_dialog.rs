@@ -0,0 +1,236 @@
+#![allow(dead_code)]
+
+use crate::components::{TextInputChanged, TextInputView};
+use crate::state::{GitCredentials, RemoteOperation};
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Events emitted by the re-authentication dialog
+#[derive(Clone)]
+pub enum AuthDialogEvent {
+    /// User entered new credentials; the operation should be retried
+    Submitted {
+        operation: RemoteOperation,
+        credentials: GitCredentials,
+    },
+    /// User dismissed the dialog
+    Cancelled,
+}
+
+impl EventEmitter<AuthDialogEvent> for AuthDialog {}
+
+/// Dialog shown when a remote rejects the stored credentials (HTTP 401/403)
+pub struct AuthDialog {
+    operation: RemoteOperation,
+    username_input: Entity<TextInputView>,
+    token_input: Entity<TextInputView>,
+    /// Error from the last attempt, shown above the form
+    error: Option<String>,
+    /// Whether a retry is in flight
+    is_submitting: bool,
+}
+
+impl AuthDialog {
+    pub fn new(
+        operation: RemoteOperation,
+        username: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let username_input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .with_placeholder("Username")
+                .with_content(username.unwrap_or_default())
+        });
+        let token_input = cx.new(|cx| {
+            TextInputView::new(cx).with_placeholder("Personal access token")
+        });
+
+        // Re-render so the retry button reflects whether the form is complete
+        for input in [&username_input, &token_input] {
+            cx.subscribe(input, |_this, _input, _event: &TextInputChanged, cx| {
+                cx.notify();
+            })
+            .detach();
+        }
+
+        Self {
+            operation,
+            username_input,
+            token_input,
+            error: None,
+            is_submitting: false,
+        }
+    }
+
+    pub fn operation(&self) -> RemoteOperation {
+        self.operation.clone()
+    }
+
+    /// Show an error after a failed retry and allow another attempt
+    pub fn set_error(&mut self, error: impl Into<String>, cx: &mut Context<Self>) {
+        self.error = Some(error.into());
+        self.is_submitting = false;
+        self.token_input.update(cx, |input, cx| {
+            input.set_content("", cx);
+        });
+        cx.notify();
+    }
+
+    fn submit(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        let username = self.username_input.read(cx).content().trim().to_string();
+        let password = self.token_input.read(cx).content().trim().to_string();
+        if username.is_empty() || password.is_empty() || self.is_submitting {
+            return;
+        }
+
+        self.is_submitting = true;
+        self.error = None;
+        cx.emit(AuthDialogEvent::Submitted {
+            operation: self.operation.clone(),
+            credentials: GitCredentials { username, password },
+        });
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(AuthDialogEvent::Cancelled);
+    }
+}
+
+impl Render for AuthDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let can_submit = !self.is_submitting
+            && !self.username_input.read(cx).content().trim().is_empty()
+            && !self.token_input.read(cx).content().trim().is_empty();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_4()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(theme.yellow)
+                            .child("Authentication Required"),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .child(format!(
+                                "{} was rejected by the remote. Your token may have expired or \
+                                 require two-factor authentication. Enter a new token to retry.",
+                                self.operation.label()
+                            )),
+                    ),
+            )
+            // Error from the previous attempt
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error),
+                )
+            })
+            // Form
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.overlay2)
+                                    .child("Username"),
+                            )
+                            .child(self.username_input.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.overlay2)
+                                    .child("Token"),
+                            )
+                            .child(self.token_input.clone()),
+                    ),
+            )
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id("auth-cancel-btn")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("Cancel")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.cancel(window, cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("auth-retry-btn")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(if can_submit {
+                                theme.blue
+                            } else {
+                                theme.surface1
+                            })
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.base)
+                            .when(can_submit, |this| {
+                                this.cursor_pointer().hover(|s| s.bg(theme.lavender))
+                            })
+                            .child(if self.is_submitting {
+                                "Retrying...".to_string()
+                            } else {
+                                format!("Sign In & {}", self.operation.label())
+                            })
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.submit(window, cx);
+                            })),
+                    ),
+            )
+    }
+}
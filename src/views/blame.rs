@@ -0,0 +1,237 @@
+#![allow(dead_code)]
+
+use crate::components::CopyDetailsButton;
+use crate::git::{BlameLine, FileBlame};
+use crate::state::{ErrorDetails, GitState};
+use crate::theme::{ActiveTheme, Theme};
+use gpui::prelude::*;
+use gpui::*;
+
+/// Emitted when the blame view should be closed
+#[derive(Clone, Debug)]
+pub struct BlameDismissed;
+
+impl EventEmitter<BlameDismissed> for BlameView {}
+
+/// Lines of a working tree file with the commit that last changed each one
+pub struct BlameView {
+    git_state: Entity<GitState>,
+    path: String,
+    blame: Option<FileBlame>,
+    error: Option<ErrorDetails>,
+}
+
+impl BlameView {
+    pub fn new(git_state: Entity<GitState>, path: String, cx: &mut Context<Self>) -> Self {
+        let task = git_state.read(cx).file_blame(&path, cx);
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(blame) => this.blame = Some(blame),
+                    Err(e) => {
+                        log::error!("Failed to blame {}: {}", this.path, e);
+                        this.error = Some(this.git_state.read(cx).error_details("Blame", &e));
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+
+        Self {
+            git_state,
+            path,
+            blame: None,
+            error: None,
+        }
+    }
+
+    /// Scroll the graph to the commit and close the view
+    fn show_commit(&mut self, sha: &str, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.focus_commit(sha, cx));
+        match result {
+            Ok(()) => cx.emit(BlameDismissed),
+            Err(e) => {
+                log::error!("Failed to show commit: {}", e);
+                self.error = Some(self.git_state.read(cx).error_details("Show commit", &e));
+                cx.notify();
+            }
+        }
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(BlameDismissed);
+    }
+}
+
+impl Render for BlameView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let lines = self.blame.as_ref().map(|blame| blame.lines.clone());
+        let loading = lines.is_none() && self.error.is_none();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Blame"),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .child(self.path.clone()),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("close-blame")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error.message)
+                        .child(CopyDetailsButton::new("blame-error-details", error.report)),
+                )
+            })
+            // Lines
+            .child(
+                div()
+                    .id("blame-lines")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .flex()
+                    .flex_col()
+                    .when(loading, |this| {
+                        this.child(
+                            div()
+                                .py_8()
+                                .text_sm()
+                                .text_center()
+                                .text_color(theme.overlay0)
+                                .child("Loading…"),
+                        )
+                    })
+                    .children(
+                        lines
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|line| self.render_line(line, &theme, cx)),
+                    ),
+            )
+    }
+}
+
+impl BlameView {
+    fn render_line(&self, line: BlameLine, theme: &Theme, cx: &mut Context<Self>) -> AnyElement {
+        // Commit details are shown once per run of lines
+        let annotation = match (&line.commit, line.starts_run) {
+            (Some(commit), true) => {
+                let sha = commit.sha.clone();
+                let date = commit.timestamp.with_timezone(&chrono::Local);
+                let date = date.format("%Y-%m-%d").to_string();
+                div()
+                    .id(ElementId::NamedInteger("blame-commit".into(), line.line as u64))
+                    .flex()
+                    .gap_2()
+                    .overflow_hidden()
+                    .cursor_pointer()
+                    .hover(|s| s.text_color(theme.blue))
+                    .child(
+                        div()
+                            .flex_shrink_0()
+                            .text_color(theme.peach)
+                            .child(commit.short_sha.clone()),
+                    )
+                    .child(div().truncate().child(commit.author.clone()))
+                    .child(div().flex_shrink_0().text_color(theme.overlay0).child(date))
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.show_commit(&sha, cx);
+                    }))
+                    .into_any_element()
+            }
+            (None, true) => div()
+                .text_color(theme.overlay0)
+                .child("Not committed yet")
+                .into_any_element(),
+            (_, false) => div().into_any_element(),
+        };
+
+        div()
+            .flex()
+            .items_start()
+            .text_xs()
+            .when(line.starts_run && line.line > 1, |this| {
+                this.border_t_1().border_color(theme.surface0)
+            })
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .w(px(240.0))
+                    .pr_2()
+                    .text_color(theme.overlay2)
+                    .child(annotation),
+            )
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .w_10()
+                    .pr_2()
+                    .text_right()
+                    .text_color(theme.overlay0)
+                    .child(line.line.to_string()),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .font_family("monospace")
+                    .text_color(theme.text)
+                    .child(line.content),
+            )
+            .into_any_element()
+    }
+}
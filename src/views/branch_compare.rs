@@ -0,0 +1,543 @@
+#![allow(dead_code)]
+
+use crate::git::{CommitInfo, MboxOptions};
+use crate::components::{AuthorLink, CopyDetailsButton};
+use crate::state::{ErrorDetails, GitState};
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Maximum number of commits listed for a branch
+const COMPARE_LIMIT: usize = 500;
+
+/// Emitted when the comparison view should be closed
+#[derive(Clone, Debug)]
+pub struct BranchCompareDismissed;
+
+impl EventEmitter<BranchCompareDismissed> for BranchCompareView {}
+
+/// Lists commits that exist only on another branch and lets the user
+/// cherry-pick a selection of them onto the current branch
+pub struct BranchCompareView {
+    git_state: Entity<GitState>,
+    /// Branch being compared against HEAD
+    branch: Option<String>,
+    /// Commits on `branch` that are not in HEAD, newest first
+    commits: Vec<CommitInfo>,
+    /// SHAs of commits selected for cherry-picking
+    selected: Vec<String>,
+    /// Start exported patches with a "[PATCH 0/n]" cover letter
+    cover_letter: bool,
+    error: Option<ErrorDetails>,
+}
+
+impl BranchCompareView {
+    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+        // Observe git state changes (branch list, cherry-pick progress)
+        cx.observe(&git_state, |_this, _git_state, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            git_state,
+            branch: None,
+            commits: Vec::new(),
+            selected: Vec::new(),
+            cover_letter: false,
+            error: None,
+        }
+    }
+
+    fn select_branch(&mut self, name: String, cx: &mut Context<Self>) {
+        let git_state = self.git_state.read(cx);
+        match git_state.commits_not_in_head(&name, COMPARE_LIMIT) {
+            Ok(commits) => {
+                self.commits = commits;
+                self.error = None;
+            }
+            Err(e) => {
+                log::error!("Failed to compare branch {}: {}", name, e);
+                self.commits.clear();
+                let operation = format!("Compare branch {}", name);
+                self.error = Some(git_state.error_details(&operation, &e));
+            }
+        }
+        self.branch = Some(name);
+        self.selected.clear();
+        cx.notify();
+    }
+
+    fn toggle_commit(&mut self, sha: String, cx: &mut Context<Self>) {
+        if let Some(pos) = self.selected.iter().position(|s| *s == sha) {
+            self.selected.remove(pos);
+        } else {
+            self.selected.push(sha);
+        }
+        cx.notify();
+    }
+
+    fn toggle_all(&mut self, cx: &mut Context<Self>) {
+        let selectable: Vec<String> = self
+            .commits
+            .iter()
+            .filter(|c| c.parents.len() <= 1)
+            .map(|c| c.sha.clone())
+            .collect();
+
+        if self.selected.len() == selectable.len() {
+            self.selected.clear();
+        } else {
+            self.selected = selectable;
+        }
+        cx.notify();
+    }
+
+    /// Selected commits, oldest first
+    fn selected_oldest_first(&self) -> Vec<String> {
+        self.commits
+            .iter()
+            .rev()
+            .filter(|c| self.selected.contains(&c.sha))
+            .map(|c| c.sha.clone())
+            .collect()
+    }
+
+    fn cherry_pick_selected(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        // Apply oldest first so the history keeps its original order
+        let shas = self.selected_oldest_first();
+
+        self.selected.clear();
+        self.git_state.update(cx, |state, cx| {
+            state.cherry_pick_sequence(shas, cx);
+        });
+    }
+
+    fn toggle_cover_letter(&mut self, cx: &mut Context<Self>) {
+        self.cover_letter = !self.cover_letter;
+        cx.notify();
+    }
+
+    /// Save the selected commits as an mbox for `git am` or a mail client
+    fn export_selected(&mut self, cx: &mut Context<Self>) {
+        let shas = self.selected_oldest_first();
+        let options = MboxOptions {
+            cover_letter: self.cover_letter,
+            ..MboxOptions::default()
+        };
+        let git_state = self.git_state.read(cx);
+        let mbox = match git_state.export_mbox(&shas, &options) {
+            Ok(mbox) => mbox,
+            Err(e) => {
+                log::error!("Failed to export patches: {}", e);
+                self.error = Some(git_state.error_details("Export patches", &e));
+                cx.notify();
+                return;
+            }
+        };
+        let Some(dir) = git_state.path.clone() else {
+            return;
+        };
+
+        let branch = self.branch.as_deref().unwrap_or("patches");
+        let file_name = format!("{}.mbox", branch.replace('/', "-"));
+        let receiver = cx.prompt_for_new_path(&dir, Some(&file_name));
+        cx.spawn(async move |_this, _cx| {
+            if let Ok(Ok(Some(path))) = receiver.await {
+                if let Err(e) = std::fs::write(&path, mbox) {
+                    log::error!("Failed to save patches: {}", e);
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        self.git_state.update(cx, |state, cx| {
+            if !state
+                .cherry_pick_progress
+                .as_ref()
+                .is_some_and(|p| p.is_running())
+            {
+                state.clear_cherry_pick_progress(cx);
+            }
+        });
+        cx.emit(BranchCompareDismissed);
+    }
+}
+
+impl Render for BranchCompareView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let git_state = self.git_state.read(cx);
+        let current_branch = git_state
+            .current_branch()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "HEAD".to_string());
+        let branches: Vec<(String, Option<String>)> = git_state
+            .branches
+            .iter()
+            .filter(|b| !b.is_head && !b.name.ends_with("/HEAD"))
+            .map(|b| (b.name.clone(), b.tracking_label()))
+            .collect();
+        let progress = git_state.cherry_pick_progress.clone();
+        let is_running = progress.as_ref().is_some_and(|p| p.is_running());
+        let selected_count = self.selected.len();
+        let can_pick = selected_count > 0 && !is_running;
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Compare Branches"),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .child(match &self.branch {
+                                        Some(branch) => format!(
+                                            "{} commit{} on {} not in {}",
+                                            self.commits.len(),
+                                            if self.commits.len() == 1 { "" } else { "s" },
+                                            branch,
+                                            current_branch
+                                        ),
+                                        None => format!("Select a branch to compare with {}", current_branch),
+                                    }),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("close-compare")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    ),
+            )
+            // Branch selector
+            .child(
+                div()
+                    .id("compare-branch-list")
+                    .flex()
+                    .flex_wrap()
+                    .gap_1()
+                    .max_h(px(96.0))
+                    .overflow_y_scroll()
+                    .children(branches.into_iter().map(|(name, tracking)| {
+                        let is_selected = self.branch.as_ref() == Some(&name);
+                        let branch_name = name.clone();
+
+                        div()
+                            .id(ElementId::Name(format!("compare-branch-{}", name).into()))
+                            .px_2()
+                            .py_px()
+                            .rounded_md()
+                            .text_xs()
+                            .cursor_pointer()
+                            .bg(if is_selected {
+                                theme.blue
+                            } else {
+                                theme.surface0
+                            })
+                            .text_color(if is_selected {
+                                theme.base
+                            } else {
+                                theme.text
+                            })
+                            .when(!is_selected, |this| this.hover(|s| s.bg(theme.surface1)))
+                            .flex()
+                            .gap_1()
+                            .child(name)
+                            .when_some(tracking, |this, tracking| {
+                                this.child(
+                                    div()
+                                        .text_color(if is_selected {
+                                            theme.surface0
+                                        } else {
+                                            theme.overlay0
+                                        })
+                                        .child(format!("→ {}", tracking)),
+                                )
+                            })
+                            .on_click(cx.listener(move |this, _event, _window, cx| {
+                                this.select_branch(branch_name.clone(), cx);
+                            }))
+                    })),
+            )
+            // Commit list
+            .child(
+                div()
+                    .id("compare-commit-list")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .when_some(self.error.clone(), |this, error| {
+                        this.child(
+                            div()
+                                .flex()
+                                .flex_col()
+                                .gap_1()
+                                .text_sm()
+                                .text_color(theme.red)
+                                .child(error.message)
+                                .child(CopyDetailsButton::new(
+                                    "compare-error-details",
+                                    error.report,
+                                )),
+                        )
+                    })
+                    .when(self.branch.is_some() && self.commits.is_empty() && self.error.is_none(), |this| {
+                        this.child(
+                            div()
+                                .text_sm()
+                                .text_color(theme.overlay0)
+                                .child("No commits unique to this branch"),
+                        )
+                    })
+                    .children(self.commits.iter().map(|commit| {
+                        self.render_commit(commit, cx)
+                    })),
+            )
+            // Progress
+            .when_some(progress, |this, progress| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(if progress.error.is_some() {
+                            theme.red
+                        } else if progress.is_running() {
+                            theme.yellow
+                        } else {
+                            theme.green
+                        })
+                        .child(match (&progress.error, &progress.conflicted, &progress.current) {
+                            (Some(error), _, _) => format!(
+                                "Stopped after {}/{}: {}",
+                                progress.completed, progress.total, error
+                            ),
+                            (None, Some(conflicted), _) => format!(
+                                "Stopped at {} with conflicts ({}/{})",
+                                conflicted,
+                                progress.completed + 1,
+                                progress.total
+                            ),
+                            (None, None, Some(current)) => format!(
+                                "Cherry-picking {} ({}/{})...",
+                                current,
+                                progress.completed + 1,
+                                progress.total
+                            ),
+                            (None, None, None) => format!(
+                                "Cherry-picked {}/{} commits",
+                                progress.completed, progress.total
+                            ),
+                        }),
+                )
+            })
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .id("compare-select-all")
+                                    .px_3()
+                                    .py_1()
+                                    .rounded_md()
+                                    .bg(theme.surface0)
+                                    .text_sm()
+                                    .text_color(theme.text)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface1))
+                                    .child("Select All")
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.toggle_all(cx);
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .id("compare-cover-letter")
+                                    .px_3()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .cursor_pointer()
+                                    .when(self.cover_letter, |this| {
+                                        this.bg(theme.blue).text_color(theme.base)
+                                    })
+                                    .when(!self.cover_letter, |this| {
+                                        this.bg(theme.surface0)
+                                            .text_color(theme.text)
+                                            .hover(|s| s.bg(theme.surface1))
+                                    })
+                                    .child("Cover Letter")
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.toggle_cover_letter(cx);
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .id("compare-export-patches")
+                                    .px_3()
+                                    .py_1()
+                                    .rounded_md()
+                                    .bg(theme.surface0)
+                                    .text_sm()
+                                    .text_color(if selected_count > 0 {
+                                        theme.text
+                                    } else {
+                                        theme.overlay0
+                                    })
+                                    .when(selected_count > 0, |this| {
+                                        this.cursor_pointer()
+                                            .hover(|s| s.bg(theme.surface1))
+                                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                                this.export_selected(cx);
+                                            }))
+                                    })
+                                    .child("Export Patches..."),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("compare-cherry-pick")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(if can_pick {
+                                theme.blue
+                            } else {
+                                theme.surface1
+                            })
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.base)
+                            .when(can_pick, |this| {
+                                this.cursor_pointer()
+                                    .hover(|s| s.bg(theme.lavender))
+                                    .on_click(cx.listener(|this, _event, window, cx| {
+                                        this.cherry_pick_selected(window, cx);
+                                    }))
+                            })
+                            .child(format!(
+                                "Cherry-pick {} onto {}",
+                                selected_count, current_branch
+                            )),
+                    ),
+            )
+    }
+}
+
+impl BranchCompareView {
+    fn render_commit(&self, commit: &CommitInfo, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let sha = commit.sha.clone();
+        let is_selected = self.selected.contains(&sha);
+        // Merge commits need a mainline and are not offered for cherry-picking
+        let is_merge = commit.parents.len() > 1;
+
+        div()
+            .id(ElementId::Name(format!("compare-commit-{}", sha).into()))
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .when(!is_merge, |this| {
+                this.cursor_pointer()
+                    .hover(|s| s.bg(theme.surface0))
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.toggle_commit(sha.clone(), cx);
+                    }))
+            })
+            .child(
+                div()
+                    .w_4()
+                    .h_4()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(if is_merge {
+                        theme.surface1
+                    } else {
+                        theme.overlay0
+                    })
+                    .when(is_selected, |this| {
+                        this.bg(theme.blue)
+                            .border_color(theme.blue)
+                            .text_xs()
+                            .text_color(theme.base)
+                            .child("✓")
+                    }),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.yellow)
+                    .child(commit.short_sha.clone()),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .text_sm()
+                    .text_color(if is_merge {
+                        theme.overlay0
+                    } else {
+                        theme.text
+                    })
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .text_ellipsis()
+                    .child(commit.message.clone()),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.overlay0)
+                    .child(AuthorLink::new(
+                        ElementId::Name(format!("compare-author-{}", commit.sha).into()),
+                        commit,
+                    )),
+            )
+    }
+}
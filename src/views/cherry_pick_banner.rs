@@ -0,0 +1,229 @@
+#![allow(dead_code)]
+
+use crate::actions::ShowConflictDialog;
+use crate::git::PickKind;
+use crate::state::GitState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Shown above the panels while a multi-commit cherry-pick runs, or while a
+/// cherry-pick or revert waits on conflicts, with the controls to resolve,
+/// continue or abort it
+pub struct CherryPickBanner {
+    git_state: Entity<GitState>,
+    /// Error from the last action
+    error: Option<String>,
+}
+
+impl CherryPickBanner {
+    pub fn new(git_state: Entity<GitState>, _cx: &mut Context<Self>) -> Self {
+        Self {
+            git_state,
+            error: None,
+        }
+    }
+
+    fn continue_pick(&mut self, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.continue_pick(cx));
+        self.finish(result, "continue", cx);
+    }
+
+    fn abort(&mut self, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.abort_pick(cx));
+        self.finish(result, "abort", cx);
+    }
+
+    fn dismiss(&mut self, cx: &mut Context<Self>) {
+        self.error = None;
+        self.git_state
+            .update(cx, |state, cx| state.clear_cherry_pick_progress(cx));
+    }
+
+    fn finish(&mut self, result: anyhow::Result<()>, action: &str, cx: &mut Context<Self>) {
+        self.error = match result {
+            Ok(()) => None,
+            Err(e) => {
+                log::error!("Failed to {}: {}", action, e);
+                Some(e.to_string())
+            }
+        };
+        cx.notify();
+    }
+}
+
+impl Render for CherryPickBanner {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let git_state = self.git_state.read(cx);
+        let has_conflicts = git_state.conflict_info.is_some();
+        let waiting_detail = |remaining: Option<usize>| {
+            if has_conflicts {
+                match remaining {
+                    Some(remaining) => format!(
+                        "Resolve the conflicts, then continue to commit it and pick the \
+                         remaining {}, or abort to go back to where you started.",
+                        remaining
+                    ),
+                    None => "Resolve the conflicts, then continue to commit it, or abort."
+                        .to_string(),
+                }
+            } else {
+                "Conflicts resolved. Continue to commit it with its original message."
+                    .to_string()
+            }
+        };
+
+        let (title, detail, waiting, running, failed) = match (
+            git_state.cherry_pick_progress.clone(),
+            git_state.interrupted_pick.clone(),
+        ) {
+            (Some(progress), _) => {
+                let (title, detail) =
+                    match (&progress.error, &progress.conflicted, &progress.current) {
+                        (Some(error), _, _) => (
+                            format!(
+                                "Cherry-pick stopped after {}/{} commits",
+                                progress.completed, progress.total
+                            ),
+                            Some(error.clone()),
+                        ),
+                        (None, Some(conflicted), _) => (
+                            format!("Cherry-picking {} stopped on conflicts", conflicted),
+                            // The conflicted commit isn't counted as completed yet
+                            Some(waiting_detail(Some(progress.total - progress.completed - 1))),
+                        ),
+                        (None, None, Some(current)) => (
+                            format!(
+                                "Cherry-picking {} ({}/{})...",
+                                current,
+                                progress.completed + 1,
+                                progress.total
+                            ),
+                            None,
+                        ),
+                        (None, None, None) => (
+                            format!(
+                                "Cherry-picked {}/{} commits",
+                                progress.completed, progress.total
+                            ),
+                            None,
+                        ),
+                    };
+                let waiting = progress.conflicted.is_some();
+                (title, detail, waiting, progress.is_running(), progress.error.is_some())
+            }
+            (None, Some(pick)) => {
+                let verb = match pick.kind {
+                    PickKind::CherryPick => "Cherry-picking",
+                    PickKind::Revert => "Reverting",
+                };
+                let title = format!(
+                    "{} {} \"{}\" stopped on conflicts",
+                    verb,
+                    pick.short_sha(),
+                    pick.summary
+                );
+                (title, Some(waiting_detail(None)), true, true, false)
+            }
+            (None, None) => return div(),
+        };
+
+        let (accent, background) = if failed {
+            (theme.red, theme.red_bg)
+        } else if running {
+            (theme.yellow, theme.yellow_bg)
+        } else {
+            (theme.green, theme.green_bg)
+        };
+
+        let button = |id: &'static str, label: &'static str, enabled: bool| {
+            div()
+                .id(id)
+                .flex_shrink_0()
+                .px_3()
+                .py_1()
+                .rounded_md()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .bg(if enabled { accent } else { theme.surface1 })
+                .text_color(if enabled { theme.base } else { theme.overlay0 })
+                .when(enabled, |this| {
+                    this.cursor_pointer().hover(|s| s.opacity(0.9))
+                })
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .bg(background)
+            .border_b_1()
+            .border_color(accent)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_col()
+                            .text_xs()
+                            .child(
+                                div()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(accent)
+                                    .child(title),
+                            )
+                            .when_some(detail, |this, detail| {
+                                this.child(div().text_color(theme.text).child(detail))
+                            }),
+                    )
+                    .when(waiting && has_conflicts, |this| {
+                        this.child(
+                            button("cherry-pick-resolve", "Resolve Conflicts", true).on_click(
+                                |_event, window, cx| {
+                                    window.dispatch_action(Box::new(ShowConflictDialog), cx);
+                                },
+                            ),
+                        )
+                    })
+                    .when(waiting, |this| {
+                        this.child(
+                            button("cherry-pick-continue", "Continue", !has_conflicts).when(
+                                !has_conflicts,
+                                |this| {
+                                    this.on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.continue_pick(cx);
+                                    }))
+                                },
+                            ),
+                        )
+                        .child(button("cherry-pick-abort", "Abort", true).on_click(
+                            cx.listener(|this, _event, _window, cx| {
+                                this.abort(cx);
+                            }),
+                        ))
+                    })
+                    .when(!running, |this| {
+                        this.child(button("cherry-pick-dismiss", "Dismiss", true).on_click(
+                            cx.listener(|this, _event, _window, cx| {
+                                this.dismiss(cx);
+                            }),
+                        ))
+                    }),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_xs().text_color(theme.red).child(error))
+            })
+    }
+}
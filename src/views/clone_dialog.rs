@@ -0,0 +1,379 @@
+#![allow(dead_code)]
+
+use crate::components::{TextInputChanged, TextInputView};
+use crate::git::remote::{self, CloneOptions, RemoteAuth};
+use crate::state::GitCredentials;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+use std::path::PathBuf;
+
+/// Events emitted by the clone dialog
+#[derive(Clone)]
+pub enum CloneDialogEvent {
+    /// The repository was cloned into this directory
+    Cloned(PathBuf),
+    Cancelled,
+}
+
+impl EventEmitter<CloneDialogEvent> for CloneDialog {}
+
+/// Asks for a remote URL and a parent directory, then clones into a
+/// directory named after the repository. Huge repositories can be cloned
+/// shallow or with a single branch.
+pub struct CloneDialog {
+    url_input: Entity<TextInputView>,
+    /// Number of commits to fetch; empty for the full history
+    depth_input: Entity<TextInputView>,
+    /// Branch to check out; empty for the remote's default
+    branch_input: Entity<TextInputView>,
+    single_branch: bool,
+    /// Directory the clone is created in
+    parent_dir: Option<PathBuf>,
+    auth: Option<GitCredentials>,
+    error: Option<String>,
+    is_cloning: bool,
+}
+
+impl CloneDialog {
+    pub fn new(auth: Option<GitCredentials>, cx: &mut Context<Self>) -> Self {
+        let url_input = cx.new(|cx| {
+            TextInputView::new(cx).with_placeholder("https://github.com/owner/repo.git")
+        });
+
+        let depth_input = cx.new(|cx| TextInputView::new(cx).with_placeholder("Full history"));
+        let branch_input = cx.new(|cx| TextInputView::new(cx).with_placeholder("Default branch"));
+
+        // Re-render so the destination and validation follow the inputs
+        for input in [&url_input, &depth_input, &branch_input] {
+            cx.subscribe(input, |_this, _input, _event: &TextInputChanged, cx| {
+                cx.notify();
+            })
+            .detach();
+        }
+
+        Self {
+            url_input,
+            depth_input,
+            branch_input,
+            single_branch: false,
+            parent_dir: dirs::home_dir(),
+            auth,
+            error: None,
+            is_cloning: false,
+        }
+    }
+
+    fn url(&self, cx: &App) -> String {
+        self.url_input.read(cx).content().trim().to_string()
+    }
+
+    fn destination(&self, cx: &App) -> Option<PathBuf> {
+        let name = remote::clone_directory_name(&self.url(cx))?;
+        Some(self.parent_dir.as_ref()?.join(name))
+    }
+
+    /// The clone options entered, or why they are invalid
+    fn options(&self, cx: &App) -> std::result::Result<CloneOptions, &'static str> {
+        let depth = self.depth_input.read(cx).content().trim().to_string();
+        let depth = match depth.as_str() {
+            "" => None,
+            depth => match depth.parse::<u32>() {
+                Ok(depth) if depth > 0 => Some(depth),
+                _ => return Err("Depth must be a positive number of commits"),
+            },
+        };
+        let branch = self.branch_input.read(cx).content().trim().to_string();
+        let branch = (!branch.is_empty()).then_some(branch);
+        if self.single_branch && branch.is_none() {
+            return Err("Enter the branch to clone on its own");
+        }
+
+        Ok(CloneOptions {
+            depth,
+            branch,
+            single_branch: self.single_branch,
+        })
+    }
+
+    fn toggle_single_branch(&mut self, cx: &mut Context<Self>) {
+        self.single_branch = !self.single_branch;
+        cx.notify();
+    }
+
+    fn choose_parent_dir(&mut self, cx: &mut Context<Self>) {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: false,
+            directories: true,
+            multiple: false,
+            prompt: Some("Clone Here".into()),
+        });
+
+        cx.spawn(async move |this, cx| {
+            if let Ok(Ok(Some(paths))) = receiver.await {
+                if let Some(path) = paths.into_iter().next() {
+                    this.update(cx, |dialog, cx| {
+                        dialog.parent_dir = Some(path);
+                        cx.notify();
+                    })
+                    .ok();
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn clone_repository(&mut self, cx: &mut Context<Self>) {
+        let url = self.url(cx);
+        let Some(destination) = self.destination(cx) else {
+            return;
+        };
+        let Ok(options) = self.options(cx) else {
+            return;
+        };
+        if self.is_cloning {
+            return;
+        }
+        if destination.exists() {
+            self.error = Some(format!("{} already exists", destination.display()));
+            cx.notify();
+            return;
+        }
+
+        self.is_cloning = true;
+        self.error = None;
+        cx.notify();
+
+        let auth = self.auth.clone();
+        cx.spawn(async move |this, cx| {
+            let target = destination.clone();
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    let auth = auth.map(|creds| RemoteAuth {
+                        username: creds.username,
+                        password: creds.password,
+                    });
+                    remote::clone_repository(&url, &target, auth.as_ref(), &options).map(|_| ())
+                })
+                .await;
+
+            this.update(cx, |dialog, cx| {
+                dialog.is_cloning = false;
+                match result {
+                    Ok(()) => cx.emit(CloneDialogEvent::Cloned(destination)),
+                    Err(e) => {
+                        log::error!("Failed to clone repository: {}", e);
+                        dialog.error = Some(e.to_string());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn cancel(&mut self, cx: &mut Context<Self>) {
+        // A clone in flight can't be interrupted; let it finish
+        if !self.is_cloning {
+            cx.emit(CloneDialogEvent::Cancelled);
+        }
+    }
+}
+
+impl Render for CloneDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let destination = self.destination(cx);
+        let options_error = self.options(cx).err();
+        let can_clone = !self.is_cloning && destination.is_some() && options_error.is_none();
+        let single_branch = self.single_branch;
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_4()
+            // Header
+            .child(
+                div()
+                    .text_lg()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(theme.text)
+                    .child("Clone Repository"),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error),
+                )
+            })
+            // Form
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(div().text_xs().text_color(theme.overlay2).child("URL"))
+                    .child(self.url_input.clone()),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(div().text_xs().text_color(theme.overlay2).child("Destination"))
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .text_sm()
+                                    .text_color(if destination.is_some() {
+                                        theme.text
+                                    } else {
+                                        theme.overlay0
+                                    })
+                                    .truncate()
+                                    .child(match &destination {
+                                        Some(path) => path.display().to_string(),
+                                        None => "Enter a URL to pick a directory name".to_string(),
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("clone-choose-dir")
+                                    .flex_shrink_0()
+                                    .px_3()
+                                    .py_1()
+                                    .rounded_md()
+                                    .bg(theme.surface0)
+                                    .text_sm()
+                                    .text_color(theme.text)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface1))
+                                    .child("Choose...")
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.choose_parent_dir(cx);
+                                    })),
+                            ),
+                    ),
+            )
+            // Options for large repositories
+            .child(
+                div()
+                    .flex()
+                    .gap_3()
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(div().text_xs().text_color(theme.overlay2).child("Depth"))
+                            .child(self.depth_input.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(div().text_xs().text_color(theme.overlay2).child("Branch"))
+                            .child(self.branch_input.clone()),
+                    ),
+            )
+            .child(
+                div()
+                    .id("clone-single-branch")
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .cursor_pointer()
+                    .child(
+                        div()
+                            .w_4()
+                            .h_4()
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(theme.overlay0)
+                            .when(single_branch, |this| {
+                                this.bg(theme.blue)
+                                    .border_color(theme.blue)
+                                    .text_xs()
+                                    .text_color(theme.base)
+                                    .child("✓")
+                            }),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .child("Fetch only this branch"),
+                    )
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.toggle_single_branch(cx);
+                    })),
+            )
+            .when_some(options_error, |this, error| {
+                this.child(div().text_xs().text_color(theme.red).child(error))
+            })
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id("clone-cancel-btn")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("Cancel")
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.cancel(cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("clone-confirm-btn")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(if can_clone { theme.blue } else { theme.surface1 })
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.base)
+                            .when(can_clone, |this| {
+                                this.cursor_pointer().hover(|s| s.bg(theme.lavender))
+                            })
+                            .child(if self.is_cloning { "Cloning..." } else { "Clone" })
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.clone_repository(cx);
+                            })),
+                    ),
+            )
+    }
+}
@@ -1,5 +1,8 @@
-use crate::components::TextInputView;
-use crate::state::GitState;
+use crate::actions::UndoCommit;
+use crate::components::{TextInputChanged, TextInputView};
+use crate::i18n::{t_plural, ActiveLocale};
+use crate::state::{CommitLintMode, CommitOutcome, DraftState, GitState, HookRun};
+use crate::theme::ActiveTheme;
 use gpui::prelude::*;
 use gpui::*;
 
@@ -7,8 +10,16 @@ pub struct CommitForm {
     git_state: Entity<GitState>,
     commit_message: Entity<TextInputView>,
     amend: bool,
+    /// The commit being amended is already on a remote
+    amend_pushed: bool,
     /// Saved message when switching between amend/non-amend modes
     saved_message: String,
+    /// Crash-recovery drafts the message is mirrored into
+    drafts: Option<Entity<DraftState>>,
+    /// A commit is waiting on the pre-commit and commit-msg hooks
+    awaiting_hooks: bool,
+    /// The hook output panel is expanded
+    hook_output_open: bool,
 }
 
 impl CommitForm {
@@ -20,7 +31,33 @@ impl CommitForm {
         });
 
         // Observe git state changes
-        cx.observe(&git_state, |_this, _git_state, cx| {
+        cx.observe(&git_state, |this, git_state, cx| {
+            if this.awaiting_hooks {
+                let finished = git_state
+                    .read(cx)
+                    .hook_run
+                    .as_ref()
+                    .map_or(Some(false), |run| (!run.running).then_some(run.committed));
+                if let Some(committed) = finished {
+                    this.awaiting_hooks = false;
+                    if committed {
+                        this.clear_message(cx);
+                    } else {
+                        // Show why the hooks stopped the commit
+                        this.hook_output_open = true;
+                    }
+                }
+            }
+            cx.notify();
+        })
+        .detach();
+
+        // Amend mode shows the previous commit's message, which is not a draft
+        cx.subscribe(&commit_message, |this, _input, event: &TextInputChanged, cx| {
+            if let (Some(drafts), false) = (&this.drafts, this.amend) {
+                drafts.update(cx, |drafts, _cx| drafts.set_commit_message(&event.0));
+            }
+            // Message checks are shown as it is typed
             cx.notify();
         })
         .detach();
@@ -29,17 +66,50 @@ impl CommitForm {
             git_state,
             commit_message,
             amend: false,
+            amend_pushed: false,
             saved_message: String::new(),
+            drafts: None,
+            awaiting_hooks: false,
+            hook_output_open: false,
+        }
+    }
+
+    pub fn set_drafts(&mut self, drafts: Entity<DraftState>, cx: &mut Context<Self>) {
+        cx.observe(&drafts, |this, _drafts, cx| {
+            this.apply_restored_draft(cx);
+        })
+        .detach();
+        self.drafts = Some(drafts);
+        self.apply_restored_draft(cx);
+    }
+
+    /// Fill in a commit message recovered after a crash
+    fn apply_restored_draft(&mut self, cx: &mut Context<Self>) {
+        let Some(drafts) = &self.drafts else {
+            return;
+        };
+        let Some(message) = drafts.update(cx, |drafts, _cx| drafts.take_restored_commit_message())
+        else {
+            return;
+        };
+        if self.amend {
+            self.saved_message = message;
+        } else {
+            self.commit_message
+                .update(cx, |input, cx| input.set_content(message, cx));
         }
     }
 
     fn toggle_amend(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
         let current_message = self.commit_message.read(cx).content().to_string();
+        // Flip first so the message swap below is not mistaken for a draft edit
+        self.amend = !self.amend;
 
-        if !self.amend {
+        if self.amend {
             // Switching to amend mode
             // Save current message and load previous commit message
             self.saved_message = current_message;
+            self.amend_pushed = self.git_state.read(cx).is_head_pushed();
             if let Some(last_message) = self.git_state.read(cx).get_last_commit_message() {
                 let trimmed = last_message.trim().to_string();
                 self.commit_message.update(cx, |input, cx| {
@@ -55,7 +125,6 @@ impl CommitForm {
             });
         }
 
-        self.amend = !self.amend;
         cx.notify();
     }
 
@@ -65,38 +134,154 @@ impl CommitForm {
             return;
         }
 
+        let git_state = self.git_state.read(cx);
+        if self.awaiting_hooks
+            || git_state.commit_lint == CommitLintMode::Block
+                && !git_state.lint_message(&message).is_empty()
+        {
+            return;
+        }
+
         let amend = self.amend;
-        self.git_state.update(cx, |state, cx| {
-            let result = if amend {
-                state.amend_commit(&message, cx)
-            } else {
-                state.create_commit(&message, cx)
-            };
-
-            if let Err(e) = result {
-                log::error!("Failed to commit: {}", e);
-            }
+        let result = self.git_state.update(cx, |state, cx| {
+            state.commit_with_hooks(&message, amend, cx)
         });
 
-        // Clear the commit message after successful commit
+        match result {
+            Ok(CommitOutcome::Committed) => {
+                self.clear_message(cx);
+                // Focus back to the input
+                let focus_handle = self.commit_message.read(cx).focus_handle(cx);
+                window.focus(&focus_handle, cx);
+            }
+            // The message is cleared once the hooks pass
+            Ok(CommitOutcome::Pending) => {
+                self.awaiting_hooks = true;
+                self.hook_output_open = false;
+                cx.notify();
+            }
+            // Keep the message so it can be fixed and retried
+            Err(e) => log::error!("Failed to commit: {}", e),
+        }
+    }
+
+    /// Clear the commit message (and its draft) after a successful commit
+    fn clear_message(&mut self, cx: &mut Context<Self>) {
+        self.amend = false;
+        self.amend_pushed = false;
+        self.saved_message.clear();
         self.commit_message.update(cx, |input, cx| {
             input.set_content("", cx);
         });
-        self.amend = false;
         cx.notify();
+    }
+
+    fn render_hook_run(&self, run: &HookRun, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let (status, color, bg) = match (&run.error, run.running) {
+            (Some(error), _) => (error.clone(), theme.red, theme.red_bg),
+            (None, true) => ("Running hooks…".to_string(), theme.overlay2, theme.mantle),
+            (None, false) => ("Hooks passed".to_string(), theme.green, theme.green_bg),
+        };
+        let open = self.hook_output_open && !run.output.is_empty();
 
-        // Focus back to the input
-        let focus_handle = self.commit_message.read(cx).focus_handle(cx);
-        window.focus(&focus_handle, cx);
+        div()
+            .flex()
+            .flex_col()
+            .rounded_md()
+            .bg(bg)
+            .text_xs()
+            .child(
+                div()
+                    .id("hook-run-header")
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .px_2()
+                    .py_1()
+                    .when(!run.output.is_empty(), |this| {
+                        this.cursor_pointer().on_click(cx.listener(|this, _event, _window, cx| {
+                            this.hook_output_open = !this.hook_output_open;
+                            cx.notify();
+                        }))
+                    })
+                    .child(
+                        div()
+                            .w_3()
+                            .text_color(theme.overlay0)
+                            .child(match (run.output.is_empty(), open) {
+                                (true, _) => "",
+                                (false, true) => "▾",
+                                (false, false) => "▸",
+                            }),
+                    )
+                    .child(div().flex_1().text_color(color).child(status))
+                    .when(!run.running, |this| {
+                        this.child(
+                            div()
+                                .id("dismiss-hook-run")
+                                .px_1()
+                                .rounded_sm()
+                                .text_color(theme.overlay0)
+                                .cursor_pointer()
+                                .hover(|s| s.text_color(theme.text))
+                                .child("×")
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.git_state
+                                        .update(cx, |state, cx| state.clear_hook_run(cx));
+                                })),
+                        )
+                    }),
+            )
+            .when(open, |this| {
+                this.child(
+                    div()
+                        .id("hook-output")
+                        .max_h(px(200.0))
+                        .overflow_y_scroll()
+                        .px_2()
+                        .pb_1()
+                        .font_family("monospace")
+                        .text_color(theme.text)
+                        .when(run.dropped > 0, |this| {
+                            this.child(
+                                div()
+                                    .text_color(theme.overlay0)
+                                    .child(format!("… {} earlier lines", run.dropped)),
+                            )
+                        })
+                        .children(run.output.iter().map(|line| div().child(line.clone()))),
+                )
+            })
     }
 }
 
 impl Render for CommitForm {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let locale = cx.locale();
         let git_state = self.git_state.read(cx);
         let staged_count = git_state.staged_files().len();
-        let can_commit = staged_count > 0;
+        let selected_count = git_state.selected_files.len();
         let amend = self.amend;
+        // A selection commits just those files instead of the index
+        let commit_selected = selected_count > 0 && !amend;
+        // Amending can change just the message, so it needs no staged files
+        let can_commit = staged_count > 0 || commit_selected || amend;
+        let amend_pushed = amend && self.amend_pushed;
+        let lint_warnings = git_state.lint_message(self.commit_message.read(cx).content());
+        let lint_blocks =
+            git_state.commit_lint == CommitLintMode::Block && !lint_warnings.is_empty();
+        let can_commit = can_commit && !lint_blocks;
+        let can_commit = can_commit && !self.awaiting_hooks;
+        let hook_run = git_state.hook_run.clone();
+        let button_label = if amend {
+            "Amend Last Commit".to_string()
+        } else if commit_selected {
+            t_plural(locale, "commit.buttonSelectedFiles", selected_count as i64)
+        } else {
+            t_plural(locale, "commit.buttonFiles", staged_count as i64)
+        };
 
         div()
             .flex()
@@ -111,10 +296,22 @@ impl Render for CommitForm {
                     .child(
                         div()
                             .text_xs()
-                            .text_color(rgb(0x9399b2))
+                            .text_color(theme.overlay2)
                             .child("Commit message"),
                     )
-                    .child(self.commit_message.clone()),
+                    .child(self.commit_message.clone())
+                    .children(lint_warnings.into_iter().map(|warning| {
+                        div()
+                            .flex()
+                            .gap_1()
+                            .text_xs()
+                            .text_color(if lint_blocks { theme.red } else { theme.yellow })
+                            .child("⚠")
+                            .child(warning.message)
+                    }))
+                    .when_some(hook_run, |this, run| {
+                        this.child(self.render_hook_run(&run, cx))
+                    }),
             )
             // Options row
             .child(
@@ -138,14 +335,14 @@ impl Render for CommitForm {
                                     .rounded_sm()
                                     .border_1()
                                     .border_color(if amend {
-                                        rgb(0x89b4fa)
+                                        theme.blue
                                     } else {
-                                        rgb(0x6c7086)
+                                        theme.overlay0
                                     })
                                     .bg(if amend {
-                                        rgb(0x89b4fa)
+                                        theme.blue
                                     } else {
-                                        rgb(0x313244)
+                                        theme.surface0
                                     })
                                     .flex()
                                     .items_center()
@@ -154,7 +351,7 @@ impl Render for CommitForm {
                                         this.child(
                                             div()
                                                 .text_xs()
-                                                .text_color(rgb(0x1e1e2e))
+                                                .text_color(theme.base)
                                                 .child("✓"),
                                         )
                                     }),
@@ -162,11 +359,41 @@ impl Render for CommitForm {
                             .child(
                                 div()
                                     .text_xs()
-                                    .text_color(rgb(0x9399b2))
-                                    .child("Amend"),
+                                    .text_color(theme.overlay2)
+                                    .child("Amend last commit"),
                             ),
+                    )
+                    .child(
+                        div()
+                            .id("undo-commit-button")
+                            .ml_auto()
+                            .px_2()
+                            .rounded_sm()
+                            .text_xs()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("Undo commit")
+                            .on_click(cx.listener(|_this, _event, window, cx| {
+                                window.dispatch_action(Box::new(UndoCommit), cx);
+                            })),
                     ),
             )
+            .when(amend_pushed, |this| {
+                this.child(
+                    div()
+                        .px_2()
+                        .py_1()
+                        .rounded_md()
+                        .bg(theme.yellow_bg)
+                        .text_xs()
+                        .text_color(theme.yellow)
+                        .child(
+                            "This commit has already been pushed. Amending it rewrites \
+                             history and will need a force push.",
+                        ),
+                )
+            })
             // Commit button
             .child(
                 div()
@@ -178,30 +405,26 @@ impl Render for CommitForm {
                     .py_2()
                     .rounded_md()
                     .bg(if can_commit {
-                        rgb(0xa6e3a1)
+                        theme.green
                     } else {
-                        rgb(0x45475a)
+                        theme.surface1
                     })
                     .text_sm()
                     .font_weight(FontWeight::SEMIBOLD)
                     .text_color(if can_commit {
-                        rgb(0x1e1e2e)
+                        theme.base
                     } else {
-                        rgb(0x6c7086)
+                        theme.overlay0
                     })
                     .when(can_commit, |this| {
                         this.cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x94e2d5)))
-                            .active(|s| s.bg(rgb(0x89b4fa)))
+                            .hover(|s| s.bg(theme.teal))
+                            .active(|s| s.bg(theme.blue))
                             .on_click(cx.listener(|this, _event, window, cx| {
                                 this.do_commit(window, cx);
                             }))
                     })
-                    .child(format!(
-                        "Commit ({} file{})",
-                        staged_count,
-                        if staged_count == 1 { "" } else { "s" }
-                    )),
+                    .child(button_label),
             )
     }
 }
@@ -1,34 +1,81 @@
 #![allow(dead_code)]
 
-use crate::components::TextInputView;
-use crate::git::ResetMode;
-use crate::state::GitState;
+use crate::actions::{CompareRevisions, ExportSnapshot, ShowConflictDialog};
+use crate::components::{
+    AuthorAvatar, AuthorLink, SignatureBadge, TextInputChanged, TextInputView,
+};
+use crate::git::{
+    BranchInfo, BranchKind, CommitSignature, InvalidBranchName, PreviewOperation, ResetMode,
+};
+use crate::i18n::{time_ago, ActiveLocale, Locale};
+use crate::state::{AvatarState, GitState, SignatureCheck};
+use crate::theme::{ActiveTheme, Theme};
 use gpui::prelude::*;
 use gpui::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 const NODE_RADIUS: f32 = 4.0;
 const COLUMN_WIDTH: f32 = 16.0;
-const ROW_HEIGHT: f32 = 32.0;
+pub const ROW_HEIGHT: f32 = 32.0;
 const GRAPH_PADDING: f32 = 8.0;
 
+/// What to do with a release notes draft when a tag is created
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReleaseNotesTarget {
+    None,
+    Clipboard,
+    File,
+}
+
+impl ReleaseNotesTarget {
+    const ALL: [Self; 3] = [Self::None, Self::Clipboard, Self::File];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Clipboard => "Copy",
+            Self::File => "Save",
+        }
+    }
+}
+
 /// What form is currently shown in the context menu
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum ContextMenuMode {
     Normal,
     CreateBranch,
     CreateTag,
+    /// Pick the remote branch `tracking_branch` follows
+    SetUpstream,
 }
 
 pub struct CommitGraph {
     git_state: Entity<GitState>,
+    avatars: Entity<AvatarState>,
     /// Context menu state
     context_menu: Option<ContextMenuState>,
     /// Input for branch name
     branch_name_input: Entity<TextInputView>,
+    /// Why the typed branch name is invalid, checked as it is typed
+    branch_name_error: Option<InvalidBranchName>,
+    /// Switch to a branch after creating it; off by default so creating a
+    /// branch never touches the working tree unasked
+    checkout_new_branch: bool,
     /// Input for tag name
     tag_name_input: Entity<TextInputView>,
     /// Input for tag message
     tag_message_input: Entity<TextInputView>,
+    /// Release notes draft for the tag being created
+    release_notes_target: ReleaseNotesTarget,
+    /// Graph revision last rendered
+    graph_revision: u64,
+    /// Branch or commit picked with "Select for Compare"
+    compare_base: Option<String>,
+    /// Commits cmd-clicked or shift-clicked for cherry-picking
+    picked: Vec<String>,
+    /// Commit a shift-click selects the range from
+    pick_anchor: Option<String>,
 }
 
 #[derive(Clone)]
@@ -36,16 +83,38 @@ struct ContextMenuState {
     sha: String,
     position: Point<Pixels>,
     is_merge_commit: bool,
+    /// Local branch at the commit, other than the current one
+    branch: Option<String>,
+    /// Local branch at the commit whose upstream can be changed, preferring
+    /// the current one
+    tracking_branch: Option<String>,
+    /// Remote-tracking branch at the commit that can be checked out as a
+    /// local branch
+    remote_branch: Option<String>,
     mode: ContextMenuMode,
 }
 
 impl CommitGraph {
-    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
-        // Observe git state changes
-        cx.observe(&git_state, |_this, _git_state, cx| {
-            cx.notify();
+    pub fn new(
+        git_state: Entity<GitState>,
+        avatars: Entity<AvatarState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        // Re-render only when the history changed, not on every git state notify
+        let graph_revision = git_state.read(cx).revisions().graph;
+        cx.observe(&git_state, |this, git_state, cx| {
+            let revision = git_state.read(cx).revisions().graph;
+            if this.graph_revision != revision {
+                this.graph_revision = revision;
+                cx.notify();
+            }
         })
         .detach();
+        // The graph is rendered as a cached view, so theme and language
+        // changes have to invalidate it explicitly
+        cx.observe_global::<Theme>(|_this, cx| cx.notify()).detach();
+        cx.observe_global::<Locale>(|_this, cx| cx.notify()).detach();
+        cx.observe(&avatars, |_this, _avatars, cx| cx.notify()).detach();
 
         // Create input views for forms
         let branch_name_input =
@@ -54,39 +123,54 @@ impl CommitGraph {
         let tag_message_input =
             cx.new(|cx| TextInputView::new(cx).with_placeholder("Message (optional)"));
 
+        cx.subscribe(&branch_name_input, |this, _input, event: &TextInputChanged, cx| {
+            // An empty field just disables Create; don't nag before typing
+            this.branch_name_error = if event.0.is_empty() {
+                None
+            } else if this.git_state.read(cx).has_local_branch(&event.0) {
+                Some(InvalidBranchName {
+                    reason: format!("A branch named '{}' already exists", event.0),
+                    suggestion: None,
+                })
+            } else {
+                BranchInfo::validate_name(&event.0).err()
+            };
+            cx.notify();
+        })
+        .detach();
+
         Self {
             git_state,
+            avatars,
             context_menu: None,
             branch_name_input,
+            branch_name_error: None,
+            checkout_new_branch: false,
             tag_name_input,
             tag_message_input,
+            release_notes_target: ReleaseNotesTarget::None,
+            graph_revision,
+            compare_base: None,
+            picked: Vec::new(),
+            pick_anchor: None,
         }
     }
 
-    fn show_context_menu(
-        &mut self,
-        sha: String,
-        position: Point<Pixels>,
-        is_merge_commit: bool,
-        cx: &mut Context<Self>,
-    ) {
+    fn show_context_menu(&mut self, menu: ContextMenuState, cx: &mut Context<Self>) {
         // Reset input fields when opening menu
         self.branch_name_input.update(cx, |input, cx| {
             input.set_content("", cx);
         });
+        self.branch_name_error = None;
         self.tag_name_input.update(cx, |input, cx| {
             input.set_content("", cx);
         });
         self.tag_message_input.update(cx, |input, cx| {
             input.set_content("", cx);
         });
+        self.release_notes_target = ReleaseNotesTarget::None;
 
-        self.context_menu = Some(ContextMenuState {
-            sha,
-            position,
-            is_merge_commit,
-            mode: ContextMenuMode::Normal,
-        });
+        self.context_menu = Some(menu);
         cx.notify();
     }
 
@@ -97,6 +181,20 @@ impl CommitGraph {
         }
     }
 
+    /// Replace the typed branch name with the suggested correction
+    fn use_suggested_branch_name(&mut self, cx: &mut Context<Self>) {
+        let Some(suggestion) = self
+            .branch_name_error
+            .as_ref()
+            .and_then(|e| e.suggestion.clone())
+        else {
+            return;
+        };
+        self.branch_name_input.update(cx, |input, cx| {
+            input.set_content(suggestion, cx);
+        });
+    }
+
     fn hide_context_menu(&mut self, cx: &mut Context<Self>) {
         self.context_menu = None;
         cx.notify();
@@ -111,20 +209,49 @@ impl CommitGraph {
         self.hide_context_menu(cx);
     }
 
+    fn checkout_remote_branch(&mut self, remote_branch: &str, cx: &mut Context<Self>) {
+        self.git_state.update(cx, |state, cx| {
+            if let Err(e) = state.checkout_remote_branch(remote_branch, cx) {
+                log::error!("Failed to check out {}: {}", remote_branch, e);
+            }
+        });
+        self.hide_context_menu(cx);
+    }
+
     fn create_branch_from(&mut self, sha: &str, _window: &mut Window, cx: &mut Context<Self>) {
         // Get branch name from input
         let branch_name = self.branch_name_input.read(cx).content().to_string();
         if branch_name.is_empty() {
             return;
         }
+        if let Err(e) = BranchInfo::validate_name(&branch_name) {
+            self.branch_name_error = Some(e);
+            cx.notify();
+            return;
+        }
 
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.checkout_commit(sha, cx) {
-                log::error!("Failed to checkout: {}", e);
-                return;
-            }
-            if let Err(e) = state.create_branch(&branch_name, cx) {
+        let checkout = self.checkout_new_branch;
+        let result = self.git_state.update(cx, |state, cx| {
+            state.create_branch_at(&branch_name, sha, checkout, cx)
+        });
+        match result {
+            Ok(()) => self.hide_context_menu(cx),
+            Err(e) => {
                 log::error!("Failed to create branch: {}", e);
+                // Keep the form open with the reason, e.g. a failed checkout
+                self.branch_name_error = Some(InvalidBranchName {
+                    reason: e.to_string(),
+                    suggestion: None,
+                });
+                cx.notify();
+            }
+        }
+    }
+
+    fn set_upstream(&mut self, branch: &str, upstream: Option<&str>, cx: &mut Context<Self>) {
+        self.git_state.update(cx, |state, cx| {
+            if let Err(e) = state.set_upstream(branch, upstream, cx) {
+                log::error!("Failed to set upstream of {}: {}", branch, e);
             }
         });
         self.hide_context_menu(cx);
@@ -144,20 +271,155 @@ impl CommitGraph {
             Some(tag_message.as_str())
         };
 
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.create_tag(&tag_name, sha, message, cx) {
-                log::error!("Failed to create tag: {}", e);
-            }
+        let created = self.git_state.update(cx, |state, cx| {
+            state
+                .create_tag(&tag_name, sha, message, cx)
+                .map_err(|e| log::error!("Failed to create tag: {}", e))
+                .is_ok()
         });
+        if created && self.release_notes_target != ReleaseNotesTarget::None {
+            self.export_release_notes(&tag_name, sha, cx);
+        }
+        self.hide_context_menu(cx);
+    }
+
+    /// Draft release notes for a new tag and copy or save them
+    fn export_release_notes(&mut self, tag_name: &str, sha: &str, cx: &mut Context<Self>) {
+        let notes = match self.git_state.read(cx).release_notes(tag_name, sha) {
+            Ok(notes) => notes,
+            Err(e) => {
+                log::error!("Failed to draft release notes: {}", e);
+                return;
+            }
+        };
+
+        match self.release_notes_target {
+            ReleaseNotesTarget::None => {}
+            ReleaseNotesTarget::Clipboard => {
+                cx.write_to_clipboard(ClipboardItem::new_string(notes));
+            }
+            ReleaseNotesTarget::File => {
+                let Some(dir) = self.git_state.read(cx).path.clone() else {
+                    return;
+                };
+                let file_name = format!("RELEASE_NOTES-{}.md", tag_name.replace('/', "-"));
+                let receiver = cx.prompt_for_new_path(&dir, Some(&file_name));
+                cx.spawn(async move |_this, _cx| {
+                    if let Ok(Ok(Some(path))) = receiver.await {
+                        if let Err(e) = std::fs::write(&path, notes) {
+                            log::error!("Failed to save release notes: {}", e);
+                        }
+                    }
+                })
+                .detach();
+            }
+        }
+    }
+
+    /// Cherry-pick the commit onto HEAD. Conflicts open the conflict dialog.
+    fn cherry_pick(&mut self, sha: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.cherry_pick(sha, cx));
+        match result {
+            Ok(true) => window.dispatch_action(Box::new(ShowConflictDialog), cx),
+            Ok(false) => {}
+            Err(e) => log::error!("Failed to cherry-pick: {}", e),
+        }
         self.hide_context_menu(cx);
     }
 
-    fn cherry_pick(&mut self, sha: &str, _window: &mut Window, cx: &mut Context<Self>) {
+    /// Add a commit to the commits picked for cherry-picking, or take it out
+    fn toggle_picked(&mut self, sha: String, cx: &mut Context<Self>) {
+        match self.picked.iter().position(|picked| *picked == sha) {
+            Some(index) => {
+                self.picked.remove(index);
+            }
+            None => self.picked.push(sha.clone()),
+        }
+        self.pick_anchor = Some(sha);
+        cx.notify();
+    }
+
+    /// Pick every commit between the anchor and `sha`. Merge commits need a
+    /// mainline and are left out.
+    fn pick_range(&mut self, sha: String, cx: &mut Context<Self>) {
+        let Some(commits) = self.git_state.read(cx).commits.clone() else {
+            return;
+        };
+        let position = |sha: &str| commits.nodes.iter().position(|node| node.commit.sha == sha);
+        let Some(end) = position(sha.as_str()) else {
+            return;
+        };
+        let start = self
+            .pick_anchor
+            .as_deref()
+            .and_then(position)
+            .unwrap_or(end);
+        let (from, to) = (start.min(end), start.max(end));
+        for node in &commits.nodes[from..=to] {
+            if node.commit.parents.len() <= 1 && !self.picked.contains(&node.commit.sha) {
+                self.picked.push(node.commit.sha.clone());
+            }
+        }
+        if self.pick_anchor.is_none() {
+            self.pick_anchor = Some(sha);
+        }
+        cx.notify();
+    }
+
+    fn clear_picked(&mut self, cx: &mut Context<Self>) {
+        self.picked.clear();
+        self.pick_anchor = None;
+        cx.notify();
+    }
+
+    /// Cherry-pick the picked commits oldest first, so they keep their
+    /// order in the history
+    fn cherry_pick_picked(&mut self, cx: &mut Context<Self>) {
+        let shas: Vec<String> = match &self.git_state.read(cx).commits {
+            Some(commits) => commits
+                .nodes
+                .iter()
+                .rev()
+                .filter(|node| self.picked.contains(&node.commit.sha))
+                .map(|node| node.commit.sha.clone())
+                .collect(),
+            None => Vec::new(),
+        };
         self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.cherry_pick(sha, cx) {
-                log::error!("Failed to cherry-pick: {}", e);
+            state.cherry_pick_sequence(shas, cx);
+        });
+        self.picked.clear();
+        self.pick_anchor = None;
+        self.hide_context_menu(cx);
+    }
+
+    /// Merge the commit into HEAD with the configured merge mode, or preview
+    /// it first when preview mode is on. Conflicts open the conflict dialog.
+    fn merge_into_head(&mut self, sha: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let result = self.git_state.update(cx, |state, cx| {
+            if state.preview_operations {
+                let operation = PreviewOperation::Merge {
+                    sha: sha.to_string(),
+                };
+                state.preview_operation(operation, cx).map(|_| false)
+            } else {
+                state.merge_commit(sha, cx)
             }
         });
+        match result {
+            Ok(true) => window.dispatch_action(Box::new(ShowConflictDialog), cx),
+            Ok(false) => {}
+            Err(e) => log::error!("Failed to merge: {}", e),
+        }
+        self.hide_context_menu(cx);
+    }
+
+    /// Open the merge dialog for a branch at the commit
+    fn request_merge_branch(&mut self, branch: &str, cx: &mut Context<Self>) {
+        self.git_state
+            .update(cx, |state, cx| state.request_merge_branch(branch, cx));
         self.hide_context_menu(cx);
     }
 
@@ -165,14 +427,17 @@ impl CommitGraph {
         &mut self,
         sha: &str,
         mainline: Option<u32>,
-        _window: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.revert_commit(sha, mainline, cx) {
-                log::error!("Failed to revert: {}", e);
-            }
-        });
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.revert_commit(sha, mainline, cx));
+        match result {
+            Ok(true) => window.dispatch_action(Box::new(ShowConflictDialog), cx),
+            Ok(false) => {}
+            Err(e) => log::error!("Failed to revert: {}", e),
+        }
         self.hide_context_menu(cx);
     }
 
@@ -184,19 +449,111 @@ impl CommitGraph {
         cx: &mut Context<Self>,
     ) {
         self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.reset_to_commit(sha, mode, cx) {
+            if let Err(e) = state.reset_or_preview(sha, mode, cx) {
                 log::error!("Failed to reset: {}", e);
             }
         });
         self.hide_context_menu(cx);
     }
+
+    fn open_commit_on_web(&mut self, sha: &str, cx: &mut Context<Self>) {
+        if let Some(url) = self.git_state.read(cx).commit_web_url(sha) {
+            cx.open_url(&url);
+        }
+        self.hide_context_menu(cx);
+    }
+
+    /// Open the branch's pull request, or the page creating one
+    fn open_pull_request(&mut self, branch: &str, cx: &mut Context<Self>) {
+        match self.git_state.read(cx).pull_request_url(branch) {
+            Ok(url) => cx.open_url(&url),
+            Err(e) => log::error!("Failed to open a pull request for {}: {}", branch, e),
+        }
+        self.hide_context_menu(cx);
+    }
+
+    fn select_for_compare(&mut self, revision: String, cx: &mut Context<Self>) {
+        self.compare_base = Some(revision);
+        self.hide_context_menu(cx);
+    }
+
+    /// Compare the revision picked earlier with `head`
+    fn compare_with_selected(
+        &mut self,
+        head: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(base) = self.compare_base.clone() {
+            window.dispatch_action(Box::new(CompareRevisions { base, head }), cx);
+        }
+        self.hide_context_menu(cx);
+    }
+
+    fn preview_operation(
+        &mut self,
+        operation: PreviewOperation,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let title = operation.title();
+        self.git_state.update(cx, |state, cx| {
+            if let Err(e) = state.preview_operation(operation, cx) {
+                log::error!("Failed to preview {}: {}", title, e);
+            }
+        });
+        self.hide_context_menu(cx);
+    }
 }
 
 impl Render for CommitGraph {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let git_state_read = self.git_state.read(cx);
-        let commits = git_state_read.commits.clone();
+        let theme = *cx.theme();
+        // Requested before the state is borrowed for reading below
+        let shas: Vec<String> = match &self.git_state.read(cx).commits {
+            Some(commits) => commits.nodes.iter().map(|node| node.commit.sha.clone()).collect(),
+            None => Vec::new(),
+        };
+        let signatures: Vec<Option<CommitSignature>> = self
+            .git_state
+            .update(cx, |state, cx| state.signature_checks(&shas, cx))
+            .into_iter()
+            .map(SignatureCheck::ready)
+            .collect();
+        // Copied out so the state isn't borrowed while avatars are requested
+        let (commits, current_branch, focused_commit, pull_requests) = {
+            let git_state_read = self.git_state.read(cx);
+            let pull_requests: HashMap<String, String> = match git_state_read.remote_web_url() {
+                Some(web_url) => git_state_read
+                    .pull_requests
+                    .iter()
+                    .map(|(branch, pull_request)| {
+                        let reference =
+                            web_url.provider.pull_request_reference(pull_request.number);
+                        (branch.clone(), reference)
+                    })
+                    .collect(),
+                None => HashMap::new(),
+            };
+            (
+                git_state_read.commits.clone(),
+                git_state_read.current_branch().map(|b| b.to_string()),
+                git_state_read.focused_commit.clone(),
+                pull_requests,
+            )
+        };
+        let has_commits = commits.is_some();
         let context_menu = self.context_menu.clone();
+        let avatars: Vec<Option<PathBuf>> = match &commits {
+            Some(commits) => self.avatars.update(cx, |avatars, cx| {
+                commits
+                    .nodes
+                    .iter()
+                    .map(|node| avatars.avatar(&node.commit.email, cx))
+                    .collect()
+            }),
+            None => Vec::new(),
+        };
 
         div()
             .flex()
@@ -217,22 +574,84 @@ impl Render for CommitGraph {
                 this.children(commits.nodes.iter().enumerate().map(|(idx, node)| {
                     let sha = node.commit.sha.clone();
                     let is_merge = node.commit.parents.len() > 1;
+                    let branch = node
+                        .commit
+                        .branches
+                        .iter()
+                        .find(|b| Some(*b) != current_branch.as_ref())
+                        .cloned();
+                    let tracking_branch = node
+                        .commit
+                        .branches
+                        .iter()
+                        .find(|b| Some(*b) == current_branch.as_ref())
+                        .or(node.commit.branches.first())
+                        .cloned();
+                    // Remote branches with a local branch here are already
+                    // checked out
+                    let remote_branch = node
+                        .commit
+                        .remotes
+                        .iter()
+                        .find(|remote| {
+                            !remote.ends_with("/HEAD")
+                                && !node.commit.branches.iter().any(|local| {
+                                    remote.split_once('/').map(|(_, name)| name)
+                                        == Some(local.as_str())
+                                })
+                        })
+                        .cloned();
+                    let picked_sha = sha.clone();
                     div()
-                        .child(CommitRow::new(node.clone(), idx, commits.max_column))
+                        .id(ElementId::Name(format!("graph-row-{}", sha).into()))
+                        .child(
+                            CommitRow::new(node.clone(), idx, commits.max_column)
+                                .focused(focused_commit.as_ref() == Some(&node.commit.sha))
+                                .picked(self.picked.contains(&node.commit.sha))
+                                .avatar(avatars[idx].clone())
+                                .signature(signatures[idx].clone())
+                                .pull_requests(
+                                    node.commit
+                                        .branches
+                                        .iter()
+                                        .filter_map(|branch| {
+                                            let reference = pull_requests.get(branch)?;
+                                            Some((branch.clone(), reference.clone()))
+                                        })
+                                        .collect(),
+                                ),
+                        )
+                        // Cmd-click picks commits for cherry-picking and
+                        // shift-click picks a range; merges can't be picked
+                        .on_click(cx.listener(move |this, event: &ClickEvent, _window, cx| {
+                            if event.modifiers().secondary() {
+                                if !is_merge {
+                                    this.toggle_picked(picked_sha.clone(), cx);
+                                }
+                            } else if event.modifiers().shift {
+                                this.pick_range(picked_sha.clone(), cx);
+                            } else if !this.picked.is_empty() {
+                                this.clear_picked(cx);
+                            }
+                        }))
                         .on_mouse_down(
                             MouseButton::Right,
                             cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
-                                this.show_context_menu(
-                                    sha.clone(),
-                                    event.position,
-                                    is_merge,
-                                    cx,
-                                );
+                                let menu = ContextMenuState {
+                                    sha: sha.clone(),
+                                    position: event.position,
+                                    is_merge_commit: is_merge,
+                                    branch: branch.clone(),
+                                    tracking_branch: tracking_branch.clone(),
+                                    remote_branch: remote_branch.clone(),
+                                    mode: ContextMenuMode::Normal,
+                                };
+                                this.show_context_menu(menu, cx);
                             }),
                         )
                 }))
             })
-            .when(git_state_read.commits.is_none(), |this| {
+            .when(!has_commits, |this| {
                 this.child(
                     div()
                         .flex()
@@ -240,7 +659,7 @@ impl Render for CommitGraph {
                         .justify_center()
                         .py_8()
                         .text_sm()
-                        .text_color(rgb(0x6c7086))
+                        .text_color(theme.overlay0)
                         .child("No commits"),
                 )
             })
@@ -257,17 +676,51 @@ impl CommitGraph {
         menu: ContextMenuState,
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
+        let theme = *cx.theme();
         let sha = menu.sha.clone();
         let sha_checkout = sha.clone();
         let sha_branch = sha.clone();
         let sha_tag = sha.clone();
         let sha_cherry = sha.clone();
         let sha_revert = sha.clone();
+        let sha_merge = sha.clone();
+        let sha_merge_preview = sha.clone();
+        let sha_rebase = sha.clone();
         let sha_reset_soft = sha.clone();
         let sha_reset_mixed = sha.clone();
         let sha_reset_hard = sha.clone();
+        let sha_web = sha.clone();
+        // Branches are compared by name so the comparison reads as one
+        let revision = menu.tracking_branch.clone().unwrap_or_else(|| sha.clone());
+        let compare_base = self
+            .compare_base
+            .clone()
+            .filter(|base| *base != revision && *base != sha);
+        // Snapshots are named after the branch at the commit, if any
+        let snapshot = ExportSnapshot {
+            revision: sha.clone(),
+            name: menu
+                .tracking_branch
+                .clone()
+                .unwrap_or_else(|| sha.chars().take(7).collect()),
+        };
         let is_merge = menu.is_merge_commit;
+        let branch = menu.branch.clone();
+        let tracking_branch = menu.tracking_branch.clone();
         let mode = menu.mode;
+        let git_state = self.git_state.read(cx);
+        let provider = git_state.remote_web_url().map(|web_url| web_url.provider);
+        // Pull requests are offered for the branch whose upstream can be set
+        let pull_request_branch = tracking_branch.clone();
+        let pull_request = pull_request_branch
+            .as_ref()
+            .and_then(|branch| git_state.pull_requests.get(branch))
+            .map(|pull_request| pull_request.number);
+        let picked_count = self.picked.len();
+        let sequence_running = git_state
+            .cherry_pick_progress
+            .as_ref()
+            .is_some_and(|progress| progress.is_running());
 
         let base = div()
             .absolute()
@@ -275,16 +728,118 @@ impl CommitGraph {
             .top(menu.position.y)
             .w(px(240.0))
             .rounded_lg()
-            .bg(rgb(0x181825))
+            .bg(theme.mantle)
             .border_1()
-            .border_color(rgb(0x313244))
+            .border_color(theme.surface0)
             .shadow_lg()
             .py_1()
             .flex()
             .flex_col();
 
         match mode {
+            ContextMenuMode::SetUpstream => {
+                let Some(local) = tracking_branch else {
+                    return base;
+                };
+                let git_state = self.git_state.read(cx);
+                let current_upstream = git_state
+                    .branches
+                    .iter()
+                    .find(|b| b.name == local && b.branch_type == BranchKind::Local)
+                    .and_then(|b| b.upstream.clone());
+                let remote_branches: Vec<String> = git_state
+                    .branches
+                    .iter()
+                    .filter(|b| b.branch_type == BranchKind::Remote && !b.name.ends_with("/HEAD"))
+                    .map(|b| b.name.clone())
+                    .collect();
+                let unset_branch = local.clone();
+
+                base.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .text_xs()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(theme.blue)
+                        .child(format!("Upstream of '{}'", local)),
+                )
+                .when(remote_branches.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .px_3()
+                            .py_1()
+                            .text_xs()
+                            .text_color(theme.overlay0)
+                            .child("No remote branches; fetch first"),
+                    )
+                })
+                .child(
+                    div()
+                        .id("ctx-upstream-list")
+                        .flex()
+                        .flex_col()
+                        .max_h(px(240.0))
+                        .overflow_y_scroll()
+                        .children(remote_branches.into_iter().map(|remote| {
+                            let is_current = current_upstream.as_ref() == Some(&remote);
+                            let local = local.clone();
+                            let upstream = remote.clone();
+                            div()
+                                .id(ElementId::Name(format!("ctx-upstream-{}", remote).into()))
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .px_3()
+                                .py_1()
+                                .text_sm()
+                                .text_color(if is_current { theme.blue } else { theme.text })
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface0))
+                                .child(div().w_3().child(if is_current { "✓" } else { "" }))
+                                .child(div().truncate().child(remote))
+                                .on_click(cx.listener(move |this, _event, _window, cx| {
+                                    this.set_upstream(&local, Some(&upstream), cx);
+                                }))
+                        })),
+                )
+                .child(div().h_px().bg(theme.surface0).my_1())
+                .when(current_upstream.is_some(), |this| {
+                    this.child(
+                        div()
+                            .id("ctx-upstream-unset")
+                            .px_3()
+                            .py_1()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .child("Stop Tracking")
+                            .on_click(cx.listener(move |this, _event, _window, cx| {
+                                this.set_upstream(&unset_branch, None, cx);
+                            })),
+                    )
+                })
+                .child(
+                    div()
+                        .id("ctx-upstream-cancel")
+                        .px_3()
+                        .py_1()
+                        .text_sm()
+                        .text_color(theme.overlay2)
+                        .cursor_pointer()
+                        .hover(|s| s.bg(theme.surface0))
+                        .child("Cancel")
+                        .on_click(cx.listener(|this, _event, _window, cx| {
+                            this.set_context_menu_mode(ContextMenuMode::Normal, cx);
+                        })),
+                )
+            }
             ContextMenuMode::CreateBranch => {
+                let branch_error = self.branch_name_error.clone();
+                let can_create = branch_error.is_none()
+                    && !self.branch_name_input.read(cx).content().is_empty();
+
                 // Branch creation form
                 base.child(
                     div()
@@ -292,7 +847,7 @@ impl CommitGraph {
                         .py_2()
                         .text_xs()
                         .font_weight(FontWeight::SEMIBOLD)
-                        .text_color(rgb(0x89b4fa))
+                        .text_color(theme.blue)
                         .child("Create Branch"),
                 )
                 .child(
@@ -301,6 +856,68 @@ impl CommitGraph {
                         .py_2()
                         .child(self.branch_name_input.clone()),
                 )
+                .when_some(branch_error, |this, error| {
+                    this.child(
+                        div()
+                            .px_3()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .text_xs()
+                            .child(div().text_color(theme.red).child(error.reason))
+                            .when_some(error.suggestion, |this, suggestion| {
+                                this.child(
+                                    div()
+                                        .id("ctx-branch-suggestion")
+                                        .text_color(theme.blue)
+                                        .cursor_pointer()
+                                        .hover(|s| s.underline())
+                                        .child(format!("Use '{}'", suggestion))
+                                        .on_click(cx.listener(|this, _event, _window, cx| {
+                                            this.use_suggested_branch_name(cx);
+                                        })),
+                                )
+                            }),
+                    )
+                })
+                .child(
+                    div()
+                        .id("ctx-branch-checkout")
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .px_3()
+                        .pt_1()
+                        .cursor_pointer()
+                        .child(
+                            div()
+                                .w_4()
+                                .h_4()
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .rounded_sm()
+                                .border_1()
+                                .border_color(theme.overlay0)
+                                .when(self.checkout_new_branch, |this| {
+                                    this.bg(theme.blue)
+                                        .border_color(theme.blue)
+                                        .text_xs()
+                                        .text_color(theme.base)
+                                        .child("✓")
+                                }),
+                        )
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(theme.text)
+                                .child("Check out after creating"),
+                        )
+                        .on_click(cx.listener(|this, _event, _window, cx| {
+                            this.checkout_new_branch = !this.checkout_new_branch;
+                            cx.notify();
+                        })),
+                )
                 .child(
                     div()
                         .flex()
@@ -314,16 +931,18 @@ impl CommitGraph {
                                 .px_3()
                                 .py_1()
                                 .rounded_md()
-                                .bg(rgb(0x89b4fa))
+                                .bg(if can_create { theme.blue } else { theme.surface1 })
                                 .text_sm()
-                                .text_color(rgb(0x1e1e2e))
+                                .text_color(if can_create { theme.base } else { theme.overlay0 })
                                 .text_center()
-                                .cursor_pointer()
-                                .hover(|s| s.bg(rgb(0xb4befe)))
                                 .child("Create")
-                                .on_click(cx.listener(move |this, _event, window, cx| {
-                                    this.create_branch_from(&sha_branch, window, cx);
-                                })),
+                                .when(can_create, |this| {
+                                    this.cursor_pointer()
+                                        .hover(|s| s.bg(theme.lavender))
+                                        .on_click(cx.listener(move |this, _event, window, cx| {
+                                            this.create_branch_from(&sha_branch, window, cx);
+                                        }))
+                                }),
                         )
                         .child(
                             div()
@@ -331,11 +950,11 @@ impl CommitGraph {
                                 .px_3()
                                 .py_1()
                                 .rounded_md()
-                                .bg(rgb(0x313244))
+                                .bg(theme.surface0)
                                 .text_sm()
-                                .text_color(rgb(0xcdd6f4))
+                                .text_color(theme.text)
                                 .cursor_pointer()
-                                .hover(|s| s.bg(rgb(0x45475a)))
+                                .hover(|s| s.bg(theme.surface1))
                                 .child("Cancel")
                                 .on_click(cx.listener(|this, _event, _window, cx| {
                                     this.set_context_menu_mode(ContextMenuMode::Normal, cx);
@@ -351,7 +970,7 @@ impl CommitGraph {
                         .py_2()
                         .text_xs()
                         .font_weight(FontWeight::SEMIBOLD)
-                        .text_color(rgb(0xf9e2af))
+                        .text_color(theme.yellow)
                         .child("Create Tag"),
                 )
                 .child(
@@ -366,6 +985,45 @@ impl CommitGraph {
                         .py_1()
                         .child(self.tag_message_input.clone()),
                 )
+                // Release notes draft
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_1()
+                        .px_3()
+                        .py_1()
+                        .child(
+                            div()
+                                .flex_1()
+                                .text_xs()
+                                .text_color(theme.overlay2)
+                                .child("Release notes"),
+                        )
+                        .children(ReleaseNotesTarget::ALL.into_iter().map(|target| {
+                            let is_selected = self.release_notes_target == target;
+                            div()
+                                .id(ElementId::Name(
+                                    format!("ctx-tag-notes-{}", target.label()).into(),
+                                ))
+                                .px_2()
+                                .py_px()
+                                .rounded_sm()
+                                .text_xs()
+                                .when(is_selected, |this| {
+                                    this.bg(theme.yellow).text_color(theme.base)
+                                })
+                                .when(!is_selected, |this| {
+                                    this.bg(theme.surface0).text_color(theme.text)
+                                })
+                                .cursor_pointer()
+                                .child(target.label())
+                                .on_click(cx.listener(move |this, _event, _window, cx| {
+                                    this.release_notes_target = target;
+                                    cx.notify();
+                                }))
+                        })),
+                )
                 .child(
                     div()
                         .flex()
@@ -379,12 +1037,12 @@ impl CommitGraph {
                                 .px_3()
                                 .py_1()
                                 .rounded_md()
-                                .bg(rgb(0xf9e2af))
+                                .bg(theme.yellow)
                                 .text_sm()
-                                .text_color(rgb(0x1e1e2e))
+                                .text_color(theme.base)
                                 .text_center()
                                 .cursor_pointer()
-                                .hover(|s| s.bg(rgb(0xfab387)))
+                                .hover(|s| s.bg(theme.peach))
                                 .child("Create")
                                 .on_click(cx.listener(move |this, _event, window, cx| {
                                     this.create_tag_at(&sha_tag, window, cx);
@@ -396,11 +1054,11 @@ impl CommitGraph {
                                 .px_3()
                                 .py_1()
                                 .rounded_md()
-                                .bg(rgb(0x313244))
+                                .bg(theme.surface0)
                                 .text_sm()
-                                .text_color(rgb(0xcdd6f4))
+                                .text_color(theme.text)
                                 .cursor_pointer()
-                                .hover(|s| s.bg(rgb(0x45475a)))
+                                .hover(|s| s.bg(theme.surface1))
                                 .child("Cancel")
                                 .on_click(cx.listener(|this, _event, _window, cx| {
                                     this.set_context_menu_mode(ContextMenuMode::Normal, cx);
@@ -418,14 +1076,31 @@ impl CommitGraph {
                             .px_3()
                             .py_2()
                             .text_sm()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x313244)))
+                            .hover(|s| s.bg(theme.surface0))
                             .child("Checkout")
                             .on_click(cx.listener(move |this, _event, window, cx| {
                                 this.checkout_commit(&sha_checkout, window, cx);
                             })),
                     )
+                    // Local branch tracking the remote branch at the commit
+                    .when_some(menu.remote_branch.clone(), |this, remote_branch| {
+                        this.child(
+                            div()
+                                .id("ctx-checkout-remote")
+                                .px_3()
+                                .py_2()
+                                .text_sm()
+                                .text_color(theme.text)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface0))
+                                .child(format!("Checkout '{}' as Local Branch", remote_branch))
+                                .on_click(cx.listener(move |this, _event, _window, cx| {
+                                    this.checkout_remote_branch(&remote_branch, cx);
+                                })),
+                        )
+                    })
                     // Create branch (opens form)
                     .child(
                         div()
@@ -433,9 +1108,9 @@ impl CommitGraph {
                             .px_3()
                             .py_2()
                             .text_sm()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x313244)))
+                            .hover(|s| s.bg(theme.surface0))
                             .child("Create Branch...")
                             .on_click(cx.listener(|this, _event, _window, cx| {
                                 this.set_context_menu_mode(ContextMenuMode::CreateBranch, cx);
@@ -448,16 +1123,130 @@ impl CommitGraph {
                             .px_3()
                             .py_2()
                             .text_sm()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x313244)))
+                            .hover(|s| s.bg(theme.surface0))
                             .child("Create Tag...")
                             .on_click(cx.listener(|this, _event, _window, cx| {
                                 this.set_context_menu_mode(ContextMenuMode::CreateTag, cx);
                             })),
                     )
+                    // Compare with the commit or branch selected before
+                    .child({
+                        let revision = revision.clone();
+                        div()
+                            .id("ctx-select-compare")
+                            .px_3()
+                            .py_2()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .child("Select for Compare")
+                            .on_click(cx.listener(move |this, _event, _window, cx| {
+                                this.select_for_compare(revision.clone(), cx);
+                            }))
+                    })
+                    .when_some(compare_base, |this, base| {
+                        this.child(
+                            div()
+                                .id("ctx-compare-selected")
+                                .px_3()
+                                .py_2()
+                                .text_sm()
+                                .text_color(theme.text)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface0))
+                                .child(format!("Compare with Selected ({})", short_revision(&base)))
+                                .on_click(cx.listener(move |this, _event, window, cx| {
+                                    this.compare_with_selected(revision.clone(), window, cx);
+                                })),
+                        )
+                    })
+                    // Set upstream (opens the remote branch list)
+                    .when_some(tracking_branch, |this, local| {
+                        this.child(
+                            div()
+                                .id("ctx-set-upstream")
+                                .px_3()
+                                .py_2()
+                                .text_sm()
+                                .text_color(theme.text)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface0))
+                                .child(format!("Set Upstream of '{}'...", local))
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.set_context_menu_mode(ContextMenuMode::SetUpstream, cx);
+                                })),
+                        )
+                    })
+                    // Hosting service links
+                    .when_some(provider, |this, provider| {
+                        let commit_label = match provider.name() {
+                            Some(name) => format!("Open Commit on {}", name),
+                            None => "Open Commit in Browser".to_string(),
+                        };
+                        this.child(
+                            div()
+                                .id("ctx-open-commit-web")
+                                .px_3()
+                                .py_2()
+                                .text_sm()
+                                .text_color(theme.text)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface0))
+                                .child(commit_label)
+                                .on_click(cx.listener(move |this, _event, _window, cx| {
+                                    this.open_commit_on_web(&sha_web, cx);
+                                })),
+                        )
+                        .when_some(pull_request_branch, |this, branch| {
+                            let label = match pull_request {
+                                Some(number) => format!(
+                                    "Open {} {}",
+                                    provider.pull_request_name(),
+                                    provider.pull_request_reference(number)
+                                ),
+                                None => format!(
+                                    "Create {} for '{}'",
+                                    provider.pull_request_name(),
+                                    branch
+                                ),
+                            };
+                            this.child(
+                                div()
+                                    .id("ctx-pull-request")
+                                    .px_3()
+                                    .py_2()
+                                    .text_sm()
+                                    .text_color(theme.text)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0))
+                                    .child(label)
+                                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                                        this.open_pull_request(&branch, cx);
+                                    })),
+                            )
+                        })
+                    })
+                    // Export the tree as an archive
+                    .child(
+                        div()
+                            .id("ctx-export-snapshot")
+                            .px_3()
+                            .py_2()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .child("Export Snapshot...")
+                            .on_click(cx.listener(move |this, _event, window, cx| {
+                                window.dispatch_action(Box::new(snapshot.clone()), cx);
+                                this.hide_context_menu(cx);
+                            })),
+                    )
                     // Separator
-                    .child(div().h_px().bg(rgb(0x313244)).my_1())
+                    .child(div().h_px().bg(theme.surface0).my_1())
                     // Cherry-pick
                     .child(
                         div()
@@ -465,14 +1254,52 @@ impl CommitGraph {
                             .px_3()
                             .py_2()
                             .text_sm()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x313244)))
+                            .hover(|s| s.bg(theme.surface0))
                             .child("Cherry-pick")
                             .on_click(cx.listener(move |this, _event, window, cx| {
                                 this.cherry_pick(&sha_cherry, window, cx);
                             })),
                     )
+                    // Commits picked with cmd-click or shift-click, oldest first
+                    .when(picked_count > 0 && !sequence_running, |this| {
+                        this.child(
+                            div()
+                                .id("ctx-cherry-pick-selected")
+                                .px_3()
+                                .py_2()
+                                .text_sm()
+                                .text_color(theme.text)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface0))
+                                .child(format!(
+                                    "Cherry-pick {} Selected Commit{}",
+                                    picked_count,
+                                    if picked_count == 1 { "" } else { "s" }
+                                ))
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.cherry_pick_picked(cx);
+                                })),
+                        )
+                    })
+                    .when(picked_count > 0, |this| {
+                        this.child(
+                            div()
+                                .id("ctx-clear-picked")
+                                .px_3()
+                                .py_2()
+                                .text_sm()
+                                .text_color(theme.overlay2)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface0))
+                                .child("Clear Selection")
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.clear_picked(cx);
+                                    this.hide_context_menu(cx);
+                                })),
+                        )
+                    })
                     // Revert
                     .child(
                         div()
@@ -480,9 +1307,9 @@ impl CommitGraph {
                             .px_3()
                             .py_2()
                             .text_sm()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x313244)))
+                            .hover(|s| s.bg(theme.surface0))
                             .child(if is_merge {
                                 "Revert (mainline 1)"
                             } else {
@@ -493,13 +1320,83 @@ impl CommitGraph {
                                 this.revert_commit(&sha_revert, mainline, window, cx);
                             })),
                     )
+                    // Merge
+                    .child(
+                        div()
+                            .id("ctx-merge")
+                            .px_3()
+                            .py_2()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .child(match &menu.branch {
+                                Some(branch) => format!("Merge '{}' into Current", branch),
+                                None => "Merge into Current".to_string(),
+                            })
+                            .on_click(cx.listener(move |this, _event, window, cx| {
+                                this.merge_into_head(&sha_merge, window, cx);
+                            })),
+                    )
+                    // Merge with a mode choice (branches only)
+                    .when_some(branch, |this, branch| {
+                        this.child(
+                            div()
+                                .id("ctx-merge-branch")
+                                .px_3()
+                                .py_2()
+                                .text_sm()
+                                .text_color(theme.text)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface0))
+                                .child("Merge into Current Branch...")
+                                .on_click(cx.listener(move |this, _event, _window, cx| {
+                                    this.request_merge_branch(&branch, cx);
+                                })),
+                        )
+                    })
+                    // Previews
+                    .child(
+                        div()
+                            .id("ctx-preview-merge")
+                            .px_3()
+                            .py_2()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .child("Preview Merge into HEAD")
+                            .on_click(cx.listener(move |this, _event, window, cx| {
+                                let operation = PreviewOperation::Merge {
+                                    sha: sha_merge_preview.clone(),
+                                };
+                                this.preview_operation(operation, window, cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("ctx-preview-rebase")
+                            .px_3()
+                            .py_2()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .child("Preview Rebase onto Here")
+                            .on_click(cx.listener(move |this, _event, window, cx| {
+                                let operation = PreviewOperation::Rebase {
+                                    onto: sha_rebase.clone(),
+                                };
+                                this.preview_operation(operation, window, cx);
+                            })),
+                    )
                     // Separator
-                    .child(div().h_px().bg(rgb(0x313244)).my_1())
+                    .child(div().h_px().bg(theme.surface0).my_1())
                     // Reset submenu
                     .child(
                         div()
                             .text_xs()
-                            .text_color(rgb(0x6c7086))
+                            .text_color(theme.overlay0)
                             .px_3()
                             .py_1()
                             .child("Reset to this commit:"),
@@ -510,9 +1407,9 @@ impl CommitGraph {
                             .px_3()
                             .py_2()
                             .text_sm()
-                            .text_color(rgb(0xa6e3a1))
+                            .text_color(theme.green)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x313244)))
+                            .hover(|s| s.bg(theme.surface0))
                             .child("Soft (keep changes staged)")
                             .on_click(cx.listener(move |this, _event, window, cx| {
                                 this.reset_to_commit(&sha_reset_soft, ResetMode::Soft, window, cx);
@@ -524,9 +1421,9 @@ impl CommitGraph {
                             .px_3()
                             .py_2()
                             .text_sm()
-                            .text_color(rgb(0xf9e2af))
+                            .text_color(theme.yellow)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x313244)))
+                            .hover(|s| s.bg(theme.surface0))
                             .child("Mixed (keep changes unstaged)")
                             .on_click(cx.listener(move |this, _event, window, cx| {
                                 this.reset_to_commit(&sha_reset_mixed, ResetMode::Mixed, window, cx);
@@ -538,9 +1435,9 @@ impl CommitGraph {
                             .px_3()
                             .py_2()
                             .text_sm()
-                            .text_color(rgb(0xf38ba8))
+                            .text_color(theme.red)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x313244)))
+                            .hover(|s| s.bg(theme.surface0))
                             .child("Hard (discard all changes)")
                             .on_click(cx.listener(move |this, _event, window, cx| {
                                 this.reset_to_commit(&sha_reset_hard, ResetMode::Hard, window, cx);
@@ -551,11 +1448,31 @@ impl CommitGraph {
     }
 }
 
+/// A branch name as is, or a SHA shortened to seven characters
+fn short_revision(revision: &str) -> &str {
+    let is_sha = revision.len() == 40 && revision.chars().all(|c| c.is_ascii_hexdigit());
+    if is_sha {
+        &revision[..7]
+    } else {
+        revision
+    }
+}
+
 #[derive(IntoElement)]
 pub struct CommitRow {
     node: crate::git::GraphNode,
     row_index: usize,
     max_column: usize,
+    /// Highlighted as the target of a jump to this commit
+    focused: bool,
+    /// Picked for cherry-picking
+    picked: bool,
+    /// Author avatar image, once loaded
+    avatar: Option<PathBuf>,
+    /// Signature check result, once verified
+    signature: Option<CommitSignature>,
+    /// Open pull request references ("#12") of the branches at the commit
+    pull_requests: Vec<(String, String)>,
 }
 
 impl CommitRow {
@@ -564,12 +1481,43 @@ impl CommitRow {
             node,
             row_index,
             max_column,
+            focused: false,
+            picked: false,
+            avatar: None,
+            signature: None,
+            pull_requests: Vec::new(),
         }
     }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    pub fn picked(mut self, picked: bool) -> Self {
+        self.picked = picked;
+        self
+    }
+
+    pub fn avatar(mut self, avatar: Option<PathBuf>) -> Self {
+        self.avatar = avatar;
+        self
+    }
+
+    pub fn signature(mut self, signature: Option<CommitSignature>) -> Self {
+        self.signature = signature;
+        self
+    }
+
+    pub fn pull_requests(mut self, pull_requests: Vec<(String, String)>) -> Self {
+        self.pull_requests = pull_requests;
+        self
+    }
 }
 
 impl RenderOnce for CommitRow {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         let commit = &self.node.commit;
         let graph_width =
             ((self.max_column + 1) as f32 * COLUMN_WIDTH + GRAPH_PADDING * 2.0) as i32;
@@ -580,8 +1528,9 @@ impl RenderOnce for CommitRow {
             .items_center()
             .h(px(ROW_HEIGHT))
             .px_2()
+            .when(self.focused || self.picked, |this| this.bg(theme.blue_bg))
             .cursor_pointer()
-            .hover(|s| s.bg(rgb(0x313244)))
+            .hover(|s| s.bg(theme.surface0))
             // Graph column
             .child(
                 div()
@@ -609,29 +1558,42 @@ impl RenderOnce for CommitRow {
                             .overflow_hidden()
                             // Branch labels
                             .children(commit.branches.iter().take(2).map(|branch| {
+                                let pull_request = self
+                                    .pull_requests
+                                    .iter()
+                                    .find(|(b, _)| b == branch)
+                                    .map(|(_, reference)| reference.clone());
                                 div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_1()
                                     .px_1()
                                     .rounded_sm()
-                                    .bg(rgb(0x89b4fa))
+                                    .bg(theme.blue)
                                     .text_xs()
-                                    .text_color(rgb(0x1e1e2e))
+                                    .text_color(theme.base)
                                     .child(branch.clone())
+                                    .when_some(pull_request, |this, reference| {
+                                        this.child(
+                                            div().font_weight(FontWeight::BOLD).child(reference),
+                                        )
+                                    })
                             }))
                             // Tag labels
                             .children(commit.tags.iter().take(1).map(|tag| {
                                 div()
                                     .px_1()
                                     .rounded_sm()
-                                    .bg(rgb(0xf9e2af))
+                                    .bg(theme.yellow)
                                     .text_xs()
-                                    .text_color(rgb(0x1e1e2e))
+                                    .text_color(theme.base)
                                     .child(tag.clone())
                             }))
                             // Commit message
                             .child(
                                 div()
                                     .text_sm()
-                                    .text_color(rgb(0xcdd6f4))
+                                    .text_color(theme.text)
                                     .text_ellipsis()
                                     .child(commit.message.clone()),
                             ),
@@ -643,17 +1605,27 @@ impl RenderOnce for CommitRow {
                             .items_center()
                             .gap_2()
                             .text_xs()
-                            .text_color(rgb(0x6c7086))
-                            .child(commit.author.clone())
+                            .text_color(theme.overlay0)
+                            .child(AuthorAvatar::new(&commit.author, self.avatar.clone()))
+                            .child(AuthorLink::new(
+                                ElementId::Name(format!("author-{}", commit.sha).into()),
+                                commit,
+                            ))
                             .child("·")
-                            .child(commit.relative_time()),
+                            .child(time_ago(cx.locale(), commit.timestamp))
+                            .when_some(self.signature, |this, signature| {
+                                this.child(SignatureBadge::new(
+                                    ElementId::Name(format!("signature-{}", commit.sha).into()),
+                                    signature,
+                                ))
+                            }),
                     ),
             )
             // SHA
             .child(
                 div()
                     .text_xs()
-                    .text_color(rgb(0x6c7086))
+                    .text_color(theme.overlay0)
                     .child(commit.short_sha.clone()),
             )
     }
@@ -662,17 +1634,18 @@ impl RenderOnce for CommitRow {
 #[derive(IntoElement)]
 pub struct GraphNode {
     column: usize,
-    color: u32,
+    color: usize,
 }
 
 impl GraphNode {
-    pub fn new(column: usize, color: u32) -> Self {
+    pub fn new(column: usize, color: usize) -> Self {
         Self { column, color }
     }
 }
 
 impl RenderOnce for GraphNode {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         let x = GRAPH_PADDING + (self.column as f32 * COLUMN_WIDTH) + COLUMN_WIDTH / 2.0;
 
         div()
@@ -680,6 +1653,6 @@ impl RenderOnce for GraphNode {
             .left(px(x - NODE_RADIUS))
             .size(px(NODE_RADIUS * 2.0))
             .rounded_full()
-            .bg(rgb(self.color))
+            .bg(theme.graph_color(self.color))
     }
 }
@@ -0,0 +1,485 @@
+#![allow(dead_code)]
+
+use crate::actions::ShowDiff;
+use crate::components::CopyDetailsButton;
+use crate::git::{CommitInfo, CompareMode, Comparison, FileDiff};
+use crate::state::{ErrorDetails, GitState};
+use crate::theme::{ActiveTheme, Theme};
+use crate::views::syntax::highlight_diff;
+use crate::views::DiffLine;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Most commits listed for each side of a comparison
+const COMPARE_COMMIT_LIMIT: usize = 500;
+
+/// Emitted when the comparison should be closed
+#[derive(Clone, Debug)]
+pub struct CompareDismissed;
+
+impl EventEmitter<CompareDismissed> for CompareView {}
+
+/// Commits and combined diff between two commits or branches
+pub struct CompareView {
+    git_state: Entity<GitState>,
+    base: String,
+    head: String,
+    mode: CompareMode,
+    comparison: Option<Comparison>,
+    /// Index into the comparison's files of the diff shown
+    selected_file: Option<usize>,
+    error: Option<ErrorDetails>,
+}
+
+impl CompareView {
+    pub fn new(
+        git_state: Entity<GitState>,
+        base: String,
+        head: String,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut view = Self {
+            git_state,
+            base,
+            head,
+            mode: CompareMode::default(),
+            comparison: None,
+            selected_file: None,
+            error: None,
+        };
+        view.load(cx);
+        view
+    }
+
+    fn load(&mut self, cx: &mut Context<Self>) {
+        self.comparison = None;
+        self.selected_file = None;
+        self.error = None;
+        let task = self.git_state.read(cx).compare(
+            &self.base,
+            &self.head,
+            self.mode,
+            COMPARE_COMMIT_LIMIT,
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(comparison) => {
+                        this.selected_file = (!comparison.files.is_empty()).then_some(0);
+                        this.comparison = Some(comparison);
+                    }
+                    Err(e) => {
+                        log::error!("Failed to compare {} and {}: {}", this.base, this.head, e);
+                        this.error = Some(this.git_state.read(cx).error_details("Compare", &e));
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+        cx.notify();
+    }
+
+    fn set_mode(&mut self, mode: CompareMode, cx: &mut Context<Self>) {
+        if self.mode != mode {
+            self.mode = mode;
+            self.load(cx);
+        }
+    }
+
+    fn swap(&mut self, cx: &mut Context<Self>) {
+        std::mem::swap(&mut self.base, &mut self.head);
+        self.load(cx);
+    }
+
+    fn select_file(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_file = Some(ix);
+        cx.notify();
+    }
+
+    /// Scroll the graph to the commit and close the comparison
+    fn show_commit(&mut self, sha: &str, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.focus_commit(sha, cx));
+        match result {
+            Ok(()) => cx.emit(CompareDismissed),
+            Err(e) => {
+                log::error!("Failed to show commit: {}", e);
+                self.error = Some(self.git_state.read(cx).error_details("Show commit", &e));
+                cx.notify();
+            }
+        }
+    }
+
+    /// Open the changed files in the diff viewer, which steps through their
+    /// hunks and files
+    fn open_in_diff_viewer(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(comparison) = &self.comparison else {
+            return;
+        };
+        let index = self.selected_file.unwrap_or(0);
+        self.git_state
+            .update(cx, |state, cx| state.show_comparison_diff(comparison, index, cx));
+        cx.emit(CompareDismissed);
+        window.dispatch_action(Box::new(ShowDiff), cx);
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(CompareDismissed);
+    }
+}
+
+impl Render for CompareView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let comparison = self.comparison.clone();
+        let loading = comparison.is_none() && self.error.is_none();
+        let selected_diff = comparison
+            .as_ref()
+            .zip(self.selected_file)
+            .and_then(|(comparison, ix)| comparison.files.get(ix).cloned());
+        let range = format!("{}{}{}", self.base, self.mode.separator(), self.head);
+        let has_files = comparison.as_ref().is_some_and(|c| !c.files.is_empty());
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .overflow_hidden()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Compare"),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_family("monospace")
+                                    .text_color(theme.overlay2)
+                                    .truncate()
+                                    .child(range),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .children(CompareMode::ALL.into_iter().map(|mode| {
+                                let active = self.mode == mode;
+                                div()
+                                    .id(ElementId::Name(
+                                        format!("compare-mode-{}", mode.separator()).into(),
+                                    ))
+                                    .px_2()
+                                    .py_px()
+                                    .rounded_sm()
+                                    .text_xs()
+                                    .font_family("monospace")
+                                    .cursor_pointer()
+                                    .when(active, |this| this.bg(theme.blue).text_color(theme.base))
+                                    .when(!active, |this| {
+                                        this.bg(theme.surface0)
+                                            .text_color(theme.overlay2)
+                                            .hover(|s| s.bg(theme.surface1))
+                                    })
+                                    .child(mode.separator())
+                                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                                        this.set_mode(mode, cx);
+                                    }))
+                            }))
+                            .when(has_files, |this| {
+                                this.child(
+                                    div()
+                                        .id("compare-open-diff")
+                                        .px_2()
+                                        .py_px()
+                                        .rounded_sm()
+                                        .text_xs()
+                                        .text_color(theme.overlay2)
+                                        .cursor_pointer()
+                                        .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                        .child("Open in Diff Viewer")
+                                        .on_click(cx.listener(|this, _event, window, cx| {
+                                            this.open_in_diff_viewer(window, cx);
+                                        })),
+                                )
+                            })
+                            .child(
+                                div()
+                                    .id("compare-swap")
+                                    .px_2()
+                                    .py_px()
+                                    .rounded_sm()
+                                    .text_xs()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("Swap")
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.swap(cx);
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .id("close-compare")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("×")
+                                    .on_click(cx.listener(|this, _event, window, cx| {
+                                        this.dismiss(window, cx);
+                                    })),
+                            ),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error.message)
+                        .child(CopyDetailsButton::new("compare-error-details", error.report)),
+                )
+            })
+            .when(loading, |this| {
+                this.child(
+                    div()
+                        .py_8()
+                        .text_sm()
+                        .text_center()
+                        .text_color(theme.overlay0)
+                        .child("Comparing…"),
+                )
+            })
+            .when_some(comparison, |this, comparison| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_1()
+                        .gap_3()
+                        .overflow_hidden()
+                        .child(self.render_sidebar(&comparison, &theme, cx))
+                        .child(self.render_diff(selected_diff, &theme)),
+                )
+            })
+    }
+}
+
+impl CompareView {
+    /// Commits on each side, then the changed files
+    fn render_sidebar(
+        &self,
+        comparison: &Comparison,
+        theme: &Theme,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let section = |title: String| {
+            div()
+                .px_2()
+                .py_1()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(theme.overlay2)
+                .child(title)
+        };
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        let head_count = comparison.head_commits.len();
+        let base_count = comparison.base_commits.len();
+        let file_count = comparison.files.len();
+
+        div()
+            .id("compare-sidebar")
+            .flex_shrink_0()
+            .w(px(320.0))
+            .flex()
+            .flex_col()
+            .gap_1()
+            .overflow_y_scroll()
+            .child(section(format!(
+                "{} commit{} only on {}",
+                head_count,
+                plural(head_count),
+                comparison.head
+            )))
+            .children(
+                comparison
+                    .head_commits
+                    .iter()
+                    .map(|commit| self.render_commit(commit, theme, cx)),
+            )
+            .when(comparison.mode == CompareMode::ThreeDot, |this| {
+                this.child(section(format!(
+                    "{} commit{} only on {}",
+                    base_count,
+                    plural(base_count),
+                    comparison.base
+                )))
+                .children(
+                    comparison
+                        .base_commits
+                        .iter()
+                        .map(|commit| self.render_commit(commit, theme, cx)),
+                )
+                .when_some(comparison.merge_base.clone(), |this, merge_base| {
+                    this.child(
+                        div()
+                            .px_2()
+                            .text_xs()
+                            .text_color(theme.overlay0)
+                            .child(format!("Merge base {}", merge_base)),
+                    )
+                })
+            })
+            .child(section(format!(
+                "{} file{} changed, +{} −{}",
+                file_count,
+                plural(file_count),
+                comparison.additions(),
+                comparison.deletions()
+            )))
+            .children(comparison.files.iter().enumerate().map(|(ix, file)| {
+                let selected = self.selected_file == Some(ix);
+                div()
+                    .id(ElementId::NamedInteger("compare-file".into(), ix as u64))
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .px_2()
+                    .py_px()
+                    .rounded_sm()
+                    .text_xs()
+                    .cursor_pointer()
+                    .when(selected, |this| this.bg(theme.blue_bg))
+                    .hover(|s| s.bg(theme.surface0))
+                    .child(
+                        div()
+                            .flex_1()
+                            .truncate()
+                            .text_color(theme.text)
+                            .child(file.path.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_shrink_0()
+                            .text_color(theme.green)
+                            .child(format!("+{}", file.additions)),
+                    )
+                    .child(
+                        div()
+                            .flex_shrink_0()
+                            .text_color(theme.red)
+                            .child(format!("−{}", file.deletions)),
+                    )
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.select_file(ix, cx);
+                    }))
+            }))
+    }
+
+    fn render_commit(
+        &self,
+        commit: &CommitInfo,
+        theme: &Theme,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let sha = commit.sha.clone();
+        div()
+            .id(ElementId::Name(format!("compare-commit-{}", commit.sha).into()))
+            .flex()
+            .gap_2()
+            .px_2()
+            .py_px()
+            .rounded_sm()
+            .text_xs()
+            .cursor_pointer()
+            .hover(|s| s.bg(theme.surface0))
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .text_color(theme.peach)
+                    .child(commit.short_sha.clone()),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .truncate()
+                    .text_color(theme.text)
+                    .child(commit.message.clone()),
+            )
+            .on_click(cx.listener(move |this, _event, _window, cx| {
+                this.show_commit(&sha, cx);
+            }))
+    }
+
+    fn render_diff(&self, diff: Option<FileDiff>, theme: &Theme) -> impl IntoElement {
+        let notice = |text: &'static str| {
+            div()
+                .py_8()
+                .text_sm()
+                .text_center()
+                .text_color(theme.overlay0)
+                .child(text)
+        };
+        let highlights = diff
+            .as_ref()
+            .and_then(|diff| highlight_diff(diff, diff.lines.len(), theme.appearance));
+
+        div()
+            .id("compare-diff")
+            .flex_1()
+            .overflow_y_scroll()
+            .rounded_md()
+            .border_1()
+            .border_color(theme.surface0)
+            .when(diff.is_none(), |this| this.child(notice("No differences")))
+            .when_some(diff, |this, diff| {
+                this.when(diff.too_large, |this| {
+                    this.child(notice("File is too large to show"))
+                })
+                .when(diff.is_binary && !diff.too_large, |this| {
+                    this.child(notice("Binary file"))
+                })
+                .children(diff.lines.iter().enumerate().map(|(ix, line)| {
+                    let spans = highlights
+                        .as_ref()
+                        .and_then(|h| h.get(ix))
+                        .cloned()
+                        .unwrap_or_default();
+                    DiffLine::new(line.clone()).with_highlights(spans)
+                }))
+            })
+    }
+}
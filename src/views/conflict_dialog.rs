@@ -1,15 +1,24 @@
 #![allow(dead_code)]
 
-use crate::git::{ConflictInfo, ConflictStrategy, ConflictedFile};
-use crate::state::GitState;
+use crate::components::{TextInputChanged, TextInputView};
+use crate::git::{ConflictInfo, ConflictStrategy, ConflictedFile, PickKind};
+use crate::i18n::{t_plural, ActiveLocale};
+use crate::state::{DraftState, GitState};
+use crate::theme::{ActiveTheme, Theme};
+use crate::views::{ConflictEditor, ConflictEditorEvent};
 use gpui::prelude::*;
 use gpui::*;
 
 pub struct ConflictDialog {
     git_state: Entity<GitState>,
+    drafts: Entity<DraftState>,
     conflict_info: Option<ConflictInfo>,
     mode: ConflictResolutionMode,
     per_file_selections: Vec<(String, Option<ConflictStrategy>)>,
+    /// Hunk editor for one file, shown in place of the file list
+    editor: Option<Entity<ConflictEditor>>,
+    /// Merge commit message, shown once "Complete Merge" is clicked
+    merge_message: Option<Entity<TextInputView>>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -19,44 +28,93 @@ pub enum ConflictResolutionMode {
 }
 
 impl ConflictDialog {
-    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
-        let git_state_read = git_state.read(cx);
-        let conflict_info = git_state_read.conflict_info.clone();
+    pub fn new(
+        git_state: Entity<GitState>,
+        drafts: Entity<DraftState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let conflict_info = git_state.read(cx).conflict_info.clone();
+
+        // Observe git state changes, keeping choices for files still conflicted
+        cx.observe(&git_state, |this, git_state, cx| {
+            this.conflict_info = git_state.read(cx).conflict_info.clone();
+            this.sync_selections();
+            cx.notify();
+        })
+        .detach();
+
+        // Pick up choices recovered after a crash
+        cx.observe(&drafts, |this, _drafts, cx| {
+            this.apply_restored_selections(cx);
+        })
+        .detach();
 
-        let per_file_selections = conflict_info
+        let mut dialog = Self {
+            git_state,
+            drafts,
+            conflict_info,
+            mode: ConflictResolutionMode::Bulk,
+            per_file_selections: Vec::new(),
+            editor: None,
+            merge_message: None,
+        };
+        dialog.sync_selections();
+        dialog.apply_restored_selections(cx);
+        dialog
+    }
+
+    /// Rebuild the per-file list from the conflicted files
+    fn sync_selections(&mut self) {
+        let previous = std::mem::take(&mut self.per_file_selections);
+        self.per_file_selections = self
+            .conflict_info
             .as_ref()
             .map(|info| {
                 info.conflicted_files
                     .iter()
-                    .map(|f| (f.path.clone(), None))
+                    .map(|f| {
+                        let strategy = previous
+                            .iter()
+                            .find(|(path, _)| *path == f.path)
+                            .and_then(|(_, strategy)| *strategy);
+                        (f.path.clone(), strategy)
+                    })
                     .collect()
             })
             .unwrap_or_default();
+    }
 
-        // Observe git state changes
-        cx.observe(&git_state, |this, git_state, cx| {
-            let git_state_read = git_state.read(cx);
-            this.conflict_info = git_state_read.conflict_info.clone();
-            this.per_file_selections = this
-                .conflict_info
-                .as_ref()
-                .map(|info| {
-                    info.conflicted_files
-                        .iter()
-                        .map(|f| (f.path.clone(), None))
-                        .collect()
-                })
-                .unwrap_or_default();
+    fn apply_restored_selections(&mut self, cx: &mut Context<Self>) {
+        let restored = self
+            .drafts
+            .update(cx, |drafts, _cx| drafts.take_restored_conflict_selections());
+        if restored.is_empty() {
+            return;
+        }
+
+        for (path, strategy) in restored {
+            if let Some(selection) = self.per_file_selections.iter_mut().find(|(p, _)| *p == path) {
+                selection.1 = Some(strategy);
+            }
+        }
+        self.mode = ConflictResolutionMode::PerFile;
+        cx.notify();
+    }
+
+    fn open_editor(&mut self, path: String, cx: &mut Context<Self>) {
+        let git_state = self.git_state.clone();
+        let editor = cx.new(|cx| ConflictEditor::new(git_state, path, cx));
+        cx.subscribe(&editor, |this, _editor, event: &ConflictEditorEvent, cx| {
+            match event {
+                ConflictEditorEvent::Resolved | ConflictEditorEvent::Closed => {
+                    this.editor = None;
+                }
+            }
             cx.notify();
         })
         .detach();
-
-        Self {
-            git_state,
-            conflict_info,
-            mode: ConflictResolutionMode::Bulk,
-            per_file_selections,
-        }
+        self.editor = Some(editor);
+        cx.notify();
     }
 
     fn set_mode(&mut self, mode: ConflictResolutionMode, cx: &mut Context<Self>) {
@@ -65,11 +123,19 @@ impl ConflictDialog {
     }
 
     fn resolve_all(&mut self, strategy: ConflictStrategy, _window: &mut Window, cx: &mut Context<Self>) {
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.resolve_all_conflicts(strategy, cx) {
-                log::error!("Failed to resolve all conflicts: {}", e);
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.resolve_all_conflicts(strategy, cx));
+
+        match result {
+            // Leave the files the strategy can't handle for per-file resolution
+            Ok(skipped) if skipped > 0 => {
+                log::warn!("{} file(s) could not be resolved with {}", skipped, strategy.label());
+                self.set_mode(ConflictResolutionMode::PerFile, cx);
             }
-        });
+            Ok(_) => self.clear_drafts(cx),
+            Err(e) => log::error!("Failed to resolve all conflicts: {}", e),
+        }
     }
 
     fn set_file_strategy(
@@ -85,9 +151,16 @@ impl ConflictDialog {
         {
             selection.1 = Some(strategy);
         }
+        self.drafts
+            .update(cx, |drafts, _cx| drafts.set_conflict_selection(&path, strategy));
         cx.notify();
     }
 
+    fn clear_drafts(&mut self, cx: &mut Context<Self>) {
+        self.drafts
+            .update(cx, |drafts, _cx| drafts.clear_conflict_selections());
+    }
+
     fn resolve_per_file(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
         let resolutions: Vec<_> = self
             .per_file_selections
@@ -100,32 +173,91 @@ impl ConflictDialog {
             return;
         }
 
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.resolve_conflicts_per_file(resolutions, cx) {
-                log::error!("Failed to resolve conflicts: {}", e);
-            }
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.resolve_conflicts_per_file(resolutions, cx));
+        match result {
+            Ok(_) => self.clear_drafts(cx),
+            Err(e) => log::error!("Failed to resolve conflicts: {}", e),
+        }
+    }
+
+    /// Ask for the merge commit message, pre-filled with the conventional one
+    fn edit_merge_message(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let message = self
+            .conflict_info
+            .as_ref()
+            .and_then(|info| info.merge_message.clone())
+            .unwrap_or_else(|| "Merge commit".to_string());
+        let input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .multiline(true)
+                .with_placeholder("Merge commit message")
+                .with_content(message)
         });
+        // Re-render so "Commit Merge" tracks whether the message is empty
+        cx.subscribe(&input, |_this, _input, _event: &TextInputChanged, cx| {
+            cx.notify();
+        })
+        .detach();
+        let focus_handle = input.read(cx).focus_handle(cx);
+        window.focus(&focus_handle, cx);
+        self.merge_message = Some(input);
+        cx.notify();
+    }
+
+    fn cancel_merge_message(&mut self, cx: &mut Context<Self>) {
+        self.merge_message = None;
+        cx.notify();
     }
 
     fn complete_merge(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.complete_merge(None, cx) {
-                log::error!("Failed to complete merge: {}", e);
-            }
-        });
+        let Some(input) = &self.merge_message else {
+            return;
+        };
+        let message = input.read(cx).content().trim().to_string();
+        if message.is_empty() {
+            log::warn!("Merge commit message is empty");
+            return;
+        }
+
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.complete_merge(Some(&message), cx));
+        match result {
+            Ok(()) => self.cancel_merge_message(cx),
+            Err(e) => log::error!("Failed to complete merge: {}", e),
+        }
+    }
+
+    /// Finish a cherry-pick or revert whose conflicts are resolved
+    fn continue_pick(&mut self, cx: &mut Context<Self>) {
+        let result = self.git_state.update(cx, |state, cx| state.continue_pick(cx));
+        if let Err(e) = result {
+            log::error!("Failed to continue: {}", e);
+        }
     }
 
     fn abort_merge(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.abort_merge(cx) {
-                log::error!("Failed to abort merge: {}", e);
+        let is_pick = self.conflict_info.as_ref().is_some_and(|info| info.pick.is_some());
+        let result = self.git_state.update(cx, |state, cx| {
+            if is_pick {
+                state.abort_pick(cx)
+            } else {
+                state.abort_merge(cx)
             }
         });
+        match result {
+            Ok(()) => self.clear_drafts(cx),
+            Err(e) => log::error!("Failed to abort merge: {}", e),
+        }
     }
 }
 
 impl Render for ConflictDialog {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let locale = cx.locale();
         let Some(conflict_info) = &self.conflict_info else {
             return div()
                 .flex()
@@ -134,10 +266,13 @@ impl Render for ConflictDialog {
                 .size_full()
                 .child(
                     div()
-                        .text_color(rgb(0x9399b2))
+                        .text_color(theme.overlay2)
                         .child("No merge conflicts"),
                 );
         };
+        if let Some(editor) = self.editor.clone() {
+            return div().size_full().child(editor);
+        }
 
         let source = conflict_info
             .source_branch
@@ -148,12 +283,39 @@ impl Render for ConflictDialog {
             .clone()
             .unwrap_or_else(|| "target".to_string());
         let file_count = conflict_info.conflicted_files.len();
+        let is_stash_conflict = conflict_info.is_stash_conflict;
+        let (title, verb, preposition, abort_label) = match conflict_info.pick {
+            _ if is_stash_conflict => (
+                "Stash Conflicts".to_string(),
+                "Reapplying",
+                "onto",
+                "Abort (Keep Stash)".to_string(),
+            ),
+            Some(PickKind::CherryPick) => (
+                "Cherry-pick Conflicts".to_string(),
+                "Cherry-picking",
+                "onto",
+                "Abort Cherry-pick".to_string(),
+            ),
+            Some(PickKind::Revert) => (
+                "Revert Conflicts".to_string(),
+                "Reverting",
+                "on",
+                "Abort Revert".to_string(),
+            ),
+            None => (
+                "Merge Conflicts".to_string(),
+                "Merging",
+                "into",
+                "Abort Merge".to_string(),
+            ),
+        };
 
         div()
             .flex()
             .flex_col()
             .size_full()
-            .bg(rgb(0x1e1e2e))
+            .bg(theme.base)
             .p_4()
             .gap_4()
             // Header
@@ -171,19 +333,20 @@ impl Render for ConflictDialog {
                                 div()
                                     .text_lg()
                                     .font_weight(FontWeight::BOLD)
-                                    .text_color(rgb(0xf38ba8))
-                                    .child("Merge Conflicts"),
+                                    .text_color(theme.red)
+                                    .child(title),
                             )
                             .child(
                                 div()
                                     .text_sm()
-                                    .text_color(rgb(0x9399b2))
+                                    .text_color(theme.overlay2)
                                     .child(format!(
-                                        "Merging {} into {} - {} file{} conflicted",
+                                        "{} {} {} {} - {}",
+                                        verb,
                                         source,
+                                        preposition,
                                         target,
-                                        file_count,
-                                        if file_count == 1 { "" } else { "s" }
+                                        t_plural(locale, "conflict.count", file_count as i64)
                                     )),
                             ),
                     )
@@ -194,12 +357,13 @@ impl Render for ConflictDialog {
                             .px_3()
                             .py_1()
                             .rounded_md()
-                            .bg(rgb(0x313244))
+                            .bg(theme.surface0)
                             .text_sm()
-                            .text_color(rgb(0xf38ba8))
+                            .text_color(theme.red)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x45475a)))
-                            .child("Abort Merge")
+                            .hover(|s| s.bg(theme.surface1))
+                            // The autostash is kept, so aborting doesn't lose the changes
+                            .child(abort_label)
                             .on_click(cx.listener(|this, _event, window, cx| {
                                 this.abort_merge(window, cx);
                             })),
@@ -218,20 +382,20 @@ impl Render for ConflictDialog {
                             .py_1()
                             .rounded_md()
                             .bg(if self.mode == ConflictResolutionMode::Bulk {
-                                rgb(0x89b4fa)
+                                theme.blue
                             } else {
-                                rgb(0x313244)
+                                theme.surface0
                             })
                             .text_sm()
                             .text_color(if self.mode == ConflictResolutionMode::Bulk {
-                                rgb(0x1e1e2e)
+                                theme.base
                             } else {
-                                rgb(0xcdd6f4)
+                                theme.text
                             })
                             .cursor_pointer()
                             .hover(|s| {
                                 if self.mode != ConflictResolutionMode::Bulk {
-                                    s.bg(rgb(0x45475a))
+                                    s.bg(theme.surface1)
                                 } else {
                                     s
                                 }
@@ -248,20 +412,20 @@ impl Render for ConflictDialog {
                             .py_1()
                             .rounded_md()
                             .bg(if self.mode == ConflictResolutionMode::PerFile {
-                                rgb(0x89b4fa)
+                                theme.blue
                             } else {
-                                rgb(0x313244)
+                                theme.surface0
                             })
                             .text_sm()
                             .text_color(if self.mode == ConflictResolutionMode::PerFile {
-                                rgb(0x1e1e2e)
+                                theme.base
                             } else {
-                                rgb(0xcdd6f4)
+                                theme.text
                             })
                             .cursor_pointer()
                             .hover(|s| {
                                 if self.mode != ConflictResolutionMode::PerFile {
-                                    s.bg(rgb(0x45475a))
+                                    s.bg(theme.surface1)
                                 } else {
                                     s
                                 }
@@ -279,7 +443,7 @@ impl Render for ConflictDialog {
                     .flex_1()
                     .overflow_y_scroll()
                     .rounded_md()
-                    .bg(rgb(0x181825))
+                    .bg(theme.mantle)
                     .p_2()
                     .child(
                         div()
@@ -291,8 +455,11 @@ impl Render for ConflictDialog {
                             })),
                     ),
             )
-            // Actions
-            .child(self.render_actions(cx))
+            // Actions, or the merge message once completing
+            .child(match self.merge_message.clone() {
+                Some(input) => self.render_merge_message(input, cx),
+                None => self.render_actions(cx).into_any_element(),
+            })
     }
 }
 
@@ -302,6 +469,7 @@ impl ConflictDialog {
         file: ConflictedFile,
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
+        let theme = *cx.theme();
         let path = file.path.clone();
         let selected_strategy = self
             .per_file_selections
@@ -323,7 +491,7 @@ impl ConflictDialog {
             .px_3()
             .py_2()
             .rounded_md()
-            .hover(|s| s.bg(rgb(0x313244)))
+            .hover(|s| s.bg(theme.surface0))
             .child(
                 div()
                     .flex()
@@ -338,13 +506,13 @@ impl ConflictDialog {
                             .child(
                                 div()
                                     .text_sm()
-                                    .text_color(rgb(0xf38ba8))
+                                    .text_color(theme.red)
                                     .child("C"),
                             )
                             .child(
                                 div()
                                     .text_sm()
-                                    .text_color(rgb(0xcdd6f4))
+                                    .text_color(theme.text)
                                     .text_ellipsis()
                                     .child(filename),
                             ),
@@ -353,7 +521,7 @@ impl ConflictDialog {
                         this.child(
                             div()
                                 .text_xs()
-                                .text_color(rgb(0x6c7086))
+                                .text_color(theme.overlay0)
                                 .pl_5()
                                 .text_ellipsis()
                                 .child(dir),
@@ -363,7 +531,7 @@ impl ConflictDialog {
                         this.child(
                             div()
                                 .text_xs()
-                                .text_color(rgb(0xf9e2af))
+                                .text_color(theme.yellow)
                                 .pl_5()
                                 .child("Deleted by us"),
                         )
@@ -372,89 +540,87 @@ impl ConflictDialog {
                         this.child(
                             div()
                                 .text_xs()
-                                .text_color(rgb(0xf9e2af))
+                                .text_color(theme.yellow)
                                 .pl_5()
                                 .child("Deleted by them"),
                         )
                     }),
             )
+            // Hunk-by-hunk editing needs both sides of the file
+            .when(!file.is_deleted_by_us && !file.is_deleted_by_them, |this| {
+                let path = path.clone();
+                this.child(
+                    div()
+                        .id(ElementId::Name(format!("edit-hunks-{}", path).into()))
+                        .flex_shrink_0()
+                        .mr_2()
+                        .px_2()
+                        .py_1()
+                        .rounded_sm()
+                        .bg(theme.surface0)
+                        .text_xs()
+                        .text_color(theme.mauve)
+                        .cursor_pointer()
+                        .hover(|s| s.bg(theme.surface1))
+                        .child("Edit Hunks")
+                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                            this.open_editor(path.clone(), cx);
+                        })),
+                )
+            })
             .when(self.mode == ConflictResolutionMode::PerFile, |this| {
-                let path_ours = path.clone();
-                let path_theirs = path.clone();
                 this.child(
                     div()
                         .flex()
                         .items_center()
                         .gap_1()
-                        .child(
+                        .children(ConflictStrategy::ALL.into_iter().map(|strategy| {
+                            let path = path.clone();
+                            let is_selected = selected_strategy == Some(strategy);
+                            let supported = file.supports(strategy);
+
                             div()
-                                .id(ElementId::Name(format!("ours-{}", path_ours).into()))
+                                .id(ElementId::Name(
+                                    format!("{}-{}", strategy.label(), path).into(),
+                                ))
                                 .px_2()
                                 .py_1()
                                 .rounded_sm()
-                                .bg(if selected_strategy == Some(ConflictStrategy::Ours) {
-                                    rgb(0xa6e3a1)
+                                .bg(if is_selected {
+                                    strategy_color(strategy, &theme)
                                 } else {
-                                    rgb(0x313244)
+                                    theme.surface0
                                 })
                                 .text_xs()
-                                .text_color(if selected_strategy == Some(ConflictStrategy::Ours) {
-                                    rgb(0x1e1e2e)
+                                .text_color(if is_selected {
+                                    theme.base
+                                } else if supported {
+                                    theme.text
                                 } else {
-                                    rgb(0xcdd6f4)
+                                    theme.overlay0
                                 })
-                                .cursor_pointer()
-                                .hover(|s| s.bg(rgb(0x45475a)))
-                                .child("Ours")
-                                .on_click(cx.listener(move |this, _event, _window, cx| {
-                                    this.set_file_strategy(
-                                        path_ours.clone(),
-                                        ConflictStrategy::Ours,
-                                        cx,
-                                    );
-                                })),
-                        )
-                        .child(
-                            div()
-                                .id(ElementId::Name(format!("theirs-{}", path_theirs).into()))
-                                .px_2()
-                                .py_1()
-                                .rounded_sm()
-                                .bg(if selected_strategy == Some(ConflictStrategy::Theirs) {
-                                    rgb(0x89b4fa)
-                                } else {
-                                    rgb(0x313244)
+                                .child(strategy.label())
+                                .when(supported, |this| {
+                                    this.cursor_pointer()
+                                        .hover(|s| s.bg(theme.surface1))
+                                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                                            this.set_file_strategy(path.clone(), strategy, cx);
+                                        }))
                                 })
-                                .text_xs()
-                                .text_color(
-                                    if selected_strategy == Some(ConflictStrategy::Theirs) {
-                                        rgb(0x1e1e2e)
-                                    } else {
-                                        rgb(0xcdd6f4)
-                                    },
-                                )
-                                .cursor_pointer()
-                                .hover(|s| s.bg(rgb(0x45475a)))
-                                .child("Theirs")
-                                .on_click(cx.listener(move |this, _event, _window, cx| {
-                                    this.set_file_strategy(
-                                        path_theirs.clone(),
-                                        ConflictStrategy::Theirs,
-                                        cx,
-                                    );
-                                })),
-                        ),
+                        })),
                 )
             })
     }
 
     fn render_actions(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
         let git_state_read = self.git_state.read(cx);
         let has_remaining_conflicts = git_state_read
             .conflict_info
             .as_ref()
             .map(|info| !info.conflicted_files.is_empty())
             .unwrap_or(false);
+        let pick = git_state_read.interrupted_pick.as_ref().map(|pick| pick.kind);
 
         let all_selected = self.mode == ConflictResolutionMode::PerFile
             && self
@@ -468,7 +634,7 @@ impl ConflictDialog {
             .justify_between()
             .pt_2()
             .border_t_1()
-            .border_color(rgb(0x313244))
+            .border_color(theme.surface0)
             .when(self.mode == ConflictResolutionMode::Bulk, |this| {
                 this.child(
                     div()
@@ -481,12 +647,12 @@ impl ConflictDialog {
                                 .px_4()
                                 .py_2()
                                 .rounded_md()
-                                .bg(rgb(0xa6e3a1))
+                                .bg(theme.green)
                                 .text_sm()
                                 .font_weight(FontWeight::SEMIBOLD)
-                                .text_color(rgb(0x1e1e2e))
+                                .text_color(theme.base)
                                 .cursor_pointer()
-                                .hover(|s| s.bg(rgb(0x94e2d5)))
+                                .hover(|s| s.bg(theme.teal))
                                 .child("Accept Ours (All)")
                                 .on_click(cx.listener(|this, _event, window, cx| {
                                     this.resolve_all(ConflictStrategy::Ours, window, cx);
@@ -498,16 +664,39 @@ impl ConflictDialog {
                                 .px_4()
                                 .py_2()
                                 .rounded_md()
-                                .bg(rgb(0x89b4fa))
+                                .bg(theme.blue)
                                 .text_sm()
                                 .font_weight(FontWeight::SEMIBOLD)
-                                .text_color(rgb(0x1e1e2e))
+                                .text_color(theme.base)
                                 .cursor_pointer()
-                                .hover(|s| s.bg(rgb(0xb4befe)))
+                                .hover(|s| s.bg(theme.lavender))
                                 .child("Accept Theirs (All)")
                                 .on_click(cx.listener(|this, _event, window, cx| {
                                     this.resolve_all(ConflictStrategy::Theirs, window, cx);
                                 })),
+                        )
+                        // Union and Base skip files they can't apply to
+                        .children(
+                            [ConflictStrategy::Union, ConflictStrategy::Base].into_iter().map(
+                                |strategy| {
+                                    div()
+                                        .id(ElementId::Name(
+                                            format!("resolve-{}-btn", strategy.label()).into(),
+                                        ))
+                                        .px_3()
+                                        .py_2()
+                                        .rounded_md()
+                                        .bg(theme.surface0)
+                                        .text_sm()
+                                        .text_color(strategy_color(strategy, &theme))
+                                        .cursor_pointer()
+                                        .hover(|s| s.bg(theme.surface1))
+                                        .child(format!("{} (All)", strategy.label()))
+                                        .on_click(cx.listener(move |this, _event, window, cx| {
+                                            this.resolve_all(strategy, window, cx);
+                                        }))
+                                },
+                            ),
                         ),
                 )
             })
@@ -519,20 +708,20 @@ impl ConflictDialog {
                         .py_2()
                         .rounded_md()
                         .bg(if all_selected {
-                            rgb(0xa6e3a1)
+                            theme.green
                         } else {
-                            rgb(0x45475a)
+                            theme.surface1
                         })
                         .text_sm()
                         .font_weight(FontWeight::SEMIBOLD)
                         .text_color(if all_selected {
-                            rgb(0x1e1e2e)
+                            theme.base
                         } else {
-                            rgb(0x6c7086)
+                            theme.overlay0
                         })
                         .when(all_selected, |this| {
                             this.cursor_pointer()
-                                .hover(|s| s.bg(rgb(0x94e2d5)))
+                                .hover(|s| s.bg(theme.teal))
                                 .on_click(cx.listener(|this, _event, window, cx| {
                                     this.resolve_per_file(window, cx);
                                 }))
@@ -548,17 +737,109 @@ impl ConflictDialog {
                         .px_4()
                         .py_2()
                         .rounded_md()
-                        .bg(rgb(0xa6e3a1))
+                        .bg(theme.green)
                         .text_sm()
                         .font_weight(FontWeight::SEMIBOLD)
-                        .text_color(rgb(0x1e1e2e))
+                        .text_color(theme.base)
                         .cursor_pointer()
-                        .hover(|s| s.bg(rgb(0x94e2d5)))
-                        .child("Complete Merge")
-                        .on_click(cx.listener(|this, _event, window, cx| {
-                            this.complete_merge(window, cx);
+                        .hover(|s| s.bg(theme.teal))
+                        .child(match pick {
+                            Some(kind) => format!("Continue {}", kind.label()),
+                            None => "Complete Merge".to_string(),
+                        })
+                        .on_click(cx.listener(move |this, _event, window, cx| {
+                            // Picks keep the message of the commit they apply
+                            if pick.is_some() {
+                                this.continue_pick(cx);
+                            } else {
+                                this.edit_merge_message(window, cx);
+                            }
                         })),
                 )
             })
     }
+
+    fn render_merge_message(
+        &self,
+        input: Entity<TextInputView>,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let theme = *cx.theme();
+        let has_message = !input.read(cx).content().trim().is_empty();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .pt_2()
+            .border_t_1()
+            .border_color(theme.surface0)
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.overlay2)
+                    .child("Merge commit message"),
+            )
+            .child(input)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id("merge-message-back-btn")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("Back")
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.cancel_merge_message(cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("commit-merge-btn")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(if has_message {
+                                theme.green
+                            } else {
+                                theme.surface1
+                            })
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(if has_message {
+                                theme.base
+                            } else {
+                                theme.overlay0
+                            })
+                            .when(has_message, |this| {
+                                this.cursor_pointer()
+                                    .hover(|s| s.bg(theme.teal))
+                                    .on_click(cx.listener(|this, _event, window, cx| {
+                                        this.complete_merge(window, cx);
+                                    }))
+                            })
+                            .child("Commit Merge"),
+                    ),
+            )
+            .into_any_element()
+    }
+}
+
+fn strategy_color(strategy: ConflictStrategy, theme: &Theme) -> Rgba {
+    match strategy {
+        ConflictStrategy::Ours => theme.green,
+        ConflictStrategy::Theirs => theme.blue,
+        ConflictStrategy::Union => theme.mauve,
+        ConflictStrategy::Base => theme.yellow,
+    }
 }
@@ -0,0 +1,443 @@
+#![allow(dead_code)]
+
+use crate::components::{CopyDetailsButton, TextInputChanged, TextInputView};
+use crate::git::{ConflictDocument, ConflictHunk, ConflictSegment, HunkResolution};
+use crate::state::{ErrorDetails, GitState};
+use crate::theme::{ActiveTheme, Theme};
+use gpui::prelude::*;
+use gpui::*;
+
+/// Unchanged lines shown around each conflict; the rest are collapsed
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Clone, Debug)]
+pub enum ConflictEditorEvent {
+    /// The file was written and staged
+    Resolved,
+    Closed,
+}
+
+impl EventEmitter<ConflictEditorEvent> for ConflictEditor {}
+
+/// Three-way editor for one conflicted file: each conflict hunk is resolved
+/// with ours, theirs, both, or hand-edited text before the file is staged
+pub struct ConflictEditor {
+    git_state: Entity<GitState>,
+    path: String,
+    document: Option<ConflictDocument>,
+    resolutions: Vec<Option<HunkResolution>>,
+    /// Inline editors for hunks being edited by hand
+    editors: Vec<Option<Entity<TextInputView>>>,
+    error: Option<ErrorDetails>,
+}
+
+impl ConflictEditor {
+    pub fn new(git_state: Entity<GitState>, path: String, cx: &mut Context<Self>) -> Self {
+        let (document, error) = match git_state.read(cx).conflict_document(&path) {
+            Ok(document) => (Some(document), None),
+            Err(e) => {
+                log::error!("Failed to read conflicts in {}: {}", path, e);
+                let details = git_state.read(cx).error_details("Read conflicts", &e);
+                (None, Some(details))
+            }
+        };
+        let hunk_count = document.as_ref().map_or(0, |d| d.hunk_count());
+
+        Self {
+            git_state,
+            path,
+            document,
+            resolutions: vec![None; hunk_count],
+            editors: vec![None; hunk_count],
+            error,
+        }
+    }
+
+    fn set_resolution(&mut self, index: usize, resolution: HunkResolution, cx: &mut Context<Self>) {
+        self.resolutions[index] = Some(resolution);
+        self.editors[index] = None;
+        cx.notify();
+    }
+
+    /// Open an inline editor seeded with the hunk's current resolution,
+    /// or both sides when it has none yet
+    fn edit_hunk(&mut self, index: usize, hunk: &ConflictHunk, cx: &mut Context<Self>) {
+        let resolution = self.resolutions[index].clone().unwrap_or(HunkResolution::Both);
+        let text = hunk.resolve(&resolution);
+        let editor = cx.new(|cx| TextInputView::new(cx).multiline(true).with_content(text.clone()));
+        cx.subscribe(&editor, move |this, _editor, event: &TextInputChanged, cx| {
+            this.resolutions[index] = Some(HunkResolution::Custom(event.0.to_string()));
+            cx.notify();
+        })
+        .detach();
+
+        self.resolutions[index] = Some(HunkResolution::Custom(text));
+        self.editors[index] = Some(editor);
+        cx.notify();
+    }
+
+    fn is_complete(&self) -> bool {
+        self.document.is_some() && self.resolutions.iter().all(|r| r.is_some())
+    }
+
+    fn mark_resolved(&mut self, cx: &mut Context<Self>) {
+        let Some(document) = &self.document else {
+            return;
+        };
+        let result = document.resolve(&self.resolutions).and_then(|content| {
+            self.git_state.update(cx, |state, cx| {
+                state.resolve_conflict_with_content(&self.path, &content, cx)
+            })
+        });
+
+        match result {
+            Ok(()) => cx.emit(ConflictEditorEvent::Resolved),
+            Err(e) => {
+                log::error!("Failed to resolve {}: {}", self.path, e);
+                self.error = Some(self.git_state.read(cx).error_details("Resolve conflicts", &e));
+                cx.notify();
+            }
+        }
+    }
+
+    fn close(&mut self, cx: &mut Context<Self>) {
+        cx.emit(ConflictEditorEvent::Closed);
+    }
+}
+
+impl Render for ConflictEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let total = self.resolutions.len();
+        let resolved = self.resolutions.iter().filter(|r| r.is_some()).count();
+        let is_complete = self.is_complete();
+
+        let mut body = Vec::new();
+        if let Some(document) = &self.document {
+            let last = document.segments.len().saturating_sub(1);
+            let mut hunk_index = 0;
+            for (ix, segment) in document.segments.iter().enumerate() {
+                match segment {
+                    ConflictSegment::Common(lines) => body.push(
+                        render_common(lines, ix > 0, ix < last, &theme).into_any_element(),
+                    ),
+                    ConflictSegment::Conflict(hunk) => {
+                        let hunk = self.render_hunk(hunk_index, hunk, &theme, cx);
+                        body.push(hunk.into_any_element());
+                        hunk_index += 1;
+                    }
+                }
+            }
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child(format!("Resolve {}", self.path)),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .child(format!("{} of {} conflicts resolved", resolved, total)),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("conflict-editor-back")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("Back to Files")
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.close(cx);
+                            })),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error.message)
+                        .child(CopyDetailsButton::new("conflict-editor-error", error.report)),
+                )
+            })
+            // File with conflict hunks
+            .child(
+                div()
+                    .id("conflict-editor-scroll")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .children(body),
+            )
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .pt_2()
+                    .border_t_1()
+                    .border_color(theme.surface0)
+                    .child(
+                        div()
+                            .id("conflict-editor-resolve")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(if is_complete { theme.green } else { theme.surface1 })
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(if is_complete { theme.base } else { theme.overlay0 })
+                            .when(is_complete, |this| {
+                                this.cursor_pointer()
+                                    .hover(|s| s.bg(theme.teal))
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.mark_resolved(cx);
+                                    }))
+                            })
+                            .child("Mark Resolved"),
+                    ),
+            )
+    }
+}
+
+impl ConflictEditor {
+    fn render_hunk(
+        &self,
+        index: usize,
+        hunk: &ConflictHunk,
+        theme: &Theme,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let resolution = self.resolutions[index].clone();
+        let editor = self.editors[index].clone();
+
+        let choices = [HunkResolution::Ours, HunkResolution::Theirs, HunkResolution::Both]
+            .into_iter()
+            .map(|choice| {
+                let is_selected = resolution.as_ref() == Some(&choice);
+                let label = choice.label();
+                div()
+                    .id(ElementId::Name(format!("hunk-{}-{}", index, label).into()))
+                    .px_2()
+                    .py_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .cursor_pointer()
+                    .when(is_selected, |this| this.bg(theme.blue).text_color(theme.base))
+                    .when(!is_selected, |this| {
+                        this.bg(theme.surface0)
+                            .text_color(theme.text)
+                            .hover(|s| s.bg(theme.surface1))
+                    })
+                    .child(label)
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.set_resolution(index, choice.clone(), cx);
+                    }))
+            });
+        let is_custom = matches!(resolution, Some(HunkResolution::Custom(_)));
+        let hunk_edit = hunk.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .p_2()
+            .rounded_md()
+            .border_1()
+            .border_color(if resolution.is_some() { theme.surface1 } else { theme.red })
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.overlay2)
+                            .child(format!("Conflict {}", index + 1)),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_1()
+                            .children(choices)
+                            .child(
+                                div()
+                                    .id(ElementId::Name(format!("hunk-{}-edit", index).into()))
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_sm()
+                                    .text_xs()
+                                    .cursor_pointer()
+                                    .when(is_custom, |this| {
+                                        this.bg(theme.mauve).text_color(theme.base)
+                                    })
+                                    .when(!is_custom, |this| {
+                                        this.bg(theme.surface0)
+                                            .text_color(theme.text)
+                                            .hover(|s| s.bg(theme.surface1))
+                                    })
+                                    .child("Edit")
+                                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                                        this.edit_hunk(index, &hunk_edit, cx);
+                                    })),
+                            ),
+                    ),
+            )
+            // Sides
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(render_side(
+                        format!("Ours ({})", hunk.ours_label),
+                        &hunk.ours,
+                        theme.green,
+                        theme.green_bg,
+                        theme,
+                    ))
+                    .when_some(hunk.base.as_ref(), |this, base| {
+                        this.child(render_side(
+                            "Base".to_string(),
+                            base,
+                            theme.yellow,
+                            theme.yellow_bg,
+                            theme,
+                        ))
+                    })
+                    .child(render_side(
+                        format!("Theirs ({})", hunk.theirs_label),
+                        &hunk.theirs,
+                        theme.blue,
+                        theme.blue_bg,
+                        theme,
+                    )),
+            )
+            .when_some(editor, |this, editor| this.child(editor))
+    }
+}
+
+fn render_side(
+    title: String,
+    lines: &[String],
+    color: Rgba,
+    background: Rgba,
+    theme: &Theme,
+) -> impl IntoElement {
+    div()
+        .flex_1()
+        .min_w_0()
+        .flex()
+        .flex_col()
+        .rounded_sm()
+        .bg(background)
+        .child(
+            div()
+                .px_2()
+                .py_1()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(color)
+                .truncate()
+                .child(title),
+        )
+        .when(lines.is_empty(), |this| {
+            this.child(
+                div()
+                    .px_2()
+                    .pb_1()
+                    .text_xs()
+                    .italic()
+                    .text_color(theme.overlay0)
+                    .child("(no lines)"),
+            )
+        })
+        .children(lines.iter().map(|line| render_line(line, theme.text)))
+}
+
+/// Unchanged text between conflicts, collapsed to the lines next to them
+fn render_common(
+    lines: &[String],
+    after_conflict: bool,
+    before_conflict: bool,
+    theme: &Theme,
+) -> impl IntoElement {
+    let head = if after_conflict { CONTEXT_LINES } else { 0 };
+    let tail = if before_conflict { CONTEXT_LINES } else { 0 };
+    let (shown_head, hidden, shown_tail) = if head + tail >= lines.len() {
+        (lines, 0, &lines[lines.len()..])
+    } else {
+        (
+            &lines[..head],
+            lines.len() - head - tail,
+            &lines[lines.len() - tail..],
+        )
+    };
+
+    div()
+        .flex()
+        .flex_col()
+        .children(shown_head.iter().map(|line| render_line(line, theme.overlay2)))
+        .when(hidden > 0, |this| {
+            this.child(
+                div()
+                    .px_2()
+                    .text_xs()
+                    .text_color(theme.overlay0)
+                    .child(format!("⋯ {} unchanged lines", hidden)),
+            )
+        })
+        .children(shown_tail.iter().map(|line| render_line(line, theme.overlay2)))
+}
+
+fn render_line(line: &str, color: Rgba) -> impl IntoElement {
+    div()
+        .px_2()
+        .font_family("monospace")
+        .text_xs()
+        .text_color(color)
+        .whitespace_nowrap()
+        .child(line.trim_end_matches(['\n', '\r']).to_string())
+}
@@ -0,0 +1,176 @@
+#![allow(dead_code)]
+
+use crate::components::{TextInputChanged, TextInputView};
+use crate::git::{BranchInfo, InvalidBranchName};
+use crate::state::GitState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Shown above the panels while HEAD is detached, explaining that new
+/// commits won't be on a branch and offering to create one here or to go
+/// back to the branch checked out before
+pub struct DetachedHeadBanner {
+    git_state: Entity<GitState>,
+    branch_name_input: Entity<TextInputView>,
+    /// Why the typed branch name is invalid
+    name_error: Option<InvalidBranchName>,
+    /// Error from the last action
+    error: Option<String>,
+}
+
+impl DetachedHeadBanner {
+    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+        let branch_name_input =
+            cx.new(|cx| TextInputView::new(cx).with_placeholder("New branch name"));
+
+        cx.subscribe(&branch_name_input, |this, _input, event: &TextInputChanged, cx| {
+            this.name_error = if event.0.is_empty() {
+                None
+            } else {
+                BranchInfo::validate_name(&event.0).err()
+            };
+            this.error = None;
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            git_state,
+            branch_name_input,
+            name_error: None,
+            error: None,
+        }
+    }
+
+    fn create_branch(&mut self, cx: &mut Context<Self>) {
+        let name = self.branch_name_input.read(cx).content().trim().to_string();
+        if name.is_empty() || self.name_error.is_some() {
+            return;
+        }
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.create_branch_at_head(&name, cx));
+        self.finish(result, "create branch", cx);
+    }
+
+    fn return_to_previous_branch(&mut self, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.return_to_previous_branch(cx))
+            .map(|_| ());
+        self.finish(result, "check out previous branch", cx);
+    }
+
+    fn finish(&mut self, result: anyhow::Result<()>, action: &str, cx: &mut Context<Self>) {
+        match result {
+            Ok(()) => {
+                self.error = None;
+                self.branch_name_input.update(cx, |input, cx| {
+                    input.set_content("", cx);
+                });
+            }
+            Err(e) => {
+                log::error!("Failed to {}: {}", action, e);
+                self.error = Some(e.to_string());
+            }
+        }
+        cx.notify();
+    }
+}
+
+impl Render for DetachedHeadBanner {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let info = self.git_state.read(cx).repository_info.clone();
+        let short_sha = info
+            .as_ref()
+            .and_then(|info| info.head_sha.as_ref())
+            .map(|sha| sha.chars().take(7).collect::<String>())
+            .unwrap_or_default();
+        let previous_branch = info.and_then(|info| info.previous_branch);
+        let can_create = self.name_error.is_none()
+            && !self.branch_name_input.read(cx).content().trim().is_empty();
+
+        let button = |id: &'static str, label: String, enabled: bool| {
+            div()
+                .id(id)
+                .flex_shrink_0()
+                .px_3()
+                .py_1()
+                .rounded_md()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .bg(if enabled { theme.yellow } else { theme.surface1 })
+                .text_color(if enabled { theme.base } else { theme.overlay0 })
+                .when(enabled, |this| {
+                    this.cursor_pointer().hover(|s| s.bg(theme.peach))
+                })
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .bg(theme.yellow_bg)
+            .border_b_1()
+            .border_color(theme.yellow)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_col()
+                            .text_xs()
+                            .child(
+                                div()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(theme.yellow)
+                                    .child(format!("HEAD is detached at {}", short_sha)),
+                            )
+                            .child(div().text_color(theme.text).child(
+                                "Commits made now won't belong to any branch and can be lost \
+                                 when you check out something else.",
+                            )),
+                    )
+                    .child(div().w(px(180.0)).child(self.branch_name_input.clone()))
+                    .child(
+                        button("detached-create-branch", "Create Branch Here".into(), can_create)
+                            .when(can_create, |this| {
+                                this.on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.create_branch(cx);
+                                }))
+                            }),
+                    )
+                    .when_some(previous_branch, |this, branch| {
+                        this.child(
+                            button("detached-return", format!("Back to {}", branch), true)
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.return_to_previous_branch(cx);
+                                })),
+                        )
+                    }),
+            )
+            .when_some(self.name_error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.red)
+                        .child(match error.suggestion {
+                            Some(suggestion) => format!("{} (try '{}')", error.reason, suggestion),
+                            None => error.reason,
+                        }),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_xs().text_color(theme.red).child(error))
+            })
+    }
+}
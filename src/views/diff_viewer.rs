@@ -1,39 +1,643 @@
 #![allow(dead_code)]
 
-use crate::git::{DiffLineType, FileDiff};
+use crate::actions::{
+    NextDiffFile, NextHunk, PreviousDiffFile, PreviousHunk, PullLfsObjects, ToggleDiffFileList,
+};
+use crate::git::{
+    format_size, DiffDisplayOptions, DiffLineId, DiffLineType, FileDiff, LfsChange, LfsPointer,
+    LineAction,
+};
+use crate::state::{GitState, SettingsState, ToastState, DIFF_PAGE_LINES};
+use crate::theme::{ActiveTheme, Theme};
+use crate::views::syntax::{highlight_diff, HighlightSpan};
 use gpui::prelude::*;
 use gpui::*;
+use std::ops::{Range, RangeInclusive};
+use std::rc::Rc;
+
+/// Copies a permalink for the given lines (or the whole file when None)
+type PermalinkHandler = Rc<dyn Fn(Option<RangeInclusive<u32>>, &mut App)>;
+
+/// Stages or discards the given lines of an unstaged diff
+type LineActionHandler = Rc<dyn Fn(LineAction, Vec<DiffLineId>, &mut App)>;
+
+/// Opens the diffed file in the external editor
+type OpenInEditorHandler = Rc<dyn Fn(&mut App)>;
 
 #[derive(IntoElement)]
 pub struct DiffViewer {
     diff: FileDiff,
+    git_state: Option<Entity<GitState>>,
+    toast_state: Option<Entity<ToastState>>,
+    /// Scroll position, shared with the app so hunk keys can scroll it
+    scroll_handle: Option<ScrollHandle>,
+    /// Show the file list beside a diff of several files
+    show_file_list: bool,
+    /// Where the whitespace and context options are saved
+    settings: Option<Entity<SettingsState>>,
 }
 
 impl DiffViewer {
     pub fn new(diff: FileDiff) -> Self {
-        Self { diff }
+        Self {
+            diff,
+            git_state: None,
+            toast_state: None,
+            scroll_handle: None,
+            show_file_list: false,
+            settings: None,
+        }
+    }
+
+    /// Enable "Copy permalink" for the file and its lines, and opening the
+    /// file in the external editor
+    pub fn with_permalinks(
+        mut self,
+        git_state: Entity<GitState>,
+        toast_state: Entity<ToastState>,
+    ) -> Self {
+        self.git_state = Some(git_state);
+        self.toast_state = Some(toast_state);
+        self
+    }
+
+    /// Add the hunk and file stepping controls, scrolling `scroll_handle`
+    pub fn with_navigation(mut self, scroll_handle: ScrollHandle, show_file_list: bool) -> Self {
+        self.scroll_handle = Some(scroll_handle);
+        self.show_file_list = show_file_list;
+        self
+    }
+
+    /// Add the whitespace and context line toggles, saved to settings
+    pub fn with_options(mut self, settings: Entity<SettingsState>) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    /// Opens the file in the external editor at the first change
+    fn open_in_editor_handler(&self) -> Option<OpenInEditorHandler> {
+        let git_state = self.git_state.clone()?;
+        let path = self.diff.path.clone();
+        let line = self.diff.first_changed_line();
+        Some(Rc::new(move |cx| {
+            let result = git_state.update(cx, |state, cx| state.open_in_editor(&path, line, cx));
+            if let Err(e) = result {
+                log::error!("Failed to open {} in the editor: {:#}", path, e);
+            }
+        }))
+    }
+
+    fn permalink_handler(&self, cx: &App) -> Option<PermalinkHandler> {
+        let git_state = self.git_state.clone()?;
+        let toast_state = self.toast_state.clone()?;
+        git_state.read(cx).remote_web_url()?;
+
+        let path = self.diff.path.clone();
+        Some(Rc::new(move |lines, cx| {
+            let state = git_state.read(cx);
+            let result = state.permalink(&path, lines);
+            let has_unpushed = state.repository_info.as_ref().is_some_and(|r| r.ahead > 0);
+
+            match result {
+                Ok(url) => {
+                    cx.write_to_clipboard(ClipboardItem::new_string(url));
+                    toast_state.update(cx, |toast, cx| {
+                        if has_unpushed {
+                            toast.warning(
+                                "Permalink copied, but HEAD has not been pushed yet",
+                                cx,
+                            );
+                        } else {
+                            toast.success("Permalink copied", cx);
+                        }
+                    });
+                }
+                Err(e) => {
+                    log::error!("Failed to build permalink: {}", e);
+                    let details = git_state.read(cx).error_details("Copy permalink", &e);
+                    toast_state.update(cx, |toast, cx| {
+                        let message = format!("Failed to copy permalink: {}", e);
+                        toast.error_with_details(message, &details, cx);
+                    });
+                }
+            }
+        }))
+    }
+
+    fn line_action_handler(&self) -> Option<LineActionHandler> {
+        if !self.diff.unstaged {
+            return None;
+        }
+        let git_state = self.git_state.clone()?;
+        let toast_state = self.toast_state.clone()?;
+
+        Some(Rc::new(move |action, lines, cx| {
+            let result =
+                git_state.update(cx, |state, cx| state.apply_diff_lines(&lines, action, cx));
+            if let Err(e) = result {
+                let operation = match action {
+                    LineAction::Stage => "Stage lines",
+                    LineAction::Discard => "Discard lines",
+                };
+                log::error!("Failed to {}: {}", operation.to_lowercase(), e);
+                let details = git_state.read(cx).error_details(operation, &e);
+                toast_state.update(cx, |toast, cx| {
+                    let message = format!("{} failed: {}", operation, e);
+                    toast.error_with_details(message, &details, cx);
+                });
+            }
+        }))
+    }
+
+    /// Summary of a Git LFS pointer change, shown instead of the pointer
+    /// text
+    fn render_lfs_change(&self, change: LfsChange, cx: &App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let missing = change.new.as_ref().is_some_and(|pointer| {
+            self.git_state
+                .as_ref()
+                .is_some_and(|state| !state.read(cx).is_lfs_object_downloaded(pointer))
+        });
+        let row = |label: &'static str, pointer: Option<LfsPointer>| {
+            div()
+                .flex()
+                .gap_3()
+                .child(div().w_10().text_color(theme.overlay0).child(label))
+                .child(match pointer {
+                    Some(pointer) => div()
+                        .flex()
+                        .gap_3()
+                        .child(
+                            div()
+                                .font_family("monospace")
+                                .text_color(theme.text)
+                                .child(format!("sha256:{}…", &pointer.oid[..12])),
+                        )
+                        .child(div().text_color(theme.overlay2).child(format_size(pointer.size))),
+                    None => div().text_color(theme.overlay0).child("—"),
+                })
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .p_4()
+            .text_sm()
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.overlay2)
+                    .child("Stored with Git LFS; the repository holds a pointer to the content"),
+            )
+            .child(row("Old", change.old))
+            .child(row("New", change.new))
+            .when(missing, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .text_xs()
+                        .child(
+                            div()
+                                .text_color(theme.yellow)
+                                .child("The content has not been downloaded"),
+                        )
+                        .child(
+                            div()
+                                .id("diff-pull-lfs")
+                                .px_2()
+                                .py_1()
+                                .rounded_md()
+                                .text_color(theme.overlay2)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                .child("Pull LFS Objects")
+                                .on_click(|_event, window, cx| {
+                                    window.dispatch_action(Box::new(PullLfsObjects), cx);
+                                }),
+                        ),
+                )
+            })
+    }
+
+    /// Sizes of both sides and the difference, shown instead of the lines
+    /// of a file over the size limit or with binary content
+    fn render_size_summary(&self, title: &'static str, cx: &App) -> Div {
+        let theme = *cx.theme();
+        let diff = &self.diff;
+        let change = diff.size_change();
+        let change = match change.cmp(&0) {
+            std::cmp::Ordering::Greater => format!("+{}", format_size(change as u64)),
+            std::cmp::Ordering::Less => format!("-{}", format_size(change.unsigned_abs())),
+            std::cmp::Ordering::Equal => "No size change".to_string(),
+        };
+        let size = |size: u64| {
+            if size == 0 {
+                "—".to_string()
+            } else {
+                format_size(size)
+            }
+        };
+        let row = |label: &'static str, value: String| {
+            div()
+                .flex()
+                .gap_3()
+                .child(div().w_16().text_color(theme.overlay0).child(label))
+                .child(div().text_color(theme.text).child(value))
+        };
+
+        // Start-aligned so buttons added below keep their own width
+        div()
+            .flex()
+            .flex_col()
+            .items_start()
+            .gap_2()
+            .p_4()
+            .text_sm()
+            .child(div().text_xs().text_color(theme.overlay2).child(title))
+            .child(row("Old", size(diff.old_size)))
+            .child(row("New", size(diff.new_size)))
+            .child(row("Change", change))
+    }
+
+    fn render_too_large(&self, cx: &App) -> impl IntoElement {
+        let theme = *cx.theme();
+        self.render_size_summary("This file is too large to show as a diff", cx)
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.overlay0)
+                    .child("Raise the limit under Settings → Largest File to Diff to load it"),
+            )
+    }
+
+    /// Placeholder for binary content, with the start of each side as hex
+    /// on request
+    fn render_binary(&self, cx: &App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let hex_preview = self
+            .git_state
+            .as_ref()
+            .and_then(|state| state.read(cx).hex_preview.clone());
+        let showing = hex_preview.is_some();
+        let dump = |label: &'static str, rows: Vec<String>| {
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .child(div().text_xs().text_color(theme.overlay0).child(label))
+                .child(
+                    div()
+                        .p_2()
+                        .rounded_md()
+                        .bg(theme.mantle)
+                        .font_family("monospace")
+                        .text_xs()
+                        .text_color(theme.text)
+                        .when(rows.is_empty(), |this| {
+                            this.text_color(theme.overlay0).child("No content")
+                        })
+                        .children(rows.into_iter().map(|row| div().child(row))),
+                )
+        };
+
+        self.render_size_summary("Binary file; there is no text diff to show", cx)
+            .when_some(self.git_state.clone(), |this, git_state| {
+                let toast_state = self.toast_state.clone();
+                this.child(
+                    div()
+                        .id("diff-hex-preview")
+                        .px_2()
+                        .py_1()
+                        .rounded_md()
+                        .text_xs()
+                        .text_color(theme.overlay2)
+                        .cursor_pointer()
+                        .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                        .child(if showing { "Hide Hex" } else { "Show Hex" })
+                        .on_click(move |_event, _window, cx| {
+                            let result =
+                                git_state.update(cx, |state, cx| state.toggle_hex_preview(cx));
+                            if let Err(e) = result {
+                                log::error!("Failed to read binary content: {}", e);
+                                if let Some(toast_state) = &toast_state {
+                                    toast_state.update(cx, |toast, cx| {
+                                        let message = format!("Failed to read the file: {}", e);
+                                        toast.error(message, cx);
+                                    });
+                                }
+                            }
+                        }),
+                )
+            })
+            .when_some(hex_preview, |this, preview| {
+                this.child(dump("Old", preview.old)).child(dump("New", preview.new))
+            })
+    }
+
+    /// Toggles for how the diff is computed; changing one reloads the diff
+    fn render_options(&self, settings: Entity<SettingsState>, cx: &App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let options = settings.read(cx).data.diff_options;
+        let toggle = |id: ElementId, label: String, active: bool, new: DiffDisplayOptions| {
+            let settings = settings.clone();
+            div()
+                .id(id)
+                .px_2()
+                .py_px()
+                .rounded_sm()
+                .cursor_pointer()
+                .when(active, |this| this.bg(theme.blue).text_color(theme.base))
+                .when(!active, |this| {
+                    this.bg(theme.surface0)
+                        .text_color(theme.overlay2)
+                        .hover(|s| s.bg(theme.surface1))
+                })
+                .child(label)
+                .on_click(move |_event, _window, cx| {
+                    settings.update(cx, |settings, cx| settings.set_diff_options(new, cx));
+                })
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .px_4()
+            .py_1()
+            .border_b_1()
+            .border_color(theme.surface0)
+            .text_xs()
+            .child(toggle(
+                "diff-ignore-whitespace".into(),
+                "Ignore Whitespace".to_string(),
+                options.ignore_whitespace,
+                DiffDisplayOptions {
+                    ignore_whitespace: !options.ignore_whitespace,
+                    ..options
+                },
+            ))
+            .child(toggle(
+                "diff-ignore-blank-lines".into(),
+                "Ignore Blank Lines".to_string(),
+                options.ignore_blank_lines,
+                DiffDisplayOptions {
+                    ignore_blank_lines: !options.ignore_blank_lines,
+                    ..options
+                },
+            ))
+            .child(div().ml_3().mr_1().text_color(theme.overlay0).child("Context"))
+            .children(DiffDisplayOptions::CONTEXT_LINES.into_iter().map(|lines| {
+                toggle(
+                    ElementId::NamedInteger("diff-context".into(), lines as u64),
+                    lines.to_string(),
+                    options.context_lines == lines,
+                    DiffDisplayOptions {
+                        context_lines: lines,
+                        ..options
+                    },
+                )
+            }))
+    }
+
+    /// Files changed together with this one, with the current file
+    /// highlighted; clicking a file shows it
+    fn render_file_list(
+        &self,
+        files: Vec<(String, usize, usize)>,
+        selected: usize,
+        theme: &Theme,
+    ) -> impl IntoElement {
+        let git_state = self.git_state.clone();
+        let scroll_handle = self.scroll_handle.clone();
+
+        div()
+            .id("diff-file-list")
+            .flex()
+            .flex_col()
+            .flex_shrink_0()
+            .w(px(220.0))
+            .gap_px()
+            .p_2()
+            .bg(theme.mantle)
+            .border_r_1()
+            .border_color(theme.surface0)
+            .overflow_y_scroll()
+            .children(files.into_iter().enumerate().map(|(ix, (path, additions, deletions))| {
+                let git_state = git_state.clone();
+                let scroll_handle = scroll_handle.clone();
+                div()
+                    .id(ElementId::NamedInteger("diff-file".into(), ix as u64))
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .px_2()
+                    .py_px()
+                    .rounded_sm()
+                    .text_xs()
+                    .cursor_pointer()
+                    .when(ix == selected, |this| this.bg(theme.blue_bg))
+                    .hover(|s| s.bg(theme.surface0))
+                    .child(div().flex_1().truncate().text_color(theme.text).child(path))
+                    .child(
+                        div()
+                            .flex_shrink_0()
+                            .text_color(theme.green)
+                            .child(format!("+{}", additions)),
+                    )
+                    .child(
+                        div()
+                            .flex_shrink_0()
+                            .text_color(theme.red)
+                            .child(format!("−{}", deletions)),
+                    )
+                    .on_click(move |_event, _window, cx| {
+                        if let Some(git_state) = &git_state {
+                            git_state.update(cx, |state, cx| state.select_diff_file(ix, cx));
+                        }
+                        if let Some(scroll_handle) = &scroll_handle {
+                            scroll_handle.set_offset(point(px(0.0), px(0.0)));
+                        }
+                    })
+            }))
+    }
+
+    /// Changed lines each row acts on: the line itself, or every change in
+    /// the hunk for a hunk header
+    fn action_lines(&self) -> Vec<Vec<DiffLineId>> {
+        let lines = &self.diff.lines;
+        let id = |line: &crate::git::DiffLine| (line.old_lineno, line.new_lineno);
+
+        lines
+            .iter()
+            .enumerate()
+            .map(|(ix, line)| match line.line_type {
+                DiffLineType::Addition | DiffLineType::Deletion => vec![id(line)],
+                DiffLineType::Header if line.content.starts_with("@@") => lines[ix + 1..]
+                    .iter()
+                    .take_while(|l| l.line_type != DiffLineType::Header)
+                    .filter(|l| {
+                        matches!(l.line_type, DiffLineType::Addition | DiffLineType::Deletion)
+                    })
+                    .map(id)
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .collect()
     }
 }
 
 impl RenderOnce for DiffViewer {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let line_limit = self
+            .git_state
+            .as_ref()
+            .map_or(DIFF_PAGE_LINES, |state| state.read(cx).diff_line_limit);
+        let highlights = highlight_diff(&self.diff, line_limit, theme.appearance);
+        let permalink = self.permalink_handler(cx);
+        let open_in_editor = self.open_in_editor_handler();
+        let line_actions = self.line_action_handler();
+        let lfs_change = LfsChange::from_diff(&self.diff);
+        let lfs_tracked = lfs_change.is_some()
+            || self.git_state.as_ref().is_some_and(|state| {
+                state.read(cx).lfs_files.contains_key(&self.diff.path)
+            });
+        let lfs_summary = lfs_change.map(|change| self.render_lfs_change(change, cx));
+        let too_large = self.diff.too_large.then(|| self.render_too_large(cx));
+        let binary = (self.diff.is_binary && !self.diff.too_large).then(|| self.render_binary(cx));
+        // The summaries replace the pointer text and binary notice
+        let lines = if lfs_summary.is_some() || binary.is_some() {
+            &[][..]
+        } else {
+            &self.diff.lines[..self.diff.lines.len().min(line_limit)]
+        };
+        let remaining_lines = self.diff.lines.len() - lines.len();
+        let load_more = self
+            .git_state
+            .clone()
+            .filter(|_| lfs_summary.is_none() && binary.is_none() && remaining_lines > 0);
+        let mut action_lines = line_actions
+            .as_ref()
+            .map(|_| self.action_lines())
+            .unwrap_or_default()
+            .into_iter();
+        let navigable = self.scroll_handle.is_some();
+        let hunk_count = self.diff.hunk_starts().len();
+        let (current_hunk, files, file_index) = match &self.git_state {
+            Some(state) => {
+                let state = state.read(cx);
+                let files: Vec<_> = state
+                    .diff_files
+                    .iter()
+                    .map(|file| (file.path.clone(), file.additions, file.deletions))
+                    .collect();
+                (state.diff_hunk, files, state.diff_file_index)
+            }
+            None => (None, Vec::new(), 0),
+        };
+        let multi_file = navigable && files.len() > 1;
+        let show_file_list = multi_file && self.show_file_list;
+        let hunk_label = match current_hunk {
+            Some(hunk) => format!("Hunk {}/{}", hunk + 1, hunk_count),
+            None => format!("Hunk –/{}", hunk_count),
+        };
+        let file_label = format!("File {}/{}", file_index + 1, files.len());
+        let file_list = show_file_list.then(|| self.render_file_list(files, file_index, &theme));
+        let options_bar = self.settings.clone().map(|settings| self.render_options(settings, cx));
+
+        let navigation = navigable.then(|| {
+            div()
+                .flex()
+                .items_center()
+                .gap_1()
+                .text_xs()
+                .text_color(theme.overlay2)
+                .child(nav_button("diff-prev-hunk", "↑", PreviousHunk, &theme))
+                .child(hunk_label)
+                .child(nav_button("diff-next-hunk", "↓", NextHunk, &theme))
+                .when(multi_file, |this| {
+                    this.child(nav_button("diff-prev-file", "←", PreviousDiffFile, &theme))
+                        .child(file_label)
+                        .child(nav_button("diff-next-file", "→", NextDiffFile, &theme))
+                        .child(nav_button("diff-files", "Files", ToggleDiffFileList, &theme))
+                })
+        });
+
+        let content = div()
+            .id("diff-scroll")
+            .flex_1()
+            .overflow_y_scroll()
+            .when_some(self.scroll_handle.clone(), |this, handle| this.track_scroll(&handle))
+            .p_2()
+            .when_some(lfs_summary, |this, summary| this.child(summary))
+            .when_some(too_large, |this, summary| this.child(summary))
+            .when_some(binary, |this, summary| this.child(summary))
+            .children(lines.iter().enumerate().map(|(ix, line)| {
+                let spans = highlights
+                    .as_ref()
+                    .and_then(|h| h.get(ix))
+                    .cloned()
+                    .unwrap_or_default();
+                let mut diff_line = DiffLine::new(line.clone()).with_highlights(spans);
+                if let Some(handler) = permalink.clone() {
+                    diff_line = diff_line.with_permalink(ix, handler);
+                }
+                if let Some(handler) = line_actions.clone() {
+                    let lines = action_lines.next().unwrap_or_default();
+                    diff_line = diff_line.with_line_actions(ix, lines, handler);
+                }
+                diff_line
+            }))
+            .when_some(load_more, |this, git_state| {
+                let count = remaining_lines.min(DIFF_PAGE_LINES);
+                this.child(
+                    div()
+                        .id("diff-load-more")
+                        .flex()
+                        .justify_center()
+                        .gap_2()
+                        .mt_2()
+                        .py_2()
+                        .rounded_md()
+                        .text_xs()
+                        .text_color(theme.overlay2)
+                        .bg(theme.mantle)
+                        .cursor_pointer()
+                        .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                        .child(format!("Load {} more lines", count))
+                        .child(
+                            div()
+                                .text_color(theme.overlay0)
+                                .child(format!("({} remaining)", remaining_lines)),
+                        )
+                        .on_click(move |_event, _window, cx| {
+                            git_state.update(cx, |state, cx| {
+                                state.show_more_diff_lines(cx);
+                            });
+                        }),
+                )
+            });
+
         div()
             .absolute()
             .inset_0()
             .flex()
             .items_center()
             .justify_center()
-            .bg(rgba(0x00000088))
+            .bg(theme.backdrop)
             .child(
                 div()
                     .flex()
                     .flex_col()
-                    .w(px(800.0))
+                    .w(px(if show_file_list { 1020.0 } else { 800.0 }))
                     .h(px(600.0))
                     .rounded_lg()
-                    .bg(rgb(0x1e1e2e))
+                    .bg(theme.base)
                     .border_1()
-                    .border_color(rgb(0x313244))
+                    .border_color(theme.surface0)
                     .overflow_hidden()
                     // Header
                     .child(
@@ -43,9 +647,9 @@ impl RenderOnce for DiffViewer {
                             .justify_between()
                             .px_4()
                             .py_3()
-                            .bg(rgb(0x181825))
+                            .bg(theme.mantle)
                             .border_b_1()
-                            .border_color(rgb(0x313244))
+                            .border_color(theme.surface0)
                             .child(
                                 div()
                                     .flex()
@@ -55,27 +659,73 @@ impl RenderOnce for DiffViewer {
                                         div()
                                             .text_sm()
                                             .font_weight(FontWeight::SEMIBOLD)
-                                            .text_color(rgb(0xcdd6f4))
+                                            .text_color(theme.text)
                                             .child(self.diff.path.clone()),
                                     )
-                                    .child(
-                                        div()
-                                            .flex()
-                                            .items_center()
-                                            .gap_2()
-                                            .text_xs()
-                                            .child(
-                                                div()
-                                                    .text_color(rgb(0xa6e3a1))
-                                                    .child(format!("+{}", self.diff.additions)),
-                                            )
-                                            .child(
-                                                div()
-                                                    .text_color(rgb(0xf38ba8))
-                                                    .child(format!("-{}", self.diff.deletions)),
-                                            ),
-                                    ),
+                                    .when(lfs_tracked, |this| {
+                                        this.child(
+                                            div()
+                                                .px_1()
+                                                .rounded_sm()
+                                                .bg(theme.surface0)
+                                                .text_xs()
+                                                .text_color(theme.teal)
+                                                .child("LFS"),
+                                        )
+                                    })
+                                    .when(!self.diff.too_large && !self.diff.is_binary, |this| {
+                                        this.child(
+                                            div()
+                                                .flex()
+                                                .items_center()
+                                                .gap_2()
+                                                .text_xs()
+                                                .child(
+                                                    div()
+                                                        .text_color(theme.green)
+                                                        .child(format!("+{}", self.diff.additions)),
+                                                )
+                                                .child(
+                                                    div()
+                                                        .text_color(theme.red)
+                                                        .child(format!("-{}", self.diff.deletions)),
+                                                ),
+                                        )
+                                    }),
                             )
+                            .children(navigation)
+                            .when_some(open_in_editor, |this, handler| {
+                                this.child(
+                                    div()
+                                        .id("open-in-editor")
+                                        .px_2()
+                                        .py_1()
+                                        .rounded_md()
+                                        .text_xs()
+                                        .text_color(theme.overlay2)
+                                        .cursor_pointer()
+                                        .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                        .child("Open in Editor")
+                                        .on_click(move |_event, _window, cx| handler(cx)),
+                                )
+                            })
+                            .when_some(permalink.clone(), |this, handler| {
+                                this.child(
+                                    div()
+                                        .id("copy-permalink")
+                                        .px_2()
+                                        .py_1()
+                                        .rounded_md()
+                                        .text_xs()
+                                        .text_color(theme.overlay2)
+                                        .cursor_pointer()
+                                        .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                        .child("Copy Permalink")
+                                        .on_click(move |_event, _window, cx| {
+                                            handler(None, cx);
+                                        }),
+                                )
+                            })
                             .child(
                                 div()
                                     .id("close-diff")
@@ -83,22 +733,21 @@ impl RenderOnce for DiffViewer {
                                     .py_1()
                                     .rounded_md()
                                     .text_sm()
-                                    .text_color(rgb(0x9399b2))
+                                    .text_color(theme.overlay2)
                                     .cursor_pointer()
-                                    .hover(|s| s.bg(rgb(0x313244)).text_color(rgb(0xcdd6f4)))
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
                                     .child("×"),
                             ),
                     )
-                    // Diff content
+                    .children(options_bar)
+                    // File list and diff content
                     .child(
                         div()
-                            .id("diff-scroll")
+                            .flex()
                             .flex_1()
-                            .overflow_y_scroll()
-                            .p_2()
-                            .children(
-                                self.diff.lines.iter().map(|line| DiffLine::new(line.clone())),
-                            ),
+                            .overflow_hidden()
+                            .children(file_list)
+                            .child(content),
                     ),
             )
     }
@@ -107,58 +756,249 @@ impl RenderOnce for DiffViewer {
 #[derive(IntoElement)]
 pub struct DiffLine {
     line: crate::git::DiffLine,
+    highlights: Vec<HighlightSpan>,
+    /// Line index and handler for copying a permalink from the gutter
+    permalink: Option<(usize, PermalinkHandler)>,
+    /// Line index, the lines to stage or discard (empty for context lines)
+    /// and the handler, shown as gutter controls
+    line_actions: Option<(usize, Vec<DiffLineId>, LineActionHandler)>,
 }
 
 impl DiffLine {
     pub fn new(line: crate::git::DiffLine) -> Self {
-        Self { line }
+        Self {
+            line,
+            highlights: Vec::new(),
+            permalink: None,
+            line_actions: None,
+        }
+    }
+
+    /// Render the content using syntax highlighted spans
+    pub fn with_highlights(mut self, highlights: Vec<HighlightSpan>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    /// Make the line number gutter copy a permalink when clicked
+    pub fn with_permalink(mut self, ix: usize, handler: PermalinkHandler) -> Self {
+        self.permalink = Some((ix, handler));
+        self
+    }
+
+    /// Add stage and discard controls to the gutter
+    pub fn with_line_actions(
+        mut self,
+        ix: usize,
+        lines: Vec<DiffLineId>,
+        handler: LineActionHandler,
+    ) -> Self {
+        self.line_actions = Some((ix, lines, handler));
+        self
+    }
+
+    /// Lines in the HEAD version of the file this line links to. Hunk
+    /// headers link to the whole old-side range of the hunk.
+    fn permalink_lines(&self) -> Option<RangeInclusive<u32>> {
+        match self.line.line_type {
+            DiffLineType::Context | DiffLineType::Deletion => {
+                self.line.old_lineno.map(|n| n..=n)
+            }
+            DiffLineType::Header => {
+                // "@@ -start,count +start,count @@"
+                let old = self.line.content.strip_prefix("@@ -")?.split(' ').next()?;
+                let (start, count) = match old.split_once(',') {
+                    Some((start, count)) => (start.parse::<u32>().ok()?, count.parse().ok()?),
+                    None => (old.parse::<u32>().ok()?, 1),
+                };
+                (start > 0 && count > 0).then(|| start..=start + count - 1)
+            }
+            DiffLineType::Addition => None,
+        }
     }
 }
 
 impl RenderOnce for DiffLine {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
-        let (bg_color, text_color, prefix) = match self.line.line_type {
-            DiffLineType::Addition => (rgb(0x1a3d2e), rgb(0xa6e3a1), "+"),
-            DiffLineType::Deletion => (rgb(0x3d1a1a), rgb(0xf38ba8), "-"),
-            DiffLineType::Context => (rgb(0x1e1e2e), rgb(0xcdd6f4), " "),
-            DiffLineType::Header => (rgb(0x313244), rgb(0x89b4fa), ""),
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        // Worked out before the highlights are moved out of the line
+        let permalink = self.permalink_lines().zip(self.permalink.clone());
+        let (bg_color, text_color, emphasis_color, prefix) = match self.line.line_type {
+            DiffLineType::Addition => (theme.green_bg, theme.green, theme.green_highlight, "+"),
+            DiffLineType::Deletion => (theme.red_bg, theme.red, theme.red_highlight, "-"),
+            DiffLineType::Context => (theme.base, theme.text, theme.base, " "),
+            DiffLineType::Header => (theme.surface0, theme.blue, theme.surface0, ""),
+        };
+
+        let spans = if self.highlights.is_empty() {
+            vec![HighlightSpan {
+                text: self.line.content.trim_end().to_string(),
+                color: text_color,
+            }]
+        } else {
+            self.highlights
+        };
+        let segments = split_changed(spans, &self.line.changed_ranges);
+
+        let gutter = div()
+            .flex()
+            .items_center()
+            .w_20()
+            .px_2()
+            .text_xs()
+            .text_color(theme.overlay0)
+            .child(
+                self.line
+                    .old_lineno
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| " ".to_string()),
+            )
+            .child(
+                self.line
+                    .new_lineno
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| " ".to_string()),
+            );
+
+        // Clicking the gutter copies a permalink for the line (or hunk)
+        let gutter = match permalink {
+            Some((lines, (ix, handler))) => gutter
+                .id(ElementId::Name(format!("diff-gutter-{}", ix).into()))
+                .cursor_pointer()
+                .hover(|s| s.text_color(theme.blue))
+                .on_click(move |_event, _window, cx| {
+                    handler(Some(lines.clone()), cx);
+                })
+                .into_any_element(),
+            None => gutter.into_any_element(),
         };
 
+        // Stage and discard controls; context rows keep the column empty
+        let controls = self.line_actions.map(|(ix, lines, handler)| {
+            let has_lines = !lines.is_empty();
+            let discard_lines = lines.clone();
+            let discard_handler = handler.clone();
+
+            div()
+                .flex()
+                .items_center()
+                .flex_shrink_0()
+                .w_10()
+                .text_xs()
+                .when(has_lines, |this| {
+                    this.child(
+                        div()
+                            .id(ElementId::Name(format!("diff-stage-{}", ix).into()))
+                            .w_5()
+                            .text_center()
+                            .rounded_sm()
+                            .text_color(theme.green)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("+")
+                            .on_click(move |_event, _window, cx| {
+                                handler(LineAction::Stage, lines.clone(), cx);
+                            }),
+                    )
+                    .child(
+                        div()
+                            .id(ElementId::Name(format!("diff-discard-{}", ix).into()))
+                            .w_5()
+                            .text_center()
+                            .rounded_sm()
+                            .text_color(theme.red)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("↺")
+                            .on_click(move |_event, _window, cx| {
+                                discard_handler(LineAction::Discard, discard_lines.clone(), cx);
+                            }),
+                    )
+                })
+        });
+
         div()
             .flex()
             .items_start()
             .text_sm()
             .bg(bg_color)
+            .children(controls)
             // Line numbers
-            .child(
-                div()
-                    .flex()
-                    .items_center()
-                    .w_20()
-                    .px_2()
-                    .text_xs()
-                    .text_color(rgb(0x6c7086))
-                    .child(
-                        self.line
-                            .old_lineno
-                            .map(|n| n.to_string())
-                            .unwrap_or_else(|| " ".to_string()),
-                    )
-                    .child(
-                        self.line
-                            .new_lineno
-                            .map(|n| n.to_string())
-                            .unwrap_or_else(|| " ".to_string()),
-                    ),
-            )
+            .child(gutter)
             // Prefix
             .child(div().w_4().text_color(text_color).child(prefix.to_string()))
-            // Content
+            // Content, with intraline changes emphasized
             .child(
                 div()
                     .flex_1()
-                    .text_color(text_color)
-                    .child(self.line.content.trim_end().to_string()),
+                    .flex()
+                    .children(segments.into_iter().map(|(span, changed)| {
+                        div()
+                            .text_color(span.color)
+                            .when(changed, |this| this.bg(emphasis_color).rounded_sm())
+                            .child(span.text)
+                    })),
             )
     }
 }
+
+/// Header button that dispatches one of the diff viewer's actions
+fn nav_button(
+    id: &'static str,
+    label: &'static str,
+    action: impl Action,
+    theme: &Theme,
+) -> Stateful<Div> {
+    div()
+        .id(id)
+        .px_1p5()
+        .rounded_sm()
+        .text_color(theme.overlay2)
+        .cursor_pointer()
+        .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+        .child(label)
+        .on_click(move |_event, window, cx| {
+            window.dispatch_action(action.boxed_clone(), cx);
+        })
+}
+
+/// Split spans at the boundaries of changed byte ranges, flagging the pieces
+/// that fall inside a change
+fn split_changed(
+    spans: Vec<HighlightSpan>,
+    changed: &[Range<usize>],
+) -> Vec<(HighlightSpan, bool)> {
+    let mut segments = Vec::new();
+    let mut offset = 0;
+
+    for span in spans {
+        let start = offset;
+        let end = offset + span.text.len();
+        offset = end;
+
+        let mut cuts = vec![start, end];
+        for range in changed {
+            for boundary in [range.start, range.end] {
+                if boundary > start && boundary < end {
+                    cuts.push(boundary);
+                }
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        for window in cuts.windows(2) {
+            let (a, b) = (window[0], window[1]);
+            let is_changed = changed.iter().any(|r| r.start <= a && b <= r.end);
+            segments.push((
+                HighlightSpan {
+                    text: span.text[a - start..b - start].to_string(),
+                    color: span.color,
+                },
+                is_changed,
+            ));
+        }
+    }
+
+    segments
+}
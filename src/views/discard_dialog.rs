@@ -0,0 +1,194 @@
+#![allow(dead_code)]
+
+use crate::state::GitState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Confirms the pending discard, listing the files whose changes would be
+/// lost and offering to back them up to the trash first
+#[derive(IntoElement)]
+pub struct DiscardDialog {
+    git_state: Entity<GitState>,
+}
+
+impl DiscardDialog {
+    pub fn new(git_state: Entity<GitState>) -> Self {
+        Self { git_state }
+    }
+}
+
+impl RenderOnce for DiscardDialog {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let Some(request) = self.git_state.read(cx).pending_discard.clone() else {
+            return div().into_any_element();
+        };
+        let file_count = request.files.len();
+        let backup = request.backup;
+        let git_state_close = self.git_state.clone();
+        let git_state_backup = self.git_state.clone();
+        let git_state_cancel = self.git_state.clone();
+        let git_state_confirm = self.git_state.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_start()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child(format!("{}?", request.scope.title())),
+                            )
+                            .child(div().text_sm().text_color(theme.overlay2).child(format!(
+                                "Uncommitted changes to {} file{} will be lost",
+                                file_count,
+                                if file_count == 1 { "" } else { "s" }
+                            ))),
+                    )
+                    .child(
+                        div()
+                            .id("discard-close")
+                            .px_2()
+                            .rounded_md()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .child("×")
+                            .on_click(move |_event, _window, cx| {
+                                git_state_close.update(cx, |state, cx| state.cancel_discard(cx));
+                            }),
+                    ),
+            )
+            .when_some(request.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error),
+                )
+            })
+            // Affected files
+            .child(
+                div()
+                    .id("discard-files")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .children(request.files.iter().map(|path| {
+                        div()
+                            .py_px()
+                            .font_family("monospace")
+                            .text_xs()
+                            .text_color(theme.text)
+                            .child(path.clone())
+                    })),
+            )
+            // Backup option
+            .child(
+                div()
+                    .id("discard-backup")
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .cursor_pointer()
+                    .child(
+                        div()
+                            .size_4()
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(if backup { theme.blue } else { theme.overlay0 })
+                            .when(backup, |this| this.bg(theme.blue))
+                            .text_xs()
+                            .text_color(theme.base)
+                            .child(if backup { "✓" } else { "" }),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .child("Back up to trash so the changes can be restored"),
+                    )
+                    .on_click(move |_event, _window, cx| {
+                        git_state_backup
+                            .update(cx, |state, cx| state.set_discard_backup(!backup, cx));
+                    }),
+            )
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .pt_2()
+                    .border_t_1()
+                    .border_color(theme.surface0)
+                    .child(
+                        div()
+                            .id("discard-cancel")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("Cancel")
+                            .on_click(move |_event, _window, cx| {
+                                git_state_cancel.update(cx, |state, cx| state.cancel_discard(cx));
+                            }),
+                    )
+                    .child(
+                        div()
+                            .id("discard-confirm")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(theme.red)
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.base)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.maroon))
+                            .child(format!("Discard {}", file_count))
+                            .on_click(move |_event, _window, cx| {
+                                git_state_confirm.update(cx, |state, cx| {
+                                    if let Err(e) = state.confirm_discard(cx) {
+                                        log::error!("Failed to discard changes: {}", e);
+                                    }
+                                });
+                            }),
+                    ),
+            )
+            .into_any_element()
+    }
+}
@@ -0,0 +1,131 @@
+#![allow(dead_code)]
+
+use crate::state::DraftData;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Events emitted by the draft recovery dialog
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DraftRecoveryEvent {
+    Restore,
+    Discard,
+}
+
+impl EventEmitter<DraftRecoveryEvent> for DraftRecoveryDialog {}
+
+/// Offers to restore work left behind by a session that quit unexpectedly
+pub struct DraftRecoveryDialog {
+    draft: DraftData,
+}
+
+impl DraftRecoveryDialog {
+    pub fn new(draft: DraftData, _cx: &mut Context<Self>) -> Self {
+        Self { draft }
+    }
+}
+
+impl Render for DraftRecoveryDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let message = self.draft.commit_message.trim().to_string();
+        let selections = self.draft.conflict_selections.len();
+        let saved_at = self
+            .draft
+            .saved_at
+            .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string());
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            .child(
+                div()
+                    .text_lg()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(theme.text)
+                    .child("Restore Unsaved Work?"),
+            )
+            .child(div().text_sm().text_color(theme.overlay2).child(match saved_at {
+                Some(time) => format!(
+                    "Awabancha did not quit cleanly. These drafts were saved at {}.",
+                    time
+                ),
+                None => "Awabancha did not quit cleanly. These drafts were saved.".to_string(),
+            }))
+            .when(!message.is_empty(), |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .child(div().text_xs().text_color(theme.overlay2).child("Commit message"))
+                        .child(
+                            div()
+                                .id("draft-message")
+                                .max_h(px(120.0))
+                                .overflow_y_scroll()
+                                .px_3()
+                                .py_2()
+                                .rounded_md()
+                                .bg(theme.mantle)
+                                .text_sm()
+                                .text_color(theme.text)
+                                .child(message),
+                        ),
+                )
+            })
+            .when(selections > 0, |this| {
+                this.child(div().text_sm().text_color(theme.text).child(format!(
+                    "Conflict choices for {} file{}",
+                    selections,
+                    if selections == 1 { "" } else { "s" }
+                )))
+            })
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .mt_auto()
+                    .child(
+                        div()
+                            .id("draft-discard")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("Discard")
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.emit(DraftRecoveryEvent::Discard);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("draft-restore")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(theme.blue)
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.base)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.lavender))
+                            .child("Restore")
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.emit(DraftRecoveryEvent::Restore);
+                            })),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,129 @@
+#![allow(dead_code)]
+
+use super::settings::SettingsButton;
+use crate::components::{TextInputChanged, TextInputView};
+use crate::i18n::{t, ActiveLocale};
+use crate::state::SettingsState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Settings section for the command files are opened with
+pub struct ExternalEditorView {
+    settings: Entity<SettingsState>,
+    command_input: Entity<TextInputView>,
+}
+
+impl ExternalEditorView {
+    pub fn new(settings: Entity<SettingsState>, cx: &mut Context<Self>) -> Self {
+        let command = settings.read(cx).data.editor_command.clone().unwrap_or_default();
+        let command_input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .with_placeholder("code --goto {file}:{line}")
+                .with_content(command)
+        });
+        cx.subscribe(&command_input, |_this, _input, _event: &TextInputChanged, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            settings,
+            command_input,
+        }
+    }
+
+    fn saved_command(&self, cx: &App) -> String {
+        self.settings
+            .read(cx)
+            .data
+            .editor_command
+            .clone()
+            .unwrap_or_default()
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        let command = self.command_input.read(cx).content().trim().to_string();
+        let command = (!command.is_empty()).then_some(command);
+        self.settings
+            .update(cx, |settings, cx| settings.set_editor_command(command, cx));
+        cx.notify();
+    }
+
+    fn clear(&mut self, cx: &mut Context<Self>) {
+        self.settings
+            .update(cx, |settings, cx| settings.set_editor_command(None, cx));
+        self.command_input.update(cx, |input, cx| input.set_content("", cx));
+        cx.notify();
+    }
+}
+
+impl Render for ExternalEditorView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let locale = cx.locale();
+        let saved = self.saved_command(cx);
+        let typed = self.command_input.read(cx).content().trim().to_string();
+        let can_save = typed != saved;
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_3()
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.blue)
+                            .child(t(locale, "settings.editorCommand")),
+                    )
+                    .when(!saved.is_empty(), |this| {
+                        this.child(
+                            SettingsButton::new(t(locale, "settings.editorCommandClear"), false)
+                                .id("clear-editor-command")
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.clear(cx);
+                                })),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().flex_1().child(self.command_input.clone()))
+                    .child(
+                        div()
+                            .id("save-editor-command")
+                            .flex_shrink_0()
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .bg(if can_save { theme.blue } else { theme.surface0 })
+                            .text_color(if can_save { theme.base } else { theme.overlay0 })
+                            .child(t(locale, "common.save"))
+                            .when(can_save, |this| {
+                                this.cursor_pointer()
+                                    .hover(|s| s.bg(theme.lavender))
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.save(cx)
+                                    }))
+                            }),
+                    ),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.overlay0)
+                    .child(t(locale, "settings.editorCommandHint")),
+            )
+    }
+}
@@ -1,13 +1,96 @@
 #![allow(dead_code)]
 
-use crate::actions::ShowDiff;
-use crate::git::FileStatus;
-use crate::state::GitState;
+use crate::actions::{
+    DiscardAll, PullLfsObjects, ReviewChanges, ShowBlame, ShowDiff, ShowFileHistory,
+};
+use crate::components::context_menu::{ContextMenu, ContextMenuItem};
+use crate::components::{TextInputChanged, TextInputView};
+use crate::git::{
+    format_size, DiffLineType, DiscardScope, FileFilter, FileStatus, FileStatusType,
+    IgnorePattern, StatusGroup,
+};
+use crate::state::{reveal_label, GitState, HunkPreview};
+use crate::theme::ActiveTheme;
 use gpui::prelude::*;
 use gpui::*;
+use std::collections::{BTreeMap, HashSet};
+
+/// Indentation per tree level in pixels
+const TREE_INDENT: f32 = 12.0;
 
 pub struct FileList {
     git_state: Entity<GitState>,
+    /// Group files into a directory tree instead of a flat list
+    tree_view: bool,
+    /// Collapsed directories, keyed by section and path
+    collapsed_dirs: HashSet<(bool, String)>,
+    /// Menu opened by right-clicking a file
+    context_menu: Option<FileMenuState>,
+    /// Path or glob typed above the list
+    filter_input: Entity<TextInputView>,
+    /// Files shown, by path and status
+    filter: FileFilter,
+    /// Staging held back because it would store files meant for Git LFS
+    /// as regular blobs
+    lfs_warning: Option<LfsStagingWarning>,
+    /// Row a shift-click selects from, by section and path
+    selection_anchor: Option<(bool, String)>,
+    /// Rows in display order as of the last render, for range selection
+    visible_files: Vec<(bool, String)>,
+}
+
+/// What a stage click was about to stage
+#[derive(Clone)]
+enum StageTarget {
+    File(String),
+    Directory(String),
+    /// The selected files
+    Files(Vec<String>),
+}
+
+#[derive(Clone)]
+struct LfsStagingWarning {
+    target: StageTarget,
+    warnings: Vec<String>,
+}
+
+#[derive(Clone)]
+struct FileMenuState {
+    path: String,
+    /// Opened on a row of the staged section
+    is_staged: bool,
+    /// Offer ignore rules for the file
+    is_untracked: bool,
+    /// Deleted in the working tree, so there is nothing to open
+    is_deleted: bool,
+    position: Point<Pixels>,
+}
+
+/// Directory node in the tree view
+#[derive(Default)]
+struct DirNode {
+    dirs: BTreeMap<String, DirNode>,
+    files: Vec<FileStatus>,
+}
+
+impl DirNode {
+    fn build(files: Vec<FileStatus>) -> Self {
+        let mut root = DirNode::default();
+        for file in files {
+            let mut node = &mut root;
+            if let Some((dir, _)) = file.path.rsplit_once('/') {
+                for part in dir.split('/') {
+                    node = node.dirs.entry(part.to_string()).or_default();
+                }
+            }
+            node.files.push(file);
+        }
+        root
+    }
+
+    fn file_count(&self) -> usize {
+        self.files.len() + self.dirs.values().map(|d| d.file_count()).sum::<usize>()
+    }
 }
 
 impl FileList {
@@ -18,10 +101,78 @@ impl FileList {
         })
         .detach();
 
-        Self { git_state }
+        let filter_input =
+            cx.new(|cx| TextInputView::new(cx).with_placeholder("Filter files (e.g. *.rs)"));
+        cx.subscribe(&filter_input, |this, _input, event: &TextInputChanged, cx| {
+            this.filter.query = event.0.to_string();
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            git_state,
+            tree_view: false,
+            collapsed_dirs: HashSet::new(),
+            context_menu: None,
+            filter_input,
+            filter: FileFilter::default(),
+            lfs_warning: None,
+            selection_anchor: None,
+            visible_files: Vec::new(),
+        }
+    }
+
+    pub fn set_tree_view(&mut self, tree_view: bool, cx: &mut Context<Self>) {
+        self.tree_view = tree_view;
+        cx.notify();
+    }
+
+    fn toggle_dir(&mut self, is_staged: bool, dir: String, cx: &mut Context<Self>) {
+        let key = (is_staged, dir);
+        if !self.collapsed_dirs.remove(&key) {
+            self.collapsed_dirs.insert(key);
+        }
+        cx.notify();
+    }
+
+    fn stage_directory(&mut self, dir: String, cx: &mut Context<Self>) {
+        let prefix = format!("{}/", dir);
+        let paths: Vec<String> = self
+            .git_state
+            .read(cx)
+            .unstaged_files()
+            .into_iter()
+            .filter(|file| file.path.starts_with(&prefix))
+            .map(|file| file.path.clone())
+            .collect();
+        if self.warn_lfs_staging(StageTarget::Directory(dir.clone()), &paths, cx) {
+            return;
+        }
+        self.git_state.update(cx, |state, cx| {
+            if let Err(e) = state.stage_directory(&dir, cx) {
+                log::error!("Failed to stage directory: {}", e);
+            }
+        });
+    }
+
+    fn unstage_directory(&mut self, dir: String, cx: &mut Context<Self>) {
+        self.git_state.update(cx, |state, cx| {
+            if let Err(e) = state.unstage_directory(&dir, cx) {
+                log::error!("Failed to unstage directory: {}", e);
+            }
+        });
+    }
+
+    fn request_discard_directory(&mut self, dir: String, cx: &mut Context<Self>) {
+        self.git_state.update(cx, |state, cx| {
+            state.request_discard(DiscardScope::Directory(dir), cx);
+        });
     }
 
     fn stage_file(&mut self, path: String, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.warn_lfs_staging(StageTarget::File(path.clone()), std::slice::from_ref(&path), cx) {
+            return;
+        }
         self.git_state.update(cx, |state, cx| {
             if let Err(e) = state.stage_file(&path, cx) {
                 log::error!("Failed to stage file: {}", e);
@@ -29,6 +180,64 @@ impl FileList {
         });
     }
 
+    /// Hold back staging `target` when any of `paths` match a Git LFS
+    /// pattern but would be stored as a regular blob, since libgit2 doesn't
+    /// run the LFS filter. Returns whether staging was held back.
+    fn warn_lfs_staging(
+        &mut self,
+        target: StageTarget,
+        paths: &[String],
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let warnings = self.git_state.read(cx).lfs_staging_warnings(paths);
+        if warnings.is_empty() {
+            return false;
+        }
+        self.lfs_warning = Some(LfsStagingWarning { target, warnings });
+        cx.notify();
+        true
+    }
+
+    /// Stage the held back files with `git add`, which stores LFS pointers
+    fn stage_with_lfs(&mut self, cx: &mut Context<Self>) {
+        let Some(warning) = self.lfs_warning.take() else {
+            return;
+        };
+        let paths = match warning.target {
+            StageTarget::File(path) | StageTarget::Directory(path) => vec![path],
+            StageTarget::Files(paths) => paths,
+        };
+        self.git_state.update(cx, |state, cx| {
+            if let Err(e) = state.stage_with_lfs(&paths, cx) {
+                log::error!("Failed to stage with Git LFS: {}", e);
+            }
+        });
+        cx.notify();
+    }
+
+    /// Stage the held back files as regular blobs after all
+    fn stage_despite_lfs_warning(&mut self, cx: &mut Context<Self>) {
+        let Some(warning) = self.lfs_warning.take() else {
+            return;
+        };
+        self.git_state.update(cx, |state, cx| {
+            let result = match &warning.target {
+                StageTarget::File(path) => state.stage_file(path, cx),
+                StageTarget::Directory(dir) => state.stage_directory(dir, cx),
+                StageTarget::Files(paths) => state.stage_files(paths, cx),
+            };
+            if let Err(e) = result {
+                log::error!("Failed to stage: {}", e);
+            }
+        });
+        cx.notify();
+    }
+
+    fn dismiss_lfs_warning(&mut self, cx: &mut Context<Self>) {
+        self.lfs_warning = None;
+        cx.notify();
+    }
+
     fn unstage_file(&mut self, path: String, _window: &mut Window, cx: &mut Context<Self>) {
         self.git_state.update(cx, |state, cx| {
             if let Err(e) = state.unstage_file(&path, cx) {
@@ -39,15 +248,185 @@ impl FileList {
 
     fn discard_file(&mut self, path: String, _window: &mut Window, cx: &mut Context<Self>) {
         self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.discard_file(&path, cx) {
-                log::error!("Failed to discard file: {}", e);
+            state.request_discard(DiscardScope::File(path), cx);
+        });
+    }
+
+    /// Start loading the hunk preview as soon as a file is hovered, so it's
+    /// usually ready by the time the popover appears
+    fn preload_hunk_preview(&mut self, path: &str, is_staged: bool, cx: &mut Context<Self>) {
+        self.git_state.update(cx, |state, cx| {
+            state.hunk_preview(path, is_staged, cx);
+        });
+    }
+
+    fn show_context_menu(&mut self, menu: FileMenuState, cx: &mut Context<Self>) {
+        self.context_menu = Some(menu);
+        cx.notify();
+    }
+
+    fn hide_context_menu(&mut self, cx: &mut Context<Self>) {
+        self.context_menu = None;
+        cx.notify();
+    }
+
+    fn ignore(&mut self, pattern: IgnorePattern, cx: &mut Context<Self>) {
+        self.git_state.update(cx, |state, cx| {
+            if let Err(e) = state.ignore(&pattern, cx) {
+                log::error!("Failed to update .gitignore: {}", e);
             }
         });
+        self.hide_context_menu(cx);
+    }
+
+    fn open_in_editor(&mut self, path: String, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.open_in_editor(&path, None, cx));
+        if let Err(e) = result {
+            log::error!("Failed to open {} in the editor: {:#}", path, e);
+        }
     }
 
-    fn show_diff(&mut self, path: String, window: &mut Window, cx: &mut Context<Self>) {
+    fn reveal_in_file_manager(&mut self, path: String, cx: &mut Context<Self>) {
+        if let Err(e) = self.git_state.read(cx).reveal_in_file_manager(&path) {
+            log::error!("Failed to reveal {}: {:#}", path, e);
+        }
+    }
+
+    fn copy_path(&mut self, path: String, cx: &mut Context<Self>) {
+        cx.write_to_clipboard(ClipboardItem::new_string(path));
+    }
+
+    fn toggle_status_group(&mut self, group: StatusGroup, cx: &mut Context<Self>) {
+        self.filter.toggle_group(group);
+        cx.notify();
+    }
+
+    fn clear_filter(&mut self, cx: &mut Context<Self>) {
+        self.filter.groups.clear();
+        self.filter_input.update(cx, |input, cx| input.set_content("", cx));
+        cx.notify();
+    }
+
+    fn toggle_show_ignored(&mut self, cx: &mut Context<Self>) {
         self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.load_file_diff(&path, cx) {
+            let show = !state.show_ignored;
+            state.set_show_ignored(show, cx);
+        });
+    }
+
+    fn toggle_selection(&mut self, path: String, is_staged: bool, cx: &mut Context<Self>) {
+        self.git_state.update(cx, |state, cx| {
+            state.toggle_file_selection(&path, cx);
+        });
+        self.selection_anchor = Some((is_staged, path));
+    }
+
+    /// Select every row between the anchor and the clicked row, across
+    /// sections as they are shown
+    fn select_range(&mut self, path: String, is_staged: bool, cx: &mut Context<Self>) {
+        let row = (is_staged, path);
+        let Some(end) = self.visible_files.iter().position(|r| *r == row) else {
+            return;
+        };
+        let start = self
+            .selection_anchor
+            .as_ref()
+            .and_then(|anchor| self.visible_files.iter().position(|r| r == anchor))
+            .unwrap_or(end);
+        let (from, to) = (start.min(end), start.max(end));
+        let paths: Vec<String> = self.visible_files[from..=to]
+            .iter()
+            .map(|(_, path)| path.clone())
+            .collect();
+        self.git_state.update(cx, |state, cx| state.select_files(&paths, cx));
+        if self.selection_anchor.is_none() {
+            self.selection_anchor = Some(row);
+        }
+    }
+
+    fn clear_selection(&mut self, cx: &mut Context<Self>) {
+        self.selection_anchor = None;
+        self.git_state.update(cx, |state, cx| state.deselect_all_files(cx));
+    }
+
+    fn stage_selection(&mut self, cx: &mut Context<Self>) {
+        let paths = self.git_state.read(cx).selected_files.clone();
+        if self.warn_lfs_staging(StageTarget::Files(paths.clone()), &paths, cx) {
+            return;
+        }
+        self.git_state.update(cx, |state, cx| {
+            if let Err(e) = state.stage_files(&paths, cx) {
+                log::error!("Failed to stage selected files: {}", e);
+            }
+        });
+    }
+
+    fn unstage_selection(&mut self, cx: &mut Context<Self>) {
+        let paths = self.git_state.read(cx).selected_files.clone();
+        self.git_state.update(cx, |state, cx| {
+            if let Err(e) = state.unstage_files(&paths, cx) {
+                log::error!("Failed to unstage selected files: {}", e);
+            }
+        });
+    }
+
+    fn request_discard_selection(&mut self, cx: &mut Context<Self>) {
+        self.git_state.update(cx, |state, cx| {
+            let paths = state.selected_files.clone();
+            state.request_discard(DiscardScope::Files(paths), cx);
+        });
+    }
+
+    /// Rows of a section in the order they are shown, leaving out files in
+    /// collapsed directories
+    fn visible_rows(&self, files: &[FileStatus], is_staged: bool) -> Vec<(bool, String)> {
+        if !self.tree_view {
+            return files.iter().map(|f| (is_staged, f.path.clone())).collect();
+        }
+
+        fn walk(
+            list: &FileList,
+            node: &DirNode,
+            path: &str,
+            is_staged: bool,
+            rows: &mut Vec<(bool, String)>,
+        ) {
+            for (name, child) in &node.dirs {
+                let dir = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", path, name)
+                };
+                if !list.collapsed_dirs.contains(&(is_staged, dir.clone())) {
+                    walk(list, child, &dir, is_staged, rows);
+                }
+            }
+            rows.extend(node.files.iter().map(|f| (is_staged, f.path.clone())));
+        }
+
+        let mut rows = Vec::new();
+        walk(self, &DirNode::build(files.to_vec()), "", is_staged, &mut rows);
+        rows
+    }
+
+    /// Open the diff of a file; unstaged files show only their unstaged
+    /// changes, which can be staged or discarded line by line
+    fn show_diff(
+        &mut self,
+        path: String,
+        is_staged: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.git_state.update(cx, |state, cx| {
+            let result = if is_staged {
+                state.load_file_diff(&path, cx)
+            } else {
+                state.load_unstaged_diff(&path, cx)
+            };
+            if let Err(e) = result {
                 log::error!("Failed to load diff: {}", e);
             }
         });
@@ -57,23 +436,223 @@ impl FileList {
 
 impl Render for FileList {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
         let git_state_read = self.git_state.read(cx);
 
         let staged_files: Vec<_> = git_state_read
             .staged_files()
-            .iter()
-            .map(|f| (*f).clone())
+            .into_iter()
+            .filter(|f| self.filter.matches(f))
+            .cloned()
             .collect();
         let unstaged_files: Vec<_> = git_state_read
             .unstaged_files()
-            .iter()
-            .map(|f| (*f).clone())
+            .into_iter()
+            .filter(|f| self.filter.matches(f))
+            .cloned()
             .collect();
         let is_empty = git_state_read.files.is_empty();
+        let all_filtered = !is_empty && staged_files.is_empty() && unstaged_files.is_empty();
+        let filter_active = self.filter.is_active();
+        let selected_count = git_state_read.selected_files.len();
+        // Bulk actions only show when some selected file can take them
+        let selected_staged = staged_files.iter().any(|f| git_state_read.is_file_selected(&f.path));
+        let selected_unstaged = unstaged_files
+            .iter()
+            .any(|f| git_state_read.is_file_selected(&f.path));
+        let selected_discardable = unstaged_files.iter().any(|f| {
+            f.status != FileStatusType::Untracked && git_state_read.is_file_selected(&f.path)
+        });
+        let show_ignored = git_state_read.show_ignored;
+        let ignored_files = git_state_read.ignored_files.clone();
+        let lfs_missing = git_state_read.lfs_missing.len();
+        let lfs_pulling = git_state_read.lfs_pulling;
+        let lfs_warning = self.lfs_warning.clone();
+        let context_menu = self.context_menu.clone();
+
+        let mut visible_files = self.visible_rows(&staged_files, true);
+        visible_files.extend(self.visible_rows(&unstaged_files, false));
+        self.visible_files = visible_files;
 
         div()
             .flex()
             .flex_col()
+            .relative()
+            // Click outside to close context menu
+            .when(context_menu.is_some(), |this| {
+                this.on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _event: &MouseDownEvent, _window, cx| {
+                        this.hide_context_menu(cx);
+                    }),
+                )
+            })
+            // Pointers checked out without their content
+            .when(lfs_missing > 0, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .justify_between()
+                        .gap_2()
+                        .px_4()
+                        .py_1()
+                        .bg(theme.yellow_bg)
+                        .text_xs()
+                        .child(div().text_color(theme.yellow).child(format!(
+                            "{} Git LFS file{} not downloaded",
+                            lfs_missing,
+                            if lfs_missing == 1 { "" } else { "s" }
+                        )))
+                        .child(
+                            div()
+                                .id("pull-lfs-objects")
+                                .flex_shrink_0()
+                                .px_2()
+                                .rounded_sm()
+                                .text_color(theme.overlay2)
+                                .when(lfs_pulling, |this| this.child("Pulling…"))
+                                .when(!lfs_pulling, |this| {
+                                    this.cursor_pointer()
+                                        .hover(|s| s.bg(theme.surface1).text_color(theme.text))
+                                        .child("Pull LFS Objects")
+                                        .on_click(|_event, window, cx| {
+                                            window.dispatch_action(Box::new(PullLfsObjects), cx);
+                                        })
+                                }),
+                        ),
+                )
+            })
+            // Filter input and status chips
+            .when(!is_empty, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .px_4()
+                        .py_2()
+                        .child(self.filter_input.clone())
+                        .child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_1()
+                                .children(StatusGroup::ALL.into_iter().map(|group| {
+                                    let active = self.filter.groups.contains(&group);
+                                    div()
+                                        .id(ElementId::Name(
+                                            format!("filter-{}", group.label()).into(),
+                                        ))
+                                        .px_2()
+                                        .py_px()
+                                        .rounded_sm()
+                                        .text_xs()
+                                        .cursor_pointer()
+                                        .when(active, |this| {
+                                            this.bg(theme.blue).text_color(theme.base)
+                                        })
+                                        .when(!active, |this| {
+                                            this.bg(theme.surface0)
+                                                .text_color(theme.overlay2)
+                                                .hover(|s| s.bg(theme.surface1))
+                                        })
+                                        .child(group.label())
+                                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                                            this.toggle_status_group(group, cx);
+                                        }))
+                                }))
+                                .when(filter_active, |this| {
+                                    this.child(
+                                        div()
+                                            .id("clear-file-filter")
+                                            .ml_auto()
+                                            .px_2()
+                                            .rounded_sm()
+                                            .text_xs()
+                                            .text_color(theme.overlay2)
+                                            .cursor_pointer()
+                                            .hover(|s| s.bg(theme.surface1).text_color(theme.text))
+                                            .child("Clear")
+                                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                                this.clear_filter(cx);
+                                            })),
+                                    )
+                                }),
+                        ),
+                )
+            })
+            // Selection bar, with bulk actions for the selected files, which
+            // are also what a partial commit includes
+            .when(selected_count > 0, |this| {
+                let action = |id: &'static str, label: &'static str, color: Rgba| {
+                    div()
+                        .id(id)
+                        .px_2()
+                        .rounded_sm()
+                        .text_color(color)
+                        .cursor_pointer()
+                        .hover(|s| s.bg(theme.surface1))
+                        .child(label)
+                };
+                this.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .justify_between()
+                        .gap_2()
+                        .px_4()
+                        .py_1()
+                        .bg(theme.blue_bg)
+                        .text_xs()
+                        .child(div().text_color(theme.blue).child(format!(
+                            "{} file{} selected",
+                            selected_count,
+                            if selected_count == 1 { "" } else { "s" }
+                        )))
+                        .child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_1()
+                                .when(selected_unstaged, |this| {
+                                    this.child(
+                                        action("stage-selection", "Stage", theme.green).on_click(
+                                            cx.listener(|this, _event, _window, cx| {
+                                                this.stage_selection(cx);
+                                            }),
+                                        ),
+                                    )
+                                })
+                                .when(selected_staged, |this| {
+                                    this.child(
+                                        action("unstage-selection", "Unstage", theme.overlay2)
+                                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                                this.unstage_selection(cx);
+                                            })),
+                                    )
+                                })
+                                .when(selected_discardable, |this| {
+                                    this.child(
+                                        action("discard-selection", "Discard…", theme.red)
+                                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                                this.request_discard_selection(cx);
+                                            })),
+                                    )
+                                })
+                                .child(
+                                    action("clear-file-selection", "Clear", theme.overlay2)
+                                        .on_click(cx.listener(|this, _event, _window, cx| {
+                                            this.clear_selection(cx);
+                                        })),
+                                ),
+                        ),
+                )
+            })
+            // Staging held back for files meant for Git LFS
+            .when_some(lfs_warning, |this, warning| {
+                this.child(self.render_lfs_warning(warning, cx))
+            })
             // Staged section
             .when(!staged_files.is_empty(), |this| {
                 this.child(
@@ -86,22 +665,11 @@ impl Render for FileList {
                                 .py_1()
                                 .text_xs()
                                 .font_weight(FontWeight::SEMIBOLD)
-                                .text_color(rgb(0xa6e3a1))
-                                .bg(rgb(0x181825))
+                                .text_color(theme.green)
+                                .bg(theme.mantle)
                                 .child("Staged"),
                         )
-                        .children(staged_files.into_iter().map(|file| {
-                            let path = file.path.clone();
-                            let path_for_double = path.clone();
-                            self.render_file_item(file, true, cx)
-                                .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
-                                    if event.click_count() == 2 {
-                                        this.show_diff(path_for_double.clone(), window, cx);
-                                    } else {
-                                        this.unstage_file(path.clone(), window, cx);
-                                    }
-                                }))
-                        })),
+                        .children(self.render_section(staged_files, true, cx)),
                 )
             })
             // Unstaged section
@@ -112,26 +680,61 @@ impl Render for FileList {
                         .flex_col()
                         .child(
                             div()
+                                .flex()
+                                .items_center()
+                                .justify_between()
                                 .px_4()
                                 .py_1()
                                 .text_xs()
-                                .font_weight(FontWeight::SEMIBOLD)
-                                .text_color(rgb(0xfab387))
-                                .bg(rgb(0x181825))
-                                .child("Unstaged"),
+                                .bg(theme.mantle)
+                                .child(
+                                    div()
+                                        .font_weight(FontWeight::SEMIBOLD)
+                                        .text_color(theme.peach)
+                                        .child("Unstaged"),
+                                )
+                                .child(
+                                    div()
+                                        .flex()
+                                        .items_center()
+                                        .gap_1()
+                                        .child(
+                                            div()
+                                                .id("review-changes")
+                                                .px_2()
+                                                .rounded_sm()
+                                                .text_color(theme.overlay2)
+                                                .cursor_pointer()
+                                                .hover(|s| {
+                                                    s.bg(theme.surface1).text_color(theme.text)
+                                                })
+                                                .child("Review")
+                                                .on_click(|_event, window, cx| {
+                                                    window.dispatch_action(
+                                                        Box::new(ReviewChanges),
+                                                        cx,
+                                                    );
+                                                }),
+                                        )
+                                        .child(
+                                            div()
+                                                .id("discard-all")
+                                                .px_2()
+                                                .rounded_sm()
+                                                .text_color(theme.red)
+                                                .cursor_pointer()
+                                                .hover(|s| s.bg(theme.surface1))
+                                                .child("Discard all")
+                                                .on_click(|_event, window, cx| {
+                                                    window.dispatch_action(
+                                                        Box::new(DiscardAll),
+                                                        cx,
+                                                    );
+                                                }),
+                                        ),
+                                ),
                         )
-                        .children(unstaged_files.into_iter().map(|file| {
-                            let path = file.path.clone();
-                            let path_for_double = path.clone();
-                            self.render_file_item(file, false, cx)
-                                .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
-                                    if event.click_count() == 2 {
-                                        this.show_diff(path_for_double.clone(), window, cx);
-                                    } else {
-                                        this.stage_file(path.clone(), window, cx);
-                                    }
-                                }))
-                        })),
+                        .children(self.render_section(unstaged_files, false, cx)),
                 )
             })
             // Empty state
@@ -143,22 +746,295 @@ impl Render for FileList {
                         .justify_center()
                         .py_8()
                         .text_sm()
-                        .text_color(rgb(0x6c7086))
+                        .text_color(theme.overlay0)
                         .child("No changes"),
                 )
             })
+            .when(all_filtered, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .py_8()
+                        .text_sm()
+                        .text_color(theme.overlay0)
+                        .child("No files match the filter"),
+                )
+            })
+            // Ignored files
+            .child(
+                div()
+                    .id("toggle-ignored")
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .px_4()
+                    .py_1()
+                    .text_xs()
+                    .bg(theme.mantle)
+                    .cursor_pointer()
+                    .hover(|s| s.bg(theme.surface0))
+                    .child(
+                        div()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.overlay2)
+                            .child("Ignored"),
+                    )
+                    .child(
+                        div()
+                            .text_color(theme.overlay0)
+                            .child(if show_ignored { "Hide" } else { "Show" }),
+                    )
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.toggle_show_ignored(cx);
+                    })),
+            )
+            .when(show_ignored, |this| {
+                this.children(ignored_files.into_iter().map(|path| {
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .px_4()
+                        .py_1()
+                        .child(
+                            div()
+                                .w_5()
+                                .text_xs()
+                                .font_weight(FontWeight::BOLD)
+                                .text_color(theme.overlay0)
+                                .child("I"),
+                        )
+                        .child(
+                            div()
+                                .flex_1()
+                                .text_sm()
+                                .text_color(theme.overlay0)
+                                .text_ellipsis()
+                                .child(path),
+                        )
+                }))
+            })
+            // Context menu
+            .when_some(context_menu, |this, menu| {
+                this.child(self.render_context_menu(menu, cx))
+            })
     }
 }
 
 impl FileList {
+    /// Rows for one section, flat or grouped into directories
+    fn render_section(
+        &self,
+        files: Vec<FileStatus>,
+        is_staged: bool,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        if !self.tree_view {
+            return files
+                .into_iter()
+                .map(|file| self.render_file_row(file, is_staged, None, cx))
+                .collect();
+        }
+
+        let mut rows = Vec::new();
+        self.render_dir_node(&DirNode::build(files), "", 0, is_staged, &mut rows, cx);
+        rows
+    }
+
+    fn render_dir_node(
+        &self,
+        node: &DirNode,
+        path: &str,
+        depth: usize,
+        is_staged: bool,
+        rows: &mut Vec<AnyElement>,
+        cx: &mut Context<Self>,
+    ) {
+        for (name, child) in &node.dirs {
+            let dir = if path.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", path, name)
+            };
+            let collapsed = self.collapsed_dirs.contains(&(is_staged, dir.clone()));
+
+            rows.push(self.render_dir_row(
+                name,
+                &dir,
+                child.file_count(),
+                depth,
+                is_staged,
+                collapsed,
+                cx,
+            ));
+            if !collapsed {
+                self.render_dir_node(child, &dir, depth + 1, is_staged, rows, cx);
+            }
+        }
+
+        for file in &node.files {
+            rows.push(self.render_file_row(file.clone(), is_staged, Some(depth), cx));
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_dir_row(
+        &self,
+        name: &str,
+        dir: &str,
+        file_count: usize,
+        depth: usize,
+        is_staged: bool,
+        collapsed: bool,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let theme = *cx.theme();
+        let dir_toggle = dir.to_string();
+        let dir_stage = dir.to_string();
+        let dir_discard = dir.to_string();
+        let section = if is_staged { "staged" } else { "unstaged" };
+
+        div()
+            .id(ElementId::Name(format!("dir-{}-{}", section, dir).into()))
+            .flex()
+            .items_center()
+            .gap_1()
+            .pl(px(16.0 + depth as f32 * TREE_INDENT))
+            .pr_4()
+            .py_1()
+            .cursor_pointer()
+            .hover(|s| s.bg(theme.surface0))
+            .on_click(cx.listener(move |this, _event, _window, cx| {
+                this.toggle_dir(is_staged, dir_toggle.clone(), cx);
+            }))
+            .child(
+                div()
+                    .w_4()
+                    .text_xs()
+                    .text_color(theme.overlay0)
+                    .child(if collapsed { "▸" } else { "▾" }),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .text_sm()
+                    .text_color(theme.overlay2)
+                    .text_ellipsis()
+                    .child(format!("{}/", name)),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.overlay0)
+                    .child(file_count.to_string()),
+            )
+            // Discard the whole directory (unstaged section only)
+            .when(!is_staged, |this| {
+                this.child(
+                    div()
+                        .id(ElementId::Name(format!("dir-discard-{}", dir).into()))
+                        .px_2()
+                        .py_px()
+                        .rounded_sm()
+                        .text_xs()
+                        .text_color(theme.red)
+                        .hover(|s| s.bg(theme.surface1))
+                        .child("↺")
+                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                            cx.stop_propagation();
+                            this.request_discard_directory(dir_discard.clone(), cx);
+                        })),
+                )
+            })
+            // Stage or unstage the whole directory
+            .child(
+                div()
+                    .id(ElementId::Name(format!("dir-stage-{}-{}", section, dir).into()))
+                    .px_2()
+                    .py_px()
+                    .rounded_sm()
+                    .text_xs()
+                    .text_color(theme.overlay2)
+                    .hover(|s| s.bg(theme.surface1).text_color(theme.text))
+                    .child(if is_staged { "−" } else { "+" })
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        cx.stop_propagation();
+                        if is_staged {
+                            this.unstage_directory(dir_stage.clone(), cx);
+                        } else {
+                            this.stage_directory(dir_stage.clone(), cx);
+                        }
+                    })),
+            )
+            .into_any_element()
+    }
+
+    /// A clickable file row. `depth` is set in the tree view, where rows are
+    /// indented and the directory is not repeated.
+    fn render_file_row(
+        &self,
+        file: FileStatus,
+        is_staged: bool,
+        depth: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let path = file.path.clone();
+        let path_for_double = path.clone();
+        let path_menu = path.clone();
+        let is_untracked = file.status == FileStatusType::Untracked;
+        let is_deleted = file.status == FileStatusType::Deleted && !is_staged;
+
+        self.render_file_item(file, is_staged, depth, cx)
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
+                    let menu = FileMenuState {
+                        path: path_menu.clone(),
+                        is_staged,
+                        is_untracked,
+                        is_deleted,
+                        position: event.position,
+                    };
+                    this.show_context_menu(menu, cx);
+                }),
+            )
+            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                if event.modifiers().secondary() {
+                    this.toggle_selection(path.clone(), is_staged, cx);
+                } else if event.modifiers().shift {
+                    this.select_range(path.clone(), is_staged, cx);
+                } else if event.click_count() == 2 {
+                    this.show_diff(path_for_double.clone(), is_staged, window, cx);
+                } else if is_staged {
+                    this.unstage_file(path.clone(), window, cx);
+                } else {
+                    this.stage_file(path.clone(), window, cx);
+                }
+            }))
+            .into_any_element()
+    }
+
     fn render_file_item(
         &self,
         file: FileStatus,
         is_staged: bool,
-        _cx: &mut Context<Self>,
+        depth: Option<usize>,
+        cx: &mut Context<Self>,
     ) -> Stateful<Div> {
+        let theme = *cx.theme();
         let status_char = file.status_char();
-        let status_color = file.status_color();
+        let status_color = theme.file_status_color(file.status);
+        let is_selected = self.git_state.read(cx).is_file_selected(&file.path);
+        let can_discard = !is_staged && file.status != FileStatusType::Untracked;
+        let path_discard = file.path.clone();
+        let path_hover = file.path.clone();
+        let path_preview = file.path.clone();
+        let git_state = self.git_state.clone();
+        let lfs = self.git_state.read(cx).lfs_files.get(&file.path).cloned();
+        // LFS files are usually binary; their badge says enough
+        let binary = lfs.is_none() && self.git_state.read(cx).binary_files.contains(&file.path);
 
         // Get just the filename for display
         let filename = file
@@ -168,8 +1044,8 @@ impl FileList {
             .unwrap_or(&file.path)
             .to_string();
 
-        // Get directory path
-        let dir_path = if file.path.contains('/') {
+        // Get directory path; the tree view shows it as parent rows instead
+        let dir_path = if file.path.contains('/') && depth.is_none() {
             file.path.rsplit_once('/').map(|(dir, _)| dir.to_string())
         } else {
             None
@@ -181,16 +1057,31 @@ impl FileList {
             .items_center()
             .gap_2()
             .px_4()
+            .when_some(depth, |this, depth| {
+                // Line up with the directory name, past the disclosure arrow
+                this.pl(px(16.0 + (depth as f32 + 1.0) * TREE_INDENT))
+            })
             .py_1()
             .cursor_pointer()
-            .hover(|s| s.bg(rgb(0x313244)))
+            .when(is_selected, |this| this.bg(theme.blue_bg))
+            .hover(|s| s.bg(theme.surface0))
+            .on_hover(cx.listener(move |this, hovered: &bool, _window, cx| {
+                if *hovered {
+                    this.preload_hunk_preview(&path_hover, is_staged, cx);
+                }
+            }))
+            .tooltip(move |_window, cx| {
+                let (git_state, path) = (git_state.clone(), path_preview.clone());
+                cx.new(|cx| HunkPreviewTooltip::new(git_state, path, is_staged, cx))
+                    .into()
+            })
             // Status indicator
             .child(
                 div()
                     .w_5()
                     .text_xs()
                     .font_weight(FontWeight::BOLD)
-                    .text_color(rgb(status_color))
+                    .text_color(status_color)
                     .child(status_char.to_string()),
             )
             // File info
@@ -203,7 +1094,7 @@ impl FileList {
                     .child(
                         div()
                             .text_sm()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .text_ellipsis()
                             .child(filename),
                     )
@@ -211,12 +1102,60 @@ impl FileList {
                         this.child(
                             div()
                                 .text_xs()
-                                .text_color(rgb(0x6c7086))
+                                .text_color(theme.overlay0)
                                 .text_ellipsis()
                                 .child(dir),
                         )
                     }),
             )
+            // Stored with Git LFS; a pointer means the content is missing
+            .when_some(lfs, |this, lfs| {
+                let missing = lfs.pointer.is_some() && !lfs.downloaded;
+                this.child(
+                    div()
+                        .flex_shrink_0()
+                        .px_1()
+                        .rounded_sm()
+                        .text_xs()
+                        .bg(if missing { theme.yellow_bg } else { theme.surface0 })
+                        .text_color(if missing { theme.yellow } else { theme.teal })
+                        .child(if missing {
+                            format!("LFS pointer · {}", format_size(lfs.size))
+                        } else {
+                            format!("LFS · {}", format_size(lfs.size))
+                        }),
+                )
+            })
+            .when(binary, |this| {
+                this.child(
+                    div()
+                        .flex_shrink_0()
+                        .px_1()
+                        .rounded_sm()
+                        .text_xs()
+                        .bg(theme.surface0)
+                        .text_color(theme.overlay2)
+                        .child("BIN"),
+                )
+            })
+            // Discard changes (tracked unstaged files only)
+            .when(can_discard, |this| {
+                this.child(
+                    div()
+                        .id(ElementId::Name(format!("file-discard-{}", file.path).into()))
+                        .px_2()
+                        .py_px()
+                        .rounded_sm()
+                        .text_xs()
+                        .text_color(theme.red)
+                        .hover(|s| s.bg(theme.surface1))
+                        .child("↺")
+                        .on_click(cx.listener(move |this, _event, window, cx| {
+                            cx.stop_propagation();
+                            this.discard_file(path_discard.clone(), window, cx);
+                        })),
+                )
+            })
             // Stage/Unstage indicator
             .child(
                 div()
@@ -224,8 +1163,8 @@ impl FileList {
                     .py_px()
                     .rounded_sm()
                     .text_xs()
-                    .text_color(rgb(0x9399b2))
-                    .hover(|s| s.bg(rgb(0x45475a)).text_color(rgb(0xcdd6f4)))
+                    .text_color(theme.overlay2)
+                    .hover(|s| s.bg(theme.surface1).text_color(theme.text))
                     .child(if is_staged { "−" } else { "+" }),
             );
 
@@ -233,6 +1172,256 @@ impl FileList {
     }
 }
 
+impl FileList {
+    fn render_lfs_warning(
+        &self,
+        warning: LfsStagingWarning,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = *cx.theme();
+        let button = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .px_2()
+                .py_px()
+                .rounded_sm()
+                .text_color(theme.overlay2)
+                .cursor_pointer()
+                .hover(|s| s.bg(theme.surface1).text_color(theme.text))
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .bg(theme.yellow_bg)
+            .text_xs()
+            .children(
+                warning
+                    .warnings
+                    .into_iter()
+                    .map(|message| div().text_color(theme.yellow).child(message)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .child(button("stage-with-lfs", "Stage with Git LFS").on_click(
+                        cx.listener(|this, _event, _window, cx| this.stage_with_lfs(cx)),
+                    ))
+                    .child(button("stage-without-lfs", "Stage Anyway").on_click(cx.listener(
+                        |this, _event, _window, cx| this.stage_despite_lfs_warning(cx),
+                    )))
+                    .child(button("dismiss-lfs-warning", "Cancel").on_click(cx.listener(
+                        |this, _event, _window, cx| this.dismiss_lfs_warning(cx),
+                    ))),
+            )
+    }
+
+    fn render_context_menu(&self, menu: FileMenuState, cx: &mut Context<Self>) -> impl IntoElement {
+        let path = menu.path.as_str();
+        let is_staged = menu.is_staged;
+        // Untracked files have no history, and deleted ones nothing to blame
+        let in_head = !menu.is_untracked;
+        let can_discard = !is_staged && !menu.is_untracked;
+
+        let mut items = vec![
+            Self::menu_item(path, "ctx-stage", "Stage", cx, Self::stage_file).disabled(is_staged),
+            Self::menu_item(path, "ctx-unstage", "Unstage", cx, Self::unstage_file)
+                .disabled(!is_staged),
+            Self::menu_item(path, "ctx-discard", "Discard Changes…", cx, Self::discard_file)
+                .danger()
+                .disabled(!can_discard),
+        ];
+        // The same for every change in the file's directory, which the flat
+        // list has no row for
+        if let Some((dir, _)) = path.rsplit_once('/') {
+            items.push(ContextMenuItem::separator());
+            if is_staged {
+                items.push(Self::menu_item(
+                    dir,
+                    "ctx-unstage-dir",
+                    "Unstage Folder",
+                    cx,
+                    |this, dir, _window, cx| this.unstage_directory(dir, cx),
+                ));
+            } else {
+                items.push(Self::menu_item(
+                    dir,
+                    "ctx-stage-dir",
+                    "Stage Folder",
+                    cx,
+                    |this, dir, _window, cx| this.stage_directory(dir, cx),
+                ));
+                items.push(
+                    Self::menu_item(
+                        dir,
+                        "ctx-discard-dir",
+                        "Discard Folder Changes…",
+                        cx,
+                        |this, dir, _window, cx| this.request_discard_directory(dir, cx),
+                    )
+                    .danger(),
+                );
+            }
+        }
+        items.extend([
+            ContextMenuItem::separator(),
+            Self::menu_item(path, "ctx-view-diff", "View Diff", cx, move |this, path, window, cx| {
+                this.show_diff(path, is_staged, window, cx);
+            }),
+            Self::menu_item(path, "ctx-history", "History", cx, |_this, path, window, cx| {
+                window.dispatch_action(Box::new(ShowFileHistory { path }), cx);
+            })
+            .disabled(!in_head),
+            Self::menu_item(path, "ctx-blame", "Blame", cx, |_this, path, window, cx| {
+                window.dispatch_action(Box::new(ShowBlame { path }), cx);
+            })
+            .disabled(menu.is_deleted || !in_head),
+            ContextMenuItem::separator(),
+        ]);
+        if !menu.is_deleted {
+            items.push(Self::menu_item(
+                path,
+                "ctx-open-editor",
+                "Open in Editor",
+                cx,
+                |this, path, _window, cx| this.open_in_editor(path, cx),
+            ));
+            items.push(Self::menu_item(
+                path,
+                "ctx-reveal",
+                reveal_label(),
+                cx,
+                |this, path, _window, cx| this.reveal_in_file_manager(path, cx),
+            ));
+        }
+        items.push(Self::menu_item(
+            path,
+            "ctx-copy-path",
+            "Copy Path",
+            cx,
+            |this, path, _window, cx| this.copy_path(path, cx),
+        ));
+
+        // Ignore rules only make sense for untracked files
+        if menu.is_untracked {
+            items.push(ContextMenuItem::separator());
+            let patterns = IgnorePattern::options_for(path);
+            for (index, pattern) in patterns.into_iter().enumerate() {
+                items.push(ContextMenuItem::new(pattern.label()).on_click(
+                    ElementId::NamedInteger("ctx-ignore".into(), index as u64),
+                    cx.listener(move |this, _event, _window, cx| {
+                        this.ignore(pattern.clone(), cx);
+                    }),
+                ));
+            }
+        }
+
+        ContextMenu::new(items, menu.position).with_header(menu.path.clone())
+    }
+
+    /// Menu item that closes the menu and runs `action` with the file's path
+    fn menu_item(
+        path: &str,
+        id: &'static str,
+        label: &'static str,
+        cx: &Context<Self>,
+        action: impl Fn(&mut Self, String, &mut Window, &mut Context<Self>) + 'static,
+    ) -> ContextMenuItem {
+        let path = path.to_string();
+        ContextMenuItem::new(label).on_click(
+            id,
+            cx.listener(move |this, _event, window, cx| {
+                this.hide_context_menu(cx);
+                action(this, path.clone(), window, cx);
+            }),
+        )
+    }
+}
+
+/// Popover with the first hunk of a hovered file's diff
+struct HunkPreviewTooltip {
+    git_state: Entity<GitState>,
+    path: String,
+    is_staged: bool,
+}
+
+impl HunkPreviewTooltip {
+    fn new(
+        git_state: Entity<GitState>,
+        path: String,
+        is_staged: bool,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        // Re-render once the preview finishes loading
+        cx.observe(&git_state, |_this, _git_state, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            git_state,
+            path,
+            is_staged,
+        }
+    }
+}
+
+impl Render for HunkPreviewTooltip {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let (path, is_staged) = (self.path.clone(), self.is_staged);
+        let preview = self
+            .git_state
+            .update(cx, |state, cx| state.hunk_preview(&path, is_staged, cx));
+
+        let container = div()
+            .max_w(px(520.0))
+            .rounded_md()
+            .bg(theme.mantle)
+            .border_1()
+            .border_color(theme.surface1)
+            .overflow_hidden()
+            .text_xs();
+
+        match preview {
+            HunkPreview::Ready(lines) => container
+                .py_1()
+                .font_family("monospace")
+                .children(lines.into_iter().map(|line| {
+                    let (prefix, color, bg) = match line.line_type {
+                        DiffLineType::Addition => ("+", theme.green, Some(theme.green_bg)),
+                        DiffLineType::Deletion => ("-", theme.red, Some(theme.red_bg)),
+                        DiffLineType::Context => (" ", theme.text, None),
+                        DiffLineType::Header => ("", theme.overlay0, None),
+                    };
+                    div()
+                        .px_2()
+                        .whitespace_nowrap()
+                        .text_ellipsis()
+                        .text_color(color)
+                        .when_some(bg, |this, bg| this.bg(bg))
+                        .child(format!("{}{}", prefix, line.content.trim_end()))
+                })),
+            HunkPreview::Loading => container
+                .px_2()
+                .py_1()
+                .text_color(theme.overlay0)
+                .child("Loading preview…"),
+            HunkPreview::Unavailable => container
+                .px_2()
+                .py_1()
+                .text_color(theme.overlay0)
+                .child("No preview available"),
+        }
+    }
+}
+
 // Keep FileListItem for backward compatibility if needed elsewhere
 #[derive(IntoElement)]
 pub struct FileListItem {
@@ -247,9 +1436,10 @@ impl FileListItem {
 }
 
 impl RenderOnce for FileListItem {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         let status_char = self.file.status_char();
-        let status_color = self.file.status_color();
+        let status_color = theme.file_status_color(self.file.status);
 
         // Get just the filename for display
         let filename = self
@@ -274,14 +1464,14 @@ impl RenderOnce for FileListItem {
             .px_4()
             .py_1()
             .cursor_pointer()
-            .hover(|s| s.bg(rgb(0x313244)))
+            .hover(|s| s.bg(theme.surface0))
             // Status indicator
             .child(
                 div()
                     .w_5()
                     .text_xs()
                     .font_weight(FontWeight::BOLD)
-                    .text_color(rgb(status_color))
+                    .text_color(status_color)
                     .child(status_char.to_string()),
             )
             // File info
@@ -294,7 +1484,7 @@ impl RenderOnce for FileListItem {
                     .child(
                         div()
                             .text_sm()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .text_ellipsis()
                             .child(filename.to_string()),
                     ),
@@ -306,8 +1496,8 @@ impl RenderOnce for FileListItem {
                     .py_px()
                     .rounded_sm()
                     .text_xs()
-                    .text_color(rgb(0x9399b2))
-                    .hover(|s| s.bg(rgb(0x45475a)).text_color(rgb(0xcdd6f4)))
+                    .text_color(theme.overlay2)
+                    .hover(|s| s.bg(theme.surface1).text_color(theme.text))
                     .child(if self.is_staged { "−" } else { "+" }),
             );
 
@@ -315,7 +1505,7 @@ impl RenderOnce for FileListItem {
             base.child(
                 div()
                     .text_xs()
-                    .text_color(rgb(0x6c7086))
+                    .text_color(theme.overlay0)
                     .text_ellipsis()
                     .child(dir),
             )
@@ -0,0 +1,187 @@
+#![allow(dead_code)]
+
+use crate::git::remote::ForcePushPlan;
+use crate::i18n::{time_ago, ActiveLocale};
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Events emitted by the force push confirmation
+#[derive(Clone, Debug)]
+pub enum ForcePushDialogEvent {
+    /// Push, provided the remote branch is still at this SHA
+    Confirmed { expected: Option<String> },
+    Cancelled,
+}
+
+impl EventEmitter<ForcePushDialogEvent> for ForcePushDialog {}
+
+/// Confirms a force push with lease, listing the remote commits it would
+/// overwrite
+pub struct ForcePushDialog {
+    plan: ForcePushPlan,
+}
+
+impl ForcePushDialog {
+    pub fn new(plan: ForcePushPlan, _cx: &mut Context<Self>) -> Self {
+        Self { plan }
+    }
+
+    fn confirm(&mut self, cx: &mut Context<Self>) {
+        cx.emit(ForcePushDialogEvent::Confirmed {
+            expected: self.plan.expected.map(|oid| oid.to_string()),
+        });
+    }
+
+    fn cancel(&mut self, cx: &mut Context<Self>) {
+        cx.emit(ForcePushDialogEvent::Cancelled);
+    }
+}
+
+impl Render for ForcePushDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let locale = cx.locale();
+        let overwritten = self.plan.overwritten.len();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_4()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(theme.text)
+                            .child(format!("Force Push {}?", self.plan.branch)),
+                    )
+                    .child(div().text_sm().text_color(theme.overlay2).child(
+                        if self.plan.expected.is_none() {
+                            format!(
+                                "{} doesn't exist yet; nothing will be overwritten",
+                                self.plan.remote_branch
+                            )
+                        } else if overwritten == 0 {
+                            format!(
+                                "{} has no commits missing from your branch",
+                                self.plan.remote_branch
+                            )
+                        } else {
+                            format!(
+                                "{} commit{} on {} will be overwritten",
+                                overwritten,
+                                if overwritten == 1 { "" } else { "s" },
+                                self.plan.remote_branch
+                            )
+                        },
+                    )),
+            )
+            // Commits that would be lost on the remote
+            .when(overwritten > 0, |this| {
+                this.child(
+                    div()
+                        .id("force-push-commits")
+                        .max_h(px(200.0))
+                        .overflow_y_scroll()
+                        .rounded_md()
+                        .bg(theme.mantle)
+                        .p_2()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .children(self.plan.overwritten.iter().map(|commit| {
+                            let summary = commit.message.lines().next().unwrap_or("").to_string();
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .text_sm()
+                                .child(
+                                    div()
+                                        .flex_shrink_0()
+                                        .font_family("monospace")
+                                        .text_color(theme.red)
+                                        .child(commit.short_sha.clone()),
+                                )
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .truncate()
+                                        .text_color(theme.text)
+                                        .child(summary),
+                                )
+                                .child(
+                                    div()
+                                        .flex_shrink_0()
+                                        .text_xs()
+                                        .text_color(theme.overlay0)
+                                        .child(format!(
+                                            "{}, {}",
+                                            commit.author,
+                                            time_ago(locale, commit.timestamp)
+                                        )),
+                                )
+                        })),
+                )
+            })
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.overlay2)
+                    .child(
+                        "The push is refused if someone pushed to the remote branch since your \
+                         last fetch.",
+                    ),
+            )
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id("force-push-cancel-btn")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("Cancel")
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.cancel(cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("force-push-confirm-btn")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(theme.red)
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.base)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.maroon))
+                            .child("Force Push")
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.confirm(cx);
+                            })),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,281 @@
+#![allow(dead_code)]
+
+use super::settings::SettingsButton;
+use crate::components::{TextInputChanged, TextInputView};
+use crate::i18n::{t, t_with_vars, ActiveLocale, Locale};
+use crate::state::{AuthMode, GitState, SettingsState, TokenStorage};
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Outcome of the last connection test
+#[derive(Clone, Debug)]
+enum ConnectionTest {
+    Running,
+    Connected,
+    Failed(String),
+}
+
+/// Settings section for the credentials used to push, pull and fetch over
+/// HTTPS. Fields are saved when they lose focus.
+pub struct GitAuthView {
+    git_state: Entity<GitState>,
+    settings: Entity<SettingsState>,
+    username_input: Entity<TextInputView>,
+    /// Empty unless a new token is being typed; the saved one is never shown
+    token_input: Entity<TextInputView>,
+    test: Option<ConnectionTest>,
+}
+
+impl GitAuthView {
+    pub fn new(
+        git_state: Entity<GitState>,
+        settings: Entity<SettingsState>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let username = settings.read(cx).data.git_username.clone().unwrap_or_default();
+        let username_input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .with_placeholder(t(cx.locale(), "settings.gitUsernamePlaceholder"))
+                .with_content(username)
+        });
+        let token_input = cx.new(|cx| TextInputView::new(cx).password(true));
+        for input in [&username_input, &token_input] {
+            cx.subscribe(input, |_this, _input, _event: &TextInputChanged, cx| {
+                cx.notify();
+            })
+            .detach();
+        }
+
+        let username_focus = username_input.read(cx).focus_handle(cx);
+        cx.on_blur(&username_focus, window, |this, _window, cx| {
+            this.save_username(cx);
+        })
+        .detach();
+        let token_focus = token_input.read(cx).focus_handle(cx);
+        cx.on_blur(&token_focus, window, |this, _window, cx| {
+            this.save_token(cx);
+        })
+        .detach();
+        // Placeholders aren't re-rendered, so translate them again when the
+        // language changes
+        cx.observe_global::<Locale>(|this, cx| {
+            let placeholder = t(cx.locale(), "settings.gitUsernamePlaceholder");
+            this.username_input
+                .update(cx, |input, cx| input.set_placeholder(placeholder, cx));
+            this.update_token_placeholder(cx);
+        })
+        .detach();
+
+        let view = Self {
+            git_state,
+            settings,
+            username_input,
+            token_input,
+            test: None,
+        };
+        view.update_token_placeholder(cx);
+        view
+    }
+
+    fn save_username(&mut self, cx: &mut Context<Self>) {
+        let username = self.username_input.read(cx).content().trim().to_string();
+        let username = (!username.is_empty()).then_some(username);
+        if self.settings.read(cx).data.git_username != username {
+            self.settings
+                .update(cx, |settings, cx| settings.set_username(username, cx));
+        }
+    }
+
+    fn save_token(&mut self, cx: &mut Context<Self>) {
+        let token = self.token_input.read(cx).content().trim().to_string();
+        if token.is_empty() {
+            return;
+        }
+        self.settings
+            .update(cx, |settings, cx| settings.set_token(Some(token), cx));
+        self.token_input.update(cx, |input, cx| input.set_content("", cx));
+        self.update_token_placeholder(cx);
+    }
+
+    fn forget_token(&mut self, cx: &mut Context<Self>) {
+        self.settings
+            .update(cx, |settings, cx| settings.set_token(None, cx));
+        self.update_token_placeholder(cx);
+    }
+
+    /// Say whether a token is saved, since the field never shows it
+    fn update_token_placeholder(&self, cx: &mut Context<Self>) {
+        let key = if self.settings.read(cx).has_token() {
+            "settings.gitTokenSaved"
+        } else {
+            "settings.gitTokenPlaceholder"
+        };
+        let placeholder = t(cx.locale(), key);
+        self.token_input
+            .update(cx, |input, cx| input.set_placeholder(placeholder, cx));
+    }
+
+    fn set_auth_mode(&mut self, mode: AuthMode, cx: &mut Context<Self>) {
+        self.test = None;
+        self.settings
+            .update(cx, |settings, cx| settings.set_auth_mode(mode, cx));
+    }
+
+    /// Save the fields, then list the default remote's refs with them
+    fn test_connection(&mut self, cx: &mut Context<Self>) {
+        self.save_username(cx);
+        self.save_token(cx);
+        let auth = self.settings.read(cx).get_auth_credentials();
+        let task = self.git_state.read(cx).test_connection(auth, cx);
+        self.test = Some(ConnectionTest::Running);
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                this.test = Some(match result {
+                    Ok(_) => ConnectionTest::Connected,
+                    Err(e) => {
+                        log::warn!("Connection test failed: {:#}", e);
+                        ConnectionTest::Failed(format!("{:#}", e))
+                    }
+                });
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+        cx.notify();
+    }
+
+    /// Label on the left, control on the right
+    fn render_row(label: String, cx: &App) -> Div {
+        let theme = *cx.theme();
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .child(div().text_sm().text_color(theme.overlay2).child(label))
+    }
+}
+
+impl Render for GitAuthView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let locale = cx.locale();
+        let settings = self.settings.read(cx);
+        let auth_mode = settings.data.git_auth_mode;
+        let has_token = settings.has_token();
+        let token_storage = settings.token_storage;
+        let credential_store = settings.credential_store_name();
+        let remote = self.git_state.read(cx).remote_name.clone();
+        let https = auth_mode == AuthMode::Https;
+        let modes = [(AuthMode::Https, "auth.https"), (AuthMode::Ssh, "auth.ssh")];
+
+        let test_status = self.test.clone().map(|test| match test {
+            ConnectionTest::Running => (
+                t_with_vars(locale, "settings.gitTestRunning", &[("remote", &remote)]),
+                theme.overlay0,
+            ),
+            ConnectionTest::Connected => (
+                t_with_vars(locale, "settings.gitTestSuccess", &[("remote", &remote)]),
+                theme.green,
+            ),
+            ConnectionTest::Failed(error) => (error, theme.red),
+        });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.blue)
+                    .child(t(locale, "settings.gitAuth")),
+            )
+            // Auth mode selector
+            .child(
+                Self::render_row(t(locale, "settings.gitAuthMethod"), cx).child(
+                    div().flex().gap_2().children(modes.map(|(mode, key)| {
+                        SettingsButton::new(t(locale, key), auth_mode == mode)
+                            .id(ElementId::Name(format!("git-auth-{}", key).into()))
+                            .on_click(cx.listener(move |this, _event, _window, cx| {
+                                this.set_auth_mode(mode, cx);
+                            }))
+                    })),
+                ),
+            )
+            .when(https, |this| {
+                this.child(
+                    Self::render_row(t(locale, "settings.gitUsername"), cx)
+                        .child(div().w(px(280.0)).child(self.username_input.clone())),
+                )
+                .child(
+                    Self::render_row(t(locale, "settings.gitToken"), cx)
+                        .child(div().w(px(280.0)).child(self.token_input.clone())),
+                )
+            })
+            // Token storage
+            .when(https && has_token, |this| {
+                this.child(
+                    Self::render_row(t(locale, "settings.gitTokenStorage"), cx).child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(match token_storage {
+                                        TokenStorage::SettingsFile => theme.yellow,
+                                        _ => theme.green,
+                                    })
+                                    .child(match token_storage {
+                                        TokenStorage::SettingsFile => {
+                                            t(locale, "settings.gitTokenPlaintext")
+                                        }
+                                        _ => credential_store.to_string(),
+                                    }),
+                            )
+                            .child(
+                                SettingsButton::new(t(locale, "settings.gitTokenForget"), false)
+                                    .id("forget-git-token")
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.forget_token(cx);
+                                    })),
+                            ),
+                    ),
+                )
+            })
+            .when(https, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_3()
+                        .child(
+                            SettingsButton::new(t(locale, "settings.gitTestConnection"), false)
+                                .id("test-git-connection")
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.test_connection(cx);
+                                })),
+                        )
+                        .when_some(test_status, |this, (status, color)| {
+                            this.child(div().flex_1().text_xs().text_color(color).child(status))
+                        }),
+                )
+            })
+            // SSH info
+            .when(!https, |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.overlay0)
+                        .child("SSH authentication uses the system SSH agent"),
+                )
+            })
+    }
+}
@@ -0,0 +1,204 @@
+#![allow(dead_code)]
+
+use super::settings::SettingsButton;
+use crate::components::{TextInputChanged, TextInputView};
+use crate::git::{Identity, IdentityScope};
+use crate::i18n::{t, ActiveLocale};
+use crate::state::{GitState, SettingsState};
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Settings section editing `user.name` and `user.email` in the global git
+/// config or the open repository's config
+pub struct GitIdentityView {
+    git_state: Entity<GitState>,
+    settings: Entity<SettingsState>,
+    scope: IdentityScope,
+    name_input: Entity<TextInputView>,
+    email_input: Entity<TextInputView>,
+    /// Values last read from or written to the config, to tell when the
+    /// fields have unsaved changes
+    saved: Identity,
+    error: Option<String>,
+}
+
+impl GitIdentityView {
+    pub fn new(
+        git_state: Entity<GitState>,
+        settings: Entity<SettingsState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let scope = IdentityScope::Global;
+        let (saved, error) = match git_state.read(cx).git_identity(scope) {
+            Ok(identity) => (identity, None),
+            Err(e) => (Identity::default(), Some(e.to_string())),
+        };
+        let name_input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .with_placeholder("Jane Doe")
+                .with_content(saved.name.clone().unwrap_or_default())
+        });
+        let email_input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .with_placeholder("jane@example.com")
+                .with_content(saved.email.clone().unwrap_or_default())
+        });
+        for input in [&name_input, &email_input] {
+            cx.subscribe(input, |_this, _input, _event: &TextInputChanged, cx| {
+                cx.notify();
+            })
+            .detach();
+        }
+
+        Self {
+            git_state,
+            settings,
+            scope,
+            name_input,
+            email_input,
+            saved,
+            error,
+        }
+    }
+
+    /// Fill the fields from `scope`'s config, dropping unsaved edits
+    fn load(&mut self, scope: IdentityScope, cx: &mut Context<Self>) {
+        self.scope = scope;
+        match self.git_state.read(cx).git_identity(scope) {
+            Ok(identity) => {
+                self.saved = identity;
+                self.error = None;
+            }
+            Err(e) => {
+                self.saved = Identity::default();
+                self.error = Some(e.to_string());
+            }
+        }
+        let (name, email) = (
+            self.saved.name.clone().unwrap_or_default(),
+            self.saved.email.clone().unwrap_or_default(),
+        );
+        self.name_input.update(cx, |input, cx| input.set_content(name, cx));
+        self.email_input.update(cx, |input, cx| input.set_content(email, cx));
+        cx.notify();
+    }
+
+    fn edited(&self, cx: &App) -> Identity {
+        Identity::new(
+            self.name_input.read(cx).content(),
+            self.email_input.read(cx).content(),
+        )
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        let identity = self.edited(cx);
+        let scope = self.scope;
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.set_git_identity(&identity, scope, cx));
+        match result {
+            Ok(()) => {
+                self.saved = identity;
+                self.error = None;
+            }
+            Err(e) => {
+                log::error!("Failed to save git identity: {}", e);
+                self.error = Some(e.to_string());
+            }
+        }
+        cx.notify();
+    }
+}
+
+impl Render for GitIdentityView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let locale = cx.locale();
+        let has_repo = self.git_state.read(cx).path.is_some();
+        let dirty = self.edited(cx) != self.saved;
+        let scope = self.scope;
+
+        let row = |label: String, input: Entity<TextInputView>| {
+            div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .gap_3()
+                .child(
+                    div()
+                        .flex_shrink_0()
+                        .text_sm()
+                        .text_color(theme.overlay2)
+                        .child(label),
+                )
+                .child(div().w_48().child(input))
+        };
+
+        let scope_buttons = [
+            (IdentityScope::Global, "settings.gitIdentityGlobal"),
+            (IdentityScope::Repository, "settings.gitIdentityRepository"),
+        ]
+        .into_iter()
+        .filter(|(s, _)| has_repo || *s == IdentityScope::Global)
+        .map(|(s, key)| {
+            SettingsButton::new(t(locale, key), scope == s)
+                .id(key)
+                .on_click(cx.listener(move |this, _event, _window, cx| this.load(s, cx)))
+        });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.blue)
+                    .child(t(locale, "settings.gitIdentity")),
+            )
+            .child(div().flex().gap_1().children(scope_buttons))
+            .child(row(t(locale, "settings.repoName"), self.name_input.clone()))
+            .child(row(t(locale, "settings.repoEmail"), self.email_input.clone()))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_3()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(if self.error.is_some() {
+                                theme.red
+                            } else {
+                                theme.overlay0
+                            })
+                            .child(match &self.error {
+                                Some(error) => error.clone(),
+                                None => t(locale, "settings.gitIdentityHint"),
+                            }),
+                    )
+                    .child(
+                        div()
+                            .id("save-git-identity")
+                            .flex_shrink_0()
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .bg(if dirty { theme.blue } else { theme.surface0 })
+                            .text_color(if dirty { theme.base } else { theme.overlay0 })
+                            .child(t(locale, "common.save"))
+                            .when(dirty, |this| {
+                                this.cursor_pointer()
+                                    .hover(|s| s.bg(theme.lavender))
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.save(cx)
+                                    }))
+                            }),
+                    ),
+            )
+    }
+}
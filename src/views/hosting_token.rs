@@ -0,0 +1,130 @@
+#![allow(dead_code)]
+
+use super::settings::SettingsButton;
+use crate::components::{TextInputChanged, TextInputView};
+use crate::i18n::{t, ActiveLocale};
+use crate::state::SettingsState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Settings section for the GitHub/GitLab API token used to show pull
+/// requests
+pub struct HostingTokenView {
+    settings: Entity<SettingsState>,
+    token_input: Entity<TextInputView>,
+}
+
+impl HostingTokenView {
+    pub fn new(settings: Entity<SettingsState>, cx: &mut Context<Self>) -> Self {
+        let token_input = cx.new(|cx| {
+            TextInputView::new(cx).with_placeholder("GitHub or GitLab personal access token")
+        });
+        cx.subscribe(&token_input, |_this, _input, _event: &TextInputChanged, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            settings,
+            token_input,
+        }
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        let token = self.token_input.read(cx).content().trim().to_string();
+        if token.is_empty() {
+            return;
+        }
+        self.settings
+            .update(cx, |settings, cx| settings.set_hosting_token(Some(token), cx));
+        self.token_input.update(cx, |input, cx| input.set_content("", cx));
+        cx.notify();
+    }
+
+    fn forget(&mut self, cx: &mut Context<Self>) {
+        self.settings
+            .update(cx, |settings, cx| settings.set_hosting_token(None, cx));
+        cx.notify();
+    }
+}
+
+impl Render for HostingTokenView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let settings = self.settings.read(cx);
+        let locale = cx.locale();
+        let has_token = settings.hosting_token().is_some();
+        let can_save = !self.token_input.read(cx).content().trim().is_empty();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.blue)
+                    .child(t(locale, "settings.hostingToken")),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_3()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(if has_token { theme.green } else { theme.overlay0 })
+                            .child(if has_token {
+                                "••••••••".to_string()
+                            } else {
+                                t(locale, "settings.hostingTokenNone")
+                            }),
+                    )
+                    .when(has_token, |this| {
+                        this.child(
+                            SettingsButton::new(t(locale, "settings.gitTokenForget"), false)
+                                .id("forget-hosting-token")
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.forget(cx);
+                                })),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().flex_1().child(self.token_input.clone()))
+                    .child(
+                        div()
+                            .id("save-hosting-token")
+                            .flex_shrink_0()
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .bg(if can_save { theme.blue } else { theme.surface0 })
+                            .text_color(if can_save { theme.base } else { theme.overlay0 })
+                            .child(t(locale, "common.save"))
+                            .when(can_save, |this| {
+                                this.cursor_pointer()
+                                    .hover(|s| s.bg(theme.lavender))
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.save(cx)
+                                    }))
+                            }),
+                    ),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.overlay0)
+                    .child(t(locale, "settings.hostingTokenHint")),
+            )
+    }
+}
@@ -0,0 +1,163 @@
+#![allow(dead_code)]
+
+use crate::actions::ShowConflictDialog;
+use crate::git::InterruptedKind;
+use crate::state::GitState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Shown above the panels while the repository is in the middle of a merge,
+/// rebase, bisect or `git am`, so a repository opened in that state offers
+/// a way out instead of only listing its files
+pub struct InterruptedOperationBanner {
+    git_state: Entity<GitState>,
+    /// Error from the last action
+    error: Option<String>,
+}
+
+impl InterruptedOperationBanner {
+    pub fn new(git_state: Entity<GitState>, _cx: &mut Context<Self>) -> Self {
+        Self {
+            git_state,
+            error: None,
+        }
+    }
+
+    fn commit(&mut self, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.commit_interrupted_operation(cx));
+        self.finish(result, "commit merge", cx);
+    }
+
+    fn abort(&mut self, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.abort_interrupted_operation(cx));
+        self.finish(result, "abort", cx);
+    }
+
+    fn finish(&mut self, result: anyhow::Result<()>, action: &str, cx: &mut Context<Self>) {
+        self.error = match result {
+            Ok(()) => None,
+            Err(e) => {
+                log::error!("Failed to {}: {}", action, e);
+                Some(e.to_string())
+            }
+        };
+        cx.notify();
+    }
+}
+
+impl Render for InterruptedOperationBanner {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let git_state = self.git_state.read(cx);
+        let Some(operation) = git_state.interrupted_operation.clone() else {
+            return div();
+        };
+        let has_conflicts = git_state.conflict_info.is_some();
+        let can_commit = operation.can_commit() && !has_conflicts;
+
+        let title = match (&operation.kind, &operation.head_name) {
+            (InterruptedKind::Rebase, Some(branch)) => {
+                format!("Rebasing {} is in progress", branch)
+            }
+            (InterruptedKind::Bisect, Some(start)) => {
+                format!("Bisecting is in progress, started from {}", start)
+            }
+            _ => format!("{} is in progress", operation.label()),
+        };
+        let detail = match operation.kind {
+            InterruptedKind::Merge if has_conflicts => {
+                "Resolve the conflicts, then commit the merge, or abort it."
+            }
+            InterruptedKind::Merge => "Conflicts resolved. Commit the merge, or abort it.",
+            InterruptedKind::Rebase | InterruptedKind::ApplyMailbox => {
+                "Continue it with the git command line, or abort to go back to where it started."
+            }
+            InterruptedKind::Bisect => {
+                "Mark commits with the git command line, or reset to go back to where it started."
+            }
+        };
+
+        let button = |id: &'static str, label: &'static str, enabled: bool| {
+            div()
+                .id(id)
+                .flex_shrink_0()
+                .px_3()
+                .py_1()
+                .rounded_md()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .bg(if enabled { theme.yellow } else { theme.surface1 })
+                .text_color(if enabled { theme.base } else { theme.overlay0 })
+                .when(enabled, |this| {
+                    this.cursor_pointer().hover(|s| s.opacity(0.9))
+                })
+                .child(label)
+        };
+        let abort_label = match operation.kind {
+            InterruptedKind::Bisect => "Reset",
+            _ => "Abort",
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .bg(theme.yellow_bg)
+            .border_b_1()
+            .border_color(theme.yellow)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_col()
+                            .text_xs()
+                            .child(
+                                div()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(theme.yellow)
+                                    .child(title),
+                            )
+                            .child(div().text_color(theme.text).child(detail)),
+                    )
+                    .when(has_conflicts, |this| {
+                        this.child(
+                            button("operation-resolve", "Resolve Conflicts", true).on_click(
+                                |_event, window, cx| {
+                                    window.dispatch_action(Box::new(ShowConflictDialog), cx);
+                                },
+                            ),
+                        )
+                    })
+                    .when(operation.can_commit(), |this| {
+                        this.child(button("operation-commit", "Commit Merge", can_commit).when(
+                            can_commit,
+                            |this| {
+                                this.on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.commit(cx);
+                                }))
+                            },
+                        ))
+                    })
+                    .child(button("operation-abort", abort_label, true).on_click(cx.listener(
+                        |this, _event, _window, cx| {
+                            this.abort(cx);
+                        },
+                    ))),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_xs().text_color(theme.red).child(error))
+            })
+    }
+}
@@ -1,4 +1,7 @@
-use crate::state::{GitState, SettingsState};
+use crate::actions::{Fetch, Pull, Push, StashSave};
+use crate::git::{FileStatusType, PreviewOperation};
+use crate::state::{DraftState, GitState, SessionState, SettingsState};
+use crate::theme::ActiveTheme;
 use crate::views::{CommitForm, FileList};
 use gpui::prelude::*;
 use gpui::*;
@@ -6,10 +9,13 @@ use gpui::*;
 pub struct LeftPanel {
     git_state: Entity<GitState>,
     settings: Option<Entity<SettingsState>>,
+    session: Option<Entity<SessionState>>,
     commit_form: Entity<CommitForm>,
     file_list: Entity<FileList>,
     /// Whether stash section is expanded
     stash_expanded: bool,
+    /// Whether the file list is grouped by directory
+    file_tree_view: bool,
 }
 
 impl LeftPanel {
@@ -26,9 +32,11 @@ impl LeftPanel {
         Self {
             git_state,
             settings: None,
+            session: None,
             commit_form,
             file_list,
             stash_expanded: false,
+            file_tree_view: false,
         }
     }
 
@@ -37,48 +45,57 @@ impl LeftPanel {
         self
     }
 
-    fn toggle_stash_expanded(&mut self, cx: &mut Context<Self>) {
-        self.stash_expanded = !self.stash_expanded;
-        cx.notify();
+    /// Restore and persist panel state through the session
+    pub fn with_session(mut self, session: Entity<SessionState>, cx: &mut App) -> Self {
+        self.stash_expanded = session.read(cx).data.stash_expanded;
+        self.file_tree_view = session.read(cx).data.file_tree_view;
+        let tree_view = self.file_tree_view;
+        self.file_list
+            .update(cx, |file_list, cx| file_list.set_tree_view(tree_view, cx));
+        self.session = Some(session);
+        self
     }
 
-    fn handle_push(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
-        let auth = self.settings.as_ref().and_then(|s| {
-            let settings = s.read(cx);
-            settings.get_auth_credentials()
-        });
+    /// Keep the commit message draft for crash recovery
+    pub fn with_drafts(self, drafts: Entity<DraftState>, cx: &mut App) -> Self {
+        self.commit_form
+            .update(cx, |commit_form, cx| commit_form.set_drafts(drafts, cx));
+        self
+    }
 
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.push(auth.as_ref(), cx) {
-                log::error!("Failed to push: {}", e);
-            }
-        });
+    fn toggle_file_tree_view(&mut self, cx: &mut Context<Self>) {
+        self.file_tree_view = !self.file_tree_view;
+        let tree_view = self.file_tree_view;
+        self.file_list
+            .update(cx, |file_list, cx| file_list.set_tree_view(tree_view, cx));
+        if let Some(session) = &self.session {
+            session.update(cx, |session, cx| session.set_file_tree_view(tree_view, cx));
+        }
+        cx.notify();
     }
 
-    fn handle_pull(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
-        let auth = self.settings.as_ref().and_then(|s| {
-            let settings = s.read(cx);
-            settings.get_auth_credentials()
-        });
+    fn toggle_stash_expanded(&mut self, cx: &mut Context<Self>) {
+        self.stash_expanded = !self.stash_expanded;
+        if let Some(session) = &self.session {
+            let expanded = self.stash_expanded;
+            session.update(cx, |session, cx| session.set_stash_expanded(expanded, cx));
+        }
+        cx.notify();
+    }
 
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.pull(auth.as_ref(), cx) {
-                log::error!("Failed to pull: {}", e);
-            }
-        });
+    fn handle_push(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Handled by the app so auth failures can prompt for new credentials
+        window.dispatch_action(Box::new(Push), cx);
     }
 
-    fn handle_fetch(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
-        let auth = self.settings.as_ref().and_then(|s| {
-            let settings = s.read(cx);
-            settings.get_auth_credentials()
-        });
+    fn handle_pull(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Handled by the app so auth failures can prompt for new credentials
+        window.dispatch_action(Box::new(Pull), cx);
+    }
 
-        self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.fetch(auth.as_ref(), cx) {
-                log::error!("Failed to fetch: {}", e);
-            }
-        });
+    fn handle_fetch(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Handled by the app so auth failures can prompt for new credentials
+        window.dispatch_action(Box::new(Fetch), cx);
     }
 
     fn handle_stage_all(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
@@ -97,14 +114,20 @@ impl LeftPanel {
         });
     }
 
-    fn handle_stash_save(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+    /// Cleaning is only offered through its preview, which lists what gets deleted
+    fn handle_preview_clean(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
         self.git_state.update(cx, |state, cx| {
-            if let Err(e) = state.stash_save(None, cx) {
-                log::error!("Failed to save stash: {}", e);
+            if let Err(e) = state.preview_operation(PreviewOperation::Clean, cx) {
+                log::error!("Failed to preview clean: {}", e);
             }
         });
     }
 
+    fn handle_stash_save(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // The app opens the stash dialog for message and options
+        window.dispatch_action(Box::new(StashSave), cx);
+    }
+
     fn handle_stash_pop(&mut self, index: usize, _window: &mut Window, cx: &mut Context<Self>) {
         self.git_state.update(cx, |state, cx| {
             if let Err(e) = state.stash_pop(index, cx) {
@@ -121,6 +144,11 @@ impl LeftPanel {
         });
     }
 
+    fn handle_stash_onto_branch(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.git_state
+            .update(cx, |state, cx| state.request_stash_onto_branch(index, cx));
+    }
+
     fn handle_stash_drop(&mut self, index: usize, _window: &mut Window, cx: &mut Context<Self>) {
         self.git_state.update(cx, |state, cx| {
             if let Err(e) = state.stash_drop(index, cx) {
@@ -132,11 +160,18 @@ impl LeftPanel {
 
 impl Render for LeftPanel {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
         let git_state_read = self.git_state.read(cx);
         let staged_count = git_state_read.staged_files().len();
         let unstaged_count = git_state_read.unstaged_files().len();
+        let untracked_count = git_state_read
+            .files
+            .iter()
+            .filter(|f| f.status == FileStatusType::Untracked)
+            .count();
         let stashes = git_state_read.stashes.clone();
         let stash_expanded = self.stash_expanded;
+        let is_fetching = git_state_read.is_fetching;
 
         div()
             .flex()
@@ -150,7 +185,7 @@ impl Render for LeftPanel {
                     .flex_col()
                     .p_4()
                     .border_b_1()
-                    .border_color(rgb(0x313244))
+                    .border_color(theme.surface0)
                     .child(self.commit_form.clone()),
             )
             // File List Header
@@ -161,13 +196,38 @@ impl Render for LeftPanel {
                     .justify_between()
                     .px_4()
                     .py_2()
-                    .bg(rgb(0x181825))
+                    .bg(theme.mantle)
                     .child(
                         div()
-                            .text_sm()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0xcdd6f4))
-                            .child("Changes"),
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(theme.text)
+                                    .child("Changes"),
+                            )
+                            // Flat list / directory tree toggle
+                            .child(
+                                div()
+                                    .id("file-tree-toggle")
+                                    .px_1()
+                                    .rounded_sm()
+                                    .text_xs()
+                                    .text_color(if self.file_tree_view {
+                                        theme.blue
+                                    } else {
+                                        theme.overlay0
+                                    })
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0))
+                                    .child(if self.file_tree_view { "Tree" } else { "List" })
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.toggle_file_tree_view(cx);
+                                    })),
+                            ),
                     )
                     .child(
                         div()
@@ -183,15 +243,33 @@ impl Render for LeftPanel {
                                         .py_px()
                                         .rounded_sm()
                                         .text_xs()
-                                        .text_color(rgb(0xa6e3a1))
+                                        .text_color(theme.green)
                                         .cursor_pointer()
-                                        .hover(|s| s.bg(rgb(0x313244)))
+                                        .hover(|s| s.bg(theme.surface0))
                                         .child("+All")
                                         .on_click(cx.listener(|this, _event, window, cx| {
                                             this.handle_stage_all(window, cx);
                                         })),
                                 )
                             })
+                            // Clean untracked files (opens a preview)
+                            .when(untracked_count > 0, |this| {
+                                this.child(
+                                    div()
+                                        .id("clean-btn")
+                                        .px_2()
+                                        .py_px()
+                                        .rounded_sm()
+                                        .text_xs()
+                                        .text_color(theme.red)
+                                        .cursor_pointer()
+                                        .hover(|s| s.bg(theme.surface0))
+                                        .child("Clean…")
+                                        .on_click(cx.listener(|this, _event, window, cx| {
+                                            this.handle_preview_clean(window, cx);
+                                        })),
+                                )
+                            })
                             // Unstage All button
                             .when(staged_count > 0, |this| {
                                 this.child(
@@ -201,9 +279,9 @@ impl Render for LeftPanel {
                                         .py_px()
                                         .rounded_sm()
                                         .text_xs()
-                                        .text_color(rgb(0xfab387))
+                                        .text_color(theme.peach)
                                         .cursor_pointer()
-                                        .hover(|s| s.bg(rgb(0x313244)))
+                                        .hover(|s| s.bg(theme.surface0))
                                         .child("-All")
                                         .on_click(cx.listener(|this, _event, window, cx| {
                                             this.handle_unstage_all(window, cx);
@@ -216,9 +294,9 @@ impl Render for LeftPanel {
                                         .px_2()
                                         .py_px()
                                         .rounded_sm()
-                                        .bg(rgb(0xa6e3a1))
+                                        .bg(theme.green)
                                         .text_xs()
-                                        .text_color(rgb(0x1e1e2e))
+                                        .text_color(theme.base)
                                         .child(format!("{} staged", staged_count)),
                                 )
                             })
@@ -228,9 +306,9 @@ impl Render for LeftPanel {
                                         .px_2()
                                         .py_px()
                                         .rounded_sm()
-                                        .bg(rgb(0xfab387))
+                                        .bg(theme.peach)
                                         .text_xs()
-                                        .text_color(rgb(0x1e1e2e))
+                                        .text_color(theme.base)
                                         .child(format!("{} unstaged", unstaged_count)),
                                 )
                             }),
@@ -250,7 +328,7 @@ impl Render for LeftPanel {
                     .flex()
                     .flex_col()
                     .border_t_1()
-                    .border_color(rgb(0x313244))
+                    .border_color(theme.surface0)
                     // Stash Header
                     .child(
                         div()
@@ -260,9 +338,9 @@ impl Render for LeftPanel {
                             .justify_between()
                             .px_4()
                             .py_2()
-                            .bg(rgb(0x181825))
+                            .bg(theme.mantle)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x1e1e2e)))
+                            .hover(|s| s.bg(theme.base))
                             .on_click(cx.listener(|this, _event, _window, cx| {
                                 this.toggle_stash_expanded(cx);
                             }))
@@ -274,14 +352,14 @@ impl Render for LeftPanel {
                                     .child(
                                         div()
                                             .text_xs()
-                                            .text_color(rgb(0x9399b2))
+                                            .text_color(theme.overlay2)
                                             .child(if stash_expanded { "▼" } else { "▶" }),
                                     )
                                     .child(
                                         div()
                                             .text_sm()
                                             .font_weight(FontWeight::SEMIBOLD)
-                                            .text_color(rgb(0xcdd6f4))
+                                            .text_color(theme.text)
                                             .child("Stashes"),
                                     )
                                     .when(!stashes.is_empty(), |this| {
@@ -290,9 +368,9 @@ impl Render for LeftPanel {
                                                 .px_2()
                                                 .py_px()
                                                 .rounded_sm()
-                                                .bg(rgb(0xcba6f7))
+                                                .bg(theme.mauve)
                                                 .text_xs()
-                                                .text_color(rgb(0x1e1e2e))
+                                                .text_color(theme.base)
                                                 .child(format!("{}", stashes.len())),
                                         )
                                     }),
@@ -305,9 +383,9 @@ impl Render for LeftPanel {
                                     .py_1()
                                     .rounded_sm()
                                     .text_xs()
-                                    .text_color(rgb(0xcba6f7))
+                                    .text_color(theme.mauve)
                                     .cursor_pointer()
-                                    .hover(|s| s.bg(rgb(0x313244)))
+                                    .hover(|s| s.bg(theme.surface0))
                                     .child("+ Stash")
                                     .on_click(cx.listener(|this, _event, window, cx| {
                                         this.handle_stash_save(window, cx);
@@ -327,6 +405,7 @@ impl Render for LeftPanel {
                                     let stash_idx = stash.index;
                                     let stash_idx_pop = stash_idx;
                                     let stash_idx_apply = stash_idx;
+                                    let stash_idx_branch = stash_idx;
                                     let stash_idx_drop = stash_idx;
                                     div()
                                         .id(ElementId::Name(format!("stash-{}", idx).into()))
@@ -336,8 +415,8 @@ impl Render for LeftPanel {
                                         .px_4()
                                         .py_2()
                                         .border_t_1()
-                                        .border_color(rgb(0x313244))
-                                        .hover(|s| s.bg(rgb(0x313244)))
+                                        .border_color(theme.surface0)
+                                        .hover(|s| s.bg(theme.surface0))
                                         // Stash info
                                         .child(
                                             div()
@@ -348,13 +427,13 @@ impl Render for LeftPanel {
                                                 .child(
                                                     div()
                                                         .text_xs()
-                                                        .text_color(rgb(0x9399b2))
+                                                        .text_color(theme.overlay2)
                                                         .child(format!("stash@{{{}}}", stash.index)),
                                                 )
                                                 .child(
                                                     div()
                                                         .text_sm()
-                                                        .text_color(rgb(0xcdd6f4))
+                                                        .text_color(theme.text)
                                                         .text_ellipsis()
                                                         .child(stash.message.clone()),
                                                 ),
@@ -373,9 +452,9 @@ impl Render for LeftPanel {
                                                         .py_1()
                                                         .rounded_sm()
                                                         .text_xs()
-                                                        .text_color(rgb(0xa6e3a1))
+                                                        .text_color(theme.green)
                                                         .cursor_pointer()
-                                                        .hover(|s| s.bg(rgb(0x45475a)))
+                                                        .hover(|s| s.bg(theme.surface1))
                                                         .child("Pop")
                                                         .on_click(cx.listener(move |this, _event, window, cx| {
                                                             this.handle_stash_pop(stash_idx_pop, window, cx);
@@ -389,14 +468,30 @@ impl Render for LeftPanel {
                                                         .py_1()
                                                         .rounded_sm()
                                                         .text_xs()
-                                                        .text_color(rgb(0x89b4fa))
+                                                        .text_color(theme.blue)
                                                         .cursor_pointer()
-                                                        .hover(|s| s.bg(rgb(0x45475a)))
+                                                        .hover(|s| s.bg(theme.surface1))
                                                         .child("Apply")
                                                         .on_click(cx.listener(move |this, _event, window, cx| {
                                                             this.handle_stash_apply(stash_idx_apply, window, cx);
                                                         })),
                                                 )
+                                                // Apply onto another branch
+                                                .child(
+                                                    div()
+                                                        .id(ElementId::Name(format!("stash-branch-{}", idx).into()))
+                                                        .px_2()
+                                                        .py_1()
+                                                        .rounded_sm()
+                                                        .text_xs()
+                                                        .text_color(theme.mauve)
+                                                        .cursor_pointer()
+                                                        .hover(|s| s.bg(theme.surface1))
+                                                        .child("To Branch…")
+                                                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                                                            this.handle_stash_onto_branch(stash_idx_branch, cx);
+                                                        })),
+                                                )
                                                 // Drop
                                                 .child(
                                                     div()
@@ -405,9 +500,9 @@ impl Render for LeftPanel {
                                                         .py_1()
                                                         .rounded_sm()
                                                         .text_xs()
-                                                        .text_color(rgb(0xf38ba8))
+                                                        .text_color(theme.red)
                                                         .cursor_pointer()
-                                                        .hover(|s| s.bg(rgb(0x45475a)))
+                                                        .hover(|s| s.bg(theme.surface1))
                                                         .child("Drop")
                                                         .on_click(cx.listener(move |this, _event, window, cx| {
                                                             this.handle_stash_drop(stash_idx_drop, window, cx);
@@ -426,7 +521,7 @@ impl Render for LeftPanel {
                     .gap_2()
                     .p_4()
                     .border_t_1()
-                    .border_color(rgb(0x313244))
+                    .border_color(theme.surface0)
                     // Push button
                     .child(
                         div()
@@ -437,11 +532,11 @@ impl Render for LeftPanel {
                             .justify_center()
                             .py_2()
                             .rounded_md()
-                            .bg(rgb(0x313244))
+                            .bg(theme.surface0)
                             .text_sm()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x45475a)))
+                            .hover(|s| s.bg(theme.surface1))
                             .child("Push")
                             .on_click(cx.listener(|this, _event, window, cx| {
                                 this.handle_push(window, cx);
@@ -457,11 +552,11 @@ impl Render for LeftPanel {
                             .justify_center()
                             .py_2()
                             .rounded_md()
-                            .bg(rgb(0x313244))
+                            .bg(theme.surface0)
                             .text_sm()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x45475a)))
+                            .hover(|s| s.bg(theme.surface1))
                             .child("Pull")
                             .on_click(cx.listener(|this, _event, window, cx| {
                                 this.handle_pull(window, cx);
@@ -477,12 +572,12 @@ impl Render for LeftPanel {
                             .justify_center()
                             .py_2()
                             .rounded_md()
-                            .bg(rgb(0x313244))
+                            .bg(theme.surface0)
                             .text_sm()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x45475a)))
-                            .child("Fetch")
+                            .hover(|s| s.bg(theme.surface1))
+                            .child(if is_fetching { "Fetching..." } else { "Fetch" })
                             .on_click(cx.listener(|this, _event, window, cx| {
                                 this.handle_fetch(window, cx);
                             })),
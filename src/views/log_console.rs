@@ -0,0 +1,266 @@
+#![allow(dead_code)]
+
+use crate::components::{TextInputChanged, TextInputView};
+use crate::state::{LogEntry, LogState};
+use crate::theme::{ActiveTheme, Theme};
+use gpui::prelude::*;
+use gpui::*;
+use log::Level;
+
+/// Most recent matching entries rendered at once
+const MAX_VISIBLE: usize = 500;
+
+/// Emitted when the logging console should be closed
+#[derive(Clone, Debug)]
+pub struct LogConsoleDismissed;
+
+impl EventEmitter<LogConsoleDismissed> for LogConsole {}
+
+/// Developer panel tailing the app's log output, filterable by level and
+/// module, so git failures can be diagnosed without a terminal
+pub struct LogConsole {
+    log_state: Entity<LogState>,
+    module_input: Entity<TextInputView>,
+    /// Least severe level shown
+    min_level: Level,
+}
+
+impl LogConsole {
+    pub fn new(log_state: Entity<LogState>, cx: &mut Context<Self>) -> Self {
+        let module_input =
+            cx.new(|cx| TextInputView::new(cx).with_placeholder("Filter by module or text"));
+
+        cx.subscribe(&module_input, |_this, _input, _event: &TextInputChanged, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        cx.observe(&log_state, |_this, _log_state, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            log_state,
+            module_input,
+            min_level: Level::Info,
+        }
+    }
+
+    fn matching_entries(&self, cx: &App) -> Vec<LogEntry> {
+        let filter = self.module_input.read(cx).content().trim().to_lowercase();
+        let mut entries: Vec<LogEntry> = self
+            .log_state
+            .read(cx)
+            .entries()
+            .iter()
+            .rev()
+            .filter(|entry| entry.level <= self.min_level)
+            .filter(|entry| {
+                filter.is_empty()
+                    || entry.target.to_lowercase().contains(&filter)
+                    || entry.message.to_lowercase().contains(&filter)
+            })
+            .take(MAX_VISIBLE)
+            .cloned()
+            .collect();
+        entries.reverse();
+        entries
+    }
+
+    /// Copy the matching entries as plain text for bug reports
+    fn copy_entries(&mut self, cx: &mut Context<Self>) {
+        let text = self
+            .matching_entries(cx)
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{} {:<5} {}: {}",
+                    entry.timestamp.format("%H:%M:%S%.3f"),
+                    entry.level,
+                    entry.target,
+                    entry.message
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        cx.write_to_clipboard(ClipboardItem::new_string(text));
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(LogConsoleDismissed);
+    }
+}
+
+impl Render for LogConsole {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let entries = self.matching_entries(cx);
+        let total = self.log_state.read(cx).entries().len();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Log Console"),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .child(format!("{} of {} entries", entries.len(), total)),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("close-log-console")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    ),
+            )
+            // Filters
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .children(
+                        [Level::Error, Level::Warn, Level::Info, Level::Debug]
+                            .into_iter()
+                            .map(|level| {
+                                let selected = self.min_level == level;
+                                console_button(
+                                    format!("log-level-{}", level),
+                                    level.as_str(),
+                                    selected,
+                                    &theme,
+                                )
+                                .on_click(cx.listener(move |this, _event, _window, cx| {
+                                    this.min_level = level;
+                                    cx.notify();
+                                }))
+                            }),
+                    )
+                    .child(div().flex_1().child(self.module_input.clone()))
+                    .child(
+                        console_button("log-copy".to_string(), "Copy", false, &theme).on_click(
+                            cx.listener(|this, _event, _window, cx| {
+                                this.copy_entries(cx);
+                            }),
+                        ),
+                    )
+                    .child(
+                        console_button("log-clear".to_string(), "Clear", false, &theme).on_click(
+                            cx.listener(|this, _event, _window, cx| {
+                                this.log_state.update(cx, |state, cx| state.clear(cx));
+                            }),
+                        ),
+                    ),
+            )
+            // Entries
+            .child(
+                div()
+                    .id("log-entries")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .font_family("monospace")
+                    .text_xs()
+                    .when(entries.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .py_8()
+                                .text_center()
+                                .text_color(theme.overlay0)
+                                .child("No log entries"),
+                        )
+                    })
+                    .children(entries.into_iter().map(|entry| render_entry(entry, &theme))),
+            )
+    }
+}
+
+fn render_entry(entry: LogEntry, theme: &Theme) -> impl IntoElement {
+    let level_color = match entry.level {
+        Level::Error => theme.red,
+        Level::Warn => theme.yellow,
+        Level::Info => theme.blue,
+        Level::Debug | Level::Trace => theme.overlay0,
+    };
+
+    div()
+        .flex()
+        .gap_2()
+        .py_px()
+        .child(
+            div()
+                .flex_shrink_0()
+                .text_color(theme.overlay0)
+                .child(entry.timestamp.format("%H:%M:%S%.3f").to_string()),
+        )
+        .child(
+            div()
+                .w_10()
+                .flex_shrink_0()
+                .text_color(level_color)
+                .child(entry.level.as_str()),
+        )
+        .child(
+            div()
+                .flex_shrink_0()
+                .max_w(px(200.0))
+                .truncate()
+                .text_color(theme.overlay2)
+                .child(entry.target),
+        )
+        .child(div().flex_1().text_color(theme.text).child(entry.message))
+}
+
+fn console_button(id: String, label: &'static str, selected: bool, theme: &Theme) -> Stateful<Div> {
+    div()
+        .id(ElementId::Name(id.into()))
+        .px_2()
+        .py_1()
+        .rounded_md()
+        .text_xs()
+        .when(selected, |this| this.bg(theme.blue).text_color(theme.base))
+        .when(!selected, |this| {
+            this.bg(theme.surface0)
+                .text_color(theme.text)
+                .hover(|s| s.bg(theme.surface1))
+        })
+        .cursor_pointer()
+        .child(label)
+}
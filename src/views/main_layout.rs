@@ -1,50 +1,163 @@
-use crate::actions::OpenSettings;
-use crate::state::{GitState, SettingsState};
-use crate::views::{LeftPanel, RightPanel};
+use crate::actions::{
+    CompareBranches, OpenSettings, ShowReflog, ShowReviewNotes, ShowSearch, ShowTags, ShowTodos,
+    ShowTrash, ShowWorktrees, ToggleProjectTree,
+};
+use crate::state::{
+    AvatarState, DraftState, GitState, SessionState, SettingsState, MAX_LEFT_PANEL_WIDTH,
+    MIN_LEFT_PANEL_WIDTH,
+};
+use crate::theme::ActiveTheme;
+use crate::views::{
+    CherryPickBanner, DetachedHeadBanner, InterruptedOperationBanner, LeftPanel, ProjectTree,
+    ProjectTreeDismissed, RightPanel, StatusBar,
+};
 use gpui::prelude::*;
 use gpui::*;
 
+/// Width of the project tree sidebar in pixels
+const PROJECT_TREE_WIDTH: f32 = 260.0;
+
 pub struct MainLayout {
     git_state: Entity<GitState>,
     #[allow(dead_code)]
     settings: Entity<SettingsState>,
+    session: Entity<SessionState>,
     left_panel: Entity<LeftPanel>,
     right_panel: Entity<RightPanel>,
+    /// Shown while HEAD is detached
+    detached_head_banner: Entity<DetachedHeadBanner>,
+    /// Shown while a multi-commit cherry-pick runs or a cherry-pick or revert
+    /// waits on conflicts
+    cherry_pick_banner: Entity<CherryPickBanner>,
+    /// Shown while a merge, rebase, bisect or `git am` is in progress
+    interrupted_operation_banner: Entity<InterruptedOperationBanner>,
+    /// File tree sidebar, only built while shown
+    project_tree: Option<Entity<ProjectTree>>,
+    /// Current left panel width, updated live while dragging the divider
+    left_panel_width: f32,
+    /// Whether the panel divider is being dragged
+    resizing: bool,
 }
 
 impl MainLayout {
     pub fn new(
         git_state: Entity<GitState>,
         settings: Entity<SettingsState>,
+        session: Entity<SessionState>,
+        drafts: Entity<DraftState>,
+        avatars: Entity<AvatarState>,
         cx: &mut Context<Self>,
     ) -> Self {
         let settings_clone = settings.clone();
+        let session_clone = session.clone();
         let left_panel = cx.new(|cx| {
-            LeftPanel::new(git_state.clone(), cx).with_settings(settings_clone)
+            LeftPanel::new(git_state.clone(), cx)
+                .with_settings(settings_clone)
+                .with_session(session_clone, cx)
+                .with_drafts(drafts, cx)
         });
-        let right_panel = cx.new(|cx| RightPanel::new(git_state.clone(), cx));
+        let left_panel_width = session.read(cx).left_panel_width();
+        let right_panel = cx.new(|cx| RightPanel::new(git_state.clone(), avatars, cx));
+        let detached_head_banner = cx.new(|cx| DetachedHeadBanner::new(git_state.clone(), cx));
+        let cherry_pick_banner = cx.new(|cx| CherryPickBanner::new(git_state.clone(), cx));
+        let interrupted_operation_banner =
+            cx.new(|cx| InterruptedOperationBanner::new(git_state.clone(), cx));
 
-        // Observe git state changes
-        cx.observe(&git_state, |_this, _git_state, cx| {
+        // Observe git state changes, remembering the selected commit for the
+        // next launch
+        cx.observe(&git_state, |this, git_state, cx| {
+            let selected = git_state.read(cx).selected_commit.as_ref().map(|c| c.sha.clone());
+            this.session
+                .update(cx, |session, cx| session.set_selected_commit(selected, cx));
             cx.notify();
         })
         .detach();
 
-        Self {
+        let mut layout = Self {
             git_state,
             settings,
+            session,
             left_panel,
             right_panel,
+            detached_head_banner,
+            cherry_pick_banner,
+            interrupted_operation_banner,
+            project_tree: None,
+            left_panel_width,
+            resizing: false,
+        };
+        if layout.session.read(cx).data.project_tree_visible {
+            layout.show_project_tree(cx);
+        }
+        layout
+    }
+
+    /// Show or hide the project tree sidebar, remembering the choice
+    pub fn toggle_project_tree(&mut self, cx: &mut Context<Self>) {
+        if self.project_tree.take().is_none() {
+            self.show_project_tree(cx);
+        }
+        let visible = self.project_tree.is_some();
+        self.session.update(cx, |session, cx| {
+            session.set_project_tree_visible(visible, cx);
+        });
+        cx.notify();
+    }
+
+    /// Open the project tree, if hidden, with the history of `path`
+    pub fn show_file_history(&mut self, path: &str, cx: &mut Context<Self>) {
+        if self.project_tree.is_none() {
+            self.toggle_project_tree(cx);
+        }
+        if let Some(tree) = &self.project_tree {
+            tree.update(cx, |tree, cx| tree.select_file(path.to_string(), cx));
+        }
+    }
+
+    fn show_project_tree(&mut self, cx: &mut Context<Self>) {
+        let git_state = self.git_state.clone();
+        let session = self.session.clone();
+        let view = cx.new(|cx| ProjectTree::new(git_state, session, cx));
+        cx.subscribe(&view, |this, _view, _event: &ProjectTreeDismissed, cx| {
+            this.toggle_project_tree(cx);
+        })
+        .detach();
+        self.project_tree = Some(view);
+    }
+
+    fn resize_left_panel(&mut self, event: &MouseMoveEvent, cx: &mut Context<Self>) {
+        if !self.resizing {
+            return;
         }
+        // The left panel starts at the window edge, so its width is the cursor x
+        self.left_panel_width =
+            f32::from(event.position.x).clamp(MIN_LEFT_PANEL_WIDTH, MAX_LEFT_PANEL_WIDTH);
+        cx.notify();
+    }
+
+    fn finish_resize(&mut self, cx: &mut Context<Self>) {
+        if !self.resizing {
+            return;
+        }
+        self.resizing = false;
+        let width = self.left_panel_width;
+        self.session.update(cx, |session, cx| {
+            session.set_left_panel_width(width, cx);
+        });
+        cx.notify();
     }
 }
 
 impl Render for MainLayout {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
         let git_state_read = self.git_state.read(cx);
 
         let current_branch = git_state_read.current_branch().map(|s| s.to_string());
         let is_detached = git_state_read.is_detached();
+        let has_cherry_pick = git_state_read.cherry_pick_progress.is_some()
+            || git_state_read.interrupted_pick.is_some();
+        let has_interrupted_operation = git_state_read.interrupted_operation.is_some();
         let ahead = git_state_read
             .repository_info
             .as_ref()
@@ -60,6 +173,15 @@ impl Render for MainLayout {
             .flex()
             .flex_col()
             .size_full()
+            .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+                this.resize_left_panel(event, cx);
+            }))
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _event: &MouseUpEvent, _window, cx| {
+                    this.finish_resize(cx);
+                }),
+            )
             // Header bar
             .child(
                 div()
@@ -68,9 +190,9 @@ impl Render for MainLayout {
                     .justify_between()
                     .px_4()
                     .h_12()
-                    .bg(rgb(0x181825))
+                    .bg(theme.mantle)
                     .border_b_1()
-                    .border_color(rgb(0x313244))
+                    .border_color(theme.surface0)
                     // Left: Branch info
                     .child(
                         div()
@@ -81,9 +203,9 @@ impl Render for MainLayout {
                                 div()
                                     .text_sm()
                                     .text_color(if is_detached {
-                                        rgb(0xf9e2af)
+                                        theme.yellow
                                     } else {
-                                        rgb(0x89b4fa)
+                                        theme.blue
                                     })
                                     .child(if is_detached {
                                         "HEAD detached".to_string()
@@ -95,29 +217,183 @@ impl Render for MainLayout {
                                 this.child(
                                     div()
                                         .text_xs()
-                                        .text_color(rgb(0x9399b2))
+                                        .text_color(theme.overlay2)
                                         .child(format!("↑{} ↓{}", ahead, behind)),
                                 )
                             }),
                     )
-                    // Right: Settings button
+                    // Right: Files, Notes, TODOs, Tags, Worktrees, Compare and Settings buttons
                     .child(
                         div()
-                            .id("settings-button")
-                            .px_2()
-                            .py_1()
-                            .rounded_md()
-                            .text_sm()
-                            .text_color(rgb(0x9399b2))
-                            .cursor_pointer()
-                            .hover(|s| s.bg(rgb(0x313244)).text_color(rgb(0xcdd6f4)))
-                            .child("Settings")
-                            .on_click(|_event, window, cx| {
-                                window.dispatch_action(Box::new(OpenSettings), cx);
-                            }),
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .id("files-button")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(if self.project_tree.is_some() {
+                                        theme.text
+                                    } else {
+                                        theme.overlay2
+                                    })
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("Files")
+                                    .on_click(|_event, window, cx| {
+                                        window.dispatch_action(Box::new(ToggleProjectTree), cx);
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("notes-button")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("Notes")
+                                    .on_click(|_event, window, cx| {
+                                        window.dispatch_action(Box::new(ShowReviewNotes), cx);
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("search-button")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("Search")
+                                    .on_click(|_event, window, cx| {
+                                        window.dispatch_action(Box::new(ShowSearch), cx);
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("todos-button")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("TODOs")
+                                    .on_click(|_event, window, cx| {
+                                        window.dispatch_action(Box::new(ShowTodos), cx);
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("tags-button")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("Tags")
+                                    .on_click(|_event, window, cx| {
+                                        window.dispatch_action(Box::new(ShowTags), cx);
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("reflog-button")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("Reflog")
+                                    .on_click(|_event, window, cx| {
+                                        window.dispatch_action(Box::new(ShowReflog), cx);
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("trash-button")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("Discarded")
+                                    .on_click(|_event, window, cx| {
+                                        window.dispatch_action(Box::new(ShowTrash), cx);
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("worktrees-button")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("Worktrees")
+                                    .on_click(|_event, window, cx| {
+                                        window.dispatch_action(Box::new(ShowWorktrees), cx);
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("compare-button")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("Compare")
+                                    .on_click(|_event, window, cx| {
+                                        window.dispatch_action(Box::new(CompareBranches), cx);
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("settings-button")
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("Settings")
+                                    .on_click(|_event, window, cx| {
+                                        window.dispatch_action(Box::new(OpenSettings), cx);
+                                    }),
+                            ),
                     ),
             )
-            // Main content area (left + right panels)
+            .when(is_detached, |this| {
+                this.child(self.detached_head_banner.clone())
+            })
+            .when(has_cherry_pick, |this| {
+                this.child(self.cherry_pick_banner.clone())
+            })
+            .when(has_interrupted_operation, |this| {
+                this.child(self.interrupted_operation_banner.clone())
+            })
+            // Main content area (left + right panels, then the project tree)
             .child(
                 div()
                     .flex()
@@ -129,22 +405,52 @@ impl Render for MainLayout {
                         div()
                             .flex()
                             .flex_col()
-                            .w_80()
-                            .min_w_64()
-                            .bg(rgb(0x1e1e2e))
+                            .w(px(self.left_panel_width))
+                            .flex_none()
+                            .bg(theme.base)
                             .border_r_1()
-                            .border_color(rgb(0x313244))
+                            .border_color(theme.surface0)
                             .child(self.left_panel.clone()),
                     )
+                    // Drag handle for resizing the left panel
+                    .child(
+                        div()
+                            .w_1()
+                            .h_full()
+                            .flex_none()
+                            .cursor_col_resize()
+                            .when(self.resizing, |this| this.bg(theme.surface1))
+                            .hover(|s| s.bg(theme.surface1))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(|this, _event: &MouseDownEvent, _window, cx| {
+                                    this.resizing = true;
+                                    cx.notify();
+                                }),
+                            ),
+                    )
                     // Right panel (commit graph)
                     .child(
                         div()
                             .flex()
                             .flex_col()
                             .flex_1()
-                            .bg(rgb(0x1e1e2e))
+                            .bg(theme.base)
                             .child(self.right_panel.clone()),
-                    ),
+                    )
+                    .when_some(self.project_tree.clone(), |this, project_tree| {
+                        this.child(
+                            div()
+                                .flex()
+                                .flex_col()
+                                .w(px(PROJECT_TREE_WIDTH))
+                                .flex_none()
+                                .border_l_1()
+                                .border_color(theme.surface0)
+                                .child(project_tree),
+                        )
+                    }),
             )
+            .child(StatusBar::new(self.git_state.clone()))
     }
 }
@@ -0,0 +1,251 @@
+#![allow(dead_code)]
+
+use crate::actions::ShowConflictDialog;
+use crate::git::MergeMode;
+use crate::state::GitState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Most incoming commits listed in the dialog
+const MAX_INCOMING: usize = 50;
+
+/// Confirms the pending branch merge, showing the incoming commits and
+/// letting the merge mode be changed for this merge only
+#[derive(IntoElement)]
+pub struct MergeDialog {
+    git_state: Entity<GitState>,
+}
+
+impl MergeDialog {
+    pub fn new(git_state: Entity<GitState>) -> Self {
+        Self { git_state }
+    }
+}
+
+impl RenderOnce for MergeDialog {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let git_state = self.git_state.read(cx);
+        let Some(request) = git_state.pending_merge.clone() else {
+            return div().into_any_element();
+        };
+        let current_branch = git_state.current_branch().unwrap_or("HEAD").to_string();
+        let incoming = git_state
+            .commits_not_in_head(&request.branch, MAX_INCOMING)
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to list commits to merge: {}", e);
+                Vec::new()
+            });
+        let mode_buttons = MergeMode::ALL.map(|mode| {
+            let selected = mode == request.mode;
+            let git_state = self.git_state.clone();
+            div()
+                .id(SharedString::from(format!("merge-mode-{}", mode.label())))
+                .px_3()
+                .py_1()
+                .rounded_md()
+                .text_sm()
+                .cursor_pointer()
+                .when(selected, |this| this.bg(theme.blue).text_color(theme.base))
+                .when(!selected, |this| {
+                    this.bg(theme.surface0)
+                        .text_color(theme.text)
+                        .hover(|s| s.bg(theme.surface1))
+                })
+                .child(mode.label())
+                .on_click(move |_event, _window, cx| {
+                    git_state.update(cx, |state, cx| state.set_merge_request_mode(mode, cx));
+                })
+        });
+        let git_state_close = self.git_state.clone();
+        let git_state_cancel = self.git_state.clone();
+        let git_state_confirm = self.git_state.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_start()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child(format!("Merge '{}'", request.branch)),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .child(format!("into {}", current_branch)),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("merge-close")
+                            .px_2()
+                            .rounded_md()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .child("×")
+                            .on_click(move |_event, _window, cx| {
+                                git_state_close.update(cx, |state, cx| state.cancel_merge(cx));
+                            }),
+                    ),
+            )
+            .when_some(request.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error),
+                )
+            })
+            // Merge mode
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.overlay2)
+                            .child("MERGE MODE"),
+                    )
+                    .child(div().flex().gap_2().children(mode_buttons))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.overlay0)
+                            .child(request.mode.description()),
+                    ),
+            )
+            // Incoming commits
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.overlay2)
+                    .child(if incoming.len() >= MAX_INCOMING {
+                        format!("INCOMING COMMITS ({}+)", MAX_INCOMING)
+                    } else {
+                        format!("INCOMING COMMITS ({})", incoming.len())
+                    }),
+            )
+            .child(
+                div()
+                    .id("merge-commits")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .when(incoming.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .py_4()
+                                .text_sm()
+                                .text_center()
+                                .text_color(theme.overlay0)
+                                .child("Nothing to merge"),
+                        )
+                    })
+                    .children(incoming.iter().map(|commit| {
+                        div()
+                            .flex()
+                            .gap_2()
+                            .py_px()
+                            .text_xs()
+                            .child(
+                                div()
+                                    .flex_shrink_0()
+                                    .font_family("monospace")
+                                    .text_color(theme.blue)
+                                    .child(commit.short_sha.clone()),
+                            )
+                            .child(
+                                div()
+                                    .truncate()
+                                    .text_color(theme.text)
+                                    .child(commit.message.lines().next().unwrap_or("").to_string()),
+                            )
+                    })),
+            )
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .pt_2()
+                    .border_t_1()
+                    .border_color(theme.surface0)
+                    .child(
+                        div()
+                            .id("merge-cancel")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("Cancel")
+                            .on_click(move |_event, _window, cx| {
+                                git_state_cancel.update(cx, |state, cx| state.cancel_merge(cx));
+                            }),
+                    )
+                    .child(
+                        div()
+                            .id("merge-confirm")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(theme.blue)
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.base)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.lavender))
+                            .child("Merge")
+                            .on_click(move |_event, window, cx| {
+                                let result = git_state_confirm
+                                    .update(cx, |state, cx| state.confirm_merge(cx));
+                                match result {
+                                    Ok(true) => {
+                                        window.dispatch_action(Box::new(ShowConflictDialog), cx)
+                                    }
+                                    Ok(false) => {}
+                                    Err(e) => log::error!("Failed to merge: {}", e),
+                                }
+                            }),
+                    ),
+            )
+            .into_any_element()
+    }
+}
@@ -1,21 +1,92 @@
+pub mod auth_dialog;
+pub mod blame;
+pub mod branch_compare;
+pub mod cherry_pick_banner;
+pub mod clone_dialog;
 pub mod commit_form;
 pub mod commit_graph;
+pub mod compare;
 pub mod conflict_dialog;
+pub mod conflict_editor;
+pub mod detached_head_banner;
 pub mod diff_viewer;
+pub mod discard_dialog;
+pub mod draft_recovery;
+pub mod external_editor;
 pub mod file_list;
+pub mod force_push_dialog;
+pub mod git_auth;
+pub mod git_identity;
+pub mod hosting_token;
+pub mod interrupted_operation_banner;
 pub mod left_panel;
+pub mod log_console;
 pub mod main_layout;
+pub mod merge_dialog;
+pub mod operation_preview;
+pub mod perf_overlay;
+pub mod project_tree;
+pub mod prune_report;
+pub mod reflog;
+pub mod repo_settings;
+pub mod review_mode;
+pub mod review_notes;
 pub mod right_panel;
+pub mod search;
 pub mod settings;
+pub mod signing_lists;
+pub mod stash_branch_dialog;
+pub mod stash_dialog;
+pub mod status_bar;
+pub mod syntax;
+pub mod tags;
+pub mod todos;
+pub mod trash;
 pub mod welcome;
+pub mod worktrees;
 
+pub use auth_dialog::*;
+pub use blame::*;
+pub use branch_compare::*;
+pub use cherry_pick_banner::*;
+pub use clone_dialog::*;
 pub use commit_form::*;
 pub use commit_graph::*;
+pub use compare::*;
 pub use conflict_dialog::*;
+pub use conflict_editor::*;
+pub use detached_head_banner::*;
 pub use diff_viewer::*;
+pub use discard_dialog::*;
+pub use draft_recovery::*;
+pub use external_editor::*;
 pub use file_list::*;
+pub use force_push_dialog::*;
+pub use git_auth::*;
+pub use git_identity::*;
+pub use hosting_token::*;
+pub use interrupted_operation_banner::*;
 pub use left_panel::*;
+pub use log_console::*;
 pub use main_layout::*;
+pub use merge_dialog::*;
+pub use operation_preview::*;
+pub use perf_overlay::*;
+pub use project_tree::*;
+pub use prune_report::*;
+pub use reflog::*;
+pub use repo_settings::*;
+pub use review_mode::*;
+pub use review_notes::*;
 pub use right_panel::*;
+pub use search::*;
 pub use settings::*;
+pub use signing_lists::*;
+pub use stash_branch_dialog::*;
+pub use stash_dialog::*;
+pub use status_bar::*;
+pub use tags::*;
+pub use todos::*;
+pub use trash::*;
 pub use welcome::*;
+pub use worktrees::*;
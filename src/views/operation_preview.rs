@@ -0,0 +1,287 @@
+#![allow(dead_code)]
+
+use crate::actions::ShowConflictDialog;
+use crate::git::{OperationPreview, PreviewFile};
+use crate::state::GitState;
+use crate::theme::{ActiveTheme, Theme};
+use gpui::prelude::*;
+use gpui::*;
+
+/// Shows the computed effect of the pending operation preview and lets the
+/// user run or cancel it
+#[derive(IntoElement)]
+pub struct OperationPreviewDialog {
+    git_state: Entity<GitState>,
+}
+
+impl OperationPreviewDialog {
+    pub fn new(git_state: Entity<GitState>) -> Self {
+        Self { git_state }
+    }
+}
+
+impl RenderOnce for OperationPreviewDialog {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let Some(preview) = self.git_state.read(cx).pending_preview.clone() else {
+            return div().into_any_element();
+        };
+        let operation = preview.operation.clone();
+        let can_run = operation.can_run();
+        let is_destructive = operation.is_destructive();
+        let git_state = self.git_state.clone();
+        let git_state_cancel = self.git_state.clone();
+        let git_state_run = self.git_state.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_start()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child(format!("Preview: {}", operation.title())),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(if preview.is_clean() {
+                                        theme.overlay2
+                                    } else {
+                                        theme.red
+                                    })
+                                    .child(preview.summary.clone()),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("preview-close")
+                            .px_2()
+                            .rounded_md()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .child("×")
+                            .on_click(move |_event, _window, cx| {
+                                git_state.update(cx, |state, cx| state.dismiss_preview(cx));
+                            }),
+                    ),
+            )
+            // Effects
+            .child(
+                div()
+                    .id("preview-scroll")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .child(render_effects(&preview, &theme)),
+            )
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .pt_2()
+                    .border_t_1()
+                    .border_color(theme.surface0)
+                    .when(!can_run, |this| {
+                        this.child(
+                            div()
+                                .flex_1()
+                                .text_xs()
+                                .text_color(theme.overlay0)
+                                .child("Preview only; nothing has been changed"),
+                        )
+                    })
+                    .child(
+                        div()
+                            .id("preview-cancel")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child(if can_run { "Cancel" } else { "Close" })
+                            .on_click(move |_event, _window, cx| {
+                                git_state_cancel.update(cx, |state, cx| state.dismiss_preview(cx));
+                            }),
+                    )
+                    .when(can_run, |this| {
+                        this.child(
+                            div()
+                                .id("preview-run")
+                                .px_4()
+                                .py_2()
+                                .rounded_md()
+                                .bg(if is_destructive {
+                                    theme.red
+                                } else {
+                                    theme.blue
+                                })
+                                .text_sm()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(theme.base)
+                                .cursor_pointer()
+                                .hover(move |s| {
+                                    if is_destructive {
+                                        s.bg(theme.maroon)
+                                    } else {
+                                        s.bg(theme.lavender)
+                                    }
+                                })
+                                .child("Run")
+                                .on_click(move |_event, window, cx| {
+                                    let has_conflicts = git_state_run.update(cx, |state, cx| {
+                                        if let Err(e) = state.confirm_preview(cx) {
+                                            log::error!("Failed to run previewed operation: {}", e);
+                                        }
+                                        state.conflict_info.is_some()
+                                    });
+                                    // A merge that stopped on conflicts goes to the dialog
+                                    if has_conflicts {
+                                        window.dispatch_action(Box::new(ShowConflictDialog), cx);
+                                    }
+                                }),
+                        )
+                    }),
+            )
+            .into_any_element()
+    }
+}
+
+fn render_effects(preview: &OperationPreview, theme: &Theme) -> impl IntoElement {
+    let nothing_changes = preview.conflicts.is_empty()
+        && preview.discarded.is_empty()
+        && preview.commits.is_empty()
+        && preview.changed_files.is_empty();
+
+    div()
+        .flex()
+        .flex_col()
+        .gap_3()
+        .when(!preview.conflicts.is_empty(), |this| {
+            this.child(section(
+                format!("Conflicts ({})", preview.conflicts.len()),
+                theme.red,
+                theme,
+                preview
+                    .conflicts
+                    .iter()
+                    .map(|path| path_row(path.clone(), '!', theme.yellow, theme)),
+            ))
+        })
+        .when(!preview.discarded.is_empty(), |this| {
+            this.child(section(
+                format!("Will be lost ({})", preview.discarded.len()),
+                theme.red,
+                theme,
+                preview
+                    .discarded
+                    .iter()
+                    .map(|path| path_row(path.clone(), '-', theme.red, theme)),
+            ))
+        })
+        .when(!preview.commits.is_empty(), |this| {
+            this.child(section(
+                format!("{} ({})", preview.commits_label, preview.commits.len()),
+                theme.blue,
+                theme,
+                preview.commits.iter().map(|commit| {
+                    div()
+                        .flex()
+                        .gap_2()
+                        .text_sm()
+                        .child(div().text_color(theme.yellow).child(commit.short_sha.clone()))
+                        .child(
+                            div()
+                                .flex_1()
+                                .truncate()
+                                .text_color(theme.text)
+                                .child(commit.message.clone()),
+                        )
+                }),
+            ))
+        })
+        .when(!preview.changed_files.is_empty(), |this| {
+            this.child(section(
+                format!("Files changed ({})", preview.changed_files.len()),
+                theme.blue,
+                theme,
+                preview.changed_files.iter().map(|file: &PreviewFile| {
+                    let color = theme.file_status_color(file.status);
+                    path_row(file.path.clone(), file.status.symbol(), color, theme)
+                }),
+            ))
+        })
+        .when(nothing_changes, |this| {
+            this.child(
+                div()
+                    .py_8()
+                    .text_sm()
+                    .text_center()
+                    .text_color(theme.overlay0)
+                    .child("Nothing would change"),
+            )
+        })
+}
+
+fn section(
+    title: String,
+    color: Rgba,
+    theme: &Theme,
+    rows: impl IntoIterator<Item = impl IntoElement>,
+) -> impl IntoElement {
+    div()
+        .flex()
+        .flex_col()
+        .gap_1()
+        .child(
+            div()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(color)
+                .child(title),
+        )
+        .child(
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .rounded_md()
+                .bg(theme.mantle)
+                .p_2()
+                .children(rows),
+        )
+}
+
+fn path_row(path: String, symbol: char, color: Rgba, theme: &Theme) -> impl IntoElement {
+    div()
+        .flex()
+        .gap_2()
+        .text_sm()
+        .child(div().w_4().text_color(color).child(symbol.to_string()))
+        .child(div().flex_1().truncate().text_color(theme.text).child(path))
+}
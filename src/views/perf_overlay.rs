@@ -0,0 +1,162 @@
+#![allow(dead_code)]
+
+use crate::state::GitState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Frames averaged for the frame time readout
+const FRAME_WINDOW: usize = 120;
+
+/// Frame time above which a frame is counted as dropped (60 Hz)
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+
+/// Developer overlay showing frame times and how often entities notify, to
+/// spot state changes that redraw more than they should.
+///
+/// While shown it requests a frame on every frame, so the frame time is the
+/// cost of drawing the whole window continuously.
+pub struct PerfOverlay {
+    git_state: Entity<GitState>,
+    /// Intervals between recent frames, oldest first
+    frames: VecDeque<Duration>,
+    last_frame: Option<Instant>,
+    /// Notifies per watched entity since the overlay opened or was reset
+    notify_counts: Vec<(&'static str, usize)>,
+}
+
+impl PerfOverlay {
+    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+        let mut overlay = Self {
+            git_state: git_state.clone(),
+            frames: VecDeque::with_capacity(FRAME_WINDOW),
+            last_frame: None,
+            notify_counts: Vec::new(),
+        };
+        overlay.watch("GitState", &git_state, cx);
+        overlay
+    }
+
+    /// Count notifies of `entity` under `name`
+    pub fn watch<T: 'static>(
+        &mut self,
+        name: &'static str,
+        entity: &Entity<T>,
+        cx: &mut Context<Self>,
+    ) {
+        self.notify_counts.push((name, 0));
+        cx.observe(entity, move |this, _entity, _cx| {
+            if let Some((_, count)) = this.notify_counts.iter_mut().find(|(n, _)| *n == name) {
+                *count += 1;
+            }
+        })
+        .detach();
+    }
+
+    fn reset(&mut self, cx: &mut Context<Self>) {
+        self.frames.clear();
+        self.last_frame = None;
+        for (_, count) in &mut self.notify_counts {
+            *count = 0;
+        }
+        cx.notify();
+    }
+
+    fn record_frame(&mut self) {
+        let now = Instant::now();
+        if let Some(last) = self.last_frame.replace(now) {
+            if self.frames.len() == FRAME_WINDOW {
+                self.frames.pop_front();
+            }
+            self.frames.push_back(now - last);
+        }
+    }
+}
+
+impl Render for PerfOverlay {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.record_frame();
+        window.request_animation_frame();
+
+        let theme = *cx.theme();
+        let revisions = self.git_state.read(cx).revisions();
+        let average = if self.frames.is_empty() {
+            Duration::ZERO
+        } else {
+            self.frames.iter().sum::<Duration>() / self.frames.len() as u32
+        };
+        let worst = self.frames.iter().max().copied().unwrap_or_default();
+        let dropped = self.frames.iter().filter(|f| **f > FRAME_BUDGET).count();
+        let fps = if average.is_zero() {
+            0.0
+        } else {
+            1.0 / average.as_secs_f64()
+        };
+        let ms = |d: Duration| format!("{:.1} ms", d.as_secs_f64() * 1000.0);
+
+        let row = |label: String, value: String| {
+            div()
+                .flex()
+                .justify_between()
+                .gap_4()
+                .child(div().text_color(theme.overlay2).child(label))
+                .child(div().text_color(theme.text).child(value))
+        };
+
+        div()
+            .w(px(220.0))
+            .p_2()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .rounded_md()
+            .bg(theme.mantle)
+            .border_1()
+            .border_color(theme.surface0)
+            .shadow_lg()
+            .font_family("monospace")
+            .text_xs()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.blue)
+                            .child("Performance"),
+                    )
+                    .child(
+                        div()
+                            .id("perf-reset")
+                            .px_1()
+                            .rounded_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("Reset")
+                            .on_click(cx.listener(|this, _event, _window, cx| this.reset(cx))),
+                    ),
+            )
+            .child(row("Frame".into(), ms(average)))
+            .child(row("Worst".into(), ms(worst)))
+            .child(row("FPS".into(), format!("{:.0}", fps)))
+            .child(row(
+                "Over budget".into(),
+                format!("{} / {}", dropped, self.frames.len()),
+            ))
+            .child(div().mt_1().text_color(theme.overlay0).child("Notifies"))
+            .children(
+                self.notify_counts
+                    .iter()
+                    .map(|(name, count)| row(name.to_string(), count.to_string())),
+            )
+            .child(div().mt_1().text_color(theme.overlay0).child("GitState revisions"))
+            .child(row("status".into(), revisions.status.to_string()))
+            .child(row("refs".into(), revisions.refs.to_string()))
+            .child(row("graph".into(), revisions.graph.to_string()))
+    }
+}
@@ -0,0 +1,554 @@
+#![allow(dead_code)]
+
+use crate::actions::ShowDiff;
+use crate::components::{AuthorLink, CopyDetailsButton};
+use crate::git::{CommitInfo, ProjectFile};
+use crate::i18n::{time_ago, ActiveLocale};
+use crate::state::{ErrorDetails, GitState, SessionState};
+use crate::theme::{ActiveTheme, Theme};
+use gpui::prelude::*;
+use gpui::*;
+use std::collections::{BTreeMap, HashSet};
+
+/// Indentation per tree level in pixels
+const TREE_INDENT: f32 = 12.0;
+/// Most commits listed in a file's history
+const HISTORY_LIMIT: usize = 100;
+
+/// Emitted when the sidebar should be hidden
+#[derive(Clone, Debug)]
+pub struct ProjectTreeDismissed;
+
+impl EventEmitter<ProjectTreeDismissed> for ProjectTree {}
+
+/// Read-only tree of the repository's files, with the history of the
+/// selected file
+pub struct ProjectTree {
+    git_state: Entity<GitState>,
+    session: Entity<SessionState>,
+    files: Vec<ProjectFile>,
+    /// Directories are collapsed unless listed here
+    expanded_dirs: HashSet<String>,
+    selected: Option<String>,
+    history: Vec<CommitInfo>,
+    error: Option<ErrorDetails>,
+}
+
+/// Directory node of the tree
+#[derive(Default)]
+struct ProjectDir {
+    dirs: BTreeMap<String, ProjectDir>,
+    files: Vec<ProjectFile>,
+}
+
+impl ProjectDir {
+    fn build(files: &[ProjectFile]) -> Self {
+        let mut root = ProjectDir::default();
+        for file in files {
+            let mut node = &mut root;
+            if let Some((dir, _)) = file.path.rsplit_once('/') {
+                for part in dir.split('/') {
+                    node = node.dirs.entry(part.to_string()).or_default();
+                }
+            }
+            node.files.push(file.clone());
+        }
+        root
+    }
+}
+
+impl ProjectTree {
+    pub fn new(
+        git_state: Entity<GitState>,
+        session: Entity<SessionState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        // Reading the index is cheap, and the tree only exists while shown
+        cx.observe(&git_state, |this, _git_state, cx| {
+            this.reload(cx);
+        })
+        .detach();
+
+        let mut tree = Self {
+            git_state,
+            session,
+            files: Vec::new(),
+            expanded_dirs: HashSet::new(),
+            selected: None,
+            history: Vec::new(),
+            error: None,
+        };
+        tree.reload(cx);
+        tree
+    }
+
+    fn include_untracked(&self, cx: &App) -> bool {
+        self.session.read(cx).data.project_tree_untracked
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let include_untracked = self.include_untracked(cx);
+        match self.git_state.read(cx).project_files(include_untracked) {
+            Ok(files) => {
+                self.files = files;
+                self.error = None;
+            }
+            Err(e) => {
+                log::error!("Failed to list project files: {}", e);
+                self.files.clear();
+                self.error = Some(self.git_state.read(cx).error_details("List files", &e));
+            }
+        }
+
+        // The selected file may have been deleted or untracked
+        if let Some(selected) = &self.selected {
+            if !self.files.iter().any(|f| &f.path == selected) {
+                self.selected = None;
+                self.history.clear();
+            }
+        }
+        cx.notify();
+    }
+
+    fn toggle_untracked(&mut self, cx: &mut Context<Self>) {
+        let include_untracked = !self.include_untracked(cx);
+        self.session.update(cx, |session, cx| {
+            session.set_project_tree_untracked(include_untracked, cx)
+        });
+        self.reload(cx);
+    }
+
+    fn toggle_dir(&mut self, dir: String, cx: &mut Context<Self>) {
+        if !self.expanded_dirs.remove(&dir) {
+            self.expanded_dirs.insert(dir);
+        }
+        cx.notify();
+    }
+
+    /// Show the history of a file, expanding the directories it's in
+    pub fn select_file(&mut self, path: String, cx: &mut Context<Self>) {
+        let mut dir = path.as_str();
+        while let Some((parent, _)) = dir.rsplit_once('/') {
+            self.expanded_dirs.insert(parent.to_string());
+            dir = parent;
+        }
+        match self.git_state.read(cx).file_history(&path, HISTORY_LIMIT) {
+            Ok(history) => {
+                self.history = history;
+                self.error = None;
+            }
+            Err(e) => {
+                log::error!("Failed to load history of {}: {}", path, e);
+                self.history.clear();
+                self.error = Some(self.git_state.read(cx).error_details("File history", &e));
+            }
+        }
+        self.selected = Some(path);
+        cx.notify();
+    }
+
+    /// Open the uncommitted changes of the selected file
+    fn show_changes(&mut self, path: String, window: &mut Window, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.load_file_diff(&path, cx));
+        self.show_diff(result, window, cx);
+    }
+
+    fn open_in_editor(&mut self, path: String, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.open_in_editor(&path, None, cx));
+        if let Err(e) = result {
+            log::error!("Failed to open {} in the editor: {:#}", path, e);
+        }
+    }
+
+    fn reveal_in_file_manager(&mut self, path: String, cx: &mut Context<Self>) {
+        if let Err(e) = self.git_state.read(cx).reveal_in_file_manager(&path) {
+            log::error!("Failed to reveal {}: {:#}", path, e);
+        }
+    }
+
+    /// Open what a commit changed in the selected file
+    fn show_commit_diff(
+        &mut self,
+        sha: String,
+        path: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.load_commit_file_diff(&sha, &path, cx));
+        self.show_diff(result, window, cx);
+    }
+
+    fn show_diff(
+        &mut self,
+        result: anyhow::Result<()>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match result {
+            Ok(()) => window.dispatch_action(Box::new(ShowDiff), cx),
+            Err(e) => {
+                log::error!("Failed to load diff: {}", e);
+                self.error = Some(self.git_state.read(cx).error_details("Load diff", &e));
+                cx.notify();
+            }
+        }
+    }
+
+    fn dismiss(&mut self, cx: &mut Context<Self>) {
+        cx.emit(ProjectTreeDismissed);
+    }
+}
+
+impl Render for ProjectTree {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let include_untracked = self.include_untracked(cx);
+
+        let mut rows = Vec::new();
+        self.render_dir(&ProjectDir::build(&self.files), "", 0, &mut rows, cx);
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .px_3()
+                    .py_2()
+                    .bg(theme.mantle)
+                    .border_b_1()
+                    .border_color(theme.surface0)
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(theme.overlay2)
+                            .child("Project"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .id("project-tree-untracked")
+                                    .px_2()
+                                    .py_px()
+                                    .rounded_sm()
+                                    .text_xs()
+                                    .cursor_pointer()
+                                    .when(include_untracked, |this| {
+                                        this.bg(theme.blue).text_color(theme.base)
+                                    })
+                                    .when(!include_untracked, |this| {
+                                        this.bg(theme.surface0)
+                                            .text_color(theme.overlay2)
+                                            .hover(|s| s.bg(theme.surface1))
+                                    })
+                                    .child("Untracked")
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.toggle_untracked(cx);
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .id("project-tree-close")
+                                    .px_2()
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                                    .child("×")
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.dismiss(cx);
+                                    })),
+                            ),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .m_2()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_xs()
+                        .text_color(theme.red)
+                        .child(error.message)
+                        .child(CopyDetailsButton::new("project-tree-error", error.report)),
+                )
+            })
+            // Tree
+            .child(
+                div()
+                    .id("project-tree-files")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .py_1()
+                    .when(self.files.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .py_8()
+                                .text_sm()
+                                .text_center()
+                                .text_color(theme.overlay0)
+                                .child("No files"),
+                        )
+                    })
+                    .children(rows),
+            )
+            // History of the selected file
+            .when_some(self.selected.clone(), |this, path| {
+                this.child(self.render_history(path, &theme, cx))
+            })
+    }
+}
+
+impl ProjectTree {
+    fn render_dir(
+        &self,
+        node: &ProjectDir,
+        path: &str,
+        depth: usize,
+        rows: &mut Vec<AnyElement>,
+        cx: &mut Context<Self>,
+    ) {
+        let theme = *cx.theme();
+
+        for (name, child) in &node.dirs {
+            let dir = if path.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", path, name)
+            };
+            let expanded = self.expanded_dirs.contains(&dir);
+            let dir_toggle = dir.clone();
+
+            rows.push(
+                div()
+                    .id(ElementId::Name(format!("project-dir-{}", dir).into()))
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .pl(px(8.0 + depth as f32 * TREE_INDENT))
+                    .pr_2()
+                    .py_px()
+                    .cursor_pointer()
+                    .hover(|s| s.bg(theme.surface0))
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.toggle_dir(dir_toggle.clone(), cx);
+                    }))
+                    .child(
+                        div()
+                            .w_4()
+                            .text_xs()
+                            .text_color(theme.overlay0)
+                            .child(if expanded { "▾" } else { "▸" }),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .truncate()
+                            .child(format!("{}/", name)),
+                    )
+                    .into_any_element(),
+            );
+            if expanded {
+                self.render_dir(child, &dir, depth + 1, rows, cx);
+            }
+        }
+
+        for file in &node.files {
+            let name = file.path.rsplit('/').next().unwrap_or(&file.path).to_string();
+            let is_selected = self.selected.as_ref() == Some(&file.path);
+            let path = file.path.clone();
+
+            rows.push(
+                div()
+                    .id(ElementId::Name(format!("project-file-{}", file.path).into()))
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .pl(px(8.0 + depth as f32 * TREE_INDENT + 20.0))
+                    .pr_2()
+                    .py_px()
+                    .cursor_pointer()
+                    .when(is_selected, |this| this.bg(theme.surface1))
+                    .when(!is_selected, |this| this.hover(|s| s.bg(theme.surface0)))
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.select_file(path.clone(), cx);
+                    }))
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(if file.tracked { theme.text } else { theme.green })
+                            .truncate()
+                            .child(name),
+                    )
+                    .into_any_element(),
+            );
+        }
+    }
+
+    fn render_history(&self, path: String, theme: &Theme, cx: &mut Context<Self>) -> AnyElement {
+        let has_changes = self.git_state.read(cx).files.iter().any(|f| f.path == path);
+        let locale = cx.locale();
+        let path_changes = path.clone();
+        let (path_open, path_reveal, path_copy) = (path.clone(), path.clone(), path.clone());
+        let header_button = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .flex_shrink_0()
+                .px_2()
+                .py_px()
+                .rounded_sm()
+                .bg(theme.surface0)
+                .text_xs()
+                .text_color(theme.overlay2)
+                .cursor_pointer()
+                .hover(|s| s.bg(theme.surface1).text_color(theme.text))
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_none()
+            .h(px(260.0))
+            .border_t_1()
+            .border_color(theme.surface0)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .px_3()
+                    .py_2()
+                    .bg(theme.mantle)
+                    .child(
+                        div()
+                            .flex_1()
+                            .min_w_0()
+                            .truncate()
+                            .font_family("monospace")
+                            .text_xs()
+                            .text_color(theme.text)
+                            .child(path.clone()),
+                    )
+                    .child(header_button("project-file-open", "Open").on_click(cx.listener(
+                        move |this, _event, _window, cx| {
+                            this.open_in_editor(path_open.clone(), cx);
+                        },
+                    )))
+                    .child(header_button("project-file-reveal", "Reveal").on_click(cx.listener(
+                        move |this, _event, _window, cx| {
+                            this.reveal_in_file_manager(path_reveal.clone(), cx);
+                        },
+                    )))
+                    .child(header_button("project-file-copy-path", "Copy Path").on_click(
+                        move |_event, _window, cx| {
+                            cx.write_to_clipboard(ClipboardItem::new_string(path_copy.clone()));
+                        },
+                    ))
+                    .when(has_changes, |this| {
+                        this.child(
+                            div()
+                                .id("project-file-changes")
+                                .flex_shrink_0()
+                                .px_2()
+                                .py_px()
+                                .rounded_sm()
+                                .bg(theme.surface0)
+                                .text_xs()
+                                .text_color(theme.yellow)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface1))
+                                .child("Changes")
+                                .on_click(cx.listener(move |this, _event, window, cx| {
+                                    this.show_changes(path_changes.clone(), window, cx);
+                                })),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .id("project-file-history")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .when(self.history.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .py_4()
+                                .text_xs()
+                                .text_center()
+                                .text_color(theme.overlay0)
+                                .child("No commits"),
+                        )
+                    })
+                    .children(self.history.iter().map(|commit| {
+                        let sha = commit.sha.clone();
+                        let path = path.clone();
+                        div()
+                            .id(ElementId::Name(format!("project-history-{}", commit.sha).into()))
+                            .flex()
+                            .flex_col()
+                            .px_3()
+                            .py_1()
+                            .border_b_1()
+                            .border_color(theme.surface0)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .on_click(cx.listener(move |this, _event, window, cx| {
+                                this.show_commit_diff(sha.clone(), path.clone(), window, cx);
+                            }))
+                            .child(
+                                div()
+                                    .truncate()
+                                    .text_xs()
+                                    .text_color(theme.text)
+                                    .child(commit.message.clone()),
+                            )
+                            .child(
+                                div()
+                                    .flex()
+                                    .gap_2()
+                                    .text_xs()
+                                    .text_color(theme.overlay0)
+                                    .child(
+                                        div()
+                                            .font_family("monospace")
+                                            .text_color(theme.blue)
+                                            .child(commit.short_sha.clone()),
+                                    )
+                                    .child(AuthorLink::new(
+                                        ElementId::Name(
+                                            format!("project-author-{}", commit.sha).into(),
+                                        ),
+                                        commit,
+                                    ))
+                                    .child(time_ago(locale, commit.timestamp)),
+                            )
+                    })),
+            )
+            .into_any_element()
+    }
+}
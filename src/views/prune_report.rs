@@ -0,0 +1,276 @@
+#![allow(dead_code)]
+
+use crate::git::remote::PruneReport;
+use crate::state::GitState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Emitted when the prune report dialog should be closed
+#[derive(Clone, Debug)]
+pub struct PruneReportDismissed;
+
+impl EventEmitter<PruneReportDismissed> for PruneReportDialog {}
+
+/// Summary of remote-tracking branches removed by a prune-enabled fetch,
+/// with an option to delete the local branches that tracked them
+pub struct PruneReportDialog {
+    git_state: Entity<GitState>,
+    report: PruneReport,
+    /// Orphaned local branches selected for deletion
+    selected: Vec<String>,
+    /// Branches that could not be deleted
+    errors: Vec<String>,
+}
+
+impl PruneReportDialog {
+    pub fn new(git_state: Entity<GitState>, report: PruneReport, _cx: &mut Context<Self>) -> Self {
+        // Preselect only branches whose commits are already in HEAD
+        let selected = report
+            .orphaned
+            .iter()
+            .filter(|b| b.is_merged)
+            .map(|b| b.name.clone())
+            .collect();
+
+        Self {
+            git_state,
+            report,
+            selected,
+            errors: Vec::new(),
+        }
+    }
+
+    fn toggle_branch(&mut self, name: String, cx: &mut Context<Self>) {
+        if let Some(pos) = self.selected.iter().position(|n| *n == name) {
+            self.selected.remove(pos);
+        } else {
+            self.selected.push(name);
+        }
+        cx.notify();
+    }
+
+    fn delete_selected(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        let selected = std::mem::take(&mut self.selected);
+        let mut failed = Vec::new();
+
+        self.git_state.update(cx, |state, cx| {
+            for name in &selected {
+                if let Err(e) = state.delete_branch(name, false, cx) {
+                    log::error!("Failed to delete branch {}: {}", name, e);
+                    failed.push((name.clone(), e.to_string()));
+                }
+            }
+        });
+
+        if failed.is_empty() {
+            cx.emit(PruneReportDismissed);
+            return;
+        }
+
+        // Keep only the branches that are still around
+        self.report
+            .orphaned
+            .retain(|b| failed.iter().any(|(name, _)| *name == b.name));
+        self.errors = failed
+            .into_iter()
+            .map(|(name, error)| format!("{}: {}", name, error))
+            .collect();
+        cx.notify();
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(PruneReportDismissed);
+    }
+}
+
+impl Render for PruneReportDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let pruned_count = self.report.pruned.len();
+        let has_selection = !self.selected.is_empty();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_4()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(theme.text)
+                            .child("Remote Branches Pruned"),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .child(format!(
+                                "{} remote-tracking branch{} removed from {}",
+                                pruned_count,
+                                if pruned_count == 1 { " was" } else { "es were" },
+                                self.report.remote
+                            )),
+                    ),
+            )
+            // Pruned remote branches
+            .child(
+                div()
+                    .id("pruned-list-scroll")
+                    .max_h(px(120.0))
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .children(self.report.pruned.iter().map(|name| {
+                        div()
+                            .text_sm()
+                            .text_color(theme.overlay0)
+                            .child(name.clone())
+                    })),
+            )
+            // Orphaned local branches
+            .when(!self.report.orphaned.is_empty(), |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_2()
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(theme.overlay2)
+                                .child("These local branches tracked a removed branch. Delete them?"),
+                        )
+                        .children(self.report.orphaned.iter().map(|branch| {
+                            let name = branch.name.clone();
+                            let is_selected = self.selected.contains(&name);
+
+                            div()
+                                .id(ElementId::Name(format!("orphaned-{}", name).into()))
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .px_2()
+                                .py_1()
+                                .rounded_md()
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface0))
+                                .child(
+                                    div()
+                                        .w_4()
+                                        .h_4()
+                                        .flex()
+                                        .items_center()
+                                        .justify_center()
+                                        .rounded_sm()
+                                        .border_1()
+                                        .border_color(theme.overlay0)
+                                        .when(is_selected, |this| {
+                                            this.bg(theme.blue)
+                                                .border_color(theme.blue)
+                                                .text_xs()
+                                                .text_color(theme.base)
+                                                .child("✓")
+                                        }),
+                                )
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(theme.text)
+                                        .child(name.clone()),
+                                )
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(if branch.is_merged {
+                                            theme.green
+                                        } else {
+                                            theme.peach
+                                        })
+                                        .child(if branch.is_merged {
+                                            "merged"
+                                        } else {
+                                            "has unmerged commits"
+                                        }),
+                                )
+                                .on_click(cx.listener(move |this, _event, _window, cx| {
+                                    this.toggle_branch(name.clone(), cx);
+                                }))
+                        })),
+                )
+            })
+            // Deletion errors
+            .children(self.errors.iter().map(|error| {
+                div()
+                    .text_xs()
+                    .text_color(theme.red)
+                    .child(error.clone())
+            }))
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id("prune-keep-btn")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child(if self.report.orphaned.is_empty() {
+                                "Close"
+                            } else {
+                                "Keep All"
+                            })
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    )
+                    .when(!self.report.orphaned.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .id("prune-delete-btn")
+                                .px_3()
+                                .py_1()
+                                .rounded_md()
+                                .bg(if has_selection {
+                                    theme.red
+                                } else {
+                                    theme.surface1
+                                })
+                                .text_sm()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(theme.base)
+                                .when(has_selection, |this| {
+                                    this.cursor_pointer()
+                                        .hover(|s| s.bg(theme.maroon))
+                                        .on_click(cx.listener(|this, _event, window, cx| {
+                                            this.delete_selected(window, cx);
+                                        }))
+                                })
+                                .child(format!("Delete Selected ({})", self.selected.len())),
+                        )
+                    }),
+            )
+    }
+}
@@ -0,0 +1,315 @@
+#![allow(dead_code)]
+
+use crate::git::{ReflogEntry, ResetMode};
+use crate::components::CopyDetailsButton;
+use crate::state::{ErrorDetails, GitState};
+use crate::theme::{ActiveTheme, Theme};
+use gpui::prelude::*;
+use gpui::*;
+
+/// Most reflog entries listed
+const REFLOG_LIMIT: usize = 200;
+
+/// Events emitted by the reflog view
+#[derive(Clone, Debug)]
+pub enum ReflogEvent {
+    /// Undo the most recent destructive operation
+    Undo,
+    Dismissed,
+}
+
+impl EventEmitter<ReflogEvent> for ReflogView {}
+
+/// Browses where HEAD has been, so commits lost to a reset, rebase or
+/// deleted branch can be found and restored
+pub struct ReflogView {
+    git_state: Entity<GitState>,
+    entries: Vec<ReflogEntry>,
+    /// Entry awaiting a second click to confirm a hard reset
+    pending_reset: Option<usize>,
+    error: Option<ErrorDetails>,
+}
+
+impl ReflogView {
+    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+        cx.observe(&git_state, |this, _git_state, cx| {
+            this.reload(cx);
+        })
+        .detach();
+
+        let mut view = Self {
+            git_state,
+            entries: Vec::new(),
+            pending_reset: None,
+            error: None,
+        };
+        view.reload(cx);
+        view
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        match self.git_state.read(cx).head_reflog(REFLOG_LIMIT) {
+            Ok(entries) => self.entries = entries,
+            Err(e) => {
+                log::error!("Failed to read reflog: {}", e);
+                self.entries.clear();
+                self.error = Some(self.git_state.read(cx).error_details("Read reflog", &e));
+            }
+        }
+        cx.notify();
+    }
+
+    fn checkout(&mut self, sha: String, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.checkout_commit(&sha, cx));
+        self.set_result(result, "checkout reflog entry", cx);
+    }
+
+    /// Hard-reset the current branch to an entry; the reset is itself undoable
+    fn reset_to(&mut self, index: usize, sha: String, cx: &mut Context<Self>) {
+        if self.pending_reset != Some(index) {
+            self.pending_reset = Some(index);
+            cx.notify();
+            return;
+        }
+
+        self.pending_reset = None;
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.reset_to_commit(&sha, ResetMode::Hard, cx));
+        self.set_result(result, "reset to reflog entry", cx);
+    }
+
+    fn set_result(&mut self, result: anyhow::Result<()>, action: &str, cx: &mut Context<Self>) {
+        match result {
+            Ok(()) => self.error = None,
+            Err(e) => {
+                log::error!("Failed to {}: {}", action, e);
+                self.error = Some(self.git_state.read(cx).error_details(action, &e));
+            }
+        }
+        cx.notify();
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(ReflogEvent::Dismissed);
+    }
+}
+
+impl Render for ReflogView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let last_operation = self
+            .git_state
+            .read(cx)
+            .last_operation()
+            .map(|operation| operation.label.clone());
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Reflog"),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .child("Where HEAD has been, newest first"),
+                            ),
+                    )
+                    .when_some(last_operation, |this, label| {
+                        this.child(
+                            div()
+                                .id("reflog-undo")
+                                .px_3()
+                                .py_1()
+                                .rounded_md()
+                                .bg(theme.mauve)
+                                .text_sm()
+                                .text_color(theme.base)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.pink))
+                                .child(format!("Undo: {}", label))
+                                .on_click(cx.listener(|_this, _event, _window, cx| {
+                                    cx.emit(ReflogEvent::Undo);
+                                })),
+                        )
+                    })
+                    .child(
+                        div()
+                            .id("close-reflog")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error.message)
+                        .child(CopyDetailsButton::new("reflog-error-details", error.report)),
+                )
+            })
+            // Entries
+            .child(
+                div()
+                    .id("reflog-list")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .gap_px()
+                    .when(self.entries.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .py_8()
+                                .text_sm()
+                                .text_center()
+                                .text_color(theme.overlay0)
+                                .child("The reflog is empty"),
+                        )
+                    })
+                    .children(
+                        self.entries
+                            .iter()
+                            .map(|entry| self.render_entry(entry, &theme, cx)),
+                    ),
+            )
+    }
+}
+
+impl ReflogView {
+    fn render_entry(
+        &self,
+        entry: &ReflogEntry,
+        theme: &Theme,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let index = entry.index;
+        let is_pending_reset = self.pending_reset == Some(index);
+        let sha_checkout = entry.new_sha.clone();
+        let sha_reset = entry.new_sha.clone();
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .hover(|s| s.bg(theme.surface0))
+            .child(
+                div()
+                    .w_16()
+                    .flex_shrink_0()
+                    .text_xs()
+                    .text_color(theme.overlay0)
+                    .child(format!("HEAD@{{{}}}", index)),
+            )
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .font_family("monospace")
+                    .text_xs()
+                    .text_color(theme.blue)
+                    .child(entry.short_sha.clone()),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .truncate()
+                    .text_sm()
+                    .text_color(theme.text)
+                    .child(entry.message.clone()),
+            )
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .text_xs()
+                    .text_color(theme.overlay0)
+                    .child(entry.timestamp.format("%Y-%m-%d %H:%M").to_string()),
+            )
+            .child(
+                reflog_button(format!("reflog-checkout-{}", index), "Checkout", theme).on_click(
+                    cx.listener(move |this, _event, _window, cx| {
+                        this.checkout(sha_checkout.clone(), cx);
+                    }),
+                ),
+            )
+            .child(
+                div()
+                    .id(ElementId::Name(format!("reflog-reset-{}", index).into()))
+                    .px_2()
+                    .py_1()
+                    .rounded_md()
+                    .text_xs()
+                    .when(is_pending_reset, |this| this.bg(theme.red).text_color(theme.base))
+                    .when(!is_pending_reset, |this| {
+                        this.bg(theme.surface0).text_color(theme.red)
+                    })
+                    .cursor_pointer()
+                    .hover(|s| s.bg(theme.maroon).text_color(theme.base))
+                    .child(if is_pending_reset { "Confirm" } else { "Reset here" })
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.reset_to(index, sha_reset.clone(), cx);
+                    })),
+            )
+    }
+}
+
+fn reflog_button(id: String, label: &'static str, theme: &Theme) -> Stateful<Div> {
+    div()
+        .id(ElementId::Name(id.into()))
+        .px_2()
+        .py_1()
+        .rounded_md()
+        .bg(theme.surface0)
+        .text_xs()
+        .text_color(theme.text)
+        .cursor_pointer()
+        .hover(|s| s.bg(theme.surface1))
+        .child(label)
+}
@@ -0,0 +1,247 @@
+#![allow(dead_code)]
+
+use super::settings::SettingsButton;
+use crate::components::{TextInputChanged, TextInputView};
+use crate::i18n::{t, ActiveLocale, Locale};
+use crate::state::{AuthMode, MergeMode, RepoSettings, SettingsState};
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+use std::path::PathBuf;
+
+/// Repository tab of the settings view: identity, default remote, merge
+/// mode and auth method overrides for the open repository
+pub struct RepoSettingsView {
+    settings: Entity<SettingsState>,
+    path: PathBuf,
+    /// Remotes configured in the repository
+    remotes: Vec<String>,
+    name_input: Entity<TextInputView>,
+    email_input: Entity<TextInputView>,
+}
+
+impl RepoSettingsView {
+    pub fn new(
+        settings: Entity<SettingsState>,
+        path: PathBuf,
+        remotes: Vec<String>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let repo = settings.read(cx).repo_settings();
+        let placeholder = t(cx.locale(), "settings.repoUseGitConfig");
+        let name_input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .with_placeholder(placeholder.clone())
+                .with_content(repo.user_name.unwrap_or_default())
+        });
+        let email_input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .with_placeholder(placeholder)
+                .with_content(repo.user_email.unwrap_or_default())
+        });
+
+        // Save identity overrides as they are typed; empty means git config
+        cx.subscribe(&name_input, |this, _input, event: &TextInputChanged, cx| {
+            let name = non_empty(&event.0);
+            this.update_repo(move |repo| repo.user_name = name, cx);
+        })
+        .detach();
+        cx.subscribe(&email_input, |this, _input, event: &TextInputChanged, cx| {
+            let email = non_empty(&event.0);
+            this.update_repo(move |repo| repo.user_email = email, cx);
+        })
+        .detach();
+
+        cx.observe(&settings, |_this, _settings, cx| {
+            cx.notify();
+        })
+        .detach();
+        // Placeholders aren't re-rendered, so translate them again when the
+        // language changes
+        cx.observe_global::<Locale>(|this, cx| {
+            let placeholder = t(cx.locale(), "settings.repoUseGitConfig");
+            for input in [&this.name_input, &this.email_input] {
+                input.update(cx, |input, cx| input.set_placeholder(placeholder.clone(), cx));
+            }
+        })
+        .detach();
+
+        Self {
+            settings,
+            path,
+            remotes,
+            name_input,
+            email_input,
+        }
+    }
+
+    fn update_repo(&self, f: impl FnOnce(&mut RepoSettings), cx: &mut Context<Self>) {
+        self.settings
+            .update(cx, |settings, cx| settings.update_repo_settings(f, cx));
+    }
+
+    /// Drop every override for the repository
+    fn reset(&mut self, cx: &mut Context<Self>) {
+        self.update_repo(|repo| *repo = RepoSettings::default(), cx);
+        self.name_input.update(cx, |input, cx| input.set_content("", cx));
+        self.email_input.update(cx, |input, cx| input.set_content("", cx));
+    }
+}
+
+impl Render for RepoSettingsView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let settings = self.settings.read(cx);
+        let locale = cx.locale();
+        let repo = settings.repo_settings();
+        let default_remote = settings.default_remote();
+
+        let section = |title: String| {
+            div()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(theme.blue)
+                .child(title)
+        };
+        let row = |label: String| {
+            div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .gap_3()
+                .child(
+                    div()
+                        .flex_shrink_0()
+                        .text_sm()
+                        .text_color(theme.overlay2)
+                        .child(label),
+                )
+        };
+
+        let remote_buttons = self.remotes.iter().map(|name| {
+            let remote = name.clone();
+            SettingsButton::new(name.clone(), *name == default_remote)
+                .id(ElementId::Name(format!("repo-remote-{}", name).into()))
+                .on_click(cx.listener(move |this, _event, _window, cx| {
+                    let remote = remote.clone();
+                    this.update_repo(move |repo| repo.default_remote = Some(remote), cx);
+                }))
+        });
+
+        let merge_buttons = [
+            (None, t(locale, "settings.repoUseGlobal")),
+            (Some(MergeMode::Auto), t(locale, "settings.mergeAuto")),
+            (Some(MergeMode::FfOnly), "FF".to_string()),
+            (Some(MergeMode::NoFf), "No-FF".to_string()),
+            (Some(MergeMode::Squash), t(locale, "settings.mergeSquash")),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, (mode, label))| {
+            SettingsButton::new(label, repo.merge_mode == mode)
+                .id(ElementId::NamedInteger("repo-merge".into(), index as u64))
+                .on_click(cx.listener(move |this, _event, _window, cx| {
+                    this.update_repo(|repo| repo.merge_mode = mode, cx);
+                }))
+        });
+
+        let auth_buttons = [
+            (None, t(locale, "settings.repoUseGlobal")),
+            (Some(AuthMode::Https), t(locale, "auth.https")),
+            (Some(AuthMode::Ssh), t(locale, "auth.ssh")),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, (mode, label))| {
+            SettingsButton::new(label, repo.git_auth_mode == mode)
+                .id(ElementId::NamedInteger("repo-auth".into(), index as u64))
+                .on_click(cx.listener(move |this, _event, _window, cx| {
+                    this.update_repo(|repo| repo.git_auth_mode = mode, cx);
+                }))
+        });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_6()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .truncate()
+                            .child(self.path.display().to_string()),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.overlay0)
+                            .child(t(locale, "settings.repoHint")),
+                    ),
+            )
+            // Identity section
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .child(section(t(locale, "settings.repoIdentity")))
+                    .child(row(t(locale, "settings.repoName")).child(
+                        div().w_48().child(self.name_input.clone()),
+                    ))
+                    .child(row(t(locale, "settings.repoEmail")).child(
+                        div().w_48().child(self.email_input.clone()),
+                    )),
+            )
+            // Remote section
+            .when(!self.remotes.is_empty(), |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_3()
+                        .child(section(t(locale, "settings.repoRemote")))
+                        .child(div().flex().flex_wrap().gap_1().children(remote_buttons)),
+                )
+            })
+            // Merge section
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .child(section(t(locale, "settings.merge")))
+                    .child(div().flex().flex_wrap().gap_1().children(merge_buttons)),
+            )
+            // Auth section
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .child(section(t(locale, "settings.gitAuth")))
+                    .child(
+                        row(t(locale, "settings.gitAuthMethod"))
+                            .child(div().flex().gap_1().children(auth_buttons)),
+                    ),
+            )
+            .when(!repo.is_empty(), |this| {
+                this.child(
+                    div().flex().justify_end().child(
+                        SettingsButton::new(t(locale, "settings.repoReset"), false)
+                            .id("repo-settings-reset")
+                            .on_click(cx.listener(|this, _event, _window, cx| this.reset(cx))),
+                    ),
+                )
+            })
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
@@ -0,0 +1,366 @@
+#![allow(dead_code)]
+
+use crate::actions::{ReviewDiscard, ReviewOpenEditor, ReviewPrevious, ReviewSkip, ReviewStage};
+use crate::git::{FileDiff, FileStatusType};
+use crate::state::GitState;
+use crate::theme::ActiveTheme;
+use crate::views::syntax::highlight_diff;
+use crate::views::DiffLine;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Emitted when the review is closed
+pub struct ReviewModeDismissed;
+
+/// Full-screen walk through the unstaged files, one diff at a time, with
+/// single-key stage, skip, discard and open actions
+pub struct ReviewMode {
+    git_state: Entity<GitState>,
+    focus_handle: FocusHandle,
+    /// Unstaged files when the review started, in file list order
+    queue: Vec<String>,
+    /// Position in `queue`; past the end once every file has been seen
+    index: usize,
+    diff: Option<FileDiff>,
+    staged: usize,
+    skipped: usize,
+    discarded: usize,
+    error: Option<String>,
+}
+
+impl EventEmitter<ReviewModeDismissed> for ReviewMode {}
+
+impl ReviewMode {
+    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+        let mut queue: Vec<String> = Vec::new();
+        for file in git_state.read(cx).unstaged_files() {
+            // Conflicts are resolved in the conflict dialog, not staged blind
+            if file.status != FileStatusType::Conflicted && !queue.contains(&file.path) {
+                queue.push(file.path.clone());
+            }
+        }
+
+        let mut view = Self {
+            git_state,
+            focus_handle: cx.focus_handle(),
+            queue,
+            index: 0,
+            diff: None,
+            staged: 0,
+            skipped: 0,
+            discarded: 0,
+            error: None,
+        };
+        view.load_current(cx);
+        view
+    }
+
+    pub fn focus_handle(&self) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+
+    fn current_path(&self) -> Option<&String> {
+        self.queue.get(self.index)
+    }
+
+    fn is_untracked(&self, path: &str, cx: &App) -> bool {
+        self.git_state
+            .read(cx)
+            .files
+            .iter()
+            .any(|f| f.path == path && f.status == FileStatusType::Untracked)
+    }
+
+    fn load_current(&mut self, cx: &mut Context<Self>) {
+        self.diff = None;
+        self.error = None;
+        if let Some(path) = self.current_path().cloned() {
+            match self.git_state.read(cx).working_diff(&path) {
+                Ok(diff) => self.diff = Some(diff),
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+        cx.notify();
+    }
+
+    fn advance(&mut self, cx: &mut Context<Self>) {
+        self.index = (self.index + 1).min(self.queue.len());
+        self.load_current(cx);
+    }
+
+    fn stage(&mut self, _: &ReviewStage, _window: &mut Window, cx: &mut Context<Self>) {
+        let Some(path) = self.current_path().cloned() else {
+            return;
+        };
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.stage_file(&path, cx));
+        match result {
+            Ok(()) => {
+                self.staged += 1;
+                self.advance(cx);
+            }
+            Err(e) => {
+                log::error!("Failed to stage file: {}", e);
+                self.error = Some(e.to_string());
+                cx.notify();
+            }
+        }
+    }
+
+    fn skip(&mut self, _: &ReviewSkip, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.current_path().is_some() {
+            self.skipped += 1;
+            self.advance(cx);
+        }
+    }
+
+    /// Discard without a confirmation dialog; the keypress is the
+    /// confirmation, and the trash backup setting still applies
+    fn discard(&mut self, _: &ReviewDiscard, _window: &mut Window, cx: &mut Context<Self>) {
+        let Some(path) = self.current_path().cloned() else {
+            return;
+        };
+        if self.is_untracked(&path, cx) {
+            self.error = Some("Untracked files can't be discarded".to_string());
+            cx.notify();
+            return;
+        }
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.discard_file_now(&path, cx));
+        match result {
+            Ok(()) => {
+                self.discarded += 1;
+                self.advance(cx);
+            }
+            Err(e) => {
+                log::error!("Failed to discard file: {}", e);
+                self.error = Some(e.to_string());
+                cx.notify();
+            }
+        }
+    }
+
+    fn open_editor(&mut self, _: &ReviewOpenEditor, _window: &mut Window, cx: &mut Context<Self>) {
+        let Some(path) = self.current_path() else {
+            return;
+        };
+        if let Some(repo) = self.git_state.read(cx).path.clone() {
+            cx.open_with_system(&repo.join(path));
+        }
+    }
+
+    /// Step back to the previous file to look at it again
+    fn previous(&mut self, _: &ReviewPrevious, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.index > 0 {
+            self.index -= 1;
+            self.load_current(cx);
+        }
+    }
+
+    fn dismiss(&mut self, cx: &mut Context<Self>) {
+        cx.emit(ReviewModeDismissed);
+    }
+}
+
+impl Render for ReviewMode {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let total = self.queue.len();
+        let finished = self.index >= total;
+        let path = self.current_path().cloned();
+        let highlights = self
+            .diff
+            .as_ref()
+            .and_then(|diff| highlight_diff(diff, diff.lines.len(), theme.appearance));
+
+        let key_hint = |key: &'static str, label: &'static str| {
+            div()
+                .flex()
+                .items_center()
+                .gap_1()
+                .child(
+                    div()
+                        .px_1()
+                        .rounded_sm()
+                        .bg(theme.surface0)
+                        .text_color(theme.text)
+                        .font_family("monospace")
+                        .child(key),
+                )
+                .child(div().text_color(theme.overlay2).child(label))
+        };
+
+        div()
+            .key_context("ReviewMode")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::stage))
+            .on_action(cx.listener(Self::skip))
+            .on_action(cx.listener(Self::discard))
+            .on_action(cx.listener(Self::open_editor))
+            .on_action(cx.listener(Self::previous))
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .px_4()
+                    .py_3()
+                    .bg(theme.mantle)
+                    .border_b_1()
+                    .border_color(theme.surface0)
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .overflow_hidden()
+                            .child(
+                                div()
+                                    .flex_shrink_0()
+                                    .text_sm()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Review Changes"),
+                            )
+                            .child(
+                                div()
+                                    .flex_shrink_0()
+                                    .text_xs()
+                                    .text_color(theme.overlay0)
+                                    .child(format!("{} / {}", (self.index + 1).min(total), total)),
+                            )
+                            .when_some(path.clone(), |this, path| {
+                                this.child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(theme.text)
+                                        .text_ellipsis()
+                                        .child(path),
+                                )
+                            })
+                            .when_some(self.diff.as_ref(), |this, diff| {
+                                this.child(
+                                    div()
+                                        .flex()
+                                        .gap_2()
+                                        .flex_shrink_0()
+                                        .text_xs()
+                                        .child(
+                                            div()
+                                                .text_color(theme.green)
+                                                .child(format!("+{}", diff.additions)),
+                                        )
+                                        .child(
+                                            div()
+                                                .text_color(theme.red)
+                                                .child(format!("-{}", diff.deletions)),
+                                        ),
+                                )
+                            }),
+                    )
+                    .child(
+                        div()
+                            .id("close-review")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, _window, cx| this.dismiss(cx))),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .px_4()
+                        .py_2()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error),
+                )
+            })
+            // Diff, or a summary once every file has been seen
+            .child(if finished {
+                div()
+                    .id("review-diff")
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .items_center()
+                    .justify_center()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child(if total == 0 {
+                                "No unstaged changes to review".to_string()
+                            } else {
+                                format!("Reviewed {} files", total)
+                            }),
+                    )
+                    .child(div().text_sm().text_color(theme.overlay2).child(format!(
+                        "{} staged, {} skipped, {} discarded",
+                        self.staged, self.skipped, self.discarded
+                    )))
+            } else {
+                div()
+                    .id("review-diff")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .p_2()
+                    .when_some(self.diff.as_ref(), |this, diff| {
+                        this.when(diff.lines.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .py_8()
+                                    .text_center()
+                                    .text_sm()
+                                    .text_color(theme.overlay0)
+                                    .child("No unstaged changes left in this file"),
+                            )
+                        })
+                        .children(diff.lines.iter().enumerate().map(|(ix, line)| {
+                            let spans = highlights
+                                .as_ref()
+                                .and_then(|h| h.get(ix))
+                                .cloned()
+                                .unwrap_or_default();
+                            DiffLine::new(line.clone()).with_highlights(spans)
+                        }))
+                    })
+            })
+            // Key hints
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_4()
+                    .px_4()
+                    .py_2()
+                    .bg(theme.mantle)
+                    .border_t_1()
+                    .border_color(theme.surface0)
+                    .text_xs()
+                    .child(key_hint("S", "Stage"))
+                    .child(key_hint("N", "Skip"))
+                    .child(key_hint("D", "Discard"))
+                    .child(key_hint("E", "Open in editor"))
+                    .child(key_hint("P", "Previous"))
+                    .child(key_hint("Esc", "Close")),
+            )
+    }
+}
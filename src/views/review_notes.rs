@@ -0,0 +1,477 @@
+#![allow(dead_code)]
+
+use crate::components::{TextInputChanged, TextInputView};
+use crate::state::{GitState, NoteAnchor, ReviewNote, ReviewNotes};
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+use std::path::PathBuf;
+
+/// Emitted when the notes panel should be closed
+#[derive(Clone, Debug)]
+pub struct ReviewNotesDismissed;
+
+impl EventEmitter<ReviewNotesDismissed> for ReviewNotesView {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AnchorKind {
+    General,
+    File,
+    Commit,
+}
+
+/// Per-repository scratchpad for review notes, exportable as Markdown
+pub struct ReviewNotesView {
+    git_state: Entity<GitState>,
+    review_notes: Entity<ReviewNotes>,
+    anchor_kind: AnchorKind,
+    /// File path or commit revision, depending on `anchor_kind`
+    target_input: Entity<TextInputView>,
+    line_input: Entity<TextInputView>,
+    note_input: Entity<TextInputView>,
+    error: Option<String>,
+    /// Set after the Markdown export was copied
+    copied: bool,
+}
+
+impl ReviewNotesView {
+    pub fn new(
+        git_state: Entity<GitState>,
+        review_notes: Entity<ReviewNotes>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let target_input = cx.new(TextInputView::new);
+        let line_input = cx.new(|cx| TextInputView::new(cx).with_placeholder("Line (optional)"));
+        let note_input = cx.new(|cx| TextInputView::new(cx).with_placeholder("Write a note..."));
+
+        for input in [&target_input, &line_input, &note_input] {
+            cx.subscribe(input, |this, _input, _event: &TextInputChanged, cx| {
+                this.copied = false;
+                cx.notify();
+            })
+            .detach();
+        }
+
+        cx.observe(&review_notes, |_this, _notes, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            git_state,
+            review_notes,
+            anchor_kind: AnchorKind::General,
+            target_input,
+            line_input,
+            note_input,
+            error: None,
+            copied: false,
+        }
+    }
+
+    fn repository_path(&self, cx: &App) -> Option<PathBuf> {
+        self.git_state.read(cx).path.clone()
+    }
+
+    /// Switch the anchor type, prefilling the target from what is open
+    fn set_anchor_kind(&mut self, kind: AnchorKind, cx: &mut Context<Self>) {
+        self.anchor_kind = kind;
+        self.error = None;
+
+        let git_state = self.git_state.read(cx);
+        let (placeholder, prefill) = match kind {
+            AnchorKind::General => ("", None),
+            AnchorKind::File => (
+                "File path",
+                git_state
+                    .current_diff
+                    .as_ref()
+                    .map(|d| d.path.clone())
+                    .or_else(|| git_state.selected_files.first().cloned()),
+            ),
+            AnchorKind::Commit => (
+                "Commit SHA or ref",
+                Some(
+                    git_state
+                        .selected_commit
+                        .as_ref()
+                        .map(|c| c.short_sha.clone())
+                        .unwrap_or_else(|| "HEAD".to_string()),
+                ),
+            ),
+        };
+
+        self.target_input.update(cx, |input, cx| {
+            input.set_placeholder(placeholder, cx);
+            input.set_content(prefill.unwrap_or_default(), cx);
+        });
+        cx.notify();
+    }
+
+    fn build_anchor(&self, cx: &App) -> Result<NoteAnchor, String> {
+        let target = self.target_input.read(cx).content().trim().to_string();
+
+        match self.anchor_kind {
+            AnchorKind::General => Ok(NoteAnchor::General),
+            AnchorKind::File => {
+                if target.is_empty() {
+                    return Err("Enter a file path".to_string());
+                }
+                let line = self.line_input.read(cx).content().trim().to_string();
+                let line = if line.is_empty() {
+                    None
+                } else {
+                    Some(
+                        line.parse::<u32>()
+                            .map_err(|_| format!("Invalid line number: {}", line))?,
+                    )
+                };
+                Ok(NoteAnchor::File { path: target, line })
+            }
+            AnchorKind::Commit => {
+                let (sha, summary) = self
+                    .git_state
+                    .read(cx)
+                    .resolve_commit(&target)
+                    .map_err(|e| format!("Unknown commit '{}': {}", target, e))?;
+                Ok(NoteAnchor::Commit { sha, summary })
+            }
+        }
+    }
+
+    fn add_note(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.note_input.read(cx).content().trim().to_string();
+        let Some(repo) = self.repository_path(cx) else {
+            return;
+        };
+        if text.is_empty() {
+            return;
+        }
+
+        match self.build_anchor(cx) {
+            Ok(anchor) => {
+                self.review_notes.update(cx, |notes, cx| {
+                    notes.add_note(&repo, anchor, text, cx);
+                });
+                self.note_input.update(cx, |input, cx| input.set_content("", cx));
+                self.line_input.update(cx, |input, cx| input.set_content("", cx));
+                self.error = None;
+            }
+            Err(error) => self.error = Some(error),
+        }
+        cx.notify();
+    }
+
+    fn remove_note(&mut self, id: u64, cx: &mut Context<Self>) {
+        if let Some(repo) = self.repository_path(cx) {
+            self.review_notes.update(cx, |notes, cx| {
+                notes.remove_note(&repo, id, cx);
+            });
+        }
+    }
+
+    fn copy_markdown(&mut self, cx: &mut Context<Self>) {
+        let Some(repo) = self.repository_path(cx) else {
+            return;
+        };
+        let markdown = self.review_notes.read(cx).to_markdown(&repo);
+        cx.write_to_clipboard(ClipboardItem::new_string(markdown));
+        self.copied = true;
+        cx.notify();
+    }
+
+    fn clear_notes(&mut self, cx: &mut Context<Self>) {
+        if let Some(repo) = self.repository_path(cx) {
+            self.review_notes.update(cx, |notes, cx| notes.clear(&repo, cx));
+        }
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(ReviewNotesDismissed);
+    }
+}
+
+impl Render for ReviewNotesView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let groups = self
+            .repository_path(cx)
+            .map(|repo| self.review_notes.read(cx).grouped(&repo))
+            .unwrap_or_default();
+        let has_notes = !groups.is_empty();
+        let can_add = !self.note_input.read(cx).content().trim().is_empty();
+        let anchor_kind = self.anchor_kind;
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Review Notes"),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .child("Private notes for this repository, stored outside it"),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("close-review-notes")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    ),
+            )
+            // Notes
+            .child(
+                div()
+                    .id("review-notes-list")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .when(!has_notes, |this| {
+                        this.child(
+                            div()
+                                .text_sm()
+                                .text_color(theme.overlay0)
+                                .child("No notes yet"),
+                        )
+                    })
+                    .children(groups.into_iter().map(|(anchor, notes)| {
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(theme.blue)
+                                    .child(anchor.heading()),
+                            )
+                            .children(notes.iter().map(|note| self.render_note(note, cx)))
+                    })),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_sm().text_color(theme.red).child(error))
+            })
+            // Add form
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .children(
+                                [
+                                    (AnchorKind::General, "General"),
+                                    (AnchorKind::File, "File"),
+                                    (AnchorKind::Commit, "Commit"),
+                                ]
+                                .into_iter()
+                                .map(|(kind, label)| {
+                                    let is_selected = anchor_kind == kind;
+                                    div()
+                                        .id(ElementId::Name(
+                                            format!("note-anchor-{}", label).into(),
+                                        ))
+                                        .px_2()
+                                        .py_px()
+                                        .rounded_md()
+                                        .text_xs()
+                                        .cursor_pointer()
+                                        .bg(if is_selected {
+                                            theme.blue
+                                        } else {
+                                            theme.surface0
+                                        })
+                                        .text_color(if is_selected {
+                                            theme.base
+                                        } else {
+                                            theme.text
+                                        })
+                                        .when(!is_selected, |this| {
+                                            this.hover(|s| s.bg(theme.surface1))
+                                        })
+                                        .child(label)
+                                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                                            this.set_anchor_kind(kind, cx);
+                                        }))
+                                }),
+                            ),
+                    )
+                    .when(anchor_kind != AnchorKind::General, |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .gap_2()
+                                .child(div().flex_1().child(self.target_input.clone()))
+                                .when(anchor_kind == AnchorKind::File, |this| {
+                                    this.child(div().w_32().child(self.line_input.clone()))
+                                }),
+                        )
+                    })
+                    .child(self.note_input.clone()),
+            )
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .id("notes-copy-markdown")
+                                    .px_3()
+                                    .py_1()
+                                    .rounded_md()
+                                    .bg(theme.surface0)
+                                    .text_sm()
+                                    .text_color(if has_notes {
+                                        theme.text
+                                    } else {
+                                        theme.overlay0
+                                    })
+                                    .when(has_notes, |this| {
+                                        this.cursor_pointer()
+                                            .hover(|s| s.bg(theme.surface1))
+                                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                                this.copy_markdown(cx);
+                                            }))
+                                    })
+                                    .child(if self.copied {
+                                        "Copied!"
+                                    } else {
+                                        "Copy as Markdown"
+                                    }),
+                            )
+                            .when(has_notes, |this| {
+                                this.child(
+                                    div()
+                                        .id("notes-clear")
+                                        .px_3()
+                                        .py_1()
+                                        .rounded_md()
+                                        .text_sm()
+                                        .text_color(theme.red)
+                                        .cursor_pointer()
+                                        .hover(|s| s.bg(theme.surface0))
+                                        .child("Clear All")
+                                        .on_click(cx.listener(|this, _event, _window, cx| {
+                                            this.clear_notes(cx);
+                                        })),
+                                )
+                            }),
+                    )
+                    .child(
+                        div()
+                            .id("notes-add-btn")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(if can_add {
+                                theme.blue
+                            } else {
+                                theme.surface1
+                            })
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.base)
+                            .when(can_add, |this| {
+                                this.cursor_pointer()
+                                    .hover(|s| s.bg(theme.lavender))
+                                    .on_click(cx.listener(|this, _event, window, cx| {
+                                        this.add_note(window, cx);
+                                    }))
+                            })
+                            .child("Add Note"),
+                    ),
+            )
+    }
+}
+
+impl ReviewNotesView {
+    fn render_note(&self, note: &ReviewNote, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let id = note.id;
+        let line = match note.anchor {
+            NoteAnchor::File { line: Some(line), .. } => Some(format!("L{}", line)),
+            _ => None,
+        };
+
+        div()
+            .flex()
+            .items_start()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .hover(|s| s.bg(theme.surface0))
+            .when_some(line, |this, line| {
+                this.child(div().text_xs().text_color(theme.yellow).child(line))
+            })
+            .child(
+                div()
+                    .flex_1()
+                    .text_sm()
+                    .text_color(theme.text)
+                    .child(note.text.clone()),
+            )
+            .child(
+                div()
+                    .id(ElementId::Name(format!("remove-note-{}", id).into()))
+                    .px_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .text_color(theme.overlay0)
+                    .cursor_pointer()
+                    .hover(|s| s.text_color(theme.red))
+                    .child("×")
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.remove_note(id, cx);
+                    })),
+            )
+    }
+}
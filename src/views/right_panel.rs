@@ -1,23 +1,68 @@
-use crate::components::{TextInputChanged, TextInputView};
-use crate::git::CommitInfo;
-use crate::state::GitState;
-use crate::views::CommitGraph;
-use chrono::Datelike;
+use crate::components::dropdown::{Dropdown, DropdownOption};
+use crate::components::{
+    AuthorAvatar, AuthorLink, SignatureBadge, TextInputChanged, TextInputView,
+};
+use crate::git::{AuthorSummary, BranchKind, CommitInfo, CommitSignature, GraphFilter};
+use crate::i18n::{t_plural, ActiveLocale};
+use crate::state::{AvatarState, GitState, PICKAXE_RESULT_LIMIT};
+use crate::theme::ActiveTheme;
+use crate::views::{CommitGraph, ROW_HEIGHT};
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
 use gpui::prelude::*;
 use gpui::*;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Typing pause before a content search starts, so each keystroke doesn't
+/// start a walk of the whole history
+const PICKAXE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Most refs listed under the jump field
+const JUMP_REF_LIMIT: usize = 8;
+
+/// Selects the commit of a clicked search result
+type ClickHandler = Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
 
 pub struct RightPanel {
     git_state: Entity<GitState>,
+    avatars: Entity<AvatarState>,
     commit_graph: Entity<CommitGraph>,
     search_input: Entity<TextInputView>,
     search_query: String,
     search_results: Vec<CommitInfo>,
+    /// Search commits by the content they add or remove instead of by
+    /// message, author or SHA
+    search_changes: bool,
+    scroll_handle: ScrollHandle,
+    /// Last focused commit scrolled to, so the graph only jumps on change
+    scrolled_to: Option<String>,
+    show_filter: bool,
+    /// Show the field for jumping to a branch, tag or SHA
+    show_jump: bool,
+    jump_input: Entity<TextInputView>,
+    jump_error: Option<String>,
+    branch_picker_open: bool,
+    /// Branch picked for the filter, applied with the other fields
+    filter_branch: Option<String>,
+    author_input: Entity<TextInputView>,
+    since_input: Entity<TextInputView>,
+    until_input: Entity<TextInputView>,
+    path_input: Entity<TextInputView>,
+    /// Filter last applied from here, to notice when it is reset elsewhere
+    applied_filter: GraphFilter,
+    filter_error: Option<String>,
 }
 
 impl RightPanel {
-    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+    pub fn new(
+        git_state: Entity<GitState>,
+        avatars: Entity<AvatarState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
         let git_state_clone = git_state.clone();
-        let commit_graph = cx.new(|cx| CommitGraph::new(git_state_clone.clone(), cx));
+        let avatars_clone = avatars.clone();
+        let commit_graph =
+            cx.new(|cx| CommitGraph::new(git_state_clone.clone(), avatars_clone, cx));
 
         // Create search input
         let search_input = cx.new(|cx| {
@@ -25,36 +70,683 @@ impl RightPanel {
         });
 
         // Handle search input changes via subscription
-        let git_state_for_search = git_state.clone();
-        cx.subscribe(&search_input, move |this, _input, event: &TextInputChanged, cx| {
+        cx.subscribe(&search_input, |this, _input, event: &TextInputChanged, cx| {
             this.search_query = event.0.to_string();
-            // Search commits
-            let results = git_state_for_search
-                .read(cx)
-                .search_commits(&this.search_query, 50);
-            this.search_results = results;
+            this.run_search(cx);
+        })
+        .detach();
+
+        cx.observe(&git_state, |this, git_state, cx| {
+            // Closing the repository or clicking an author changes the filter
+            let filter = git_state.read(cx).graph_filter.clone();
+            if filter != this.applied_filter {
+                this.show_filter_fields(filter, cx);
+            }
+            this.scroll_to_focused_commit(cx);
+        })
+        .detach();
+
+        let panel = cx.entity().downgrade();
+        let jump_input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .with_placeholder("Branch, tag or SHA")
+                .on_submit(move |target, _window, cx| {
+                    let target = target.to_string();
+                    panel
+                        .update(cx, |this, cx| this.jump_to(&target, cx))
+                        .ok();
+                })
+        });
+        // Refs matching the typed text are listed below the field
+        cx.subscribe(&jump_input, |_this, _input, _event: &TextInputChanged, cx| {
             cx.notify();
         })
         .detach();
 
+        let author_input = cx.new(|cx| TextInputView::new(cx).with_placeholder("Name or email"));
+        let since_input = cx.new(|cx| TextInputView::new(cx).with_placeholder("YYYY-MM-DD"));
+        let until_input = cx.new(|cx| TextInputView::new(cx).with_placeholder("YYYY-MM-DD"));
+        let path_input = cx.new(|cx| TextInputView::new(cx).with_placeholder("src/ or *.rs"));
+
+        // Search results and the author summary show avatars as they load
+        cx.observe(&avatars, |_this, _avatars, cx| cx.notify()).detach();
+
         Self {
             git_state,
+            avatars,
             commit_graph,
             search_input,
             search_query: String::new(),
             search_results: Vec::new(),
+            search_changes: false,
+            scroll_handle: ScrollHandle::new(),
+            scrolled_to: None,
+            show_filter: false,
+            show_jump: false,
+            jump_input,
+            jump_error: None,
+            branch_picker_open: false,
+            filter_branch: None,
+            author_input,
+            since_input,
+            until_input,
+            path_input,
+            applied_filter: GraphFilter::default(),
+            filter_error: None,
         }
     }
 
+    /// Filter described by the filter fields
+    fn filter_from_fields(&self, cx: &App) -> Result<GraphFilter, String> {
+        let text = |input: &Entity<TextInputView>| {
+            let value = input.read(cx).content().trim();
+            (!value.is_empty()).then(|| value.to_string())
+        };
+        let since = parse_date(text(&self.since_input))?;
+        // The until date is inclusive, so the bound is the next midnight
+        let until = parse_date(text(&self.until_input))?
+            .map(|date| date.checked_add_days(Days::new(1)).unwrap_or(date));
+        Ok(GraphFilter {
+            branch: self.filter_branch.clone(),
+            author: text(&self.author_input),
+            since,
+            until,
+            path: text(&self.path_input),
+        })
+    }
+
+    fn apply_filter(&mut self, cx: &mut Context<Self>) {
+        let filter = match self.filter_from_fields(cx) {
+            Ok(filter) => filter,
+            Err(e) => {
+                self.filter_error = Some(e);
+                cx.notify();
+                return;
+            }
+        };
+        self.set_filter(filter, cx);
+    }
+
+    fn clear_filter(&mut self, cx: &mut Context<Self>) {
+        self.reset_filter_fields(cx);
+        self.set_filter(GraphFilter::default(), cx);
+    }
+
+    fn set_filter(&mut self, filter: GraphFilter, cx: &mut Context<Self>) {
+        self.applied_filter = filter.clone();
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.set_graph_filter(filter, cx));
+        match result {
+            Ok(()) => {
+                self.filter_error = None;
+                self.scroll_handle.set_offset(point(px(0.0), px(0.0)));
+            }
+            Err(e) => {
+                log::error!("Failed to filter commit graph: {}", e);
+                self.applied_filter = self.git_state.read(cx).graph_filter.clone();
+                self.filter_error = Some(e.to_string());
+            }
+        }
+        cx.notify();
+    }
+
+    fn reset_filter_fields(&mut self, cx: &mut Context<Self>) {
+        self.show_filter_fields(GraphFilter::default(), cx);
+    }
+
+    /// Fill the filter fields from a filter applied elsewhere
+    fn show_filter_fields(&mut self, filter: GraphFilter, cx: &mut Context<Self>) {
+        let date = |date: Option<DateTime<Utc>>| {
+            date.map(|date| date.format("%Y-%m-%d").to_string()).unwrap_or_default()
+        };
+        // The until field is inclusive, the filter's bound is the next midnight
+        let until = filter
+            .until
+            .map(|until| until.checked_sub_days(Days::new(1)).unwrap_or(until));
+        let fields = [
+            (&self.author_input, filter.author.clone().unwrap_or_default()),
+            (&self.since_input, date(filter.since)),
+            (&self.until_input, date(until)),
+            (&self.path_input, filter.path.clone().unwrap_or_default()),
+        ];
+        for (input, content) in fields {
+            input.update(cx, |input, cx| input.set_content(content, cx));
+        }
+        self.filter_branch = filter.branch.clone();
+        self.branch_picker_open = false;
+        self.filter_error = None;
+        self.applied_filter = filter;
+        cx.notify();
+    }
+
+    /// Bring the focused commit into view, leaving search so the graph shows
+    fn scroll_to_focused_commit(&mut self, cx: &mut Context<Self>) {
+        let git_state = self.git_state.read(cx);
+        let focused = git_state.focused_commit.clone();
+        if focused == self.scrolled_to {
+            return;
+        }
+        let index = focused.as_ref().and_then(|sha| {
+            git_state
+                .commits
+                .as_ref()?
+                .nodes
+                .iter()
+                .position(|node| &node.commit.sha == sha)
+        });
+        self.scrolled_to = focused;
+
+        if let Some(index) = index {
+            if !self.search_query.is_empty() {
+                self.clear_search(cx);
+            }
+            self.scroll_to_row(index, cx);
+        }
+    }
+
+    /// Scroll the graph so a row is near the top. Rows have a fixed height,
+    /// so the offset is known without laying out the rows above it.
+    fn scroll_to_row(&mut self, index: usize, cx: &mut Context<Self>) {
+        // Leave a couple of rows above the commit for context
+        let offset = index.saturating_sub(2) as f32 * ROW_HEIGHT;
+        self.scroll_handle.set_offset(point(px(0.0), px(-offset)));
+        cx.notify();
+    }
+
+    /// Scroll the graph to the commit a branch, tag or SHA points to
+    fn jump_to(&mut self, target: &str, cx: &mut Context<Self>) {
+        let target = target.trim();
+        if target.is_empty() {
+            return;
+        }
+        // Scroll again even if the commit was the last one jumped to
+        self.scrolled_to = None;
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.focus_ref(target, cx));
+        match result {
+            Ok(()) => {
+                self.show_jump = false;
+                self.jump_error = None;
+                self.jump_input.update(cx, |input, cx| input.set_content("", cx));
+            }
+            Err(e) => {
+                log::warn!("Failed to jump to {}: {}", target, e);
+                self.jump_error = Some(e.to_string());
+            }
+        }
+        cx.notify();
+    }
+
+    /// Select a search result and scroll the graph to it
+    fn select_search_result(&mut self, commit: CommitInfo, cx: &mut Context<Self>) {
+        // Scroll again even if the commit was the last one scrolled to
+        self.scrolled_to = None;
+        let short_sha = commit.short_sha.clone();
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.select_commit(commit, cx));
+        if let Err(e) = result {
+            log::warn!("Failed to show commit {}: {}", short_sha, e);
+        }
+    }
+
+    fn toggle_jump(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.show_jump = !self.show_jump;
+        self.jump_error = None;
+        if self.show_jump {
+            let focus_handle = self.jump_input.read(cx).focus_handle(cx);
+            window.focus(&focus_handle, cx);
+        }
+        cx.notify();
+    }
+
+    fn run_search(&mut self, cx: &mut Context<Self>) {
+        if !self.search_changes {
+            self.search_results = self.git_state.read(cx).search_commits(&self.search_query, 50);
+        } else if self.search_query.is_empty() {
+            self.git_state
+                .update(cx, |state, cx| state.clear_pickaxe_search(cx));
+        } else {
+            // Start once typing pauses, if the query is still the same
+            let query = self.search_query.clone();
+            cx.spawn(async move |this, cx| {
+                cx.background_executor().timer(PICKAXE_DEBOUNCE).await;
+                this.update(cx, |this, cx| {
+                    if this.search_changes && this.search_query == query {
+                        this.git_state
+                            .update(cx, |state, cx| state.start_pickaxe_search(&query, cx));
+                    }
+                })
+                .ok();
+            })
+            .detach();
+        }
+        cx.notify();
+    }
+
+    fn set_search_changes(&mut self, search_changes: bool, cx: &mut Context<Self>) {
+        if self.search_changes == search_changes {
+            return;
+        }
+        self.search_changes = search_changes;
+        self.search_results.clear();
+        let placeholder = if search_changes {
+            "Search for commits adding or removing text..."
+        } else {
+            "Search commits by message, author, or SHA..."
+        };
+        self.search_input
+            .update(cx, |input, cx| input.set_placeholder(placeholder, cx));
+        if !search_changes {
+            self.git_state
+                .update(cx, |state, cx| state.clear_pickaxe_search(cx));
+        }
+        self.run_search(cx);
+    }
+
     fn clear_search(&mut self, cx: &mut Context<Self>) {
         self.search_query.clear();
         self.search_results.clear();
         self.search_input.update(cx, |input, cx| {
             input.set_content("", cx);
         });
+        self.git_state
+            .update(cx, |state, cx| state.clear_pickaxe_search(cx));
         cx.notify();
     }
 
+    fn render_search_mode(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let pickaxe = self
+            .git_state
+            .read(cx)
+            .pickaxe
+            .clone()
+            .filter(|_| self.search_changes);
+
+        let chip = |id: &'static str, label: &'static str, selected: bool| {
+            div()
+                .id(id)
+                .px_2()
+                .py_px()
+                .rounded_sm()
+                .text_xs()
+                .bg(if selected { theme.surface1 } else { theme.surface0 })
+                .text_color(if selected { theme.text } else { theme.overlay2 })
+                .cursor_pointer()
+                .hover(|s| s.bg(theme.surface1).text_color(theme.text))
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex()
+                            .gap_1()
+                            .child(
+                                chip("search-mode-messages", "Messages", !self.search_changes)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.set_search_changes(false, cx)
+                                    })),
+                            )
+                            .child(
+                                chip("search-mode-changes", "Changes (-S)", self.search_changes)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.set_search_changes(true, cx)
+                                    })),
+                            ),
+                    )
+                    .when_some(pickaxe.clone(), |this, search| {
+                        let status = match (&search.error, search.running) {
+                            (Some(error), _) => error.clone(),
+                            (None, true) => match search.total {
+                                Some(total) => {
+                                    format!("Searching {} / {} commits", search.scanned, total)
+                                }
+                                None => "Walking history...".to_string(),
+                            },
+                            (None, false) if search.truncated => format!(
+                                "Stopped at the first {} matches after {} commits",
+                                PICKAXE_RESULT_LIMIT, search.scanned
+                            ),
+                            (None, false) => format!("Searched {} commits", search.scanned),
+                        };
+                        this.child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .min_w_0()
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(if search.error.is_some() {
+                                            theme.red
+                                        } else {
+                                            theme.overlay0
+                                        })
+                                        .text_ellipsis()
+                                        .child(status),
+                                )
+                                .when(search.running, |this| {
+                                    this.child(chip("stop-pickaxe", "Stop", false).on_click(
+                                        cx.listener(|this, _, _, cx| {
+                                            this.git_state.update(cx, |state, cx| {
+                                                state.stop_pickaxe_search(cx)
+                                            });
+                                        }),
+                                    ))
+                                }),
+                        )
+                    }),
+            )
+            .when_some(
+                pickaxe
+                    .filter(|search| search.running)
+                    .and_then(|search| {
+                        let total = search.total?.max(1);
+                        Some(search.scanned as f32 / total as f32)
+                    }),
+                |this, progress| {
+                    this.child(
+                        div()
+                            .h(px(2.0))
+                            .w_full()
+                            .rounded_sm()
+                            .bg(theme.surface0)
+                            .child(
+                                div()
+                                    .h_full()
+                                    .w(relative(progress.min(1.0)))
+                                    .rounded_sm()
+                                    .bg(theme.blue),
+                            ),
+                    )
+                },
+            )
+    }
+
+    fn render_filter(
+        &self,
+        branch_options: Vec<DropdownOption>,
+        filter_active: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = *cx.theme();
+        let panel = cx.entity();
+        let panel_select = panel.clone();
+
+        let row = |label: &'static str| {
+            div().flex().items_center().gap_2().child(
+                div()
+                    .w_16()
+                    .flex_shrink_0()
+                    .text_xs()
+                    .text_color(theme.overlay2)
+                    .child(label),
+            )
+        };
+        let button = |id: &'static str, label: &'static str, primary: bool| {
+            div()
+                .id(id)
+                .px_3()
+                .py_1()
+                .rounded_md()
+                .text_xs()
+                .bg(if primary { theme.blue } else { theme.surface0 })
+                .text_color(if primary { theme.base } else { theme.overlay2 })
+                .cursor_pointer()
+                .hover(move |s| {
+                    if primary {
+                        s.bg(theme.lavender)
+                    } else {
+                        s.bg(theme.surface1).text_color(theme.text)
+                    }
+                })
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                row("Branch").child(
+                    div().flex_1().child(
+                        Dropdown::new("graph-filter-branch", branch_options)
+                            .placeholder("All branches")
+                            .selected(self.filter_branch.clone().unwrap_or_default())
+                            .open(self.branch_picker_open)
+                            .on_toggle(move |open, _window, cx| {
+                                panel.update(cx, |this, cx| {
+                                    this.branch_picker_open = open;
+                                    cx.notify();
+                                });
+                            })
+                            .on_select(move |value, _window, cx| {
+                                let branch = (!value.is_empty()).then(|| value.to_string());
+                                panel_select.update(cx, |this, cx| {
+                                    this.filter_branch = branch;
+                                    cx.notify();
+                                });
+                            }),
+                    ),
+                ),
+            )
+            .child(row("Author").child(div().flex_1().child(self.author_input.clone())))
+            .child(
+                row("Dates")
+                    .child(div().flex_1().child(self.since_input.clone()))
+                    .child(div().text_xs().text_color(theme.overlay0).child("to"))
+                    .child(div().flex_1().child(self.until_input.clone())),
+            )
+            .child(row("Path").child(div().flex_1().child(self.path_input.clone())))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.red)
+                            .when_some(self.filter_error.clone(), |this, error| this.child(error)),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .when(filter_active, |this| {
+                                this.child(button("clear-graph-filter", "Clear", false).on_click(
+                                    cx.listener(|this, _, _, cx| this.clear_filter(cx)),
+                                ))
+                            })
+                            .child(button("apply-graph-filter", "Apply", true).on_click(
+                                cx.listener(|this, _, _, cx| this.apply_filter(cx)),
+                            )),
+                    ),
+            )
+    }
+
+    /// Field for a branch, tag or SHA to scroll the graph to, with the refs
+    /// matching what's typed
+    fn render_jump(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let query = self.jump_input.read(cx).content().trim().to_lowercase();
+        let git_state = self.git_state.read(cx);
+        let refs: Vec<(String, String, &'static str)> = git_state
+            .branches
+            .iter()
+            .map(|branch| match branch.branch_type {
+                BranchKind::Local => {
+                    (branch.name.clone(), format!("refs/heads/{}", branch.name), "branch")
+                }
+                BranchKind::Remote => {
+                    (branch.name.clone(), format!("refs/remotes/{}", branch.name), "remote")
+                }
+            })
+            .chain(
+                git_state
+                    .tags
+                    .iter()
+                    .map(|tag| (tag.name.clone(), format!("refs/tags/{}", tag.name), "tag")),
+            )
+            .filter(|(name, _, _)| name.to_lowercase().contains(&query))
+            .take(JUMP_REF_LIMIT)
+            .collect();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(self.jump_input.clone())
+            .when_some(self.jump_error.clone(), |this, error| {
+                this.child(div().text_xs().text_color(theme.red).child(error))
+            })
+            .when(refs.is_empty(), |this| {
+                this.child(
+                    div()
+                        .px_2()
+                        .text_xs()
+                        .text_color(theme.overlay0)
+                        .child("No matching refs; press Enter to jump to a SHA"),
+                )
+            })
+            .children(refs.into_iter().enumerate().map(|(ix, (name, full_name, kind))| {
+                div()
+                    .id(ElementId::NamedInteger("jump-ref".into(), ix as u64))
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .px_2()
+                    .py_px()
+                    .rounded_sm()
+                    .text_xs()
+                    .cursor_pointer()
+                    .hover(|s| s.bg(theme.surface0))
+                    .child(div().truncate().text_color(theme.text).child(name))
+                    .child(div().flex_shrink_0().text_color(theme.overlay0).child(kind))
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.jump_to(&full_name, cx);
+                    }))
+            }))
+    }
+
+    /// Commit count and most touched files of the author the graph is
+    /// filtered to
+    fn render_author_summary(
+        &self,
+        summary: AuthorSummary,
+        avatar: Option<PathBuf>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = *cx.theme();
+        let author = if summary.email.is_empty() {
+            summary.name.clone()
+        } else {
+            format!("{} <{}>", summary.name, summary.email)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .p_2()
+            .rounded_md()
+            .bg(theme.base)
+            .border_1()
+            .border_color(theme.surface0)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(AuthorAvatar::new(&summary.name, avatar).size(px(24.0)))
+                    .child(
+                        div()
+                            .flex_1()
+                            .truncate()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(theme.text)
+                            .child(author),
+                    )
+                    .child(
+                        div()
+                            .flex_shrink_0()
+                            .text_xs()
+                            .text_color(theme.overlay2)
+                            .child(t_plural(
+                                cx.locale(),
+                                "commit.count",
+                                summary.commit_count as i64,
+                            )),
+                    )
+                    .child(
+                        div()
+                            .id("clear-author-filter")
+                            .flex_shrink_0()
+                            .px_1()
+                            .rounded_sm()
+                            .text_xs()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                let filter = GraphFilter {
+                                    author: None,
+                                    ..this.applied_filter.clone()
+                                };
+                                this.show_filter_fields(filter.clone(), cx);
+                                this.set_filter(filter, cx);
+                            })),
+                    ),
+            )
+            .when(!summary.top_paths.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.overlay0)
+                        .child("Most changed files"),
+                )
+                .children(summary.top_paths.into_iter().map(|(path, count)| {
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .text_xs()
+                        .child(
+                            div()
+                                .flex_1()
+                                .truncate()
+                                .font_family("monospace")
+                                .text_color(theme.text)
+                                .child(path),
+                        )
+                        .child(
+                            div()
+                                .flex_shrink_0()
+                                .text_color(theme.overlay0)
+                                .child(count.to_string()),
+                        )
+                }))
+            })
+    }
+
     fn format_timestamp(timestamp: &chrono::DateTime<chrono::Utc>) -> String {
         use chrono::Timelike;
         format!(
@@ -70,15 +762,62 @@ impl RightPanel {
 
 impl Render for RightPanel {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let locale = cx.locale();
         let has_search = !self.search_query.is_empty();
-        let search_results = self.search_results.clone();
-        let commit_count = self
+        let git_state = self.git_state.read(cx);
+        let pickaxe_running = git_state.pickaxe.as_ref().is_some_and(|s| s.running);
+        let search_results = if self.search_changes {
+            git_state
+                .pickaxe
+                .as_ref()
+                .map(|search| search.results.clone())
+                .unwrap_or_default()
+        } else {
+            self.search_results.clone()
+        };
+        let commit_count = git_state.commits.as_ref().map(|c| c.nodes.len()).unwrap_or(0);
+        let filter_active = git_state.graph_filter.is_active();
+        let selected_sha = git_state.selected_commit.as_ref().map(|c| c.sha.clone());
+        let author_summary = git_state.author_summary.clone();
+        let branch_options: Vec<DropdownOption> = std::iter::once(DropdownOption {
+            value: String::new(),
+            label: "All branches".to_string(),
+        })
+        .chain(git_state.branches.iter().map(|branch| DropdownOption {
+            value: branch.name.clone(),
+            label: match branch.tracking_label() {
+                Some(tracking) => format!("{} → {}", branch.name, tracking),
+                None => branch.name.clone(),
+            },
+        }))
+        .collect();
+        let search_results: Vec<(CommitInfo, Option<PathBuf>)> =
+            self.avatars.update(cx, |avatars, cx| {
+                search_results
+                    .into_iter()
+                    .map(|commit| {
+                        let avatar = avatars.avatar(&commit.email, cx);
+                        (commit, avatar)
+                    })
+                    .collect()
+            });
+        let shas: Vec<String> = search_results.iter().map(|(c, _)| c.sha.clone()).collect();
+        let signatures = self
             .git_state
-            .read(cx)
-            .commits
-            .as_ref()
-            .map(|c| c.nodes.len())
-            .unwrap_or(0);
+            .update(cx, |state, cx| state.signature_checks(&shas, cx));
+        let search_results: Vec<(CommitInfo, Option<PathBuf>, Option<CommitSignature>)> =
+            search_results
+                .into_iter()
+                .zip(signatures)
+                .map(|((commit, avatar), check)| (commit, avatar, check.ready()))
+                .collect();
+        let author_summary = author_summary.map(|summary| {
+            let avatar = self
+                .avatars
+                .update(cx, |avatars, cx| avatars.avatar(&summary.email, cx));
+            (summary, avatar)
+        });
 
         div()
             .flex()
@@ -92,9 +831,9 @@ impl Render for RightPanel {
                     .flex_col()
                     .px_4()
                     .py_2()
-                    .bg(rgb(0x181825))
+                    .bg(theme.mantle)
                     .border_b_1()
-                    .border_color(rgb(0x313244))
+                    .border_color(theme.surface0)
                     .gap_2()
                     // Title row
                     .child(
@@ -111,7 +850,7 @@ impl Render for RightPanel {
                                         div()
                                             .text_sm()
                                             .font_weight(FontWeight::MEDIUM)
-                                            .text_color(rgb(0x9399b2))
+                                            .text_color(theme.overlay2)
                                             .child("Commit History"),
                                     )
                                     .child(
@@ -119,15 +858,84 @@ impl Render for RightPanel {
                                             .px_2()
                                             .py_px()
                                             .rounded_sm()
-                                            .bg(rgb(0x313244))
+                                            .bg(theme.surface0)
                                             .text_xs()
-                                            .text_color(rgb(0x6c7086))
+                                            .text_color(theme.overlay0)
                                             .child(if has_search {
-                                                format!("{} results", search_results.len())
+                                                t_plural(
+                                                    locale,
+                                                    "search.results",
+                                                    search_results.len() as i64,
+                                                )
+                                            } else if filter_active {
+                                                t_plural(
+                                                    locale,
+                                                    "commit.countFiltered",
+                                                    commit_count as i64,
+                                                )
                                             } else {
-                                                format!("{} commits", commit_count)
+                                                t_plural(
+                                                    locale,
+                                                    "commit.count",
+                                                    commit_count as i64,
+                                                )
                                             }),
                                     ),
+                            )
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_1()
+                                    .child(
+                                        div()
+                                            .id("toggle-graph-jump")
+                                            .px_2()
+                                            .py_px()
+                                            .rounded_sm()
+                                            .text_xs()
+                                            .bg(if self.show_jump {
+                                                theme.blue
+                                            } else {
+                                                theme.surface0
+                                            })
+                                            .text_color(if self.show_jump {
+                                                theme.base
+                                            } else {
+                                                theme.overlay2
+                                            })
+                                            .cursor_pointer()
+                                            .hover(|s| s.bg(theme.surface1).text_color(theme.text))
+                                            .child("Jump to")
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.toggle_jump(window, cx);
+                                            })),
+                                    )
+                                    .child(
+                                        div()
+                                            .id("toggle-graph-filter")
+                                            .px_2()
+                                            .py_px()
+                                            .rounded_sm()
+                                            .text_xs()
+                                            .bg(if filter_active {
+                                                theme.blue
+                                            } else {
+                                                theme.surface0
+                                            })
+                                            .text_color(if filter_active {
+                                                theme.base
+                                            } else {
+                                                theme.overlay2
+                                            })
+                                            .cursor_pointer()
+                                            .hover(|s| s.bg(theme.surface1).text_color(theme.text))
+                                            .child("Filter")
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.show_filter = !this.show_filter;
+                                                cx.notify();
+                                            })),
+                                    ),
                             ),
                     )
                     // Search input row
@@ -152,10 +960,10 @@ impl Render for RightPanel {
                                                 .py_1()
                                                 .rounded_sm()
                                                 .text_xs()
-                                                .text_color(rgb(0x9399b2))
+                                                .text_color(theme.overlay2)
                                                 .cursor_pointer()
                                                 .hover(|s| {
-                                                    s.bg(rgb(0x45475a)).text_color(rgb(0xcdd6f4))
+                                                    s.bg(theme.surface1).text_color(theme.text)
                                                 })
                                                 .child("×")
                                                 .on_click(cx.listener(|this, _, _, cx| {
@@ -164,7 +972,15 @@ impl Render for RightPanel {
                                         ),
                                 )
                             }),
-                    ),
+                    )
+                    .child(self.render_search_mode(cx))
+                    .when(self.show_jump, |this| this.child(self.render_jump(cx)))
+                    .when(self.show_filter, |this| {
+                        this.child(self.render_filter(branch_options, filter_active, cx))
+                    })
+                    .when_some(author_summary, |this, (summary, avatar)| {
+                        this.child(self.render_author_summary(summary, avatar, cx))
+                    }),
             )
             // Content: Search results or commit graph
             .child(
@@ -172,6 +988,7 @@ impl Render for RightPanel {
                     .id("commit-content")
                     .flex_1()
                     .overflow_scroll()
+                    .track_scroll(&self.scroll_handle)
                     .when(has_search, |this| {
                         // Show search results as a list
                         this.child(
@@ -185,40 +1002,101 @@ impl Render for RightPanel {
                                             .justify_center()
                                             .h_32()
                                             .text_sm()
-                                            .text_color(rgb(0x6c7086))
-                                            .child("No commits found"),
+                                            .text_color(theme.overlay0)
+                                            .child(if self.search_changes && pickaxe_running {
+                                                "Searching..."
+                                            } else {
+                                                "No commits found"
+                                            }),
                                     )
                                 },
                             )
                             .when(!search_results.is_empty(), |this| {
-                                this.children(search_results.into_iter().map(|commit| {
-                                    SearchResultItem::new(commit)
-                                }))
+                                this.children(search_results.into_iter().map(
+                                    |(commit, avatar, signature)| {
+                                        let selected = selected_sha.as_ref() == Some(&commit.sha);
+                                        let clicked = commit.clone();
+                                        SearchResultItem::new(commit, avatar)
+                                            .signature(signature)
+                                            .selected(selected)
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.select_search_result(clicked.clone(), cx);
+                                            }))
+                                    },
+                                ))
                             }),
                         )
                     })
-                    .when(!has_search, |this| {
-                        // Show commit graph
+                    .when(!has_search && commit_count == 0, |this| {
                         this.child(self.commit_graph.clone())
+                    })
+                    .when(!has_search && commit_count > 0, |this| {
+                        // Cached so notifies that don't touch the history
+                        // skip rebuilding every row; rows have a fixed height,
+                        // so the graph's size is known up front
+                        let height = px(commit_count as f32 * ROW_HEIGHT);
+                        this.child(
+                            AnyView::from(self.commit_graph.clone())
+                                .cached(StyleRefinement::default().w_full().h(height)),
+                        )
                     }),
             )
     }
 }
 
+/// Midnight UTC of a date typed as `YYYY-MM-DD`
+fn parse_date(value: Option<String>) -> Result<Option<DateTime<Utc>>, String> {
+    value
+        .map(|value| {
+            NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+                .map_err(|_| format!("'{}' is not a date (YYYY-MM-DD)", value))
+        })
+        .transpose()
+}
+
 /// A search result item component
 #[derive(IntoElement)]
 struct SearchResultItem {
     commit: CommitInfo,
+    avatar: Option<PathBuf>,
+    /// Signature check result, once verified
+    signature: Option<CommitSignature>,
+    /// The commit is the selected one
+    selected: bool,
+    on_click: Option<ClickHandler>,
 }
 
 impl SearchResultItem {
-    fn new(commit: CommitInfo) -> Self {
-        Self { commit }
+    fn new(commit: CommitInfo, avatar: Option<PathBuf>) -> Self {
+        Self {
+            commit,
+            avatar,
+            signature: None,
+            selected: false,
+            on_click: None,
+        }
+    }
+
+    fn signature(mut self, signature: Option<CommitSignature>) -> Self {
+        self.signature = signature;
+        self
+    }
+
+    fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    fn on_click(mut self, handler: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Box::new(handler));
+        self
     }
 }
 
 impl RenderOnce for SearchResultItem {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         let timestamp = RightPanel::format_timestamp(&self.commit.timestamp);
 
         div()
@@ -226,9 +1104,11 @@ impl RenderOnce for SearchResultItem {
             .px_4()
             .py_3()
             .border_b_1()
-            .border_color(rgb(0x313244))
+            .border_color(theme.surface0)
             .cursor_pointer()
-            .hover(|s| s.bg(rgb(0x313244)))
+            .when(self.selected, |this| this.bg(theme.blue_bg))
+            .hover(|s| s.bg(theme.surface0))
+            .when_some(self.on_click, |this, handler| this.on_click(handler))
             .child(
                 div()
                     .flex()
@@ -240,10 +1120,10 @@ impl RenderOnce for SearchResultItem {
                             .px_2()
                             .py_px()
                             .rounded_sm()
-                            .bg(rgb(0x313244))
+                            .bg(theme.surface0)
                             .text_xs()
                             .font_family("monospace")
-                            .text_color(rgb(0x89b4fa))
+                            .text_color(theme.blue)
                             .child(self.commit.short_sha.clone()),
                     )
                     // Commit details
@@ -254,7 +1134,7 @@ impl RenderOnce for SearchResultItem {
                             .child(
                                 div()
                                     .text_sm()
-                                    .text_color(rgb(0xcdd6f4))
+                                    .text_color(theme.text)
                                     .overflow_hidden()
                                     .text_ellipsis()
                                     .child(self.commit.message.lines().next().unwrap_or("").to_string()),
@@ -266,10 +1146,25 @@ impl RenderOnce for SearchResultItem {
                                     .gap_2()
                                     .mt_1()
                                     .text_xs()
-                                    .text_color(rgb(0x6c7086))
-                                    .child(self.commit.author.clone())
+                                    .text_color(theme.overlay0)
+                                    .child(AuthorAvatar::new(&self.commit.author, self.avatar))
+                                    .child(AuthorLink::new(
+                                        ElementId::Name(
+                                            format!("search-author-{}", self.commit.sha).into(),
+                                        ),
+                                        &self.commit,
+                                    ))
                                     .child("•")
-                                    .child(timestamp),
+                                    .child(timestamp)
+                                    .when_some(self.signature, |this, signature| {
+                                        this.child(SignatureBadge::new(
+                                            ElementId::Name(
+                                                format!("search-signature-{}", self.commit.sha)
+                                                    .into(),
+                                            ),
+                                            signature,
+                                        ))
+                                    }),
                             ),
                     ),
             )
@@ -287,9 +1182,9 @@ impl RenderOnce for SearchResultItem {
                                 .px_2()
                                 .py_px()
                                 .rounded_sm()
-                                .bg(rgb(0x89b4fa))
+                                .bg(theme.blue)
                                 .text_xs()
-                                .text_color(rgb(0x1e1e2e))
+                                .text_color(theme.base)
                                 .child(branch.clone())
                         }))
                         .children(self.commit.tags.iter().map(|tag| {
@@ -297,9 +1192,9 @@ impl RenderOnce for SearchResultItem {
                                 .px_2()
                                 .py_px()
                                 .rounded_sm()
-                                .bg(rgb(0xf9e2af))
+                                .bg(theme.yellow)
                                 .text_xs()
-                                .text_color(rgb(0x1e1e2e))
+                                .text_color(theme.base)
                                 .child(tag.clone())
                         })),
                 )
@@ -0,0 +1,453 @@
+#![allow(dead_code)]
+
+use crate::actions::ShowDiff;
+use crate::components::{CopyDetailsButton, TextInputChanged, TextInputView};
+use crate::git::{SearchMatch, SearchQuery, SearchScope, SearchSummary};
+use crate::state::{ErrorDetails, GitState};
+use crate::theme::{ActiveTheme, Theme};
+use futures::StreamExt;
+use gpui::prelude::*;
+use gpui::*;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Most matching lines listed for a search
+const SEARCH_RESULT_LIMIT: usize = 2000;
+
+/// Typing pause before a search starts, so each keystroke doesn't read
+/// every tracked file
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Emitted when the search panel should be closed
+#[derive(Clone, Debug)]
+pub struct SearchDismissed;
+
+impl EventEmitter<SearchDismissed> for SearchView {}
+
+/// Text search across the tracked files of the working tree or HEAD, with
+/// results listed by file as they are found
+pub struct SearchView {
+    git_state: Entity<GitState>,
+    pub query_input: Entity<TextInputView>,
+    scope: SearchScope,
+    case_sensitive: bool,
+    matches: Vec<SearchMatch>,
+    /// Set once the running search finishes
+    summary: Option<SearchSummary>,
+    error: Option<ErrorDetails>,
+    /// Replacing the running search drops it, which stops the file scan
+    search_task: Option<Task<()>>,
+}
+
+impl SearchView {
+    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+        let query_input =
+            cx.new(|cx| TextInputView::new(cx).with_placeholder("Search tracked files..."));
+        cx.subscribe(&query_input, |this, _input, _event: &TextInputChanged, cx| {
+            this.search(cx);
+        })
+        .detach();
+
+        Self {
+            git_state,
+            query_input,
+            scope: SearchScope::default(),
+            case_sensitive: false,
+            matches: Vec::new(),
+            summary: None,
+            error: None,
+            search_task: None,
+        }
+    }
+
+    fn search(&mut self, cx: &mut Context<Self>) {
+        self.matches.clear();
+        self.summary = None;
+        self.error = None;
+        let query = SearchQuery {
+            text: self.query_input.read(cx).content().to_string(),
+            scope: self.scope,
+            case_sensitive: self.case_sensitive,
+        };
+        if query.text.is_empty() {
+            self.search_task = None;
+            cx.notify();
+            return;
+        }
+
+        self.search_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(SEARCH_DEBOUNCE).await;
+            let Ok((mut matches, task)) = this.update(cx, |this, cx| {
+                this.git_state
+                    .read(cx)
+                    .search_files(query, SEARCH_RESULT_LIMIT, cx)
+            }) else {
+                return;
+            };
+
+            while let Some(batch) = matches.next().await {
+                let appended = this.update(cx, |this, cx| {
+                    this.matches.extend(batch);
+                    cx.notify();
+                });
+                if appended.is_err() {
+                    return;
+                }
+            }
+            let result = task.await;
+
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(summary) => this.summary = Some(summary),
+                    Err(e) => {
+                        log::error!("Search failed: {}", e);
+                        this.error = Some(this.git_state.read(cx).error_details("Search", &e));
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn set_scope(&mut self, scope: SearchScope, cx: &mut Context<Self>) {
+        if self.scope != scope {
+            self.scope = scope;
+            self.search(cx);
+        }
+    }
+
+    fn toggle_case_sensitive(&mut self, cx: &mut Context<Self>) {
+        self.case_sensitive = !self.case_sensitive;
+        self.search(cx);
+    }
+
+    /// Open the file at the matching line in the editor
+    fn open_match(&mut self, path: &str, line: u32, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.open_in_editor(path, Some(line), cx));
+        if let Err(e) = result {
+            log::error!("Failed to open {} in the editor: {:#}", path, e);
+            self.error = Some(self.git_state.read(cx).error_details("Open file", &e));
+            cx.notify();
+        }
+    }
+
+    /// Show the uncommitted changes of a file and close the panel
+    fn show_diff(&mut self, path: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.load_file_diff(path, cx));
+        match result {
+            Ok(()) => {
+                cx.emit(SearchDismissed);
+                window.dispatch_action(Box::new(ShowDiff), cx);
+            }
+            Err(e) => {
+                log::error!("Failed to load diff: {}", e);
+                self.error = Some(self.git_state.read(cx).error_details("Load diff", &e));
+                cx.notify();
+            }
+        }
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(SearchDismissed);
+    }
+}
+
+impl Render for SearchView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let has_query = !self.query_input.read(cx).content().is_empty();
+        let searching = has_query && self.summary.is_none() && self.error.is_none();
+        let changed: HashSet<String> = self
+            .git_state
+            .read(cx)
+            .files
+            .iter()
+            .map(|file| file.path.clone())
+            .collect();
+
+        // Group consecutive matches by file; files are searched in path order
+        let mut groups: Vec<(String, Vec<SearchMatch>)> = Vec::new();
+        for found in self.matches.iter() {
+            match groups.last_mut() {
+                Some((path, group)) if *path == found.path => group.push(found.clone()),
+                _ => groups.push((found.path.clone(), vec![found.clone()])),
+            }
+        }
+
+        let status = match self.summary {
+            _ if !has_query => "Search the text of tracked files".to_string(),
+            None => format!("Searching… {} matches so far", self.matches.len()),
+            Some(summary) if summary.truncated => format!(
+                "First {} matches in {} files",
+                summary.matches,
+                groups.len()
+            ),
+            Some(summary) => format!(
+                "{} matches in {} of {} files",
+                summary.matches,
+                groups.len(),
+                summary.files_searched
+            ),
+        };
+
+        let chip = |id: ElementId, label: &'static str, active: bool| {
+            div()
+                .id(id)
+                .px_2()
+                .py_px()
+                .rounded_sm()
+                .text_xs()
+                .cursor_pointer()
+                .when(active, |this| this.bg(theme.blue).text_color(theme.base))
+                .when(!active, |this| {
+                    this.bg(theme.surface0)
+                        .text_color(theme.overlay2)
+                        .hover(|s| s.bg(theme.surface1))
+                })
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Search"),
+                            )
+                            .child(div().text_sm().text_color(theme.overlay2).child(status)),
+                    )
+                    .child(
+                        div()
+                            .id("close-search")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    ),
+            )
+            // Query and options
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().flex_1().child(self.query_input.clone()))
+                    .children(SearchScope::ALL.into_iter().map(|scope| {
+                        let id = ElementId::Name(format!("search-scope-{:?}", scope).into());
+                        chip(id, scope.label(), self.scope == scope).on_click(cx.listener(
+                            move |this, _event, _window, cx| {
+                                this.set_scope(scope, cx);
+                            },
+                        ))
+                    }))
+                    .child(
+                        chip("search-case".into(), "Aa", self.case_sensitive).on_click(
+                            cx.listener(|this, _event, _window, cx| {
+                                this.toggle_case_sensitive(cx);
+                            }),
+                        ),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error.message)
+                        .child(CopyDetailsButton::new("search-error-details", error.report)),
+                )
+            })
+            // Matches grouped by file
+            .child(
+                div()
+                    .id("search-results")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .when(has_query && !searching && groups.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .py_8()
+                                .text_sm()
+                                .text_center()
+                                .text_color(theme.overlay0)
+                                .child("No matches"),
+                        )
+                    })
+                    .children(groups.into_iter().enumerate().map(|(group_ix, (path, group))| {
+                        let is_changed = changed.contains(&path);
+                        self.render_group(group_ix, path, group, is_changed, &theme, cx)
+                    })),
+            )
+    }
+}
+
+impl SearchView {
+    fn render_group(
+        &self,
+        group_ix: usize,
+        path: String,
+        matches: Vec<SearchMatch>,
+        is_changed: bool,
+        theme: &Theme,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let path_diff = path.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_px()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.blue)
+                            .truncate()
+                            .child(path),
+                    )
+                    .when(is_changed, |this| {
+                        this.child(
+                            div()
+                                .id(ElementId::Name(format!("search-diff-{}", group_ix).into()))
+                                .flex_shrink_0()
+                                .text_xs()
+                                .text_color(theme.overlay2)
+                                .cursor_pointer()
+                                .hover(|s| s.text_color(theme.text))
+                                .child("Show Diff")
+                                .on_click(cx.listener(move |this, _event, window, cx| {
+                                    this.show_diff(&path_diff, window, cx);
+                                })),
+                        )
+                    }),
+            )
+            .children(matches.into_iter().enumerate().map(|(ix, found)| {
+                let id = format!("search-match-{}-{}", group_ix, ix);
+                render_match(id, found, theme, cx)
+            }))
+    }
+}
+
+fn render_match(
+    id: String,
+    found: SearchMatch,
+    theme: &Theme,
+    cx: &mut Context<SearchView>,
+) -> impl IntoElement {
+    let (path, line) = (found.path.clone(), found.line);
+
+    div()
+        .id(ElementId::Name(id.into()))
+        .flex()
+        .items_center()
+        .gap_2()
+        .px_2()
+        .py_px()
+        .rounded_sm()
+        .text_xs()
+        .cursor_pointer()
+        .hover(|s| s.bg(theme.surface0))
+        .child(
+            div()
+                .flex_shrink_0()
+                .w_10()
+                .text_right()
+                .text_color(theme.overlay0)
+                .child(found.line.to_string()),
+        )
+        .child(
+            div()
+                .flex()
+                .flex_1()
+                .overflow_hidden()
+                .whitespace_nowrap()
+                .font_family("monospace")
+                .text_color(theme.text)
+                .children(preview_segments(&found).into_iter().map(|(text, is_match)| {
+                    div()
+                        .flex_shrink_0()
+                        .when(is_match, |this| {
+                            this.rounded_sm().bg(theme.yellow_bg).text_color(theme.yellow)
+                        })
+                        .child(text)
+                })),
+        )
+        .on_click(cx.listener(move |this, _event, _window, cx| {
+            this.open_match(&path, line, cx);
+        }))
+}
+
+/// The matching line without its indentation, split into runs that are
+/// and aren't occurrences of the query
+fn preview_segments(found: &SearchMatch) -> Vec<(String, bool)> {
+    let text = found.text.as_str();
+    let indent = text.len() - text.trim_start().len();
+    let mut segments = Vec::new();
+    let mut pos = indent;
+    for range in &found.ranges {
+        let start = range.start.max(pos);
+        if start >= range.end {
+            continue;
+        }
+        if start > pos {
+            segments.push((text[pos..start].to_string(), false));
+        }
+        segments.push((text[start..range.end].to_string(), true));
+        pos = range.end;
+    }
+    if pos < text.len() {
+        segments.push((text[pos..].to_string(), false));
+    }
+    segments
+}
@@ -1,29 +1,136 @@
 #![allow(dead_code)]
 
-use crate::i18n::{t, Locale};
-use crate::state::{AuthMode, MergeMode, SettingsState};
+use crate::i18n::{t, ActiveLocale, Locale};
+use crate::state::{
+    AutoFetchInterval, CommitLintMode, DiffSizeLimit, MergeMode, SettingsState, SettingsTab,
+    SigningPolicy, ThemeMode, ToastPosition,
+};
+use crate::components::dropdown::{Dropdown, DropdownOption};
+use crate::views::{
+    ExternalEditorView, GitAuthView, GitIdentityView, HostingTokenView, RepoSettingsView,
+    SigningListsView,
+};
+use crate::theme::{ActiveTheme, GraphPalette, ThemeRegistry};
 use gpui::prelude::*;
 use gpui::*;
 
+type ClickHandler = Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
+
 #[derive(IntoElement)]
 pub struct SettingsView {
     settings: Entity<SettingsState>,
+    /// Overrides for the open repository, shown in a second tab
+    repository: Option<Entity<RepoSettingsView>>,
+    /// Editor for the HTTPS username and token
+    git_auth: Option<Entity<GitAuthView>>,
+    /// Editor for `user.name` and `user.email` in git config
+    identity: Option<Entity<GitIdentityView>>,
+    /// Editor for the GitHub/GitLab API token
+    hosting_token: Option<Entity<HostingTokenView>>,
+    /// Editor for the command files are opened with
+    external_editor: Option<Entity<ExternalEditorView>>,
+    /// Editor for the protected branches and allowed signers
+    signing_lists: Option<Entity<SigningListsView>>,
 }
 
 impl SettingsView {
     pub fn new(settings: Entity<SettingsState>) -> Self {
-        Self { settings }
+        Self {
+            settings,
+            repository: None,
+            git_auth: None,
+            identity: None,
+            hosting_token: None,
+            external_editor: None,
+            signing_lists: None,
+        }
+    }
+
+    pub fn git_auth(mut self, view: Option<Entity<GitAuthView>>) -> Self {
+        self.git_auth = view;
+        self
+    }
+
+    pub fn identity(mut self, view: Option<Entity<GitIdentityView>>) -> Self {
+        self.identity = view;
+        self
+    }
+
+    pub fn hosting_token(mut self, view: Option<Entity<HostingTokenView>>) -> Self {
+        self.hosting_token = view;
+        self
+    }
+
+    pub fn external_editor(mut self, view: Option<Entity<ExternalEditorView>>) -> Self {
+        self.external_editor = view;
+        self
+    }
+
+    pub fn signing_lists(mut self, view: Option<Entity<SigningListsView>>) -> Self {
+        self.signing_lists = view;
+        self
+    }
+
+    pub fn repository(mut self, view: Option<Entity<RepoSettingsView>>) -> Self {
+        self.repository = view;
+        self
     }
 }
 
 impl RenderOnce for SettingsView {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         let settings = self.settings.read(cx);
-        let locale = settings.data.locale;
-        let auth_mode = settings.data.git_auth_mode;
+        let locale = cx.locale();
+        let theme_picker_open = settings.theme_picker_open;
+        let theme_value = match &settings.data.user_theme {
+            Some(name) => format!("user:{}", name),
+            None => theme_mode_value(settings.data.theme).to_string(),
+        };
+        let theme_choices = theme_options(locale, cx);
+        let settings_toggle = self.settings.clone();
+        let settings_select = self.settings.clone();
         let merge_mode = settings.data.merge_mode;
-        let username = settings.data.git_username.clone().unwrap_or_default();
-        let has_token = settings.data.git_token.is_some();
+        let restore_session = settings.data.restore_session;
+        let fetch_on_open = settings.data.fetch_on_open;
+        let fetch_prune = settings.data.fetch_prune;
+        let auto_fetch_interval = settings.data.auto_fetch_interval;
+        let autostash = settings.data.autostash;
+        let preview_operations = settings.data.preview_operations;
+        let trash_discards = settings.data.trash_discards;
+        let control_socket = settings.data.control_socket;
+        let toast_position = settings.data.toast_position;
+        let graph_palette = settings.data.graph_palette;
+        let fetch_avatars = settings.data.fetch_avatars;
+        let sticky_errors = settings.data.sticky_errors;
+        let max_diff_size = settings.data.max_diff_size;
+        let signing_policy = settings.data.signing_policy;
+        let commit_lint = settings.data.commit_lint;
+        let run_hooks = settings.data.run_hooks;
+        let repository = self.repository.clone();
+        let git_auth = self.git_auth.clone();
+        let identity = self.identity.clone();
+        let hosting_token = self.hosting_token.clone();
+        let external_editor = self.external_editor.clone();
+        let signing_lists = self.signing_lists.clone();
+        let show_repository = repository.is_some() && settings.tab == SettingsTab::Repository;
+        let tab_buttons = [
+            (SettingsTab::General, "settings.tabGeneral"),
+            (SettingsTab::Repository, "settings.tabRepository"),
+        ]
+        .map(|(tab, key)| {
+            let settings = self.settings.clone();
+            let selected = if show_repository {
+                tab == SettingsTab::Repository
+            } else {
+                tab == SettingsTab::General
+            };
+            SettingsButton::new(t(locale, key), selected)
+                .id(key)
+                .on_click(move |_event, _window, cx| {
+                    settings.update(cx, |settings, cx| settings.set_tab(tab, cx));
+                })
+        });
 
         div()
             .absolute()
@@ -31,7 +138,7 @@ impl RenderOnce for SettingsView {
             .flex()
             .items_center()
             .justify_center()
-            .bg(rgba(0x00000088))
+            .bg(theme.backdrop)
             .child(
                 div()
                     .flex()
@@ -39,9 +146,9 @@ impl RenderOnce for SettingsView {
                     .w_96()
                     .max_h(px(600.0))
                     .rounded_lg()
-                    .bg(rgb(0x1e1e2e))
+                    .bg(theme.base)
                     .border_1()
-                    .border_color(rgb(0x313244))
+                    .border_color(theme.surface0)
                     .overflow_hidden()
                     // Header
                     .child(
@@ -51,14 +158,14 @@ impl RenderOnce for SettingsView {
                             .justify_between()
                             .px_4()
                             .py_3()
-                            .bg(rgb(0x181825))
+                            .bg(theme.mantle)
                             .border_b_1()
-                            .border_color(rgb(0x313244))
+                            .border_color(theme.surface0)
                             .child(
                                 div()
                                     .text_sm()
                                     .font_weight(FontWeight::SEMIBOLD)
-                                    .text_color(rgb(0xcdd6f4))
+                                    .text_color(theme.text)
                                     .child(t(locale, "settings.title")),
                             )
                             .child(
@@ -68,16 +175,40 @@ impl RenderOnce for SettingsView {
                                     .py_1()
                                     .rounded_md()
                                     .text_sm()
-                                    .text_color(rgb(0x9399b2))
+                                    .text_color(theme.overlay2)
                                     .cursor_pointer()
-                                    .hover(|s| s.bg(rgb(0x313244)).text_color(rgb(0xcdd6f4)))
+                                    .hover(|s| s.bg(theme.surface0).text_color(theme.text))
                                     .child("×"),
                             ),
                     )
+                    // Tabs, when a repository is open
+                    .when(repository.is_some(), |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .gap_1()
+                                .px_4()
+                                .py_2()
+                                .border_b_1()
+                                .border_color(theme.surface0)
+                                .children(tab_buttons),
+                        )
+                    })
+                    .when_some(repository.filter(|_| show_repository), |this, view| {
+                        this.child(
+                            div()
+                                .id("repo-settings-scroll")
+                                .flex_1()
+                                .overflow_scroll()
+                                .p_4()
+                                .child(view),
+                        )
+                    })
                     // Content
                     .child(
                         div()
                             .id("settings-scroll")
+                            .when(show_repository, |this| this.hidden())
                             .flex_1()
                             .overflow_scroll()
                             .p_4()
@@ -94,7 +225,7 @@ impl RenderOnce for SettingsView {
                                         div()
                                             .text_xs()
                                             .font_weight(FontWeight::SEMIBOLD)
-                                            .text_color(rgb(0x89b4fa))
+                                            .text_color(theme.blue)
                                             .child(t(locale, "settings.general")),
                                     )
                                     .child(
@@ -105,33 +236,49 @@ impl RenderOnce for SettingsView {
                                             .child(
                                                 div()
                                                     .text_sm()
-                                                    .text_color(rgb(0x9399b2))
+                                                    .text_color(theme.overlay2)
                                                     .child(t(locale, "settings.language")),
                                             )
                                             .child(
                                                 div()
                                                     .flex()
+                                                    .flex_wrap()
                                                     .gap_1()
                                                     .children(Locale::all().iter().map(|l| {
                                                         LanguageButton::new(*l, locale == *l)
                                                     })),
                                             ),
-                                    ),
-                            )
-                            // Git Authentication section
-                            .child(
-                                div()
-                                    .flex()
-                                    .flex_col()
-                                    .gap_3()
+                                    )
                                     .child(
                                         div()
-                                            .text_xs()
-                                            .font_weight(FontWeight::SEMIBOLD)
-                                            .text_color(rgb(0x89b4fa))
-                                            .child(t(locale, "settings.gitAuth")),
+                                            .flex()
+                                            .items_start()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .py_2()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.theme")),
+                                            )
+                                            .child(
+                                                div().w_48().child(
+                                                    Dropdown::new("theme-picker", theme_choices)
+                                                        .selected(theme_value)
+                                                        .open(theme_picker_open)
+                                                        .on_toggle(move |open, _window, cx| {
+                                                            settings_toggle.update(cx, |settings, cx| {
+                                                                settings.set_theme_picker_open(open, cx);
+                                                            });
+                                                        })
+                                                        .on_select(move |value, _window, cx| {
+                                                            settings_select.update(cx, |settings, cx| {
+                                                                select_theme(settings, value, cx);
+                                                            });
+                                                        }),
+                                                ),
+                                            ),
                                     )
-                                    // Auth Mode selector
                                     .child(
                                         div()
                                             .flex()
@@ -140,99 +287,380 @@ impl RenderOnce for SettingsView {
                                             .child(
                                                 div()
                                                     .text_sm()
-                                                    .text_color(rgb(0x9399b2))
-                                                    .child(t(locale, "settings.gitAuthMethod")),
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.graphPalette")),
                                             )
                                             .child(
                                                 div()
                                                     .flex()
-                                                    .gap_2()
-                                                    .child(SettingsButton::new(
-                                                        t(locale, "auth.https"),
-                                                        auth_mode == AuthMode::Https,
-                                                    ))
-                                                    .child(SettingsButton::new(
-                                                        t(locale, "auth.ssh"),
-                                                        auth_mode == AuthMode::Ssh,
+                                                    .gap_1()
+                                                    .children(GraphPalette::ALL.into_iter().map(|palette| {
+                                                        let settings = self.settings.clone();
+                                                        let (id, label) = graph_palette_label(palette, locale);
+                                                        SettingsButton::new(label, graph_palette == palette)
+                                                            .id(ElementId::Name(format!("graph-palette-{}", id).into()))
+                                                            .on_click(move |_event, _window, cx| {
+                                                                settings.update(cx, |settings, cx| {
+                                                                    settings.set_graph_palette(palette, cx);
+                                                                });
+                                                            })
+                                                    })),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.fetchAvatars")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children([(true, "settings.on"), (false, "settings.off")].into_iter().map(
+                                                        |(enabled, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), fetch_avatars == enabled)
+                                                                .id(ElementId::Name(format!("{}-fetch-avatars", key).into()))
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_fetch_avatars(enabled, cx);
+                                                                    });
+                                                                })
+                                                        },
                                                     )),
                                             ),
                                     )
-                                    // Username display (HTTPS only)
-                                    .when(auth_mode == AuthMode::Https, |this| {
-                                        this.child(
-                                            div()
-                                                .flex()
-                                                .items_center()
-                                                .justify_between()
-                                                .child(
-                                                    div()
-                                                        .text_sm()
-                                                        .text_color(rgb(0x9399b2))
-                                                        .child(t(locale, "settings.gitUsername")),
-                                                )
-                                                .child(
-                                                    div()
-                                                        .px_3()
-                                                        .py_1()
-                                                        .rounded_md()
-                                                        .bg(rgb(0x313244))
-                                                        .text_sm()
-                                                        .text_color(if username.is_empty() {
-                                                            rgb(0x6c7086)
-                                                        } else {
-                                                            rgb(0xcdd6f4)
-                                                        })
-                                                        .child(if username.is_empty() {
-                                                            t(locale, "settings.gitUsernamePlaceholder")
-                                                        } else {
-                                                            username
-                                                        }),
-                                                ),
-                                        )
-                                    })
-                                    // Token status (HTTPS only)
-                                    .when(auth_mode == AuthMode::Https, |this| {
-                                        this.child(
-                                            div()
-                                                .flex()
-                                                .items_center()
-                                                .justify_between()
-                                                .child(
-                                                    div()
-                                                        .text_sm()
-                                                        .text_color(rgb(0x9399b2))
-                                                        .child(t(locale, "settings.gitToken")),
-                                                )
-                                                .child(
-                                                    div()
-                                                        .px_3()
-                                                        .py_1()
-                                                        .rounded_md()
-                                                        .bg(rgb(0x313244))
-                                                        .text_sm()
-                                                        .text_color(if has_token {
-                                                            rgb(0xa6e3a1)
-                                                        } else {
-                                                            rgb(0xf38ba8)
-                                                        })
-                                                        .child(if has_token {
-                                                            "••••••••".to_string()
-                                                        } else {
-                                                            t(locale, "settings.gitTokenPlaceholder")
-                                                        }),
-                                                ),
-                                        )
-                                    })
-                                    // SSH info
-                                    .when(auth_mode == AuthMode::Ssh, |this| {
-                                        this.child(
-                                            div()
-                                                .text_xs()
-                                                .text_color(rgb(0x6c7086))
-                                                .child("SSH authentication uses the system SSH agent"),
-                                        )
-                                    }),
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.restoreSession")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children([(true, "settings.on"), (false, "settings.off")].into_iter().map(
+                                                        |(enabled, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), restore_session == enabled)
+                                                                .id(ElementId::Name(format!("{}-restore", key).into()))
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_restore_session(enabled, cx);
+                                                                    });
+                                                                })
+                                                        },
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.fetchOnOpen")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children([(true, "settings.on"), (false, "settings.off")].into_iter().map(
+                                                        |(enabled, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), fetch_on_open == enabled)
+                                                                .id(key)
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_fetch_on_open(enabled, cx);
+                                                                    });
+                                                                })
+                                                        },
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.fetchPrune")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children([(true, "settings.on"), (false, "settings.off")].into_iter().map(
+                                                        |(enabled, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), fetch_prune == enabled)
+                                                                .id(ElementId::Name(format!("{}-prune", key).into()))
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_fetch_prune(enabled, cx);
+                                                                    });
+                                                                })
+                                                        },
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.autoFetch")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children(AutoFetchInterval::ALL.into_iter().map(|interval| {
+                                                        let settings = self.settings.clone();
+                                                        let (id, label) = auto_fetch_label(interval, locale);
+                                                        SettingsButton::new(label, auto_fetch_interval == interval)
+                                                            .id(ElementId::Name(format!("auto-fetch-{}", id).into()))
+                                                            .on_click(move |_event, _window, cx| {
+                                                                settings.update(cx, |settings, cx| {
+                                                                    settings.set_auto_fetch_interval(interval, cx);
+                                                                });
+                                                            })
+                                                    })),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.autostash")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children([(true, "settings.on"), (false, "settings.off")].into_iter().map(
+                                                        |(enabled, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), autostash == enabled)
+                                                                .id(ElementId::Name(format!("{}-autostash", key).into()))
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_autostash(enabled, cx);
+                                                                    });
+                                                                })
+                                                        },
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.previewOperations")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children([(true, "settings.on"), (false, "settings.off")].into_iter().map(
+                                                        |(enabled, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), preview_operations == enabled)
+                                                                .id(ElementId::Name(format!("{}-preview", key).into()))
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_preview_operations(enabled, cx);
+                                                                    });
+                                                                })
+                                                        },
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.trashDiscards")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children([(true, "settings.on"), (false, "settings.off")].into_iter().map(
+                                                        |(enabled, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), trash_discards == enabled)
+                                                                .id(ElementId::Name(format!("{}-trash-discards", key).into()))
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_trash_discards(enabled, cx);
+                                                                    });
+                                                                })
+                                                        },
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.controlSocket")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children([(true, "settings.on"), (false, "settings.off")].into_iter().map(
+                                                        |(enabled, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), control_socket == enabled)
+                                                                .id(ElementId::Name(format!("{}-control-socket", key).into()))
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_control_socket(enabled, cx);
+                                                                    });
+                                                                })
+                                                        },
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.toastPosition")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children(ToastPosition::ALL.into_iter().map(|position| {
+                                                        let settings = self.settings.clone();
+                                                        let (id, label) = toast_position_label(position);
+                                                        SettingsButton::new(label, toast_position == position)
+                                                            .id(ElementId::Name(format!("toast-position-{}", id).into()))
+                                                            .on_click(move |_event, _window, cx| {
+                                                                settings.update(cx, |settings, cx| {
+                                                                    settings.set_toast_position(position, cx);
+                                                                });
+                                                            })
+                                                    })),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.stickyErrors")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children([(true, "settings.on"), (false, "settings.off")].into_iter().map(
+                                                        |(enabled, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), sticky_errors == enabled)
+                                                                .id(ElementId::Name(format!("{}-sticky-errors", key).into()))
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_sticky_errors(enabled, cx);
+                                                                    });
+                                                                })
+                                                        },
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.maxDiffSize")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children(DiffSizeLimit::ALL.into_iter().map(|limit| {
+                                                        let settings = self.settings.clone();
+                                                        let (id, label) = diff_size_label(limit);
+                                                        SettingsButton::new(label, max_diff_size == limit)
+                                                            .id(ElementId::Name(format!("max-diff-size-{}", id).into()))
+                                                            .on_click(move |_event, _window, cx| {
+                                                                settings.update(cx, |settings, cx| {
+                                                                    settings.set_max_diff_size(limit, cx);
+                                                                });
+                                                            })
+                                                    })),
+                                            ),
+                                    ),
                             )
+                            // Git Authentication section
+                            .children(git_auth)
+                            // Git Identity section
+                            .children(identity)
+                            // Hosting API token section
+                            .children(hosting_token)
+                            // External editor section
+                            .children(external_editor)
                             // Merge Options section
                             .child(
                                 div()
@@ -243,7 +671,7 @@ impl RenderOnce for SettingsView {
                                         div()
                                             .text_xs()
                                             .font_weight(FontWeight::SEMIBOLD)
-                                            .text_color(rgb(0x89b4fa))
+                                            .text_color(theme.blue)
                                             .child(t(locale, "settings.merge")),
                                     )
                                     .child(
@@ -254,7 +682,7 @@ impl RenderOnce for SettingsView {
                                             .child(
                                                 div()
                                                     .text_sm()
-                                                    .text_color(rgb(0x9399b2))
+                                                    .text_color(theme.overlay2)
                                                     .child(t(locale, "settings.mergeLabel")),
                                             )
                                             .child(
@@ -270,7 +698,7 @@ impl RenderOnce for SettingsView {
                                     .child(
                                         div()
                                             .text_xs()
-                                            .text_color(rgb(0x6c7086))
+                                            .text_color(theme.overlay0)
                                             .child(match merge_mode {
                                                 MergeMode::Auto => "Auto: Let git decide the best strategy",
                                                 MergeMode::FfOnly => "FF-Only: Only allow fast-forward merges",
@@ -279,6 +707,150 @@ impl RenderOnce for SettingsView {
                                             }),
                                     ),
                             )
+                            // Commit Messages section
+                            .child(
+                                div()
+                                    .flex()
+                                    .flex_col()
+                                    .gap_3()
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .text_color(theme.blue)
+                                            .child(t(locale, "settings.commitMessages")),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.commitLint")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children(
+                                                        [
+                                                            (CommitLintMode::Off, "settings.commitLintOff"),
+                                                            (CommitLintMode::Warn, "settings.commitLintWarn"),
+                                                            (CommitLintMode::Block, "settings.commitLintBlock"),
+                                                        ]
+                                                        .into_iter()
+                                                        .map(|(mode, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), commit_lint == mode)
+                                                                .id(key)
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_commit_lint(mode, cx);
+                                                                    });
+                                                                })
+                                                        }),
+                                                    ),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.runHooks")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children([(true, "settings.on"), (false, "settings.off")].into_iter().map(
+                                                        |(enabled, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(t(locale, key), run_hooks == enabled)
+                                                                .id(ElementId::Name(format!("{}-run-hooks", key).into()))
+                                                                .on_click(move |_event, _window, cx| {
+                                                                    settings.update(cx, |settings, cx| {
+                                                                        settings.set_run_hooks(enabled, cx);
+                                                                    });
+                                                                })
+                                                        },
+                                                    )),
+                                            ),
+                                    ),
+                            )
+                            // Commit Signing section
+                            .child(
+                                div()
+                                    .flex()
+                                    .flex_col()
+                                    .gap_3()
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .text_color(theme.blue)
+                                            .child(t(locale, "settings.signing")),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(theme.overlay2)
+                                                    .child(t(locale, "settings.signingPolicy")),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_1()
+                                                    .children(
+                                                        [
+                                                            (SigningPolicy::Off, "settings.signingOff"),
+                                                            (SigningPolicy::Warn, "settings.signingWarn"),
+                                                            (SigningPolicy::Block, "settings.signingBlock"),
+                                                        ]
+                                                        .into_iter()
+                                                        .map(|(policy, key)| {
+                                                            let settings = self.settings.clone();
+                                                            SettingsButton::new(
+                                                                t(locale, key),
+                                                                signing_policy == policy,
+                                                            )
+                                                            .id(key)
+                                                            .on_click(move |_event, _window, cx| {
+                                                                settings.update(cx, |settings, cx| {
+                                                                    settings.set_signing_policy(policy, cx);
+                                                                });
+                                                            })
+                                                        }),
+                                                    ),
+                                            ),
+                                    )
+                                    .children(signing_lists)
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(theme.overlay0)
+                                            .child(t(
+                                                locale,
+                                                match signing_policy {
+                                                    SigningPolicy::Off => "settings.signingOffHint",
+                                                    SigningPolicy::Warn => "settings.signingWarnHint",
+                                                    SigningPolicy::Block => "settings.signingBlockHint",
+                                                },
+                                            )),
+                                    ),
+                            )
                             // About section
                             .child(
                                 div()
@@ -289,7 +861,7 @@ impl RenderOnce for SettingsView {
                                         div()
                                             .text_xs()
                                             .font_weight(FontWeight::SEMIBOLD)
-                                            .text_color(rgb(0x89b4fa))
+                                            .text_color(theme.blue)
                                             .child(t(locale, "settings.about")),
                                     )
                                     .child(
@@ -306,7 +878,7 @@ impl RenderOnce for SettingsView {
                                                         div()
                                                             .text_lg()
                                                             .font_weight(FontWeight::BOLD)
-                                                            .text_color(rgb(0xcdd6f4))
+                                                            .text_color(theme.text)
                                                             .child(t(locale, "app.name")),
                                                     )
                                                     .child(
@@ -314,22 +886,22 @@ impl RenderOnce for SettingsView {
                                                             .px_2()
                                                             .py_px()
                                                             .rounded_sm()
-                                                            .bg(rgb(0x313244))
+                                                            .bg(theme.surface0)
                                                             .text_xs()
-                                                            .text_color(rgb(0x9399b2))
+                                                            .text_color(theme.overlay2)
                                                             .child("v0.1.0"),
                                                     ),
                                             )
                                             .child(
                                                 div()
                                                     .text_sm()
-                                                    .text_color(rgb(0x6c7086))
+                                                    .text_color(theme.overlay0)
                                                     .child(t(locale, "app.tagline")),
                                             )
                                             .child(
                                                 div()
                                                     .text_xs()
-                                                    .text_color(rgb(0x6c7086))
+                                                    .text_color(theme.overlay0)
                                                     .child("Powered by git2-rs and gpui"),
                                             ),
                                     ),
@@ -344,7 +916,7 @@ impl RenderOnce for SettingsView {
                                         div()
                                             .text_xs()
                                             .font_weight(FontWeight::SEMIBOLD)
-                                            .text_color(rgb(0x89b4fa))
+                                            .text_color(theme.blue)
                                             .child(t(locale, "settings.keyboard")),
                                     )
                                     .child(
@@ -353,7 +925,7 @@ impl RenderOnce for SettingsView {
                                             .flex_col()
                                             .gap_1()
                                             .text_xs()
-                                            .text_color(rgb(0x9399b2))
+                                            .text_color(theme.overlay2)
                                             .child(KeyboardShortcut::new("Cmd+O", t(locale, "welcome.openRepo")))
                                             .child(KeyboardShortcut::new("Cmd+S", t(locale, "fileList.stageAll")))
                                             .child(KeyboardShortcut::new("Cmd+Enter", t(locale, "commit.button")))
@@ -369,40 +941,151 @@ impl RenderOnce for SettingsView {
     }
 }
 
+fn theme_mode_value(mode: ThemeMode) -> &'static str {
+    match mode {
+        ThemeMode::Dark => "dark",
+        ThemeMode::Light => "light",
+        ThemeMode::System => "system",
+    }
+}
+
+/// Element id suffix and arrow label for a toast corner
+fn graph_palette_label(palette: GraphPalette, locale: Locale) -> (&'static str, String) {
+    match palette {
+        GraphPalette::Theme => ("theme", t(locale, "settings.graphPaletteTheme")),
+        // Published scheme names, not translated
+        GraphPalette::OkabeIto => ("okabe-ito", "Okabe-Ito".to_string()),
+        GraphPalette::TolBright => ("tol-bright", "Tol".to_string()),
+    }
+}
+
+fn auto_fetch_label(interval: AutoFetchInterval, locale: Locale) -> (&'static str, String) {
+    match interval {
+        AutoFetchInterval::Off => ("off", t(locale, "settings.off")),
+        AutoFetchInterval::FiveMinutes => ("5m", "5m".to_string()),
+        AutoFetchInterval::FifteenMinutes => ("15m", "15m".to_string()),
+        AutoFetchInterval::ThirtyMinutes => ("30m", "30m".to_string()),
+    }
+}
+
+fn diff_size_label(limit: DiffSizeLimit) -> (&'static str, &'static str) {
+    match limit {
+        DiffSizeLimit::QuarterMegabyte => ("256k", "256 KB"),
+        DiffSizeLimit::OneMegabyte => ("1m", "1 MB"),
+        DiffSizeLimit::FiveMegabytes => ("5m", "5 MB"),
+        DiffSizeLimit::TwentyMegabytes => ("20m", "20 MB"),
+    }
+}
+
+fn toast_position_label(position: ToastPosition) -> (&'static str, &'static str) {
+    match position {
+        ToastPosition::TopLeft => ("top-left", "↖"),
+        ToastPosition::TopRight => ("top-right", "↗"),
+        ToastPosition::BottomLeft => ("bottom-left", "↙"),
+        ToastPosition::BottomRight => ("bottom-right", "↘"),
+    }
+}
+
+/// Built-in themes followed by the installed user themes
+fn theme_options(locale: Locale, cx: &App) -> Vec<DropdownOption> {
+    let mut options: Vec<DropdownOption> = [
+        (ThemeMode::Dark, "settings.themeDark"),
+        (ThemeMode::Light, "settings.themeLight"),
+        (ThemeMode::System, "settings.themeSystem"),
+    ]
+    .into_iter()
+    .map(|(mode, key)| DropdownOption {
+        value: theme_mode_value(mode).to_string(),
+        label: t(locale, key),
+    })
+    .collect();
+
+    if let Some(registry) = cx.try_global::<ThemeRegistry>() {
+        options.extend(registry.themes.iter().map(|theme| DropdownOption {
+            value: format!("user:{}", theme.name),
+            label: theme.name.clone(),
+        }));
+    }
+    options
+}
+
+fn select_theme(settings: &mut SettingsState, value: &str, cx: &mut Context<SettingsState>) {
+    match value {
+        "dark" => settings.set_theme(ThemeMode::Dark, cx),
+        "light" => settings.set_theme(ThemeMode::Light, cx),
+        "system" => settings.set_theme(ThemeMode::System, cx),
+        other => {
+            if let Some(name) = other.strip_prefix("user:") {
+                settings.set_user_theme(name.to_string(), cx);
+            }
+        }
+    }
+}
+
 #[derive(IntoElement)]
-struct SettingsButton {
+pub(crate) struct SettingsButton {
+    id: Option<ElementId>,
     label: String,
     selected: bool,
+    on_click: Option<ClickHandler>,
 }
 
 impl SettingsButton {
-    fn new(label: impl Into<String>, selected: bool) -> Self {
+    pub(crate) fn new(label: impl Into<String>, selected: bool) -> Self {
         Self {
+            id: None,
             label: label.into(),
             selected,
+            on_click: None,
         }
     }
+
+    /// Set an explicit element id (labels such as "Off" repeat across sections)
+    pub(crate) fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub(crate) fn on_click(
+        mut self,
+        handler: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_click = Some(Box::new(handler));
+        self
+    }
 }
 
 impl RenderOnce for SettingsButton {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let on_click = self.on_click;
+        let id = self
+            .id
+            .unwrap_or_else(|| ElementId::Name(format!("settings-button-{}", self.label).into()));
+
         div()
+            .id(id)
             .px_3()
             .py_1()
             .rounded_md()
             .text_sm()
             .cursor_pointer()
             .bg(if self.selected {
-                rgb(0x89b4fa)
+                theme.blue
             } else {
-                rgb(0x313244)
+                theme.surface0
             })
             .text_color(if self.selected {
-                rgb(0x1e1e2e)
+                theme.base
             } else {
-                rgb(0xcdd6f4)
+                theme.text
+            })
+            .when(!self.selected, |this| this.hover(|s| s.bg(theme.surface1)))
+            .when_some(on_click, |this, handler| {
+                this.on_click(move |event, window, cx| {
+                    handler(event, window, cx);
+                })
             })
-            .when(!self.selected, |this| this.hover(|s| s.bg(rgb(0x45475a))))
             .child(self.label)
     }
 }
@@ -423,7 +1106,8 @@ impl MergeButton {
 }
 
 impl RenderOnce for MergeButton {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         div()
             .px_2()
             .py_1()
@@ -431,16 +1115,16 @@ impl RenderOnce for MergeButton {
             .text_xs()
             .cursor_pointer()
             .bg(if self.selected {
-                rgb(0x89b4fa)
+                theme.blue
             } else {
-                rgb(0x313244)
+                theme.surface0
             })
             .text_color(if self.selected {
-                rgb(0x1e1e2e)
+                theme.base
             } else {
-                rgb(0xcdd6f4)
+                theme.text
             })
-            .when(!self.selected, |this| this.hover(|s| s.bg(rgb(0x45475a))))
+            .when(!self.selected, |this| this.hover(|s| s.bg(theme.surface1)))
             .child(self.label)
     }
 }
@@ -458,13 +1142,18 @@ impl LanguageButton {
 }
 
 impl RenderOnce for LanguageButton {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         // Use short labels for the buttons
         let label = match self.locale {
             Locale::En => "EN",
             Locale::Ja => "日本語",
             Locale::ZhHans => "简体",
             Locale::ZhHant => "繁體",
+            Locale::Ko => "한국어",
+            Locale::De => "DE",
+            Locale::Fr => "FR",
+            Locale::Es => "ES",
         };
 
         div()
@@ -474,16 +1163,16 @@ impl RenderOnce for LanguageButton {
             .text_xs()
             .cursor_pointer()
             .bg(if self.selected {
-                rgb(0x89b4fa)
+                theme.blue
             } else {
-                rgb(0x313244)
+                theme.surface0
             })
             .text_color(if self.selected {
-                rgb(0x1e1e2e)
+                theme.base
             } else {
-                rgb(0xcdd6f4)
+                theme.text
             })
-            .when(!self.selected, |this| this.hover(|s| s.bg(rgb(0x45475a))))
+            .when(!self.selected, |this| this.hover(|s| s.bg(theme.surface1)))
             .child(label)
     }
 }
@@ -504,7 +1193,8 @@ impl KeyboardShortcut {
 }
 
 impl RenderOnce for KeyboardShortcut {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         div()
             .flex()
             .items_center()
@@ -512,7 +1202,7 @@ impl RenderOnce for KeyboardShortcut {
             .py_1()
             .child(
                 div()
-                    .text_color(rgb(0x9399b2))
+                    .text_color(theme.overlay2)
                     .child(self.description),
             )
             .child(
@@ -520,8 +1210,8 @@ impl RenderOnce for KeyboardShortcut {
                     .px_2()
                     .py_px()
                     .rounded_sm()
-                    .bg(rgb(0x313244))
-                    .text_color(rgb(0xcdd6f4))
+                    .bg(theme.surface0)
+                    .text_color(theme.text)
                     .font_weight(FontWeight::MEDIUM)
                     .child(self.shortcut),
             )
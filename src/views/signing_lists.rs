@@ -0,0 +1,133 @@
+#![allow(dead_code)]
+
+use crate::components::{TextInputChanged, TextInputView};
+use crate::i18n::{t, ActiveLocale};
+use crate::state::SettingsState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Settings editor for the branches the signing policy guards and the keys
+/// it trusts, each entered as a comma-separated list
+pub struct SigningListsView {
+    settings: Entity<SettingsState>,
+    branches_input: Entity<TextInputView>,
+    signers_input: Entity<TextInputView>,
+}
+
+impl SigningListsView {
+    pub fn new(settings: Entity<SettingsState>, cx: &mut Context<Self>) -> Self {
+        let locale = cx.locale();
+        let data = &settings.read(cx).data;
+        let branches = data.protected_branches.join(", ");
+        let signers = data.allowed_signers.join(", ");
+        let branches_input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .with_placeholder("main, master")
+                .with_content(branches)
+        });
+        let signers_input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .with_placeholder(t(locale, "settings.allowedSignersAny"))
+                .with_content(signers)
+        });
+        for input in [&branches_input, &signers_input] {
+            cx.subscribe(input, |_this, _input, _event: &TextInputChanged, cx| {
+                cx.notify();
+            })
+            .detach();
+        }
+
+        Self {
+            settings,
+            branches_input,
+            signers_input,
+        }
+    }
+
+    fn typed(input: &Entity<TextInputView>, cx: &App) -> Vec<String> {
+        split_list(input.read(cx).content())
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        let branches = Self::typed(&self.branches_input, cx);
+        let signers = Self::typed(&self.signers_input, cx);
+        self.settings.update(cx, |settings, cx| {
+            settings.set_protected_branches(branches, cx);
+            settings.set_allowed_signers(signers, cx);
+        });
+        cx.notify();
+    }
+}
+
+/// Entries of a comma-separated list, trimmed and without blanks
+fn split_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| entry.to_string())
+        .collect()
+}
+
+impl Render for SigningListsView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let locale = cx.locale();
+        let data = &self.settings.read(cx).data;
+        let can_save = Self::typed(&self.branches_input, cx) != data.protected_branches
+            || Self::typed(&self.signers_input, cx) != data.allowed_signers;
+
+        let row = |label: &str, input: &Entity<TextInputView>| {
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(theme.overlay2)
+                        .child(t(locale, label)),
+                )
+                .child(input.clone())
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(row("settings.protectedBranches", &self.branches_input))
+            .child(row("settings.allowedSigners", &self.signers_input))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.overlay0)
+                            .child(t(locale, "settings.signingListsHint")),
+                    )
+                    .child(
+                        div()
+                            .id("save-signing-lists")
+                            .flex_shrink_0()
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .bg(if can_save { theme.blue } else { theme.surface0 })
+                            .text_color(if can_save { theme.base } else { theme.overlay0 })
+                            .child(t(locale, "common.save"))
+                            .when(can_save, |this| {
+                                this.cursor_pointer()
+                                    .hover(|s| s.bg(theme.lavender))
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.save(cx)
+                                    }))
+                            }),
+                    ),
+            )
+    }
+}
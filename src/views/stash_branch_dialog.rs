@@ -0,0 +1,264 @@
+#![allow(dead_code)]
+
+use crate::actions::ShowConflictDialog;
+use crate::git::{BranchKind, FileStatusType};
+use crate::state::GitState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Picks a local branch to check out and apply the pending stash onto
+#[derive(IntoElement)]
+pub struct StashBranchDialog {
+    git_state: Entity<GitState>,
+}
+
+impl StashBranchDialog {
+    pub fn new(git_state: Entity<GitState>) -> Self {
+        Self { git_state }
+    }
+}
+
+impl RenderOnce for StashBranchDialog {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let git_state = self.git_state.read(cx);
+        let Some(request) = git_state.pending_stash_branch.clone() else {
+            return div().into_any_element();
+        };
+        let branches: Vec<(String, bool)> = git_state
+            .branches
+            .iter()
+            .filter(|b| b.branch_type == BranchKind::Local)
+            .map(|b| (b.name.clone(), b.is_head))
+            .collect();
+        // Untracked files are left alone; tracked changes would mix with the stash
+        let is_dirty = git_state
+            .files
+            .iter()
+            .any(|f| f.status != FileStatusType::Untracked);
+        let can_apply = request.branch.is_some() && !is_dirty;
+
+        let branch_rows = branches.into_iter().map(|(name, is_head)| {
+            let selected = request.branch.as_ref() == Some(&name);
+            let git_state = self.git_state.clone();
+            let branch = name.clone();
+            div()
+                .id(SharedString::from(format!("stash-branch-{}", name)))
+                .flex()
+                .items_center()
+                .justify_between()
+                .px_3()
+                .py_1()
+                .rounded_md()
+                .text_sm()
+                .cursor_pointer()
+                .when(selected, |this| this.bg(theme.blue).text_color(theme.base))
+                .when(!selected, |this| {
+                    this.text_color(theme.text).hover(|s| s.bg(theme.surface0))
+                })
+                .child(div().truncate().child(name))
+                .when(is_head, |this| {
+                    this.child(div().flex_shrink_0().text_xs().child("current"))
+                })
+                .on_click(move |_event, _window, cx| {
+                    git_state.update(cx, |state, cx| state.set_stash_branch_target(&branch, cx));
+                })
+        });
+        let git_state_close = self.git_state.clone();
+        let git_state_keep = self.git_state.clone();
+        let git_state_cancel = self.git_state.clone();
+        let git_state_confirm = self.git_state.clone();
+        let keep_stash = request.keep_stash;
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_start()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Apply Stash onto Branch"),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .truncate()
+                                    .child(format!(
+                                        "stash@{{{}}}: {}",
+                                        request.index, request.message
+                                    )),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("stash-branch-close")
+                            .px_2()
+                            .rounded_md()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0))
+                            .child("×")
+                            .on_click(move |_event, _window, cx| {
+                                git_state_close
+                                    .update(cx, |state, cx| state.cancel_stash_branch(cx));
+                            }),
+                    ),
+            )
+            .when(is_dirty, |this| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.yellow_bg)
+                        .text_sm()
+                        .text_color(theme.yellow)
+                        .child("Commit or stash your local changes before switching branches"),
+                )
+            })
+            .when_some(request.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error),
+                )
+            })
+            // Branches
+            .child(
+                div()
+                    .id("stash-branches")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .children(branch_rows),
+            )
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .pt_2()
+                    .border_t_1()
+                    .border_color(theme.surface0)
+                    .child(
+                        div()
+                            .id("stash-branch-keep")
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .child(
+                                div()
+                                    .w_4()
+                                    .h_4()
+                                    .flex()
+                                    .items_center()
+                                    .justify_center()
+                                    .rounded_sm()
+                                    .border_1()
+                                    .border_color(theme.overlay0)
+                                    .when(keep_stash, |this| {
+                                        this.bg(theme.blue)
+                                            .border_color(theme.blue)
+                                            .text_xs()
+                                            .text_color(theme.base)
+                                            .child("✓")
+                                    }),
+                            )
+                            .child("Keep stash")
+                            .on_click(move |_event, _window, cx| {
+                                git_state_keep.update(cx, |state, cx| {
+                                    state.set_stash_branch_keep(!keep_stash, cx)
+                                });
+                            }),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .id("stash-branch-cancel")
+                                    .px_4()
+                                    .py_2()
+                                    .rounded_md()
+                                    .bg(theme.surface0)
+                                    .text_sm()
+                                    .text_color(theme.text)
+                                    .cursor_pointer()
+                                    .hover(|s| s.bg(theme.surface1))
+                                    .child("Cancel")
+                                    .on_click(move |_event, _window, cx| {
+                                        git_state_cancel
+                                            .update(cx, |state, cx| state.cancel_stash_branch(cx));
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .id("stash-branch-confirm")
+                                    .px_4()
+                                    .py_2()
+                                    .rounded_md()
+                                    .bg(if can_apply { theme.blue } else { theme.surface1 })
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(if can_apply { theme.base } else { theme.overlay0 })
+                                    .child("Check Out & Apply")
+                                    .when(can_apply, |this| {
+                                        this.cursor_pointer()
+                                            .hover(|s| s.bg(theme.lavender))
+                                            .on_click(move |_event, window, cx| {
+                                                let result = git_state_confirm
+                                                    .update(cx, |state, cx| {
+                                                        state.confirm_stash_branch(cx)
+                                                    });
+                                                match result {
+                                                    Ok(true) => window.dispatch_action(
+                                                        Box::new(ShowConflictDialog),
+                                                        cx,
+                                                    ),
+                                                    Ok(false) => {}
+                                                    Err(e) => log::error!(
+                                                        "Failed to apply stash onto branch: {}",
+                                                        e
+                                                    ),
+                                                }
+                                            })
+                                    }),
+                            ),
+                    ),
+            )
+            .into_any_element()
+    }
+}
@@ -0,0 +1,248 @@
+#![allow(dead_code)]
+
+use crate::components::{CopyDetailsButton, TextInputView};
+use crate::state::{ErrorDetails, GitState};
+use crate::theme::{ActiveTheme, Theme};
+use git2::StashFlags;
+use gpui::prelude::*;
+use gpui::*;
+
+/// Emitted when the stash dialog should be closed
+#[derive(Clone, Debug)]
+pub struct StashDialogDismissed;
+
+impl EventEmitter<StashDialogDismissed> for StashDialog {}
+
+/// Stash local changes with a message and options
+pub struct StashDialog {
+    git_state: Entity<GitState>,
+    message_input: Entity<TextInputView>,
+    include_untracked: bool,
+    keep_index: bool,
+    /// Stash only the files selected in the file list
+    only_selected: bool,
+    error: Option<ErrorDetails>,
+}
+
+impl StashDialog {
+    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+        let message_input =
+            cx.new(|cx| TextInputView::new(cx).with_placeholder("Stash message (optional)"));
+        let only_selected = !git_state.read(cx).selected_files.is_empty();
+
+        Self {
+            git_state,
+            message_input,
+            include_untracked: false,
+            keep_index: false,
+            only_selected,
+            error: None,
+        }
+    }
+
+    pub fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.message_input.read(cx).focus_handle(cx)
+    }
+
+    fn save(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        let message = self.message_input.read(cx).content().trim().to_string();
+        let message = (!message.is_empty()).then_some(message);
+
+        let mut flags = StashFlags::DEFAULT;
+        if self.include_untracked {
+            flags |= StashFlags::INCLUDE_UNTRACKED;
+        }
+        if self.keep_index {
+            flags |= StashFlags::KEEP_INDEX;
+        }
+
+        let result = self.git_state.update(cx, |state, cx| {
+            let paths = if self.only_selected {
+                state.selected_files.clone()
+            } else {
+                Vec::new()
+            };
+            state.stash_save(message.as_deref(), flags, &paths, cx)
+        });
+
+        match result {
+            Ok(()) => cx.emit(StashDialogDismissed),
+            Err(e) => {
+                log::error!("Failed to save stash: {}", e);
+                self.error = Some(self.git_state.read(cx).error_details("Stash changes", &e));
+                cx.notify();
+            }
+        }
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(StashDialogDismissed);
+    }
+}
+
+impl Render for StashDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let selected_count = self.git_state.read(cx).selected_files.len();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_4()
+            // Header
+            .child(
+                div()
+                    .text_lg()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(theme.text)
+                    .child("Stash Changes"),
+            )
+            // Message
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(div().text_xs().text_color(theme.overlay2).child("Message"))
+                    .child(self.message_input.clone())
+                    .when(self.only_selected && selected_count > 0, |this| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(theme.overlay0)
+                                .child("A stash of selected files takes git's default message"),
+                        )
+                    }),
+            )
+            // Options
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        checkbox(
+                            "stash-untracked",
+                            "Include untracked files",
+                            self.include_untracked,
+                            &theme,
+                        )
+                        .on_click(cx.listener(|this, _event, _window, cx| {
+                            this.include_untracked = !this.include_untracked;
+                            cx.notify();
+                        })),
+                    )
+                    .child(
+                        checkbox(
+                            "stash-keep-index",
+                            "Keep staged changes in the index",
+                            self.keep_index,
+                            &theme,
+                        )
+                        .on_click(cx.listener(|this, _event, _window, cx| {
+                            this.keep_index = !this.keep_index;
+                            cx.notify();
+                        })),
+                    )
+                    .when(selected_count > 0, |this| {
+                        this.child(
+                            checkbox(
+                                "stash-only-selected",
+                                &format!(
+                                    "Only the {} selected file{}",
+                                    selected_count,
+                                    if selected_count == 1 { "" } else { "s" }
+                                ),
+                                self.only_selected,
+                                &theme,
+                            )
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.only_selected = !this.only_selected;
+                                cx.notify();
+                            })),
+                        )
+                    }),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error.message)
+                        .child(CopyDetailsButton::new("stash-error-details", error.report)),
+                )
+            })
+            // Actions
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_2()
+                    .mt_auto()
+                    .child(
+                        div()
+                            .id("stash-cancel")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(theme.surface0)
+                            .text_sm()
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("Cancel")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("stash-save")
+                            .px_4()
+                            .py_2()
+                            .rounded_md()
+                            .bg(theme.mauve)
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.base)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.pink))
+                            .child("Stash")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.save(window, cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+fn checkbox(id: &'static str, label: &str, checked: bool, theme: &Theme) -> Stateful<Div> {
+    div()
+        .id(id)
+        .flex()
+        .items_center()
+        .gap_2()
+        .cursor_pointer()
+        .child(
+            div()
+                .size_4()
+                .rounded_sm()
+                .border_1()
+                .border_color(if checked { theme.blue } else { theme.overlay0 })
+                .bg(if checked { theme.blue } else { theme.surface0 })
+                .flex()
+                .items_center()
+                .justify_center()
+                .when(checked, |this| {
+                    this.child(div().text_xs().text_color(theme.base).child("✓"))
+                }),
+        )
+        .child(div().text_sm().text_color(theme.text).child(label.to_string()))
+}
@@ -0,0 +1,179 @@
+#![allow(dead_code)]
+
+use crate::actions::FetchFullHistory;
+use crate::git::BranchKind;
+use crate::i18n::{t, t_with_vars, time_ago, ActiveLocale};
+use crate::state::GitState;
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+use std::collections::HashSet;
+
+/// Bottom bar summarizing the open repository: branch and upstream,
+/// ahead/behind counts, changed files, any operation in progress and when
+/// the remotes were last fetched. A shallow clone offers to fetch the rest
+/// of its history.
+#[derive(IntoElement)]
+pub struct StatusBar {
+    git_state: Entity<GitState>,
+}
+
+impl StatusBar {
+    pub fn new(git_state: Entity<GitState>) -> Self {
+        Self { git_state }
+    }
+}
+
+impl RenderOnce for StatusBar {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
+        let locale = cx.locale();
+        let git_state = self.git_state.read(cx);
+        let info = git_state.repository_info.clone();
+        let is_detached = git_state.is_detached();
+        let is_fetching = git_state.is_fetching;
+
+        let branch = if is_detached {
+            "HEAD detached".to_string()
+        } else {
+            git_state
+                .current_branch()
+                .unwrap_or("No branch")
+                .to_string()
+        };
+        let upstream = git_state
+            .branches
+            .iter()
+            .find(|b| b.is_head && b.branch_type == BranchKind::Local)
+            .and_then(|b| b.upstream.clone());
+        let (ahead, behind) = info.as_ref().map_or((0, 0), |i| (i.ahead, i.behind));
+        let state_label = info.as_ref().and_then(|i| i.state_label());
+        let last_fetch = info.as_ref().and_then(|i| i.last_fetch);
+        let is_shallow = info.as_ref().is_some_and(|i| i.is_shallow);
+
+        // A file staged and modified again is listed twice
+        let changed = git_state
+            .files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect::<HashSet<_>>()
+            .len();
+
+        let separator = || div().text_color(theme.surface1).child("│");
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_4()
+            .px_3()
+            .h(px(24.0))
+            .flex_none()
+            .bg(theme.mantle)
+            .border_t_1()
+            .border_color(theme.surface0)
+            .text_xs()
+            .text_color(theme.overlay2)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .overflow_hidden()
+                    .child(
+                        div()
+                            .text_color(if is_detached { theme.yellow } else { theme.blue })
+                            .truncate()
+                            .child(branch),
+                    )
+                    .when_some(upstream, |this, upstream| {
+                        this.child(
+                            div()
+                                .text_color(theme.overlay0)
+                                .child(format!("→ {}", upstream)),
+                        )
+                    })
+                    .when(ahead > 0 || behind > 0, |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .gap_1()
+                                .child(
+                                    div()
+                                        .text_color(if ahead > 0 {
+                                            theme.green
+                                        } else {
+                                            theme.overlay0
+                                        })
+                                        .child(format!("↑{}", ahead)),
+                                )
+                                .child(
+                                    div()
+                                        .text_color(if behind > 0 {
+                                            theme.peach
+                                        } else {
+                                            theme.overlay0
+                                        })
+                                        .child(format!("↓{}", behind)),
+                                ),
+                        )
+                    })
+                    .child(separator())
+                    .child(if changed == 0 {
+                        div().text_color(theme.green).child("Clean")
+                    } else {
+                        div().text_color(theme.yellow).child(if changed == 1 {
+                            "1 changed file".to_string()
+                        } else {
+                            format!("{} changed files", changed)
+                        })
+                    })
+                    .when_some(state_label, |this, label| {
+                        this.child(separator()).child(
+                            div()
+                                .px_1()
+                                .rounded_sm()
+                                .bg(theme.yellow_bg)
+                                .text_color(theme.yellow)
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .child(label),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .flex_shrink_0()
+                    .when(is_shallow, |this| {
+                        this.child(
+                            div()
+                                .id("status-fetch-full-history")
+                                .px_1()
+                                .rounded_sm()
+                                .bg(theme.blue_bg)
+                                .text_color(theme.blue)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.surface1))
+                                .child("Shallow clone · Fetch full history")
+                                .on_click(|_event, window, cx| {
+                                    window.dispatch_action(Box::new(FetchFullHistory), cx);
+                                }),
+                        )
+                        .child(separator())
+                    })
+                    .child(if is_fetching {
+                        t(locale, "status.fetching")
+                    } else {
+                        match last_fetch {
+                            Some(time) => {
+                                let time = time_ago(locale, time);
+                                t_with_vars(locale, "status.fetched", &[("time", &time)])
+                            }
+                            None => t(locale, "status.neverFetched"),
+                        }
+                    }),
+            )
+    }
+}
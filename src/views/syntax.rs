@@ -0,0 +1,195 @@
+#![allow(dead_code)]
+
+use crate::git::{DiffLineType, FileDiff};
+use crate::theme::{Appearance, Theme as AppTheme};
+use gpui::Rgba;
+use parking_lot::Mutex;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{
+    Color, ScopeSelectors, Style, StyleModifier, Theme, ThemeItem, ThemeSettings,
+};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+
+/// Maximum number of highlighted diffs kept in memory
+const MAX_CACHED_DIFFS: usize = 32;
+
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static DARK_THEME: LazyLock<Theme> = LazyLock::new(|| syntect_theme(&AppTheme::dark()));
+static LIGHT_THEME: LazyLock<Theme> = LazyLock::new(|| syntect_theme(&AppTheme::light()));
+static CACHE: LazyLock<Mutex<HashMap<u64, HighlightedDiff>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A run of text rendered with a single foreground color
+#[derive(Clone, Debug)]
+pub struct HighlightSpan {
+    pub text: String,
+    pub color: Rgba,
+}
+
+/// Highlighted spans for each line of a diff (empty for header lines)
+pub type HighlightedDiff = Arc<Vec<Vec<HighlightSpan>>>;
+
+/// Highlight the first `limit` lines of a diff based on its file extension,
+/// using colors for the given appearance.
+///
+/// Returns None when no syntax is known for the file. Results are cached by
+/// diff content so re-rendering the viewer does not re-run the highlighter.
+pub fn highlight_diff(
+    diff: &FileDiff,
+    limit: usize,
+    appearance: Appearance,
+) -> Option<HighlightedDiff> {
+    let syntax = syntax_for_path(&diff.path)?;
+    let key = cache_key(diff, limit, appearance);
+    let theme: &Theme = match appearance {
+        Appearance::Dark => &DARK_THEME,
+        Appearance::Light => &LIGHT_THEME,
+    };
+
+    if let Some(cached) = CACHE.lock().get(&key) {
+        return Some(cached.clone());
+    }
+
+    // Old and new sides are highlighted separately so multi-line constructs
+    // (block comments, strings) carry over correctly on each side
+    let mut old_side = HighlightLines::new(syntax, theme);
+    let mut new_side = HighlightLines::new(syntax, theme);
+
+    let lines = diff
+        .lines
+        .iter()
+        .take(limit)
+        .map(|line| {
+            let content = if line.content.ends_with('\n') {
+                line.content.clone()
+            } else {
+                format!("{}\n", line.content)
+            };
+
+            let ranges = match line.line_type {
+                DiffLineType::Addition => new_side.highlight_line(&content, &SYNTAX_SET).ok(),
+                DiffLineType::Deletion => old_side.highlight_line(&content, &SYNTAX_SET).ok(),
+                DiffLineType::Context => {
+                    let _ = old_side.highlight_line(&content, &SYNTAX_SET);
+                    new_side.highlight_line(&content, &SYNTAX_SET).ok()
+                }
+                DiffLineType::Header => {
+                    // Hunks are not contiguous, so restart parsing at each header
+                    old_side = HighlightLines::new(syntax, theme);
+                    new_side = HighlightLines::new(syntax, theme);
+                    None
+                }
+            };
+
+            ranges.map(|ranges| to_spans(&ranges)).unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let highlighted = Arc::new(lines);
+
+    let mut cache = CACHE.lock();
+    if cache.len() >= MAX_CACHED_DIFFS {
+        cache.clear();
+    }
+    cache.insert(key, highlighted.clone());
+
+    Some(highlighted)
+}
+
+fn syntax_for_path(path: &str) -> Option<&'static SyntaxReference> {
+    let filename = path.rsplit('/').next().unwrap_or(path);
+    let extension = filename.rsplit_once('.').map(|(_, ext)| ext).unwrap_or(filename);
+    SYNTAX_SET.find_syntax_by_extension(extension)
+}
+
+fn cache_key(diff: &FileDiff, limit: usize, appearance: Appearance) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (appearance as u8).hash(&mut hasher);
+    diff.path.hash(&mut hasher);
+    for line in diff.lines.iter().take(limit) {
+        line.content.hash(&mut hasher);
+        (line.line_type as u8).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn to_spans(ranges: &[(Style, &str)]) -> Vec<HighlightSpan> {
+    ranges
+        .iter()
+        .map(|(style, text)| HighlightSpan {
+            text: text.trim_end_matches('\n').to_string(),
+            color: color_to_rgba(style.foreground),
+        })
+        .filter(|span| !span.text.is_empty())
+        .collect()
+}
+
+fn color_to_rgba(color: Color) -> Rgba {
+    Rgba {
+        r: color.r as f32 / 255.0,
+        g: color.g as f32 / 255.0,
+        b: color.b as f32 / 255.0,
+        a: 1.0,
+    }
+}
+
+fn rgba_to_color(color: Rgba) -> Color {
+    Color {
+        r: (color.r * 255.0).round() as u8,
+        g: (color.g * 255.0).round() as u8,
+        b: (color.b * 255.0).round() as u8,
+        a: 0xff,
+    }
+}
+
+/// Build a syntect theme from the app theme's Catppuccin colors
+fn syntect_theme(theme: &AppTheme) -> Theme {
+    let scopes = [
+        ("comment, punctuation.definition.comment", theme.overlay0),
+        ("string, constant.character.escape", theme.green),
+        ("constant.numeric, constant.language", theme.peach),
+        ("constant.other, variable.other.constant", theme.peach),
+        ("keyword, storage", theme.mauve),
+        ("keyword.operator", theme.sky),
+        ("entity.name.function, support.function", theme.blue),
+        ("meta.function-call", theme.blue),
+        ("entity.name.type, entity.name.class", theme.yellow),
+        ("support.type, support.class", theme.yellow),
+        ("entity.name.tag", theme.blue),
+        ("entity.other.attribute-name", theme.yellow),
+        ("variable.parameter", theme.maroon),
+        ("variable.language", theme.red),
+        ("entity.name.namespace, entity.name.module", theme.pink),
+        ("markup.heading", theme.red),
+        ("punctuation", theme.overlay2),
+    ];
+
+    let scopes = scopes
+        .iter()
+        .filter_map(|(selector, color)| {
+            Some(ThemeItem {
+                scope: ScopeSelectors::from_str(selector).ok()?,
+                style: StyleModifier {
+                    foreground: Some(rgba_to_color(*color)),
+                    background: None,
+                    font_style: None,
+                },
+            })
+        })
+        .collect();
+
+    Theme {
+        name: Some("Catppuccin".to_string()),
+        settings: ThemeSettings {
+            foreground: Some(rgba_to_color(theme.text)),
+            ..Default::default()
+        },
+        scopes,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,332 @@
+#![allow(dead_code)]
+
+use crate::git::TagInfo;
+use crate::components::CopyDetailsButton;
+use crate::state::{ErrorDetails, GitState};
+use crate::theme::{ActiveTheme, Theme};
+use gpui::prelude::*;
+use gpui::*;
+
+/// Events emitted by the tags panel
+#[derive(Clone, Debug)]
+pub enum TagsPanelEvent {
+    /// Push the named tag to origin
+    Push(String),
+    Dismissed,
+}
+
+impl EventEmitter<TagsPanelEvent> for TagsPanel {}
+
+/// Lists the repository's tags with their target commits and lets the user
+/// delete, check out, push or jump to one
+pub struct TagsPanel {
+    git_state: Entity<GitState>,
+    /// Tag awaiting a second click to confirm deletion
+    pending_delete: Option<String>,
+    error: Option<ErrorDetails>,
+}
+
+impl TagsPanel {
+    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+        cx.observe(&git_state, |_this, _git_state, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            git_state,
+            pending_delete: None,
+            error: None,
+        }
+    }
+
+    fn delete_tag(&mut self, name: String, cx: &mut Context<Self>) {
+        if self.pending_delete.as_ref() != Some(&name) {
+            self.pending_delete = Some(name);
+            cx.notify();
+            return;
+        }
+
+        self.pending_delete = None;
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.delete_tag(&name, cx));
+        self.set_result(result, "delete tag", cx);
+    }
+
+    fn checkout_tag(&mut self, sha: String, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.checkout_commit(&sha, cx));
+        self.set_result(result, "checkout tag", cx);
+    }
+
+    /// Scroll the graph to the tagged commit and close the panel
+    fn show_in_graph(&mut self, sha: String, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.focus_commit(&sha, cx));
+        match result {
+            Ok(()) => cx.emit(TagsPanelEvent::Dismissed),
+            Err(e) => self.set_result(Err(e), "show tag in graph", cx),
+        }
+    }
+
+    fn set_result(&mut self, result: anyhow::Result<()>, action: &str, cx: &mut Context<Self>) {
+        match result {
+            Ok(()) => self.error = None,
+            Err(e) => {
+                log::error!("Failed to {}: {}", action, e);
+                self.error = Some(self.git_state.read(cx).error_details(action, &e));
+            }
+        }
+        cx.notify();
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(TagsPanelEvent::Dismissed);
+    }
+}
+
+impl Render for TagsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let tags = self.git_state.read(cx).tags.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Tags"),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .child(format!("{} tags", tags.len())),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("close-tags")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error.message)
+                        .child(CopyDetailsButton::new("tag-error-details", error.report)),
+                )
+            })
+            // Tag list
+            .child(
+                div()
+                    .id("tag-list")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .when(tags.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .py_8()
+                                .text_sm()
+                                .text_center()
+                                .text_color(theme.overlay0)
+                                .child("No tags"),
+                        )
+                    })
+                    .children(
+                        tags.iter()
+                            .enumerate()
+                            .map(|(ix, tag)| self.render_tag(ix, tag, cx)),
+                    ),
+            )
+    }
+}
+
+impl TagsPanel {
+    fn render_tag(&self, ix: usize, tag: &TagInfo, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let is_pending_delete = self.pending_delete.as_ref() == Some(&tag.name);
+        let sha_show = tag.sha.clone();
+        let sha_checkout = tag.sha.clone();
+        let name_push = tag.name.clone();
+        let name_delete = tag.name.clone();
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .hover(|s| s.bg(theme.surface0))
+            .child(
+                div()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .gap_px()
+                    .overflow_hidden()
+                    // Name and date
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .px_1()
+                                    .rounded_sm()
+                                    .bg(theme.yellow)
+                                    .text_xs()
+                                    .text_color(theme.base)
+                                    .child(tag.name.clone()),
+                            )
+                            .when(tag.is_annotated, |this| {
+                                this.child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(theme.overlay2)
+                                        .child("annotated"),
+                                )
+                            })
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.overlay0)
+                                    .child(tag.timestamp.format("%Y-%m-%d %H:%M").to_string()),
+                            ),
+                    )
+                    // Target commit
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .text_xs()
+                            .overflow_hidden()
+                            .child(
+                                div()
+                                    .font_family("monospace")
+                                    .text_color(theme.blue)
+                                    .child(tag.short_sha.clone()),
+                            )
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .truncate()
+                                    .text_color(theme.text)
+                                    .child(tag.summary.clone()),
+                            ),
+                    )
+                    // Tag message
+                    .when_some(tag.message.clone().filter(|m| !m.is_empty()), |this, message| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(theme.overlay2)
+                                .truncate()
+                                .child(message),
+                        )
+                    }),
+            )
+            .child(
+                tag_button(format!("tag-show-{}", ix), "Show", &theme).on_click(cx.listener(
+                    move |this, _event, _window, cx| {
+                        this.show_in_graph(sha_show.clone(), cx);
+                    },
+                )),
+            )
+            .child(
+                tag_button(format!("tag-checkout-{}", ix), "Checkout", &theme).on_click(
+                    cx.listener(move |this, _event, _window, cx| {
+                        this.checkout_tag(sha_checkout.clone(), cx);
+                    }),
+                ),
+            )
+            .child(
+                tag_button(format!("tag-push-{}", ix), "Push", &theme).on_click(cx.listener(
+                    move |_this, _event, _window, cx| {
+                        cx.emit(TagsPanelEvent::Push(name_push.clone()));
+                    },
+                )),
+            )
+            .child(
+                div()
+                    .id(ElementId::Name(format!("tag-delete-{}", ix).into()))
+                    .px_2()
+                    .py_1()
+                    .rounded_md()
+                    .text_xs()
+                    .when(is_pending_delete, |this| this.bg(theme.red).text_color(theme.base))
+                    .when(!is_pending_delete, |this| {
+                        this.bg(theme.surface0).text_color(theme.red)
+                    })
+                    .cursor_pointer()
+                    .hover(|s| s.bg(theme.maroon).text_color(theme.base))
+                    .child(if is_pending_delete { "Confirm" } else { "Delete" })
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.delete_tag(name_delete.clone(), cx);
+                    })),
+            )
+    }
+}
+
+fn tag_button(id: String, label: &'static str, theme: &Theme) -> Stateful<Div> {
+    div()
+        .id(ElementId::Name(id.into()))
+        .px_2()
+        .py_1()
+        .rounded_md()
+        .bg(theme.surface0)
+        .text_xs()
+        .text_color(theme.text)
+        .cursor_pointer()
+        .hover(|s| s.bg(theme.surface1))
+        .child(label)
+}
@@ -0,0 +1,330 @@
+#![allow(dead_code)]
+
+use crate::components::AuthorLink;
+use crate::git::TodoItem;
+use crate::state::{GitState, TodoState};
+use crate::theme::{ActiveTheme, Theme};
+use gpui::prelude::*;
+use gpui::*;
+
+/// Emitted when the TODO panel should be closed
+#[derive(Clone, Debug)]
+pub struct TodosDismissed;
+
+impl EventEmitter<TodosDismissed> for TodosView {}
+
+/// TODO/FIXME comments in tracked files, linked to their file and line and
+/// to the last commit that touched them
+pub struct TodosView {
+    git_state: Entity<GitState>,
+    todo_state: Entity<TodoState>,
+    error: Option<String>,
+}
+
+impl TodosView {
+    pub fn new(
+        git_state: Entity<GitState>,
+        todo_state: Entity<TodoState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        cx.observe(&todo_state, |_this, _todo_state, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        // Rescan when the repository changes; unchanged files are reused
+        cx.observe(&git_state, |this, _git_state, cx| {
+            this.scan(cx);
+        })
+        .detach();
+
+        let mut view = Self {
+            git_state,
+            todo_state,
+            error: None,
+        };
+        view.scan(cx);
+        view
+    }
+
+    fn scan(&mut self, cx: &mut Context<Self>) {
+        let Some(path) = self.git_state.read(cx).path.clone() else {
+            return;
+        };
+        self.todo_state.update(cx, |state, cx| state.scan(path, cx));
+    }
+
+    fn open_file(&mut self, path: &str, cx: &mut Context<Self>) {
+        if let Some(repo) = self.git_state.read(cx).path.clone() {
+            cx.open_with_system(&repo.join(path));
+        }
+    }
+
+    /// Open the line on the hosting service at the commit that last touched it
+    fn open_permalink(&mut self, item: &TodoItem, cx: &mut Context<Self>) {
+        let Some(commit) = &item.commit else {
+            return;
+        };
+        let Some(web_url) = self.git_state.read(cx).remote_web_url() else {
+            return;
+        };
+        let url = web_url.permalink(&commit.sha, &item.path, Some(commit.line..=commit.line));
+        cx.open_url(&url);
+    }
+
+    /// Scroll the graph to the commit and close the panel
+    fn show_commit(&mut self, sha: &str, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.focus_commit(sha, cx));
+        match result {
+            Ok(()) => cx.emit(TodosDismissed),
+            Err(e) => {
+                log::error!("Failed to show commit: {}", e);
+                self.error = Some(e.to_string());
+                cx.notify();
+            }
+        }
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(TodosDismissed);
+    }
+}
+
+impl Render for TodosView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let todo_state = self.todo_state.read(cx);
+        let items = todo_state.items.clone();
+        let is_scanning = todo_state.is_scanning;
+        let error = self.error.clone().or_else(|| todo_state.error.clone());
+        let has_permalinks = self.git_state.read(cx).remote_web_url().is_some();
+
+        // Group consecutive items by file; items are sorted by path
+        let mut groups: Vec<(String, Vec<TodoItem>)> = Vec::new();
+        for item in items.iter() {
+            match groups.last_mut() {
+                Some((path, group)) if *path == item.path => group.push(item.clone()),
+                _ => groups.push((item.path.clone(), vec![item.clone()])),
+            }
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("TODOs"),
+                            )
+                            .child(div().text_sm().text_color(theme.overlay2).child(
+                                if is_scanning {
+                                    "Scanning tracked files...".to_string()
+                                } else {
+                                    format!("{} in {} files", items.len(), groups.len())
+                                },
+                            )),
+                    )
+                    .child(
+                        div()
+                            .id("close-todos")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    ),
+            )
+            .when_some(error, |this, error| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error),
+                )
+            })
+            // Items grouped by file
+            .child(
+                div()
+                    .id("todo-list")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .when(items.is_empty() && !is_scanning, |this| {
+                        this.child(
+                            div()
+                                .py_8()
+                                .text_sm()
+                                .text_center()
+                                .text_color(theme.overlay0)
+                                .child("No TODO or FIXME comments"),
+                        )
+                    })
+                    .children(groups.into_iter().enumerate().map(|(group_ix, (path, group))| {
+                        self.render_group(group_ix, path, group, has_permalinks, cx)
+                    })),
+            )
+    }
+}
+
+impl TodosView {
+    fn render_group(
+        &self,
+        group_ix: usize,
+        path: String,
+        items: Vec<TodoItem>,
+        has_permalinks: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = *cx.theme();
+        let path_open = path.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_px()
+            .child(
+                div()
+                    .id(ElementId::Name(format!("todo-file-{}", group_ix).into()))
+                    .px_2()
+                    .py_1()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.blue)
+                    .cursor_pointer()
+                    .hover(|s| s.text_color(theme.lavender))
+                    .child(path)
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.open_file(&path_open, cx);
+                    })),
+            )
+            .children(items.into_iter().enumerate().map(|(ix, item)| {
+                let id = format!("{}-{}", group_ix, ix);
+                render_item(id, item, has_permalinks, &theme, cx)
+            }))
+    }
+}
+
+fn render_item(
+    id: String,
+    item: TodoItem,
+    has_permalinks: bool,
+    theme: &Theme,
+    cx: &mut Context<TodosView>,
+) -> impl IntoElement {
+    let marker_color = if item.marker == "FIXME" {
+        theme.red
+    } else {
+        theme.yellow
+    };
+    let can_link = has_permalinks && item.commit.is_some();
+    let item_link = item.clone();
+
+    div()
+        .flex()
+        .items_center()
+        .gap_2()
+        .px_2()
+        .py_1()
+        .rounded_md()
+        .hover(|s| s.bg(theme.surface0))
+        .child(
+            div()
+                .w_10()
+                .flex_shrink_0()
+                .text_xs()
+                .text_color(theme.overlay0)
+                .child(format!("L{}", item.line)),
+        )
+        .child(
+            div()
+                .px_1()
+                .rounded_sm()
+                .bg(marker_color)
+                .text_xs()
+                .text_color(theme.base)
+                .child(item.marker),
+        )
+        .child(
+            div()
+                .flex_1()
+                .truncate()
+                .text_sm()
+                .text_color(theme.text)
+                .child(item.text.clone()),
+        )
+        .child(match item.commit.clone() {
+            Some(commit) => div()
+                .id(ElementId::Name(format!("todo-commit-{}", id).into()))
+                .flex()
+                .gap_1()
+                .text_xs()
+                .text_color(theme.overlay2)
+                .cursor_pointer()
+                .hover(|s| s.text_color(theme.text))
+                .child(div().font_family("monospace").child(commit.short_sha.clone()))
+                .child(AuthorLink::with_author(
+                    ElementId::Name(format!("todo-author-{}", id).into()),
+                    &commit.author,
+                    &commit.email,
+                ))
+                .on_click(cx.listener(move |this, _event, _window, cx| {
+                    this.show_commit(&commit.sha, cx);
+                }))
+                .into_any_element(),
+            None => div()
+                .text_xs()
+                .text_color(theme.peach)
+                .child("uncommitted")
+                .into_any_element(),
+        })
+        .when(can_link, |this| {
+            this.child(
+                div()
+                    .id(ElementId::Name(format!("todo-link-{}", id).into()))
+                    .px_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .text_color(theme.overlay2)
+                    .cursor_pointer()
+                    .hover(|s| s.bg(theme.surface1).text_color(theme.text))
+                    .child("↗")
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.open_permalink(&item_link, cx);
+                    })),
+            )
+        })
+}
@@ -0,0 +1,277 @@
+#![allow(dead_code)]
+
+use crate::components::CopyDetailsButton;
+use crate::git::TrashEntry;
+use crate::state::{ErrorDetails, GitState};
+use crate::theme::{ActiveTheme, Theme};
+use gpui::prelude::*;
+use gpui::*;
+
+/// Emitted when the trash view should be closed
+#[derive(Clone, Debug)]
+pub struct TrashDismissed;
+
+impl EventEmitter<TrashDismissed> for TrashView {}
+
+/// Lists changes backed up before a discard so they can be written back
+pub struct TrashView {
+    git_state: Entity<GitState>,
+    entries: Vec<TrashEntry>,
+    /// Entry awaiting a second click to confirm deletion
+    pending_delete: Option<String>,
+    error: Option<ErrorDetails>,
+}
+
+impl TrashView {
+    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+        cx.observe(&git_state, |this, _git_state, cx| {
+            this.reload(cx);
+        })
+        .detach();
+
+        let mut view = Self {
+            git_state,
+            entries: Vec::new(),
+            pending_delete: None,
+            error: None,
+        };
+        view.reload(cx);
+        view
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        match self.git_state.read(cx).trash_entries() {
+            Ok(entries) => self.entries = entries,
+            Err(e) => {
+                log::error!("Failed to read trash: {}", e);
+                self.entries.clear();
+                self.error = Some(self.git_state.read(cx).error_details("Read trash", &e));
+            }
+        }
+        cx.notify();
+    }
+
+    fn restore(&mut self, entry: TrashEntry, cx: &mut Context<Self>) {
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.restore_trash_entry(&entry, cx));
+        self.set_result(result, "restore discarded changes", cx);
+    }
+
+    fn delete(&mut self, entry: TrashEntry, cx: &mut Context<Self>) {
+        if self.pending_delete.as_ref() != Some(&entry.id) {
+            self.pending_delete = Some(entry.id);
+            cx.notify();
+            return;
+        }
+
+        self.pending_delete = None;
+        let result = self
+            .git_state
+            .update(cx, |state, cx| state.delete_trash_entry(&entry, cx));
+        self.set_result(result, "delete discarded changes", cx);
+    }
+
+    fn set_result(&mut self, result: anyhow::Result<()>, action: &str, cx: &mut Context<Self>) {
+        match result {
+            Ok(()) => self.error = None,
+            Err(e) => {
+                log::error!("Failed to {}: {}", action, e);
+                self.error = Some(self.git_state.read(cx).error_details(action, &e));
+            }
+        }
+        cx.notify();
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(TrashDismissed);
+    }
+}
+
+impl Render for TrashView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Restore Discarded"),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .child("Restoring overwrites the files in the working tree"),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("close-trash")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error.message)
+                        .child(CopyDetailsButton::new("trash-error-details", error.report)),
+                )
+            })
+            // Entries
+            .child(
+                div()
+                    .id("trash-list")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .when(self.entries.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .py_8()
+                                .text_sm()
+                                .text_center()
+                                .text_color(theme.overlay0)
+                                .child("Nothing has been discarded"),
+                        )
+                    })
+                    .children(
+                        self.entries
+                            .iter()
+                            .map(|entry| self.render_entry(entry, &theme, cx)),
+                    ),
+            )
+    }
+}
+
+impl TrashView {
+    fn render_entry(
+        &self,
+        entry: &TrashEntry,
+        theme: &Theme,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let is_pending_delete = self.pending_delete.as_ref() == Some(&entry.id);
+        let entry_restore = entry.clone();
+        let entry_delete = entry.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .p_3()
+            .rounded_md()
+            .bg(theme.mantle)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .truncate()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child(entry.label.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_shrink_0()
+                            .text_xs()
+                            .text_color(theme.overlay0)
+                            .child(
+                                entry
+                                    .created_at
+                                    .with_timezone(&chrono::Local)
+                                    .format("%Y-%m-%d %H:%M")
+                                    .to_string(),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id(ElementId::Name(format!("trash-restore-{}", entry.id).into()))
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .bg(theme.blue)
+                            .text_xs()
+                            .text_color(theme.base)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.lavender))
+                            .child("Restore")
+                            .on_click(cx.listener(move |this, _event, _window, cx| {
+                                this.restore(entry_restore.clone(), cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id(ElementId::Name(format!("trash-delete-{}", entry.id).into()))
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_xs()
+                            .when(is_pending_delete, |this| {
+                                this.bg(theme.red).text_color(theme.base)
+                            })
+                            .when(!is_pending_delete, |this| {
+                                this.bg(theme.surface0).text_color(theme.red)
+                            })
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.maroon).text_color(theme.base))
+                            .child(if is_pending_delete { "Confirm" } else { "Delete" })
+                            .on_click(cx.listener(move |this, _event, _window, cx| {
+                                this.delete(entry_delete.clone(), cx);
+                            })),
+                    ),
+            )
+            .children(entry.files.iter().map(|path| {
+                div()
+                    .font_family("monospace")
+                    .text_xs()
+                    .text_color(theme.overlay2)
+                    .child(path.clone())
+            }))
+    }
+}
@@ -1,14 +1,20 @@
-use crate::state::RecentProjects;
+use crate::actions::CloneRepository;
+use crate::state::{ProjectStatus, RecentProjects};
+use crate::theme::ActiveTheme;
 use gpui::prelude::*;
 use gpui::*;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+/// Receives the path of a recent project
+type ProjectHandler = Arc<dyn Fn(&PathBuf, &mut Window, &mut App) + Send + Sync + 'static>;
+
 #[derive(IntoElement)]
 pub struct WelcomeView {
     recent_projects: Entity<RecentProjects>,
-    on_open_repository: Option<Arc<dyn Fn(&PathBuf, &mut Window, &mut App) + Send + Sync + 'static>>,
+    on_open_repository: Option<ProjectHandler>,
     on_open_dialog: Option<Arc<dyn Fn(&(), &mut Window, &mut App) + Send + Sync + 'static>>,
+    on_locate_project: Option<ProjectHandler>,
 }
 
 impl WelcomeView {
@@ -17,6 +23,7 @@ impl WelcomeView {
             recent_projects,
             on_open_repository: None,
             on_open_dialog: None,
+            on_locate_project: None,
         }
     }
 
@@ -36,6 +43,15 @@ impl WelcomeView {
         self
     }
 
+    /// Called with the old path of a recent project that is missing
+    pub fn on_locate_project(
+        mut self,
+        handler: impl Fn(&PathBuf, &mut Window, &mut App) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_locate_project = Some(Arc::new(handler));
+        self
+    }
+
     /// Check if a path is a valid git repository
     fn is_git_repository(path: &PathBuf) -> bool {
         // Check for .git directory
@@ -60,8 +76,15 @@ impl WelcomeView {
 
 impl RenderOnce for WelcomeView {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = *cx.theme();
         let recent = self.recent_projects.read(cx);
-        let projects: Vec<_> = recent.projects().to_vec();
+        let projects: Vec<_> = recent
+            .projects()
+            .iter()
+            .map(|project| (project.clone(), recent.status(&project.path).cloned()))
+            .collect();
+        let recent_projects = self.recent_projects.clone();
+        let on_locate = self.on_locate_project.clone();
         let on_open = self.on_open_repository.clone();
         let on_open_for_drop = on_open.clone();
         let on_open_dialog = self.on_open_dialog.clone();
@@ -108,13 +131,13 @@ impl RenderOnce for WelcomeView {
                         div()
                             .text_3xl()
                             .font_weight(FontWeight::BOLD)
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(theme.text)
                             .child("Awabancha"),
                     )
                     .child(
                         div()
                             .text_sm()
-                            .text_color(rgb(0x9399b2))
+                            .text_color(theme.overlay2)
                             .child("A fast Git GUI client"),
                     ),
             )
@@ -122,28 +145,50 @@ impl RenderOnce for WelcomeView {
             .child(
                 div()
                     .text_xs()
-                    .text_color(rgb(0x6c7086))
+                    .text_color(theme.overlay0)
                     .child("Drop a git repository folder here"),
             )
-            // Open Repository Button
+            // Open and Clone buttons
             .child(
                 div()
-                    .id("open-repo-button")
-                    .px_6()
-                    .py_3()
-                    .rounded_lg()
-                    .bg(rgb(0x89b4fa))
-                    .text_color(rgb(0x1e1e2e))
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .cursor_pointer()
-                    .hover(|s| s.bg(rgb(0xb4befe)))
-                    .active(|s| s.bg(rgb(0x7287fd)))
-                    .child("Open Repository")
-                    .on_click(move |_event, window, cx| {
-                        if let Some(ref handler) = on_open_dialog {
-                            handler(&(), window, cx);
-                        }
-                    }),
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        div()
+                            .id("open-repo-button")
+                            .px_6()
+                            .py_3()
+                            .rounded_lg()
+                            .bg(theme.blue)
+                            .text_color(theme.base)
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.lavender))
+                            .active(|s| s.bg(theme.blue_active))
+                            .child("Open Repository")
+                            .on_click(move |_event, window, cx| {
+                                if let Some(ref handler) = on_open_dialog {
+                                    handler(&(), window, cx);
+                                }
+                            }),
+                    )
+                    .child(
+                        div()
+                            .id("clone-repo-button")
+                            .px_6()
+                            .py_3()
+                            .rounded_lg()
+                            .bg(theme.surface0)
+                            .text_color(theme.text)
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface1))
+                            .child("Clone Repository")
+                            .on_click(|_event, window, cx| {
+                                window.dispatch_action(Box::new(CloneRepository), cx);
+                            }),
+                    ),
             )
             // Recent Projects
             .child(
@@ -156,18 +201,95 @@ impl RenderOnce for WelcomeView {
                         this.child(
                             div()
                                 .text_sm()
-                                .text_color(rgb(0x9399b2))
+                                .text_color(theme.overlay2)
                                 .mb_2()
                                 .child("Recent Projects"),
                         )
                     })
-                    .children(projects.into_iter().map(|project| {
+                    .children(projects.into_iter().map(|(project, status)| {
                         let path = project.path.clone();
+                        let missing = status == Some(ProjectStatus::Missing);
                         let on_open_clone = on_open.clone();
+                        let on_locate_clone = on_locate.clone();
+                        let id = |kind: &str| {
+                            ElementId::Name(
+                                format!("recent-{}{}", kind, project.path.display()).into(),
+                            )
+                        };
+
+                        let pin_recent = recent_projects.clone();
+                        let pin_path = path.clone();
+                        let pin_button = div()
+                            .id(id("pin-"))
+                            .flex_shrink_0()
+                            .text_sm()
+                            .text_color(if project.pinned {
+                                theme.yellow
+                            } else {
+                                theme.overlay0
+                            })
+                            .cursor_pointer()
+                            .hover(|s| s.text_color(theme.yellow))
+                            .child(if project.pinned { "★" } else { "☆" })
+                            .on_click(move |_event, _window, cx| {
+                                cx.stop_propagation();
+                                pin_recent.update(cx, |recent, cx| {
+                                    recent.toggle_pinned(&pin_path, cx);
+                                });
+                            });
+
+                        let remove_recent = recent_projects.clone();
+                        let remove_path = path.clone();
+                        let remove_button = div()
+                            .id(id("remove-"))
+                            .flex_shrink_0()
+                            .px_1()
+                            .rounded_sm()
+                            .text_sm()
+                            .text_color(theme.overlay0)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.red))
+                            .child("×")
+                            .on_click(move |_event, _window, cx| {
+                                cx.stop_propagation();
+                                remove_recent.update(cx, |recent, cx| {
+                                    recent.remove_project(&remove_path, cx);
+                                });
+                            });
+
+                        // Branch and dirty state, once read in the background
+                        let details = match &status {
+                            Some(ProjectStatus::Available(summary)) => Some(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
+                                    .text_xs()
+                                    .child(
+                                        div().text_color(theme.blue).child(
+                                            summary
+                                                .branch
+                                                .clone()
+                                                .unwrap_or_else(|| "detached HEAD".to_string()),
+                                        ),
+                                    )
+                                    .when(summary.dirty, |this| {
+                                        this.child(
+                                            div().text_color(theme.yellow).child("● modified"),
+                                        )
+                                    }),
+                            ),
+                            Some(ProjectStatus::Missing) => Some(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.red)
+                                    .child("Not found at this path"),
+                            ),
+                            None => None,
+                        };
+
                         div()
-                            .id(ElementId::Name(
-                                format!("recent-{}", project.path.display()).into(),
-                            ))
+                            .id(id(""))
                             .flex()
                             .items_center()
                             .gap_3()
@@ -175,34 +297,58 @@ impl RenderOnce for WelcomeView {
                             .py_2()
                             .rounded_md()
                             .cursor_pointer()
-                            .bg(rgb(0x313244))
-                            .hover(|s| s.bg(rgb(0x45475a)))
+                            .bg(theme.surface0)
+                            .hover(|s| s.bg(theme.surface1))
                             .on_click(move |_event, window, cx| {
-                                if let Some(ref handler) = on_open_clone {
+                                // A missing project can only be located
+                                let handler = if missing {
+                                    &on_locate_clone
+                                } else {
+                                    &on_open_clone
+                                };
+                                if let Some(handler) = handler {
                                     handler(&path, window, cx);
                                 }
                             })
+                            .child(pin_button)
                             .child(
                                 div()
+                                    .flex_1()
                                     .flex()
                                     .flex_col()
                                     .overflow_hidden()
+                                    .when(missing, |this| this.opacity(0.5))
                                     .child(
                                         div()
                                             .text_sm()
                                             .font_weight(FontWeight::MEDIUM)
-                                            .text_color(rgb(0xcdd6f4))
+                                            .text_color(theme.text)
                                             .text_ellipsis()
                                             .child(project.name.clone()),
                                     )
                                     .child(
                                         div()
                                             .text_xs()
-                                            .text_color(rgb(0x6c7086))
+                                            .text_color(theme.overlay0)
                                             .text_ellipsis()
                                             .child(project.path.display().to_string()),
-                                    ),
+                                    )
+                                    .children(details),
                             )
+                            .when(missing, |this| {
+                                this.child(
+                                    div()
+                                        .flex_shrink_0()
+                                        .px_2()
+                                        .py_1()
+                                        .rounded_sm()
+                                        .bg(theme.surface1)
+                                        .text_xs()
+                                        .text_color(theme.text)
+                                        .child("Locate..."),
+                                )
+                            })
+                            .child(remove_button)
                     })),
             )
     }
@@ -0,0 +1,463 @@
+#![allow(dead_code)]
+
+use crate::components::{CopyDetailsButton, TextInputChanged, TextInputView};
+use crate::git::WorktreeInfo;
+use crate::state::{ErrorDetails, GitState};
+use crate::theme::ActiveTheme;
+use gpui::prelude::*;
+use gpui::*;
+use std::path::PathBuf;
+
+/// Events emitted by the worktrees view
+#[derive(Clone, Debug)]
+pub enum WorktreesEvent {
+    /// Switch the window to the worktree at this path
+    Open(PathBuf),
+    Dismissed,
+}
+
+impl EventEmitter<WorktreesEvent> for WorktreesView {}
+
+/// Lists the repository's worktrees and lets the user add, remove or switch
+/// to one
+pub struct WorktreesView {
+    git_state: Entity<GitState>,
+    path_input: Entity<TextInputView>,
+    branch_input: Entity<TextInputView>,
+    /// Create the branch from HEAD instead of checking out an existing one
+    create_branch: bool,
+    /// Last path filled in automatically from the branch name
+    suggested_path: String,
+    /// Worktree whose removal failed because of local changes
+    pending_force: Option<String>,
+    error: Option<ErrorDetails>,
+}
+
+impl WorktreesView {
+    pub fn new(git_state: Entity<GitState>, cx: &mut Context<Self>) -> Self {
+        let path_input = cx.new(|cx| {
+            TextInputView::new(cx).with_placeholder("Path for the new worktree")
+        });
+        let branch_input = cx.new(|cx| TextInputView::new(cx).with_placeholder("Branch"));
+
+        // Suggest a sibling directory named after the branch as it is typed
+        cx.subscribe(&branch_input, |this, _input, _event: &TextInputChanged, cx| {
+            this.suggest_path(cx);
+            cx.notify();
+        })
+        .detach();
+        cx.subscribe(&path_input, |_this, _input, _event: &TextInputChanged, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        cx.observe(&git_state, |_this, _git_state, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            git_state,
+            path_input,
+            branch_input,
+            create_branch: true,
+            suggested_path: String::new(),
+            pending_force: None,
+            error: None,
+        }
+    }
+
+    /// Fill the path with `<repo>-<branch>` next to the main worktree unless
+    /// the user has typed a different path
+    fn suggest_path(&mut self, cx: &mut Context<Self>) {
+        let current = self.path_input.read(cx).content().to_string();
+        if !current.is_empty() && current != self.suggested_path {
+            return;
+        }
+
+        let Some(main) = self
+            .git_state
+            .read(cx)
+            .worktrees
+            .iter()
+            .find(|w| w.is_main)
+            .map(|w| w.path.clone())
+        else {
+            return;
+        };
+        let (Some(parent), Some(repo_name)) = (main.parent(), main.file_name()) else {
+            return;
+        };
+
+        let branch = self.branch_input.read(cx).content().trim().replace('/', "-");
+        self.suggested_path = if branch.is_empty() {
+            String::new()
+        } else {
+            parent
+                .join(format!("{}-{}", repo_name.to_string_lossy(), branch))
+                .to_string_lossy()
+                .to_string()
+        };
+
+        let suggestion = self.suggested_path.clone();
+        self.path_input.update(cx, |input, cx| {
+            input.set_content(suggestion, cx);
+        });
+    }
+
+    fn add_worktree(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        let path = self.path_input.read(cx).content().trim().to_string();
+        let branch = self.branch_input.read(cx).content().trim().to_string();
+        if path.is_empty() || branch.is_empty() {
+            return;
+        }
+
+        let create_branch = self.create_branch;
+        let result = self.git_state.update(cx, |state, cx| {
+            state.add_worktree(&PathBuf::from(&path), &branch, create_branch, cx)
+        });
+
+        match result {
+            Ok(()) => {
+                self.error = None;
+                self.path_input.update(cx, |input, cx| input.set_content("", cx));
+                self.branch_input.update(cx, |input, cx| input.set_content("", cx));
+            }
+            Err(e) => {
+                log::error!("Failed to add worktree: {}", e);
+                self.error = Some(self.git_state.read(cx).error_details("Add worktree", &e));
+            }
+        }
+        cx.notify();
+    }
+
+    fn remove_worktree(&mut self, name: String, cx: &mut Context<Self>) {
+        let force = self.pending_force.as_deref() == Some(name.as_str());
+        let result = self.git_state.update(cx, |state, cx| {
+            state.remove_worktree(&name, force, cx)
+        });
+
+        match result {
+            Ok(()) => {
+                self.error = None;
+                self.pending_force = None;
+            }
+            Err(e) => {
+                log::error!("Failed to remove worktree {}: {}", name, e);
+                let operation = format!("Remove worktree {}", name);
+                self.error = Some(self.git_state.read(cx).error_details(&operation, &e));
+                // A second click discards the worktree's local changes
+                self.pending_force = (!force).then_some(name);
+            }
+        }
+        cx.notify();
+    }
+
+    fn toggle_create_branch(&mut self, cx: &mut Context<Self>) {
+        self.create_branch = !self.create_branch;
+        cx.notify();
+    }
+
+    fn dismiss(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(WorktreesEvent::Dismissed);
+    }
+}
+
+impl Render for WorktreesView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let worktrees = self.git_state.read(cx).worktrees.clone();
+        let can_add = !self.path_input.read(cx).content().trim().is_empty()
+            && !self.branch_input.read(cx).content().trim().is_empty();
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(theme.base)
+            .p_4()
+            .gap_3()
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Worktrees"),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.overlay2)
+                                    .child("Check out several branches side by side"),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("close-worktrees")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(theme.overlay2)
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.surface0).text_color(theme.text))
+                            .child("×")
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.dismiss(window, cx);
+                            })),
+                    ),
+            )
+            // Worktree list
+            .child(
+                div()
+                    .id("worktree-list")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .rounded_md()
+                    .bg(theme.mantle)
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .children(
+                        worktrees
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, worktree)| self.render_worktree(ix, worktree, cx)),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(theme.red_bg)
+                        .text_sm()
+                        .text_color(theme.red)
+                        .child(error.message)
+                        .child(CopyDetailsButton::new("worktree-error-details", error.report)),
+                )
+            })
+            // Add form
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.overlay2)
+                            .child("Add Worktree"),
+                    )
+                    .child(self.branch_input.clone())
+                    .child(self.path_input.clone())
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .justify_between()
+                            .child(
+                                div()
+                                    .id("worktree-create-branch")
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
+                                    .cursor_pointer()
+                                    .child(
+                                        div()
+                                            .w_4()
+                                            .h_4()
+                                            .flex()
+                                            .items_center()
+                                            .justify_center()
+                                            .rounded_sm()
+                                            .border_1()
+                                            .border_color(theme.overlay0)
+                                            .when(self.create_branch, |this| {
+                                                this.bg(theme.blue)
+                                                    .border_color(theme.blue)
+                                                    .text_xs()
+                                                    .text_color(theme.base)
+                                                    .child("✓")
+                                            }),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_sm()
+                                            .text_color(theme.text)
+                                            .child("Create new branch from HEAD"),
+                                    )
+                                    .on_click(cx.listener(|this, _event, _window, cx| {
+                                        this.toggle_create_branch(cx);
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .id("worktree-add-btn")
+                                    .px_3()
+                                    .py_1()
+                                    .rounded_md()
+                                    .bg(if can_add {
+                                        theme.blue
+                                    } else {
+                                        theme.surface1
+                                    })
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(theme.base)
+                                    .when(can_add, |this| {
+                                        this.cursor_pointer()
+                                            .hover(|s| s.bg(theme.lavender))
+                                            .on_click(cx.listener(|this, _event, window, cx| {
+                                                this.add_worktree(window, cx);
+                                            }))
+                                    })
+                                    .child("Add"),
+                            ),
+                    ),
+            )
+    }
+}
+
+impl WorktreesView {
+    fn render_worktree(
+        &self,
+        ix: usize,
+        worktree: &WorktreeInfo,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = *cx.theme();
+        let path = worktree.path.clone();
+        let can_open = !worktree.is_current && !worktree.is_prunable;
+        let removable_name = worktree
+            .name
+            .clone()
+            .filter(|_| !worktree.is_main && !worktree.is_current && !worktree.is_locked);
+        let is_pending_force = removable_name.is_some()
+            && self.pending_force.as_ref() == removable_name.as_ref();
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .when(worktree.is_current, |this| this.bg(theme.surface0))
+            .child(
+                div()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .overflow_hidden()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.blue)
+                                    .child(
+                                        worktree
+                                            .branch
+                                            .clone()
+                                            .unwrap_or_else(|| "(detached)".to_string()),
+                                    ),
+                            )
+                            .when(worktree.is_main, |this| {
+                                this.child(badge("main", theme.overlay2))
+                            })
+                            .when(worktree.is_current, |this| {
+                                this.child(badge("current", theme.green))
+                            })
+                            .when(worktree.is_locked, |this| {
+                                this.child(badge("locked", theme.yellow))
+                            })
+                            .when(worktree.is_prunable, |this| {
+                                this.child(badge("missing", theme.red))
+                            }),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.overlay0)
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .child(worktree.path.to_string_lossy().to_string()),
+                    ),
+            )
+            .when(can_open, |this| {
+                this.child(
+                    div()
+                        .id(ElementId::Name(format!("worktree-open-{}", ix).into()))
+                        .px_3()
+                        .py_1()
+                        .rounded_md()
+                        .bg(theme.surface0)
+                        .text_sm()
+                        .text_color(theme.text)
+                        .cursor_pointer()
+                        .hover(|s| s.bg(theme.surface1))
+                        .child("Open")
+                        .on_click(cx.listener(move |_this, _event, _window, cx| {
+                            cx.emit(WorktreesEvent::Open(path.clone()));
+                        })),
+                )
+            })
+            .when_some(removable_name, |this, name| {
+                this.child(
+                    div()
+                        .id(ElementId::Name(format!("worktree-remove-{}", ix).into()))
+                        .px_3()
+                        .py_1()
+                        .rounded_md()
+                        .when(is_pending_force, |this| {
+                            this.bg(theme.red).text_color(theme.base)
+                        })
+                        .when(!is_pending_force, |this| {
+                            this.bg(theme.surface0).text_color(theme.red)
+                        })
+                        .text_sm()
+                        .cursor_pointer()
+                        .hover(|s| s.bg(theme.maroon).text_color(theme.base))
+                        .child(if is_pending_force {
+                            "Force Remove"
+                        } else {
+                            "Remove"
+                        })
+                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                            this.remove_worktree(name.clone(), cx);
+                        })),
+                )
+            })
+    }
+}
+
+fn badge(label: &'static str, color: Rgba) -> impl IntoElement {
+    div()
+        .px_1()
+        .rounded_sm()
+        .border_1()
+        .border_color(color)
+        .text_xs()
+        .text_color(color)
+        .child(label)
+}